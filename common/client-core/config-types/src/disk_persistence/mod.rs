@@ -18,6 +18,7 @@ pub const DEFAULT_PUBLIC_IDENTITY_KEY_FILENAME: &str = "public_identity.pem";
 pub const DEFAULT_PRIVATE_ENCRYPTION_KEY_FILENAME: &str = "private_encryption.pem";
 pub const DEFAULT_PUBLIC_ENCRYPTION_KEY_FILENAME: &str = "public_encryption.pem";
 pub const DEFAULT_ACK_KEY_FILENAME: &str = "ack_key.pem";
+pub const DEFAULT_STORAGE_CIPHER_FILENAME: &str = "storage_cipher.json";
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -129,6 +130,14 @@ impl ClientKeysPaths {
     pub fn ack_key(&self) -> &Path {
         &self.ack_key_file
     }
+
+    /// Path to the file containing the parameters of the cipher used for encrypting the client
+    /// storage at rest. It is kept alongside the private identity key and only exists
+    /// if the storage encryption has been enabled.
+    pub fn storage_cipher_file(&self) -> PathBuf {
+        self.private_identity_key_file
+            .with_file_name(DEFAULT_STORAGE_CIPHER_FILENAME)
+    }
 }
 
 fn file_exists(path: &Path) -> Option<PathBuf> {
@@ -3,7 +3,7 @@
 
 use nym_config::defaults::NymNetworkDetails;
 use nym_sphinx_addressing::Recipient;
-use nym_sphinx_params::{PacketSize, PacketType};
+use nym_sphinx_params::{PacketSize, PacketType, PayloadCompression};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use url::Url;
@@ -61,6 +61,11 @@ const DEFAULT_MAXIMUM_REPLY_SURB_AGE: Duration = Duration::from_secs(12 * 60 * 6
 // 24 hours
 const DEFAULT_MAXIMUM_REPLY_KEY_AGE: Duration = Duration::from_secs(24 * 60 * 60);
 
+// 10min
+const DEFAULT_INCOMPLETE_SET_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+// 128MiB
+const DEFAULT_RECONSTRUCTION_MEMORY_BUDGET: usize = 128 * 1024 * 1024;
+
 use crate::error::InvalidTrafficModeFailure;
 pub use nym_country_group::CountryGroup;
 
@@ -391,6 +396,12 @@ pub struct Traffic {
     pub secondary_packet_size: Option<PacketSize>,
 
     pub packet_type: PacketType,
+
+    /// Specifies the optional compression algorithm applied to the sent messages before they get
+    /// split into sphinx packets. Each message indicates whether it has been compressed,
+    /// so that the recipient knows whether it has to decompress it.
+    /// Note that the recipients running older software would not be able to recover compressed messages.
+    pub payload_compression: Option<PayloadCompression>,
 }
 
 impl Traffic {
@@ -415,6 +426,7 @@ impl Default for Traffic {
             primary_packet_size: PacketSize::RegularPacket,
             secondary_packet_size: None,
             packet_type: PacketType::Mix,
+            payload_compression: None,
         }
     }
 }
@@ -529,6 +541,27 @@ pub struct Topology {
     /// Specifies a minimum performance of a gateway that is used on route construction.
     /// This setting is only applicable when `NymApi` topology is used.
     pub minimum_gateway_performance: u8,
+
+    /// Specifies how nodes are chosen from the topology during route construction.
+    /// When `performance_weighted` is used, `minimum_mixnode_performance` and `minimum_gateway_performance`
+    /// are also enforced regardless of the topology structure.
+    pub route_selection: RouteSelection,
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteSelection {
+    /// Every node has the same probability of being chosen.
+    #[default]
+    Uniform,
+
+    /// Nodes are chosen with probability proportional to their total stake.
+    /// Requires the topology to include stake information, nodes with unknown stake are never chosen.
+    StakeWeighted,
+
+    /// Nodes are chosen with probability proportional to their performance.
+    /// Requires the topology to include performance information, nodes with unknown performance are never chosen.
+    PerformanceWeighted,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -565,6 +598,7 @@ impl Default for Topology {
             topology_structure: TopologyStructure::default(),
             minimum_mixnode_performance: DEFAULT_MIN_MIXNODE_PERFORMANCE,
             minimum_gateway_performance: DEFAULT_MIN_GATEWAY_PERFORMANCE,
+            route_selection: RouteSelection::default(),
         }
     }
 }
@@ -631,6 +665,42 @@ impl Default for ReplySurbs {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageReconstruction {
+    /// Defines the amount of time after which a partially received message that has not received
+    /// any new fragments is assumed to be lost and is dropped.
+    #[serde(with = "humantime_serde")]
+    pub incomplete_set_timeout: Duration,
+
+    /// Defines the maximum amount of data (in bytes) buffered across all partially received messages.
+    pub memory_budget: usize,
+
+    /// Specifies which partially received messages are dropped first once the `memory_budget` is exceeded.
+    pub eviction_policy: EvictionPolicy,
+}
+
+impl Default for MessageReconstruction {
+    fn default() -> Self {
+        MessageReconstruction {
+            incomplete_set_timeout: DEFAULT_INCOMPLETE_SET_TIMEOUT,
+            memory_budget: DEFAULT_RECONSTRUCTION_MEMORY_BUDGET,
+            eviction_policy: EvictionPolicy::default(),
+        }
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Drop the message that has not received any fragments for the longest time.
+    #[default]
+    OldestFirst,
+
+    /// Drop the message that currently holds the most data.
+    LargestFirst,
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DebugConfig {
@@ -651,6 +721,9 @@ pub struct DebugConfig {
 
     /// Defines all configuration options related to reply SURBs.
     pub reply_surbs: ReplySurbs,
+
+    /// Defines all configuration options related to reconstructing received messages.
+    pub message_reconstruction: MessageReconstruction,
 }
 
 impl DebugConfig {
@@ -672,6 +745,7 @@ impl Default for DebugConfig {
             acknowledgements: Default::default(),
             topology: Default::default(),
             reply_surbs: Default::default(),
+            message_reconstruction: Default::default(),
         }
     }
 }
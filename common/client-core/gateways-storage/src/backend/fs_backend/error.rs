@@ -42,4 +42,16 @@ pub enum StorageError {
 
     #[error("gateway {gateway_id} does not exist in the storage")]
     GatewayDoesNotExist { gateway_id: String },
+
+    #[error("the shared keys of gateway {gateway_id} are encrypted, but no storage cipher has been provided")]
+    MissingStorageCipher { gateway_id: String },
+
+    #[error("failed to encrypt or decrypt the gateway shared keys: {source}")]
+    CipherFailure {
+        #[from]
+        source: nym_store_cipher::Error,
+    },
+
+    #[error("the decrypted shared keys of gateway {gateway_id} are not valid utf8")]
+    MalformedDecryptedSharedKeys { gateway_id: String },
 }
@@ -169,6 +169,32 @@ impl StorageManager {
         Ok(())
     }
 
+    pub(crate) async fn get_all_remote_gateway_details(
+        &self,
+    ) -> Result<Vec<RawRemoteGatewayDetails>, sqlx::Error> {
+        sqlx::query_as!(
+            RawRemoteGatewayDetails,
+            "SELECT * FROM remote_gateway_details"
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    pub(crate) async fn update_remote_gateway_shared_keys(
+        &self,
+        gateway_id: &str,
+        derived_aes128_ctr_blake3_hmac_keys_bs58: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE remote_gateway_details SET derived_aes128_ctr_blake3_hmac_keys_bs58 = ? WHERE gateway_id_bs58 = ?",
+            derived_aes128_ctr_blake3_hmac_keys_bs58,
+            gateway_id
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
     pub(crate) async fn remove_remote_gateway_details(
         &self,
         gateway_id: &str,
@@ -3,12 +3,15 @@
 
 use crate::{
     ActiveGateway, BadGateway, GatewayDetails, GatewayRegistration, GatewayType,
-    GatewaysDetailsStore, StorageError,
+    GatewaysDetailsStore, RawRemoteGatewayDetails, StorageError,
 };
 use async_trait::async_trait;
 use manager::StorageManager;
 use nym_crypto::asymmetric::identity::PublicKey;
+use nym_store_cipher::{is_encrypted_blob, StoreCipher};
 use std::path::Path;
+use std::sync::Arc;
+use zeroize::{Zeroize, Zeroizing};
 
 pub mod error;
 mod manager;
@@ -16,14 +19,99 @@ mod models;
 
 pub struct OnDiskGatewaysDetails {
     manager: StorageManager,
+
+    /// Optional cipher used for keeping the gateway shared keys encrypted at rest.
+    cipher: Option<Arc<StoreCipher>>,
 }
 
 impl OnDiskGatewaysDetails {
     pub async fn init<P: AsRef<Path>>(database_path: P) -> Result<Self, StorageError> {
         Ok(OnDiskGatewaysDetails {
             manager: StorageManager::init(database_path).await?,
+            cipher: None,
         })
     }
+
+    /// Makes the storage encrypt all gateway shared keys it persists from now on.
+    /// Keys stored before the cipher has been provided can still be read.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<StoreCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Encrypts all gateway shared keys that have been stored in plaintext,
+    /// for example before the cipher has been provided.
+    /// Returns the number of entries that got encrypted.
+    pub async fn encrypt_plaintext_shared_keys(&self) -> Result<usize, StorageError> {
+        if self.cipher.is_none() {
+            return Ok(0);
+        }
+
+        let mut encrypted = 0;
+        for mut raw_details in self.manager.get_all_remote_gateway_details().await? {
+            let already_encrypted =
+                bs58::decode(&raw_details.derived_aes128_ctr_blake3_hmac_keys_bs58)
+                    .into_vec()
+                    .map(|decoded| is_encrypted_blob(&decoded))
+                    .unwrap_or_default();
+            if already_encrypted {
+                continue;
+            }
+
+            self.seal_shared_keys(&mut raw_details)?;
+            self.manager
+                .update_remote_gateway_shared_keys(
+                    &raw_details.gateway_id_bs58,
+                    &raw_details.derived_aes128_ctr_blake3_hmac_keys_bs58,
+                )
+                .await?;
+            encrypted += 1;
+        }
+
+        Ok(encrypted)
+    }
+
+    fn seal_shared_keys(&self, raw: &mut RawRemoteGatewayDetails) -> Result<(), StorageError> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(());
+        };
+
+        let blob =
+            cipher.encrypt_to_blob(raw.derived_aes128_ctr_blake3_hmac_keys_bs58.as_bytes())?;
+        let mut plaintext = std::mem::replace(
+            &mut raw.derived_aes128_ctr_blake3_hmac_keys_bs58,
+            bs58::encode(blob).into_string(),
+        );
+        plaintext.zeroize();
+        Ok(())
+    }
+
+    fn unseal_shared_keys(&self, raw: &mut RawRemoteGatewayDetails) -> Result<(), StorageError> {
+        // if the value doesn't decode, leave it to the conversion to report malformed keys
+        let Ok(decoded) = bs58::decode(&raw.derived_aes128_ctr_blake3_hmac_keys_bs58).into_vec()
+        else {
+            return Ok(());
+        };
+        if !is_encrypted_blob(&decoded) {
+            return Ok(());
+        }
+
+        let Some(cipher) = &self.cipher else {
+            return Err(StorageError::MissingStorageCipher {
+                gateway_id: raw.gateway_id_bs58.clone(),
+            });
+        };
+
+        let plaintext = Zeroizing::new(cipher.decrypt_blob(&decoded)?);
+        let Ok(keys_bs58) = String::from_utf8(plaintext.to_vec()) else {
+            return Err(StorageError::MalformedDecryptedSharedKeys {
+                gateway_id: raw.gateway_id_bs58.clone(),
+            });
+        };
+        raw.derived_aes128_ctr_blake3_hmac_keys_bs58 = keys_bs58;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -91,7 +179,8 @@ impl GatewaysDetailsStore for OnDiskGatewaysDetails {
 
         let details = match typ {
             GatewayType::Remote => {
-                let raw_details = self.manager.get_remote_gateway_details(gateway_id).await?;
+                let mut raw_details = self.manager.get_remote_gateway_details(gateway_id).await?;
+                self.unseal_shared_keys(&mut raw_details)?;
                 GatewayDetails::Remote(raw_details.try_into()?)
             }
             GatewayType::Custom => {
@@ -117,7 +206,8 @@ impl GatewaysDetailsStore for OnDiskGatewaysDetails {
 
         match &details.details {
             GatewayDetails::Remote(remote_details) => {
-                let raw_details = remote_details.into();
+                let mut raw_details = remote_details.into();
+                self.seal_shared_keys(&mut raw_details)?;
                 self.manager
                     .set_remote_gateway_details(&raw_details)
                     .await?;
@@ -4,6 +4,7 @@
 use crate::cli_helpers::types::GatewayInfo;
 use crate::cli_helpers::{CliClient, CliClientConfig};
 use crate::client::base_client::non_wasm_helpers::setup_fs_gateways_storage;
+use crate::client::base_client::storage::encryption::load_storage_cipher_if_encrypted;
 use crate::{
     client::{
         base_client::storage::helpers::{get_all_registered_identities, set_active_gateway},
@@ -76,8 +77,12 @@ where
     let core = config.core_config();
     let paths = config.common_paths();
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
-    let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
+    let mut key_store = OnDiskKeys::new(paths.keys.clone());
+    let mut details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
+    if let Some(cipher) = load_storage_cipher_if_encrypted(&paths.keys)? {
+        key_store = key_store.with_cipher(cipher.clone());
+        details_store = details_store.with_cipher(cipher);
+    }
 
     // Attempt to use a user-provided gateway, if possible
     let user_chosen_gateway_id = common_args.gateway_id;
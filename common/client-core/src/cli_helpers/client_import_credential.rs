@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_helpers::{CliClient, CliClientConfig};
+use crate::client::base_client::storage::encryption::load_storage_cipher_if_encrypted;
 use std::fs;
 use std::path::PathBuf;
 
@@ -43,8 +44,11 @@ where
     let config = C::try_load_current_config(id).await?;
     let paths = config.common_paths();
 
-    let credentials_store =
+    let mut credentials_store =
         nym_credential_storage::initialise_persistent_storage(&paths.credentials_database).await;
+    if let Some(cipher) = load_storage_cipher_if_encrypted(&paths.keys)? {
+        credentials_store = credentials_store.with_cipher(cipher);
+    }
 
     let raw_credential = match common_args.credential_data {
         Some(data) => data,
@@ -6,11 +6,18 @@ use crate::error::ClientCoreError;
 use crate::{
     client::{
         base_client::{
-            non_wasm_helpers::setup_fs_gateways_storage, storage::helpers::set_active_gateway,
+            non_wasm_helpers::setup_fs_gateways_storage,
+            storage::{
+                encryption::{
+                    create_storage_cipher, encrypt_plaintext_keys, finalise_storage_encryption,
+                    prepare_storage_encryption, storage_is_encrypted, StoragePassphrase,
+                },
+                helpers::set_active_gateway,
+            },
         },
         key_manager::persistence::OnDiskKeys,
     },
-    init::types::{GatewaySelectionSpecification, GatewaySetup, InitResults},
+    init::types::{GatewaySelectionSpecification, GatewaySetup, InitResults, InitialisationResult},
 };
 use log::info;
 use nym_client_core_gateways_storage::GatewayDetails;
@@ -88,6 +95,16 @@ pub struct CommonClientInitArgs {
     /// Disable loop cover traffic and the Poisson rate limiter (for debugging only)
     #[cfg_attr(feature = "cli", clap(long, hide = true))]
     pub no_cover: bool,
+
+    /// Encrypt the client storage at rest using a passphrase.
+    /// If the client has already been initialised, its existing storage is going to be encrypted instead.
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub encrypt: bool,
+
+    /// Path to the file containing the storage passphrase.
+    /// If unspecified, it's going to be read from the environment or prompted for.
+    #[cfg_attr(feature = "cli", clap(long, requires = "encrypt"))]
+    pub storage_passphrase_file: Option<PathBuf>,
 }
 
 pub struct InitResultsWithConfig<T> {
@@ -110,6 +127,9 @@ where
     let id = &common_args.id;
 
     if C::default_config_path(id).exists() {
+        if common_args.encrypt {
+            return encrypt_existing_client::<C>(common_args).await;
+        }
         eprintln!("{} client \"{id}\" was already initialised before", C::NAME);
         return Err(ClientCoreError::AlreadyInitialised {
             client_id: id.to_string(),
@@ -147,8 +167,16 @@ where
             .join(",")
     );
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
-    let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
+    let mut key_store = OnDiskKeys::new(paths.keys.clone());
+    let mut details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
+
+    if common_args.encrypt {
+        let passphrase =
+            StoragePassphrase::obtain(common_args.storage_passphrase_file.as_deref(), true)?;
+        let cipher = create_storage_cipher(&paths.keys, &passphrase)?;
+        key_store = key_store.with_cipher(cipher.clone());
+        details_store = details_store.with_cipher(cipher);
+    }
 
     let mut rng = OsRng;
     crate::init::generate_new_client_keys(&mut rng, &key_store).await?;
@@ -215,3 +243,87 @@ where
         init_results,
     })
 }
+
+/// Encrypts the storage of an already initialised client: re-stores its private keys and
+/// encrypts the gateway shared keys alongside the credentials.
+/// The reply SURB database is going to be recreated, as it's going to be repopulated anyway.
+async fn encrypt_existing_client<C>(
+    common_args: &CommonClientInitArgs,
+) -> Result<InitResultsWithConfig<C::Config>, C::Error>
+where
+    C: InitialisableClient,
+{
+    let id = &common_args.id;
+    info!(
+        "encrypting storage of the existing {} client \"{id}\"",
+        C::NAME
+    );
+
+    C::try_upgrade_outdated_config(id).await?;
+    let config = C::try_load_current_config(id).await?;
+    let paths = config.common_paths();
+
+    if storage_is_encrypted(&paths.keys) {
+        return Err(ClientCoreError::StorageAlreadyEncrypted.into());
+    }
+
+    // the storage only gets marked as encrypted once everything else has been encrypted,
+    // so that an interrupted attempt could be resumed by running the command again
+    let passphrase =
+        StoragePassphrase::obtain(common_args.storage_passphrase_file.as_deref(), true)?;
+    let cipher = prepare_storage_encryption(&paths.keys, &passphrase)?;
+
+    encrypt_plaintext_keys(&paths.keys, cipher.clone()).await?;
+    let key_store = OnDiskKeys::new(paths.keys.clone()).with_cipher(cipher.clone());
+
+    let details_store = setup_fs_gateways_storage(&paths.gateway_registrations)
+        .await?
+        .with_cipher(cipher.clone());
+    let encrypted_gateways = details_store
+        .encrypt_plaintext_shared_keys()
+        .await
+        .map_err(|source| ClientCoreError::GatewaysDetailsStoreError {
+            source: Box::new(source),
+        })?;
+    info!("encrypted shared keys of {encrypted_gateways} gateway(s)");
+
+    let credential_store =
+        nym_credential_storage::initialise_persistent_storage(&paths.credentials_database)
+            .await
+            .with_cipher(cipher);
+    let encrypted_credentials =
+        credential_store
+            .encrypt_plaintext_entries()
+            .await
+            .map_err(|source| ClientCoreError::CredentialStoreError {
+                source: Box::new(source),
+            })?;
+    info!("encrypted {encrypted_credentials} credential entries");
+
+    if paths.reply_surb_database.exists() {
+        std::fs::remove_file(&paths.reply_surb_database).map_err(ClientCoreError::from)?;
+    }
+
+    finalise_storage_encryption(&paths.keys)?;
+
+    eprintln!("Encrypted the storage of {} client \"{id}\"", C::NAME);
+
+    let init_details = InitialisationResult::try_load(&key_store, &details_store).await?;
+    let address = init_details.client_address();
+
+    let GatewayDetails::Remote(gateway_details) = init_details.gateway_registration.details else {
+        return Err(ClientCoreError::UnexpectedPersistedCustomGatewayDetails)?;
+    };
+
+    let init_results = InitResults::new(
+        config.core_config(),
+        address,
+        &gateway_details,
+        init_details.gateway_registration.registration_timestamp,
+    );
+
+    Ok(InitResultsWithConfig {
+        config,
+        init_results,
+    })
+}
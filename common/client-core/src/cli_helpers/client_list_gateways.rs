@@ -4,6 +4,7 @@
 use super::types::GatewayInfo;
 use crate::cli_helpers::{CliClient, CliClientConfig};
 use crate::client::base_client::non_wasm_helpers::setup_fs_gateways_storage;
+use crate::client::base_client::storage::encryption::load_storage_cipher_if_encrypted;
 use crate::client::base_client::storage::helpers::{
     get_active_gateway_identity, get_gateway_registrations,
 };
@@ -43,7 +44,10 @@ where
     let config = C::try_load_current_config(id).await?;
     let paths = config.common_paths();
 
-    let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
+    let mut details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
+    if let Some(cipher) = load_storage_cipher_if_encrypted(&paths.keys)? {
+        details_store = details_store.with_cipher(cipher);
+    }
 
     let active_gateway = get_active_gateway_identity(&details_store).await?;
 
@@ -25,9 +25,10 @@ use crate::client::replies::reply_storage::{
 };
 use crate::client::topology_control::nym_api_provider::NymApiTopologyProvider;
 use crate::client::topology_control::{
-    nym_api_provider, TopologyAccessor, TopologyRefresher, TopologyRefresherConfig,
+    nym_api_provider, route_selection_strategy, TopologyAccessor, TopologyRefresher,
+    TopologyRefresherConfig,
 };
-use crate::config::{Config, DebugConfig};
+use crate::config::{Config, DebugConfig, MessageReconstruction};
 use crate::error::ClientCoreError;
 use crate::init::{
     setup_gateway,
@@ -345,6 +346,7 @@ where
         reply_controller_sender: ReplyControllerSender,
         shutdown: TaskClient,
         packet_statistics_control: PacketStatisticsReporter,
+        reconstruction_config: MessageReconstruction,
     ) {
         info!("Starting received messages buffer controller...");
         let controller: ReceivedMessagesBufferController<SphinxMessageReceiver> =
@@ -355,6 +357,7 @@ where
                 reply_key_storage,
                 reply_controller_sender,
                 packet_statistics_control,
+                reconstruction_config,
             );
         controller.start_with_shutdown(shutdown)
     }
@@ -513,7 +516,8 @@ where
         mut shutdown: TaskClient,
     ) -> Result<(), ClientCoreError> {
         let topology_refresher_config =
-            TopologyRefresherConfig::new(topology_config.topology_refresh_rate);
+            TopologyRefresherConfig::new(topology_config.topology_refresh_rate)
+                .with_route_selection_strategy(route_selection_strategy(&topology_config));
 
         let mut topology_refresher = TopologyRefresher::new(
             topology_refresher_config,
@@ -752,6 +756,7 @@ where
             reply_controller_sender.clone(),
             shutdown.fork("received_messages_buffer"),
             packet_stats_reporter.clone(),
+            self.config.debug.message_reconstruction,
         );
 
         // The message_sender is the transmitter for any component generating sphinx packets
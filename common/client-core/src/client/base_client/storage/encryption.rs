@@ -0,0 +1,392 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for the opt-in, passphrase-based, encryption at rest of the on-disk client storage.
+//!
+//! Once enabled, the parameters of the derived cipher (i.e. the kdf salt alongside
+//! a verification ciphertext) are kept in [`ClientKeysPaths::storage_cipher_file`],
+//! and its existence indicates the passphrase has to be provided in order to load the client.
+
+use crate::client::base_client::storage::helpers::{load_client_keys, store_client_keys};
+use crate::client::key_manager::persistence::OnDiskKeys;
+use crate::config::disk_persistence::ClientKeysPaths;
+use crate::error::ClientCoreError;
+use nym_pemstore::encrypted::is_encrypted_key_file;
+use nym_store_cipher::{ExportedStoreCipher, StoreCipher};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// Environment variable that can be used for providing the storage passphrase directly.
+pub const STORAGE_PASSPHRASE_ENV: &str = "NYM_CLIENT_STORAGE_PASSPHRASE";
+
+/// Environment variable that can be used for providing path to the file containing the storage passphrase.
+pub const STORAGE_PASSPHRASE_FILE_ENV: &str = "NYM_CLIENT_STORAGE_PASSPHRASE_FILE";
+
+/// Passphrase used for deriving the storage encryption key.
+pub struct StoragePassphrase(Zeroizing<String>);
+
+impl StoragePassphrase {
+    pub fn new(passphrase: impl Into<String>) -> Result<Self, ClientCoreError> {
+        let passphrase = Zeroizing::new(passphrase.into());
+        if passphrase.is_empty() {
+            return Err(ClientCoreError::EmptyStoragePassphrase);
+        }
+        Ok(StoragePassphrase(passphrase))
+    }
+
+    /// Reads the passphrase from the provided file, ignoring any trailing newline.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ClientCoreError> {
+        let path = path.as_ref();
+        let content = Zeroizing::new(fs::read_to_string(path).map_err(|source| {
+            ClientCoreError::StoragePassphraseLoadFailure {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?);
+        Self::new(content.trim_end_matches(['\r', '\n']))
+    }
+
+    /// Attempts to retrieve the passphrase from either [`STORAGE_PASSPHRASE_ENV`]
+    /// or the file pointed to by [`STORAGE_PASSPHRASE_FILE_ENV`].
+    pub fn from_env() -> Result<Option<Self>, ClientCoreError> {
+        if let Ok(passphrase) = std::env::var(STORAGE_PASSPHRASE_ENV) {
+            return Self::new(passphrase).map(Some);
+        }
+        if let Ok(path) = std::env::var(STORAGE_PASSPHRASE_FILE_ENV) {
+            return Self::from_file(path).map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Interactively asks the user for the passphrase.
+    #[cfg(feature = "cli")]
+    pub fn prompt(confirm: bool) -> Result<Self, ClientCoreError> {
+        let mut prompt = inquire::Password::new("Storage passphrase:")
+            .with_display_mode(inquire::PasswordDisplayMode::Masked);
+        if !confirm {
+            prompt = prompt.without_confirmation();
+        }
+
+        // if we can't prompt (e.g. there's no tty), treat it as if no passphrase was provided
+        let passphrase = prompt.prompt().map_err(|err| {
+            log::debug!("failed to prompt for the storage passphrase: {err}");
+            missing_passphrase()
+        })?;
+        Self::new(passphrase)
+    }
+
+    /// Obtains the passphrase from, in order of priority, the explicitly provided file,
+    /// the environment or, if available, an interactive prompt.
+    pub fn obtain(file: Option<&Path>, confirm: bool) -> Result<Self, ClientCoreError> {
+        if let Some(file) = file {
+            return Self::from_file(file);
+        }
+        if let Some(passphrase) = Self::from_env()? {
+            return Ok(passphrase);
+        }
+        Self::interactive_fallback(confirm)
+    }
+
+    #[cfg(feature = "cli")]
+    fn interactive_fallback(confirm: bool) -> Result<Self, ClientCoreError> {
+        Self::prompt(confirm)
+    }
+
+    #[cfg(not(feature = "cli"))]
+    fn interactive_fallback(_confirm: bool) -> Result<Self, ClientCoreError> {
+        Err(missing_passphrase())
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+fn missing_passphrase() -> ClientCoreError {
+    ClientCoreError::MissingStoragePassphrase {
+        env_var: STORAGE_PASSPHRASE_ENV,
+        file_env_var: STORAGE_PASSPHRASE_FILE_ENV,
+    }
+}
+
+/// Checks whether the client storage has been set up to be encrypted at rest.
+pub fn storage_is_encrypted(paths: &ClientKeysPaths) -> bool {
+    matches!(paths.storage_cipher_file().try_exists(), Ok(true))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut raw = OsString::from(path.as_os_str());
+    raw.push(suffix);
+    PathBuf::from(raw)
+}
+
+/// Location of the cipher parameters while an existing plaintext storage is being encrypted.
+/// They're only moved to [`ClientKeysPaths::storage_cipher_file`] once everything got encrypted.
+fn pending_storage_cipher_file(paths: &ClientKeysPaths) -> PathBuf {
+    with_suffix(&paths.storage_cipher_file(), ".pending")
+}
+
+/// Location of the encrypted keys before they're moved in place of the plaintext ones.
+fn staged_keys_paths(paths: &ClientKeysPaths) -> ClientKeysPaths {
+    ClientKeysPaths {
+        private_identity_key_file: with_suffix(&paths.private_identity_key_file, ".tmp"),
+        public_identity_key_file: with_suffix(&paths.public_identity_key_file, ".tmp"),
+        private_encryption_key_file: with_suffix(&paths.private_encryption_key_file, ".tmp"),
+        public_encryption_key_file: with_suffix(&paths.public_encryption_key_file, ".tmp"),
+        ack_key_file: with_suffix(&paths.ack_key_file, ".tmp"),
+    }
+}
+
+fn key_files(paths: &ClientKeysPaths) -> [&Path; 5] {
+    [
+        &paths.private_identity_key_file,
+        &paths.public_identity_key_file,
+        &paths.private_encryption_key_file,
+        &paths.public_encryption_key_file,
+        &paths.ack_key_file,
+    ]
+}
+
+fn rename(from: &Path, to: &Path) -> Result<(), ClientCoreError> {
+    fs::rename(from, to).map_err(|source| ClientCoreError::StorageCipherFileFailure {
+        path: to.to_path_buf(),
+        source,
+    })
+}
+
+fn write_exported_cipher(path: PathBuf, cipher: &StoreCipher) -> Result<(), ClientCoreError> {
+    let exported = cipher.export_aes256gcm()?;
+    let serialised = serde_json::to_vec_pretty(&exported).map_err(|source| {
+        ClientCoreError::MalformedStorageCipherFile {
+            path: path.clone(),
+            source,
+        }
+    })?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| ClientCoreError::StorageCipherFileFailure {
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    // make sure we never end up with a partially written file
+    let temp_path = with_suffix(&path, ".tmp");
+    fs::write(&temp_path, serialised).map_err(|source| {
+        ClientCoreError::StorageCipherFileFailure {
+            path: temp_path.clone(),
+            source,
+        }
+    })?;
+    rename(&temp_path, &path)
+}
+
+fn import_cipher_file(
+    path: PathBuf,
+    passphrase: &StoragePassphrase,
+) -> Result<Arc<StoreCipher>, ClientCoreError> {
+    let raw = fs::read(&path).map_err(|source| ClientCoreError::StorageCipherFileFailure {
+        path: path.clone(),
+        source,
+    })?;
+    let exported: ExportedStoreCipher = serde_json::from_slice(&raw)
+        .map_err(|source| ClientCoreError::MalformedStorageCipherFile { path, source })?;
+
+    Ok(Arc::new(StoreCipher::import_aes256gcm(
+        passphrase.as_bytes(),
+        exported,
+    )?))
+}
+
+/// Derives a fresh storage cipher out of the passphrase and persists its parameters,
+/// marking the storage as encrypted.
+pub fn create_storage_cipher(
+    paths: &ClientKeysPaths,
+    passphrase: &StoragePassphrase,
+) -> Result<Arc<StoreCipher>, ClientCoreError> {
+    let cipher = StoreCipher::new_with_default_kdf(passphrase.as_bytes())?;
+    write_exported_cipher(paths.storage_cipher_file(), &cipher)?;
+    Ok(Arc::new(cipher))
+}
+
+/// Recovers the storage cipher out of the passphrase and the persisted parameters.
+pub fn load_storage_cipher(
+    paths: &ClientKeysPaths,
+    passphrase: &StoragePassphrase,
+) -> Result<Arc<StoreCipher>, ClientCoreError> {
+    import_cipher_file(paths.storage_cipher_file(), passphrase)
+}
+
+/// Derives the cipher for encrypting an existing plaintext storage, or recovers it if
+/// a previous attempt got interrupted. Its parameters are kept aside, without marking
+/// the storage as encrypted, until [`finalise_storage_encryption`] is called.
+pub fn prepare_storage_encryption(
+    paths: &ClientKeysPaths,
+    passphrase: &StoragePassphrase,
+) -> Result<Arc<StoreCipher>, ClientCoreError> {
+    let pending = pending_storage_cipher_file(paths);
+    if matches!(pending.try_exists(), Ok(true)) {
+        return import_cipher_file(pending, passphrase);
+    }
+
+    let cipher = StoreCipher::new_with_default_kdf(passphrase.as_bytes())?;
+    write_exported_cipher(pending, &cipher)?;
+    Ok(Arc::new(cipher))
+}
+
+/// Replaces the plaintext private keys with their encrypted variants.
+/// The encrypted keys are first written to temporary files and are only moved into place
+/// once all of them got stored. If that process gets interrupted, calling this function
+/// again (with the same cipher) finishes it.
+pub async fn encrypt_plaintext_keys(
+    paths: &ClientKeysPaths,
+    cipher: Arc<StoreCipher>,
+) -> Result<(), ClientCoreError> {
+    let staged = staged_keys_paths(paths);
+
+    // if any key has already been moved into place, all of them have been fully staged before
+    let moving_started = [
+        paths.private_identity_key(),
+        paths.private_encryption_key(),
+        paths.ack_key(),
+    ]
+    .into_iter()
+    .any(|path| is_encrypted_key_file(path).unwrap_or_default());
+
+    if !moving_started {
+        let client_keys = load_client_keys(&OnDiskKeys::new(paths.clone())).await?;
+        store_client_keys(
+            client_keys,
+            &OnDiskKeys::new(staged.clone()).with_cipher(cipher),
+        )
+        .await?;
+    }
+
+    for (staged_file, key_file) in key_files(&staged).into_iter().zip(key_files(paths)) {
+        if matches!(staged_file.try_exists(), Ok(true)) {
+            rename(staged_file, key_file)?;
+        }
+    }
+    Ok(())
+}
+
+/// Marks the storage as encrypted by moving the cipher parameters created by
+/// [`prepare_storage_encryption`] into place. It must only be called once everything
+/// else has been encrypted.
+pub fn finalise_storage_encryption(paths: &ClientKeysPaths) -> Result<(), ClientCoreError> {
+    rename(
+        &pending_storage_cipher_file(paths),
+        &paths.storage_cipher_file(),
+    )
+}
+
+/// If the client storage is encrypted, obtains the passphrase (see [`StoragePassphrase::obtain`])
+/// and recovers the storage cipher.
+pub fn load_storage_cipher_if_encrypted(
+    paths: &ClientKeysPaths,
+) -> Result<Option<Arc<StoreCipher>>, ClientCoreError> {
+    if !storage_is_encrypted(paths) {
+        return Ok(None);
+    }
+
+    let passphrase = StoragePassphrase::obtain(None, false)?;
+    load_storage_cipher(paths, &passphrase).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::key_manager::ClientKeys;
+    use futures::executor::block_on;
+    use rand::rngs::OsRng;
+
+    fn assert_same_keys(a: &ClientKeys, b: &ClientKeys) {
+        assert_eq!(
+            a.identity_keypair().private_key().to_bytes(),
+            b.identity_keypair().private_key().to_bytes()
+        );
+        assert_eq!(
+            a.encryption_keypair().private_key().to_bytes(),
+            b.encryption_keypair().private_key().to_bytes()
+        );
+        assert_eq!(a.ack_key().to_bytes(), b.ack_key().to_bytes());
+    }
+
+    #[test]
+    fn encrypting_existing_plaintext_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ClientKeysPaths::new_base(dir.path());
+        let keys = ClientKeys::generate_new(&mut OsRng);
+        block_on(store_client_keys(
+            keys.clone(),
+            &OnDiskKeys::new(paths.clone()),
+        ))
+        .unwrap();
+
+        let passphrase = StoragePassphrase::new("my-secret-passphrase").unwrap();
+        let cipher = prepare_storage_encryption(&paths, &passphrase).unwrap();
+        // the storage is not marked as encrypted until everything has been migrated
+        assert!(!storage_is_encrypted(&paths));
+
+        block_on(encrypt_plaintext_keys(&paths, cipher.clone())).unwrap();
+        assert!(is_encrypted_key_file(paths.private_identity_key()).unwrap());
+        assert!(is_encrypted_key_file(paths.private_encryption_key()).unwrap());
+        assert!(is_encrypted_key_file(paths.ack_key()).unwrap());
+        for staged in key_files(&staged_keys_paths(&paths)) {
+            assert!(!staged.exists())
+        }
+        assert!(!storage_is_encrypted(&paths));
+
+        finalise_storage_encryption(&paths).unwrap();
+        assert!(storage_is_encrypted(&paths));
+        assert!(!pending_storage_cipher_file(&paths).exists());
+
+        let cipher = load_storage_cipher(&paths, &passphrase).unwrap();
+        let loaded = block_on(load_client_keys(
+            &OnDiskKeys::new(paths.clone()).with_cipher(cipher),
+        ))
+        .unwrap();
+        assert_same_keys(&keys, &loaded);
+
+        let wrong_passphrase = StoragePassphrase::new("not-my-passphrase").unwrap();
+        assert!(load_storage_cipher(&paths, &wrong_passphrase).is_err());
+    }
+
+    #[test]
+    fn resuming_interrupted_encryption() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ClientKeysPaths::new_base(dir.path());
+        let keys = ClientKeys::generate_new(&mut OsRng);
+        block_on(store_client_keys(
+            keys.clone(),
+            &OnDiskKeys::new(paths.clone()),
+        ))
+        .unwrap();
+
+        let passphrase = StoragePassphrase::new("my-secret-passphrase").unwrap();
+        let cipher = prepare_storage_encryption(&paths, &passphrase).unwrap();
+
+        // simulate getting interrupted after all keys got staged, but only one got moved in place
+        let staged = staged_keys_paths(&paths);
+        block_on(store_client_keys(
+            keys.clone(),
+            &OnDiskKeys::new(staged.clone()).with_cipher(cipher),
+        ))
+        .unwrap();
+        fs::rename(&staged.ack_key_file, &paths.ack_key_file).unwrap();
+
+        // the cipher is recovered rather than derived anew
+        let cipher = prepare_storage_encryption(&paths, &passphrase).unwrap();
+        block_on(encrypt_plaintext_keys(&paths, cipher.clone())).unwrap();
+        finalise_storage_encryption(&paths).unwrap();
+
+        let loaded = block_on(load_client_keys(
+            &OnDiskKeys::new(paths.clone()).with_cipher(cipher),
+        ))
+        .unwrap();
+        assert_same_keys(&keys, &loaded);
+    }
+}
@@ -32,6 +32,8 @@ pub use nym_client_core_gateways_storage::{GatewaysDetailsStore, InMemGatewaysDe
 #[cfg(all(not(target_arch = "wasm32"), feature = "fs-gateways-storage"))]
 pub use nym_client_core_gateways_storage::{OnDiskGatewaysDetails, StorageError};
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod encryption;
 pub mod helpers;
 
 #[cfg(all(
@@ -130,24 +132,45 @@ impl OnDiskPersistent {
         }
     }
 
+    /// Sets up the on-disk storage using the provided paths.
+    /// If the storage has been encrypted, the passphrase is going to be obtained
+    /// from the environment or, if possible, an interactive prompt.
     pub async fn from_paths(
         paths: CommonClientPaths,
         debug_config: &config::DebugConfig,
     ) -> Result<Self, ClientCoreError> {
-        let key_store = OnDiskKeys::new(paths.keys);
+        let cipher = encryption::load_storage_cipher_if_encrypted(&paths.keys)?;
+        Self::from_paths_with_cipher(paths, debug_config, cipher).await
+    }
 
-        let reply_store = non_wasm_helpers::setup_fs_reply_surb_backend(
+    /// Sets up the on-disk storage using the provided paths, with all the sensitive data
+    /// being encrypted using the provided cipher, if any.
+    pub async fn from_paths_with_cipher(
+        paths: CommonClientPaths,
+        debug_config: &config::DebugConfig,
+        cipher: Option<std::sync::Arc<nym_store_cipher::StoreCipher>>,
+    ) -> Result<Self, ClientCoreError> {
+        let mut key_store = OnDiskKeys::new(paths.keys);
+
+        let mut reply_store = non_wasm_helpers::setup_fs_reply_surb_backend(
             paths.reply_surb_database,
             &debug_config.reply_surbs,
         )
         .await?;
 
-        let credential_store =
+        let mut credential_store =
             nym_credential_storage::initialise_persistent_storage(paths.credentials_database).await;
 
-        let gateway_details_store =
+        let mut gateway_details_store =
             non_wasm_helpers::setup_fs_gateways_storage(paths.gateway_registrations).await?;
 
+        if let Some(cipher) = cipher {
+            key_store = key_store.with_cipher(cipher.clone());
+            reply_store = reply_store.with_cipher(cipher.clone());
+            credential_store = credential_store.with_cipher(cipher.clone());
+            gateway_details_store = gateway_details_store.with_cipher(cipher);
+        }
+
         Ok(OnDiskPersistent {
             key_store,
             reply_store,
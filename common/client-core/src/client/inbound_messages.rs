@@ -3,6 +3,7 @@
 
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
+use nym_sphinx::chunking::ForwardErrorCorrection;
 use nym_sphinx::forwarding::packet::MixPacket;
 use nym_sphinx::params::PacketType;
 use nym_task::connections::TransmissionLane;
@@ -29,6 +30,7 @@ pub enum InputMessage {
         data: Vec<u8>,
         lane: TransmissionLane,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     },
 
     /// Creates a message used for a duplex anonymous communication where the recipient
@@ -45,6 +47,7 @@ pub enum InputMessage {
         reply_surbs: u32,
         lane: TransmissionLane,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     },
 
     /// Attempt to use our internally received and stored `ReplySurb` to send the message back
@@ -95,6 +98,7 @@ impl InputMessage {
             data,
             lane,
             mix_hops: None,
+            forward_error_correction: None,
         };
         if let Some(packet_type) = packet_type {
             InputMessage::new_wrapper(message, packet_type)
@@ -117,6 +121,7 @@ impl InputMessage {
             data,
             lane,
             mix_hops,
+            forward_error_correction: None,
         };
         if let Some(packet_type) = packet_type {
             InputMessage::new_wrapper(message, packet_type)
@@ -138,6 +143,7 @@ impl InputMessage {
             reply_surbs,
             lane,
             mix_hops: None,
+            forward_error_correction: None,
         };
         if let Some(packet_type) = packet_type {
             InputMessage::new_wrapper(message, packet_type)
@@ -162,6 +168,7 @@ impl InputMessage {
             reply_surbs,
             lane,
             mix_hops,
+            forward_error_correction: None,
         };
         if let Some(packet_type) = packet_type {
             InputMessage::new_wrapper(message, packet_type)
@@ -188,6 +195,32 @@ impl InputMessage {
         }
     }
 
+    /// Attaches parity packets to this message so that the recipient could reconstruct it
+    /// even if some of its packets got lost in the network.
+    /// Note that it has no effect on `Premade` and `Reply` messages.
+    #[must_use]
+    pub fn with_forward_error_correction(mut self, fec: ForwardErrorCorrection) -> Self {
+        self.set_forward_error_correction(fec);
+        self
+    }
+
+    fn set_forward_error_correction(&mut self, fec: ForwardErrorCorrection) {
+        match self {
+            InputMessage::Regular {
+                forward_error_correction,
+                ..
+            }
+            | InputMessage::Anonymous {
+                forward_error_correction,
+                ..
+            } => *forward_error_correction = Some(fec),
+            InputMessage::MessageWrapper { message, .. } => {
+                message.set_forward_error_correction(fec)
+            }
+            InputMessage::Premade { .. } | InputMessage::Reply { .. } => {}
+        }
+    }
+
     pub fn lane(&self) -> &TransmissionLane {
         match self {
             InputMessage::Regular { lane, .. }
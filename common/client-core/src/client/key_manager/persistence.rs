@@ -16,6 +16,10 @@ use nym_pemstore::traits::{PemStorableKey, PemStorableKeyPair};
 use nym_pemstore::KeyPairPath;
 #[cfg(not(target_arch = "wasm32"))]
 use nym_sphinx::acknowledgements::AckKey;
+#[cfg(not(target_arch = "wasm32"))]
+use nym_store_cipher::StoreCipher;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
 
 // we have to define it as an async trait since wasm storage is async
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -62,24 +66,47 @@ pub enum OnDiskKeysError {
         #[source]
         err: std::io::Error,
     },
+
+    #[error("the keys are encrypted, but no storage cipher has been provided")]
+    MissingStorageCipher,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 pub struct OnDiskKeys {
     paths: ClientKeysPaths,
+
+    /// Optional cipher used for keeping the private keys encrypted at rest.
+    cipher: Option<Arc<StoreCipher>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl From<ClientKeysPaths> for OnDiskKeys {
     fn from(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys::new(paths)
     }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl OnDiskKeys {
     pub fn new(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys {
+            paths,
+            cipher: None,
+        }
+    }
+
+    /// Makes the store keep all the private keys encrypted with the provided cipher.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<StoreCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn ensure_readable(&self) -> Result<(), OnDiskKeysError> {
+        if self.cipher.is_none() && self.paths.storage_cipher_file().exists() {
+            return Err(OnDiskKeysError::MissingStorageCipher);
+        }
+        Ok(())
     }
 
     #[doc(hidden)]
@@ -99,7 +126,12 @@ impl OnDiskKeys {
         path: &std::path::Path,
         name: impl Into<String>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_key(path).map_err(|err| OnDiskKeysError::KeyLoadFailure {
+        self.ensure_readable()?;
+        let res = match &self.cipher {
+            Some(cipher) => nym_pemstore::encrypted::load_encrypted_key(path, cipher),
+            None => nym_pemstore::load_key(path),
+        };
+        res.map_err(|err| OnDiskKeysError::KeyLoadFailure {
             key: name.into(),
             path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
             err,
@@ -111,7 +143,12 @@ impl OnDiskKeys {
         paths: KeyPairPath,
         name: impl Into<String>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_keypair(&paths).map_err(|err| OnDiskKeysError::KeyPairLoadFailure {
+        self.ensure_readable()?;
+        let res = match &self.cipher {
+            Some(cipher) => nym_pemstore::encrypted::load_encrypted_keypair(&paths, cipher),
+            None => nym_pemstore::load_keypair(&paths),
+        };
+        res.map_err(|err| OnDiskKeysError::KeyPairLoadFailure {
             keys: name.into(),
             paths,
             err,
@@ -124,7 +161,11 @@ impl OnDiskKeys {
         path: &std::path::Path,
         name: impl Into<String>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_key(key, path).map_err(|err| OnDiskKeysError::KeyStoreFailure {
+        let res = match &self.cipher {
+            Some(cipher) => nym_pemstore::encrypted::store_encrypted_key(key, path, cipher),
+            None => nym_pemstore::store_key(key, path),
+        };
+        res.map_err(|err| OnDiskKeysError::KeyStoreFailure {
             key: name.into(),
             path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
             err,
@@ -137,12 +178,14 @@ impl OnDiskKeys {
         paths: KeyPairPath,
         name: impl Into<String>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_keypair(keys, &paths).map_err(|err| {
-            OnDiskKeysError::KeyPairStoreFailure {
-                keys: name.into(),
-                paths,
-                err,
-            }
+        let res = match &self.cipher {
+            Some(cipher) => nym_pemstore::encrypted::store_encrypted_keypair(keys, &paths, cipher),
+            None => nym_pemstore::store_keypair(keys, &paths),
+        };
+        res.map_err(|err| OnDiskKeysError::KeyPairStoreFailure {
+            keys: name.into(),
+            paths,
+            err,
         })
     }
 
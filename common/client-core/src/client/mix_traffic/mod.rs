@@ -2,10 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client::mix_traffic::transceiver::GatewayTransceiver;
+use crate::error::ClientCoreStatusMessage;
 use crate::spawn_future;
 use log::*;
 use nym_sphinx::forwarding::packet::MixPacket;
 
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
 pub type BatchMixMessageSender = tokio::sync::mpsc::Sender<Vec<MixPacket>>;
 pub type BatchMixMessageReceiver = tokio::sync::mpsc::Receiver<Vec<MixPacket>>;
 
@@ -63,6 +70,14 @@ impl MixTrafficController {
     async fn on_messages(&mut self, mut mix_packets: Vec<MixPacket>) {
         debug_assert!(!mix_packets.is_empty());
 
+        // if the gateway has told us we're sending too fast, wait before pushing anything else
+        if let Some(back_off) = self.gateway_transceiver.throttled_for() {
+            warn!(
+                "the gateway is throttling us - waiting {back_off:?} before sending more packets"
+            );
+            sleep(back_off).await;
+        }
+
         let result = if mix_packets.len() == 1 {
             let mix_packet = mix_packets.pop().unwrap();
             self.gateway_transceiver.send_mix_packet(mix_packet).await
@@ -76,11 +91,6 @@ impl MixTrafficController {
             Err(err) => {
                 error!("Failed to send sphinx packet(s) to the gateway: {err}");
                 self.consecutive_gateway_failure_count += 1;
-                if self.consecutive_gateway_failure_count == MAX_FAILURE_COUNT {
-                    // todo: in the future this should initiate a 'graceful' shutdown or try
-                    // to reconnect?
-                    panic!("failed to send sphinx packet to the gateway {MAX_FAILURE_COUNT} times in a row - assuming the gateway is dead. Can't do anything about it yet :(")
-                }
             }
             Ok(_) => {
                 trace!("We *might* have managed to forward sphinx packet(s) to the gateway!");
@@ -89,6 +99,10 @@ impl MixTrafficController {
         }
     }
 
+    fn gateway_is_unreachable(&self) -> bool {
+        self.consecutive_gateway_failure_count >= MAX_FAILURE_COUNT
+    }
+
     pub fn start_with_shutdown(mut self, mut shutdown: nym_task::TaskClient) {
         spawn_future(async move {
             debug!("Started MixTrafficController with graceful shutdown support");
@@ -98,6 +112,18 @@ impl MixTrafficController {
                     mix_packets = self.mix_rx.recv() => match mix_packets {
                         Some(mix_packets) => {
                             self.on_messages(mix_packets).await;
+                            if self.gateway_is_unreachable() {
+                                let status = ClientCoreStatusMessage::GatewayIsUnreachable {
+                                    gateway_id: self.gateway_transceiver.gateway_identity().to_base58_string(),
+                                    failures: self.consecutive_gateway_failure_count,
+                                };
+                                error!("{status}");
+
+                                // let whoever is listening decide what to do about it (e.g. switch to another gateway)
+                                // and stop the controller, which in turn will signal an unexpected shutdown
+                                shutdown.send_status_msg(Box::new(status));
+                                return;
+                            }
                         },
                         None => {
                             log::trace!("MixTrafficController: Stopping since channel closed");
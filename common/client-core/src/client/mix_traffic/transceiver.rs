@@ -11,6 +11,7 @@ use nym_sphinx::forwarding::packet::MixPacket;
 use nym_validator_client::nyxd::contract_traits::DkgQueryClient;
 use std::fmt::Debug;
 use std::os::raw::c_int as RawFd;
+use std::time::Duration;
 use thiserror::Error;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -48,6 +49,11 @@ pub trait GatewaySender {
         }
         Ok(())
     }
+
+    /// Returns the remaining amount of time the gateway has asked us to stop sending packets for, if any.
+    fn throttled_for(&self) -> Option<Duration> {
+        None
+    }
 }
 
 /// this trait defines the functionality of being able to correctly route
@@ -90,6 +96,11 @@ impl<G: GatewaySender + ?Sized + Send> GatewaySender for Box<G> {
     ) -> Result<(), ErasedGatewayError> {
         (**self).batch_send_mix_packets(packets).await
     }
+
+    #[inline]
+    fn throttled_for(&self) -> Option<Duration> {
+        (**self).throttled_for()
+    }
 }
 
 impl<G: GatewayReceiver + ?Sized> GatewayReceiver for Box<G> {
@@ -149,6 +160,10 @@ where
             .await
             .map_err(erase_err)
     }
+
+    fn throttled_for(&self) -> Option<Duration> {
+        self.gateway_client.throttled_for()
+    }
 }
 
 impl<C, St> GatewayReceiver for RemoteGateway<C, St> {}
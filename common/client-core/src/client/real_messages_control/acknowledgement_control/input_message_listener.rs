@@ -8,6 +8,7 @@ use crate::client::replies::reply_controller::ReplyControllerSender;
 use log::*;
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
+use nym_sphinx::chunking::ForwardErrorCorrection;
 use nym_sphinx::forwarding::packet::MixPacket;
 use nym_sphinx::params::PacketType;
 use nym_task::connections::TransmissionLane;
@@ -74,16 +75,25 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     ) {
         if let Err(err) = self
             .message_handler
-            .try_send_plain_message(recipient, content, lane, packet_type, mix_hops)
+            .try_send_plain_message(
+                recipient,
+                content,
+                lane,
+                packet_type,
+                mix_hops,
+                forward_error_correction,
+            )
             .await
         {
             warn!("failed to send a plain message - {err}")
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn handle_repliable_message(
         &mut self,
         recipient: Recipient,
@@ -92,6 +102,7 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     ) {
         if let Err(err) = self
             .message_handler
@@ -102,6 +113,7 @@ where
                 lane,
                 packet_type,
                 mix_hops,
+                forward_error_correction,
             )
             .await
         {
@@ -116,9 +128,17 @@ where
                 data,
                 lane,
                 mix_hops,
+                forward_error_correction,
             } => {
-                self.handle_plain_message(recipient, data, lane, PacketType::Mix, mix_hops)
-                    .await
+                self.handle_plain_message(
+                    recipient,
+                    data,
+                    lane,
+                    PacketType::Mix,
+                    mix_hops,
+                    forward_error_correction,
+                )
+                .await
             }
             InputMessage::Anonymous {
                 recipient,
@@ -126,6 +146,7 @@ where
                 reply_surbs,
                 lane,
                 mix_hops,
+                forward_error_correction,
             } => {
                 self.handle_repliable_message(
                     recipient,
@@ -134,6 +155,7 @@ where
                     lane,
                     PacketType::Mix,
                     mix_hops,
+                    forward_error_correction,
                 )
                 .await
             }
@@ -154,9 +176,17 @@ where
                     data,
                     lane,
                     mix_hops,
+                    forward_error_correction,
                 } => {
-                    self.handle_plain_message(recipient, data, lane, packet_type, mix_hops)
-                        .await
+                    self.handle_plain_message(
+                        recipient,
+                        data,
+                        lane,
+                        packet_type,
+                        mix_hops,
+                        forward_error_correction,
+                    )
+                    .await
                 }
                 InputMessage::Anonymous {
                     recipient,
@@ -164,6 +194,7 @@ where
                     reply_surbs,
                     lane,
                     mix_hops,
+                    forward_error_correction,
                 } => {
                     self.handle_repliable_message(
                         recipient,
@@ -172,6 +203,7 @@ where
                         lane,
                         packet_type,
                         mix_hops,
+                        forward_error_correction,
                     )
                     .await
                 }
@@ -14,8 +14,9 @@ use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::{AnonymousSenderTag, RepliableMessage, ReplyMessage};
 use nym_sphinx::anonymous_replies::{ReplySurb, SurbEncryptionKey};
 use nym_sphinx::chunking::fragment::{Fragment, FragmentIdentifier};
+use nym_sphinx::chunking::ForwardErrorCorrection;
 use nym_sphinx::message::NymMessage;
-use nym_sphinx::params::{PacketSize, PacketType, DEFAULT_NUM_MIX_HOPS};
+use nym_sphinx::params::{PacketSize, PacketType, PayloadCompression, DEFAULT_NUM_MIX_HOPS};
 use nym_sphinx::preparer::{MessagePreparer, PreparedFragment};
 use nym_sphinx::Delay;
 use nym_task::connections::TransmissionLane;
@@ -106,6 +107,9 @@ pub(crate) struct Config {
 
     /// Optional secondary predefined packet size used for the encapsulated messages.
     secondary_packet_size: Option<PacketSize>,
+
+    /// Optional compression algorithm applied to the messages before they get split into packets.
+    payload_compression: Option<PayloadCompression>,
 }
 
 impl Config {
@@ -123,6 +127,7 @@ impl Config {
             num_mix_hops: DEFAULT_NUM_MIX_HOPS,
             primary_packet_size: PacketSize::default(),
             secondary_packet_size: None,
+            payload_compression: None,
         }
     }
 
@@ -144,6 +149,12 @@ impl Config {
         self.secondary_packet_size = packet_size;
         self
     }
+
+    /// Allows compressing the sent messages.
+    pub fn with_payload_compression(mut self, compression: Option<PayloadCompression>) -> Self {
+        self.payload_compression = compression;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -180,7 +191,8 @@ where
             config.average_packet_delay,
             config.average_ack_delay,
         )
-        .with_mix_hops(config.num_mix_hops);
+        .with_mix_hops(config.num_mix_hops)
+        .with_payload_compression(config.payload_compression);
 
         MessageHandler {
             config,
@@ -419,10 +431,18 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     ) -> Result<(), PreparationError> {
         let message = NymMessage::new_plain(message);
-        self.try_split_and_send_non_reply_message(message, recipient, lane, packet_type, mix_hops)
-            .await
+        self.try_split_and_send_non_reply_message(
+            message,
+            recipient,
+            lane,
+            packet_type,
+            mix_hops,
+            forward_error_correction,
+        )
+        .await
     }
 
     pub(crate) async fn try_split_and_send_non_reply_message(
@@ -432,6 +452,7 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     ) -> Result<(), PreparationError> {
         debug!("Sending non-reply message with packet type {packet_type}");
         // TODO: I really dislike existence of this assertion, it implies code has to be re-organised
@@ -447,9 +468,19 @@ where
             self.optimal_packet_size(&message)
         };
         debug!("Using {packet_size} packets for {message}");
-        let fragments = self
-            .message_preparer
-            .pad_and_split_message(message, packet_size);
+        let fragments = match forward_error_correction {
+            Some(fec) => {
+                debug!(
+                    "attaching {}% of parity packets to the message",
+                    fec.parity_percentage()
+                );
+                self.message_preparer
+                    .pad_and_split_message_with_fec(message, packet_size, fec)
+            }
+            None => self
+                .message_preparer
+                .pad_and_split_message(message, packet_size),
+        };
 
         let mut pending_acks = Vec::with_capacity(fragments.len());
         let mut real_messages = Vec::with_capacity(fragments.len());
@@ -507,6 +538,7 @@ where
             TransmissionLane::AdditionalReplySurbs,
             packet_type,
             mix_hops,
+            None,
         )
         .await?;
 
@@ -516,6 +548,7 @@ where
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn try_send_message_with_reply_surbs(
         &mut self,
         recipient: Recipient,
@@ -524,6 +557,7 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        forward_error_correction: Option<ForwardErrorCorrection>,
     ) -> Result<(), SurbWrappedPreparationError> {
         debug!("Sending message with reply SURBs with packet type {packet_type}");
         let sender_tag = self.get_or_create_sender_tag(&recipient);
@@ -534,8 +568,15 @@ where
         let message =
             NymMessage::new_repliable(RepliableMessage::new_data(message, sender_tag, reply_surbs));
 
-        self.try_split_and_send_non_reply_message(message, recipient, lane, packet_type, mix_hops)
-            .await?;
+        self.try_split_and_send_non_reply_message(
+            message,
+            recipient,
+            lane,
+            packet_type,
+            mix_hops,
+            forward_error_correction,
+        )
+        .await?;
 
         log::trace!("storing {} reply keys", reply_keys.len());
         self.reply_key_storage.insert_multiple(reply_keys);
@@ -100,6 +100,7 @@ impl<'a> From<&'a Config> for message_handler::Config {
         )
         .with_custom_primary_packet_size(cfg.traffic.primary_packet_size)
         .with_custom_secondary_packet_size(cfg.traffic.secondary_packet_size)
+        .with_payload_compression(cfg.traffic.payload_compression)
     }
 }
 
@@ -5,6 +5,8 @@ use crate::client::{
     packet_statistics_control::{PacketStatisticsEvent, PacketStatisticsReporter},
     replies::{reply_controller::ReplyControllerSender, reply_storage::SentReplyKeys},
 };
+use crate::config;
+use crate::error::ClientCoreStatusMessage;
 use crate::spawn_future;
 use futures::channel::mpsc;
 use futures::lock::Mutex;
@@ -17,6 +19,9 @@ use nym_sphinx::anonymous_replies::requests::{
     RepliableMessage, RepliableMessageContent, ReplyMessage, ReplyMessageContent,
 };
 use nym_sphinx::anonymous_replies::{encryption_key::EncryptionKeyDigest, SurbEncryptionKey};
+use nym_sphinx::chunking::reconstruction::{
+    EvictedSet, EvictionPolicy, EvictionReason, MessageReconstructor, ReconstructionLimits,
+};
 use nym_sphinx::message::{NymMessage, PlainMessage};
 use nym_sphinx::params::ReplySurbKeyDigestAlgorithm;
 use nym_sphinx::receiver::{MessageReceiver, MessageRecoveryError, ReconstructedMessage};
@@ -164,12 +169,17 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
         reply_key_storage: SentReplyKeys,
         reply_controller_sender: ReplyControllerSender,
         stats_tx: PacketStatisticsReporter,
+        reconstruction_limits: ReconstructionLimits,
     ) -> Self {
+        let mut message_receiver = R::new();
+        *message_receiver.reconstructor() =
+            MessageReconstructor::with_limits(reconstruction_limits);
+
         ReceivedMessagesBuffer {
             inner: Arc::new(Mutex::new(ReceivedMessagesBufferInner {
                 messages: Vec::new(),
                 local_encryption_keypair,
-                message_receiver: R::new(),
+                message_receiver,
                 message_sender: None,
                 recently_reconstructed: HashSet::new(),
                 stats_tx,
@@ -352,10 +362,12 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
             })
     }
 
+    /// Processes newly received packets and returns information about any incomplete messages
+    /// that had to be dropped by the reconstructor in the process.
     async fn handle_new_received(
         &mut self,
         msgs: Vec<Vec<u8>>,
-    ) -> Result<(), MessageRecoveryError> {
+    ) -> Result<Vec<EvictedSet>, MessageRecoveryError> {
         trace!(
             "Processing {:?} new message that might get added to the buffer!",
             msgs.len()
@@ -382,12 +394,16 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
             }
         }
 
+        let evicted = inner_guard
+            .message_receiver
+            .reconstructor()
+            .take_evicted_sets();
         drop(inner_guard);
 
         if !completed_messages.is_empty() {
             self.handle_reconstructed_messages(completed_messages).await
         }
-        Ok(())
+        Ok(evicted)
     }
 }
 
@@ -466,6 +482,28 @@ impl<R: MessageReceiver> FragmentedMessageReceiver<R> {
         }
     }
 
+    fn report_evicted_sets(&self, evicted: Vec<EvictedSet>, shutdown: &mut nym_task::TaskClient) {
+        for set in evicted {
+            let reason = match set.reason {
+                EvictionReason::Expired => "timed out waiting for the remaining fragments",
+                EvictionReason::MemoryBudgetExceeded => "reconstruction memory budget exceeded",
+                EvictionReason::ReconstructionFailure => "received fragments were malformed",
+            };
+            warn!(
+                "dropped incomplete message set {} with {}/{} fragments received ({} bytes): {reason}",
+                set.set_id, set.received_fragments, set.total_fragments, set.buffered_bytes
+            );
+            shutdown.send_status_msg(Box::new(
+                ClientCoreStatusMessage::IncompleteMessageDropped {
+                    set_id: set.set_id,
+                    received_fragments: set.received_fragments,
+                    total_fragments: set.total_fragments,
+                    reason: reason.to_string(),
+                },
+            ));
+        }
+    }
+
     async fn run_with_shutdown(
         &mut self,
         mut shutdown: nym_task::TaskClient,
@@ -475,7 +513,8 @@ impl<R: MessageReceiver> FragmentedMessageReceiver<R> {
             tokio::select! {
                 new_messages = self.mixnet_packet_receiver.next() => {
                     if let Some(new_messages) = new_messages {
-                        self.received_buffer.handle_new_received(new_messages).await?;
+                        let evicted = self.received_buffer.handle_new_received(new_messages).await?;
+                        self.report_evicted_sets(evicted, &mut shutdown);
                     } else {
                         log::trace!("FragmentedMessageReceiver: Stopping since channel closed");
                         break;
@@ -492,6 +531,17 @@ impl<R: MessageReceiver> FragmentedMessageReceiver<R> {
     }
 }
 
+fn reconstruction_limits(cfg: config::MessageReconstruction) -> ReconstructionLimits {
+    ReconstructionLimits {
+        incomplete_set_timeout: cfg.incomplete_set_timeout,
+        memory_budget: cfg.memory_budget,
+        eviction_policy: match cfg.eviction_policy {
+            config::EvictionPolicy::OldestFirst => EvictionPolicy::OldestFirst,
+            config::EvictionPolicy::LargestFirst => EvictionPolicy::LargestFirst,
+        },
+    }
+}
+
 pub(crate) struct ReceivedMessagesBufferController<R: MessageReceiver> {
     fragmented_message_receiver: FragmentedMessageReceiver<R>,
     request_receiver: RequestReceiver<R>,
@@ -505,12 +555,14 @@ impl<R: MessageReceiver + Clone + Send + 'static> ReceivedMessagesBufferControll
         reply_key_storage: SentReplyKeys,
         reply_controller_sender: ReplyControllerSender,
         packet_statistics_reporter: PacketStatisticsReporter,
+        reconstruction_config: config::MessageReconstruction,
     ) -> Self {
         let received_buffer = ReceivedMessagesBuffer::new(
             local_encryption_keypair,
             reply_key_storage,
             reply_controller_sender,
             packet_statistics_reporter,
+            reconstruction_limits(reconstruction_config),
         );
 
         ReceivedMessagesBufferController {
@@ -543,3 +595,16 @@ impl<R: MessageReceiver + Clone + Send + 'static> ReceivedMessagesBufferControll
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_reconstruction_config_matches_default_limits() {
+        assert_eq!(
+            reconstruction_limits(Default::default()),
+            ReconstructionLimits::default()
+        );
+    }
+}
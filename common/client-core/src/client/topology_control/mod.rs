@@ -1,14 +1,16 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::config;
 use crate::spawn_future;
 pub(crate) use accessor::{TopologyAccessor, TopologyReadPermit};
 use futures::StreamExt;
 use log::*;
 use nym_sphinx::addressing::nodes::NodeIdentity;
 use nym_topology::provider_trait::TopologyProvider;
-use nym_topology::NymTopologyError;
+use nym_topology::{NymTopologyError, RouteSelectionStrategy};
 use std::time::Duration;
+use time::OffsetDateTime;
 
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::time::sleep;
@@ -25,11 +27,38 @@ const MAX_FAILURE_COUNT: usize = 10;
 
 pub struct TopologyRefresherConfig {
     refresh_rate: Duration,
+    route_selection: RouteSelectionStrategy,
 }
 
 impl TopologyRefresherConfig {
     pub fn new(refresh_rate: Duration) -> Self {
-        TopologyRefresherConfig { refresh_rate }
+        TopologyRefresherConfig {
+            refresh_rate,
+            route_selection: Default::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_route_selection_strategy(
+        mut self,
+        route_selection: RouteSelectionStrategy,
+    ) -> Self {
+        self.route_selection = route_selection;
+        self
+    }
+}
+
+/// Translates the route selection setting from the client config into the strategy used by the topology.
+pub fn route_selection_strategy(config: &config::Topology) -> RouteSelectionStrategy {
+    match config.route_selection {
+        config::RouteSelection::Uniform => RouteSelectionStrategy::Uniform,
+        config::RouteSelection::StakeWeighted => RouteSelectionStrategy::StakeWeighted,
+        config::RouteSelection::PerformanceWeighted => {
+            RouteSelectionStrategy::PerformanceWeighted {
+                minimum_mixnode_performance: config.minimum_mixnode_performance,
+                minimum_gateway_performance: config.minimum_gateway_performance,
+            }
+        }
     }
 }
 
@@ -38,6 +67,7 @@ pub struct TopologyRefresher {
     topology_accessor: TopologyAccessor,
 
     refresh_rate: Duration,
+    route_selection: RouteSelectionStrategy,
     consecutive_failure_count: usize,
 }
 
@@ -51,6 +81,7 @@ impl TopologyRefresher {
             topology_provider,
             topology_accessor,
             refresh_rate: cfg.refresh_rate,
+            route_selection: cfg.route_selection,
             consecutive_failure_count: 0,
         }
     }
@@ -69,7 +100,15 @@ impl TopologyRefresher {
                 .await;
         }
 
-        let new_topology = self.topology_provider.get_new_topology().await;
+        let new_topology = self
+            .topology_provider
+            .get_new_topology()
+            .await
+            .map(|mut topology| {
+                // the provider might have returned keys of nodes that have already rotated them
+                topology.apply_sphinx_key_rotations(OffsetDateTime::now_utc());
+                topology.with_route_selection_strategy(self.route_selection)
+            });
         if new_topology.is_none() {
             warn!("failed to obtain new network topology");
         }
@@ -214,6 +214,45 @@ pub enum ClientCoreError {
 
     #[error("this client has already registered with gateway {gateway_id}")]
     AlreadyRegistered { gateway_id: String },
+
+    #[error("the storage of this client is already encrypted")]
+    StorageAlreadyEncrypted,
+
+    #[error("the storage of this client is encrypted, but no passphrase has been provided. Set the {env_var} (or {file_env_var}) environment variable or run the client interactively")]
+    MissingStoragePassphrase {
+        env_var: &'static str,
+        file_env_var: &'static str,
+    },
+
+    #[error("failed to read the storage passphrase from '{}': {source}", path.display())]
+    StoragePassphraseLoadFailure {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("the provided storage passphrase is empty")]
+    EmptyStoragePassphrase,
+
+    #[error("failed to read or write the storage cipher parameters at '{}': {source}", path.display())]
+    StorageCipherFileFailure {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("the storage cipher parameters at '{}' are malformed: {source}", path.display())]
+    MalformedStorageCipherFile {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("storage cipher failure: {source}")]
+    StorageCipherFailure {
+        #[from]
+        source: nym_store_cipher::Error,
+    },
 }
 
 /// Set of messages that the client can send to listeners via the task manager
@@ -225,4 +264,15 @@ pub enum ClientCoreStatusMessage {
     // NOTE: The nym-connect frontend listens for these strings, so don't change them until we have a more robust mechanism in place
     #[error("The connected gateway is very slow, or the connection to it is very slow")]
     GatewayIsVerySlow,
+
+    #[error("failed to send sphinx packets to gateway {gateway_id} {failures} times in a row - assuming the gateway is dead")]
+    GatewayIsUnreachable { gateway_id: String, failures: usize },
+
+    #[error("dropped incomplete message set {set_id} ({received_fragments}/{total_fragments} fragments received): {reason}")]
+    IncompleteMessageDropped {
+        set_id: i32,
+        received_fragments: usize,
+        total_fragments: usize,
+        reason: String,
+    },
 }
@@ -61,4 +61,13 @@ pub enum StorageError {
     FailedToCreateStorage {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+
+    #[error("the stored data is encrypted, but no storage cipher has been provided")]
+    MissingStorageCipher,
+
+    #[error("failed to encrypt or decrypt the stored data: {source}")]
+    CipherFailure {
+        #[from]
+        source: nym_store_cipher::Error,
+    },
 }
@@ -12,8 +12,10 @@ use crate::{
 use async_trait::async_trait;
 use log::{error, info, warn};
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
+use nym_store_cipher::{is_encrypted_blob, StoreCipher};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use time::OffsetDateTime;
 
 pub use self::error::StorageError;
@@ -27,6 +29,9 @@ pub struct Backend {
     temporary_old_path: Option<PathBuf>,
     database_path: PathBuf,
     manager: StorageManager,
+
+    /// Optional cipher used for keeping the reply keys and reply surbs encrypted at rest.
+    cipher: Option<Arc<StoreCipher>>,
 }
 
 impl Backend {
@@ -47,6 +52,7 @@ impl Backend {
             temporary_old_path: None,
             database_path: owned_path,
             manager,
+            cipher: None,
         };
 
         Ok(backend)
@@ -125,9 +131,36 @@ impl Backend {
             database_path: owned_path,
             // manager: StorageManagerState::Storage(manager),
             manager,
+            cipher: None,
         })
     }
 
+    /// Makes the backend encrypt the reply keys and reply surbs it persists from now on.
+    /// Data stored before the cipher has been provided can still be read.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<StoreCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn seal(&self, data: &mut Vec<u8>) -> Result<(), StorageError> {
+        if let Some(cipher) = &self.cipher {
+            *data = cipher.encrypt_to_blob(data)?;
+        }
+        Ok(())
+    }
+
+    fn unseal(&self, data: &mut Vec<u8>) -> Result<(), StorageError> {
+        if !is_encrypted_blob(data) {
+            return Ok(());
+        }
+        let Some(cipher) = &self.cipher else {
+            return Err(StorageError::MissingStorageCipher);
+        };
+        *data = cipher.decrypt_blob(data)?;
+        Ok(())
+    }
+
     async fn close_pool(&mut self) {
         self.manager.connection_pool.close().await;
     }
@@ -214,7 +247,10 @@ impl Backend {
         // something weird has happened and we can't trust the rest of the data
         let raw = stored
             .into_iter()
-            .map(TryInto::try_into)
+            .map(|mut stored| -> Result<_, StorageError> {
+                self.unseal(&mut stored.reply_key)?;
+                stored.try_into()
+            })
             .collect::<Result<_, _>>()?;
 
         Ok(SentReplyKeys::from_raw(raw))
@@ -223,9 +259,9 @@ impl Backend {
     async fn dump_sender_reply_keys(&self, reply_keys: &SentReplyKeys) -> Result<(), StorageError> {
         for map_ref in reply_keys.as_raw_iter() {
             let (digest, key) = map_ref.pair();
-            self.manager
-                .insert_reply_key(StoredReplyKey::new(*digest, *key))
-                .await?;
+            let mut stored = StoredReplyKey::new(*digest, *key);
+            self.seal(&mut stored.reply_key)?;
+            self.manager.insert_reply_key(stored).await?;
         }
         Ok(())
     }
@@ -244,7 +280,10 @@ impl Backend {
                 .get_reply_surbs(sender_id)
                 .await?
                 .into_iter()
-                .map(|raw| raw.try_into())
+                .map(|mut raw| -> Result<_, StorageError> {
+                    self.unseal(&mut raw.reply_surb)?;
+                    raw.try_into()
+                })
                 .collect::<Result<_, _>>()?;
 
             received_surbs.push((
@@ -275,9 +314,9 @@ impl Backend {
                 .await?;
 
             for reply_surb in received_surbs.surbs_ref() {
-                self.manager
-                    .insert_reply_surb(StoredReplySurb::new(sender_id, reply_surb))
-                    .await?
+                let mut stored = StoredReplySurb::new(sender_id, reply_surb);
+                self.seal(&mut stored.reply_surb)?;
+                self.manager.insert_reply_surb(stored).await?
             }
         }
         Ok(())
@@ -7,6 +7,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use time::OffsetDateTime;
 
+/// Maximum amount of time we're willing to back off for when throttled by the gateway,
+/// regardless of what it has asked for.
+const MAX_THROTTLING_BACKOFF: Duration = Duration::from_secs(60);
+
 #[derive(Clone, Default)]
 pub struct ClientBandwidth {
     inner: Arc<ClientBandwidthInner>,
@@ -22,6 +26,9 @@ struct ClientBandwidthInner {
 
     /// defines the timestamp when the bandwidth value was last updated
     last_updated_ts: AtomicI64,
+
+    /// defines the timestamp (in milliseconds) until which the gateway has asked us to stop sending packets
+    throttled_until_ms: AtomicI64,
 }
 
 impl ClientBandwidth {
@@ -31,6 +38,7 @@ impl ClientBandwidth {
                 available: AtomicI64::new(0),
                 last_logged_ts: AtomicI64::new(0),
                 last_updated_ts: AtomicI64::new(0),
+                throttled_until_ms: AtomicI64::new(0),
             }),
         }
     }
@@ -81,9 +89,53 @@ impl ClientBandwidth {
         self.log_bandwidth(Some(now))
     }
 
+    pub(crate) fn mark_throttled(&self, retry_after: Duration) {
+        let now_ms = (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64;
+        let retry_after_ms = retry_after.min(MAX_THROTTLING_BACKOFF).as_millis() as i64;
+        let until_ms = now_ms.checked_add(retry_after_ms).unwrap_or(i64::MAX);
+        self.inner
+            .throttled_until_ms
+            .store(until_ms, Ordering::Release)
+    }
+
+    /// Returns the remaining amount of time the gateway has asked us to back off for, if any.
+    pub(crate) fn throttled_for(&self) -> Option<Duration> {
+        let until_ms = self.inner.throttled_until_ms.load(Ordering::Acquire);
+        let now_ms = (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64;
+        if until_ms > now_ms {
+            Some(Duration::from_millis((until_ms - now_ms) as u64))
+        } else {
+            None
+        }
+    }
+
     fn last_logged(&self) -> OffsetDateTime {
         // SAFETY: this value is always populated with valid timestamps
         OffsetDateTime::from_unix_timestamp(self.inner.last_logged_ts.load(Ordering::Relaxed))
             .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttling_duration_is_bounded() {
+        let bandwidth = ClientBandwidth::new_empty();
+        assert!(bandwidth.throttled_for().is_none());
+
+        bandwidth.mark_throttled(Duration::from_millis(500));
+        let throttled_for = bandwidth.throttled_for().unwrap();
+        assert!(throttled_for <= Duration::from_millis(500));
+
+        // the gateway might have sent us `u64::MAX` milliseconds
+        bandwidth.mark_throttled(Duration::from_millis(u64::MAX));
+        let throttled_for = bandwidth.throttled_for().unwrap();
+        assert!(throttled_for <= MAX_THROTTLING_BACKOFF);
+        assert!(throttled_for > MAX_THROTTLING_BACKOFF - Duration::from_secs(5));
+
+        bandwidth.mark_throttled(Duration::MAX);
+        assert!(bandwidth.throttled_for().unwrap() <= MAX_THROTTLING_BACKOFF);
+    }
+}
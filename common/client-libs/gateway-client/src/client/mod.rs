@@ -29,6 +29,7 @@ use nym_task::TaskClient;
 use nym_validator_client::nyxd::contract_traits::DkgQueryClient;
 use rand::rngs::OsRng;
 use std::sync::Arc;
+use std::time::Duration;
 use tungstenite::protocol::Message;
 use url::Url;
 
@@ -135,6 +136,11 @@ impl<C, St> GatewayClient<C, St> {
         self.bandwidth.remaining()
     }
 
+    /// Returns the remaining amount of time the gateway has asked us to stop sending packets for, if any.
+    pub fn throttled_for(&self) -> Option<Duration> {
+        self.bandwidth.throttled_for()
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     async fn _close_connection(&mut self) -> Result<(), GatewayClientError> {
         match std::mem::replace(&mut self.connection, SocketState::NotConnected) {
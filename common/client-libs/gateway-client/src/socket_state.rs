@@ -15,6 +15,7 @@ use nym_gateway_requests::{ServerResponse, SimpleGatewayRequestsError};
 use nym_task::TaskClient;
 use std::os::raw::c_int as RawFd;
 use std::sync::Arc;
+use std::time::Duration;
 use tungstenite::{protocol::Message, Error as WsError};
 
 use si_scale::helpers::bibytes2;
@@ -176,6 +177,12 @@ impl PartiallyDelegatedRouter {
                         // UNIMPLEMENTED: we should stop sending messages until we recover bandwidth
                         Ok(())
                     }
+                    SimpleGatewayRequestsError::Throttled { retry_after_ms } => {
+                        warn!("the gateway is throttling our packets. backing off for {retry_after_ms}ms");
+                        self.client_bandwidth
+                            .mark_throttled(Duration::from_millis(retry_after_ms));
+                        Ok(())
+                    }
                     _ => {
                         error!("[2] gateway failure: {error}");
                         Err(GatewayClientError::TypedGatewayError(error))
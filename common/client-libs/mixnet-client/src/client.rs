@@ -4,6 +4,7 @@
 use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
+use nym_noise::{upgrade_noise_initiator, Connection, NoiseConfig, NoiseError};
 use nym_sphinx::addressing::nodes::NymNodeRoutingAddress;
 use nym_sphinx::framing::codec::NymCodec;
 use nym_sphinx::framing::packet::FramedNymPacket;
@@ -25,6 +26,7 @@ pub struct Config {
     initial_connection_timeout: Duration,
     maximum_connection_buffer_size: usize,
     use_legacy_version: bool,
+    noise_config: Option<NoiseConfig>,
 }
 
 impl Config {
@@ -41,8 +43,16 @@ impl Config {
             initial_connection_timeout,
             maximum_connection_buffer_size,
             use_legacy_version,
+            noise_config: None,
         }
     }
+
+    /// Attempt to use noise for all connections to nodes whose keys are known.
+    #[must_use]
+    pub fn with_noise_config(mut self, noise_config: NoiseConfig) -> Self {
+        self.noise_config = Some(noise_config);
+        self
+    }
 }
 
 pub trait SendWithoutResponse {
@@ -83,28 +93,25 @@ impl Client {
         }
     }
 
-    async fn manage_connection(
+    async fn establish_connection(
         address: SocketAddr,
-        receiver: mpsc::Receiver<FramedNymPacket>,
         connection_timeout: Duration,
         current_reconnection: &AtomicU32,
-    ) {
+    ) -> Option<TcpStream> {
         let connection_fut = TcpStream::connect(address);
 
-        let conn = match tokio::time::timeout(connection_timeout, connection_fut).await {
+        match tokio::time::timeout(connection_timeout, connection_fut).await {
             Ok(stream_res) => match stream_res {
                 Ok(stream) => {
                     debug!("Managed to establish connection to {}", address);
-                    // if we managed to connect, reset the reconnection count (whatever it might have been)
-                    current_reconnection.store(0, Ordering::Release);
-                    Framed::new(stream, NymCodec)
+                    Some(stream)
                 }
                 Err(err) => {
                     debug!(
                         "failed to establish connection to {} (err: {})",
                         address, err
                     );
-                    return;
+                    None
                 }
             },
             Err(_) => {
@@ -115,10 +122,62 @@ impl Client {
 
                 // we failed to connect - increase reconnection attempt
                 current_reconnection.fetch_add(1, Ordering::SeqCst);
-                return;
+                None
+            }
+        }
+    }
+
+    async fn manage_connection(
+        address: SocketAddr,
+        receiver: mpsc::Receiver<FramedNymPacket>,
+        connection_timeout: Duration,
+        noise_config: Option<NoiseConfig>,
+        current_reconnection: &AtomicU32,
+    ) {
+        let Some(stream) =
+            Self::establish_connection(address, connection_timeout, current_reconnection).await
+        else {
+            return;
+        };
+
+        // note: we deliberately do not remember whether the remote supports noise between reconnections
+        // so that nodes that got upgraded in the meantime would start using it
+        let conn = match noise_config {
+            Some(noise_config) => {
+                match upgrade_noise_initiator(stream, &noise_config).await {
+                    Ok(conn) => conn,
+                    Err(NoiseError::RemoteUnsupported) => {
+                        // the remote has explicitly rejected the handshake, so it must be running
+                        // an older version that does not support noise. fallback to the plaintext connection
+                        debug!("{address} does not support noise. falling back to plaintext");
+
+                        let Some(stream) = Self::establish_connection(
+                            address,
+                            connection_timeout,
+                            current_reconnection,
+                        )
+                        .await
+                        else {
+                            return;
+                        };
+                        Connection::Raw(stream)
+                    }
+                    Err(err) => {
+                        // any other failure (timeout, reset, etc.) might have been transient, so don't
+                        // downgrade the connection and instead retry it with a backoff
+                        warn!("failed to establish noise session with {address}: {err}");
+                        current_reconnection.fetch_add(1, Ordering::SeqCst);
+                        return;
+                    }
+                }
             }
+            None => Connection::Raw(stream),
         };
 
+        // if we managed to connect, reset the reconnection count (whatever it might have been)
+        current_reconnection.store(0, Ordering::Release);
+        let conn = Framed::new(conn, NymCodec);
+
         // Take whatever the receiver channel produces and put it on the connection.
         // We could have as well used conn.send_all(receiver.map(Ok)), but considering we don't care
         // about neither receiver nor the connection, it doesn't matter which one gets consumed
@@ -172,8 +231,9 @@ impl Client {
         let reconnection_attempt = current_reconnection_attempt.load(Ordering::Acquire);
         let backoff = self.determine_backoff(reconnection_attempt);
 
-        // copy the value before moving into another task
+        // copy the values before moving into another task
         let initial_connection_timeout = self.config.initial_connection_timeout;
+        let noise_config = self.config.noise_config.clone();
 
         tokio::spawn(async move {
             // before executing the manager, wait for what was specified, if anything
@@ -186,6 +246,7 @@ impl Client {
                 address.into(),
                 receiver,
                 initial_connection_timeout,
+                noise_config,
                 &current_reconnection_attempt,
             )
             .await
@@ -261,6 +322,7 @@ mod tests {
             initial_connection_timeout: Duration::from_millis(1_500),
             maximum_connection_buffer_size: 128,
             use_legacy_version: false,
+            noise_config: None,
         })
     }
 
@@ -10,8 +10,9 @@ use crate::{
 };
 use nym_api_requests::ecash::models::{
     AggregatedCoinIndicesSignatureResponse, AggregatedExpirationDateSignatureResponse,
-    BatchRedeemTicketsBody, EcashBatchTicketRedemptionResponse, EcashTicketVerificationResponse,
-    SpentCredentialsResponse, VerifyEcashTicketBody,
+    BatchRedeemTicketsBody, DepositRefundRequestBody, EcashBatchTicketRedemptionResponse,
+    EcashDepositRefundResponse, EcashTicketVerificationResponse, SpentCredentialsResponse,
+    VerifyEcashTicketBody,
 };
 use nym_api_requests::ecash::{
     BlindSignRequestBody, BlindedSignatureResponse, PartialCoinIndicesSignatureResponse,
@@ -398,6 +399,13 @@ impl NymApiClient {
             .await?)
     }
 
+    pub async fn request_deposit_refund(
+        &self,
+        request_body: &DepositRefundRequestBody,
+    ) -> Result<EcashDepositRefundResponse, ValidatorClientError> {
+        Ok(self.nym_api.request_deposit_refund(request_body).await?)
+    }
+
     pub async fn spent_credentials_filter(
         &self,
     ) -> Result<SpentCredentialsResponse, ValidatorClientError> {
@@ -6,8 +6,8 @@ use crate::nym_api::routes::{ecash, CORE_STATUS_COUNT, SINCE_ARG};
 use async_trait::async_trait;
 use nym_api_requests::ecash::models::{
     AggregatedCoinIndicesSignatureResponse, AggregatedExpirationDateSignatureResponse,
-    BatchRedeemTicketsBody, EcashBatchTicketRedemptionResponse, EcashTicketVerificationResponse,
-    VerifyEcashTicketBody,
+    BatchRedeemTicketsBody, DepositRefundRequestBody, EcashBatchTicketRedemptionResponse,
+    EcashDepositRefundResponse, EcashTicketVerificationResponse, VerifyEcashTicketBody,
 };
 use nym_api_requests::nym_nodes::{CachedNodesResponse, SkimmedNode};
 use nym_http_api_client::{ApiClient, NO_PARAMS};
@@ -479,6 +479,22 @@ pub trait NymApiClientExt: ApiClient {
         .await
     }
 
+    async fn request_deposit_refund(
+        &self,
+        request_body: &DepositRefundRequestBody,
+    ) -> Result<EcashDepositRefundResponse, NymAPIError> {
+        self.post_json(
+            &[
+                routes::API_VERSION,
+                routes::ECASH_ROUTES,
+                routes::ECASH_DEPOSIT_REFUND,
+            ],
+            NO_PARAMS,
+            request_body,
+        )
+        .await
+    }
+
     async fn double_spending_filter_v1(&self) -> Result<SpentCredentialsResponse, NymAPIError> {
         self.get_json(
             &[
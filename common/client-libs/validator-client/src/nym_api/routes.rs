@@ -21,6 +21,7 @@ pub mod ecash {
     pub const ECASH_BLIND_SIGN: &str = "blind-sign";
     pub const VERIFY_ECASH_TICKET: &str = "verify-ecash-ticket";
     pub const BATCH_REDEEM_ECASH_TICKETS: &str = "batch-redeem-ecash-tickets";
+    pub const ECASH_DEPOSIT_REFUND: &str = "deposit-refund";
     pub const PARTIAL_EXPIRATION_DATE_SIGNATURES: &str = "partial-expiration-date-signatures";
     pub const GLOBAL_EXPIRATION_DATE_SIGNATURES: &str = "aggregated-expiration-date-signatures";
     pub const PARTIAL_COIN_INDICES_SIGNATURES: &str = "partial-coin-indices-signatures";
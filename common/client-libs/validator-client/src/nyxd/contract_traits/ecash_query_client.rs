@@ -14,7 +14,8 @@ pub use nym_ecash_contract_common::blacklist::{
     BlacklistedAccount, BlacklistedAccountResponse, PagedBlacklistedAccountResponse,
 };
 pub use nym_ecash_contract_common::deposit::{
-    Deposit, DepositData, DepositId, DepositResponse, PagedDepositsResponse,
+    Deposit, DepositData, DepositId, DepositRefund, DepositRefundResponse, DepositResponse,
+    PagedDepositsResponse,
 };
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -59,6 +60,14 @@ pub trait EcashQueryClient {
         self.query_ecash_contract(EcashQueryMsg::GetDepositsPaged { start_after, limit })
             .await
     }
+
+    async fn get_deposit_refund(
+        &self,
+        deposit_id: u32,
+    ) -> Result<DepositRefundResponse, NyxdError> {
+        self.query_ecash_contract(EcashQueryMsg::GetDepositRefund { deposit_id })
+            .await
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -118,6 +127,9 @@ mod tests {
                 client.get_deposits_paged(start_after, limit).ignore()
             }
             QueryMsg::GetRequiredDepositAmount {} => client.get_required_deposit_amount().ignore(),
+            QueryMsg::GetDepositRefund { deposit_id } => {
+                client.get_deposit_refund(deposit_id).ignore()
+            }
         };
     }
 }
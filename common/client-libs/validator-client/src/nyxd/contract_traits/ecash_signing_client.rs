@@ -47,6 +47,20 @@ pub trait EcashSigningClient {
             .await
     }
 
+    async fn request_deposit_refund(
+        &self,
+        deposit_id: u32,
+        signature_bs58: String,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        let req = EcashExecuteMsg::RequestDepositRefund {
+            deposit_id,
+            signature_bs58,
+        };
+        self.execute_ecash_contract(fee, req, "Ecash::RequestDepositRefund".to_string(), vec![])
+            .await
+    }
+
     async fn update_admin(
         &self,
         admin: String,
@@ -140,6 +154,13 @@ mod tests {
                 .request_ticket_redemption(commitment_bs58, number_of_tickets, None)
                 .ignore(),
             ExecuteMsg::RedeemTickets { .. } => unimplemented!(), // no redeem tickets method for the client
+            ExecuteMsg::RequestDepositRefund {
+                deposit_id,
+                signature_bs58,
+            } => client
+                .request_deposit_refund(deposit_id, signature_bs58, None)
+                .ignore(),
+            ExecuteMsg::RefundDeposit { .. } => unimplemented!(), // no refund deposit method for the client
             ExecuteMsg::UpdateAdmin { admin } => client.update_admin(admin, None).ignore(),
             ExecuteMsg::UpdateDepositValue { new_deposit } => client
                 .update_deposit_value(new_deposit.into(), None)
@@ -12,6 +12,10 @@ use nym_mixnet_contract_common::{
     delegation,
     delegation::{MixNodeDelegationResponse, OwnerProxySubKey},
     families::{Family, FamilyHead},
+    gateway::{
+        GatewayDelegation, GatewayRewardingDetailsResponse, GatewayRewardingParams,
+        PagedGatewayDelegationsResponse,
+    },
     mixnode::{
         MixnodeRewardingDetailsResponse, PagedMixnodesDetailsResponse,
         PagedUnbondedMixnodesResponse, StakeSaturationResponse, UnbondedMixnodeResponse,
@@ -277,8 +281,48 @@ pub trait MixnetQueryClient {
         .await
     }
 
+    async fn get_gateway_rewarding_params(&self) -> Result<GatewayRewardingParams, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetGatewayRewardingParams {})
+            .await
+    }
+
+    async fn get_gateway_rewarding_details(
+        &self,
+        identity: IdentityKey,
+    ) -> Result<GatewayRewardingDetailsResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetGatewayRewardingDetails { identity })
+            .await
+    }
+
     // delegation-related:
 
+    /// Gets list of all delegations towards particular gateway on particular page.
+    async fn get_gateway_delegations_paged(
+        &self,
+        identity: IdentityKeyRef<'_>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<PagedGatewayDelegationsResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetGatewayDelegations {
+            identity: identity.to_string(),
+            start_after,
+            limit,
+        })
+        .await
+    }
+
+    async fn get_pending_gateway_delegator_reward(
+        &self,
+        delegator: &AccountId,
+        identity: IdentityKey,
+    ) -> Result<PendingRewardResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetPendingGatewayDelegatorReward {
+            address: delegator.to_string(),
+            identity,
+        })
+        .await
+    }
+
     /// Gets list of all delegations towards particular mixnode on particular page.
     async fn get_mixnode_delegations_paged(
         &self,
@@ -513,6 +557,13 @@ pub trait PagedMixnetQueryClient: MixnetQueryClient {
         collect_paged!(self, get_gateways_paged, nodes)
     }
 
+    async fn get_all_gateway_delegations(
+        &self,
+        identity: IdentityKeyRef<'_>,
+    ) -> Result<Vec<GatewayDelegation>, NyxdError> {
+        collect_paged!(self, get_gateway_delegations_paged, delegations, identity)
+    }
+
     async fn get_all_single_mixnode_delegations(
         &self,
         mix_id: MixId,
@@ -666,6 +717,22 @@ mod tests {
             MixnetQueryMsg::GetOwnedGateway { address } => {
                 client.get_owned_gateway(&address.parse().unwrap()).ignore()
             }
+            MixnetQueryMsg::GetGatewayRewardingParams {} => {
+                client.get_gateway_rewarding_params().ignore()
+            }
+            MixnetQueryMsg::GetGatewayRewardingDetails { identity } => {
+                client.get_gateway_rewarding_details(identity).ignore()
+            }
+            MixnetQueryMsg::GetGatewayDelegations {
+                identity,
+                start_after,
+                limit,
+            } => client
+                .get_gateway_delegations_paged(&identity, start_after, limit)
+                .ignore(),
+            MixnetQueryMsg::GetPendingGatewayDelegatorReward { address, identity } => client
+                .get_pending_gateway_delegator_reward(&address.parse().unwrap(), identity)
+                .ignore(),
             MixnetQueryMsg::GetMixnodeDelegations {
                 mix_id,
                 start_after,
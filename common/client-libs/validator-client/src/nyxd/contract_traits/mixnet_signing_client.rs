@@ -11,12 +11,12 @@ use async_trait::async_trait;
 use cosmrs::AccountId;
 use nym_contracts_common::signing::MessageSignature;
 use nym_mixnet_contract_common::families::FamilyHead;
-use nym_mixnet_contract_common::gateway::GatewayConfigUpdate;
+use nym_mixnet_contract_common::gateway::{GatewayConfigUpdate, GatewayRewardingParams};
 use nym_mixnet_contract_common::mixnode::{MixNodeConfigUpdate, MixNodeCostParams};
 use nym_mixnet_contract_common::reward_params::{IntervalRewardingParamsUpdate, Performance};
 use nym_mixnet_contract_common::{
-    ContractStateParams, ExecuteMsg as MixnetExecuteMsg, Gateway, Layer, LayerAssignment, MixId,
-    MixNode,
+    ContractStateParams, ExecuteMsg as MixnetExecuteMsg, Gateway, IdentityKey, Layer,
+    LayerAssignment, MixId, MixNode,
 };
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -93,6 +93,19 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn update_gateway_rewarding_parameters(
+        &self,
+        updated_params: GatewayRewardingParams,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::UpdateGatewayRewardingParams { updated_params },
+            vec![],
+        )
+        .await
+    }
+
     async fn update_interval_config(
         &self,
         epochs_in_interval: u32,
@@ -550,6 +563,19 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn update_gateway_cost_params(
+        &self,
+        new_costs: MixNodeCostParams,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::UpdateGatewayCostParams { new_costs },
+            vec![],
+        )
+        .await
+    }
+
     // delegation-related:
 
     async fn delegate_to_mixnode(
@@ -614,6 +640,73 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn redelegate_from_mixnode(
+        &self,
+        from: MixId,
+        to: MixId,
+        amount: Coin,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::RedelegateFromMixnode {
+                from,
+                to,
+                amount: amount.into(),
+            },
+            vec![],
+        )
+        .await
+    }
+
+    async fn redelegate_from_mixnode_on_behalf(
+        &self,
+        delegate: AccountId,
+        from: MixId,
+        to: MixId,
+        amount: Coin,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::RedelegateFromMixnodeOnBehalf {
+                from,
+                to,
+                amount: amount.into(),
+                delegate: delegate.to_string(),
+            },
+            vec![],
+        )
+        .await
+    }
+
+    async fn delegate_to_gateway(
+        &self,
+        identity: IdentityKey,
+        amount: Coin,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::DelegateToGateway { identity },
+            vec![amount],
+        )
+        .await
+    }
+
+    async fn undelegate_from_gateway(
+        &self,
+        identity: IdentityKey,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::UndelegateFromGateway { identity },
+            vec![],
+        )
+        .await
+    }
+
     // reward-related
 
     async fn reward_mixnode(
@@ -633,6 +726,23 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn reward_gateway(
+        &self,
+        identity: IdentityKey,
+        performance: Performance,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::RewardGateway {
+                identity,
+                performance,
+            },
+            vec![],
+        )
+        .await
+    }
+
     async fn withdraw_operator_reward(&self, fee: Option<Fee>) -> Result<ExecuteResult, NyxdError> {
         self.execute_mixnet_contract(fee, MixnetExecuteMsg::WithdrawOperatorReward {}, vec![])
             .await
@@ -683,6 +793,20 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn set_delegation_auto_compounding(
+        &self,
+        mix_id: MixId,
+        enabled: bool,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::SetDelegationAutoCompounding { mix_id, enabled },
+            vec![],
+        )
+        .await
+    }
+
     async fn migrate_vested_mixnode(&self, fee: Option<Fee>) -> Result<ExecuteResult, NyxdError> {
         self.execute_mixnet_contract(fee, MixnetExecuteMsg::MigrateVestedMixNode {}, vec![])
             .await
@@ -817,6 +941,9 @@ mod tests {
             } => client
                 .update_rewarding_parameters(updated_params, force_immediately, None)
                 .ignore(),
+            MixnetExecuteMsg::UpdateGatewayRewardingParams { updated_params } => client
+                .update_gateway_rewarding_parameters(updated_params, None)
+                .ignore(),
             MixnetExecuteMsg::UpdateIntervalConfig {
                 epochs_in_interval,
                 epoch_duration_secs,
@@ -918,6 +1045,9 @@ mod tests {
             MixnetExecuteMsg::UpdateGatewayConfigOnBehalf { new_config, owner } => client
                 .update_gateway_config_on_behalf(owner.parse().unwrap(), new_config, None)
                 .ignore(),
+            MixnetExecuteMsg::UpdateGatewayCostParams { new_costs } => {
+                client.update_gateway_cost_params(new_costs, None).ignore()
+            }
             MixnetExecuteMsg::DelegateToMixnode { mix_id } => client
                 .delegate_to_mixnode(mix_id, mock_coin(), None)
                 .ignore(),
@@ -930,10 +1060,37 @@ mod tests {
             MixnetExecuteMsg::UndelegateFromMixnodeOnBehalf { mix_id, delegate } => client
                 .undelegate_to_mixnode_on_behalf(delegate.parse().unwrap(), mix_id, None)
                 .ignore(),
+            MixnetExecuteMsg::RedelegateFromMixnode { from, to, amount } => client
+                .redelegate_from_mixnode(from, to, amount.into(), None)
+                .ignore(),
+            MixnetExecuteMsg::RedelegateFromMixnodeOnBehalf {
+                from,
+                to,
+                amount,
+                delegate,
+            } => client
+                .redelegate_from_mixnode_on_behalf(
+                    delegate.parse().unwrap(),
+                    from,
+                    to,
+                    amount.into(),
+                    None,
+                )
+                .ignore(),
+            MixnetExecuteMsg::DelegateToGateway { identity } => client
+                .delegate_to_gateway(identity, mock_coin(), None)
+                .ignore(),
+            MixnetExecuteMsg::UndelegateFromGateway { identity } => {
+                client.undelegate_from_gateway(identity, None).ignore()
+            }
             MixnetExecuteMsg::RewardMixnode {
                 mix_id,
                 performance,
             } => client.reward_mixnode(mix_id, performance, None).ignore(),
+            MixnetExecuteMsg::RewardGateway {
+                identity,
+                performance,
+            } => client.reward_gateway(identity, performance, None).ignore(),
             MixnetExecuteMsg::WithdrawOperatorReward {} => {
                 client.withdraw_operator_reward(None).ignore()
             }
@@ -946,6 +1103,9 @@ mod tests {
             MixnetExecuteMsg::WithdrawDelegatorRewardOnBehalf { mix_id, owner } => client
                 .withdraw_delegator_reward_on_behalf(owner.parse().unwrap(), mix_id, None)
                 .ignore(),
+            MixnetExecuteMsg::SetDelegationAutoCompounding { mix_id, enabled } => client
+                .set_delegation_auto_compounding(mix_id, enabled, None)
+                .ignore(),
             MixnetExecuteMsg::MigrateVestedMixNode { .. } => {
                 client.migrate_vested_mixnode(None).ignore()
             }
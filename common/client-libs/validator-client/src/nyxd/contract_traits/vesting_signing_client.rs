@@ -285,6 +285,7 @@ pub trait VestingSigningClient {
             VestingExecuteMsg::TrackReward {
                 amount: amount.into(),
                 address,
+                compounded_into: None,
             },
             Vec::new(),
         )
@@ -461,7 +462,9 @@ mod tests {
             VestingExecuteMsg::KickFamilyMember { member } => {
                 client.vesting_kick_family_member(member, None).ignore()
             }
-            VestingExecuteMsg::TrackReward { amount, address } => client
+            VestingExecuteMsg::TrackReward {
+                amount, address, ..
+            } => client
                 .vesting_track_reward(amount.into(), address, None)
                 .ignore(),
             VestingExecuteMsg::ClaimOperatorReward {} => {
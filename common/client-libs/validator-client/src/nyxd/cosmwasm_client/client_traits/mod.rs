@@ -5,4 +5,4 @@ pub mod query_client;
 pub mod signing_client;
 
 pub use query_client::CosmWasmClient;
-pub use signing_client::SigningCosmWasmClient;
+pub use signing_client::{was_exported, SigningCosmWasmClient};
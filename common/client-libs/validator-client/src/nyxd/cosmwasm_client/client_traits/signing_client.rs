@@ -10,8 +10,9 @@ use crate::nyxd::cosmwasm_client::types::*;
 use crate::nyxd::error::NyxdError;
 use crate::nyxd::fee::{Fee, DEFAULT_SIMULATED_GAS_MULTIPLIER};
 use crate::nyxd::helpers::find_tx_attribute;
-use crate::nyxd::{Coin, GasAdjustable, GasPrice, TxResponse};
-use crate::signing::signer::OfflineSigner;
+use crate::nyxd::{Coin, ExecTxResult, GasAdjustable, GasPrice, Hash, TxResponse};
+use crate::signing::offline_transaction::UnsignedTransaction;
+use crate::signing::signer::{OfflineSigner, SignerType};
 use crate::signing::tx_signer::TxSigner;
 use crate::signing::SignerData;
 use async_trait::async_trait;
@@ -32,6 +33,12 @@ use sha2::Sha256;
 use std::time::SystemTime;
 use tendermint_rpc::endpoint::broadcast;
 
+/// Checks whether the transaction behind the response has only been exported for offline signing
+/// (by a generate-only signer) rather than actually broadcast to the chain.
+pub fn was_exported(response: &TxResponse) -> bool {
+    response.hash == Hash::None
+}
+
 fn empty_fee() -> tx::Fee {
     tx::Fee {
         amount: vec![],
@@ -678,6 +685,12 @@ where
             .determine_transaction_fee(signer_address, &messages, fee, &memo)
             .await?;
 
+        if self.is_generate_only() {
+            return self
+                .export_unsigned_transaction(signer_address, messages, fee, memo)
+                .await;
+        }
+
         let tx_raw = self.sign(signer_address, messages, fee, memo, None).await?;
         let tx_bytes = tx_raw
             .to_bytes()
@@ -686,6 +699,43 @@ where
         self.broadcast_tx(tx_bytes, None, None).await
     }
 
+    /// Rather than signing and broadcasting the transaction, export it via the generate-only signer
+    /// so that it could be signed offline.
+    /// The returned response does not correspond to any on-chain transaction and thus has no hash,
+    /// see [`was_exported`].
+    async fn export_unsigned_transaction(
+        &self,
+        signer_address: &AccountId,
+        messages: Vec<Any>,
+        fee: tx::Fee,
+        memo: String,
+    ) -> Result<TxResponse, NyxdError> {
+        let sequence_response = self.get_sequence(signer_address).await?;
+        let chain_id = self.get_chain_id().await?;
+        let signer_data = SignerData::new_from_sequence_response(sequence_response, chain_id);
+
+        let unsigned = UnsignedTransaction::new(signer_address, messages, fee, memo, signer_data)?;
+        self.export_unsigned(unsigned)?;
+
+        Ok(TxResponse {
+            hash: Hash::None,
+            height: Default::default(),
+            index: 0,
+            tx_result: ExecTxResult {
+                code: Default::default(),
+                data: Default::default(),
+                log: Default::default(),
+                info: Default::default(),
+                gas_wanted: Default::default(),
+                gas_used: Default::default(),
+                events: vec![],
+                codespace: Default::default(),
+            },
+            tx: vec![],
+            proof: None,
+        })
+    }
+
     async fn sign(
         &self,
         signer_address: &AccountId,
@@ -706,13 +756,23 @@ where
             }
         };
 
-        Ok(<Self as TxSigner>::sign_direct(
-            self,
-            signer_address,
-            messages,
-            fee,
-            memo,
-            signer_data,
-        )?)
+        match self.preferred_signer_type() {
+            SignerType::Amino => Ok(<Self as TxSigner>::sign_amino(
+                self,
+                signer_address,
+                messages,
+                fee,
+                memo,
+                signer_data,
+            )?),
+            SignerType::Direct => Ok(<Self as TxSigner>::sign_direct(
+                self,
+                signer_address,
+                messages,
+                fee,
+                memo,
+                signer_data,
+            )?),
+        }
     }
 }
@@ -11,12 +11,16 @@ use crate::nyxd::error::NyxdError;
 use crate::nyxd::{Config, GasPrice, Hash, Height};
 use crate::rpc::TendermintRpcClient;
 use crate::signing::{
-    signer::{NoSigner, OfflineSigner},
+    amino::AminoSignDoc,
+    offline_transaction::UnsignedTransaction,
+    signer::{NoSigner, OfflineSigner, Signature, SignerType},
     AccountData,
 };
 use async_trait::async_trait;
+use cosmrs::crypto::PublicKey;
 use cosmrs::tendermint::{abci, evidence::Evidence, Genesis};
 use cosmrs::tx::{Raw, SignDoc};
+use cosmrs::AccountId;
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 use tendermint_rpc::endpoint::*;
@@ -324,10 +328,30 @@ where
 {
     type Error = S::Error;
 
+    fn signer_addresses(&self) -> Result<Vec<AccountId>, Self::Error> {
+        self.signer.signer_addresses()
+    }
+
     fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error> {
         self.signer.get_accounts()
     }
 
+    fn preferred_signer_type(&self) -> SignerType {
+        self.signer.preferred_signer_type()
+    }
+
+    fn account_public_key(&self, signer_address: &AccountId) -> Result<PublicKey, Self::Error> {
+        self.signer.account_public_key(signer_address)
+    }
+
+    fn sign_amino_doc(
+        &self,
+        signer_address: &AccountId,
+        sign_doc: &AminoSignDoc,
+    ) -> Result<Signature, Self::Error> {
+        self.signer.sign_amino_doc(signer_address, sign_doc)
+    }
+
     fn sign_direct_with_account(
         &self,
         signer: &AccountData,
@@ -335,6 +359,14 @@ where
     ) -> Result<Raw, Self::Error> {
         self.signer.sign_direct_with_account(signer, sign_doc)
     }
+
+    fn is_generate_only(&self) -> bool {
+        self.signer.is_generate_only()
+    }
+
+    fn export_unsigned(&self, transaction: UnsignedTransaction) -> Result<(), Self::Error> {
+        self.signer.export_unsigned(transaction)
+    }
 }
 
 #[async_trait]
@@ -3,6 +3,9 @@
 
 use crate::nyxd::cosmwasm_client::types::ContractCodeId;
 use crate::signing::direct_wallet::DirectSecp256k1HdWalletError;
+#[cfg(feature = "ledger")]
+use crate::signing::ledger::LedgerSignerError;
+use crate::signing::signer::SigningError;
 use cosmrs::tendermint::Hash;
 use cosmrs::{
     tendermint::{abci::Code as AbciCode, block},
@@ -26,6 +29,13 @@ pub enum NyxdError {
     #[error(transparent)]
     WalletError(#[from] DirectSecp256k1HdWalletError),
 
+    #[error(transparent)]
+    SigningError(#[from] SigningError),
+
+    #[cfg(feature = "ledger")]
+    #[error(transparent)]
+    LedgerSignerError(#[from] LedgerSignerError),
+
     #[error("There was an issue on the cosmrs side: {0}")]
     CosmrsError(#[from] cosmrs::Error),
 
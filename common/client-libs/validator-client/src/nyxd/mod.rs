@@ -14,9 +14,12 @@ use crate::nyxd::cosmwasm_client::types::{
 use crate::nyxd::cosmwasm_client::MaybeSigningClient;
 use crate::nyxd::error::NyxdError;
 use crate::nyxd::fee::DEFAULT_SIMULATED_GAS_MULTIPLIER;
+use crate::signing::amino::AminoSignDoc;
 use crate::signing::direct_wallet::DirectSecp256k1HdWallet;
+use crate::signing::offline_transaction::UnsignedTransaction;
 use crate::signing::signer::NoSigner;
 use crate::signing::signer::OfflineSigner;
+use crate::signing::signer::{Signature, SignerType};
 use crate::signing::tx_signer::TxSigner;
 use crate::signing::AccountData;
 use crate::{DirectSigningReqwestRpcNyxdClient, QueryReqwestRpcNyxdClient, ReqwestRpcClient};
@@ -844,10 +847,30 @@ where
 {
     type Error = S::Error;
 
+    fn signer_addresses(&self) -> Result<Vec<AccountId>, Self::Error> {
+        self.client.signer_addresses()
+    }
+
     fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error> {
         self.client.get_accounts()
     }
 
+    fn preferred_signer_type(&self) -> SignerType {
+        self.client.preferred_signer_type()
+    }
+
+    fn account_public_key(&self, signer_address: &AccountId) -> Result<PublicKey, Self::Error> {
+        self.client.account_public_key(signer_address)
+    }
+
+    fn sign_amino_doc(
+        &self,
+        signer_address: &AccountId,
+        sign_doc: &AminoSignDoc,
+    ) -> Result<Signature, Self::Error> {
+        self.client.sign_amino_doc(signer_address, sign_doc)
+    }
+
     fn sign_direct_with_account(
         &self,
         signer: &AccountData,
@@ -855,6 +878,14 @@ where
     ) -> Result<Raw, Self::Error> {
         self.client.sign_direct_with_account(signer, sign_doc)
     }
+
+    fn is_generate_only(&self) -> bool {
+        self.client.is_generate_only()
+    }
+
+    fn export_unsigned(&self, transaction: UnsignedTransaction) -> Result<(), Self::Error> {
+        self.client.export_unsigned(transaction)
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,233 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for the legacy amino JSON signing mode (`SIGN_MODE_LEGACY_AMINO_JSON`).
+//! It's the only mode understood by hardware wallets, such as Ledger, as they have to be able
+//! to display the content of the transaction to the user before signing it.
+
+use crate::signing::signer::SigningError;
+use crate::signing::SignerData;
+use cosmrs::bank::MsgSend;
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::tx::{self, Msg};
+use cosmrs::{Any, Coin};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+pub const BANK_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const WASM_EXECUTE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+
+/// Amino JSON representation of a single transaction message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub value: Value,
+}
+
+impl AminoMsg {
+    /// Attempts to convert the protobuf encoded message into its amino JSON representation.
+    /// Note that only the messages that can be sent by our clients are supported.
+    pub fn try_from_any(msg: &Any) -> Result<Self, SigningError> {
+        match msg.type_url.as_str() {
+            BANK_SEND_TYPE_URL => {
+                let send = MsgSend::from_any(msg)
+                    .map_err(|source| SigningError::AminoEncodingFailure { source })?;
+                Ok(AminoMsg {
+                    typ: "cosmos-sdk/MsgSend".to_string(),
+                    value: json!({
+                        "from_address": send.from_address.to_string(),
+                        "to_address": send.to_address.to_string(),
+                        "amount": coins_json(&send.amount),
+                    }),
+                })
+            }
+            WASM_EXECUTE_CONTRACT_TYPE_URL => {
+                let execute = MsgExecuteContract::from_any(msg)
+                    .map_err(|source| SigningError::AminoEncodingFailure { source })?;
+                let contract_msg: Value = serde_json::from_slice(&execute.msg)
+                    .map_err(|err| SigningError::AminoEncodingFailure { source: err.into() })?;
+                Ok(AminoMsg {
+                    typ: "wasm/MsgExecuteContract".to_string(),
+                    value: json!({
+                        "sender": execute.sender.to_string(),
+                        "contract": execute.contract.to_string(),
+                        "msg": contract_msg,
+                        "funds": coins_json(&execute.funds),
+                    }),
+                })
+            }
+            other => Err(SigningError::UnsupportedAminoMessage {
+                type_url: other.to_string(),
+            }),
+        }
+    }
+}
+
+/// Amino JSON equivalent of the cosmos-sdk `StdSignDoc`.
+#[derive(Debug, Clone)]
+pub struct AminoSignDoc {
+    pub account_number: u64,
+    pub chain_id: String,
+    pub fee: tx::Fee,
+    pub memo: String,
+    pub msgs: Vec<AminoMsg>,
+    pub sequence: u64,
+}
+
+impl AminoSignDoc {
+    pub fn new(
+        messages: &[Any],
+        fee: tx::Fee,
+        memo: impl Into<String>,
+        signer_data: &SignerData,
+    ) -> Result<Self, SigningError> {
+        let msgs = messages
+            .iter()
+            .map(AminoMsg::try_from_any)
+            .collect::<Result<_, _>>()?;
+
+        Ok(AminoSignDoc {
+            account_number: signer_data.account_number,
+            chain_id: signer_data.chain_id.to_string(),
+            fee,
+            memo: memo.into(),
+            msgs,
+            sequence: signer_data.sequence,
+        })
+    }
+
+    fn fee_json(&self) -> Value {
+        let mut fee = Map::new();
+        fee.insert("amount".to_string(), coins_json(&self.fee.amount));
+        fee.insert(
+            "gas".to_string(),
+            Value::String(self.fee.gas_limit.to_string()),
+        );
+        if let Some(payer) = &self.fee.payer {
+            fee.insert("payer".to_string(), Value::String(payer.to_string()));
+        }
+        if let Some(granter) = &self.fee.granter {
+            fee.insert("granter".to_string(), Value::String(granter.to_string()));
+        }
+        Value::Object(fee)
+    }
+
+    /// Produces the canonical JSON representation of the sign doc, i.e. with all the keys sorted
+    /// and with the same escaping as the one used by the chain when verifying the signature.
+    pub fn to_canonical_json(&self) -> String {
+        let doc = json!({
+            "account_number": self.account_number.to_string(),
+            "chain_id": self.chain_id,
+            "fee": self.fee_json(),
+            "memo": self.memo,
+            "msgs": self.msgs,
+            "sequence": self.sequence.to_string(),
+        });
+
+        // go's `encoding/json` escapes html characters by default
+        sorted(doc)
+            .to_string()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026")
+    }
+
+    pub fn to_sign_bytes(&self) -> Vec<u8> {
+        self.to_canonical_json().into_bytes()
+    }
+}
+
+fn coins_json(coins: &[Coin]) -> Value {
+    Value::Array(
+        coins
+            .iter()
+            .map(|coin| {
+                json!({
+                    "amount": coin.amount.to_string(),
+                    "denom": coin.denom.to_string(),
+                })
+            })
+            .collect(),
+    )
+}
+
+// recursively sort all the keys regardless of whether `serde_json/preserve_order` is enabled
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sorted(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmrs::AccountId;
+
+    fn test_signer_data() -> SignerData {
+        SignerData::new(42, 7, "nyx".parse().unwrap())
+    }
+
+    #[test]
+    fn sign_doc_is_canonical() {
+        let from: AccountId = "n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf".parse().unwrap();
+        let to: AccountId = "n1h5hgn94nsq4kh99rjj794hr5h5q6yfm2lr52es".parse().unwrap();
+        let send = MsgSend {
+            from_address: from,
+            to_address: to,
+            amount: vec![Coin::new(1000, "unym").unwrap()],
+        }
+        .to_any()
+        .unwrap();
+        let fee = tx::Fee::from_amount_and_gas(Coin::new(5000, "unym").unwrap(), 200000u64);
+
+        let doc = AminoSignDoc::new(&[send], fee, "<memo>", &test_signer_data()).unwrap();
+        let expected = concat!(
+            r#"{"account_number":"42","chain_id":"nyx","fee":{"amount":[{"amount":"5000","denom":"unym"}],"gas":"200000"},"#,
+            r#""memo":"\u003cmemo\u003e","msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"1000","denom":"unym"}],"#,
+            r#""from_address":"n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf","to_address":"n1h5hgn94nsq4kh99rjj794hr5h5q6yfm2lr52es"}}],"sequence":"7"}"#
+        );
+        assert_eq!(doc.to_canonical_json(), expected);
+    }
+
+    #[test]
+    fn contract_messages_are_sorted() {
+        let execute = MsgExecuteContract {
+            sender: "n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf".parse().unwrap(),
+            contract: "n1h5hgn94nsq4kh99rjj794hr5h5q6yfm2lr52es".parse().unwrap(),
+            msg: br#"{"delegate_to_mixnode":{"mix_id":42,"amount":"1"}}"#.to_vec(),
+            funds: vec![],
+        }
+        .to_any()
+        .unwrap();
+
+        let amino = AminoMsg::try_from_any(&execute).unwrap();
+        assert_eq!(
+            sorted(amino.value).to_string(),
+            r#"{"contract":"n1h5hgn94nsq4kh99rjj794hr5h5q6yfm2lr52es","funds":[],"msg":{"delegate_to_mixnode":{"amount":"1","mix_id":42}},"sender":"n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf"}"#
+        );
+    }
+
+    #[test]
+    fn unsupported_messages_are_rejected() {
+        let msg = Any {
+            type_url: "/cosmos.gov.v1beta1.MsgVote".to_string(),
+            value: vec![],
+        };
+        assert!(matches!(
+            AminoMsg::try_from_any(&msg),
+            Err(SigningError::UnsupportedAminoMessage { .. })
+        ))
+    }
+}
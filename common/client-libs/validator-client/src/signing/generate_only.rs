@@ -0,0 +1,119 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::signing::offline_transaction::UnsignedTransaction;
+use crate::signing::signer::{OfflineSigner, SigningError};
+use crate::signing::AccountData;
+use cosmrs::AccountId;
+use std::sync::{Arc, Mutex};
+
+/// Signer that only knows the address of the account and has no access to any of its keys.
+/// Rather than signing transactions, it collects them in their unsigned form so that they could be
+/// signed on a different machine.
+#[derive(Debug, Clone)]
+pub struct GenerateOnlySigner {
+    address: AccountId,
+
+    // shared so that the transactions could be retrieved after the signer has been moved into the client
+    exported: Arc<Mutex<Vec<UnsignedTransaction>>>,
+}
+
+impl GenerateOnlySigner {
+    pub fn new(address: AccountId) -> Self {
+        GenerateOnlySigner {
+            address,
+            exported: Default::default(),
+        }
+    }
+
+    /// Retrieves all transactions exported so far, clearing the internal buffer.
+    pub fn take_exported(&self) -> Vec<UnsignedTransaction> {
+        std::mem::take(&mut *self.exported.lock().expect("mutex got poisoned"))
+    }
+}
+
+impl OfflineSigner for GenerateOnlySigner {
+    type Error = SigningError;
+
+    fn signer_addresses(&self) -> Result<Vec<AccountId>, Self::Error> {
+        Ok(vec![self.address.clone()])
+    }
+
+    fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error> {
+        Err(SigningError::NoPrivateKeys)
+    }
+
+    fn is_generate_only(&self) -> bool {
+        true
+    }
+
+    fn export_unsigned(&self, transaction: UnsignedTransaction) -> Result<(), Self::Error> {
+        self.exported
+            .lock()
+            .expect("mutex got poisoned")
+            .push(transaction);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::SignerData;
+    use cosmrs::bank::MsgSend;
+    use cosmrs::tx::{self, Msg};
+
+    fn unsigned(address: &AccountId, sequence: u64) -> UnsignedTransaction {
+        let send = MsgSend {
+            from_address: address.clone(),
+            to_address: address.clone(),
+            amount: vec![cosmrs::Coin::new(1000, "unym").unwrap()],
+        }
+        .to_any()
+        .unwrap();
+        let fee = tx::Fee::from_amount_and_gas(cosmrs::Coin::new(5000, "unym").unwrap(), 200000u64);
+        UnsignedTransaction::new(
+            address,
+            vec![send],
+            fee,
+            "memo",
+            SignerData::new(42, sequence, "nyx".parse().unwrap()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn only_exposes_the_address() {
+        let address: AccountId = "n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf".parse().unwrap();
+        let signer = GenerateOnlySigner::new(address.clone());
+
+        assert!(signer.is_generate_only());
+        assert_eq!(signer.signer_addresses().unwrap(), vec![address]);
+        assert!(matches!(
+            signer.get_accounts(),
+            Err(SigningError::NoPrivateKeys)
+        ));
+    }
+
+    #[test]
+    fn exported_transactions_roundtrip() {
+        let address: AccountId = "n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf".parse().unwrap();
+        let signer = GenerateOnlySigner::new(address.clone());
+
+        // the clone moved into the client shares the buffer with the original
+        let client_signer = signer.clone();
+        let first = unsigned(&address, 1);
+        let second = unsigned(&address, 2);
+        client_signer.export_unsigned(first.clone()).unwrap();
+        client_signer.export_unsigned(second.clone()).unwrap();
+
+        let exported = signer.take_exported();
+        assert_eq!(exported, vec![first, second]);
+        assert!(signer.take_exported().is_empty());
+
+        // as printed by the cli and later loaded for signing
+        let serialized = serde_json::to_string_pretty(&exported[0]).unwrap();
+        let loaded: UnsignedTransaction = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(loaded, exported[0]);
+    }
+}
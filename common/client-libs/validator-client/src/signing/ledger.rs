@@ -0,0 +1,280 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::signing::amino::AminoSignDoc;
+use crate::signing::signer::{OfflineSigner, SignerType, SigningError};
+use crate::signing::AccountData;
+use cosmrs::bip32::DerivationPath;
+use cosmrs::crypto::secp256k1::Signature;
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use nym_ledger::error::LedgerError;
+use nym_ledger::transport::LedgerTransport;
+use nym_ledger::CosmosLedger;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LedgerSignerError {
+    #[error(transparent)]
+    SigningFailure(#[from] SigningError),
+
+    #[error("failed to communicate with the ledger device: {source}")]
+    DeviceFailure {
+        #[from]
+        source: LedgerError,
+    },
+
+    #[error("the ledger device returned an invalid address '{address}'")]
+    InvalidAddress { address: String },
+
+    #[error("the ledger device returned an invalid public key")]
+    InvalidPublicKey,
+}
+
+struct LedgerAccount<T> {
+    device: CosmosLedger<T>,
+    address: AccountId,
+    public_key: PublicKey,
+}
+
+// derived manually as otherwise it would have required `T: Clone`
+impl<T> Clone for LedgerAccount<T> {
+    fn clone(&self) -> Self {
+        LedgerAccount {
+            device: self.device.clone(),
+            address: self.address.clone(),
+            public_key: self.public_key,
+        }
+    }
+}
+
+/// Signer backed by a Ledger device running the Cosmos application.
+/// The private keys never leave the device and thus only the amino JSON signing mode is supported.
+pub struct LedgerSigner<T = nym_ledger::TransportNativeHID> {
+    accounts: Vec<LedgerAccount<T>>,
+}
+
+impl<T> Clone for LedgerSigner<T> {
+    fn clone(&self) -> Self {
+        LedgerSigner {
+            accounts: self.accounts.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for LedgerSigner<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field(
+                "accounts",
+                &self
+                    .accounts
+                    .iter()
+                    .map(|account| account.address.as_ref())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl LedgerSigner {
+    /// Connects to the first available Ledger device and retrieves the accounts
+    /// under all of the provided derivation paths.
+    pub fn connect(prefix: &str, hd_paths: Vec<DerivationPath>) -> Result<Self, LedgerSignerError> {
+        let Some(first) = hd_paths.first() else {
+            return Ok(LedgerSigner {
+                accounts: Vec::new(),
+            });
+        };
+
+        let device = CosmosLedger::new(first.clone(), prefix.to_string())?;
+        Self::new_with_device(device, hd_paths)
+    }
+}
+
+impl<T: LedgerTransport> LedgerSigner<T> {
+    /// Retrieves the accounts under all of the provided derivation paths from the already connected device.
+    pub fn new_with_device(
+        device: CosmosLedger<T>,
+        hd_paths: Vec<DerivationPath>,
+    ) -> Result<Self, LedgerSignerError> {
+        let accounts = hd_paths
+            .into_iter()
+            .map(|path| Self::retrieve_account(device.with_path(path)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(LedgerSigner { accounts })
+    }
+
+    fn retrieve_account(device: CosmosLedger<T>) -> Result<LedgerAccount<T>, LedgerSignerError> {
+        let response = device.get_addr_secp256k1(false)?;
+        let address = response
+            .address
+            .parse()
+            .map_err(|_| LedgerSignerError::InvalidAddress {
+                address: response.address.clone(),
+            })?;
+
+        let public_key =
+            cosmrs::tendermint::PublicKey::from_raw_secp256k1(&response.public_key.to_sec1_bytes())
+                .ok_or(LedgerSignerError::InvalidPublicKey)?
+                .into();
+
+        Ok(LedgerAccount {
+            device,
+            address,
+            public_key,
+        })
+    }
+
+    /// Requests the device to display the address of the specified account so that the user
+    /// could verify it matches the one shown by the application.
+    pub fn display_address(&self, signer_address: &AccountId) -> Result<(), LedgerSignerError> {
+        self.find_ledger_account(signer_address)?
+            .device
+            .get_addr_secp256k1(true)?;
+        Ok(())
+    }
+
+    fn find_ledger_account(
+        &self,
+        signer_address: &AccountId,
+    ) -> Result<&LedgerAccount<T>, SigningError> {
+        self.accounts
+            .iter()
+            .find(|account| &account.address == signer_address)
+            .ok_or_else(|| SigningError::AccountNotFound {
+                account: signer_address.clone(),
+            })
+    }
+}
+
+impl<T: LedgerTransport> OfflineSigner for LedgerSigner<T> {
+    type Error = LedgerSignerError;
+
+    fn signer_addresses(&self) -> Result<Vec<AccountId>, Self::Error> {
+        Ok(self
+            .accounts
+            .iter()
+            .map(|account| account.address.clone())
+            .collect())
+    }
+
+    fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error> {
+        Err(SigningError::NoPrivateKeys.into())
+    }
+
+    fn preferred_signer_type(&self) -> SignerType {
+        SignerType::Amino
+    }
+
+    fn account_public_key(&self, signer_address: &AccountId) -> Result<PublicKey, Self::Error> {
+        Ok(self.find_ledger_account(signer_address)?.public_key)
+    }
+
+    fn sign_amino_doc(
+        &self,
+        signer_address: &AccountId,
+        sign_doc: &AminoSignDoc,
+    ) -> Result<Signature, Self::Error> {
+        let account = self.find_ledger_account(signer_address)?;
+        let signature = account
+            .device
+            .sign_secp256k1(sign_doc.to_canonical_json())?
+            .signature;
+
+        // the device returns DER-encoded signatures that are not guaranteed to be in the low-S form
+        // that is required by the chain
+        Ok(signature.normalize_s().unwrap_or(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::tx_signer::TxSigner;
+    use crate::signing::SignerData;
+    use cosmrs::bank::MsgSend;
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::tx::{self, Msg, Raw};
+    use cosmrs::Coin;
+    use k256::ecdsa::signature::Verifier;
+    use nym_ledger::mock::MockLedgerDevice;
+
+    fn mock_signer(key: [u8; 32]) -> (LedgerSigner<MockLedgerDevice>, AccountId) {
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(&key.into()).unwrap();
+        let address = SigningKey::from_slice(&key)
+            .unwrap()
+            .public_key()
+            .account_id("n")
+            .unwrap();
+
+        let device = CosmosLedger::new_with_transport(
+            "m/44'/118'/0'/0/0".parse().unwrap(),
+            "n".to_string(),
+            MockLedgerDevice::new(signing_key, address.to_string()),
+        );
+        let signer =
+            LedgerSigner::new_with_device(device, vec!["m/44'/118'/0'/0/0".parse().unwrap()])
+                .unwrap();
+        (signer, address)
+    }
+
+    #[test]
+    fn retrieves_accounts_from_device() {
+        let (signer, address) = mock_signer([42u8; 32]);
+        assert_eq!(signer.signer_addresses().unwrap(), vec![address.clone()]);
+        assert_eq!(signer.preferred_signer_type(), SignerType::Amino);
+        assert!(matches!(
+            signer.get_accounts(),
+            Err(LedgerSignerError::SigningFailure(
+                SigningError::NoPrivateKeys
+            ))
+        ));
+    }
+
+    #[test]
+    fn signs_transactions_in_amino_mode() {
+        let key = [42u8; 32];
+        let (signer, address) = mock_signer(key);
+
+        let send = MsgSend {
+            from_address: address.clone(),
+            to_address: address.clone(),
+            amount: vec![Coin::new(1000, "unym").unwrap()],
+        }
+        .to_any()
+        .unwrap();
+        let fee = tx::Fee::from_amount_and_gas(Coin::new(5000, "unym").unwrap(), 200000u64);
+        let signer_data = SignerData::new(42, 7, "nyx".parse().unwrap());
+
+        let raw: Raw = <LedgerSigner<MockLedgerDevice> as TxSigner>::sign_amino(
+            &signer,
+            &address,
+            vec![send.clone()],
+            fee.clone(),
+            "memo",
+            signer_data,
+        )
+        .unwrap();
+
+        let expected_doc = AminoSignDoc::new(
+            &[send],
+            fee,
+            "memo",
+            &SignerData::new(42, 7, "nyx".parse().unwrap()),
+        )
+        .unwrap();
+        let tx = cosmrs::Tx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+
+        // the device must have been asked to sign exactly the canonical amino document
+        let verifying_key = *k256::ecdsa::SigningKey::from_bytes(&key.into())
+            .unwrap()
+            .verifying_key();
+        let signature = Signature::try_from(tx.signatures[0].as_slice()).unwrap();
+        assert!(verifying_key
+            .verify(&expected_doc.to_sign_bytes(), &signature)
+            .is_ok());
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 7);
+    }
+}
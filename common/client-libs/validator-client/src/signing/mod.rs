@@ -8,7 +8,12 @@ use cosmrs::tendermint::chain;
 use cosmrs::tx::{AccountNumber, SequenceNumber};
 use cosmrs::AccountId;
 
+pub mod amino;
 pub mod direct_wallet;
+pub mod generate_only;
+#[cfg(feature = "ledger")]
+pub mod ledger;
+pub mod offline_transaction;
 pub mod signer;
 pub mod tx_signer;
 
@@ -0,0 +1,286 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nyxd::error::NyxdError;
+use crate::nyxd::Coin;
+use crate::signing::signer::OfflineSigner;
+use crate::signing::SignerData;
+use cosmrs::tx::{self, SignDoc, SignerInfo};
+use cosmrs::{AccountId, Any};
+use serde::{Deserialize, Serialize};
+
+/// Fee of an unsigned transaction in a form that can be easily inspected before signing it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OfflineFee {
+    pub amount: Vec<Coin>,
+    pub gas_limit: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payer: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub granter: Option<String>,
+}
+
+impl From<tx::Fee> for OfflineFee {
+    fn from(fee: tx::Fee) -> Self {
+        OfflineFee {
+            amount: fee.amount.into_iter().map(Into::into).collect(),
+            gas_limit: fee.gas_limit,
+            payer: fee.payer.map(|payer| payer.to_string()),
+            granter: fee.granter.map(|granter| granter.to_string()),
+        }
+    }
+}
+
+impl TryFrom<OfflineFee> for tx::Fee {
+    type Error = NyxdError;
+
+    fn try_from(fee: OfflineFee) -> Result<Self, Self::Error> {
+        let parse_address = |address: String| {
+            address
+                .parse::<AccountId>()
+                .map_err(|_| NyxdError::MalformedAccountAddress(address))
+        };
+
+        Ok(tx::Fee {
+            amount: fee.amount.into_iter().map(Into::into).collect(),
+            gas_limit: fee.gas_limit,
+            payer: fee.payer.map(parse_address).transpose()?,
+            granter: fee.granter.map(parse_address).transpose()?,
+        })
+    }
+}
+
+/// Transaction that has been fully constructed, including its fee and the signer's sequence number,
+/// but has not been signed yet. It's meant to be moved to a different (possibly air-gapped) machine
+/// holding the signing keys.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    pub signer_address: String,
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+
+    /// Protobuf-encoded transaction body containing all the messages and the memo.
+    #[serde(with = "base64_bytes")]
+    pub body_bytes: Vec<u8>,
+
+    /// Type urls of all messages included in the body, purely informational.
+    pub message_types: Vec<String>,
+    pub memo: String,
+    pub fee: OfflineFee,
+}
+
+impl UnsignedTransaction {
+    pub fn new(
+        signer_address: &AccountId,
+        messages: Vec<Any>,
+        fee: tx::Fee,
+        memo: impl Into<String>,
+        signer_data: SignerData,
+    ) -> Result<Self, NyxdError> {
+        let memo = memo.into();
+        let message_types = messages.iter().map(|msg| msg.type_url.clone()).collect();
+
+        // TODO: experiment with this field (the same value as used by `TxSigner::sign_direct`)
+        let timeout_height = 0u32;
+        let body_bytes = tx::Body::new(messages, memo.clone(), timeout_height).into_bytes()?;
+
+        Ok(UnsignedTransaction {
+            signer_address: signer_address.to_string(),
+            chain_id: signer_data.chain_id.to_string(),
+            account_number: signer_data.account_number,
+            sequence: signer_data.sequence,
+            body_bytes,
+            message_types,
+            memo,
+            fee: fee.into(),
+        })
+    }
+
+    pub fn signer_address(&self) -> Result<AccountId, NyxdError> {
+        self.signer_address
+            .parse()
+            .map_err(|_| NyxdError::MalformedAccountAddress(self.signer_address.clone()))
+    }
+
+    /// Signs the transaction using the account of the specified signer matching the `signer_address`.
+    pub fn sign<S>(&self, signer: &S) -> Result<SignedTransaction, NyxdError>
+    where
+        S: OfflineSigner,
+        NyxdError: From<S::Error>,
+    {
+        let account = signer.find_account(&self.signer_address()?)?;
+
+        let signer_info = SignerInfo::single_direct(Some(account.public_key), self.sequence);
+        let auth_info = signer_info.auth_info(self.fee.clone().try_into()?);
+
+        let sign_doc = SignDoc {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: auth_info.into_bytes()?,
+            chain_id: self.chain_id.clone(),
+            account_number: self.account_number,
+        };
+
+        let tx_raw = signer.sign_direct_with_account(&account, sign_doc)?;
+        let tx_bytes = tx_raw
+            .to_bytes()
+            .map_err(|_| NyxdError::SerializationError("Tx".to_owned()))?;
+
+        Ok(SignedTransaction {
+            signer_address: self.signer_address.clone(),
+            chain_id: self.chain_id.clone(),
+            tx_bytes,
+        })
+    }
+}
+
+/// Signed transaction ready to get broadcast to the chain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignedTransaction {
+    pub signer_address: String,
+    pub chain_id: String,
+
+    #[serde(with = "base64_bytes")]
+    pub tx_bytes: Vec<u8>,
+}
+
+mod base64_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(bytes))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        base64::decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::direct_wallet::DirectSecp256k1HdWallet;
+    use crate::signing::signer::Signature;
+    use cosmrs::bank::MsgSend;
+    use cosmrs::tx::Msg;
+    use k256::ecdsa::signature::Verifier;
+
+    const MNEMONIC: &str = "crush minute paddle tobacco message debate cabin peace bar jacket execute twenty winner view sure mask popular couch penalty fragile demise fresh pizza stove";
+
+    fn wallet() -> (DirectSecp256k1HdWallet, AccountId) {
+        let wallet = DirectSecp256k1HdWallet::from_mnemonic("n", MNEMONIC.parse().unwrap());
+        let address = wallet.signer_addresses().unwrap()[0].clone();
+        (wallet, address)
+    }
+
+    fn unsigned_send(address: &AccountId) -> (UnsignedTransaction, Any) {
+        let send = MsgSend {
+            from_address: address.clone(),
+            to_address: address.clone(),
+            amount: vec![cosmrs::Coin::new(1000, "unym").unwrap()],
+        }
+        .to_any()
+        .unwrap();
+        let mut fee =
+            tx::Fee::from_amount_and_gas(cosmrs::Coin::new(5000, "unym").unwrap(), 200000u64);
+        fee.granter = Some(address.clone());
+
+        let unsigned = UnsignedTransaction::new(
+            address,
+            vec![send.clone()],
+            fee,
+            "memo",
+            SignerData::new(42, 7, "nyx".parse().unwrap()),
+        )
+        .unwrap();
+        (unsigned, send)
+    }
+
+    #[test]
+    fn fee_roundtrip() {
+        let (_, address) = wallet();
+        let fee = tx::Fee {
+            amount: vec![cosmrs::Coin::new(5000, "unym").unwrap()],
+            gas_limit: 123456,
+            payer: Some(address.clone()),
+            granter: Some(address),
+        };
+
+        let offline: OfflineFee = fee.clone().into();
+        let recovered: tx::Fee = offline.try_into().unwrap();
+        assert_eq!(fee, recovered);
+    }
+
+    #[test]
+    fn unsigned_transaction_json_roundtrip() {
+        let (_, address) = wallet();
+        let (unsigned, send) = unsigned_send(&address);
+        assert_eq!(unsigned.message_types, vec![send.type_url]);
+
+        let serialized = serde_json::to_string(&unsigned).unwrap();
+        let deserialized: UnsignedTransaction = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(unsigned, deserialized);
+        assert_eq!(deserialized.signer_address().unwrap(), address);
+    }
+
+    #[test]
+    fn signed_transaction_json_roundtrip() {
+        let (wallet, address) = wallet();
+        let (unsigned, _) = unsigned_send(&address);
+        let signed = unsigned.sign(&wallet).unwrap();
+
+        let serialized = serde_json::to_string(&signed).unwrap();
+        let deserialized: SignedTransaction = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(signed, deserialized);
+    }
+
+    #[test]
+    fn signing_preserves_the_exported_transaction() {
+        let (wallet, address) = wallet();
+        let (unsigned, send) = unsigned_send(&address);
+
+        // the transaction is moved to the offline machine as json
+        let transported: UnsignedTransaction =
+            serde_json::from_str(&serde_json::to_string(&unsigned).unwrap()).unwrap();
+        let signed = transported.sign(&wallet).unwrap();
+        assert_eq!(signed.chain_id, "nyx");
+        assert_eq!(signed.signer_address, address.to_string());
+
+        let tx = cosmrs::Tx::from_bytes(&signed.tx_bytes).unwrap();
+        assert_eq!(tx.body.messages, vec![send]);
+        assert_eq!(tx.body.memo, "memo");
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 7);
+        assert_eq!(
+            tx.auth_info.fee,
+            tx::Fee::try_from(unsigned.fee.clone()).unwrap()
+        );
+
+        let sign_doc = SignDoc {
+            body_bytes: unsigned.body_bytes.clone(),
+            auth_info_bytes: tx.auth_info.into_bytes().unwrap(),
+            chain_id: "nyx".to_string(),
+            account_number: 42,
+        };
+        let public_key = wallet.get_accounts().unwrap()[0].public_key;
+        let verifying_key =
+            k256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key.to_bytes()).unwrap();
+        let signature = Signature::try_from(tx.signatures[0].as_slice()).unwrap();
+        assert!(verifying_key
+            .verify(&sign_doc.into_bytes().unwrap(), &signature)
+            .is_ok());
+    }
+
+    #[test]
+    fn signing_requires_matching_account() {
+        let (_, address) = wallet();
+        let (unsigned, _) = unsigned_send(&address);
+
+        let other = DirectSecp256k1HdWallet::from_mnemonic("n", "acquire rebel spot skin gun such erupt pull swear must define ill chief turtle today flower chunk truth battle claw rigid detail gym feel".parse().unwrap());
+        assert!(unsigned.sign(&other).is_err());
+    }
+}
@@ -1,8 +1,11 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::signing::amino::AminoSignDoc;
+use crate::signing::offline_transaction::UnsignedTransaction;
 use crate::signing::AccountData;
 pub use cosmrs::crypto::secp256k1::Signature;
+use cosmrs::crypto::PublicKey;
 use cosmrs::tx::SignDoc;
 use cosmrs::{tx, AccountId};
 use thiserror::Error;
@@ -20,6 +23,18 @@ pub enum SigningError {
 
     #[error("failed to construct the sign doc: {source}")]
     SignDocFailure { source: eyre::Report },
+
+    #[error("this signer does not have access to any private keys")]
+    NoPrivateKeys,
+
+    #[error("this signer does not support exporting unsigned transactions")]
+    UnsupportedUnsignedExport,
+
+    #[error("message of type {type_url} can't be signed in the amino JSON mode")]
+    UnsupportedAminoMessage { type_url: String },
+
+    #[error("failed to encode the transaction in the amino JSON format: {source}")]
+    AminoEncodingFailure { source: eyre::Report },
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -45,6 +60,15 @@ pub trait OfflineSigner {
 
     fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error>;
 
+    /// Specifies the signing mode that should be used for all transactions created with this signer.
+    fn preferred_signer_type(&self) -> SignerType {
+        SignerType::Direct
+    }
+
+    fn account_public_key(&self, signer_address: &AccountId) -> Result<PublicKey, Self::Error> {
+        Ok(self.find_account(signer_address)?.public_key)
+    }
+
     fn find_account(&self, signer_address: &AccountId) -> Result<AccountData, Self::Error> {
         // TODO: we could really use some zeroize action here
         let accounts = self.get_accounts()?;
@@ -100,9 +124,24 @@ pub trait OfflineSigner {
         .into())
     }
 
-    // fn sign_amino(&self, signer_address: &AccountId, sign_doc: AminoSignDoc) -> Result<tx::Raw, Self::Error>;
+    /// Specifies whether this signer, rather than signing the transactions, only exports them
+    /// so that they could be signed elsewhere, for example on an air-gapped machine.
+    fn is_generate_only(&self) -> bool {
+        false
+    }
+
+    fn export_unsigned(&self, _transaction: UnsignedTransaction) -> Result<(), Self::Error> {
+        Err(SigningError::UnsupportedUnsignedExport.into())
+    }
 
-    // fn sign_amino_with_account(&self, signer: &AccountData, sign_doc: AminoSignDoc) -> Result<tx::Raw, Self::Error>;
+    /// Signs the canonical JSON representation of the provided amino sign doc.
+    fn sign_amino_doc(
+        &self,
+        signer_address: &AccountId,
+        sign_doc: &AminoSignDoc,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_raw(signer_address, sign_doc.to_sign_bytes())
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -1,27 +1,57 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::signing::amino::AminoSignDoc;
 use crate::signing::signer::{OfflineSigner, SigningError};
 use crate::signing::SignerData;
+use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode;
 use cosmrs::tx::{SignDoc, SignerInfo};
 use cosmrs::{tx, AccountId, Any};
 
 // extension trait for the OfflineSigner to allow to sign transactions
 pub trait TxSigner: OfflineSigner {
     fn signer_public_key(&self, signer_address: &AccountId) -> Option<tx::SignerPublicKey> {
-        let account = self.find_account(signer_address).ok()?;
-        Some(account.public_key().into())
+        let public_key = self.account_public_key(signer_address).ok()?;
+        Some(public_key.into())
     }
 
     fn sign_amino(
         &self,
-        _signer_address: &AccountId,
-        _messages: Vec<Any>,
-        _fee: tx::Fee,
-        _memo: impl Into<String> + Send + 'static,
-        _signer_data: SignerData,
+        signer_address: &AccountId,
+        messages: Vec<Any>,
+        fee: tx::Fee,
+        memo: impl Into<String> + Send + 'static,
+        signer_data: SignerData,
     ) -> Result<tx::Raw, <Self as OfflineSigner>::Error> {
-        unimplemented!()
+        let public_key = self.account_public_key(signer_address)?;
+        let memo = memo.into();
+
+        // TODO: experiment with this field
+        let timeout_height = 0u32;
+
+        let sign_doc = AminoSignDoc::new(&messages, fee.clone(), memo.clone(), &signer_data)?;
+        let tx_body = tx::Body::new(messages, memo, timeout_height);
+        let signer_info = SignerInfo {
+            public_key: Some(public_key.into()),
+            mode_info: tx::ModeInfo::Single(tx::mode_info::Single {
+                mode: SignMode::LegacyAminoJson,
+            }),
+            sequence: signer_data.sequence,
+        };
+        let auth_info = signer_info.auth_info(fee);
+
+        let signature = self.sign_amino_doc(signer_address, &sign_doc)?;
+
+        Ok(cosmrs::proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: tx_body
+                .into_bytes()
+                .map_err(|source| SigningError::SignDocFailure { source })?,
+            auth_info_bytes: auth_info
+                .into_bytes()
+                .map_err(|source| SigningError::SignDocFailure { source })?,
+            signatures: vec![signature.to_bytes().to_vec()],
+        }
+        .into())
     }
 
     fn sign_direct(
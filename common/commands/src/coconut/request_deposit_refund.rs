@@ -0,0 +1,96 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::SigningClient;
+use crate::utils::CommonConfigsWrapper;
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use log::{info, warn};
+use nym_credential_storage::initialise_persistent_storage;
+use nym_credential_storage::storage::Storage;
+use nym_ecash_contract_common::deposit::deposit_refund_request_plaintext;
+use nym_validator_client::coconut::all_ecash_api_clients;
+use nym_validator_client::ecash::models::DepositRefundRequestBody;
+use nym_validator_client::nyxd::contract_traits::{DkgQueryClient, EcashSigningClient};
+use nym_validator_client::nyxd::cosmwasm_client::ToSingletonContractData;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Config file of the client that made the deposit.
+    #[clap(long)]
+    pub(crate) client_config: PathBuf,
+
+    /// Id of the deposit to refund. If not specified, refunds of all deposits associated with
+    /// incomplete ticketbook issuances are going to be requested.
+    #[clap(long)]
+    pub(crate) deposit_id: Option<u32>,
+}
+
+pub async fn execute(args: Args, client: SigningClient) -> anyhow::Result<()> {
+    let loaded = CommonConfigsWrapper::try_load(args.client_config)?;
+
+    if let Ok(id) = loaded.try_get_id() {
+        println!("loaded config file for client '{id}'");
+    }
+
+    let Ok(credentials_store) = loaded.try_get_credentials_store() else {
+        bail!("the loaded config does not have a credentials store information")
+    };
+
+    println!(
+        "using credentials store at '{}'",
+        credentials_store.display()
+    );
+
+    let persistent_storage = initialise_persistent_storage(credentials_store).await;
+    let pending = persistent_storage
+        .get_pending_ticketbooks()
+        .await
+        .map_err(|err| anyhow!("failed to retrieve pending ticketbooks: {err}"))?;
+
+    let to_refund = pending
+        .into_iter()
+        .filter(|p| {
+            args.deposit_id
+                .map(|id| id == p.pending_ticketbook.deposit_id())
+                .unwrap_or(true)
+        })
+        .collect::<Vec<_>>();
+
+    if to_refund.is_empty() {
+        bail!("there are no incomplete ticketbook issuances matching the request")
+    }
+
+    let epoch_id = client.get_current_epoch().await?.epoch_id;
+    let ecash_apis = all_ecash_api_clients(&client, epoch_id).await?;
+    let receiver = client.address();
+
+    for issuance in to_refund {
+        let deposit_id = issuance.pending_ticketbook.deposit_id();
+
+        // prove we own the deposit and bind the refund to our account
+        let plaintext = deposit_refund_request_plaintext(deposit_id, receiver.as_ref());
+        let signature = issuance.pending_ticketbook.identity_key().sign(plaintext);
+
+        let res = client
+            .request_deposit_refund(deposit_id, signature.to_base58_string(), None)
+            .await?;
+        let proposal_id = res.parse_singleton_u64_contract_data()?;
+        info!("created refund proposal {proposal_id} for deposit {deposit_id}");
+
+        let request = DepositRefundRequestBody {
+            deposit_id,
+            proposal_id,
+        };
+        for ecash_api in &ecash_apis {
+            if let Err(err) = ecash_api.api_client.request_deposit_refund(&request).await {
+                warn!("{ecash_api} has rejected the refund request of deposit {deposit_id}: {err}")
+            }
+        }
+
+        println!("requested refund of deposit {deposit_id} (proposal {proposal_id})");
+    }
+
+    Ok(())
+}
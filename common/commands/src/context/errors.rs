@@ -21,4 +21,7 @@ pub enum ContextError {
 
     #[error(transparent)]
     ValidatorClientError(#[from] nym_validator_client::ValidatorClientError),
+
+    #[error(transparent)]
+    LedgerError(#[from] nym_validator_client::signing::ledger::LedgerSignerError),
 }
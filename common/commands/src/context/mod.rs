@@ -8,16 +8,23 @@ use nym_network_defaults::{
     NymNetworkDetails,
 };
 pub use nym_validator_client::nym_api::Client as NymApiClient;
-use nym_validator_client::nyxd::{self, AccountId, NyxdClient};
+use nym_validator_client::nyxd::error::NyxdError;
+use nym_validator_client::nyxd::{self, bip32::DerivationPath, AccountId, NyxdClient, PublicKey};
+use nym_validator_client::signing::amino::AminoSignDoc;
+use nym_validator_client::signing::generate_only::GenerateOnlySigner;
+use nym_validator_client::signing::ledger::LedgerSigner;
+use nym_validator_client::signing::offline_transaction::UnsignedTransaction;
+use nym_validator_client::signing::signer::{OfflineSigner, Signature, SignerType};
+use nym_validator_client::signing::AccountData;
 use nym_validator_client::{
-    DirectSigningHttpRpcNyxdClient, DirectSigningHttpRpcValidatorClient, QueryHttpRpcNyxdClient,
-    QueryHttpRpcValidatorClient,
+    http_client, DirectSecp256k1HdWallet, DirectSigningHttpRpcValidatorClient, HttpRpcClient,
+    QueryHttpRpcNyxdClient, QueryHttpRpcValidatorClient,
 };
 use tap::prelude::*;
 
 pub mod errors;
 
-pub type SigningClient = DirectSigningHttpRpcNyxdClient;
+pub type SigningClient = NyxdClient<HttpRpcClient, CliSigner>;
 pub type QueryClient = QueryHttpRpcNyxdClient;
 pub type SigningClientWithNyxd = DirectSigningHttpRpcValidatorClient;
 pub type QueryClientWithNyxd = QueryHttpRpcValidatorClient;
@@ -30,6 +37,107 @@ pub struct ClientArgs {
     pub mnemonic: Option<bip39::Mnemonic>,
     pub mixnet_contract_address: Option<AccountId>,
     pub vesting_contract_address: Option<AccountId>,
+
+    /// If specified, the signing client is not going to sign nor broadcast any transactions,
+    /// instead they are going to be exported via this signer.
+    pub generate_only: Option<GenerateOnlySigner>,
+
+    /// Sign the transactions with a Ledger device rather than with the provided mnemonic.
+    pub ledger: bool,
+
+    /// Index of the account (as in the `m/44'/118'/{index}'/0/0` derivation path) used for signing.
+    pub account_index: u32,
+}
+
+/// Signer used by the signing client. It either holds the actual keys derived from the mnemonic,
+/// delegates the signing to a Ledger device or, when running with `--generate-only`, only knows
+/// the account address and exports unsigned transactions so that they could be signed on an offline machine.
+#[derive(Debug, Clone)]
+pub enum CliSigner {
+    Wallet(DirectSecp256k1HdWallet),
+    Ledger(LedgerSigner),
+    GenerateOnly(GenerateOnlySigner),
+}
+
+impl OfflineSigner for CliSigner {
+    type Error = NyxdError;
+
+    fn signer_addresses(&self) -> Result<Vec<AccountId>, Self::Error> {
+        match self {
+            CliSigner::Wallet(wallet) => Ok(wallet.signer_addresses()?),
+            CliSigner::Ledger(ledger) => Ok(ledger.signer_addresses()?),
+            CliSigner::GenerateOnly(signer) => Ok(signer.signer_addresses()?),
+        }
+    }
+
+    fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error> {
+        match self {
+            CliSigner::Wallet(wallet) => Ok(wallet.get_accounts()?),
+            CliSigner::Ledger(ledger) => Ok(ledger.get_accounts()?),
+            CliSigner::GenerateOnly(signer) => Ok(signer.get_accounts()?),
+        }
+    }
+
+    fn preferred_signer_type(&self) -> SignerType {
+        match self {
+            CliSigner::Wallet(wallet) => wallet.preferred_signer_type(),
+            CliSigner::Ledger(ledger) => ledger.preferred_signer_type(),
+            CliSigner::GenerateOnly(signer) => signer.preferred_signer_type(),
+        }
+    }
+
+    fn account_public_key(&self, signer_address: &AccountId) -> Result<PublicKey, Self::Error> {
+        match self {
+            CliSigner::Wallet(wallet) => Ok(wallet.account_public_key(signer_address)?),
+            CliSigner::Ledger(ledger) => Ok(ledger.account_public_key(signer_address)?),
+            CliSigner::GenerateOnly(signer) => Ok(signer.account_public_key(signer_address)?),
+        }
+    }
+
+    fn sign_direct_with_account(
+        &self,
+        signer: &AccountData,
+        sign_doc: nyxd::tx::SignDoc,
+    ) -> Result<nyxd::tx::Raw, Self::Error> {
+        match self {
+            CliSigner::Wallet(wallet) => Ok(wallet.sign_direct_with_account(signer, sign_doc)?),
+            CliSigner::Ledger(ledger) => Ok(ledger.sign_direct_with_account(signer, sign_doc)?),
+            CliSigner::GenerateOnly(generate_only) => {
+                Ok(generate_only.sign_direct_with_account(signer, sign_doc)?)
+            }
+        }
+    }
+
+    fn sign_amino_doc(
+        &self,
+        signer_address: &AccountId,
+        sign_doc: &AminoSignDoc,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            CliSigner::Wallet(wallet) => Ok(wallet.sign_amino_doc(signer_address, sign_doc)?),
+            CliSigner::Ledger(ledger) => Ok(ledger.sign_amino_doc(signer_address, sign_doc)?),
+            CliSigner::GenerateOnly(signer) => Ok(signer.sign_amino_doc(signer_address, sign_doc)?),
+        }
+    }
+
+    fn is_generate_only(&self) -> bool {
+        matches!(self, CliSigner::GenerateOnly(_))
+    }
+
+    fn export_unsigned(&self, transaction: UnsignedTransaction) -> Result<(), Self::Error> {
+        match self {
+            CliSigner::Wallet(wallet) => Ok(wallet.export_unsigned(transaction)?),
+            CliSigner::Ledger(ledger) => Ok(ledger.export_unsigned(transaction)?),
+            CliSigner::GenerateOnly(signer) => Ok(signer.export_unsigned(transaction)?),
+        }
+    }
+}
+
+/// Derivation path of the account with the specified index, i.e. `m/44'/118'/{index}'/0/0`.
+pub fn account_derivation_path(account_index: u32) -> DerivationPath {
+    format!("m/44'/118'/{account_index}'/0/0")
+        .parse()
+        .expect("the derivation path is well-formed")
 }
 
 pub fn get_network_details(args: &ClientArgs) -> Result<NymNetworkDetails, ContextError> {
@@ -64,14 +172,28 @@ pub fn create_signing_client(
     let client_config = nyxd::Config::try_from_nym_network_details(network_details)
         .tap_err(|err| log::error!("Failed to get client config - {err}"))?;
 
-    // get mnemonic
-    let mnemonic = match std::env::var("MNEMONIC") {
-        Ok(value) => bip39::Mnemonic::parse(value)?,
-        // env var MNEMONIC is not present, so try to fall back to arg --mnemonic ...
-        Err(_) => match args.mnemonic {
-            Some(value) => value,
-            None => return Err(ContextError::MnemonicNotProvided), // no env var or arg provided
-        },
+    let prefix = &network_details.chain_details.bech32_account_prefix;
+    let hd_path = account_derivation_path(args.account_index);
+
+    let signer = match args.generate_only {
+        Some(generate_only) => CliSigner::GenerateOnly(generate_only),
+        None if args.ledger => CliSigner::Ledger(LedgerSigner::connect(prefix, vec![hd_path])?),
+        None => {
+            // get mnemonic
+            let mnemonic = match std::env::var("MNEMONIC") {
+                Ok(value) => bip39::Mnemonic::parse(value)?,
+                // env var MNEMONIC is not present, so try to fall back to arg --mnemonic ...
+                Err(_) => match args.mnemonic {
+                    Some(value) => value,
+                    None => return Err(ContextError::MnemonicNotProvided), // no env var or arg provided
+                },
+            };
+            CliSigner::Wallet(
+                DirectSecp256k1HdWallet::builder(prefix)
+                    .with_hd_paths(vec![hd_path])
+                    .build(mnemonic),
+            )
+        }
     };
 
     let nyxd_url = network_details
@@ -81,8 +203,12 @@ pub fn create_signing_client(
         .nyxd_url
         .as_str();
 
-    match NyxdClient::connect_with_mnemonic(client_config, nyxd_url, mnemonic) {
-        Ok(client) => Ok(client),
+    match http_client(nyxd_url) {
+        Ok(client) => Ok(NyxdClient::connect_with_signer(
+            client_config,
+            client,
+            signer,
+        )),
         Err(e) => Err(ContextError::NyxdError(format!("{e}"))),
     }
 }
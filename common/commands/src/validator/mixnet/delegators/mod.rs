@@ -9,6 +9,7 @@ pub mod delegate_to_mixnode;
 pub mod delegate_to_multiple_mixnodes;
 pub mod migrate_vested_delegation;
 pub mod query_for_delegations;
+pub mod redelegate_from_mixnode;
 pub mod undelegate_from_mixnode;
 pub mod vesting_delegate_to_mixnode;
 pub mod vesting_undelegate_from_mixnode;
@@ -32,6 +33,8 @@ pub enum MixnetDelegatorsCommands {
     DelegateMulti(delegate_to_multiple_mixnodes::Args),
     /// Undelegate from a mixnode
     Undelegate(undelegate_from_mixnode::Args),
+    /// Move stake from one mixnode to another at the end of the current epoch
+    Redelegate(redelegate_from_mixnode::Args),
     /// Delegate to a mixnode with locked tokens
     DelegateVesting(vesting_delegate_to_mixnode::Args),
     /// Undelegate from a mixnode (when originally using locked tokens)
@@ -124,6 +124,24 @@ async fn print_delegation_events(events: Vec<PendingEpochEvent>, client: &Signin
                     ]);
                 }
             }
+            PendingEpochEventKind::Redelegate {
+                owner,
+                from,
+                to,
+                amount,
+                proxy,
+                ..
+            } => {
+                if owner.as_str() == client.nyxd.address().as_ref() {
+                    table.add_row(vec![
+                        "not-sure-if-applicable".into(),
+                        format!("{from} -> {to}"),
+                        pretty_cosmwasm_coin(&amount),
+                        "Redelegate".to_string(),
+                        proxy.map(Addr::into_string).unwrap_or_else(|| "-".into()),
+                    ]);
+                }
+            }
             _ => {}
         }
     }
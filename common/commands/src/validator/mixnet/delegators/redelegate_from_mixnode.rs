@@ -0,0 +1,41 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::SigningClient;
+use clap::Parser;
+use log::info;
+use nym_mixnet_contract_common::{Coin, MixId};
+use nym_validator_client::nyxd::contract_traits::MixnetSigningClient;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Id of the mixnode the stake is moved away from
+    #[clap(long)]
+    pub from: MixId,
+
+    /// Id of the mixnode the stake is moved towards
+    #[clap(long)]
+    pub to: MixId,
+
+    /// Amount of stake to move (if it exceeds the current delegation, the entire delegation is moved)
+    #[clap(long)]
+    pub amount: u128,
+}
+
+pub async fn redelegate_from_mixnode(args: Args, client: SigningClient) {
+    let denom = client.current_chain_details().mix_denom.base.as_str();
+
+    info!(
+        "Starting redelegation from mixnode {} to mixnode {}",
+        args.from, args.to
+    );
+
+    let coin = Coin::new(args.amount, denom);
+
+    let res = client
+        .redelegate_from_mixnode(args.from, args.to, coin.into(), None)
+        .await
+        .expect("failed to redelegate between mixnodes!");
+
+    info!("redelegating between mixnodes: {:?}", res);
+}
@@ -0,0 +1,32 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::QueryClient;
+use crate::utils::show_error;
+use clap::Parser;
+use nym_validator_client::nyxd::CosmWasmClient;
+use nym_validator_client::signing::offline_transaction::SignedTransaction;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Path to the transaction signed with `tx sign`
+    #[clap(long)]
+    pub signed_transaction: PathBuf,
+}
+
+pub async fn broadcast(args: Args, client: &QueryClient) -> anyhow::Result<()> {
+    let signed: SignedTransaction =
+        serde_json::from_str(&fs::read_to_string(&args.signed_transaction)?)?;
+
+    match client.broadcast_tx(signed.tx_bytes, None, None).await {
+        Ok(res) => {
+            println!("{}", json!(res))
+        }
+        Err(e) => show_error(e),
+    }
+
+    Ok(())
+}
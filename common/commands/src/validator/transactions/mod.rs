@@ -3,8 +3,10 @@
 
 use clap::{Args, Subcommand};
 
+pub mod broadcast_transaction;
 pub mod get_transaction;
 pub mod query_transactions;
+pub mod sign_transaction;
 
 #[derive(Debug, Args)]
 #[clap(args_conflicts_with_subcommands = true, subcommand_required = true)]
@@ -19,4 +21,8 @@ pub enum TransactionsCommands {
     Get(crate::validator::transactions::get_transaction::Args),
     /// Query for transactions
     Query(crate::validator::transactions::query_transactions::Args),
+    /// Sign a transaction generated with `--generate-only`. It does not require network access
+    Sign(crate::validator::transactions::sign_transaction::Args),
+    /// Broadcast a transaction signed with `tx sign`
+    Broadcast(crate::validator::transactions::broadcast_transaction::Args),
 }
@@ -0,0 +1,62 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use log::info;
+use nym_network_defaults::NymNetworkDetails;
+use nym_validator_client::signing::offline_transaction::UnsignedTransaction;
+use nym_validator_client::DirectSecp256k1HdWallet;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Path to the unsigned transaction generated with `--generate-only`
+    #[clap(long)]
+    pub unsigned_transaction: PathBuf,
+
+    /// Optional path to the file where the signed transaction should be written to.
+    /// If not specified, it will be written to stdout
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+// note: this does not require any network connectivity, so it can be run on an air-gapped machine
+pub fn sign(
+    args: Args,
+    network_details: &NymNetworkDetails,
+    mnemonic: bip39::Mnemonic,
+) -> anyhow::Result<()> {
+    let unsigned: UnsignedTransaction =
+        serde_json::from_str(&fs::read_to_string(&args.unsigned_transaction)?)?;
+
+    info!(
+        "signing transaction on behalf of {} (chain: {}, account number: {}, sequence: {})",
+        unsigned.signer_address, unsigned.chain_id, unsigned.account_number, unsigned.sequence
+    );
+    info!("included messages: {:?}", unsigned.message_types);
+    info!("memo: '{}'", unsigned.memo);
+    info!(
+        "fee: {:?} with gas limit of {}",
+        unsigned.fee.amount, unsigned.fee.gas_limit
+    );
+
+    let prefix = &network_details.chain_details.bech32_account_prefix;
+    let wallet = DirectSecp256k1HdWallet::from_mnemonic(prefix, mnemonic);
+
+    let signed = unsigned.sign(&wallet)?;
+    let serialised = serde_json::to_string_pretty(&signed)?;
+
+    match args.output {
+        Some(output) => {
+            fs::write(&output, serialised)?;
+            info!(
+                "the signed transaction has been written to {}",
+                output.display()
+            )
+        }
+        None => println!("{serialised}"),
+    }
+
+    Ok(())
+}
@@ -8,4 +8,8 @@ use cosmwasm_std::Coin;
 pub struct PoolCounters {
     pub total_deposited: Coin,
     pub total_redeemed: Coin,
+
+    // introduced alongside deposit refunds, so it might not be present in the existing state
+    #[serde(default)]
+    pub total_refunded: Coin,
 }
@@ -3,10 +3,13 @@
 
 use crate::error::EcashContractError;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{StdError, StdResult};
+use cosmwasm_std::{Addr, StdError, StdResult};
 
 pub type DepositId = u32;
 
+/// Domain separator prepended to every deposit refund request plaintext.
+pub const DEPOSIT_REFUND_REQUEST_PREFIX: &[u8] = b"nym-ecash-deposit-refund";
+
 #[cw_serde]
 pub struct Deposit {
     pub bs58_encoded_ed25519_pubkey: String,
@@ -47,6 +50,33 @@ impl Deposit {
     }
 }
 
+/// Construct the plaintext that has to be signed with the ed25519 identity key associated with the deposit
+/// in order to request the refund of its funds into the specified receiver account.
+pub fn deposit_refund_request_plaintext(deposit_id: DepositId, receiver: &str) -> Vec<u8> {
+    DEPOSIT_REFUND_REQUEST_PREFIX
+        .iter()
+        .chain(deposit_id.to_be_bytes().iter())
+        .chain(receiver.as_bytes().iter())
+        .copied()
+        .collect()
+}
+
+#[cw_serde]
+pub struct DepositRefund {
+    /// Address of the account that is going to receive the refunded funds.
+    pub receiver: Addr,
+
+    /// Id of the multisig proposal created to approve the refund.
+    pub proposal_id: Option<u64>,
+}
+
+#[cw_serde]
+pub struct DepositRefundResponse {
+    pub id: DepositId,
+
+    pub refund: Option<DepositRefund>,
+}
+
 #[cw_serde]
 pub struct DepositResponse {
     pub id: DepositId,
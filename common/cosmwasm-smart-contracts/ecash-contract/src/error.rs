@@ -1,6 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::deposit::DepositId;
 use cosmwasm_std::{Coin, StdError};
 use cw_controllers::AdminError;
 use cw_utils::PaymentError;
@@ -65,4 +66,19 @@ pub enum EcashContractError {
 
     #[error("the account blacklisting hasn't been fully implemented yet")]
     UnimplementedBlacklisting,
+
+    #[error("could not find ticketbook deposit with id {deposit_id}")]
+    NonExistentDeposit { deposit_id: DepositId },
+
+    #[error("the provided deposit refund signature was malformed")]
+    MalformedRefundSignature,
+
+    #[error("the provided refund signature for deposit {deposit_id} is invalid")]
+    InvalidRefundSignature { deposit_id: DepositId },
+
+    #[error("refund of deposit {deposit_id} has already been requested")]
+    RefundAlreadyRequested { deposit_id: DepositId },
+
+    #[error("there are no pending refund requests for deposit {deposit_id}")]
+    NoPendingRefund { deposit_id: DepositId },
 }
@@ -3,7 +3,9 @@
 
 // event types
 pub const DEPOSITED_FUNDS_EVENT_TYPE: &str = "deposited-funds";
+pub const DEPOSIT_REFUND_EVENT_TYPE: &str = "deposit-refund";
 
 pub const DEPOSIT_ID: &str = "deposit-id";
+pub const REFUND_RECEIVER: &str = "refund-receiver";
 pub const WASM_EVENT_NAME: &str = "wasm";
 pub const PROPOSAL_ID_ATTRIBUTE_NAME: &str = "proposal_id";
@@ -7,7 +7,7 @@ use cosmwasm_std::Coin;
 #[cfg(feature = "schema")]
 use crate::blacklist::{BlacklistedAccountResponse, PagedBlacklistedAccountResponse};
 #[cfg(feature = "schema")]
-use crate::deposit::{DepositResponse, PagedDepositsResponse};
+use crate::deposit::{DepositRefundResponse, DepositResponse, PagedDepositsResponse};
 #[cfg(feature = "schema")]
 use cosmwasm_schema::QueryResponses;
 
@@ -38,6 +38,19 @@ pub enum ExecuteMsg {
         gw: String,
     },
 
+    /// Used by clients to request refund of a deposit whose ticketbook has never been issued or has expired unused.
+    /// The signature has to be made with the deposit's identity key over the data
+    /// produced by `deposit_refund_request_plaintext`.
+    RequestDepositRefund {
+        deposit_id: u32,
+        signature_bs58: String,
+    },
+
+    /// The actual message that gets executed, after multisig votes, that returns the deposit funds to the requester
+    RefundDeposit {
+        deposit_id: u32,
+    },
+
     UpdateAdmin {
         admin: String,
     },
@@ -78,6 +91,9 @@ pub enum QueryMsg {
         limit: Option<u32>,
         start_after: Option<u32>,
     },
+
+    #[cfg_attr(feature = "schema", returns(DepositRefundResponse))]
+    GetDepositRefund { deposit_id: u32 },
 }
 
 #[cw_serde]
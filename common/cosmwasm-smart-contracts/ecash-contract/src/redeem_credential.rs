@@ -3,3 +3,4 @@
 
 // TODO: to be moved to multisig
 pub const BATCH_REDEMPTION_PROPOSAL_TITLE: &str = "ecash-redemption";
+pub const DEPOSIT_REFUND_PROPOSAL_TITLE: &str = "ecash-deposit-refund";
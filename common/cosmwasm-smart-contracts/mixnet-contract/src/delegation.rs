@@ -56,6 +56,11 @@ pub struct Delegation {
 
     /// Proxy address used to delegate the funds on behalf of another address
     pub proxy: Option<Addr>,
+
+    /// Flag indicating whether the rewards accumulated by this delegation should get automatically
+    /// added to its principal amount at the end of each epoch.
+    #[serde(default)]
+    pub auto_compound: bool,
 }
 
 impl Delegation {
@@ -78,6 +83,7 @@ impl Delegation {
             amount,
             height,
             proxy: None,
+            auto_compound: false,
         }
     }
 
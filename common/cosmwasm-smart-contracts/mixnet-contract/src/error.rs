@@ -129,6 +129,30 @@ pub enum MixnetContractError {
         proxy: Option<String>,
     },
 
+    #[error("Could not find any delegation information associated with gateway {identity} for {address}")]
+    NoGatewayDelegationFound {
+        identity: IdentityKey,
+        address: String,
+    },
+
+    #[error("Gateway with identity {identity} does not exist")]
+    GatewayBondNotFound { identity: IdentityKey },
+
+    #[error("Gateway {identity} has already been rewarded during the current rewarding epoch ({absolute_epoch_id})")]
+    GatewayAlreadyRewarded {
+        identity: IdentityKey,
+        absolute_epoch_id: u32,
+    },
+
+    #[error("All of the {rewarded_gateways} gateways have already been rewarded during the current rewarding epoch ({absolute_epoch_id})")]
+    GatewayRewardingLimitReached {
+        rewarded_gateways: u32,
+        absolute_epoch_id: u32,
+    },
+
+    #[error("Attempted to redelegate tokens from mixnode {mix_id} back to itself")]
+    RedelegationToSameMixnode { mix_id: MixId },
+
     #[error("Provided message to update rewarding params did not contain any updates")]
     EmptyParamsChangeMsg,
 
@@ -254,6 +278,9 @@ pub enum MixnetContractError {
         provided: Uint128,
         range: OperatingCostRange,
     },
+
+    #[error("received a reply to an unknown submessage: {id}")]
+    UnknownReplyId { id: u64 },
 }
 
 impl MixnetContractError {
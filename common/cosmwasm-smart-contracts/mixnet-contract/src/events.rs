@@ -1,7 +1,7 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::gateway::GatewayConfigUpdate;
+use crate::gateway::{GatewayConfigUpdate, GatewayRewardingParams};
 use crate::mixnode::{MixNodeConfigUpdate, MixNodeCostParams};
 use crate::reward_params::{IntervalRewardParams, IntervalRewardingParamsUpdate};
 use crate::rewarding::RewardDistribution;
@@ -37,6 +37,9 @@ pub enum MixnetEventType {
     Delegation,
     DelegationOnUnbonding,
     Undelegation,
+    PendingRedelegation,
+    Redelegation,
+    RedelegationOnUnbonding,
     ContractSettingsUpdate,
     RewardingValidatorUpdate,
     BeginEpochTransition,
@@ -47,6 +50,19 @@ pub enum MixnetEventType {
     PendingIntervalConfigUpdate,
     IntervalConfigUpdate,
     GatewayConfigUpdate,
+    PendingGatewayCostParamsUpdate,
+    GatewayCostParamsUpdate,
+    GatewayRewarding,
+    GatewayRewardingParamsUpdate,
+    PendingGatewayDelegation,
+    PendingGatewayUndelegation,
+    GatewayDelegation,
+    GatewayDelegationOnUnbonding,
+    GatewayUndelegation,
+    DelegationAutoCompoundingUpdate,
+    DelegatorRewardCompounding,
+    AutoCompoundingExecution,
+    CompoundedRewardTrackingFailure,
 }
 
 impl From<MixnetEventType> for String {
@@ -83,6 +99,9 @@ impl Display for MixnetEventType {
             MixnetEventType::PendingUndelegation => "pending_undelegation",
             MixnetEventType::Delegation => "delegation",
             MixnetEventType::Undelegation => "undelegation",
+            MixnetEventType::PendingRedelegation => "pending_redelegation",
+            MixnetEventType::Redelegation => "redelegation",
+            MixnetEventType::RedelegationOnUnbonding => "redelegation_on_unbonding_node",
             MixnetEventType::ContractSettingsUpdate => "settings_update",
             MixnetEventType::RewardingValidatorUpdate => "rewarding_validator_address_update",
             MixnetEventType::BeginEpochTransition => "beginning_epoch_transition",
@@ -94,6 +113,23 @@ impl Display for MixnetEventType {
             MixnetEventType::IntervalConfigUpdate => "interval_config_update",
             MixnetEventType::DelegationOnUnbonding => "delegation_on_unbonding_node",
             MixnetEventType::GatewayConfigUpdate => "gateway_config_update",
+            MixnetEventType::PendingGatewayCostParamsUpdate => "pending_gateway_cost_params_update",
+            MixnetEventType::GatewayCostParamsUpdate => "gateway_cost_params_update",
+            MixnetEventType::GatewayRewarding => "gateway_rewarding",
+            MixnetEventType::GatewayRewardingParamsUpdate => "gateway_rewarding_params_update",
+            MixnetEventType::PendingGatewayDelegation => "pending_gateway_delegation",
+            MixnetEventType::PendingGatewayUndelegation => "pending_gateway_undelegation",
+            MixnetEventType::GatewayDelegation => "gateway_delegation",
+            MixnetEventType::GatewayDelegationOnUnbonding => "gateway_delegation_on_unbonding_node",
+            MixnetEventType::GatewayUndelegation => "gateway_undelegation",
+            MixnetEventType::DelegationAutoCompoundingUpdate => {
+                "delegation_auto_compounding_update"
+            }
+            MixnetEventType::DelegatorRewardCompounding => "delegator_reward_compounding",
+            MixnetEventType::AutoCompoundingExecution => "auto_compounding_execution",
+            MixnetEventType::CompoundedRewardTrackingFailure => {
+                "compounded_reward_tracking_failure"
+            }
         };
 
         write!(f, "{EVENT_VERSION_PREFIX}{event_name}")
@@ -109,7 +145,10 @@ pub const AMOUNT_KEY: &str = "amount";
 // delegation/undelegation
 pub const DELEGATOR_KEY: &str = "delegator";
 pub const DELEGATION_TARGET_KEY: &str = "delegation_target";
+pub const REDELEGATION_SOURCE_KEY: &str = "redelegation_source";
 pub const UNIT_REWARD_KEY: &str = "unit_reward";
+pub const AUTO_COMPOUND_KEY: &str = "auto_compound";
+pub const PROXY_KEY: &str = "proxy";
 
 // bonding/unbonding
 pub const MIX_ID_KEY: &str = "mix_id";
@@ -131,6 +170,8 @@ pub const NEW_REWARDING_VALIDATOR_ADDRESS_KEY: &str = "new_rewarding_validator_a
 pub const UPDATED_MIXNODE_CONFIG_KEY: &str = "updated_mixnode_config";
 pub const UPDATED_GATEWAY_CONFIG_KEY: &str = "updated_gateway_config";
 pub const UPDATED_MIXNODE_COST_PARAMS_KEY: &str = "updated_mixnode_cost_params";
+pub const UPDATED_GATEWAY_COST_PARAMS_KEY: &str = "updated_gateway_cost_params";
+pub const UPDATED_GATEWAY_REWARDING_PARAMS_KEY: &str = "updated_gateway_rewarding_params";
 
 // rewarding
 pub const INTERVAL_KEY: &str = "interval_details";
@@ -154,6 +195,8 @@ pub const NEW_CURRENT_EPOCH_KEY: &str = "new_current_epoch";
 
 // interval
 pub const EVENTS_EXECUTED_KEY: &str = "number_of_events_executed";
+pub const DELEGATIONS_COMPOUNDED_KEY: &str = "number_of_delegations_compounded";
+pub const FAILURE_REASON_KEY: &str = "failure_reason";
 pub const EVENT_CREATION_HEIGHT_KEY: &str = "created_at";
 pub const REWARDED_SET_NODES_KEY: &str = "rewarded_set_nodes";
 pub const NEW_EPOCHS_DURATION_SECS_KEY: &str = "new_epoch_durations_secs";
@@ -201,6 +244,44 @@ pub fn new_withdraw_delegator_reward_event(delegator: &Addr, amount: Coin, mix_i
         .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
 }
 
+pub fn new_delegation_auto_compounding_update_event(
+    delegator: &Addr,
+    mix_id: MixId,
+    enabled: bool,
+) -> Event {
+    Event::new(MixnetEventType::DelegationAutoCompoundingUpdate)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
+        .add_attribute(AUTO_COMPOUND_KEY, enabled.to_string())
+}
+
+pub fn new_delegator_reward_compounding_event(
+    delegator: &Addr,
+    proxy: &Option<Addr>,
+    amount: Coin,
+    mix_id: MixId,
+) -> Event {
+    let mut event = Event::new(MixnetEventType::DelegatorRewardCompounding)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string());
+
+    if let Some(proxy) = proxy {
+        event = event.add_attribute(PROXY_KEY, proxy)
+    }
+    event
+}
+
+pub fn new_auto_compounding_execution_event(compounded: u32) -> Event {
+    Event::new(MixnetEventType::AutoCompoundingExecution)
+        .add_attribute(DELEGATIONS_COMPOUNDED_KEY, compounded.to_string())
+}
+
+pub fn new_compounded_reward_tracking_failure_event(reason: String) -> Event {
+    Event::new(MixnetEventType::CompoundedRewardTrackingFailure)
+        .add_attribute(FAILURE_REASON_KEY, reason)
+}
+
 pub fn new_active_set_update_event(created_at: BlockHeight, new_size: u32) -> Event {
     Event::new(MixnetEventType::ActiveSetUpdate)
         .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
@@ -265,6 +346,41 @@ pub fn new_pending_undelegation_event(delegator: &Addr, mix_id: MixId) -> Event
         .add_attribute(MIX_ID_KEY, mix_id.to_string())
 }
 
+pub fn new_redelegation_event(
+    created_at: BlockHeight,
+    delegator: &Addr,
+    amount: &Coin,
+    from: MixId,
+    to: MixId,
+) -> Event {
+    Event::new(MixnetEventType::Redelegation)
+        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(REDELEGATION_SOURCE_KEY, from.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, to.to_string())
+}
+
+pub fn new_redelegation_on_unbonded_node_event(delegator: &Addr, from: MixId, to: MixId) -> Event {
+    Event::new(MixnetEventType::RedelegationOnUnbonding)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(REDELEGATION_SOURCE_KEY, from.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, to.to_string())
+}
+
+pub fn new_pending_redelegation_event(
+    delegator: &Addr,
+    amount: &Coin,
+    from: MixId,
+    to: MixId,
+) -> Event {
+    Event::new(MixnetEventType::PendingRedelegation)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(REDELEGATION_SOURCE_KEY, from.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, to.to_string())
+}
+
 pub fn new_gateway_bonding_event(
     owner: &Addr,
     amount: &Coin,
@@ -547,3 +663,140 @@ pub fn new_pending_interval_config_update_event(
             approximate_time_remaining_secs.to_string(),
         )
 }
+
+pub fn new_gateway_pending_cost_params_update_event(
+    identity: IdentityKeyRef<'_>,
+    owner: &Addr,
+    new_costs: &MixNodeCostParams,
+) -> Event {
+    Event::new(MixnetEventType::PendingGatewayCostParamsUpdate)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(UPDATED_GATEWAY_COST_PARAMS_KEY, new_costs.to_inline_json())
+}
+
+pub fn new_gateway_cost_params_update_event(
+    created_at: BlockHeight,
+    identity: IdentityKeyRef<'_>,
+    new_costs: &MixNodeCostParams,
+) -> Event {
+    Event::new(MixnetEventType::GatewayCostParamsUpdate)
+        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(UPDATED_GATEWAY_COST_PARAMS_KEY, new_costs.to_inline_json())
+}
+
+pub fn new_gateway_rewarding_params_update_event(updated: &GatewayRewardingParams) -> Event {
+    Event::new(MixnetEventType::GatewayRewardingParamsUpdate).add_attribute(
+        UPDATED_GATEWAY_REWARDING_PARAMS_KEY,
+        updated.to_inline_json(),
+    )
+}
+
+pub fn new_pending_gateway_delegation_event(
+    delegator: &Addr,
+    amount: &Coin,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(MixnetEventType::PendingGatewayDelegation)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, identity)
+}
+
+pub fn new_gateway_delegation_event(
+    created_at: BlockHeight,
+    delegator: &Addr,
+    amount: &Coin,
+    identity: IdentityKeyRef<'_>,
+    unit_reward: Decimal,
+) -> Event {
+    Event::new(MixnetEventType::GatewayDelegation)
+        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, identity)
+        .add_attribute(UNIT_REWARD_KEY, unit_reward.to_string())
+}
+
+pub fn new_gateway_delegation_on_unbonded_node_event(
+    delegator: &Addr,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(MixnetEventType::GatewayDelegationOnUnbonding)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(DELEGATION_TARGET_KEY, identity)
+}
+
+pub fn new_pending_gateway_undelegation_event(
+    delegator: &Addr,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(MixnetEventType::PendingGatewayUndelegation)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+}
+
+pub fn new_gateway_undelegation_event(
+    created_at: BlockHeight,
+    delegator: &Addr,
+    identity: IdentityKeyRef<'_>,
+    amount: &Coin,
+) -> Event {
+    Event::new(MixnetEventType::GatewayUndelegation)
+        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+}
+
+pub fn new_not_found_gateway_operator_rewarding_event(
+    interval: Interval,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(MixnetEventType::GatewayRewarding)
+        .add_attribute(
+            INTERVAL_KEY,
+            interval.current_epoch_absolute_id().to_string(),
+        )
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(NO_REWARD_REASON_KEY, BOND_NOT_FOUND_VALUE)
+}
+
+pub fn new_zero_uptime_gateway_operator_rewarding_event(
+    interval: Interval,
+    identity: IdentityKeyRef<'_>,
+) -> Event {
+    Event::new(MixnetEventType::GatewayRewarding)
+        .add_attribute(
+            INTERVAL_KEY,
+            interval.current_epoch_absolute_id().to_string(),
+        )
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(NO_REWARD_REASON_KEY, ZERO_PERFORMANCE_VALUE)
+}
+
+pub fn new_gateway_rewarding_event(
+    interval: Interval,
+    identity: IdentityKeyRef<'_>,
+    reward_distribution: RewardDistribution,
+    prior_delegates: Decimal,
+    prior_unit_reward: Decimal,
+) -> Event {
+    Event::new(MixnetEventType::GatewayRewarding)
+        .add_attribute(
+            INTERVAL_KEY,
+            interval.current_epoch_absolute_id().to_string(),
+        )
+        .add_attribute(PRIOR_DELEGATES_KEY, prior_delegates.to_string())
+        .add_attribute(PRIOR_UNIT_REWARD_KEY, prior_unit_reward.to_string())
+        .add_attribute(NODE_IDENTITY_KEY, identity)
+        .add_attribute(
+            OPERATOR_REWARD_KEY,
+            reward_distribution.operator.to_string(),
+        )
+        .add_attribute(
+            DELEGATES_REWARD_KEY,
+            reward_distribution.delegates.to_string(),
+        )
+}
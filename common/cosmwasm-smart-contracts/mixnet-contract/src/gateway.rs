@@ -1,9 +1,13 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::constants::TOKEN_SUPPLY;
+use crate::helpers::IntoBaseDecimal;
+use crate::mixnode::MixNodeRewarding;
+use crate::reward_params::Performance;
 use crate::{IdentityKey, SphinxKey};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Decimal, StdResult};
 use std::cmp::Ordering;
 use std::fmt::Display;
 
@@ -196,6 +200,164 @@ pub struct GatewayBondResponse {
     pub gateway: Option<GatewayBond>,
 }
 
+/// Rewarding information of a gateway.
+// gateways share the exact same stake accounting (operator pledge, delegations and the "unit delegation")
+// with mixnodes, the only difference being how the reward for each epoch is determined.
+pub type GatewayRewarding = MixNodeRewarding;
+
+/// Parameters used for determining the rewards of all gateways in each epoch.
+#[cw_serde]
+#[derive(Copy)]
+pub struct GatewayRewardingParams {
+    /// Amount of tokens distributed among all gateways in each epoch,
+    /// assuming all of them had perfect performance and were fully saturated.
+    pub epoch_reward_budget: Decimal,
+
+    /// Amount of tokens (pledge and delegations combined) at which a gateway becomes fully saturated.
+    pub stake_saturation_point: Decimal,
+
+    /// The expected number of gateways among which the epoch reward budget is split.
+    pub rewarded_gateways: u32,
+}
+
+impl GatewayRewardingParams {
+    /// Parameters under which none of the gateways are going to receive any rewards.
+    pub fn disabled(stake_saturation_point: Decimal) -> Self {
+        GatewayRewardingParams {
+            epoch_reward_budget: Decimal::zero(),
+            stake_saturation_point,
+            rewarded_gateways: 0,
+        }
+    }
+
+    pub fn to_inline_json(&self) -> String {
+        serde_json_wasm::to_string(self).unwrap_or_else(|_| "serialisation failure".into())
+    }
+
+    /// Saturation over all the tokens staked over this gateway.
+    pub fn bond_saturation(&self, gateway_rewarding: &GatewayRewarding) -> Decimal {
+        // make sure our saturation is never greater than 1
+        if self.stake_saturation_point.is_zero()
+            || gateway_rewarding.node_bond() > self.stake_saturation_point
+        {
+            Decimal::one()
+        } else {
+            gateway_rewarding.node_bond() / self.stake_saturation_point
+        }
+    }
+
+    /// Determines the total reward of the gateway, i.e. the amount to be split between the operator
+    /// and its delegators, for the epoch given its measured performance.
+    pub fn node_reward(
+        &self,
+        gateway_rewarding: &GatewayRewarding,
+        performance: Performance,
+    ) -> Decimal {
+        if self.rewarded_gateways == 0 {
+            return Decimal::zero();
+        }
+
+        self.epoch_reward_budget
+            * Decimal::from_ratio(1u32, self.rewarded_gateways)
+            * performance.value()
+            * self.bond_saturation(gateway_rewarding)
+    }
+}
+
+/// Information about tokens being delegated towards given gateway in order to accrue rewards
+/// with its work.
+#[cw_serde]
+pub struct GatewayDelegation {
+    /// Address of the owner of this delegation.
+    pub owner: Addr,
+
+    /// Identity key of the gateway that this delegation was performed against.
+    pub gateway_identity: IdentityKey,
+
+    /// Value of the "unit delegation" associated with the gateway at the time of delegation.
+    pub cumulative_reward_ratio: Decimal,
+
+    /// Original delegation amount. Note that it is never mutated as delegation accumulates rewards.
+    pub amount: Coin,
+
+    /// Block height where this delegation occurred.
+    pub height: u64,
+}
+
+pub type GatewayDelegationStorageKey = (IdentityKey, String);
+
+impl GatewayDelegation {
+    pub fn new(
+        owner: Addr,
+        gateway_identity: IdentityKey,
+        cumulative_reward_ratio: Decimal,
+        amount: Coin,
+        height: u64,
+    ) -> Self {
+        assert!(
+            amount.amount <= TOKEN_SUPPLY,
+            "delegation cannot be larger than the token supply"
+        );
+
+        GatewayDelegation {
+            owner,
+            gateway_identity,
+            cumulative_reward_ratio,
+            amount,
+            height,
+        }
+    }
+
+    pub fn generate_storage_key(
+        gateway_identity: IdentityKey,
+        owner_address: &Addr,
+    ) -> GatewayDelegationStorageKey {
+        (gateway_identity, owner_address.to_string())
+    }
+
+    pub fn dec_amount(&self) -> StdResult<Decimal> {
+        self.amount.amount.into_base_decimal()
+    }
+
+    pub fn storage_key(&self) -> GatewayDelegationStorageKey {
+        Self::generate_storage_key(self.gateway_identity.clone(), &self.owner)
+    }
+
+    /// Determines the reward accumulated by this delegation that has not yet been claimed.
+    pub fn pending_reward(&self, gateway_rewarding: &GatewayRewarding) -> StdResult<Decimal> {
+        gateway_rewarding.determine_delegated_reward(self.cumulative_reward_ratio, &self.amount)
+    }
+}
+
+/// Response containing rewarding information of a gateway with the provided identity key.
+#[cw_serde]
+pub struct GatewayRewardingDetailsResponse {
+    /// The identity key (base58-encoded ed25519 public key) of the gateway.
+    pub identity: IdentityKey,
+
+    /// If there exists a gateway with the provided identity key, this field contains its rewarding information.
+    pub rewarding_details: Option<GatewayRewarding>,
+}
+
+/// Response containing paged list of all delegations made towards particular gateway.
+#[cw_serde]
+pub struct PagedGatewayDelegationsResponse {
+    /// Each individual delegation made.
+    pub delegations: Vec<GatewayDelegation>,
+
+    /// Field indicating paging information for the following queries if the caller wishes to get further entries.
+    pub start_next_after: Option<String>,
+}
+
+impl PagedGatewayDelegationsResponse {
+    pub fn new(delegations: Vec<GatewayDelegation>, start_next_after: Option<String>) -> Self {
+        PagedGatewayDelegationsResponse {
+            delegations,
+            start_next_after,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,4 +443,35 @@ mod tests {
         // same bond and delegation, so it's just ordered by height
         assert!(gate1 < gate2);
     }
+
+    #[test]
+    fn gateway_node_reward() {
+        let pledge = Coin::new(1_000_000_000_000, "unym");
+        let cost_params = crate::mixnode::MixNodeCostParams {
+            profit_margin_percent: crate::Percent::from_percentage_value(10).unwrap(),
+            interval_operating_cost: Coin::new(40_000_000, "unym"),
+        };
+        let rewarding = GatewayRewarding::initialise_new(cost_params, &pledge, 0).unwrap();
+
+        let params = GatewayRewardingParams {
+            epoch_reward_budget: Decimal::from_atomics(10_000_000u32, 0).unwrap(),
+            stake_saturation_point: Decimal::from_atomics(2_000_000_000_000u64, 0).unwrap(),
+            rewarded_gateways: 10,
+        };
+
+        // half saturated node with perfect performance gets half of its share of the budget
+        let reward = params.node_reward(&rewarding, Performance::hundred());
+        assert_eq!(reward, Decimal::from_atomics(500_000u32, 0).unwrap());
+
+        // and it scales linearly with performance
+        let reward =
+            params.node_reward(&rewarding, Performance::from_percentage_value(50).unwrap());
+        assert_eq!(reward, Decimal::from_atomics(250_000u32, 0).unwrap());
+
+        // nobody gets anything if the rewarding is disabled
+        let disabled = GatewayRewardingParams::disabled(params.stake_saturation_point);
+        assert!(disabled
+            .node_reward(&rewarding, Performance::hundred())
+            .is_zero());
+    }
 }
@@ -31,8 +31,9 @@ pub use families::{
     FamilyMembersByLabelResponse, PagedFamiliesResponse, PagedMembersResponse,
 };
 pub use gateway::{
-    Gateway, GatewayBond, GatewayBondResponse, GatewayConfigUpdate, GatewayOwnershipResponse,
-    PagedGatewayResponse,
+    Gateway, GatewayBond, GatewayBondResponse, GatewayConfigUpdate, GatewayDelegation,
+    GatewayOwnershipResponse, GatewayRewarding, GatewayRewardingDetailsResponse,
+    GatewayRewardingParams, PagedGatewayDelegationsResponse, PagedGatewayResponse,
 };
 pub use interval::{
     CurrentIntervalResponse, EpochId, EpochState, EpochStatus, Interval, IntervalId,
@@ -220,11 +220,36 @@ impl MixNodeRewarding {
         &mut self,
         delegation: &mut Delegation,
     ) -> Result<Coin, MixnetContractError> {
-        let reward = self.determine_delegation_reward(delegation)?;
+        let reward =
+            self.withdraw_delegated_reward(delegation.cumulative_reward_ratio, &delegation.amount)?;
+        delegation.cumulative_reward_ratio = self.full_reward_ratio();
+        Ok(reward)
+    }
+
+    /// Adds the reward accumulated by the delegation to its principal amount
+    /// and returns the amount that got compounded.
+    pub fn compound_delegator_reward(
+        &mut self,
+        delegation: &mut Delegation,
+    ) -> Result<Coin, MixnetContractError> {
+        let reward = self.withdraw_delegator_reward(delegation)?;
+        self.increase_delegates_uint128(reward.amount)?;
+        delegation.amount.amount += reward.amount;
+        Ok(reward)
+    }
+
+    /// Removes the reward accumulated by the delegation made at the provided reward ratio
+    /// without touching the underlying delegated amount.
+    /// It's the responsibility of the caller to update the ratio of the delegation afterwards.
+    pub fn withdraw_delegated_reward(
+        &mut self,
+        cumulative_reward_ratio: Decimal,
+        amount: &Coin,
+    ) -> Result<Coin, MixnetContractError> {
+        let reward = self.determine_delegated_reward(cumulative_reward_ratio, amount)?;
         self.decrease_delegates_decimal(reward)?;
 
-        delegation.cumulative_reward_ratio = self.full_reward_ratio();
-        Ok(truncate_reward(reward, &delegation.amount.denom))
+        Ok(truncate_reward(reward, &amount.denom))
     }
 
     pub fn node_bond(&self) -> Decimal {
@@ -351,11 +376,20 @@ impl MixNodeRewarding {
     }
 
     pub fn determine_delegation_reward(&self, delegation: &Delegation) -> StdResult<Decimal> {
-        let starting_ratio = delegation.cumulative_reward_ratio;
+        self.determine_delegated_reward(delegation.cumulative_reward_ratio, &delegation.amount)
+    }
+
+    /// Determines the reward accumulated by tokens delegated at the provided cumulative reward ratio.
+    pub fn determine_delegated_reward(
+        &self,
+        cumulative_reward_ratio: Decimal,
+        amount: &Coin,
+    ) -> StdResult<Decimal> {
+        let starting_ratio = cumulative_reward_ratio;
         let ending_ratio = self.full_reward_ratio();
         let adjust = starting_ratio + self.unit_delegation;
 
-        Ok((ending_ratio - starting_ratio) * delegation.dec_amount()? / adjust)
+        Ok((ending_ratio - starting_ratio) * amount.amount.into_base_decimal()? / adjust)
     }
 
     // this updates `unique_delegations` field
@@ -445,10 +479,20 @@ impl MixNodeRewarding {
     }
 
     pub fn undelegate(&mut self, delegation: &Delegation) -> Result<Coin, MixnetContractError> {
-        let reward = self.determine_delegation_reward(delegation)?;
-        let full_amount = reward + delegation.dec_amount()?;
+        self.remove_delegated(delegation.cumulative_reward_ratio, &delegation.amount)
+    }
+
+    /// Completely removes the tokens delegated at the provided cumulative reward ratio
+    /// and returns them alongside all of their accumulated rewards.
+    pub fn remove_delegated(
+        &mut self,
+        cumulative_reward_ratio: Decimal,
+        amount: &Coin,
+    ) -> Result<Coin, MixnetContractError> {
+        let reward = self.determine_delegated_reward(cumulative_reward_ratio, amount)?;
+        let full_amount = reward + amount.amount.into_base_decimal()?;
         self.remove_delegation_decimal(full_amount)?;
-        Ok(truncate_reward(full_amount, &delegation.amount.denom))
+        Ok(truncate_reward(full_amount, &amount.denom))
     }
 
     pub fn decrease_delegates_decimal(
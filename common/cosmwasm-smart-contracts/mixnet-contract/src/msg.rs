@@ -4,7 +4,7 @@
 use crate::delegation::{self, OwnerProxySubKey};
 use crate::error::MixnetContractError;
 use crate::families::FamilyHead;
-use crate::gateway::{Gateway, GatewayConfigUpdate};
+use crate::gateway::{Gateway, GatewayConfigUpdate, GatewayRewardingParams};
 use crate::helpers::IntoBaseDecimal;
 use crate::mixnode::{Layer, MixNode, MixNodeConfigUpdate, MixNodeCostParams};
 use crate::pending_events::{EpochEventId, IntervalEventId};
@@ -28,7 +28,10 @@ use crate::{
         FamilyByHeadResponse, FamilyByLabelResponse, FamilyMembersByHeadResponse,
         FamilyMembersByLabelResponse, PagedFamiliesResponse, PagedMembersResponse,
     },
-    gateway::{GatewayBondResponse, GatewayOwnershipResponse, PagedGatewayResponse},
+    gateway::{
+        GatewayBondResponse, GatewayOwnershipResponse, GatewayRewardingDetailsResponse,
+        PagedGatewayDelegationsResponse, PagedGatewayResponse,
+    },
     interval::{CurrentIntervalResponse, EpochStatus},
     mixnode::{
         MixOwnershipResponse, MixnodeDetailsByIdentityResponse, MixnodeDetailsResponse,
@@ -242,6 +245,12 @@ pub enum ExecuteMsg {
         new_config: GatewayConfigUpdate,
         owner: String,
     },
+    UpdateGatewayCostParams {
+        new_costs: MixNodeCostParams,
+    },
+    UpdateGatewayRewardingParams {
+        updated_params: GatewayRewardingParams,
+    },
 
     // delegation-related:
     DelegateToMixnode {
@@ -258,12 +267,33 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         delegate: String,
     },
+    RedelegateFromMixnode {
+        from: MixId,
+        to: MixId,
+        amount: Coin,
+    },
+    RedelegateFromMixnodeOnBehalf {
+        from: MixId,
+        to: MixId,
+        amount: Coin,
+        delegate: String,
+    },
+    DelegateToGateway {
+        identity: IdentityKey,
+    },
+    UndelegateFromGateway {
+        identity: IdentityKey,
+    },
 
     // reward-related
     RewardMixnode {
         mix_id: MixId,
         performance: Performance,
     },
+    RewardGateway {
+        identity: IdentityKey,
+        performance: Performance,
+    },
     WithdrawOperatorReward {},
     WithdrawOperatorRewardOnBehalf {
         owner: String,
@@ -275,6 +305,13 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         owner: String,
     },
+    /// Changes whether the rewards of the sender's delegations towards the specified mixnode
+    /// (including the ones made with the vesting contract tokens)
+    /// are going to be automatically compounded at the end of each epoch.
+    SetDelegationAutoCompounding {
+        mix_id: MixId,
+        enabled: bool,
+    },
 
     // vesting migration:
     MigrateVestedMixNode {},
@@ -370,6 +407,10 @@ impl ExecuteMsg {
             ExecuteMsg::UpdateGatewayConfigOnBehalf { .. } => {
                 "updating gateway configuration on behalf".into()
             }
+            ExecuteMsg::UpdateGatewayCostParams { .. } => "updating gateway cost parameters".into(),
+            ExecuteMsg::UpdateGatewayRewardingParams { .. } => {
+                "updating gateway rewarding parameters".into()
+            }
             ExecuteMsg::DelegateToMixnode { mix_id } => format!("delegating to mixnode {mix_id}"),
             ExecuteMsg::DelegateToMixnodeOnBehalf { mix_id, .. } => {
                 format!("delegating to mixnode {mix_id} on behalf")
@@ -380,10 +421,30 @@ impl ExecuteMsg {
             ExecuteMsg::UndelegateFromMixnodeOnBehalf { mix_id, .. } => {
                 format!("removing delegation from mixnode {mix_id} on behalf")
             }
+            ExecuteMsg::RedelegateFromMixnode { from, to, amount } => {
+                format!("moving {amount} of delegation from mixnode {from} to mixnode {to}")
+            }
+            ExecuteMsg::RedelegateFromMixnodeOnBehalf {
+                from, to, amount, ..
+            } => {
+                format!(
+                    "moving {amount} of delegation from mixnode {from} to mixnode {to} on behalf"
+                )
+            }
+            ExecuteMsg::DelegateToGateway { identity } => {
+                format!("delegating to gateway {identity}")
+            }
+            ExecuteMsg::UndelegateFromGateway { identity } => {
+                format!("removing delegation from gateway {identity}")
+            }
             ExecuteMsg::RewardMixnode {
                 mix_id,
                 performance,
             } => format!("rewarding mixnode {mix_id} for performance {performance}"),
+            ExecuteMsg::RewardGateway {
+                identity,
+                performance,
+            } => format!("rewarding gateway {identity} for performance {performance}"),
             ExecuteMsg::WithdrawOperatorReward { .. } => "withdrawing operator reward".into(),
             ExecuteMsg::WithdrawOperatorRewardOnBehalf { .. } => {
                 "withdrawing operator reward on behalf".into()
@@ -394,6 +455,13 @@ impl ExecuteMsg {
             ExecuteMsg::WithdrawDelegatorRewardOnBehalf { mix_id, .. } => {
                 format!("withdrawing delegator reward from mixnode {mix_id} on behalf")
             }
+            ExecuteMsg::SetDelegationAutoCompounding { mix_id, enabled } => {
+                if *enabled {
+                    format!("enabling reward compounding of delegation towards mixnode {mix_id}")
+                } else {
+                    format!("disabling reward compounding of delegation towards mixnode {mix_id}")
+                }
+            }
             ExecuteMsg::MigrateVestedMixNode { .. } => "migrate vested mixnode".into(),
             ExecuteMsg::MigrateVestedDelegation { .. } => "migrate vested delegation".to_string(),
 
@@ -617,6 +685,40 @@ pub enum QueryMsg {
         address: String,
     },
 
+    /// Gets the current parameters used for gateway rewarding.
+    #[cfg_attr(feature = "schema", returns(GatewayRewardingParams))]
+    GetGatewayRewardingParams {},
+
+    /// Gets the rewarding information of a gateway given its identity key.
+    #[cfg_attr(feature = "schema", returns(GatewayRewardingDetailsResponse))]
+    GetGatewayRewardingDetails {
+        /// The identity key (base58-encoded ed25519 public key) of the gateway used for the query.
+        identity: IdentityKey,
+    },
+
+    /// Gets all delegations associated with particular gateway
+    #[cfg_attr(feature = "schema", returns(PagedGatewayDelegationsResponse))]
+    GetGatewayDelegations {
+        /// The identity key (base58-encoded ed25519 public key) of the gateway used for the query.
+        identity: IdentityKey,
+
+        /// Pagination control for the values returned by the query. Note that the provided value itself will **not** be used for the response.
+        start_after: Option<String>,
+
+        /// Controls the maximum number of entries returned by the query. Note that too large values will be overwritten by a saner default.
+        limit: Option<u32>,
+    },
+
+    /// Gets the reward amount accrued by the particular gateway delegator that has not yet been claimed.
+    #[cfg_attr(feature = "schema", returns(PendingRewardResponse))]
+    GetPendingGatewayDelegatorReward {
+        /// Address of the delegator to use for the query.
+        address: String,
+
+        /// The identity key (base58-encoded ed25519 public key) of the gateway used for the query.
+        identity: IdentityKey,
+    },
+
     // delegation-related:
     /// Gets all delegations associated with particular mixnode
     #[cfg_attr(feature = "schema", returns(PagedMixNodeDelegationsResponse))]
@@ -777,4 +879,9 @@ pub enum QueryMsg {
 #[cw_serde]
 pub struct MigrateMsg {
     pub vesting_contract_address: Option<String>,
+
+    /// Parameters used for gateway rewarding. If not provided, and the contract hasn't had them set before,
+    /// gateway rewarding will remain disabled until explicitly configured.
+    #[serde(default)]
+    pub gateway_rewarding_params: Option<GatewayRewardingParams>,
 }
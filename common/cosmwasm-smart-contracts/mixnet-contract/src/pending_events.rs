@@ -3,7 +3,7 @@
 
 use crate::mixnode::MixNodeCostParams;
 use crate::reward_params::IntervalRewardingParamsUpdate;
-use crate::{BlockHeight, MixId};
+use crate::{BlockHeight, IdentityKey, MixId};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Coin};
 
@@ -69,6 +69,28 @@ pub enum PendingEpochEventKind {
         proxy: Option<Addr>,
     },
 
+    /// Request to move (part of) an existing delegation from one mixnode to another.
+    /// Note that if the full delegation is moved, all of its accumulated rewards will get moved with it.
+    #[serde(alias = "Redelegate")]
+    #[non_exhaustive]
+    Redelegate {
+        /// The address of the owner of the delegation.
+        owner: Addr,
+
+        /// The id of the mixnode the tokens are moved away from.
+        from: MixId,
+
+        /// The id of the mixnode the tokens are moved towards.
+        to: MixId,
+
+        /// The amount of tokens to move between the delegations.
+        amount: Coin,
+
+        /// Entity who made the redelegation on behalf of the owner.
+        /// If present, it's most likely the address of the vesting contract.
+        proxy: Option<Addr>,
+    },
+
     /// Request to pledge more tokens (by the node operator) towards its node.
     #[serde(alias = "PledgeMore")]
     PledgeMore {
@@ -102,6 +124,28 @@ pub enum PendingEpochEventKind {
         /// The new desired size of the active set.
         new_size: u32,
     },
+
+    /// Request to create a delegation towards particular gateway.
+    /// Note that if such delegation already exists, it will get updated with the provided token amount.
+    DelegateToGateway {
+        /// The address of the owner of the delegation.
+        owner: Addr,
+
+        /// The identity key of the gateway used for the delegation.
+        identity: IdentityKey,
+
+        /// The amount of tokens to use for the delegation.
+        amount: Coin,
+    },
+
+    /// Request to remove delegation from particular gateway.
+    UndelegateFromGateway {
+        /// The address of the owner of the delegation.
+        owner: Addr,
+
+        /// The identity key of the gateway used for the delegation.
+        identity: IdentityKey,
+    },
 }
 
 impl PendingEpochEventKind {
@@ -128,6 +172,16 @@ impl PendingEpochEventKind {
             proxy: None,
         }
     }
+
+    pub fn new_redelegate(owner: Addr, from: MixId, to: MixId, amount: Coin) -> Self {
+        PendingEpochEventKind::Redelegate {
+            owner,
+            from,
+            to,
+            amount,
+            proxy: None,
+        }
+    }
 }
 
 impl From<(EpochEventId, PendingEpochEventData)> for PendingEpochEvent {
@@ -172,6 +226,15 @@ pub enum PendingIntervalEventKind {
         new_costs: MixNodeCostParams,
     },
 
+    /// Request to update cost parameters of given gateway.
+    ChangeGatewayCostParams {
+        /// The identity key of the gateway that will have its cost parameters updated.
+        identity: IdentityKey,
+
+        /// The new updated cost function of this gateway.
+        new_costs: MixNodeCostParams,
+    },
+
     /// Request to update the underlying rewarding parameters used by the system
     #[serde(alias = "UpdateRewardingParams")]
     UpdateRewardingParams {
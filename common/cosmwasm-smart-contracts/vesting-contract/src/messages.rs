@@ -55,6 +55,10 @@ pub enum ExecuteMsg {
     TrackReward {
         amount: Coin,
         address: String,
+        /// If set, the reward has not been paid out, but instead got compounded
+        /// into the delegation towards the specified mixnode.
+        #[serde(default)]
+        compounded_into: Option<MixId>,
     },
     ClaimOperatorReward {},
     ClaimDelegatorReward {
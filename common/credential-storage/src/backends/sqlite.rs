@@ -135,6 +135,44 @@ impl SqliteEcashTicketbookManager {
             .await
     }
 
+    pub(crate) async fn update_pending_ticketbook_data(
+        &self,
+        deposit_id: i64,
+        data: &[u8],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE pending_issuance SET pending_ticketbook_data = ? WHERE deposit_id = ?",
+            data,
+            deposit_id
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn get_all_ticketbooks(
+        &self,
+    ) -> Result<Vec<StoredIssuedTicketbook>, sqlx::Error> {
+        sqlx::query_as("SELECT * FROM ecash_ticketbook")
+            .fetch_all(&self.connection_pool)
+            .await
+    }
+
+    pub(crate) async fn update_ticketbook_data(
+        &self,
+        ticketbook_id: i64,
+        data: &[u8],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE ecash_ticketbook SET ticketbook_data = ? WHERE id = ?",
+            data,
+            ticketbook_id
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
     pub(crate) async fn remove_pending_ticketbook(
         &self,
         pending_id: i64,
@@ -27,6 +27,13 @@ pub enum StorageError {
 
     #[error("Database unique constraint violation. Is the credential already imported?")]
     ConstraintUnique,
+
+    #[error("the stored credential data is encrypted, but no storage cipher has been provided")]
+    MissingStorageCipher,
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "persistent-storage"))]
+    #[error("failed to encrypt or decrypt the stored credential data - {0}")]
+    CipherFailure(#[from] nym_store_cipher::Error),
 }
 
 impl StorageError {
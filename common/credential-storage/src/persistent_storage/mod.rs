@@ -19,8 +19,10 @@ use nym_compact_ecash::VerificationKeyAuth;
 use nym_credentials::ecash::bandwidth::serialiser::VersionedSerialise;
 use nym_credentials::{IssuanceTicketBook, IssuedTicketBook};
 use nym_ecash_time::{ecash_today, Date, EcashTime};
+use nym_store_cipher::{is_encrypted_blob, StoreCipher};
 use sqlx::ConnectOptions;
 use std::path::Path;
+use std::sync::Arc;
 use zeroize::Zeroizing;
 
 mod helpers;
@@ -29,6 +31,9 @@ mod helpers;
 #[derive(Clone)]
 pub struct PersistentStorage {
     storage_manager: SqliteEcashTicketbookManager,
+
+    /// Optional cipher used for keeping the ticketbook data encrypted at rest.
+    cipher: Option<Arc<StoreCipher>>,
 }
 
 impl PersistentStorage {
@@ -64,8 +69,68 @@ impl PersistentStorage {
 
         Ok(PersistentStorage {
             storage_manager: SqliteEcashTicketbookManager::new(connection_pool.clone()),
+            cipher: None,
         })
     }
+
+    /// Makes the storage encrypt all ticketbook data it persists from now on.
+    /// Data stored before the cipher has been provided can still be read.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<StoreCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn seal(&self, data: Zeroizing<Vec<u8>>) -> Result<Zeroizing<Vec<u8>>, StorageError> {
+        match &self.cipher {
+            Some(cipher) => Ok(Zeroizing::new(cipher.encrypt_to_blob(&data)?)),
+            None => Ok(data),
+        }
+    }
+
+    fn unseal(&self, data: &[u8]) -> Result<Zeroizing<Vec<u8>>, StorageError> {
+        if !is_encrypted_blob(data) {
+            return Ok(Zeroizing::new(data.to_vec()));
+        }
+        let Some(cipher) = &self.cipher else {
+            return Err(StorageError::MissingStorageCipher);
+        };
+        Ok(Zeroizing::new(cipher.decrypt_blob(data)?))
+    }
+
+    /// Encrypts all ticketbook data that has been stored in plaintext,
+    /// for example before the cipher has been provided.
+    /// Returns the number of entries that got encrypted.
+    pub async fn encrypt_plaintext_entries(&self) -> Result<usize, StorageError> {
+        let Some(cipher) = &self.cipher else {
+            return Err(StorageError::MissingStorageCipher);
+        };
+
+        let mut encrypted = 0;
+        for pending in self.storage_manager.get_pending_ticketbooks().await? {
+            if is_encrypted_blob(&pending.pending_ticketbook_data) {
+                continue;
+            }
+            let blob = cipher.encrypt_to_blob(&pending.pending_ticketbook_data)?;
+            self.storage_manager
+                .update_pending_ticketbook_data(pending.deposit_id, &blob)
+                .await?;
+            encrypted += 1;
+        }
+
+        for ticketbook in self.storage_manager.get_all_ticketbooks().await? {
+            if is_encrypted_blob(&ticketbook.ticketbook_data) {
+                continue;
+            }
+            let blob = cipher.encrypt_to_blob(&ticketbook.ticketbook_data)?;
+            self.storage_manager
+                .update_ticketbook_data(ticketbook.id, &blob)
+                .await?;
+            encrypted += 1;
+        }
+
+        Ok(encrypted)
+    }
 }
 
 #[async_trait]
@@ -86,7 +151,7 @@ impl Storage for PersistentStorage {
         ticketbook: &IssuanceTicketBook,
     ) -> Result<(), Self::StorageError> {
         let ser = ticketbook.pack();
-        let data = Zeroizing::new(ser.data);
+        let data = self.seal(Zeroizing::new(ser.data))?;
         let serialisation_revision = ser.revision;
 
         self.storage_manager
@@ -106,7 +171,7 @@ impl Storage for PersistentStorage {
         ticketbook: &IssuedTicketBook,
     ) -> Result<(), Self::StorageError> {
         let ser = ticketbook.pack();
-        let data = Zeroizing::new(ser.data);
+        let data = self.seal(Zeroizing::new(ser.data))?;
         let serialisation_revision = ser.revision;
 
         self.storage_manager
@@ -139,7 +204,8 @@ impl Storage for PersistentStorage {
             .await?
             .into_iter()
             .map(|p| {
-                IssuanceTicketBook::try_unpack(&p.pending_ticketbook_data, p.serialization_revision)
+                let data = self.unseal(&p.pending_ticketbook_data)?;
+                IssuanceTicketBook::try_unpack(&data, p.serialization_revision)
                     .map_err(|err| {
                         StorageError::database_inconsistency(format!(
                             "failed to deserialise stored pending ticketbook: {err}"
@@ -179,13 +245,13 @@ impl Storage for PersistentStorage {
             return Ok(None);
         };
 
-        let mut deserialised =
-            IssuedTicketBook::try_unpack(&raw.ticketbook_data, raw.serialization_revision)
-                .map_err(|err| {
-                    StorageError::database_inconsistency(format!(
-                        "failed to deserialise stored ticketbook: {err}"
-                    ))
-                })?;
+        let data = self.unseal(&raw.ticketbook_data)?;
+        let mut deserialised = IssuedTicketBook::try_unpack(&data, raw.serialization_revision)
+            .map_err(|err| {
+                StorageError::database_inconsistency(format!(
+                    "failed to deserialise stored ticketbook: {err}"
+                ))
+            })?;
 
         increase_used_ticketbook_tickets(&mut tx, raw.id, tickets).await?;
         tx.commit().await?;
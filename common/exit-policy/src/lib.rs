@@ -6,9 +6,12 @@ pub mod policy;
 #[cfg(feature = "client")]
 pub mod client;
 
+#[cfg(feature = "upstream")]
+pub mod upstream;
+
 pub use crate::policy::{
-    AddressPolicy, AddressPolicyAction, AddressPolicyRule, AddressPortPattern, PolicyError,
-    PortRange,
+    AddressPolicy, AddressPolicyAction, AddressPolicyRule, AddressPortPattern, PolicyDiff,
+    PolicyError, PortRange,
 };
 
 pub(crate) const EXIT_POLICY_FIELD_NAME: &str = "ExitPolicy";
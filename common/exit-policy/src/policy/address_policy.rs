@@ -174,6 +174,45 @@ impl AddressPolicy {
     pub fn push_rule(&mut self, rule: AddressPolicyRule) {
         self.rules.push(rule)
     }
+
+    /// Get the rules of this policy in the order they are applied.
+    pub fn rules(&self) -> &[AddressPolicyRule] {
+        &self.rules
+    }
+
+    /// Determine which rules have been added and removed in the updated version of this policy.
+    ///
+    /// Note that the diff does not capture the change in the order of rules.
+    pub fn diff(&self, updated: &AddressPolicy) -> PolicyDiff {
+        let mut added = updated.rules.clone();
+        let mut removed = Vec::new();
+
+        for rule in &self.rules {
+            match added.iter().position(|new| new == rule) {
+                // the rule is present in both versions
+                Some(idx) => {
+                    added.remove(idx);
+                }
+                None => removed.push(rule.clone()),
+            }
+        }
+
+        PolicyDiff { added, removed }
+    }
+}
+
+/// Rules that have been added and removed between two versions of an AddressPolicy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PolicyDiff {
+    pub added: Vec<AddressPolicyRule>,
+    pub removed: Vec<AddressPolicyRule>,
+}
+
+impl PolicyDiff {
+    /// Check whether both versions of the policy contain exactly the same rules.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
 
 /// A single rule in an address policy.
@@ -566,6 +605,25 @@ impl FromStr for PortRange {
 mod test {
     use super::*;
 
+    #[test]
+    fn policy_diff() {
+        let old = AddressPolicy::parse_from_torrc(
+            "ExitPolicy reject 1.2.3.4/32:*\nExitPolicy accept *:80\nExitPolicy reject *:*",
+        )
+        .unwrap();
+        let new = AddressPolicy::parse_from_torrc(
+            "ExitPolicy accept *:80\nExitPolicy accept *:443\nExitPolicy reject *:*",
+        )
+        .unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["accept *:443".parse().unwrap()]);
+        assert_eq!(diff.removed, vec!["reject 1.2.3.4/32:*".parse().unwrap()]);
+
+        assert!(old.diff(&old).is_empty());
+        assert!(AddressPolicy::new().diff(&AddressPolicy::new()).is_empty());
+    }
+
     #[test]
     fn test_bad_rules() {
         fn check(s: &str) {
@@ -16,6 +16,10 @@ pub enum PolicyError {
         source: reqwest::Error,
     },
 
+    #[cfg(feature = "client")]
+    #[error("there is no upstream source the exit policy could be refreshed from")]
+    NoUpstreamSource,
+
     #[error("/{mask} is not a valid mask for an IpV4 address")]
     InvalidIpV4Mask { mask: u8 },
 
@@ -9,6 +9,7 @@ mod address_policy;
 mod error;
 
 pub use address_policy::{
-    AddressPolicy, AddressPolicyAction, AddressPolicyRule, AddressPortPattern, IpPattern, PortRange,
+    AddressPolicy, AddressPolicyAction, AddressPolicyRule, AddressPortPattern, IpPattern,
+    PolicyDiff, PortRange,
 };
 pub use error::PolicyError;
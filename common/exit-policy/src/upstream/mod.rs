@@ -0,0 +1,195 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::get_exit_policy;
+use crate::{ExitPolicy, PolicyError};
+use reqwest::Url;
+use std::sync::{Arc, RwLock};
+use time::OffsetDateTime;
+use tracing::{info, trace};
+
+pub mod refresher;
+
+pub use refresher::ExitPolicyRefresher;
+
+/// Exit policy shared between all the components that need to either enforce or expose it.
+pub type SharedExitPolicy = Arc<UpstreamExitPolicy>;
+
+#[derive(Debug)]
+struct CurrentPolicy {
+    policy: Arc<ExitPolicy>,
+    last_updated: OffsetDateTime,
+}
+
+impl CurrentPolicy {
+    fn new(policy: ExitPolicy) -> Self {
+        CurrentPolicy {
+            policy: Arc::new(policy),
+            last_updated: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+/// Exit policy, alongside its (optional) upstream source it can be refreshed from.
+#[derive(Debug)]
+pub struct UpstreamExitPolicy {
+    upstream: Option<Url>,
+    current: RwLock<CurrentPolicy>,
+}
+
+impl From<ExitPolicy> for UpstreamExitPolicy {
+    fn from(value: ExitPolicy) -> Self {
+        UpstreamExitPolicy::new_from_policy(value)
+    }
+}
+
+impl UpstreamExitPolicy {
+    /// Retrieves the initial version of the policy from the provided upstream.
+    pub async fn new_upstream(upstream: Url) -> Result<Self, PolicyError> {
+        let policy = get_exit_policy(upstream.clone()).await?;
+        Ok(UpstreamExitPolicy {
+            upstream: Some(upstream),
+            current: RwLock::new(CurrentPolicy::new(policy)),
+        })
+    }
+
+    pub fn new_from_policy(policy: ExitPolicy) -> Self {
+        UpstreamExitPolicy {
+            upstream: None,
+            current: RwLock::new(CurrentPolicy::new(policy)),
+        }
+    }
+
+    pub fn policy(&self) -> Arc<ExitPolicy> {
+        Arc::clone(
+            &self
+                .current
+                .read()
+                .expect("exit policy lock got poisoned")
+                .policy,
+        )
+    }
+
+    /// Time of the last successful retrieval of the policy from the upstream.
+    pub fn last_updated(&self) -> OffsetDateTime {
+        self.current
+            .read()
+            .expect("exit policy lock got poisoned")
+            .last_updated
+    }
+
+    pub fn upstream(&self) -> Option<&Url> {
+        self.upstream.as_ref()
+    }
+
+    /// Attempts to retrieve the current version of the policy from the upstream and to swap it
+    /// with the one in use. On failure, the existing policy is left intact.
+    pub async fn refresh(&self) -> Result<(), PolicyError> {
+        let upstream = self
+            .upstream
+            .as_ref()
+            .ok_or(PolicyError::NoUpstreamSource)?;
+        let updated = get_exit_policy(upstream.clone()).await?;
+
+        let mut current = self.current.write().expect("exit policy lock got poisoned");
+        current.last_updated = OffsetDateTime::now_utc();
+        if *current.policy == updated {
+            trace!("the exit policy hasn't changed");
+            return Ok(());
+        }
+
+        let diff = current.policy.diff(&updated);
+        if diff.is_empty() {
+            info!("the rules of the exit policy got reordered");
+        } else {
+            info!(
+                "the exit policy has been updated: {} rule(s) added and {} rule(s) removed",
+                diff.added.len(),
+                diff.removed.len()
+            );
+            for rule in &diff.added {
+                info!("exit policy rule added: '{rule}'");
+            }
+            for rule in &diff.removed {
+                info!("exit policy rule removed: '{rule}'");
+            }
+        }
+        current.policy = Arc::new(updated);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_exit_policy;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // serves the provided policy to a single request
+    async fn serve_policy_once(listener: TcpListener, policy: &'static str) {
+        let (mut conn, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = conn.read(&mut buf).await.unwrap();
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{policy}",
+            policy.len()
+        );
+        conn.write_all(response.as_bytes()).await.unwrap();
+        conn.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn refreshing_updates_the_policy() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream: Url = format!("http://{}/policy", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+
+        let initial = parse_exit_policy("ExitPolicy reject *:*").unwrap();
+        let policy = UpstreamExitPolicy {
+            upstream: Some(upstream),
+            current: RwLock::new(CurrentPolicy::new(initial)),
+        };
+
+        let updated = "ExitPolicy accept *:53\nExitPolicy reject *:*";
+        let server = tokio::spawn(serve_policy_once(listener, updated));
+        policy.refresh().await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(*policy.policy(), parse_exit_policy(updated).unwrap());
+    }
+
+    #[tokio::test]
+    async fn last_good_policy_is_kept_when_refresh_fails() {
+        // get an address nothing is listening on
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream: Url = format!("http://{}/policy", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        drop(listener);
+
+        let initial = parse_exit_policy("ExitPolicy accept *:53\nExitPolicy reject *:*").unwrap();
+        let policy = UpstreamExitPolicy {
+            upstream: Some(upstream),
+            current: RwLock::new(CurrentPolicy::new(initial.clone())),
+        };
+        let last_updated = policy.last_updated();
+
+        assert!(policy.refresh().await.is_err());
+        assert_eq!(*policy.policy(), initial);
+        assert_eq!(policy.last_updated(), last_updated);
+    }
+
+    #[tokio::test]
+    async fn policy_without_upstream_cannot_be_refreshed() {
+        let policy = UpstreamExitPolicy::new_from_policy(ExitPolicy::new_open());
+        assert!(matches!(
+            policy.refresh().await,
+            Err(PolicyError::NoUpstreamSource)
+        ));
+        assert_eq!(*policy.policy(), ExitPolicy::new_open());
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::upstream::SharedExitPolicy;
+use nym_task::TaskClient;
+use std::time::Duration;
+use tokio::time::{interval_at, Instant};
+use tracing::{debug, trace, warn};
+
+/// Periodically retrieves the exit policy from its upstream so that any changes
+/// would get picked up without having to restart the node.
+pub struct ExitPolicyRefresher {
+    policy: SharedExitPolicy,
+    refresh_interval: Duration,
+    shutdown: TaskClient,
+}
+
+impl ExitPolicyRefresher {
+    pub fn new(policy: SharedExitPolicy, refresh_interval: Duration, shutdown: TaskClient) -> Self {
+        ExitPolicyRefresher {
+            policy,
+            refresh_interval,
+            shutdown,
+        }
+    }
+
+    async fn refresh(&self) {
+        trace!("refreshing the exit policy");
+        if let Err(err) = self.policy.refresh().await {
+            warn!("failed to refresh the exit policy: {err}. The previous policy is going to remain in use")
+        }
+    }
+
+    async fn run(&mut self) {
+        // the policy has just been retrieved during startup
+        let mut refresh_interval = interval_at(
+            Instant::now() + self.refresh_interval,
+            self.refresh_interval,
+        );
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("ExitPolicyRefresher: received shutdown");
+                }
+                _ = refresh_interval.tick() => self.refresh().await,
+            }
+        }
+        debug!("ExitPolicyRefresher: exiting");
+    }
+
+    pub fn start(mut self) {
+        tokio::spawn(async move { self.run().await });
+    }
+}
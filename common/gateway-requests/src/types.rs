@@ -93,12 +93,19 @@ pub enum SimpleGatewayRequestsError {
 
     #[error("the provided ticket has already been spent before at this gateway")]
     TicketReplay,
+
+    #[error("the client has exceeded its allowed sending rate and should back off for {retry_after_ms}ms")]
+    Throttled { retry_after_ms: u64 },
 }
 
 impl SimpleGatewayRequestsError {
     pub fn is_ticket_replay(&self) -> bool {
         matches!(self, SimpleGatewayRequestsError::TicketReplay)
     }
+
+    pub fn is_throttled(&self) -> bool {
+        matches!(self, SimpleGatewayRequestsError::Throttled { .. })
+    }
 }
 
 #[derive(Debug, Error)]
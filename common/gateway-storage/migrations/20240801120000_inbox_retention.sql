@@ -0,0 +1,14 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+ALTER TABLE message_store
+ADD COLUMN inserted_at TIMESTAMP WITHOUT TIME ZONE;
+
+-- we don't know when the existing messages got stored, so treat them as if they were inserted now
+-- (use the same rfc3339 representation as the one used when inserting new messages)
+UPDATE message_store
+SET inserted_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now');
+
+CREATE INDEX `message_store_inserted_at_index` ON `message_store` (`inserted_at`);
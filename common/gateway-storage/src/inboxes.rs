@@ -1,7 +1,8 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::models::StoredMessage;
+use crate::models::{InboxUsage, StoredMessage};
+use time::OffsetDateTime;
 
 #[derive(Clone)]
 pub(crate) struct InboxManager {
@@ -41,10 +42,12 @@ impl InboxManager {
         client_address_bs58: &str,
         content: Vec<u8>,
     ) -> Result<(), sqlx::Error> {
+        let inserted_at = OffsetDateTime::now_utc();
         sqlx::query!(
-            "INSERT INTO message_store(client_address_bs58, content) VALUES (?, ?)",
+            "INSERT INTO message_store(client_address_bs58, content, inserted_at) VALUES (?, ?, ?)",
             client_address_bs58,
             content,
+            inserted_at,
         )
         .execute(&self.connection_pool)
         .await?;
@@ -133,4 +136,71 @@ impl InboxManager {
             .await?;
         Ok(())
     }
+
+    /// Removes all messages that were inserted before the specified cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff`: messages stored before this time are going to get removed
+    ///
+    /// returns the number of removed messages.
+    pub(crate) async fn remove_messages_older_than(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query!("DELETE FROM message_store WHERE inserted_at < ?", cutoff)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Removes the oldest messages of every client whose stored messages exceed the specified size,
+    /// so that each inbox is brought back under the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_client_bytes`: maximum total size of messages that can be stored for a single client
+    ///
+    /// returns the number of removed messages.
+    pub(crate) async fn trim_inboxes(&self, max_client_bytes: i64) -> Result<u64, sqlx::Error> {
+        // for every message compute the total size of it and all the newer messages of the same client,
+        // anything above the limit is the oldest data that has to go
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM message_store
+                WHERE id IN (
+                    SELECT id FROM (
+                        SELECT
+                            id,
+                            SUM(LENGTH(content)) OVER (
+                                PARTITION BY client_address_bs58
+                                ORDER BY id DESC
+                            ) AS cumulative_size
+                        FROM message_store
+                    )
+                    WHERE cumulative_size > ?
+                );
+            "#,
+            max_client_bytes
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Retrieves the current aggregate usage of all the client inboxes.
+    pub(crate) async fn get_usage(&self) -> Result<InboxUsage, sqlx::Error> {
+        sqlx::query_as!(
+            InboxUsage,
+            r#"
+                SELECT
+                    COUNT(*) as "stored_messages!: i64",
+                    COALESCE(SUM(LENGTH(content)), 0) as "stored_bytes!: i64",
+                    COUNT(DISTINCT client_address_bs58) as "inboxes!: i64"
+                FROM message_store;
+            "#
+        )
+        .fetch_one(&self.connection_pool)
+        .await
+    }
 }
@@ -6,8 +6,8 @@ use bandwidth::BandwidthManager;
 use error::StorageError;
 use inboxes::InboxManager;
 use models::{
-    PersistedBandwidth, PersistedSharedKeys, RedemptionProposal, StoredMessage, VerifiedTicket,
-    WireguardPeer,
+    InboxUsage, PersistedBandwidth, PersistedSharedKeys, RedemptionProposal, StoredMessage,
+    VerifiedTicket, WireguardPeer,
 };
 use nym_credentials_interface::ClientTicket;
 use nym_gateway_requests::registration::handshake::SharedKeys;
@@ -105,6 +105,28 @@ pub trait Storage: Send + Sync {
     /// * `ids`: ids of the messages to remove
     async fn remove_messages(&self, ids: Vec<i64>) -> Result<(), StorageError>;
 
+    /// Removes all stored messages that were inserted before the specified cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff`: messages stored before this time are going to get removed
+    ///
+    /// returns the number of removed messages.
+    async fn remove_messages_older_than(&self, cutoff: OffsetDateTime)
+        -> Result<u64, StorageError>;
+
+    /// Removes the oldest messages of every client whose inbox exceeds the specified size.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_client_bytes`: maximum total size of messages that can be stored for a single client
+    ///
+    /// returns the number of removed messages.
+    async fn trim_inboxes(&self, max_client_bytes: i64) -> Result<u64, StorageError>;
+
+    /// Retrieves the current aggregate usage of all the client inboxes.
+    async fn inbox_usage(&self) -> Result<InboxUsage, StorageError>;
+
     /// Creates a new bandwidth entry for the particular client.
     async fn create_bandwidth_entry(&self, client_id: i64) -> Result<(), StorageError>;
 
@@ -389,6 +411,24 @@ impl Storage for PersistentStorage {
         Ok(())
     }
 
+    async fn remove_messages_older_than(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64, StorageError> {
+        Ok(self
+            .inbox_manager
+            .remove_messages_older_than(cutoff)
+            .await?)
+    }
+
+    async fn trim_inboxes(&self, max_client_bytes: i64) -> Result<u64, StorageError> {
+        Ok(self.inbox_manager.trim_inboxes(max_client_bytes).await?)
+    }
+
+    async fn inbox_usage(&self) -> Result<InboxUsage, StorageError> {
+        Ok(self.inbox_manager.get_usage().await?)
+    }
+
     async fn create_bandwidth_entry(&self, client_id: i64) -> Result<(), StorageError> {
         self.bandwidth_manager.insert_new_client(client_id).await?;
         Ok(())
@@ -22,6 +22,18 @@ pub struct StoredMessage {
     pub content: Vec<u8>,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InboxUsage {
+    /// Total number of messages stored for all the clients.
+    pub stored_messages: i64,
+
+    /// Total size (in bytes) of messages stored for all the clients.
+    pub stored_bytes: i64,
+
+    /// Number of distinct clients that have at least a single message stored.
+    pub inboxes: i64,
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct PersistedBandwidth {
     #[allow(dead_code)]
@@ -4,49 +4,63 @@
 pub mod addr_secp256k1;
 pub mod error;
 pub(crate) mod helpers;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 mod sign_secp256k1;
+pub mod transport;
 pub mod version;
 
 use crate::addr_secp256k1::AddrSecp256k1Response;
 use crate::error::LedgerError;
 use crate::helpers::path_bytes;
 use crate::sign_secp256k1::SignSecp256k1Response;
+use crate::transport::LedgerTransport;
 use crate::version::VersionResponse;
 use bip32::DerivationPath;
 use error::Result;
 use ledger_transport::APDUCommand;
 use ledger_transport_hid::hidapi::HidApi;
-use ledger_transport_hid::TransportNativeHID;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
-const CLA: u8 = 0x55;
-const INS_GET_VERSION: u8 = 0x00;
-const INS_SIGN_SECP256K1: u8 = 0x02;
-const INS_GET_ADDR_SECP256K1: u8 = 0x04;
+pub use ledger_transport_hid::TransportNativeHID;
 
-const PAYLOAD_TYPE_INIT: u8 = 0x00;
-const PAYLOAD_TYPE_ADD: u8 = 0x01;
-const PAYLOAD_TYPE_LAST: u8 = 0x02;
+pub(crate) const CLA: u8 = 0x55;
+pub(crate) const INS_GET_VERSION: u8 = 0x00;
+pub(crate) const INS_SIGN_SECP256K1: u8 = 0x02;
+pub(crate) const INS_GET_ADDR_SECP256K1: u8 = 0x04;
+
+pub(crate) const PAYLOAD_TYPE_INIT: u8 = 0x00;
+pub(crate) const PAYLOAD_TYPE_ADD: u8 = 0x01;
+pub(crate) const PAYLOAD_TYPE_LAST: u8 = 0x02;
 const CHUNK_SIZE: usize = 250;
 
 /// Manage hardware Ledger device with Cosmos specific operations, as described in the
 /// specification: https://github.com/cosmos/ledger-cosmos/blob/main/docs/APDUSPEC.md
-#[derive(Clone)]
-pub struct CosmosLedger {
+pub struct CosmosLedger<T = TransportNativeHID> {
     path: DerivationPath,
     prefix: String,
-    transport: Arc<TransportNativeHID>,
+    transport: Arc<T>,
+}
+
+impl<T> Clone for CosmosLedger<T> {
+    fn clone(&self) -> Self {
+        CosmosLedger {
+            path: self.path.clone(),
+            prefix: self.prefix.clone(),
+            transport: Arc::clone(&self.transport),
+        }
+    }
 }
 
-impl Debug for CosmosLedger {
+impl<T> Debug for CosmosLedger<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
         write!(f, "()")
     }
 }
 
-impl CosmosLedger {
+impl CosmosLedger<TransportNativeHID> {
     /// Create the connection to the first Ledger device that we can find.
     pub fn new(path: DerivationPath, prefix: String) -> Result<Self> {
         let api = HidApi::new()?;
@@ -58,6 +72,30 @@ impl CosmosLedger {
             transport,
         })
     }
+}
+
+impl<T: LedgerTransport> CosmosLedger<T> {
+    /// Create the connection to the device behind the provided transport.
+    pub fn new_with_transport(path: DerivationPath, prefix: String, transport: T) -> Self {
+        CosmosLedger {
+            path,
+            prefix,
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// Create a handle for a different account on the same device.
+    pub fn with_path(&self, path: DerivationPath) -> Self {
+        CosmosLedger {
+            path,
+            prefix: self.prefix.clone(),
+            transport: Arc::clone(&self.transport),
+        }
+    }
+
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
 
     /// Get the version of the device.
     pub fn get_version(&self) -> Result<VersionResponse> {
@@ -101,6 +139,7 @@ impl CosmosLedger {
         AddrSecp256k1Response::try_from(response)
     }
 
+    /// Sign the provided message (i.e. the amino JSON encoded sign doc) with the SECP256K1 key of the device.
     pub fn sign_secp256k1(&self, message: String) -> Result<SignSecp256k1Response> {
         let serialized_path: Vec<u8> = path_bytes(self.path.clone())?
             .into_iter()
@@ -138,3 +177,77 @@ impl CosmosLedger {
         Err(LedgerError::NoMessageFound)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockLedgerDevice;
+    use k256::ecdsa::signature::Verifier;
+    use k256::ecdsa::SigningKey;
+
+    const TEST_ADDRESS: &str = "n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf";
+
+    fn mock_ledger() -> CosmosLedger<MockLedgerDevice> {
+        let signing_key = SigningKey::from_bytes(&[42u8; 32].into()).unwrap();
+        CosmosLedger::new_with_transport(
+            "m/44'/118'/0'/0/0".parse().unwrap(),
+            "n".to_string(),
+            MockLedgerDevice::new(signing_key, TEST_ADDRESS),
+        )
+    }
+
+    #[test]
+    fn getting_version() {
+        let version = mock_ledger().get_version().unwrap();
+        assert!(!version.test_mode);
+        assert!(!version.device_locked);
+        assert_eq!((version.major, version.minor, version.patch), (2, 34, 12));
+    }
+
+    #[test]
+    fn getting_address() {
+        let ledger = mock_ledger();
+        let response = ledger.get_addr_secp256k1(false).unwrap();
+
+        let expected_key: k256::PublicKey =
+            (*ledger.transport.signing_key().verifying_key()).into();
+        assert_eq!(response.address, TEST_ADDRESS);
+        assert_eq!(response.public_key, expected_key);
+    }
+
+    #[test]
+    fn signing_multi_chunk_message() {
+        let ledger = mock_ledger();
+
+        // make sure the message has to be split into multiple chunks
+        let message = "a".repeat(3 * CHUNK_SIZE + 17);
+        let response = ledger.sign_secp256k1(message.clone()).unwrap();
+
+        let signed = ledger.transport.signed_messages();
+        assert_eq!(signed, vec![message.as_bytes().to_vec()]);
+        assert!(ledger
+            .transport
+            .signing_key()
+            .verifying_key()
+            .verify(message.as_bytes(), &response.signature)
+            .is_ok());
+    }
+
+    #[test]
+    fn signing_empty_message_is_rejected() {
+        assert!(matches!(
+            mock_ledger().sign_secp256k1(String::new()),
+            Err(LedgerError::NoMessageFound)
+        ))
+    }
+
+    #[test]
+    fn accounts_share_the_device() {
+        let ledger = mock_ledger();
+        let other = ledger.with_path("m/44'/118'/1'/0/0".parse().unwrap());
+
+        other.sign_secp256k1("foomp".to_string()).unwrap();
+        assert_eq!(ledger.transport.signed_messages().len(), 1);
+        assert_ne!(ledger.path(), other.path());
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::{LedgerError, Result};
+use crate::transport::LedgerTransport;
+use crate::{
+    CLA, INS_GET_ADDR_SECP256K1, INS_GET_VERSION, INS_SIGN_SECP256K1, PAYLOAD_TYPE_ADD,
+    PAYLOAD_TYPE_INIT, PAYLOAD_TYPE_LAST,
+};
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use ledger_transport::{APDUAnswer, APDUCommand};
+use std::sync::Mutex;
+
+const SW_NO_ERROR: u16 = 0x9000;
+const SW_DATA_INVALID: u16 = 0x6984;
+const SW_INS_NOT_SUPPORTED: u16 = 0x6D00;
+const SW_CLA_NOT_SUPPORTED: u16 = 0x6E00;
+
+/// In-memory emulation of a device running the Cosmos application.
+/// It answers to the same APDU commands as the real device, signing with the provided key.
+pub struct MockLedgerDevice {
+    signing_key: SigningKey,
+    address: String,
+    version: (u8, u8, u8),
+
+    // message chunks received so far as part of the current signing request
+    pending_message: Mutex<Option<Vec<u8>>>,
+
+    // all messages that have been signed by this device
+    signed_messages: Mutex<Vec<Vec<u8>>>,
+}
+
+impl MockLedgerDevice {
+    /// Note: the device doesn't perform any derivation, it always uses the same key
+    /// (and the same address) regardless of the requested path.
+    pub fn new(signing_key: SigningKey, address: impl Into<String>) -> Self {
+        MockLedgerDevice {
+            signing_key,
+            address: address.into(),
+            version: (2, 34, 12),
+            pending_message: Mutex::new(None),
+            signed_messages: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+
+    /// Retrieves all the messages that have been signed by this device.
+    pub fn signed_messages(&self) -> Vec<Vec<u8>> {
+        self.signed_messages
+            .lock()
+            .expect("mutex got poisoned")
+            .clone()
+    }
+
+    fn answer(data: &[u8], retcode: u16) -> Result<APDUAnswer<Vec<u8>>> {
+        let mut raw = data.to_vec();
+        raw.extend_from_slice(&retcode.to_be_bytes());
+        APDUAnswer::from_answer(raw).map_err(|err| LedgerError::APDU {
+            reason: err.to_string(),
+        })
+    }
+
+    fn handle_sign(&self, command: &APDUCommand<Vec<u8>>) -> Result<APDUAnswer<Vec<u8>>> {
+        let mut pending = self.pending_message.lock().expect("mutex got poisoned");
+        match command.p1 {
+            // the first chunk only contains the derivation path
+            PAYLOAD_TYPE_INIT => {
+                *pending = Some(Vec::new());
+                Self::answer(&[], SW_NO_ERROR)
+            }
+            PAYLOAD_TYPE_ADD | PAYLOAD_TYPE_LAST => {
+                let Some(message) = pending.as_mut() else {
+                    return Self::answer(&[], SW_DATA_INVALID);
+                };
+                message.extend_from_slice(&command.data);
+                if command.p1 == PAYLOAD_TYPE_ADD {
+                    return Self::answer(&[], SW_NO_ERROR);
+                }
+
+                let message = pending.take().unwrap_or_default();
+                let signature: Signature = self.signing_key.sign(&message);
+                self.signed_messages
+                    .lock()
+                    .expect("mutex got poisoned")
+                    .push(message);
+                Self::answer(signature.to_der().as_bytes(), SW_NO_ERROR)
+            }
+            _ => Self::answer(&[], SW_DATA_INVALID),
+        }
+    }
+}
+
+impl LedgerTransport for MockLedgerDevice {
+    fn exchange(&self, command: &APDUCommand<Vec<u8>>) -> Result<APDUAnswer<Vec<u8>>> {
+        if command.cla != CLA {
+            return Self::answer(&[], SW_CLA_NOT_SUPPORTED);
+        }
+
+        match command.ins {
+            INS_GET_VERSION => {
+                let (major, minor, patch) = self.version;
+                Self::answer(&[0, major, minor, patch, 0], SW_NO_ERROR)
+            }
+            INS_GET_ADDR_SECP256K1 => {
+                let public_key = self.signing_key.verifying_key().to_encoded_point(true);
+                let mut data = public_key.as_bytes().to_vec();
+                data.extend_from_slice(self.address.as_bytes());
+                Self::answer(&data, SW_NO_ERROR)
+            }
+            INS_SIGN_SECP256K1 => self.handle_sign(command),
+            _ => Self::answer(&[], SW_INS_NOT_SUPPORTED),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Result;
+use ledger_transport::{APDUAnswer, APDUCommand};
+use ledger_transport_hid::TransportNativeHID;
+
+/// Abstraction over the channel used for exchanging APDU messages with the device,
+/// so that the Cosmos operations could be performed against something other than a physical device.
+pub trait LedgerTransport: Send + Sync {
+    fn exchange(&self, command: &APDUCommand<Vec<u8>>) -> Result<APDUAnswer<Vec<u8>>>;
+}
+
+impl LedgerTransport for TransportNativeHID {
+    fn exchange(&self, command: &APDUCommand<Vec<u8>>) -> Result<APDUAnswer<Vec<u8>>> {
+        Ok(TransportNativeHID::exchange(self, command)?)
+    }
+}
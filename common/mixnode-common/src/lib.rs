@@ -1,4 +1,5 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
+pub mod noise_network;
 pub mod packet_processor;
 pub mod verloc;
@@ -0,0 +1,134 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use log::{debug, error, warn};
+use nym_crypto::asymmetric::encryption;
+use nym_noise::NoiseNetworkView;
+use nym_task::TaskClient;
+use nym_validator_client::nym_nodes::SkimmedNode;
+use nym_validator_client::NymApiClient;
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+use url::Url;
+
+pub const DEFAULT_NOISE_NETWORK_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Periodically retrieves the x25519 keys of all nodes in the network
+/// so that outbound mix connections could be upgraded to use noise.
+pub struct NoiseNetworkRefresher {
+    nym_api_urls: Vec<Url>,
+    currently_used_api: usize,
+    validator_client: NymApiClient,
+    refresh_interval: Duration,
+    network_view: NoiseNetworkView,
+    shutdown: TaskClient,
+}
+
+impl NoiseNetworkRefresher {
+    pub fn new(
+        mut nym_api_urls: Vec<Url>,
+        refresh_interval: Duration,
+        network_view: NoiseNetworkView,
+        shutdown: TaskClient,
+    ) -> Self {
+        assert!(
+            !nym_api_urls.is_empty(),
+            "at least one nym api endpoint must be provided"
+        );
+        nym_api_urls.shuffle(&mut thread_rng());
+
+        NoiseNetworkRefresher {
+            validator_client: NymApiClient::new(nym_api_urls[0].clone()),
+            nym_api_urls,
+            currently_used_api: 0,
+            refresh_interval,
+            network_view,
+            shutdown,
+        }
+    }
+
+    fn use_next_nym_api(&mut self) {
+        if self.nym_api_urls.len() == 1 {
+            warn!("There's only a single nym API available - it won't be possible to use a different one");
+            return;
+        }
+
+        self.currently_used_api = (self.currently_used_api + 1) % self.nym_api_urls.len();
+        self.validator_client
+            .change_nym_api(self.nym_api_urls[self.currently_used_api].clone())
+    }
+
+    fn insert_node_keys(
+        nodes: Vec<SkimmedNode>,
+        keys: &mut HashMap<SocketAddr, encryption::PublicKey>,
+    ) {
+        for node in nodes {
+            // nodes that haven't announced a noise key are going to be reached using plaintext
+            let Some(noise_key) = &node.x25519_noise_pubkey else {
+                continue;
+            };
+            let Ok(key) = encryption::PublicKey::from_base58_string(noise_key) else {
+                debug!(
+                    "node {} has announced malformed x25519 noise key",
+                    node.ed25519_identity_pubkey
+                );
+                continue;
+            };
+
+            for ip in node.ip_addresses {
+                keys.insert(SocketAddr::new(ip, node.mix_port), key);
+            }
+        }
+    }
+
+    pub async fn refresh_network_view(&mut self) {
+        let mixnodes = match self.validator_client.get_basic_mixnodes(None).await {
+            Ok(nodes) => nodes,
+            Err(err) => {
+                error!("failed to obtain list of mixnodes for the noise network view - {err}");
+                self.use_next_nym_api();
+                return;
+            }
+        };
+
+        let gateways = match self.validator_client.get_basic_gateways(None).await {
+            Ok(nodes) => nodes,
+            Err(err) => {
+                error!("failed to obtain list of gateways for the noise network view - {err}");
+                self.use_next_nym_api();
+                return;
+            }
+        };
+
+        let mut keys = HashMap::new();
+        Self::insert_node_keys(mixnodes, &mut keys);
+        Self::insert_node_keys(gateways, &mut keys);
+
+        debug!("the noise network view now contains {} entries", keys.len());
+        self.network_view.swap_view(keys);
+    }
+
+    pub async fn run(&mut self) {
+        let mut refresh_interval = tokio::time::interval(self.refresh_interval);
+        // the first tick completes immediately, but we should have already performed the initial refresh
+        refresh_interval.tick().await;
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    log::trace!("NoiseNetworkRefresher: Received shutdown");
+                }
+                _ = refresh_interval.tick() => self.refresh_network_view().await,
+            }
+        }
+        log::trace!("NoiseNetworkRefresher: Exiting");
+    }
+
+    pub fn start(mut self) {
+        tokio::spawn(async move { self.run().await });
+    }
+}
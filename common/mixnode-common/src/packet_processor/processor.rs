@@ -2,20 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::packet_processor::error::MixProcessingError;
+use crate::packet_processor::replay_protection::{
+    ReplayProtection, ReplayProtectionConfig, ReplayTag,
+};
+use crate::packet_processor::sphinx_keys::{SphinxKeys, SphinxKeysInner};
+use bytes::Bytes;
 use log::*;
-use nym_metrics::nanos;
+use nym_metrics::{inc, nanos};
 use nym_sphinx_acknowledgements::surb_ack::SurbAck;
 use nym_sphinx_addressing::nodes::NymNodeRoutingAddress;
 use nym_sphinx_forwarding::packet::MixPacket;
 use nym_sphinx_framing::packet::FramedNymPacket;
 use nym_sphinx_params::{PacketSize, PacketType};
 use nym_sphinx_types::{
-    Delay as SphinxDelay, DestinationAddressBytes, NodeAddressBytes, NymPacket, NymProcessedPacket,
-    PrivateKey, ProcessedPacket,
+    Delay as SphinxDelay, DestinationAddressBytes, NodeAddressBytes, NymPacket, NymPacketError,
+    NymProcessedPacket, PrivateKey, ProcessedPacket,
 };
 
-use std::sync::Arc;
-
 type ForwardAck = MixPacket;
 
 #[derive(Debug)]
@@ -36,29 +39,104 @@ pub enum MixProcessingResult {
 
 #[derive(Clone)]
 pub struct SphinxPacketProcessor {
-    /// Private sphinx key of this node required to unwrap received sphinx packet.
-    sphinx_key: Arc<PrivateKey>,
+    /// Private sphinx keys of this node required to unwrap received sphinx packet.
+    sphinx_keys: SphinxKeys,
+
+    /// Filter of all the packets seen before used for detecting replay attacks.
+    replay_protection: Option<ReplayProtection>,
 }
 
 impl SphinxPacketProcessor {
     /// Creates new instance of `CachedPacketProcessor`
     pub fn new(sphinx_key: PrivateKey) -> Self {
+        Self::new_with_rotating_keys(SphinxKeys::new(sphinx_key))
+    }
+
+    /// Creates new instance of `CachedPacketProcessor` using the shared set of sphinx keys
+    /// that might get updated at runtime.
+    pub fn new_with_rotating_keys(sphinx_keys: SphinxKeys) -> Self {
         SphinxPacketProcessor {
-            sphinx_key: Arc::new(sphinx_key),
+            sphinx_keys,
+            replay_protection: None,
+        }
+    }
+
+    /// Enables detection of replayed packets using the provided configuration.
+    #[must_use]
+    pub fn with_replay_protection(self, config: ReplayProtectionConfig) -> Self {
+        self.with_replay_filter(ReplayProtection::new_if_enabled(config))
+    }
+
+    /// Uses the provided, possibly shared, filter for detecting replayed packets.
+    /// Passing `None` disables the detection.
+    #[must_use]
+    pub fn with_replay_filter(mut self, replay_protection: Option<ReplayProtection>) -> Self {
+        self.replay_protection = replay_protection;
+        self
+    }
+
+    pub fn replay_protection(&self) -> Option<&ReplayProtection> {
+        self.replay_protection.as_ref()
+    }
+
+    /// Extracts the tag uniquely identifying the packet at this hop.
+    fn replay_tag(packet: &NymPacket) -> Option<ReplayTag> {
+        #[allow(unreachable_patterns)]
+        match packet {
+            NymPacket::Sphinx(packet) => Some(*packet.header.shared_secret.as_bytes()),
+            // outfox packets are not covered by the replay protection (yet)
+            _ => None,
         }
     }
 
+    /// Checks whether the packet with the provided tag has been received before.
+    fn check_replay(&self, tag: Option<ReplayTag>) -> Result<(), MixProcessingError> {
+        let (Some(replay_protection), Some(tag)) = (&self.replay_protection, tag) else {
+            return Ok(());
+        };
+
+        if replay_protection.check_and_insert(&tag) {
+            debug!("received a replayed packet");
+            inc!("replayed_packets_dropped");
+            return Err(MixProcessingError::ReplayedPacket);
+        }
+        Ok(())
+    }
+
     /// Performs a fresh sphinx unwrapping using no cache.
+    /// If the raw bytes of the received packet are available, they're used for reconstructing it
+    /// in case it has to be re-processed with any of the additional keys.
     fn perform_initial_packet_processing(
         &self,
         packet: NymPacket,
+        received: Option<Bytes>,
     ) -> Result<NymProcessedPacket, MixProcessingError> {
-        nanos!("perform_initial_packet_processing", {
-            packet.process(&self.sphinx_key).map_err(|err| {
+        // grab the tag before the packet is consumed, but only check it once we know the packet
+        // is valid, so that malformed packets could not poison the filter
+        let replay_tag = Self::replay_tag(&packet);
+        let outfox = matches!(packet, NymPacket::Outfox(_));
+        let keys = self.sphinx_keys.current();
+
+        let processed = nanos!("perform_initial_packet_processing", {
+            match packet.process(keys.primary()) {
+                Ok(processed) => Ok(processed),
+                // processing consumes the packet, so it has to be reconstructed for every other key
+                Err(err) => match received {
+                    Some(bytes) if !keys.additional().is_empty() => {
+                        FallbackPacket { outfox, bytes }.process_with_additional_keys(&keys, err)
+                    }
+                    _ => Err(err),
+                },
+            }
+            .map_err(|err| {
                 debug!("Failed to unwrap NymPacket packet: {err}");
                 MixProcessingError::NymPacketProcessingError(err)
             })
-        })
+        })?;
+
+        // note: the tag is derived from the header, so it's the same regardless of the key used
+        self.check_replay(replay_tag)?;
+        Ok(processed)
     }
 
     /// Takes the received framed packet and tries to unwrap it from the sphinx encryption.
@@ -67,8 +145,8 @@ impl SphinxPacketProcessor {
         received: FramedNymPacket,
     ) -> Result<NymProcessedPacket, MixProcessingError> {
         nanos!("perform_initial_unwrapping", {
-            let packet = received.into_inner();
-            self.perform_initial_packet_processing(packet)
+            let (packet, received) = received.into_parts();
+            self.perform_initial_packet_processing(packet, received)
         })
     }
 
@@ -238,6 +316,38 @@ impl SphinxPacketProcessor {
     }
 }
 
+/// Raw bytes of a received packet that can be re-processed with the additional sphinx keys.
+struct FallbackPacket {
+    outfox: bool,
+    bytes: Bytes,
+}
+
+impl FallbackPacket {
+    fn reconstruct(&self) -> Result<NymPacket, NymPacketError> {
+        if self.outfox {
+            NymPacket::outfox_from_bytes(&self.bytes)
+        } else {
+            NymPacket::sphinx_from_bytes(&self.bytes)
+        }
+    }
+
+    /// Attempts to process the packet with every additional key. If none of them work,
+    /// the original error from the primary key is returned.
+    fn process_with_additional_keys(
+        self,
+        keys: &SphinxKeysInner,
+        primary_err: NymPacketError,
+    ) -> Result<NymProcessedPacket, NymPacketError> {
+        for key in keys.additional() {
+            if let Ok(processed) = self.reconstruct()?.process(key) {
+                trace!("processed the packet using one of the additional sphinx keys");
+                return Ok(processed);
+            }
+        }
+        Err(primary_err)
+    }
+}
+
 // TODO: what more could we realistically test here?
 #[cfg(test)]
 mod tests {
@@ -309,6 +419,129 @@ mod tests {
         assert_eq!(data, message)
     }
 
+    #[tokio::test]
+    async fn replayed_packets_are_rejected() {
+        use nym_sphinx_types::{
+            Destination, Node, DESTINATION_ADDRESS_LENGTH, IDENTIFIER_LENGTH, NODE_ADDRESS_LENGTH,
+        };
+
+        let (local_sk, local_pk) = keygen();
+        let (_, next_pk) = keygen();
+        let processor =
+            SphinxPacketProcessor::new(local_sk).with_replay_protection(ReplayProtectionConfig {
+                expected_packets_per_rotation: 1000,
+                ..Default::default()
+            });
+
+        let route = [
+            Node::new(
+                NodeAddressBytes::from_bytes([5u8; NODE_ADDRESS_LENGTH]),
+                local_pk,
+            ),
+            Node::new(
+                NodeAddressBytes::from_bytes([4u8; NODE_ADDRESS_LENGTH]),
+                next_pk,
+            ),
+        ];
+        let destination = Destination::new(
+            DestinationAddressBytes::from_bytes([3u8; DESTINATION_ADDRESS_LENGTH]),
+            [4u8; IDENTIFIER_LENGTH],
+        );
+        let delays = vec![
+            SphinxDelay::new_from_nanos(42),
+            SphinxDelay::new_from_nanos(42),
+        ];
+        let packet_bytes = NymPacket::sphinx_build(
+            PacketSize::RegularPacket.payload_size(),
+            b"foomp",
+            &route,
+            &destination,
+            &delays,
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+
+        let framed = |bytes: &[u8]| {
+            FramedNymPacket::new(
+                NymPacket::sphinx_from_bytes(bytes).unwrap(),
+                PacketType::Mix,
+                false,
+            )
+        };
+
+        assert!(processor.process_received(framed(&packet_bytes)).is_ok());
+        assert!(matches!(
+            processor.process_received(framed(&packet_bytes)),
+            Err(MixProcessingError::ReplayedPacket)
+        ));
+    }
+
+    #[tokio::test]
+    async fn packets_for_additional_sphinx_keys_are_processed() {
+        use bytes::BytesMut;
+        use nym_sphinx_framing::codec::NymCodec;
+        use nym_sphinx_types::{
+            Destination, Node, DESTINATION_ADDRESS_LENGTH, IDENTIFIER_LENGTH, NODE_ADDRESS_LENGTH,
+        };
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let (primary_sk, _) = keygen();
+        let (next_sk, next_pk) = keygen();
+        let (previous_sk, previous_pk) = keygen();
+        let (_, hop_pk) = keygen();
+
+        let sphinx_keys = SphinxKeys::new(primary_sk);
+        let processor = SphinxPacketProcessor::new_with_rotating_keys(sphinx_keys.clone());
+
+        let packet_for = |key| {
+            let route = [
+                Node::new(
+                    NodeAddressBytes::from_bytes([5u8; NODE_ADDRESS_LENGTH]),
+                    key,
+                ),
+                Node::new(
+                    NodeAddressBytes::from_bytes([4u8; NODE_ADDRESS_LENGTH]),
+                    hop_pk,
+                ),
+            ];
+            let destination = Destination::new(
+                DestinationAddressBytes::from_bytes([3u8; DESTINATION_ADDRESS_LENGTH]),
+                [4u8; IDENTIFIER_LENGTH],
+            );
+            let delays = vec![
+                SphinxDelay::new_from_nanos(42),
+                SphinxDelay::new_from_nanos(42),
+            ];
+            let packet = NymPacket::sphinx_build(
+                PacketSize::RegularPacket.payload_size(),
+                b"foomp",
+                &route,
+                &destination,
+                &delays,
+            )
+            .unwrap();
+
+            // the additional keys are only used for the packets received from the network
+            let mut bytes = BytesMut::new();
+            NymCodec
+                .encode(
+                    FramedNymPacket::new(packet, PacketType::Mix, false),
+                    &mut bytes,
+                )
+                .unwrap();
+            NymCodec.decode(&mut bytes).unwrap().unwrap()
+        };
+
+        // the node doesn't know about the next key yet
+        assert!(processor.process_received(packet_for(next_pk)).is_err());
+
+        let (primary_sk, _) = keygen();
+        sphinx_keys.update(primary_sk, vec![previous_sk, next_sk]);
+        assert!(processor.process_received(packet_for(next_pk)).is_ok());
+        assert!(processor.process_received(packet_for(previous_pk)).is_ok());
+    }
+
     #[tokio::test]
     async fn splitting_into_ack_and_message_returns_whole_data_for_ack_outfox() {
         let processor = fixture();
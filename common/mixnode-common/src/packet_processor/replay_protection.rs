@@ -0,0 +1,205 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use bloomfilter::Bloom;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Tag uniquely identifying a packet at this particular hop.
+/// For sphinx packets it's the bytes of the (blinded) shared secret included in the header.
+pub type ReplayTag = [u8; 32];
+
+/// Number of filter generations kept around. A new generation is started whenever one of the sphinx keys
+/// of this node retires. A tag might have been inserted for the key that has only just been announced
+/// for the upcoming rotation, which is going to retire on the third rotation of the filter from now.
+const RETAINED_GENERATIONS: usize = 3;
+
+const DEFAULT_EXPECTED_PACKETS_PER_ROTATION: usize = 10_000_000;
+const DEFAULT_FALSE_POSITIVE_RATE: f64 = 1e-5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReplayProtectionConfig {
+    /// Specifies whether replay detection of received packets should be disabled.
+    pub disabled: bool,
+
+    /// Expected number of packets this node is going to receive within a single sphinx key rotation.
+    /// It's used for determining the size of the bloomfilter.
+    /// Note that if the node does not rotate its keys, or receives more packets than expected,
+    /// the filter is going to be rotated early so that its false positive rate would remain bounded.
+    pub expected_packets_per_rotation: usize,
+
+    /// Desired false positive rate of the bloomfilter, i.e. the probability of a fresh packet
+    /// being incorrectly classified as a replay (and thus dropped).
+    pub false_positive_rate: f64,
+}
+
+impl Default for ReplayProtectionConfig {
+    fn default() -> Self {
+        ReplayProtectionConfig {
+            disabled: false,
+            expected_packets_per_rotation: DEFAULT_EXPECTED_PACKETS_PER_ROTATION,
+            false_positive_rate: DEFAULT_FALSE_POSITIVE_RATE,
+        }
+    }
+}
+
+/// Rotating bloomfilter of replay tags of all packets that got received by this node.
+///
+/// The tags have to be remembered for as long as the key they were processed with is accepted,
+/// so the filter is rotated whenever one of the sphinx keys retires (see [`ReplayProtection::rotate`]).
+/// At any given time it consists of up to [`RETAINED_GENERATIONS`] filters: the current one,
+/// to which all new tags are inserted, and the previous ones, which are only used for lookups.
+#[derive(Clone)]
+pub struct ReplayProtection {
+    config: ReplayProtectionConfig,
+    inner: Arc<Mutex<ReplayProtectionInner>>,
+}
+
+struct ReplayProtectionInner {
+    /// All retained filters, starting with the current one.
+    filters: VecDeque<Bloom<ReplayTag>>,
+
+    /// Number of tags inserted into the current filter.
+    current_insertions: usize,
+}
+
+impl ReplayProtection {
+    pub fn new(config: ReplayProtectionConfig) -> Self {
+        ReplayProtection {
+            inner: Arc::new(Mutex::new(ReplayProtectionInner {
+                filters: VecDeque::from([Self::new_filter(&config)]),
+                current_insertions: 0,
+            })),
+            config,
+        }
+    }
+
+    /// Creates new instance of the filter unless the replay detection is disabled in the provided config.
+    pub fn new_if_enabled(config: ReplayProtectionConfig) -> Option<Self> {
+        if config.disabled {
+            warn!("replay protection is disabled - this node will not detect replayed packets");
+            None
+        } else {
+            Some(Self::new(config))
+        }
+    }
+
+    fn new_filter(config: &ReplayProtectionConfig) -> Bloom<ReplayTag> {
+        Bloom::new_for_fp_rate(
+            config.expected_packets_per_rotation,
+            config.false_positive_rate,
+        )
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReplayProtectionInner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("the replay protection lock got poisoned. attempting to recover");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    /// Checks whether the provided tag has already been seen before and if not, inserts it
+    /// into the filter. Returns `true` if the tag has been seen before, i.e. the packet is a replay.
+    pub fn check_and_insert(&self, tag: &ReplayTag) -> bool {
+        let mut guard = self.lock();
+
+        if guard.filters.iter().skip(1).any(|filter| filter.check(tag)) {
+            return true;
+        }
+        if guard.filters[0].check_and_set(tag) {
+            return true;
+        }
+
+        guard.current_insertions += 1;
+        if guard.current_insertions >= self.config.expected_packets_per_rotation {
+            // past this point the false positive rate would keep on growing,
+            // so rather than dropping legitimate packets, start a new generation early
+            warn!("received more packets than expected within a single sphinx key rotation - rotating the replay protection filter early. consider increasing 'expected_packets_per_rotation'");
+            self.rotate_inner(&mut guard)
+        }
+        false
+    }
+
+    /// Starts a new generation of the filter. It should be called whenever one of the sphinx keys
+    /// of this node retires, as the tags of packets processed with it no longer have to be remembered.
+    pub fn rotate(&self) {
+        let mut guard = self.lock();
+        self.rotate_inner(&mut guard)
+    }
+
+    fn rotate_inner(&self, inner: &mut ReplayProtectionInner) {
+        debug!("rotating the replay protection bloomfilter");
+
+        inner.filters.push_front(Self::new_filter(&self.config));
+        inner.filters.truncate(RETAINED_GENERATIONS);
+        inner.current_insertions = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> ReplayProtectionConfig {
+        ReplayProtectionConfig {
+            expected_packets_per_rotation: 1000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn detects_replayed_tags() {
+        let replay_protection = ReplayProtection::new(test_config());
+
+        assert!(!replay_protection.check_and_insert(&[1u8; 32]));
+        assert!(!replay_protection.check_and_insert(&[2u8; 32]));
+        assert!(replay_protection.check_and_insert(&[1u8; 32]));
+        assert!(replay_protection.check_and_insert(&[2u8; 32]));
+    }
+
+    #[test]
+    fn remembers_tags_until_all_keys_that_could_have_processed_them_retire() {
+        let replay_protection = ReplayProtection::new(test_config());
+
+        assert!(!replay_protection.check_and_insert(&[1u8; 32]));
+        for _ in 1..RETAINED_GENERATIONS {
+            replay_protection.rotate();
+            assert!(replay_protection.check_and_insert(&[1u8; 32]));
+        }
+        replay_protection.rotate();
+        assert!(!replay_protection.check_and_insert(&[1u8; 32]));
+    }
+
+    #[test]
+    fn rotates_early_once_capacity_is_reached() {
+        let replay_protection = ReplayProtection::new(ReplayProtectionConfig {
+            expected_packets_per_rotation: 10,
+            ..Default::default()
+        });
+
+        for i in 0..10u8 {
+            assert!(!replay_protection.check_and_insert(&[i; 32]));
+        }
+        assert_eq!(replay_protection.lock().filters.len(), 2);
+        assert_eq!(replay_protection.lock().current_insertions, 0);
+
+        // tags from before the early rotation are still remembered
+        assert!(replay_protection.check_and_insert(&[0u8; 32]));
+    }
+
+    #[test]
+    fn disabled_protection_is_not_created() {
+        assert!(ReplayProtection::new_if_enabled(ReplayProtectionConfig {
+            disabled: true,
+            ..test_config()
+        })
+        .is_none());
+        assert!(ReplayProtection::new_if_enabled(test_config()).is_some());
+    }
+}
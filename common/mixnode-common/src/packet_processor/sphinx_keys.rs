@@ -0,0 +1,69 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_sphinx_types::PrivateKey;
+use std::sync::{Arc, RwLock};
+
+/// Set of sphinx keys that are currently accepted by this node.
+///
+/// Packets are always attempted to be unwrapped with the primary key first and only upon failure,
+/// the additional keys (if any) are tried in order. This allows the node to rotate its sphinx keys
+/// without dropping packets constructed by clients with a slightly outdated view of the network.
+#[derive(Clone)]
+pub struct SphinxKeys {
+    inner: Arc<RwLock<Arc<SphinxKeysInner>>>,
+}
+
+pub struct SphinxKeysInner {
+    primary: PrivateKey,
+    additional: Vec<PrivateKey>,
+}
+
+impl SphinxKeysInner {
+    pub fn primary(&self) -> &PrivateKey {
+        &self.primary
+    }
+
+    pub fn additional(&self) -> &[PrivateKey] {
+        &self.additional
+    }
+}
+
+impl SphinxKeys {
+    pub fn new(primary: PrivateKey) -> Self {
+        SphinxKeys {
+            inner: Arc::new(RwLock::new(Arc::new(SphinxKeysInner {
+                primary,
+                additional: Vec::new(),
+            }))),
+        }
+    }
+
+    /// Replaces all currently accepted keys.
+    pub fn update(&self, primary: PrivateKey, additional: Vec<PrivateKey>) {
+        let updated = Arc::new(SphinxKeysInner {
+            primary,
+            additional,
+        });
+        match self.inner.write() {
+            Ok(mut guard) => *guard = updated,
+            Err(poisoned) => *poisoned.into_inner() = updated,
+        }
+    }
+
+    /// Returns the snapshot of the currently accepted keys.
+    /// The lock is released immediately, so the keys could be used for the duration of processing
+    /// a packet without blocking any concurrent updates.
+    pub fn current(&self) -> Arc<SphinxKeysInner> {
+        match self.inner.read() {
+            Ok(guard) => Arc::clone(&guard),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+}
+
+impl From<PrivateKey> for SphinxKeys {
+    fn from(primary: PrivateKey) -> Self {
+        SphinxKeys::new(primary)
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use log::warn;
+use nym_crypto::asymmetric::encryption;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(5_000);
+
+/// Shared view of the network containing the x25519 keys of all known nodes,
+/// indexed by the addresses they're listening on for mix traffic.
+/// It's used by the initiators of the connections to determine the static key of the responder.
+#[derive(Clone, Default)]
+pub struct NoiseNetworkView {
+    inner: Arc<RwLock<HashMap<SocketAddr, encryption::PublicKey>>>,
+}
+
+impl NoiseNetworkView {
+    pub fn new_empty() -> Self {
+        Default::default()
+    }
+
+    /// Replaces the current view of the network with the provided one.
+    pub fn swap_view(&self, new: HashMap<SocketAddr, encryption::PublicKey>) {
+        match self.inner.write() {
+            Ok(mut guard) => *guard = new,
+            Err(poisoned) => {
+                warn!("the noise network view lock got poisoned. attempting to recover");
+                *poisoned.into_inner() = new
+            }
+        }
+    }
+
+    pub fn get_noise_key(&self, address: &SocketAddr) -> Option<encryption::PublicKey> {
+        match self.inner.read() {
+            Ok(guard) => guard.get(address).copied(),
+            Err(poisoned) => poisoned.into_inner().get(address).copied(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self.inner.read() {
+            Ok(guard) => guard.len(),
+            Err(poisoned) => poisoned.into_inner().len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Clone)]
+pub struct NoiseConfig {
+    pub(crate) local_key: Arc<encryption::KeyPair>,
+    pub(crate) network: NoiseNetworkView,
+    pub(crate) handshake_timeout: Duration,
+    pub(crate) unsafe_disabled: bool,
+}
+
+impl NoiseConfig {
+    pub fn new(local_key: Arc<encryption::KeyPair>, network: NoiseNetworkView) -> Self {
+        NoiseConfig {
+            local_key,
+            network,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+            unsafe_disabled: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    /// Makes all connections fallback to plaintext regardless of whether the remote supports noise.
+    #[must_use]
+    pub fn with_unsafe_disabled(mut self, unsafe_disabled: bool) -> Self {
+        self.unsafe_disabled = unsafe_disabled;
+        self
+    }
+
+    pub fn network(&self) -> &NoiseNetworkView {
+        &self.network
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.unsafe_disabled
+    }
+}
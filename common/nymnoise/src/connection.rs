@@ -0,0 +1,81 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::stream::NoiseStream;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// Connection between two nodes that might or might not have been upgraded to use noise,
+/// depending on the capabilities of the remote.
+pub enum Connection<C> {
+    Raw(C),
+    Noise(NoiseStream<C>),
+}
+
+impl<C> Connection<C> {
+    pub fn is_noise(&self) -> bool {
+        matches!(self, Connection::Noise(_))
+    }
+
+    pub fn get_ref(&self) -> &C {
+        match self {
+            Connection::Raw(conn) => conn,
+            Connection::Noise(stream) => stream.get_ref(),
+        }
+    }
+
+    pub fn into_inner(self) -> C {
+        match self {
+            Connection::Raw(conn) => conn,
+            Connection::Noise(stream) => stream.into_inner(),
+        }
+    }
+}
+
+impl<C> AsyncRead for Connection<C>
+where
+    C: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Connection::Raw(conn) => Pin::new(conn).poll_read(cx, buf),
+            Connection::Noise(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl<C> AsyncWrite for Connection<C>
+where
+    C: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Connection::Raw(conn) => Pin::new(conn).poll_write(cx, buf),
+            Connection::Noise(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Connection::Raw(conn) => Pin::new(conn).poll_flush(cx),
+            Connection::Noise(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Connection::Raw(conn) => Pin::new(conn).poll_shutdown(cx),
+            Connection::Noise(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NoiseError {
+    #[error("encountered an IO error - {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("encountered a noise protocol error - {0}")]
+    ProtocolError(#[from] snow::Error),
+
+    #[error("the noise handshake did not complete within {timeout:?}")]
+    HandshakeTimeout { timeout: std::time::Duration },
+
+    #[error(
+        "the remote attempted to use an unsupported version of the noise handshake: {received}"
+    )]
+    UnsupportedVersion { received: u8 },
+
+    #[error("received a noise message that's too large ({size} bytes)")]
+    MessageTooLarge { size: usize },
+
+    #[error("the remote has closed the connection in response to the noise handshake - it most likely does not support noise")]
+    RemoteUnsupported,
+}
+
+impl From<NoiseError> for io::Error {
+    fn from(value: NoiseError) -> Self {
+        match value {
+            NoiseError::IoError(err) => err,
+            other => io::Error::new(io::ErrorKind::InvalidData, other),
+        }
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional encryption of the links between the nodes using the Noise protocol framework.
+//!
+//! The initiator of the connection has to know the static x25519 key of the responder
+//! (as established through the network topology), i.e. the `XK` handshake pattern is used.
+//! Once the handshake completes, all data, including the framing of the sphinx packets, is sent
+//! inside the encrypted session.
+//!
+//! To preserve compatibility with nodes that do not support noise, the initiator starts the handshake
+//! by sending a magic byte that is never a valid start of a plaintext framed packet.
+//! The responder peeks at the first received byte to decide whether to proceed with the handshake
+//! or to treat the connection as a legacy plaintext one.
+
+use crate::stream::NoiseStream;
+use log::{debug, trace};
+use nym_crypto::asymmetric::encryption;
+use snow::HandshakeState;
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+pub mod config;
+pub mod connection;
+pub mod error;
+pub mod stream;
+
+pub use config::{NoiseConfig, NoiseNetworkView};
+pub use connection::Connection;
+pub use error::NoiseError;
+
+/// First byte sent by the initiator of a noise connection.
+/// Plaintext connections always start with a framed packet header whose first byte is either
+/// a packet size (legacy headers) or a packet version, neither of which can ever be equal to 255.
+pub const NOISE_HANDSHAKE_MAGIC: u8 = 0xFF;
+
+/// Version of the noise handshake, sent immediately after the magic byte.
+pub const NOISE_HANDSHAKE_VERSION: u8 = 1;
+
+const NOISE_PATTERN: &str = "Noise_XK_25519_ChaChaPoly_BLAKE2s";
+const NOISE_PROLOGUE: &[u8] = b"NYM_MIXNET_NOISE_V1";
+
+pub(crate) const MAXIMUM_NOISE_MESSAGE_LENGTH: usize = 65535;
+pub(crate) const NOISE_TAG_LENGTH: usize = 16;
+
+async fn write_handshake_message<C>(
+    conn: &mut C,
+    handshake: &mut HandshakeState,
+) -> Result<(), NoiseError>
+where
+    C: AsyncWrite + Unpin,
+{
+    let mut message = vec![0u8; MAXIMUM_NOISE_MESSAGE_LENGTH];
+    let len = handshake.write_message(&[], &mut message)?;
+    conn.write_u16(len as u16).await?;
+    conn.write_all(&message[..len]).await?;
+    conn.flush().await?;
+    Ok(())
+}
+
+async fn read_handshake_message<C>(
+    conn: &mut C,
+    handshake: &mut HandshakeState,
+) -> Result<(), NoiseError>
+where
+    C: AsyncRead + Unpin,
+{
+    let len = conn.read_u16().await? as usize;
+    let mut message = vec![0u8; len];
+    conn.read_exact(&mut message).await?;
+
+    let mut payload = vec![0u8; MAXIMUM_NOISE_MESSAGE_LENGTH];
+    handshake.read_message(&message, &mut payload)?;
+    Ok(())
+}
+
+async fn initiator_handshake<C>(
+    mut conn: C,
+    local_key: &encryption::KeyPair,
+    remote_key: &encryption::PublicKey,
+) -> Result<NoiseStream<C>, NoiseError>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    let local_private = local_key.private_key().to_bytes();
+    let remote_public = remote_key.to_bytes();
+    let mut handshake = snow::Builder::new(NOISE_PATTERN.parse()?)
+        .local_private_key(&local_private)
+        .remote_public_key(&remote_public)
+        .prologue(NOISE_PROLOGUE)
+        .build_initiator()?;
+
+    // -> e, es
+    // the preamble is sent together with the first handshake message, so that a remote that does
+    // not support noise would receive all of it before rejecting it as a malformed packet
+    let mut message = vec![0u8; MAXIMUM_NOISE_MESSAGE_LENGTH];
+    let len = handshake.write_message(&[], &mut message)?;
+    let mut initial = Vec::with_capacity(4 + len);
+    initial.extend_from_slice(&[NOISE_HANDSHAKE_MAGIC, NOISE_HANDSHAKE_VERSION]);
+    initial.extend_from_slice(&(len as u16).to_be_bytes());
+    initial.extend_from_slice(&message[..len]);
+    conn.write_all(&initial).await?;
+    conn.flush().await?;
+
+    // <- e, ee
+    // if the remote closes the connection instead of responding, it has explicitly rejected our handshake
+    match read_handshake_message(&mut conn, &mut handshake).await {
+        Err(NoiseError::IoError(err)) if err.kind() == io::ErrorKind::UnexpectedEof => {
+            return Err(NoiseError::RemoteUnsupported)
+        }
+        res => res?,
+    }
+    // -> s, se
+    write_handshake_message(&mut conn, &mut handshake).await?;
+
+    Ok(NoiseStream::new(conn, handshake.into_transport_mode()?))
+}
+
+async fn responder_handshake<C>(
+    mut conn: C,
+    local_key: &encryption::KeyPair,
+) -> Result<NoiseStream<C>, NoiseError>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut preamble = [0u8; 2];
+    conn.read_exact(&mut preamble).await?;
+    if preamble[1] != NOISE_HANDSHAKE_VERSION {
+        return Err(NoiseError::UnsupportedVersion {
+            received: preamble[1],
+        });
+    }
+
+    let local_private = local_key.private_key().to_bytes();
+    let mut handshake = snow::Builder::new(NOISE_PATTERN.parse()?)
+        .local_private_key(&local_private)
+        .prologue(NOISE_PROLOGUE)
+        .build_responder()?;
+
+    // -> e, es
+    read_handshake_message(&mut conn, &mut handshake).await?;
+    // <- e, ee
+    write_handshake_message(&mut conn, &mut handshake).await?;
+    // -> s, se
+    read_handshake_message(&mut conn, &mut handshake).await?;
+
+    Ok(NoiseStream::new(conn, handshake.into_transport_mode()?))
+}
+
+/// Attempts to upgrade the outbound connection to use noise.
+/// If noise is disabled or the remote is not a known node, the connection is returned as is.
+/// If the remote does not support noise, [`NoiseError::RemoteUnsupported`] is returned and
+/// it's up to the caller to decide whether to re-attempt the connection in plaintext.
+pub async fn upgrade_noise_initiator(
+    conn: TcpStream,
+    config: &NoiseConfig,
+) -> Result<Connection<TcpStream>, NoiseError> {
+    if config.unsafe_disabled {
+        trace!("noise is disabled - using plaintext connection");
+        return Ok(Connection::Raw(conn));
+    }
+
+    let remote = conn.peer_addr()?;
+    let Some(remote_key) = config.network.get_noise_key(&remote) else {
+        debug!("{remote} is not a known node - using plaintext connection");
+        return Ok(Connection::Raw(conn));
+    };
+
+    let timeout = config.handshake_timeout;
+    match tokio::time::timeout(
+        timeout,
+        initiator_handshake(conn, &config.local_key, &remote_key),
+    )
+    .await
+    {
+        Ok(res) => res.map(Connection::Noise),
+        Err(_) => Err(NoiseError::HandshakeTimeout { timeout }),
+    }
+}
+
+/// Inspects the inbound connection and if the remote has initiated a noise handshake, completes it.
+/// Otherwise, the connection is returned as is in order to support nodes that do not use noise.
+pub async fn upgrade_noise_responder(
+    conn: TcpStream,
+    config: &NoiseConfig,
+) -> Result<Connection<TcpStream>, NoiseError> {
+    if config.unsafe_disabled {
+        trace!("noise is disabled - using plaintext connection");
+        return Ok(Connection::Raw(conn));
+    }
+
+    let timeout = config.handshake_timeout;
+    let upgrade = async move {
+        let mut first_byte = [0u8; 1];
+        let peeked = conn.peek(&mut first_byte).await?;
+        if peeked == 0 || first_byte[0] != NOISE_HANDSHAKE_MAGIC {
+            // either the connection got closed already or it's a legacy plaintext connection
+            return Ok(Connection::Raw(conn));
+        }
+        responder_handshake(conn, &config.local_key)
+            .await
+            .map(Connection::Noise)
+    };
+
+    match tokio::time::timeout(timeout, upgrade).await {
+        Ok(res) => res,
+        Err(_) => Err(NoiseError::HandshakeTimeout { timeout }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    fn keypair() -> Arc<encryption::KeyPair> {
+        Arc::new(encryption::KeyPair::new(&mut rand::thread_rng()))
+    }
+
+    #[tokio::test]
+    async fn noise_connection_can_be_established_and_used() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let responder_key = keypair();
+        let responder_config =
+            NoiseConfig::new(responder_key.clone(), NoiseNetworkView::new_empty());
+
+        let network = NoiseNetworkView::new_empty();
+        network.swap_view(HashMap::from([(address, *responder_key.public_key())]));
+        let initiator_config = NoiseConfig::new(keypair(), network);
+
+        let responder = tokio::spawn(async move {
+            let (conn, _) = listener.accept().await.unwrap();
+            let mut conn = upgrade_noise_responder(conn, &responder_config)
+                .await
+                .unwrap();
+            assert!(conn.is_noise());
+
+            let mut received = [0u8; 11];
+            conn.read_exact(&mut received).await.unwrap();
+            conn.write_all(&received).await.unwrap();
+            conn.flush().await.unwrap();
+        });
+
+        let conn = TcpStream::connect(address).await.unwrap();
+        let mut conn = upgrade_noise_initiator(conn, &initiator_config)
+            .await
+            .unwrap();
+        assert!(conn.is_noise());
+
+        conn.write_all(b"hello world").await.unwrap();
+        conn.flush().await.unwrap();
+
+        let mut echoed = [0u8; 11];
+        conn.read_exact(&mut echoed).await.unwrap();
+        assert_eq!(&echoed, b"hello world");
+
+        responder.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn connections_to_unknown_nodes_fall_back_to_plaintext() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let responder_config = NoiseConfig::new(keypair(), NoiseNetworkView::new_empty());
+        let initiator_config = NoiseConfig::new(keypair(), NoiseNetworkView::new_empty());
+
+        let responder = tokio::spawn(async move {
+            let (conn, _) = listener.accept().await.unwrap();
+            let mut conn = upgrade_noise_responder(conn, &responder_config)
+                .await
+                .unwrap();
+            assert!(!conn.is_noise());
+
+            let mut received = [0u8; 11];
+            conn.read_exact(&mut received).await.unwrap();
+            assert_eq!(&received, b"hello world");
+        });
+
+        let conn = TcpStream::connect(address).await.unwrap();
+        let mut conn = upgrade_noise_initiator(conn, &initiator_config)
+            .await
+            .unwrap();
+        assert!(!conn.is_noise());
+
+        conn.write_all(b"hello world").await.unwrap();
+        conn.flush().await.unwrap();
+
+        responder.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn legacy_remote_rejecting_the_handshake_is_reported_as_unsupported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let network = NoiseNetworkView::new_empty();
+        network.swap_view(HashMap::from([(address, *keypair().public_key())]));
+        let initiator_config = NoiseConfig::new(keypair(), network);
+
+        // legacy node fails to decode the received data as a framed packet and closes the connection
+        let responder = tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = conn.read(&mut buf).await.unwrap();
+        });
+
+        let conn = TcpStream::connect(address).await.unwrap();
+        let res = upgrade_noise_initiator(conn, &initiator_config).await;
+        assert!(matches!(res, Err(NoiseError::RemoteUnsupported)));
+
+        responder.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unresponsive_remote_is_not_reported_as_unsupported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let network = NoiseNetworkView::new_empty();
+        network.swap_view(HashMap::from([(address, *keypair().public_key())]));
+        let initiator_config = NoiseConfig::new(keypair(), network)
+            .with_handshake_timeout(std::time::Duration::from_millis(100));
+
+        // accept the connection, but never respond to the handshake
+        let responder = tokio::spawn(async move {
+            let (conn, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            drop(conn)
+        });
+
+        let conn = TcpStream::connect(address).await.unwrap();
+        let res = upgrade_noise_initiator(conn, &initiator_config).await;
+        assert!(matches!(res, Err(NoiseError::HandshakeTimeout { .. })));
+
+        responder.await.unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::NoiseError;
+use crate::{MAXIMUM_NOISE_MESSAGE_LENGTH, NOISE_TAG_LENGTH};
+use bytes::{Buf, BufMut, BytesMut};
+use snow::TransportState;
+use std::cmp::min;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_util::io::poll_read_buf;
+
+const LENGTH_PREFIX: usize = 2;
+const MAXIMUM_PLAINTEXT_LENGTH: usize = MAXIMUM_NOISE_MESSAGE_LENGTH - NOISE_TAG_LENGTH;
+
+/// Wrapper around an underlying connection that encrypts all written data and decrypts all read data
+/// using an already established noise session.
+///
+/// On the wire, each noise transport message is prefixed with its big endian encoded u16 length.
+pub struct NoiseStream<C> {
+    inner: C,
+    transport: TransportState,
+
+    // raw bytes read from the underlying connection that have not yet been decrypted
+    read_buf: BytesMut,
+
+    // decrypted bytes that have not yet been returned to the caller
+    decrypted_buf: BytesMut,
+
+    // encrypted bytes that have not yet been written to the underlying connection
+    write_buf: BytesMut,
+}
+
+impl<C> NoiseStream<C> {
+    pub(crate) fn new(inner: C, transport: TransportState) -> Self {
+        NoiseStream {
+            inner,
+            transport,
+            read_buf: BytesMut::new(),
+            decrypted_buf: BytesMut::new(),
+            write_buf: BytesMut::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Attempts to decrypt the next full noise message sitting in the read buffer.
+    /// Returns `false` if there is not enough data for a full message.
+    fn try_decrypt_next_message(&mut self) -> Result<bool, NoiseError> {
+        if self.read_buf.len() < LENGTH_PREFIX {
+            return Ok(false);
+        }
+        let message_len = u16::from_be_bytes([self.read_buf[0], self.read_buf[1]]) as usize;
+        if self.read_buf.len() < LENGTH_PREFIX + message_len {
+            self.read_buf
+                .reserve(LENGTH_PREFIX + message_len - self.read_buf.len());
+            return Ok(false);
+        }
+
+        self.read_buf.advance(LENGTH_PREFIX);
+        let message = self.read_buf.split_to(message_len);
+
+        let mut plaintext = vec![0u8; message_len];
+        let plaintext_len = self.transport.read_message(&message, &mut plaintext)?;
+        self.decrypted_buf.put_slice(&plaintext[..plaintext_len]);
+        Ok(true)
+    }
+
+    fn encrypt_message(&mut self, plaintext: &[u8]) -> Result<(), NoiseError> {
+        let mut message = vec![0u8; plaintext.len() + NOISE_TAG_LENGTH];
+        let message_len = self.transport.write_message(plaintext, &mut message)?;
+        if message_len > MAXIMUM_NOISE_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLarge { size: message_len });
+        }
+
+        self.write_buf.reserve(LENGTH_PREFIX + message_len);
+        self.write_buf.put_u16(message_len as u16);
+        self.write_buf.put_slice(&message[..message_len]);
+        Ok(())
+    }
+}
+
+impl<C> NoiseStream<C>
+where
+    C: AsyncWrite + Unpin,
+{
+    fn poll_drain_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.write_buf))?;
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.write_buf.advance(written);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<C> AsyncRead for NoiseStream<C>
+where
+    C: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if !this.decrypted_buf.is_empty() {
+                let available = min(this.decrypted_buf.len(), buf.remaining());
+                buf.put_slice(&this.decrypted_buf[..available]);
+                this.decrypted_buf.advance(available);
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.try_decrypt_next_message()? {
+                continue;
+            }
+
+            let read = ready!(poll_read_buf(
+                Pin::new(&mut this.inner),
+                cx,
+                &mut this.read_buf
+            ))?;
+            if read == 0 {
+                // the underlying connection got closed
+                return if this.read_buf.is_empty() {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()))
+                };
+            }
+        }
+    }
+}
+
+impl<C> AsyncWrite for NoiseStream<C>
+where
+    C: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // make sure we're not buffering unbounded amount of data
+        ready!(this.poll_drain_write_buf(cx))?;
+
+        let len = min(buf.len(), MAXIMUM_PLAINTEXT_LENGTH);
+        this.encrypt_message(&buf[..len])?;
+
+        // attempt to push the data out straight away, but it's fine if we can't do it just yet
+        // as it has already been buffered and will be written out on the subsequent calls
+        if let Poll::Ready(Err(err)) = this.poll_drain_write_buf(cx) {
+            return Poll::Ready(Err(err));
+        }
+
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain_write_buf(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain_write_buf(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_sphinx_params::PayloadCompression;
+use thiserror::Error;
+
+/// Upper bound on the size of a decompressed message, so that a malicious sender could not make us
+/// allocate arbitrary amounts of memory with a tiny payload.
+pub const MAX_DECOMPRESSED_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// Upper bound on the ratio between the decompressed and the compressed length of a message.
+/// Together with [`MAX_DECOMPRESSED_MESSAGE_LEN`], it ensures the amount of memory allocated for
+/// the decompression is proportional to the amount of data actually received.
+pub const MAX_COMPRESSION_RATIO: usize = 255;
+
+/// Returns the maximum length the payload of given length is allowed to decompress into.
+pub fn max_decompressed_len(compressed_len: usize) -> usize {
+    compressed_len
+        .saturating_mul(MAX_COMPRESSION_RATIO)
+        .min(MAX_DECOMPRESSED_MESSAGE_LEN)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CompressionError {
+    #[error("{algorithm} compression is not supported on this platform")]
+    UnsupportedAlgorithm { algorithm: PayloadCompression },
+
+    #[error(
+        "the declared decompressed length of {declared} exceeds the maximum of {}",
+        MAX_DECOMPRESSED_MESSAGE_LEN
+    )]
+    TooLongDecompressedMessage { declared: usize },
+
+    #[error(
+        "the declared decompressed length of {declared} is too large for the compressed payload of {compressed} bytes"
+    )]
+    ExcessiveCompressionRatio { declared: usize, compressed: usize },
+
+    #[error("failed to compress the payload using {algorithm}: {message}")]
+    CompressionFailure {
+        algorithm: PayloadCompression,
+        message: String,
+    },
+
+    #[error("failed to decompress the payload using {algorithm}: {message}")]
+    DecompressionFailure {
+        algorithm: PayloadCompression,
+        message: String,
+    },
+
+    #[error("the decompressed payload has length of {received} while {expected} was declared")]
+    UnexpectedDecompressedLength { received: usize, expected: usize },
+}
+
+/// Compresses the provided message using the specified algorithm.
+/// Note that compression using zstd is not available on wasm32, however, decompression is.
+pub fn compress(algorithm: PayloadCompression, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    match algorithm {
+        #[cfg(not(target_arch = "wasm32"))]
+        PayloadCompression::Zstd => {
+            zstd::bulk::compress(data, ZSTD_COMPRESSION_LEVEL).map_err(|err| {
+                CompressionError::CompressionFailure {
+                    algorithm,
+                    message: err.to_string(),
+                }
+            })
+        }
+        #[cfg(target_arch = "wasm32")]
+        PayloadCompression::Zstd => Err(CompressionError::UnsupportedAlgorithm { algorithm }),
+        PayloadCompression::Lz4 => Ok(lz4_flex::block::compress(data)),
+    }
+}
+
+/// Decompresses the provided payload, that is expected to decompress into exactly
+/// `decompressed_len` bytes, using the specified algorithm.
+pub fn decompress(
+    algorithm: PayloadCompression,
+    data: &[u8],
+    decompressed_len: usize,
+) -> Result<Vec<u8>, CompressionError> {
+    if decompressed_len > MAX_DECOMPRESSED_MESSAGE_LEN {
+        return Err(CompressionError::TooLongDecompressedMessage {
+            declared: decompressed_len,
+        });
+    }
+    if decompressed_len > max_decompressed_len(data.len()) {
+        return Err(CompressionError::ExcessiveCompressionRatio {
+            declared: decompressed_len,
+            compressed: data.len(),
+        });
+    }
+
+    let decompressed =
+        match algorithm {
+            #[cfg(not(target_arch = "wasm32"))]
+            PayloadCompression::Zstd => {
+                zstd::bulk::decompress(data, decompressed_len).map_err(|err| {
+                    CompressionError::DecompressionFailure {
+                        algorithm,
+                        message: err.to_string(),
+                    }
+                })?
+            }
+            #[cfg(target_arch = "wasm32")]
+            PayloadCompression::Zstd => {
+                zstd_decompress_wasm(data, decompressed_len).map_err(|err| {
+                    CompressionError::DecompressionFailure {
+                        algorithm,
+                        message: err.to_string(),
+                    }
+                })?
+            }
+            PayloadCompression::Lz4 => lz4_flex::block::decompress(data, decompressed_len)
+                .map_err(|err| CompressionError::DecompressionFailure {
+                    algorithm,
+                    message: err.to_string(),
+                })?,
+        };
+
+    if decompressed.len() != decompressed_len {
+        return Err(CompressionError::UnexpectedDecompressedLength {
+            received: decompressed.len(),
+            expected: decompressed_len,
+        });
+    }
+
+    Ok(decompressed)
+}
+
+/// The C zstd library can't be used on wasm32, so the pure rust decoder is used instead.
+/// The output is limited to a single byte past the declared length so that any excess data is still detected.
+#[cfg(target_arch = "wasm32")]
+fn zstd_decompress_wasm(data: &[u8], decompressed_len: usize) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let decoder = ruzstd::StreamingDecoder::new(data)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
+
+    let mut decompressed = Vec::with_capacity(decompressed_len);
+    decoder
+        .take(decompressed_len as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compressible_message() -> Vec<u8> {
+        b"the quick brown fox jumps over the lazy dog. "
+            .iter()
+            .copied()
+            .cycle()
+            .take(10000)
+            .collect()
+    }
+
+    #[test]
+    fn compression_roundtrip() {
+        let message = compressible_message();
+        for algorithm in [PayloadCompression::Zstd, PayloadCompression::Lz4] {
+            let compressed = compress(algorithm, &message).unwrap();
+            assert!(compressed.len() < message.len());
+
+            let decompressed = decompress(algorithm, &compressed, message.len()).unwrap();
+            assert_eq!(decompressed, message);
+        }
+    }
+
+    #[test]
+    fn decompression_requires_correct_length() {
+        let message = compressible_message();
+        for algorithm in [PayloadCompression::Zstd, PayloadCompression::Lz4] {
+            let compressed = compress(algorithm, &message).unwrap();
+            assert!(decompress(algorithm, &compressed, message.len() - 1).is_err());
+            assert!(decompress(algorithm, &compressed, message.len() + 1).is_err());
+        }
+    }
+
+    #[test]
+    fn decompression_ratio_is_bounded() {
+        let message = vec![0u8; 100_000];
+        let compressed = compress(PayloadCompression::Zstd, &message).unwrap();
+        assert!(compressed.len() * MAX_COMPRESSION_RATIO < message.len());
+
+        assert_eq!(
+            decompress(PayloadCompression::Zstd, &compressed, message.len()),
+            Err(CompressionError::ExcessiveCompressionRatio {
+                declared: message.len(),
+                compressed: compressed.len(),
+            })
+        );
+        assert_eq!(
+            decompress(
+                PayloadCompression::Zstd,
+                &[0u8; 10],
+                10 * MAX_COMPRESSION_RATIO + 1
+            ),
+            Err(CompressionError::ExcessiveCompressionRatio {
+                declared: 10 * MAX_COMPRESSION_RATIO + 1,
+                compressed: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn decompression_is_bounded() {
+        assert_eq!(
+            decompress(
+                PayloadCompression::Lz4,
+                &[],
+                MAX_DECOMPRESSED_MESSAGE_LEN + 1
+            ),
+            Err(CompressionError::TooLongDecompressedMessage {
+                declared: MAX_DECOMPRESSED_MESSAGE_LEN + 1
+            })
+        );
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fragment::{fec_fragment_payload_len, Fragment};
+use crate::set::{generate_set_id, FragmentSet};
+use crate::ChunkingError;
+use rand::Rng;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+
+/// Default amount of redundancy, as percentage of the data `Fragment`s, added to each set.
+pub const DEFAULT_PARITY_PERCENTAGE: u8 = 25;
+
+/// Parameters of the optional forward error correction applied to the `FragmentSet`s of a message.
+///
+/// Each set consisting of `k` data `Fragment`s gets extended with `m` parity `Fragment`s
+/// computed using the Reed-Solomon erasure code, so that the set could be fully recovered
+/// from *any* `k` of its `k + m` `Fragment`s. This allows the recipient to reconstruct the message
+/// without having to wait for the retransmission of the lost packets.
+///
+/// Note that unlike the regular sets, the size of forward error corrected sets is bounded
+/// by the total number of data *and* parity `Fragment`s, which cannot exceed `u8::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForwardErrorCorrection {
+    /// Number of parity `Fragment`s, expressed as percentage of the data `Fragment`s in the set.
+    /// Regardless of its value, each set always gets at least a single parity `Fragment`.
+    parity_percentage: u8,
+}
+
+impl Default for ForwardErrorCorrection {
+    fn default() -> Self {
+        ForwardErrorCorrection {
+            parity_percentage: DEFAULT_PARITY_PERCENTAGE,
+        }
+    }
+}
+
+impl ForwardErrorCorrection {
+    pub const fn new(parity_percentage: u8) -> Self {
+        ForwardErrorCorrection { parity_percentage }
+    }
+
+    pub const fn parity_percentage(&self) -> u8 {
+        self.parity_percentage
+    }
+
+    /// Determines the number of parity `Fragment`s added to a set with the provided number
+    /// of data `Fragment`s.
+    pub fn parity_fragments(&self, data_fragments: usize) -> usize {
+        (data_fragments * self.parity_percentage as usize)
+            .div_ceil(100)
+            .max(1)
+    }
+
+    /// Determines the maximum number of data `Fragment`s that can be put in a single set,
+    /// so that together with its parity `Fragment`s it would not exceed the `u8::MAX` limit.
+    pub fn max_data_fragments_per_set(&self) -> usize {
+        let mut data_fragments = u8::MAX as usize - 1;
+        while data_fragments + self.parity_fragments(data_fragments) > u8::MAX as usize {
+            data_fragments -= 1;
+        }
+        data_fragments
+    }
+
+    /// Given total number of data `Fragment`s of the message, determines how many of them
+    /// are going to be put in each of the sets.
+    fn data_fragments_per_set(&self, total_data_fragments: usize) -> Vec<usize> {
+        let max_per_set = self.max_data_fragments_per_set();
+        let full_sets = total_data_fragments / max_per_set;
+        let remaining = total_data_fragments % max_per_set;
+
+        let mut sets = vec![max_per_set; full_sets];
+        if remaining != 0 {
+            sets.push(remaining)
+        }
+        sets
+    }
+}
+
+/// Determines the number of data `Fragment`s the message is going to be split into.
+fn total_data_fragments(message_len: usize, max_plaintext_size: usize) -> usize {
+    message_len
+        .div_ceil(fec_fragment_payload_len(max_plaintext_size))
+        .max(1)
+}
+
+/// Returns number of fragments, including the parity ones, the message will be split to
+/// as well as number of available bytes in the final data fragment.
+pub fn number_of_required_fec_fragments(
+    message_len: usize,
+    max_plaintext_size: usize,
+    fec: ForwardErrorCorrection,
+) -> (usize, usize) {
+    let data_fragments = total_data_fragments(message_len, max_plaintext_size);
+    let space_left = data_fragments * fec_fragment_payload_len(max_plaintext_size) - message_len;
+
+    let total_fragments = fec
+        .data_fragments_per_set(data_fragments)
+        .into_iter()
+        .map(|set_data_fragments| set_data_fragments + fec.parity_fragments(set_data_fragments))
+        .sum();
+
+    (total_fragments, space_left)
+}
+
+/// Splits part of the underlying message into equally sized data shards (padding the last one
+/// with zeroes if required), computes the parity shards and wraps all of them into `Fragment`s.
+fn prepare_fec_fragment_set(
+    message: &[u8],
+    id: i32,
+    previous_link_id: Option<i32>,
+    next_link_id: Option<i32>,
+    max_plaintext_size: usize,
+    fec: ForwardErrorCorrection,
+) -> FragmentSet {
+    let shard_len = fec_fragment_payload_len(max_plaintext_size);
+    let data_fragments = total_data_fragments(message.len(), max_plaintext_size);
+    let parity_fragments = fec.parity_fragments(data_fragments);
+    let total_fragments = data_fragments + parity_fragments;
+    debug_assert!(total_fragments <= u8::MAX as usize);
+
+    let mut shards = Vec::with_capacity(total_fragments);
+    for i in 0..data_fragments {
+        let lb = usize::min(message.len(), i * shard_len);
+        let ub = usize::min(message.len(), (i + 1) * shard_len);
+        let mut shard = message[lb..ub].to_vec();
+        shard.resize(shard_len, 0);
+        shards.push(shard)
+    }
+    shards.resize(total_fragments, vec![0u8; shard_len]);
+
+    // the unwraps here are fine as we have just very carefully chosen the number and sizes of the shards
+    ReedSolomon::new(data_fragments, parity_fragments)
+        .unwrap()
+        .encode(&mut shards)
+        .unwrap();
+
+    shards
+        .into_iter()
+        .enumerate()
+        .map(|(i, shard)| {
+            Fragment::try_new_fec(
+                &shard,
+                id,
+                total_fragments as u8,
+                (i + 1) as u8,
+                data_fragments as u8,
+                previous_link_id,
+                next_link_id,
+                max_plaintext_size,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Entry point for splitting whole message into possibly multiple forward error corrected [`Set`]s.
+/// Note that the payload of the final data `Fragment` is padded with zeroes, so ideally
+/// the message should have been already padded to fit exactly into the data `Fragment`s.
+pub fn split_into_fec_sets<R: Rng>(
+    rng: &mut R,
+    message: &[u8],
+    max_plaintext_size: usize,
+    fec: ForwardErrorCorrection,
+) -> Vec<FragmentSet> {
+    let shard_len = fec_fragment_payload_len(max_plaintext_size);
+    let sets_data_fragments =
+        fec.data_fragments_per_set(total_data_fragments(message.len(), max_plaintext_size));
+    let num_of_sets = sets_data_fragments.len();
+
+    // pre-generate all ids for the sets
+    let set_ids: Vec<_> = std::iter::repeat_with(|| generate_set_id(rng))
+        .take(num_of_sets)
+        .collect();
+
+    let mut sets = Vec::with_capacity(num_of_sets);
+    let mut lb = 0;
+    for (i, set_data_fragments) in sets_data_fragments.into_iter().enumerate() {
+        let ub = usize::min(message.len(), lb + set_data_fragments * shard_len);
+        sets.push(prepare_fec_fragment_set(
+            &message[lb..ub],
+            set_ids[i],
+            if i == 0 { None } else { Some(set_ids[i - 1]) },
+            if i == num_of_sets - 1 {
+                None
+            } else {
+                Some(set_ids[i + 1])
+            },
+            max_plaintext_size,
+            fec,
+        ));
+        lb = ub;
+    }
+
+    sets
+}
+
+/// Recovers the original data of the forward error corrected set given at least `data_fragments`
+/// of its `Fragment`s. The missing `Fragment`s are represented by `None` entries.
+/// It fails if the received `Fragment`s turn out not to form a valid set, for example if their
+/// payloads are of inconsistent lengths.
+pub(crate) fn reconstruct_fec_set_data(
+    fragments: Vec<Option<Fragment>>,
+    data_fragments: u8,
+) -> Result<Vec<u8>, ChunkingError> {
+    let data_fragments = data_fragments as usize;
+    let parity_fragments = fragments.len() - data_fragments;
+
+    let mut shards: Vec<_> = fragments
+        .into_iter()
+        .map(|fragment| fragment.map(|fragment| fragment.extract_payload()))
+        .collect();
+
+    ReedSolomon::new(data_fragments, parity_fragments)
+        .and_then(|rs| rs.reconstruct_data(&mut shards))
+        .map_err(|err| ChunkingError::FecReconstructionFailure(err.to_string()))?;
+
+    shards
+        .into_iter()
+        .take(data_fragments)
+        .map(|shard| {
+            shard.ok_or_else(|| {
+                ChunkingError::FecReconstructionFailure("missing data shard".to_string())
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|shards| shards.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_sphinx_params::packet_sizes::PacketSize;
+    use rand::{thread_rng, RngCore};
+
+    fn max_plaintext_size() -> usize {
+        PacketSize::default().plaintext_size() - PacketSize::AckPacket.size()
+    }
+
+    fn random_message(len: usize) -> Vec<u8> {
+        let mut message = vec![0u8; len];
+        thread_rng().fill_bytes(&mut message);
+        message
+    }
+
+    #[test]
+    fn parity_fragments_are_always_present() {
+        let fec = ForwardErrorCorrection::new(0);
+        assert_eq!(fec.parity_fragments(1), 1);
+        assert_eq!(fec.parity_fragments(200), 1);
+
+        let fec = ForwardErrorCorrection::new(25);
+        assert_eq!(fec.parity_fragments(1), 1);
+        assert_eq!(fec.parity_fragments(4), 1);
+        assert_eq!(fec.parity_fragments(5), 2);
+        assert_eq!(fec.parity_fragments(100), 25);
+    }
+
+    #[test]
+    fn sets_never_exceed_maximum_number_of_fragments() {
+        for parity_percentage in [0, 1, 10, 25, 50, 100, 200, u8::MAX] {
+            let fec = ForwardErrorCorrection::new(parity_percentage);
+            let max_data = fec.max_data_fragments_per_set();
+            assert!(max_data + fec.parity_fragments(max_data) <= u8::MAX as usize);
+            assert!(max_data + 1 + fec.parity_fragments(max_data + 1) > u8::MAX as usize);
+        }
+    }
+
+    #[test]
+    fn number_of_required_fragments_matches_the_split() {
+        let fec = ForwardErrorCorrection::default();
+        let shard_len = fec_fragment_payload_len(max_plaintext_size());
+
+        for message_len in [
+            0,
+            1,
+            shard_len,
+            shard_len + 1,
+            42 * shard_len - 5,
+            500 * shard_len,
+        ] {
+            let message = random_message(message_len);
+            let sets = split_into_fec_sets(&mut thread_rng(), &message, max_plaintext_size(), fec);
+            let (fragments, space_left) =
+                number_of_required_fec_fragments(message_len, max_plaintext_size(), fec);
+
+            assert_eq!(fragments, sets.iter().map(|set| set.len()).sum::<usize>());
+            assert_eq!((message_len + space_left) % shard_len, 0);
+            for fragment in sets.into_iter().flatten() {
+                assert_eq!(fragment.serialized_size(), max_plaintext_size());
+            }
+        }
+    }
+
+    #[test]
+    fn set_can_be_reconstructed_from_any_sufficient_subset_of_fragments() {
+        let fec = ForwardErrorCorrection::new(50);
+        let shard_len = fec_fragment_payload_len(max_plaintext_size());
+        let message = random_message(10 * shard_len);
+
+        let mut sets = split_into_fec_sets(&mut thread_rng(), &message, max_plaintext_size(), fec);
+        assert_eq!(sets.len(), 1);
+        let set = sets.pop().unwrap();
+        assert_eq!(set.len(), 15);
+
+        // drop the first five fragments
+        let fragments: Vec<_> = set
+            .iter()
+            .enumerate()
+            .map(|(i, fragment)| if i < 5 { None } else { Some(fragment.clone()) })
+            .collect();
+        assert_eq!(message, reconstruct_fec_set_data(fragments, 10).unwrap());
+
+        // and now every third one
+        let fragments: Vec<_> = set
+            .into_iter()
+            .enumerate()
+            .map(|(i, fragment)| if i % 3 == 0 { None } else { Some(fragment) })
+            .collect();
+        assert_eq!(message, reconstruct_fec_set_data(fragments, 10).unwrap());
+    }
+
+    #[test]
+    fn multiple_sets_are_correctly_linked() {
+        let fec = ForwardErrorCorrection::default();
+        let shard_len = fec_fragment_payload_len(max_plaintext_size());
+        let max_data = fec.max_data_fragments_per_set();
+        let message = random_message(2 * max_data * shard_len + 1);
+
+        let sets = split_into_fec_sets(&mut thread_rng(), &message, max_plaintext_size(), fec);
+        assert_eq!(sets.len(), 3);
+
+        for fragment in &sets[0] {
+            assert_eq!(fragment.previous_fragments_set_id(), None);
+            assert_eq!(fragment.next_fragments_set_id(), Some(sets[1][0].id()));
+        }
+        for fragment in &sets[1] {
+            assert_eq!(fragment.previous_fragments_set_id(), Some(sets[0][0].id()));
+            assert_eq!(fragment.next_fragments_set_id(), Some(sets[2][0].id()));
+        }
+        for fragment in &sets[2] {
+            assert_eq!(fragment.previous_fragments_set_id(), Some(sets[1][0].id()));
+            assert_eq!(fragment.next_fragments_set_id(), None);
+            assert_eq!(fragment.data_fragments(), Some(1));
+        }
+    }
+}
@@ -34,6 +34,18 @@ pub const UNLINKED_FRAGMENTED_HEADER_LEN: usize = 7;
 /// `Fragment` in a `FragmentSet`.
 pub const LINKED_FRAGMENTED_HEADER_LEN: usize = 10;
 
+/// Header of a `Fragment` belonging to a `FragmentSet` protected with forward error correction.
+/// On top of the usual 4 byte set id, total and current fragment bytes, it contains an extra marker
+/// byte, 1 byte to represent number of data fragments in the set and two 4 byte ids of the
+/// previous and next sets. Since any subset of the fragments might be lost, every single one
+/// of them must carry the full linking information.
+pub const FEC_FRAGMENTED_HEADER_LEN: usize = 16;
+
+/// Value of the 7th header byte indicating the `Fragment` is part of a forward error corrected set.
+/// Note that it can never be confused with the other header variants as the byte is either
+/// 0 for unlinked fragments or has its highest bit set for linked ones.
+pub(crate) const FEC_FRAGMENT_MARKER: u8 = 0x01;
+
 /// Maximum size of payload of each fragment is always the maximum amount of plaintext data
 /// we can put into a sphinx packet minus length of respective fragment header.
 pub const fn unlinked_fragment_payload_max_len(max_plaintext_size: usize) -> usize {
@@ -46,6 +58,12 @@ pub const fn linked_fragment_payload_max_len(max_plaintext_size: usize) -> usize
     max_plaintext_size - LINKED_FRAGMENTED_HEADER_LEN
 }
 
+/// Unlike other fragments, the payload of each forward error corrected fragment always
+/// has the same, exact, length as all of them are used as equally sized shards for the erasure code.
+pub const fn fec_fragment_payload_len(max_plaintext_size: usize) -> usize {
+    max_plaintext_size - FEC_FRAGMENTED_HEADER_LEN
+}
+
 // TODO: should this be defined in this module or in `cover`? I can see arguments for both options...
 /// A special `FragmentIdentifier` that is not valid in all cases unless if it's used in a loop
 /// cover message.
@@ -191,6 +209,44 @@ impl Fragment {
         })
     }
 
+    /// Tries to encapsulate provided payload slice and metadata into a `Fragment` belonging
+    /// to a forward error corrected `FragmentSet`.
+    /// It can fail if payload does not have the exact expected length or some of the metadata
+    /// is malformed or self-contradictory, for example if data_fragments >= total_fragments.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_new_fec(
+        payload: &[u8],
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+        data_fragments: u8,
+        previous_fragments_set_id: Option<i32>,
+        next_fragments_set_id: Option<i32>,
+        max_plaintext_size: usize,
+    ) -> Result<Self, ChunkingError> {
+        let header = FragmentHeader::try_new_fec(
+            id,
+            total_fragments,
+            current_fragment,
+            data_fragments,
+            previous_fragments_set_id,
+            next_fragments_set_id,
+        )?;
+
+        let expected_len = fec_fragment_payload_len(max_plaintext_size);
+        if payload.len() != expected_len {
+            return Err(ChunkingError::InvalidPayloadLengthError {
+                received: payload.len(),
+                expected: expected_len,
+            });
+        }
+
+        Ok(Fragment {
+            header,
+            payload: payload.to_vec(),
+        })
+    }
+
     /// based on the size of the embedded data, determines which predefined `PacketSize`
     /// was used for construction of this `Fragment`
     pub fn serialized_size(&self) -> usize {
@@ -237,6 +293,17 @@ impl Fragment {
         self.header.current_fragment
     }
 
+    /// If the `FragmentSet` this `Fragment` belongs to is protected with forward error correction,
+    /// extracts the number of fragments required to recover it.
+    pub fn data_fragments(&self) -> Option<u8> {
+        self.header.data_fragments
+    }
+
+    /// Checks whether this `Fragment` is part of a forward error corrected `FragmentSet`.
+    pub fn is_fec_encoded(&self) -> bool {
+        self.header.data_fragments.is_some()
+    }
+
     /// Extracts information regarding id of pre-linked `FragmentSet`
     pub fn previous_fragments_set_id(&self) -> Option<i32> {
         self.header.previous_fragments_set_id
@@ -260,7 +327,11 @@ impl Fragment {
         let (header, n) = FragmentHeader::try_from_bytes(b)?;
 
         // there's no sane way to decide if payload has correct range anymore as
-        // it's no longer fixed
+        // it's no longer fixed. However, forward error corrected fragments always carry
+        // a full shard, so an empty one can't possibly be valid
+        if header.data_fragments.is_some() && b.len() == n {
+            return Err(ChunkingError::EmptyFecFragmentPayload);
+        }
 
         Ok(Fragment {
             header,
@@ -292,6 +363,13 @@ impl Fragment {
 /// where the set is linked to either preceding data (TF == 1) or proceeding data (TF == CF == 255)
 /// '1'bit || 31-bit ID || 1-byte TF || 1 byte CF || '1'bit || 31-bit LID
 ///
+/// Finally, if the set is protected with forward error correction, the header is always 16 bytes long,
+/// as every `Fragment` has to be able to recover the set metadata on its own:
+/// '1'bit || 31-bit ID || 1-byte TF || 1 byte CF || FEC marker byte || 1-byte DF ||
+/// '1'bit || 31-bit previous LID (or '0' bytes) || '1'bit || 31-bit next LID (or '0' bytes)
+/// where DF is the number of data fragments in the set. The remaining TF - DF fragments
+/// hold the parity data.
+///
 /// And hence for messages larger than `max_plaintext_size` but small enough
 /// to avoid set division (which happens if message has to be fragmented into more than 255 fragments)
 /// there is 7 bytes of overhead inside each sphinx packet sent
@@ -316,11 +394,18 @@ pub(crate) struct FragmentHeader {
 
     /// Optional ID of previous `FragmentSet` into which the original message was split.
     /// Note, this option is only valid of `current_fragment == 1`
+    /// (unless the set is forward error corrected)
     previous_fragments_set_id: Option<i32>,
 
     /// Optional ID of next `FragmentSet` into which the original message was split.
     /// Note, this option is only valid of `current_fragment == total_fragments == u8::MAX`
+    /// (unless the set is forward error corrected)
     next_fragments_set_id: Option<i32>,
+
+    /// If the `FragmentSet` is protected with forward error correction, number of `Fragment`s
+    /// holding the actual data. Any `data_fragments` out of `total_fragments` are sufficient
+    /// to recover the entire set.
+    data_fragments: Option<u8>,
 }
 
 impl FragmentHeader {
@@ -363,9 +448,89 @@ impl FragmentHeader {
             current_fragment,
             previous_fragments_set_id,
             next_fragments_set_id,
+            data_fragments: None,
         })
     }
 
+    /// Tries to create a new `FragmentHeader` for a forward error corrected `FragmentSet`.
+    /// Note that in that case every `Fragment` in the set carries its linking information.
+    fn try_new_fec(
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+        data_fragments: u8,
+        previous_fragments_set_id: Option<i32>,
+        next_fragments_set_id: Option<i32>,
+    ) -> Result<Self, ChunkingError> {
+        if id <= 0 {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        if current_fragment == 0 || total_fragments < current_fragment {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        // there must be at least a single parity fragment in the set
+        if data_fragments == 0 || data_fragments >= total_fragments {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        for linked_id in [previous_fragments_set_id, next_fragments_set_id]
+            .into_iter()
+            .flatten()
+        {
+            if linked_id <= 0 || linked_id == id {
+                return Err(ChunkingError::MalformedHeaderError);
+            }
+        }
+
+        Ok(FragmentHeader {
+            id,
+            total_fragments,
+            current_fragment,
+            previous_fragments_set_id,
+            next_fragments_set_id,
+            data_fragments: Some(data_fragments),
+        })
+    }
+
+    /// Attempts to parse the tail of the forward error corrected header, i.e. everything after
+    /// the FEC marker byte.
+    fn try_fec_from_bytes(
+        b: &[u8],
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+    ) -> Result<Self, ChunkingError> {
+        if b.len() < FEC_FRAGMENTED_HEADER_LEN {
+            return Err(ChunkingError::TooShortFragmentHeader {
+                received: b.len(),
+                expected: FEC_FRAGMENTED_HEADER_LEN,
+            });
+        }
+
+        let data_fragments = b[7];
+        let parse_linked_id = |bytes: &[u8]| {
+            let flagged_linked_id = i32::from_be_bytes(bytes.try_into().unwrap());
+            if flagged_linked_id == 0 {
+                Ok(None)
+            } else if ((flagged_linked_id >> 31) & 1) == 0 {
+                Err(ChunkingError::MalformedHeaderError)
+            } else {
+                Ok(Some(flagged_linked_id & !(1 << 31)))
+            }
+        };
+
+        let previous_fragments_set_id = parse_linked_id(&b[8..12])?;
+        let next_fragments_set_id = parse_linked_id(&b[12..16])?;
+
+        Self::try_new_fec(
+            id,
+            total_fragments,
+            current_fragment,
+            data_fragments,
+            previous_fragments_set_id,
+            next_fragments_set_id,
+        )
+    }
+
     /// Tries to recover `FragmentHeader` from slice of bytes extracted from received sphinx packet.
     /// If successful, returns `Self` and number of bytes used, as those can differ based on the
     /// type of header (unlinked or linked).
@@ -391,6 +556,13 @@ impl FragmentHeader {
             return Err(ChunkingError::MalformedHeaderError);
         }
 
+        if b[6] == FEC_FRAGMENT_MARKER {
+            return Ok((
+                Self::try_fec_from_bytes(b, id, total_fragments, current_fragment)?,
+                FEC_FRAGMENTED_HEADER_LEN,
+            ));
+        }
+
         let mut previous_fragments_set_id = None;
         let mut next_fragments_set_id = None;
 
@@ -446,6 +618,21 @@ impl FragmentHeader {
             .chain(std::iter::once(self.total_fragments))
             .chain(std::iter::once(self.current_fragment));
 
+        if let Some(data_fragments) = self.data_fragments {
+            let linked_id_bytes = |linked_id: Option<i32>| {
+                linked_id
+                    .map(|linked_id| linked_id | (1 << 31))
+                    .unwrap_or_default()
+                    .to_be_bytes()
+            };
+            return bytes_prefix_iter
+                .chain(std::iter::once(FEC_FRAGMENT_MARKER))
+                .chain(std::iter::once(data_fragments))
+                .chain(linked_id_bytes(self.previous_fragments_set_id))
+                .chain(linked_id_bytes(self.next_fragments_set_id))
+                .collect();
+        }
+
         let is_linked =
             self.previous_fragments_set_id.is_some() || self.next_fragments_set_id.is_some();
         if is_linked {
@@ -606,6 +793,55 @@ mod fragment_tests {
         assert_eq!(fragment, Fragment::try_from_bytes(&packet_bytes).unwrap());
     }
 
+    #[test]
+    fn can_be_converted_to_and_from_bytes_for_fec_payload() {
+        let mut rng = thread_rng();
+
+        let mut msg = vec![0u8; fec_fragment_payload_len(max_plaintext_size())];
+        rng.fill_bytes(&mut msg);
+
+        let unlinked_fragment = Fragment {
+            header: FragmentHeader::try_new_fec(12345, 10, 4, 8, None, None).unwrap(),
+            payload: msg.clone(),
+        };
+        let packet_bytes = unlinked_fragment.clone().into_bytes();
+        assert_eq!(packet_bytes.len(), max_plaintext_size());
+        assert_eq!(
+            unlinked_fragment,
+            Fragment::try_from_bytes(&packet_bytes).unwrap()
+        );
+
+        let linked_fragment = Fragment {
+            header: FragmentHeader::try_new_fec(12345, 10, 4, 8, Some(1234), Some(4321)).unwrap(),
+            payload: msg,
+        };
+        let packet_bytes = linked_fragment.clone().into_bytes();
+        assert_eq!(packet_bytes.len(), max_plaintext_size());
+        assert_eq!(
+            linked_fragment,
+            Fragment::try_from_bytes(&packet_bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn fec_fragment_cannot_be_recovered_without_payload() {
+        let fragment = Fragment {
+            header: FragmentHeader::try_new_fec(12345, 10, 4, 8, None, None).unwrap(),
+            payload: Vec::new(),
+        };
+        assert_eq!(
+            Fragment::try_from_bytes(&fragment.into_bytes()),
+            Err(ChunkingError::EmptyFecFragmentPayload)
+        );
+    }
+
+    #[test]
+    fn fec_header_requires_at_least_one_parity_fragment() {
+        assert!(FragmentHeader::try_new_fec(12345, 10, 4, 9, None, None).is_ok());
+        assert!(FragmentHeader::try_new_fec(12345, 10, 4, 10, None, None).is_err());
+        assert!(FragmentHeader::try_new_fec(12345, 10, 4, 0, None, None).is_err());
+    }
+
     #[test]
     fn unlinked_fragment_can_be_created_with_payload_of_valid_length() {
         let id = 12345;
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::fragment::{linked_fragment_payload_max_len, unlinked_fragment_payload_max_len};
+pub use compression::CompressionError;
+pub use fec::{number_of_required_fec_fragments, split_into_fec_sets, ForwardErrorCorrection};
 pub use set::split_into_sets;
 use thiserror::Error;
 
@@ -18,6 +20,8 @@ pub const MIN_PADDING_OVERHEAD: usize = 1;
 // they should definitely be revisited.
 // For instance there are not tests for the cases when we are padding the message
 
+pub mod compression;
+pub mod fec;
 pub mod fragment;
 pub mod reconstruction;
 pub mod set;
@@ -45,6 +49,13 @@ pub mod set;
 /// Again, the reasoning for this is further explained in `set.rs` file. However, you might
 /// also want to look at `fragment.rs` to understand the full context behind that design choice.
 ///
+/// Optionally, each `Set` might also be protected with forward error correction, in which case
+/// it is extended with additional parity `Fragment`s and can be recovered from any sufficiently
+/// large subset of them. This is explained in `fec.rs` file.
+///
+/// Before being split, the message might also get compressed, which is explained in
+/// `compression.rs` file.
+///
 /// Both of those concepts as well as their structures, i.e. `Set` and `Fragment`
 /// are further explained in the respective files.
 
@@ -68,6 +79,12 @@ pub enum ChunkingError {
 
     #[error("Received fragment identifier ({received}) is not a valid value!")]
     MalformedFragmentIdentifier { received: i32 },
+
+    #[error("Received forward error corrected fragment without any payload")]
+    EmptyFecFragmentPayload,
+
+    #[error("Failed to recover forward error corrected set: {0}")]
+    FecReconstructionFailure(String),
 }
 
 /// Returns number of fragments the message will be split to as well as number of available
@@ -1,9 +1,30 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
+use crate::fec::reconstruct_fec_set_data;
 use crate::fragment::Fragment;
 use crate::ChunkingError;
 use log::*;
 use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+/// Default amount of time an incomplete set is kept around after receiving its most recent `Fragment`.
+pub const DEFAULT_INCOMPLETE_SET_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Default upper bound on the amount of fragment payload data buffered across all incomplete sets.
+pub const DEFAULT_MEMORY_BUDGET: usize = 128 * 1024 * 1024;
+
+/// How often the buffered sets are checked for expiry.
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Maximum number of eviction records kept around until they're retrieved with
+/// [`MessageReconstructor::take_evicted_sets`]. Past that point the oldest records are discarded.
+const MAX_PENDING_EVICTION_RECORDS: usize = 1024;
 
 // TODO: perhaps a more sophisticated approach with writing to disk periodically in case
 // we're receiving fast & furious in uncompressed 4K - we don't want to keep that in memory;
@@ -31,6 +52,16 @@ struct ReconstructionBuffer {
     /// appropriately resized and all missing fragments are set to a `None`, thus keeping
     /// everything in order the whole time, allowing for O(1) insertions and O(n) reconstruction.
     fragments: Vec<Option<Fragment>>,
+
+    /// If the set is protected with forward error correction, number of `Fragment`s required
+    /// to recover its data. Otherwise, every single `Fragment` has to be received.
+    data_fragments: Option<u8>,
+
+    /// Time at which the most recent `Fragment` of this set has been received.
+    last_fragment_received: Instant,
+
+    /// Total size of payloads of all the `Fragment`s currently held by the buffer.
+    buffered_bytes: usize,
 }
 
 /// Type alias representing fully reconstructed message - its original data and list of all
@@ -56,21 +87,40 @@ impl ReconstructionBuffer {
             previous_fragments_set_id: None,
             next_fragments_set_id: None,
             fragments: fragments_buffer,
+            data_fragments: None,
+            last_fragment_received: Instant::now(),
+            buffered_bytes: 0,
+        }
+    }
+
+    /// Initialises new instance of a `ReconstructionBuffer` for a forward error corrected set
+    /// of given size that can be recovered from any `data_fragments` of its `Fragment`s.
+    fn new_fec(size: u8, data_fragments: u8) -> Self {
+        ReconstructionBuffer {
+            data_fragments: Some(data_fragments),
+            ..Self::new(size)
         }
     }
 
     /// After receiving all data, consumes `self` in order to recover original data
     /// encapsulated in this particular set.
-    fn reconstruct_set_data(self) -> Vec<u8> {
+    /// Recovery of forward error corrected sets might fail if the received `Fragment`s
+    /// turn out not to form a valid set.
+    fn reconstruct_set_data(self) -> Result<Vec<u8>, ChunkingError> {
         // Note: `reconstruct_set_data` is never called without first explicitly checking
         // if the set is complete.
         debug_assert!(self.is_complete);
 
-        self.fragments
+        if let Some(data_fragments) = self.data_fragments {
+            return reconstruct_fec_set_data(self.fragments, data_fragments);
+        }
+
+        Ok(self
+            .fragments
             .into_iter()
             .map(|fragment| fragment.unwrap().extract_payload())
             .flat_map(|fragment_data| fragment_data.into_iter())
-            .collect()
+            .collect())
     }
 
     // TODO: check what's the performance impact of this, and if it's too big, keep track of number
@@ -78,8 +128,47 @@ impl ReconstructionBuffer {
     // we might have false positives if somehow we receive a duplicate
     /// Checks if `self` is done receiving `Fragment` data by checking if there are still
     /// any `None` elements in the `fragments` vector.
+    /// For forward error corrected sets, it is sufficient to have received `data_fragments`
+    /// of the `Fragment`s instead.
     fn is_done_receiving(&self) -> bool {
-        !self.fragments.contains(&None)
+        match self.data_fragments {
+            Some(data_fragments) => {
+                self.fragments.iter().flatten().count() >= data_fragments as usize
+            }
+            None => !self.fragments.contains(&None),
+        }
+    }
+
+    /// Checks whether the metadata of the provided `Fragment` is consistent with the set it's
+    /// meant to be inserted into. This is especially important for forward error corrected sets
+    /// as the erasure code requires all of the shards to have identical lengths.
+    fn is_consistent_with(&self, fragment: &Fragment) -> bool {
+        if fragment.data_fragments() != self.data_fragments {
+            return false;
+        }
+        if self.data_fragments.is_none() {
+            return true;
+        }
+        if fragment.total_fragments() as usize != self.fragments.len() {
+            return false;
+        }
+        if fragment.payload_size() == 0 {
+            return false;
+        }
+
+        // compare against every other present fragment so that a duplicate can't be used
+        // to replace the only fragment the new one would otherwise be checked against
+        let position = fragment.current_fragment() as usize - 1;
+        self.fragments
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != position)
+            .filter_map(|(_, present)| present.as_ref())
+            .all(|present| {
+                present.payload_size() == fragment.payload_size()
+                    && present.previous_fragments_set_id() == fragment.previous_fragments_set_id()
+                    && present.next_fragments_set_id() == fragment.next_fragments_set_id()
+            })
     }
 
     /// Inserts new `Fragment` data into an appropriate position in the buffer.
@@ -93,6 +182,12 @@ impl ReconstructionBuffer {
     /// `previous_fragments_set_id` and `next_fragments_set_id` are set for the ease
     /// of access.
     fn insert_fragment(&mut self, fragment: Fragment) {
+        self.insert_fragment_at(fragment, Instant::now())
+    }
+
+    /// Inserts new `Fragment` data into the buffer as if it was received at the provided time.
+    /// Refer to [`Self::insert_fragment`] for more details.
+    fn insert_fragment_at(&mut self, fragment: Fragment, received_at: Instant) {
         // all fragments in the buffer should always have the same id as before inserting an element,
         // the correct buffer instance is looked up based on the fragment to be inserted.
         debug_assert!({
@@ -104,8 +199,17 @@ impl ReconstructionBuffer {
             }
         });
 
+        if !self.is_consistent_with(&fragment) {
+            warn!(
+                "received fragment {} (set id: {}) that is inconsistent with the rest of its set. It's going to be ignored",
+                fragment.current_fragment(),
+                fragment.id()
+            );
+            return;
+        }
+
         let fragment_index = fragment.current_fragment() as usize - 1;
-        if self.fragments[fragment_index].is_some() {
+        if let Some(duplicate) = &self.fragments[fragment_index] {
             // TODO: what to do in that case? give up on the message? overwrite it? panic?
             // it *might* be due to lock ack-packet, but let's keep the `warn` level in case
             // it could be somehow exploited
@@ -114,10 +218,21 @@ impl ReconstructionBuffer {
                 fragment.current_fragment(),
                 fragment.id()
             );
+            self.buffered_bytes -= duplicate.payload_size();
         }
+        self.last_fragment_received = received_at;
+        self.buffered_bytes += fragment.payload_size();
         self.fragments[fragment_index] = Some(fragment);
         if self.is_done_receiving() {
             self.is_complete = true;
+            if self.data_fragments.is_some() {
+                // every fragment of a forward error corrected set carries the full linking information
+                let present = self.fragments.iter().flatten().next().unwrap();
+                self.previous_fragments_set_id = present.previous_fragments_set_id();
+                self.next_fragments_set_id = present.next_fragments_set_id();
+                return;
+            }
+
             self.previous_fragments_set_id = self.fragments[0]
                 .as_ref()
                 .unwrap()
@@ -134,19 +249,110 @@ impl ReconstructionBuffer {
     }
 }
 
+/// Determines which incomplete set gets evicted first once the memory budget
+/// of the `MessageReconstructor` has been exceeded.
+#[derive(Default, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum EvictionPolicy {
+    /// Evict the set that has not received any `Fragment`s for the longest time.
+    #[default]
+    OldestFirst,
+
+    /// Evict the set that currently holds the most data.
+    LargestFirst,
+}
+
+/// Bounds on the resources used by the `MessageReconstructor` for holding incomplete sets.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ReconstructionLimits {
+    /// Amount of time after which an incomplete set that has not received any new `Fragment`s
+    /// is assumed to be lost and is evicted.
+    pub incomplete_set_timeout: Duration,
+
+    /// Maximum amount of fragment payload data buffered across all sets.
+    pub memory_budget: usize,
+
+    /// Policy used for choosing sets to evict once the `memory_budget` is exceeded.
+    pub eviction_policy: EvictionPolicy,
+}
+
+impl Default for ReconstructionLimits {
+    fn default() -> Self {
+        ReconstructionLimits {
+            incomplete_set_timeout: DEFAULT_INCOMPLETE_SET_TIMEOUT,
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            eviction_policy: EvictionPolicy::default(),
+        }
+    }
+}
+
+/// Reason for which an incomplete set got evicted from the `MessageReconstructor`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum EvictionReason {
+    /// The set has not received any new `Fragment`s within the `incomplete_set_timeout`.
+    Expired,
+
+    /// The set was evicted to bring the buffered data back within the `memory_budget`.
+    MemoryBudgetExceeded,
+
+    /// All of the `Fragment`s have been received, but the data could not be recovered from them.
+    ReconstructionFailure,
+}
+
+/// Information about an incomplete set that got dropped by the `MessageReconstructor`
+/// and thus whose underlying message can no longer be reconstructed.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct EvictedSet {
+    /// Id of the evicted set.
+    pub set_id: i32,
+
+    /// Number of `Fragment`s that have been received for the set before it got evicted.
+    pub received_fragments: usize,
+
+    /// Total number of `Fragment`s in the set.
+    pub total_fragments: usize,
+
+    /// Amount of fragment payload data that got dropped alongside the set.
+    pub buffered_bytes: usize,
+
+    /// Reason for the eviction.
+    pub reason: EvictionReason,
+}
+
+impl EvictedSet {
+    fn new(set_id: i32, buf: &ReconstructionBuffer, reason: EvictionReason) -> Self {
+        EvictedSet {
+            set_id,
+            received_fragments: buf.fragments.iter().flatten().count(),
+            total_fragments: buf.fragments.len(),
+            buffered_bytes: buf.buffered_bytes,
+            reason,
+        }
+    }
+}
+
 /// High level public structure used to buffer all received data `Fragment`s and eventually
 /// returning original messages that they encapsulate.
+///
+/// Incomplete sets are not kept indefinitely: they're evicted once they stop receiving
+/// new `Fragment`s for the configured amount of time or when the total amount of buffered
+/// data exceeds the memory budget. Otherwise, somebody could keep on sending maximum sized sets
+/// with one of the required fragments missing and all of the received data would be kept
+/// on the heap.
 #[derive(Default, PartialEq, Debug, Clone)]
 pub struct MessageReconstructor {
-    // TODO: some cleaner thread/routine that if message is incomplete and
-    // we haven't received any fragments in X time, we assume they
-    // were lost and message can't be restored.
-    // Perhaps add 'latest_fragment_timestamp' to each buffer
-    // and after N fragments received globally, check all of buffer timestamps.
-    // otherwise we are vulnerable to heap overflow attacks -> somebody can keep on sending
-    // maximum sized sets but without one of required fragments. All of the received
-    // data will be kept on the heap indefinitely in the current implementation.
     reconstructed_sets: HashMap<i32, ReconstructionBuffer>,
+
+    /// Bounds on the data held by the reconstructor.
+    limits: ReconstructionLimits,
+
+    /// Total size of payloads of all the `Fragment`s held across all `reconstructed_sets`.
+    buffered_bytes: usize,
+
+    /// Time at which the sets were last checked for expiry.
+    last_expiry_check: Option<Instant>,
+
+    /// Sets evicted since the last call to `take_evicted_sets`.
+    evicted: Vec<EvictedSet>,
 }
 
 impl MessageReconstructor {
@@ -155,6 +361,94 @@ impl MessageReconstructor {
         Default::default()
     }
 
+    /// Creates an empty `MessageReconstructor` bounded by the provided limits.
+    pub fn with_limits(limits: ReconstructionLimits) -> Self {
+        MessageReconstructor {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the total amount of fragment payload data currently buffered.
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    /// Returns information about all sets that got evicted since the last call to this method.
+    pub fn take_evicted_sets(&mut self) -> Vec<EvictedSet> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// Removes the set of given `id` and records the fact it got evicted.
+    fn evict_set(&mut self, set_id: i32, reason: EvictionReason) {
+        let Some(buf) = self.reconstructed_sets.remove(&set_id) else {
+            return;
+        };
+        self.buffered_bytes = self.buffered_bytes.saturating_sub(buf.buffered_bytes);
+
+        let evicted = EvictedSet::new(set_id, &buf, reason);
+        debug!("evicting incomplete set: {evicted:?}");
+        self.record_eviction(evicted)
+    }
+
+    /// Stores the eviction record so that it could be retrieved with [`Self::take_evicted_sets`].
+    fn record_eviction(&mut self, evicted: EvictedSet) {
+        if self.evicted.len() >= MAX_PENDING_EVICTION_RECORDS {
+            self.evicted.remove(0);
+        }
+        self.evicted.push(evicted);
+    }
+
+    /// Evicts all sets that have not received any new `Fragment`s within the `incomplete_set_timeout`.
+    fn evict_expired_sets(&mut self, now: Instant) {
+        if let Some(last_check) = self.last_expiry_check {
+            if now.duration_since(last_check) < EXPIRY_CHECK_INTERVAL {
+                return;
+            }
+        }
+        self.last_expiry_check = Some(now);
+
+        let timeout = self.limits.incomplete_set_timeout;
+        let expired: Vec<_> = self
+            .reconstructed_sets
+            .iter()
+            .filter(|(_, buf)| now.duration_since(buf.last_fragment_received) >= timeout)
+            .map(|(&id, _)| id)
+            .collect();
+
+        for set_id in expired {
+            self.evict_set(set_id, EvictionReason::Expired)
+        }
+    }
+
+    /// Evicts sets, as determined by the `eviction_policy`, until the total amount of buffered
+    /// data is back within the `memory_budget`.
+    fn enforce_memory_budget(&mut self) {
+        while self.buffered_bytes > self.limits.memory_budget {
+            let victim = match self.limits.eviction_policy {
+                EvictionPolicy::OldestFirst => self
+                    .reconstructed_sets
+                    .iter()
+                    .min_by_key(|(_, buf)| buf.last_fragment_received)
+                    .map(|(&id, _)| id),
+                EvictionPolicy::LargestFirst => self
+                    .reconstructed_sets
+                    .iter()
+                    .max_by_key(|(_, buf)| buf.buffered_bytes)
+                    .map(|(&id, _)| id),
+            };
+
+            match victim {
+                Some(set_id) => self.evict_set(set_id, EvictionReason::MemoryBudgetExceeded),
+                None => {
+                    // this should be impossible as the counter would have been zero
+                    warn!("buffered bytes counter is out of sync with the reconstructed sets");
+                    self.buffered_bytes = 0;
+                }
+            }
+        }
+    }
+
     /// Given fully received set of given `id`, if it has any post-linked sets, recursively
     /// checks if all of them were also fully received.
     fn check_front_chain(&self, id: i32) -> bool {
@@ -235,51 +529,98 @@ impl MessageReconstructor {
 
     /// Given id of a set, consume its buffer and reconstruct the original payload.
     /// Note, before you call this method, you *must* ensure set was fully received
-    fn extract_set_payload(&mut self, set_id: i32) -> Vec<u8> {
+    fn extract_set_payload(&mut self, set_id: i32) -> Result<Vec<u8>, ChunkingError> {
         debug_assert!(self.is_set_fully_received(set_id));
-        self.reconstructed_sets
-            .remove(&set_id)
-            .unwrap()
-            .reconstruct_set_data()
+        let buf = self.reconstructed_sets.remove(&set_id).unwrap();
+        self.buffered_bytes = self.buffered_bytes.saturating_sub(buf.buffered_bytes);
+        buf.reconstruct_set_data()
     }
 
     // Future consideration: perhaps for long messages, rather than return whole data allocated
     // on the heap, return file handle with the saved content?
     /// Given id of *any* one of the sets into which message was divided,
     /// reconstruct the entire original message.
+    /// If any of the sets turns out to be unrecoverable, all of them are dropped
+    /// and recorded as evicted.
     /// Note, before you call this method, you *must* ensure all sets were fully received
-    fn reconstruct_message(&mut self, set_id: i32) -> ReconstructedMessage {
+    fn reconstruct_message(&mut self, set_id: i32) -> Option<ReconstructedMessage> {
         debug_assert!(self.is_message_fully_received(set_id));
         let starting_id = self.find_starting_set_id(set_id).unwrap();
         let set_id_sequence: Vec<_> =
             std::iter::successors(Some(starting_id), |&id| self.next_linked_set_id(id)).collect();
 
-        let message_content: Vec<_> = set_id_sequence
+        // prepare the records up front as the buffers are consumed during the reconstruction
+        let eviction_records: Vec<_> = set_id_sequence
             .iter()
-            .map(|&id| self.extract_set_payload(id))
-            .flat_map(|payload| payload.into_iter())
+            .map(|id| {
+                EvictedSet::new(
+                    *id,
+                    &self.reconstructed_sets[id],
+                    EvictionReason::ReconstructionFailure,
+                )
+            })
             .collect();
 
-        (message_content, set_id_sequence)
+        let mut message_content = Vec::new();
+        for &id in &set_id_sequence {
+            match self.extract_set_payload(id) {
+                Ok(payload) => message_content.extend(payload),
+                Err(err) => {
+                    warn!("failed to reconstruct set {id}: {err}. The entire message is going to be dropped");
+                    for evicted in eviction_records {
+                        if let Some(buf) = self.reconstructed_sets.remove(&evicted.set_id) {
+                            self.buffered_bytes =
+                                self.buffered_bytes.saturating_sub(buf.buffered_bytes);
+                        }
+                        self.record_eviction(evicted)
+                    }
+                    return None;
+                }
+            }
+        }
+
+        Some((message_content, set_id_sequence))
     }
 
     /// Given recovered `Fragment`, tries to insert it into an appropriate `ReconstructionBuffer`.
     /// If a buffer does not exist, a new instance is created.
     /// If it was last remaining `Fragment` for the original message, the message is reconstructed
     /// and returned alongside all (if applicable) set ids used in the message.
+    ///
+    /// Any incomplete sets exceeding the configured limits are evicted in the process
+    /// and can be retrieved with [`Self::take_evicted_sets`].
     pub fn insert_new_fragment(&mut self, fragment: Fragment) -> Option<ReconstructedMessage> {
+        self.insert_new_fragment_at(fragment, Instant::now())
+    }
+
+    /// Inserts the provided `Fragment` as if it was received at the specified time.
+    /// Refer to [`Self::insert_new_fragment`] for more details.
+    fn insert_new_fragment_at(
+        &mut self,
+        fragment: Fragment,
+        now: Instant,
+    ) -> Option<ReconstructedMessage> {
+        self.evict_expired_sets(now);
+
         let set_id = fragment.id();
         let set_len = fragment.total_fragments();
 
-        let buf = self
-            .reconstructed_sets
-            .entry(set_id)
-            .or_insert_with(|| ReconstructionBuffer::new(set_len));
+        let buf = self.reconstructed_sets.entry(set_id).or_insert_with(|| {
+            match fragment.data_fragments() {
+                Some(data_fragments) => ReconstructionBuffer::new_fec(set_len, data_fragments),
+                None => ReconstructionBuffer::new(set_len),
+            }
+        });
+
+        let previously_buffered = buf.buffered_bytes;
+        buf.insert_fragment_at(fragment, now);
+        self.buffered_bytes =
+            self.buffered_bytes.saturating_sub(previously_buffered) + buf.buffered_bytes;
 
-        buf.insert_fragment(fragment);
         if self.is_message_fully_received(set_id) {
-            Some(self.reconstruct_message(set_id))
+            self.reconstruct_message(set_id)
         } else {
+            self.enforce_memory_budget();
             None
         }
     }
@@ -341,7 +682,7 @@ mod reconstruction_buffer {
         // acks are ignored as they will be stripped by gateways before getting to the reconstruction
 
         buf.insert_fragment(Fragment::try_from_bytes(&raw_fragments[0]).unwrap());
-        assert_eq!(message.to_vec(), buf.reconstruct_set_data());
+        assert_eq!(message.to_vec(), buf.reconstruct_set_data().unwrap());
 
         let mut buf = ReconstructionBuffer::new(3);
         let message = vec![42u8; unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE) * 3];
@@ -356,7 +697,7 @@ mod reconstruction_buffer {
         buf.insert_fragment(Fragment::try_from_bytes(&raw_fragments[0]).unwrap());
         buf.insert_fragment(Fragment::try_from_bytes(&raw_fragments[1]).unwrap());
         buf.insert_fragment(Fragment::try_from_bytes(&raw_fragments[2]).unwrap());
-        assert_eq!(message.to_vec(), buf.reconstruct_set_data());
+        assert_eq!(message.to_vec(), buf.reconstruct_set_data().unwrap());
 
         let mut buf = ReconstructionBuffer::new(u8::MAX);
         let message = vec![
@@ -373,7 +714,7 @@ mod reconstruction_buffer {
         for raw_fragment in raw_fragments {
             buf.insert_fragment(Fragment::try_from_bytes(&raw_fragment).unwrap())
         }
-        assert_eq!(message.to_vec(), buf.reconstruct_set_data());
+        assert_eq!(message.to_vec(), buf.reconstruct_set_data().unwrap());
     }
 
     #[test]
@@ -392,7 +733,7 @@ mod reconstruction_buffer {
         buf.insert_fragment(Fragment::try_from_bytes(&raw_fragments[0]).unwrap());
         buf.insert_fragment(Fragment::try_from_bytes(&raw_fragments[1]).unwrap());
 
-        buf.reconstruct_set_data();
+        buf.reconstruct_set_data().unwrap();
     }
 
     #[test]
@@ -861,6 +1202,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: None,
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
 
@@ -871,6 +1215,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: None,
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
 
@@ -998,6 +1345,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: Some(1234),
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
 
@@ -1008,6 +1358,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(12345),
                 next_fragments_set_id: Some(123),
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
 
@@ -1018,6 +1371,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(1234),
                 next_fragments_set_id: Some(12),
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
 
@@ -1028,6 +1384,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(123),
                 next_fragments_set_id: None,
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
 
@@ -1075,6 +1434,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: Some(1234),
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
         reconstructor.reconstructed_sets.insert(
@@ -1084,6 +1446,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(12345),
                 next_fragments_set_id: None,
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
         assert_eq!(reconstructor.previous_linked_set_id(12345), None);
@@ -1131,6 +1496,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: Some(1234),
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
         reconstructor.reconstructed_sets.insert(
@@ -1140,6 +1508,9 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(12345),
                 next_fragments_set_id: None,
                 fragments: vec![],
+                data_fragments: None,
+                last_fragment_received: Instant::now(),
+                buffered_bytes: 0,
             },
         );
         assert_eq!(reconstructor.next_linked_set_id(12345), Some(1234));
@@ -1174,7 +1545,7 @@ mod message_reconstructor {
             .is_none());
 
         let id = Fragment::try_from_bytes(&raw_fragments[0]).unwrap().id();
-        reconstructor.extract_set_payload(id);
+        let _ = reconstructor.extract_set_payload(id);
     }
 
     #[test]
@@ -1202,10 +1573,13 @@ mod message_reconstructor {
         let another_buf_clone = set_buf.clone();
         reconstructor.reconstructed_sets.insert(set_id, set_buf);
         assert_eq!(
-            reconstructor.extract_set_payload(set_id),
-            buf_clone.reconstruct_set_data()
+            reconstructor.extract_set_payload(set_id).unwrap(),
+            buf_clone.reconstruct_set_data().unwrap()
+        );
+        assert_eq!(
+            another_buf_clone.reconstruct_set_data().unwrap(),
+            message.to_vec()
         );
-        assert_eq!(another_buf_clone.reconstruct_set_data(), message.to_vec());
     }
 
     #[test]
@@ -1233,9 +1607,9 @@ mod message_reconstructor {
 
         reconstructor.reconstructed_sets.insert(set_id, set_buf);
         let mut reconstructor_clone = reconstructor.clone();
-        let reconstructed_message = reconstructor_clone.reconstruct_message(set_id);
+        let reconstructed_message = reconstructor_clone.reconstruct_message(set_id).unwrap();
         assert_eq!(
-            reconstructor.extract_set_payload(set_id),
+            reconstructor.extract_set_payload(set_id).unwrap(),
             reconstructed_message.0
         );
         assert_eq!(reconstructed_message.1.len(), 1);
@@ -1277,13 +1651,13 @@ mod message_reconstructor {
         let mut reconstructor_clone = reconstructor.clone();
         let mut reconstructor_clone2 = reconstructor.clone();
 
-        let extracted_set1 = reconstructor.extract_set_payload(set_id1);
-        let extracted_set2 = reconstructor.extract_set_payload(set_id2);
+        let extracted_set1 = reconstructor.extract_set_payload(set_id1).unwrap();
+        let extracted_set2 = reconstructor.extract_set_payload(set_id2).unwrap();
 
         let manually_combined_message = [extracted_set1, extracted_set2].concat();
 
-        let reconstructed_message1 = reconstructor_clone.reconstruct_message(set_id1);
-        let reconstructed_message2 = reconstructor_clone2.reconstruct_message(set_id2);
+        let reconstructed_message1 = reconstructor_clone.reconstruct_message(set_id1).unwrap();
+        let reconstructed_message2 = reconstructor_clone2.reconstruct_message(set_id2).unwrap();
 
         assert_eq!(reconstructed_message1.1.len(), 2);
         assert_eq!(reconstructed_message1.1, vec![set_id1, set_id2]);
@@ -1330,9 +1704,139 @@ mod message_reconstructor {
     }
 }
 
+#[cfg(test)]
+mod reconstructor_eviction {
+    use super::*;
+    use crate::fragment::unlinked_fragment_payload_max_len;
+
+    // just some arbitrary value to use in tests
+    const AVAILABLE_PLAINTEXT_SIZE: usize = 1024;
+
+    // produces fragments of a single 3-fragment set
+    fn three_fragment_set() -> Vec<Fragment> {
+        let message = vec![42u8; unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE) * 3];
+        crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+            .into_iter()
+            .flat_map(|fragment_set| fragment_set.into_iter())
+            .collect()
+    }
+
+    #[test]
+    fn buffered_bytes_are_tracked_until_set_is_reconstructed() {
+        let mut reconstructor = MessageReconstructor::default();
+        let mut fragments = three_fragment_set();
+        let last = fragments.pop().unwrap();
+
+        let expected: usize = fragments.iter().map(|f| f.payload_size()).sum();
+        for fragment in fragments {
+            assert!(reconstructor.insert_new_fragment(fragment).is_none());
+        }
+        assert_eq!(reconstructor.buffered_bytes(), expected);
+
+        assert!(reconstructor.insert_new_fragment(last).is_some());
+        assert_eq!(reconstructor.buffered_bytes(), 0);
+        assert!(reconstructor.take_evicted_sets().is_empty());
+    }
+
+    #[test]
+    fn incomplete_sets_are_evicted_after_timeout() {
+        let limits = ReconstructionLimits {
+            incomplete_set_timeout: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut reconstructor = MessageReconstructor::with_limits(limits);
+        let start = Instant::now();
+
+        let mut stale = three_fragment_set();
+        let stale_id = stale[0].id();
+        reconstructor.insert_new_fragment_at(stale.pop().unwrap(), start);
+
+        let mut fresh = three_fragment_set();
+        let fresh_id = fresh[0].id();
+        reconstructor.insert_new_fragment_at(fresh.pop().unwrap(), start + Duration::from_secs(30));
+        assert!(reconstructor.take_evicted_sets().is_empty());
+
+        reconstructor.insert_new_fragment_at(fresh.pop().unwrap(), start + Duration::from_secs(61));
+        let evicted = reconstructor.take_evicted_sets();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].set_id, stale_id);
+        assert_eq!(evicted[0].reason, EvictionReason::Expired);
+        assert_eq!(evicted[0].received_fragments, 1);
+        assert_eq!(evicted[0].total_fragments, 3);
+
+        assert!(!reconstructor.reconstructed_sets.contains_key(&stale_id));
+        assert!(reconstructor.reconstructed_sets.contains_key(&fresh_id));
+
+        // the remaining set can still be completed
+        assert!(reconstructor
+            .insert_new_fragment_at(fresh.pop().unwrap(), start + Duration::from_secs(62))
+            .is_some());
+    }
+
+    #[test]
+    fn oldest_set_is_evicted_when_over_memory_budget() {
+        let fragment_size = three_fragment_set()[0].payload_size();
+        let limits = ReconstructionLimits {
+            memory_budget: fragment_size * 2,
+            eviction_policy: EvictionPolicy::OldestFirst,
+            ..Default::default()
+        };
+        let mut reconstructor = MessageReconstructor::with_limits(limits);
+        let start = Instant::now();
+
+        let mut first = three_fragment_set();
+        let mut second = three_fragment_set();
+        let mut third = three_fragment_set();
+        let first_id = first[0].id();
+
+        reconstructor.insert_new_fragment_at(first.pop().unwrap(), start);
+        reconstructor.insert_new_fragment_at(second.pop().unwrap(), start + Duration::from_secs(1));
+        assert!(reconstructor.take_evicted_sets().is_empty());
+
+        reconstructor.insert_new_fragment_at(third.pop().unwrap(), start + Duration::from_secs(2));
+        let evicted = reconstructor.take_evicted_sets();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].set_id, first_id);
+        assert_eq!(evicted[0].reason, EvictionReason::MemoryBudgetExceeded);
+        assert_eq!(reconstructor.buffered_bytes(), fragment_size * 2);
+    }
+
+    #[test]
+    fn largest_set_is_evicted_when_over_memory_budget() {
+        let fragment_size = three_fragment_set()[0].payload_size();
+        let limits = ReconstructionLimits {
+            memory_budget: fragment_size * 3,
+            eviction_policy: EvictionPolicy::LargestFirst,
+            ..Default::default()
+        };
+        let mut reconstructor = MessageReconstructor::with_limits(limits);
+        let start = Instant::now();
+
+        let mut small = three_fragment_set();
+        let mut large = three_fragment_set();
+        let large_id = large[0].id();
+
+        reconstructor.insert_new_fragment_at(small.pop().unwrap(), start);
+        reconstructor.insert_new_fragment_at(large.pop().unwrap(), start);
+        reconstructor.insert_new_fragment_at(large.pop().unwrap(), start);
+        assert!(reconstructor.take_evicted_sets().is_empty());
+
+        let mut another = three_fragment_set();
+        reconstructor.insert_new_fragment_at(another.pop().unwrap(), start);
+        let evicted = reconstructor.take_evicted_sets();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].set_id, large_id);
+        assert_eq!(evicted[0].received_fragments, 2);
+        assert_eq!(evicted[0].buffered_bytes, fragment_size * 2);
+        assert_eq!(reconstructor.buffered_bytes(), fragment_size * 2);
+    }
+}
+
 #[cfg(test)]
 mod message_reconstruction {
     use super::*;
+    use crate::fec::ForwardErrorCorrection;
+    use crate::fragment::fec_fragment_payload_len;
     use rand::seq::SliceRandom;
     use rand::{thread_rng, RngCore};
 
@@ -1843,4 +2347,178 @@ mod message_reconstruction {
             }
         }
     }
+
+    fn split_with_fec(message: &[u8], fec: ForwardErrorCorrection) -> Vec<Fragment> {
+        crate::split_into_fec_sets(
+            &mut rand::rngs::OsRng,
+            message,
+            AVAILABLE_PLAINTEXT_SIZE,
+            fec,
+        )
+        .into_iter()
+        .flat_map(|fragment_set| fragment_set.into_iter())
+        .collect()
+    }
+
+    #[test]
+    fn it_reconstructs_message_with_lost_fragments() {
+        let mut rng = thread_rng();
+
+        let mut message = vec![0u8; fec_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE) * 20];
+        rng.fill_bytes(&mut message);
+
+        let mut fragments = split_with_fec(&message, ForwardErrorCorrection::new(25));
+        assert_eq!(fragments.len(), 25);
+
+        // lose 5 random fragments
+        fragments.shuffle(&mut rng);
+        fragments.truncate(20);
+
+        let mut message_reconstructor = MessageReconstructor::default();
+        let mut reconstructed = None;
+        for fragment in fragments {
+            assert!(reconstructed.is_none());
+            reconstructed = message_reconstructor.insert_new_fragment(
+                message_reconstructor
+                    .recover_fragment(fragment.into_bytes())
+                    .unwrap(),
+            );
+        }
+
+        let reconstructed_message = reconstructed.unwrap();
+        assert_eq!(reconstructed_message.0, message);
+        assert_eq!(reconstructed_message.1.len(), 1);
+    }
+
+    #[test]
+    fn it_does_not_reconstruct_message_with_too_many_lost_fragments() {
+        let mut rng = thread_rng();
+
+        let mut message = vec![0u8; fec_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE) * 20];
+        rng.fill_bytes(&mut message);
+
+        let mut fragments = split_with_fec(&message, ForwardErrorCorrection::new(25));
+        fragments.shuffle(&mut rng);
+        fragments.truncate(19);
+
+        let mut message_reconstructor = MessageReconstructor::default();
+        for fragment in fragments {
+            assert!(message_reconstructor
+                .insert_new_fragment(fragment)
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn it_reconstructs_multi_set_message_with_lost_fragments() {
+        let mut rng = thread_rng();
+
+        let fec = ForwardErrorCorrection::new(10);
+        let mut message = vec![
+            0u8;
+            fec_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE)
+                * (2 * fec.max_data_fragments_per_set() + 10)
+        ];
+        rng.fill_bytes(&mut message);
+
+        let fragments = split_with_fec(&message, fec);
+
+        // lose the first parity fragment of each set
+        let mut fragments: Vec<_> = fragments
+            .into_iter()
+            .filter(|fragment| {
+                fragment.current_fragment() != fragment.data_fragments().unwrap() + 1
+            })
+            .collect();
+        fragments.shuffle(&mut rng);
+
+        let mut message_reconstructor = MessageReconstructor::default();
+        let mut reconstructed = None;
+        for fragment in fragments {
+            if let Some(msg) = message_reconstructor.insert_new_fragment(fragment) {
+                reconstructed = Some(msg)
+            }
+        }
+
+        let reconstructed_message = reconstructed.unwrap();
+        assert_eq!(reconstructed_message.0, message);
+        assert_eq!(reconstructed_message.1.len(), 3);
+    }
+
+    #[test]
+    fn it_ignores_fec_fragments_of_inconsistent_length() {
+        let mut rng = thread_rng();
+
+        let mut message = vec![0u8; fec_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE) * 4];
+        rng.fill_bytes(&mut message);
+
+        let fragments = split_with_fec(&message, ForwardErrorCorrection::new(25));
+        assert_eq!(fragments.len(), 5);
+
+        let mut message_reconstructor = MessageReconstructor::default();
+        assert!(message_reconstructor
+            .insert_new_fragment(fragments[0].clone())
+            .is_none());
+
+        // truncated fragments are not going to be accepted into the set
+        for fragment in &fragments[1..4] {
+            let bytes = fragment.clone().into_bytes();
+            let truncated = Fragment::try_from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+            assert!(message_reconstructor
+                .insert_new_fragment(truncated)
+                .is_none());
+        }
+        assert_eq!(
+            message_reconstructor.buffered_bytes(),
+            fragments[0].payload_size()
+        );
+
+        let mut reconstructed = None;
+        for fragment in fragments.into_iter().skip(1).take(3) {
+            assert!(reconstructed.is_none());
+            reconstructed = message_reconstructor.insert_new_fragment(fragment);
+        }
+        assert_eq!(reconstructed.unwrap().0, message);
+    }
+
+    #[test]
+    fn unrecoverable_fec_set_is_dropped_and_recorded_as_evicted() {
+        let mut rng = thread_rng();
+
+        let mut message = vec![0u8; fec_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE) * 4];
+        rng.fill_bytes(&mut message);
+
+        let fragments = split_with_fec(&message, ForwardErrorCorrection::new(25));
+        let set_id = fragments[0].id();
+
+        // bypass the consistency checks to end up with shards of different lengths
+        let mut buf = ReconstructionBuffer::new_fec(5, 4);
+        for fragment in fragments.into_iter().take(4) {
+            let bytes = fragment.into_bytes();
+            let truncated = Fragment::try_from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+            let index = truncated.current_fragment() as usize - 1;
+            let fragment = if index == 0 {
+                Fragment::try_from_bytes(&bytes).unwrap()
+            } else {
+                truncated
+            };
+            buf.buffered_bytes += fragment.payload_size();
+            buf.fragments[index] = Some(fragment);
+        }
+        buf.is_complete = true;
+
+        let mut message_reconstructor = MessageReconstructor::default();
+        message_reconstructor.buffered_bytes = buf.buffered_bytes;
+        message_reconstructor.reconstructed_sets.insert(set_id, buf);
+
+        assert!(message_reconstructor.reconstruct_message(set_id).is_none());
+        assert!(message_reconstructor.reconstructed_sets.is_empty());
+        assert_eq!(message_reconstructor.buffered_bytes(), 0);
+
+        let evicted = message_reconstructor.take_evicted_sets();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].set_id, set_id);
+        assert_eq!(evicted[0].received_fragments, 4);
+        assert_eq!(evicted[0].reason, EvictionReason::ReconstructionFailure);
+    }
 }
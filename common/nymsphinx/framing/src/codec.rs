@@ -32,8 +32,9 @@ pub enum NymCodecError {
     FromBytes,
 }
 
-// TODO: in the future it could be extended to have state containing symmetric encryption key
-// so that all data could be encrypted easily (alternatively we could just slap TLS)
+// note: the codec itself does not perform any encryption. if the link between the nodes is meant
+// to be encrypted, the codec should be layered on top of the established noise session
+// (see the `nym-noise` crate)
 pub struct NymCodec;
 
 impl Encoder<FramedNymPacket> for NymCodec {
@@ -78,7 +79,9 @@ impl Decoder for NymCodec {
 
         // advance buffer past the header - at this point we have enough bytes
         src.advance(header.size());
-        let packet_bytes = src.split_to(packet_size);
+        // keep the received bytes around (without copying them) so that the packet could be
+        // reconstructed if it has to be re-processed
+        let packet_bytes = src.split_to(packet_size).freeze();
         let packet = if let Some(slice) = packet_bytes.get(..) {
             // here it could be debatable whether stream is corrupt or not,
             // but let's go with the safer approach and assume it is.
@@ -93,7 +96,11 @@ impl Decoder for NymCodec {
         };
 
         // let packet = SphinxPacket::from_bytes(&sphinx_packet_bytes)?;
-        let nymsphinx_packet = FramedNymPacket { header, packet };
+        let nymsphinx_packet = FramedNymPacket {
+            header,
+            packet,
+            received: Some(packet_bytes),
+        };
 
         // As per docs:
         // Before returning from the function, implementations should ensure that the buffer
@@ -209,6 +216,7 @@ mod packet_encoding {
         let packet = FramedNymPacket {
             header,
             packet: sphinx_packet,
+            received: None,
         };
 
         let mut bytes = BytesMut::new();
@@ -227,7 +235,11 @@ mod packet_encoding {
 
         NymPacket::outfox_from_bytes(packet_bytes.as_slice()).unwrap();
 
-        let packet = FramedNymPacket { header, packet };
+        let packet = FramedNymPacket {
+            header,
+            packet,
+            received: None,
+        };
 
         let mut bytes = BytesMut::new();
         NymCodec.encode(packet, &mut bytes).unwrap();
@@ -313,6 +325,7 @@ mod packet_encoding {
                     ..Default::default()
                 },
                 packet: make_valid_sphinx_packet(Default::default()),
+                received: None,
             };
 
             let mut bytes = BytesMut::new();
@@ -330,6 +343,7 @@ mod packet_encoding {
             let packet = FramedNymPacket {
                 header: Header::default(),
                 packet: make_valid_sphinx_packet(Default::default()),
+                received: None,
             };
 
             let mut bytes = BytesMut::new();
@@ -359,6 +373,7 @@ mod packet_encoding {
                         ..Default::default()
                     },
                     packet: make_valid_sphinx_packet(Default::default()),
+                    received: None,
                 };
 
                 let mut bytes = BytesMut::new();
@@ -386,6 +401,7 @@ mod packet_encoding {
                 let first_packet = FramedNymPacket {
                     header: Header::default(),
                     packet: make_valid_sphinx_packet(Default::default()),
+                    received: None,
                 };
 
                 let mut bytes = BytesMut::new();
@@ -405,11 +421,13 @@ mod packet_encoding {
         let packet1 = FramedNymPacket {
             header: Header::default(),
             packet: make_valid_sphinx_packet(Default::default()),
+            received: None,
         };
 
         let packet2 = FramedNymPacket {
             header: Header::default(),
             packet: make_valid_sphinx_packet(Default::default()),
+            received: None,
         };
 
         let mut bytes = BytesMut::new();
@@ -427,11 +445,13 @@ mod packet_encoding {
         let packet1 = FramedNymPacket {
             header: Header::default(),
             packet: make_valid_sphinx_packet(Default::default()),
+            received: None,
         };
 
         let packet2 = FramedNymPacket {
             header: Header::default(),
             packet: make_valid_sphinx_packet(Default::default()),
+            received: None,
         };
 
         let mut bytes = BytesMut::new();
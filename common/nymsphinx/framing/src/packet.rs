@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::codec::NymCodecError;
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use nym_sphinx_params::packet_sizes::PacketSize;
 use nym_sphinx_params::packet_version::PacketVersion;
 use nym_sphinx_params::PacketType;
@@ -15,6 +15,9 @@ pub struct FramedNymPacket {
 
     /// The actual SphinxPacket being sent.
     pub(crate) packet: NymPacket,
+
+    /// Raw bytes of the packet if it has been decoded from a stream.
+    pub(crate) received: Option<Bytes>,
 }
 
 impl FramedNymPacket {
@@ -35,7 +38,11 @@ impl FramedNymPacket {
             packet_type,
         };
 
-        FramedNymPacket { header, packet }
+        FramedNymPacket {
+            header,
+            packet,
+            received: None,
+        }
     }
 
     pub fn header(&self) -> Header {
@@ -53,6 +60,12 @@ impl FramedNymPacket {
     pub fn into_inner(self) -> NymPacket {
         self.packet
     }
+
+    /// Splits the framed packet into the actual packet and, if it has been decoded from a stream,
+    /// its raw bytes that allow reconstructing it without having to serialise it again.
+    pub fn into_parts(self) -> (NymPacket, Option<Bytes>) {
+        (self.packet, self.received)
+    }
 }
 
 // Contains any metadata that might be useful for sending between mix nodes.
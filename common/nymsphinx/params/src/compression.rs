@@ -0,0 +1,46 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("{received} is not a valid payload compression tag")]
+pub struct InvalidPayloadCompression {
+    received: u8,
+}
+
+/// Compression algorithm applied to the message payload before it gets split into fragments.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadCompression {
+    /// Better compression ratio at the cost of higher cpu usage.
+    /// Note that wasm32 clients can only decompress it, so they should use `Lz4` for sending.
+    Zstd = 1,
+
+    /// Very fast compression with a moderate compression ratio.
+    Lz4 = 2,
+}
+
+impl fmt::Display for PayloadCompression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PayloadCompression::Zstd => write!(f, "zstd"),
+            PayloadCompression::Lz4 => write!(f, "lz4"),
+        }
+    }
+}
+
+impl TryFrom<u8> for PayloadCompression {
+    type Error = InvalidPayloadCompression;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            _ if value == (PayloadCompression::Zstd as u8) => Ok(Self::Zstd),
+            _ if value == (PayloadCompression::Lz4 as u8) => Ok(Self::Lz4),
+            v => Err(InvalidPayloadCompression { received: v }),
+        }
+    }
+}
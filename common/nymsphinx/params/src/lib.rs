@@ -8,9 +8,11 @@ use nym_crypto::ctr;
 type Aes128Ctr = ctr::Ctr64BE<Aes128>;
 
 // Re-export for ease of use
+pub use compression::PayloadCompression;
 pub use packet_sizes::PacketSize;
 pub use packet_types::PacketType;
 
+pub mod compression;
 pub mod packet_sizes;
 pub mod packet_types;
 pub mod packet_version;
@@ -10,8 +10,11 @@ use nym_sphinx_anonymous_replies::requests::{
     InvalidReplyRequestError, RepliableMessage, RepliableMessageContent, ReplyMessage,
     ReplyMessageContent,
 };
+use nym_sphinx_chunking::compression::{self, CompressionError, MAX_DECOMPRESSED_MESSAGE_LEN};
 use nym_sphinx_chunking::fragment::Fragment;
-use nym_sphinx_params::{PacketSize, PacketType, ReplySurbKeyDigestAlgorithm};
+use nym_sphinx_chunking::ForwardErrorCorrection;
+use nym_sphinx_params::compression::InvalidPayloadCompression;
+use nym_sphinx_params::{PacketSize, PacketType, PayloadCompression, ReplySurbKeyDigestAlgorithm};
 use rand::Rng;
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
@@ -20,6 +23,12 @@ pub(crate) const ACK_OVERHEAD: usize = MAX_NODE_ADDRESS_UNPADDED_LEN + PacketSiz
 pub(crate) const OUTFOX_ACK_OVERHEAD: usize =
     MAX_NODE_ADDRESS_UNPADDED_LEN + PacketSize::OutfoxAckPacket.size();
 
+/// Bit of the message type tag indicating the rest of the message has been compressed.
+const COMPRESSED_MESSAGE_FLAG: u8 = 0b1000_0000;
+
+// compression algorithm || decompressed length
+const COMPRESSION_HEADER_LEN: usize = 1 + 4;
+
 #[derive(Debug, Error)]
 pub enum NymMessageError {
     #[error("{received} is not a valid type tag for a NymMessage")]
@@ -33,6 +42,15 @@ pub enum NymMessageError {
 
     #[error("Received empty message for deserialization")]
     EmptyMessage,
+
+    #[error("The received compressed message is too short to contain the compression header")]
+    TruncatedCompressionHeader,
+
+    #[error(transparent)]
+    InvalidPayloadCompression(#[from] InvalidPayloadCompression),
+
+    #[error("Failed to decompress the received message - {0}")]
+    DecompressionFailure(#[from] CompressionError),
 }
 
 #[repr(u8)]
@@ -149,20 +167,89 @@ impl NymMessage {
             .collect()
     }
 
+    // the compressed message is in the format of:
+    // (typ | COMPRESSED_MESSAGE_FLAG) || compression algorithm || decompressed length || compressed msg
+    // if the compression would not reduce the size of the message, the regular format is used instead
+    fn into_compressed_bytes(self, algorithm: PayloadCompression) -> Vec<u8> {
+        let typ = self.typ();
+        let inner = self.inner_bytes();
+
+        if inner.len() > MAX_DECOMPRESSED_MESSAGE_LEN {
+            return std::iter::once(typ as u8).chain(inner).collect();
+        }
+
+        match compression::compress(algorithm, &inner) {
+            Ok(compressed) if inner.len() > compression::max_decompressed_len(compressed.len()) => {
+                log::trace!("the message compresses beyond the ratio accepted by the recipients - sending it uncompressed");
+                std::iter::once(typ as u8).chain(inner).collect()
+            }
+            Ok(compressed) if compressed.len() + COMPRESSION_HEADER_LEN < inner.len() => {
+                log::trace!(
+                    "compressed the message from {} to {} bytes using {algorithm}",
+                    inner.len(),
+                    compressed.len()
+                );
+                [typ as u8 | COMPRESSED_MESSAGE_FLAG, algorithm as u8]
+                    .into_iter()
+                    .chain((inner.len() as u32).to_be_bytes())
+                    .chain(compressed)
+                    .collect()
+            }
+            Ok(_) => {
+                log::trace!("the message is not compressible - sending it uncompressed");
+                std::iter::once(typ as u8).chain(inner).collect()
+            }
+            Err(err) => {
+                log::warn!("failed to compress the message: {err} - sending it uncompressed");
+                std::iter::once(typ as u8).chain(inner).collect()
+            }
+        }
+    }
+
     fn try_from_bytes(bytes: &[u8], num_mix_hops: u8) -> Result<Self, NymMessageError> {
         if bytes.is_empty() {
             return Err(NymMessageError::EmptyMessage);
         }
 
-        let typ_tag = NymMessageType::try_from(bytes[0])?;
+        if bytes[0] & COMPRESSED_MESSAGE_FLAG == 0 {
+            let typ_tag = NymMessageType::try_from(bytes[0])?;
+            return Self::try_from_typed_bytes(typ_tag, &bytes[1..], num_mix_hops);
+        }
+
+        let typ_tag = NymMessageType::try_from(bytes[0] & !COMPRESSED_MESSAGE_FLAG)?;
+        if bytes.len() < 1 + COMPRESSION_HEADER_LEN {
+            return Err(NymMessageError::TruncatedCompressionHeader);
+        }
+        let algorithm = PayloadCompression::try_from(bytes[1])?;
+        // the unwrap is fine as we've just checked the length of the header
+        let decompressed_len = u32::from_be_bytes(bytes[2..6].try_into().unwrap()) as usize;
+        let decompressed = compression::decompress(
+            algorithm,
+            &bytes[1 + COMPRESSION_HEADER_LEN..],
+            decompressed_len,
+        )?;
+
+        Self::try_from_typed_bytes(typ_tag, &decompressed, num_mix_hops)
+    }
+
+    fn try_from_typed_bytes(
+        typ_tag: NymMessageType,
+        bytes: &[u8],
+        num_mix_hops: u8,
+    ) -> Result<Self, NymMessageError> {
         match typ_tag {
-            NymMessageType::Plain => Ok(NymMessage::Plain(bytes[1..].to_vec())),
+            NymMessageType::Plain => Ok(NymMessage::Plain(bytes.to_vec())),
             NymMessageType::Repliable => Ok(NymMessage::Repliable(
-                RepliableMessage::try_from_bytes(&bytes[1..], num_mix_hops)?,
+                RepliableMessage::try_from_bytes(bytes, num_mix_hops)?,
             )),
-            NymMessageType::Reply => Ok(NymMessage::Reply(ReplyMessage::try_from_bytes(
-                &bytes[1..],
-            )?)),
+            NymMessageType::Reply => Ok(NymMessage::Reply(ReplyMessage::try_from_bytes(bytes)?)),
+        }
+    }
+
+    fn into_possibly_compressed_bytes(self, compression: Option<PayloadCompression>) -> Vec<u8> {
+        match compression {
+            Some(algorithm) => self.into_compressed_bytes(algorithm),
+            None => self.into_bytes(),
         }
     }
 
@@ -207,6 +294,8 @@ impl NymMessage {
     }
 
     /// Determines the number of required packets of the provided size for the split message.
+    /// Note that it does not account for any potential compression of the message,
+    /// so it should be treated as an upper bound.
     pub fn required_packets(&self, packet_size: PacketSize, num_mix_hops: u8) -> usize {
         let plaintext_per_packet = self.true_available_plaintext_per_packet(packet_size);
         let serialized_len = self.serialized_size(num_mix_hops);
@@ -224,11 +313,16 @@ impl NymMessage {
     }
 
     /// Pads the message so that after it gets chunked, it will occupy exactly N sphinx packets.
+    /// If compression is specified, the message is compressed beforehand, if that's beneficial.
     /// Produces new_message = message || 1 || 0000....
-    pub fn pad_to_full_packet_lengths(self, plaintext_per_packet: usize) -> PaddedMessage {
+    pub fn pad_to_full_packet_lengths(
+        self,
+        plaintext_per_packet: usize,
+        compression: Option<PayloadCompression>,
+    ) -> PaddedMessage {
         let self_display = self.to_string();
 
-        let bytes = self.into_bytes();
+        let bytes = self.into_possibly_compressed_bytes(compression);
 
         // 1 (chunking::MIN_PADDING_OVERHEAD) is added as there will always have to be at least a single byte of padding (1) added
         // to be able to later distinguish the actual padding from the underlying message
@@ -256,6 +350,40 @@ impl NymMessage {
             .collect::<Vec<_>>()
             .into()
     }
+
+    /// Pads the message so that after it gets chunked into forward error corrected sets,
+    /// it will fully occupy all of its data sphinx packets.
+    /// Produces new_message = message || 1 || 0000....
+    pub fn pad_to_full_fec_packet_lengths(
+        self,
+        plaintext_per_packet: usize,
+        fec: ForwardErrorCorrection,
+        compression: Option<PayloadCompression>,
+    ) -> PaddedMessage {
+        let self_display = self.to_string();
+
+        let bytes = self.into_possibly_compressed_bytes(compression);
+        let total_required_bytes = bytes.len() + chunking::MIN_PADDING_OVERHEAD;
+
+        let (packets_used, space_left) = chunking::number_of_required_fec_fragments(
+            total_required_bytes,
+            plaintext_per_packet,
+            fec,
+        );
+
+        log::trace!(
+            "Padding {self_display} with forward error correction: {total_required_bytes} of raw plaintext bytes are required. \
+            They're going to be put into {packets_used} sphinx packets (including the parity ones) with {space_left} bytes \
+            of leftover space.",
+        );
+
+        bytes
+            .into_iter()
+            .chain(std::iter::once(1u8))
+            .chain(std::iter::repeat(0u8).take(space_left))
+            .collect::<Vec<_>>()
+            .into()
+    }
 }
 
 pub struct PaddedMessage(Vec<u8>);
@@ -278,6 +406,20 @@ impl PaddedMessage {
             .collect()
     }
 
+    /// Splits the padded message into forward error corrected [`Fragment`], including the parity ones,
+    /// that when serialized are going to become sphinx packet payloads.
+    pub fn split_into_fec_fragments<R: Rng>(
+        self,
+        rng: &mut R,
+        plaintext_per_packet: usize,
+        fec: ForwardErrorCorrection,
+    ) -> Vec<Fragment> {
+        chunking::split_into_fec_sets(rng, &self.0, plaintext_per_packet, fec)
+            .into_iter()
+            .flat_map(|fragment_set| fragment_set.into_iter())
+            .collect()
+    }
+
     // reverse of NymMessage::pad_to_full_packet_lengths
     pub fn remove_padding(self, num_mix_hops: u8) -> Result<NymMessage, NymMessageError> {
         // we are looking for first occurrence of 1 in the tail and we get its index
@@ -318,4 +460,43 @@ mod tests {
         let reply = NymMessage::new_reply(ReplyMessage::new_data_message(vec![1, 2, 3, 4, 5]));
         assert_eq!(reply.serialized_size(3), reply.into_bytes().len());
     }
+
+    #[test]
+    fn compressed_messages_can_be_recovered() {
+        let data = b"hello mixnet! ".repeat(100);
+        for algorithm in [PayloadCompression::Zstd, PayloadCompression::Lz4] {
+            let plain = NymMessage::new_plain(data.clone());
+            let uncompressed_len = plain.serialized_size(3);
+
+            let bytes = plain.into_compressed_bytes(algorithm);
+            assert!(bytes.len() < uncompressed_len);
+            assert_ne!(bytes[0] & COMPRESSED_MESSAGE_FLAG, 0);
+
+            let recovered = NymMessage::try_from_bytes(&bytes, 3).unwrap();
+            assert_eq!(recovered.into_inner_data(), data);
+        }
+    }
+
+    #[test]
+    fn incompressible_messages_are_sent_uncompressed() {
+        let data = vec![1, 2, 3, 4, 5];
+        let plain = NymMessage::new_plain(data.clone());
+        let bytes = plain.into_compressed_bytes(PayloadCompression::Zstd);
+        assert_eq!(bytes[0], NymMessageType::Plain as u8);
+
+        let recovered = NymMessage::try_from_bytes(&bytes, 3).unwrap();
+        assert_eq!(recovered.into_inner_data(), data);
+    }
+
+    #[test]
+    fn overly_compressible_messages_are_sent_uncompressed() {
+        // otherwise the recipients would reject the message due to its compression ratio
+        let data = vec![0u8; 100_000];
+        let plain = NymMessage::new_plain(data.clone());
+        let bytes = plain.into_compressed_bytes(PayloadCompression::Zstd);
+        assert_eq!(bytes[0], NymMessageType::Plain as u8);
+
+        let recovered = NymMessage::try_from_bytes(&bytes, 3).unwrap();
+        assert_eq!(recovered.into_inner_data(), data);
+    }
 }
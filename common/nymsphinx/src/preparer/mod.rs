@@ -11,9 +11,12 @@ use nym_sphinx_addressing::clients::Recipient;
 use nym_sphinx_addressing::nodes::NymNodeRoutingAddress;
 use nym_sphinx_anonymous_replies::reply_surb::ReplySurb;
 use nym_sphinx_chunking::fragment::{Fragment, FragmentIdentifier};
+use nym_sphinx_chunking::ForwardErrorCorrection;
 use nym_sphinx_forwarding::packet::MixPacket;
 use nym_sphinx_params::packet_sizes::PacketSize;
-use nym_sphinx_params::{PacketType, ReplySurbKeyDigestAlgorithm, DEFAULT_NUM_MIX_HOPS};
+use nym_sphinx_params::{
+    PacketType, PayloadCompression, ReplySurbKeyDigestAlgorithm, DEFAULT_NUM_MIX_HOPS,
+};
 use nym_sphinx_types::{Delay, NymPacket};
 use nym_topology::{NymTopology, NymTopologyError};
 use rand::{CryptoRng, Rng};
@@ -53,6 +56,11 @@ pub trait FragmentPreparer {
     fn average_packet_delay(&self) -> Duration;
     fn average_ack_delay(&self) -> Duration;
 
+    /// Compression algorithm applied to the messages before they get split into fragments.
+    fn payload_compression(&self) -> Option<PayloadCompression> {
+        None
+    }
+
     fn generate_reply_surbs(
         &mut self,
         amount: usize,
@@ -285,10 +293,29 @@ pub trait FragmentPreparer {
     ) -> Vec<Fragment> {
         let plaintext_per_packet = message.available_sphinx_plaintext_per_packet(packet_size);
 
+        let compression = self.payload_compression();
+
         message
-            .pad_to_full_packet_lengths(plaintext_per_packet)
+            .pad_to_full_packet_lengths(plaintext_per_packet, compression)
             .split_into_fragments(self.rng(), plaintext_per_packet)
     }
+
+    /// Pads and splits the message into fragments with additional parity fragments,
+    /// so that the recipient could reconstruct it even if some of them got lost.
+    fn pad_and_split_message_with_fec(
+        &mut self,
+        message: NymMessage,
+        packet_size: PacketSize,
+        fec: ForwardErrorCorrection,
+    ) -> Vec<Fragment> {
+        let plaintext_per_packet = message.available_sphinx_plaintext_per_packet(packet_size);
+
+        let compression = self.payload_compression();
+
+        message
+            .pad_to_full_fec_packet_lengths(plaintext_per_packet, fec, compression)
+            .split_into_fec_fragments(self.rng(), plaintext_per_packet, fec)
+    }
 }
 
 /// Prepares the message that is to be sent through the mix network by attaching
@@ -313,6 +340,9 @@ pub struct MessagePreparer<R> {
     /// Number of mix hops each packet ('real' message, ack, reply) is expected to take.
     /// Note that it does not include gateway hops.
     num_mix_hops: u8,
+
+    /// Optional compression algorithm applied to the messages before they get split into fragments.
+    payload_compression: Option<PayloadCompression>,
 }
 
 impl<R> MessagePreparer<R>
@@ -331,6 +361,7 @@ where
             average_packet_delay,
             average_ack_delay,
             num_mix_hops: DEFAULT_NUM_MIX_HOPS,
+            payload_compression: None,
         }
     }
 
@@ -340,6 +371,12 @@ where
         self
     }
 
+    /// Allows compressing the messages before they get split into fragments.
+    pub fn with_payload_compression(mut self, compression: Option<PayloadCompression>) -> Self {
+        self.payload_compression = compression;
+        self
+    }
+
     /// Overwrites existing sender address with the provided value.
     pub fn set_sender_address(&mut self, sender_address: Recipient) {
         self.sender_address = sender_address;
@@ -434,6 +471,15 @@ where
     ) -> Vec<Fragment> {
         <Self as FragmentPreparer>::pad_and_split_message(self, message, packet_size)
     }
+
+    pub fn pad_and_split_message_with_fec(
+        &mut self,
+        message: NymMessage,
+        packet_size: PacketSize,
+        fec: ForwardErrorCorrection,
+    ) -> Vec<Fragment> {
+        <Self as FragmentPreparer>::pad_and_split_message_with_fec(self, message, packet_size, fec)
+    }
 }
 
 impl<R: CryptoRng + Rng> FragmentPreparer for MessagePreparer<R> {
@@ -454,6 +500,10 @@ impl<R: CryptoRng + Rng> FragmentPreparer for MessagePreparer<R> {
     fn average_ack_delay(&self) -> Duration {
         self.average_ack_delay
     }
+
+    fn payload_compression(&self) -> Option<PayloadCompression> {
+        self.payload_compression
+    }
 }
 
 /*
@@ -247,6 +247,9 @@ mod message_receiver {
                 )
                 .unwrap(),
                 layer: Layer::One,
+                stake: None,
+                performance: None,
+                sphinx_key_rotation: None,
                 version: "0.8.0-dev".into(),
             }],
         );
@@ -267,6 +270,9 @@ mod message_receiver {
                 )
                 .unwrap(),
                 layer: Layer::Two,
+                stake: None,
+                performance: None,
+                sphinx_key_rotation: None,
                 version: "0.8.0-dev".into(),
             }],
         );
@@ -287,6 +293,9 @@ mod message_receiver {
                 )
                 .unwrap(),
                 layer: Layer::Three,
+                stake: None,
+                performance: None,
+                sphinx_key_rotation: None,
                 version: "0.8.0-dev".into(),
             }],
         );
@@ -308,6 +317,9 @@ mod message_receiver {
                     "EB42xvMFMD5rUCstE2CDazgQQJ22zLv8SPm1Luxni44c",
                 )
                 .unwrap(),
+                stake: None,
+                performance: None,
+                sphinx_key_rotation: None,
                 version: "0.8.0-dev".into(),
             }],
         )
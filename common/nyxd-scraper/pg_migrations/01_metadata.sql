@@ -0,0 +1,15 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+CREATE TABLE metadata
+(
+    id                    INTEGER PRIMARY KEY CHECK (id = 0),
+    last_processed_height BIGINT NOT NULL
+);
+
+CREATE TABLE pruning
+(
+    last_pruned_height BIGINT NOT NULL
+);
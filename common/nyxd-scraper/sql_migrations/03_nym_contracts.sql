@@ -0,0 +1,58 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- note: those tables are intentionally not linked with the `transaction` table
+-- as they're meant for long-term indexing and are not subject to pruning
+
+CREATE TABLE nym_contract_execution
+(
+    transaction_hash TEXT   NOT NULL,
+    "index"          BIGINT NOT NULL,
+    height           BIGINT NOT NULL,
+    contract         TEXT   NOT NULL,
+    contract_address TEXT   NOT NULL,
+    sender           TEXT   NOT NULL,
+    message_type     TEXT   NOT NULL,
+    message          TEXT   NOT NULL,
+    funds            TEXT   NOT NULL,
+    CONSTRAINT unique_execution_per_tx UNIQUE (transaction_hash, "index")
+);
+CREATE INDEX nym_contract_execution_height_index ON nym_contract_execution (height);
+CREATE INDEX nym_contract_execution_sender_index ON nym_contract_execution (sender);
+CREATE INDEX nym_contract_execution_message_type_index ON nym_contract_execution (contract, message_type);
+
+CREATE TABLE delegation_event
+(
+    transaction_hash TEXT    NOT NULL,
+    "index"          BIGINT  NOT NULL,
+    height           BIGINT  NOT NULL,
+    delegator        TEXT    NOT NULL,
+    mix_id           BIGINT  NOT NULL,
+    action           TEXT    NOT NULL,
+    amount           TEXT,
+    denom            TEXT,
+    vesting          BOOLEAN NOT NULL,
+    CONSTRAINT unique_delegation_event UNIQUE (transaction_hash, "index", mix_id, action)
+);
+CREATE INDEX delegation_event_delegator_index ON delegation_event (delegator);
+CREATE INDEX delegation_event_mix_id_index ON delegation_event (mix_id);
+CREATE INDEX delegation_event_height_index ON delegation_event (height);
+
+CREATE TABLE bonding_event
+(
+    transaction_hash TEXT    NOT NULL,
+    "index"          BIGINT  NOT NULL,
+    height           BIGINT  NOT NULL,
+    owner            TEXT    NOT NULL,
+    node_type        TEXT    NOT NULL,
+    identity_key     TEXT,
+    action           TEXT    NOT NULL,
+    amount           TEXT,
+    denom            TEXT,
+    vesting          BOOLEAN NOT NULL,
+    CONSTRAINT unique_bonding_event UNIQUE (transaction_hash, "index")
+);
+CREATE INDEX bonding_event_owner_index ON bonding_event (owner);
+CREATE INDEX bonding_event_height_index ON bonding_event (height);
@@ -7,7 +7,7 @@ use crate::block_requester::BlockRequest;
 use crate::error::ScraperError;
 use crate::modules::{BlockModule, MsgModule, TxModule};
 use crate::rpc_client::RpcClient;
-use crate::storage::{persist_block, ScraperStorage};
+use crate::storage::{persist_block, NyxdScraperStorage, NyxdScraperTransaction};
 use crate::PruningOptions;
 use futures::StreamExt;
 use std::collections::{BTreeMap, HashSet, VecDeque};
@@ -41,7 +41,7 @@ impl PendingSync {
     }
 }
 
-pub struct BlockProcessor {
+pub struct BlockProcessor<S: NyxdScraperStorage> {
     pruning_options: PruningOptions,
     cancel: CancellationToken,
     synced: Arc<Notify>,
@@ -54,24 +54,27 @@ pub struct BlockProcessor {
     rpc_client: RpcClient,
     incoming: UnboundedReceiverStream<BlockToProcess>,
     block_requester: Sender<BlockRequest>,
-    storage: ScraperStorage,
+    storage: S,
 
     // future work: rather than sending each msg to every msg module,
     // let them subscribe based on `type_url` inside the message itself
     // (like "/cosmwasm.wasm.v1.MsgExecuteContract")
-    block_modules: Vec<Box<dyn BlockModule + Send>>,
-    tx_modules: Vec<Box<dyn TxModule + Send>>,
-    msg_modules: Vec<Box<dyn MsgModule + Send>>,
+    block_modules: Vec<Box<dyn BlockModule<S::StorageTransaction> + Send>>,
+    tx_modules: Vec<Box<dyn TxModule<S::StorageTransaction> + Send>>,
+    msg_modules: Vec<Box<dyn MsgModule<S::StorageTransaction> + Send>>,
 }
 
-impl BlockProcessor {
+impl<S> BlockProcessor<S>
+where
+    S: NyxdScraperStorage,
+{
     pub async fn new(
         pruning_options: PruningOptions,
         cancel: CancellationToken,
         synced: Arc<Notify>,
         incoming: UnboundedReceiver<BlockToProcess>,
         block_requester: Sender<BlockRequest>,
-        storage: ScraperStorage,
+        storage: S,
         rpc_client: RpcClient,
     ) -> Result<Self, ScraperError> {
         let last_processed = storage.get_last_processed_height().await?;
@@ -143,9 +146,7 @@ impl BlockProcessor {
         }
 
         let commit_start = Instant::now();
-        tx.commit()
-            .await
-            .map_err(|source| ScraperError::StorageTxCommitFailure { source })?;
+        tx.commit().await?;
         crate::storage::log_db_operation_time("committing processing tx", commit_start);
 
         self.last_processed_height = full_info.block.header.height.value() as u32;
@@ -157,15 +158,24 @@ impl BlockProcessor {
         Ok(())
     }
 
-    pub fn set_block_modules(&mut self, modules: Vec<Box<dyn BlockModule + Send>>) {
+    pub fn set_block_modules(
+        &mut self,
+        modules: Vec<Box<dyn BlockModule<S::StorageTransaction> + Send>>,
+    ) {
         self.block_modules = modules;
     }
 
-    pub fn set_tx_modules(&mut self, modules: Vec<Box<dyn TxModule + Send>>) {
+    pub fn set_tx_modules(
+        &mut self,
+        modules: Vec<Box<dyn TxModule<S::StorageTransaction> + Send>>,
+    ) {
         self.tx_modules = modules;
     }
 
-    pub fn set_msg_modules(&mut self, modules: Vec<Box<dyn MsgModule + Send>>) {
+    pub fn set_msg_modules(
+        &mut self,
+        modules: Vec<Box<dyn MsgModule<S::StorageTransaction> + Send>>,
+    ) {
         self.msg_modules = modules;
     }
 
@@ -80,6 +80,14 @@ pub enum ScraperError {
         source: cosmrs::ErrorReport,
     },
 
+    #[error("could not decode contract execution message {index} in tx {hash}: {source}")]
+    MalformedExecuteContractMessage {
+        hash: Hash,
+        index: usize,
+        #[source]
+        source: cosmrs::ErrorReport,
+    },
+
     #[error("received an invalid chain subscription event of kind {kind} while we were waiting for new block data (query: '{query}')")]
     InvalidSubscriptionEvent { query: String, kind: String },
 
@@ -18,3 +18,7 @@ pub use block_processor::pruning::{PruningOptions, PruningStrategy};
 pub use modules::{BlockModule, MsgModule, TxModule};
 pub use scraper::{Config, NyxdScraper};
 pub use storage::models;
+#[cfg(feature = "postgres")]
+pub use storage::postgres::PostgresScraperStorage;
+pub use storage::sqlite::SqliteScraperStorage;
+pub use storage::{NyxdScraperStorage, NyxdScraperTransaction};
@@ -3,14 +3,14 @@
 
 use crate::block_processor::types::FullBlockInformation;
 use crate::error::ScraperError;
-use crate::storage::StorageTransaction;
+use crate::storage::NyxdScraperTransaction;
 use async_trait::async_trait;
 
 #[async_trait]
-pub trait BlockModule {
+pub trait BlockModule<T: NyxdScraperTransaction> {
     async fn handle_block(
         &mut self,
         block: &FullBlockInformation,
-        storage_tx: &mut StorageTransaction,
+        storage_tx: &mut T,
     ) -> Result<(), ScraperError>;
 }
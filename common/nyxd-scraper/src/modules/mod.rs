@@ -3,6 +3,7 @@
 
 mod block_module;
 mod msg_module;
+pub mod nym_contracts;
 mod tx_module;
 
 pub use block_module::BlockModule;
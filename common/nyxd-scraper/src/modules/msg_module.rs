@@ -3,17 +3,17 @@
 
 use crate::block_processor::types::ParsedTransactionResponse;
 use crate::error::ScraperError;
-use crate::storage::StorageTransaction;
+use crate::storage::NyxdScraperTransaction;
 use async_trait::async_trait;
 use cosmrs::Any;
 
 #[async_trait]
-pub trait MsgModule {
+pub trait MsgModule<T: NyxdScraperTransaction> {
     async fn handle_msg(
         &mut self,
         index: usize,
         msg: &Any,
         tx: &ParsedTransactionResponse,
-        storage_tx: &mut StorageTransaction,
+        storage_tx: &mut T,
     ) -> Result<(), ScraperError>;
 }
@@ -0,0 +1,92 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_processor::types::ParsedTransactionResponse;
+use crate::error::ScraperError;
+use crate::modules::nym_contracts::decode_execution;
+use crate::modules::MsgModule;
+use crate::storage::models::NymContract;
+use crate::storage::NyxdScraperTransaction;
+use async_trait::async_trait;
+use cosmrs::{AccountId, Any};
+use nym_ecash_contract_common::msg::ExecuteMsg;
+
+/// Records all executions of the ecash contract, such as ticketbook deposits or redemption requests.
+pub struct EcashContractModule {
+    contract_address: AccountId,
+}
+
+impl EcashContractModule {
+    pub fn new(contract_address: AccountId) -> Self {
+        EcashContractModule { contract_address }
+    }
+}
+
+#[async_trait]
+impl<T> MsgModule<T> for EcashContractModule
+where
+    T: NyxdScraperTransaction,
+{
+    async fn handle_msg(
+        &mut self,
+        index: usize,
+        msg: &Any,
+        tx: &ParsedTransactionResponse,
+        storage_tx: &mut T,
+    ) -> Result<(), ScraperError> {
+        let Some(decoded) = decode_execution::<ExecuteMsg>(
+            NymContract::Ecash,
+            &self.contract_address,
+            index,
+            msg,
+            tx,
+        )?
+        else {
+            return Ok(());
+        };
+
+        storage_tx
+            .insert_contract_execution(&decoded.execution)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::nym_contracts::tests::{
+        coin, execute_msg, transaction, RecordingTransaction, CONTRACT, SENDER,
+    };
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn executions_are_recorded() {
+        let mut module = EcashContractModule::new(CONTRACT.parse().unwrap());
+        let deposit = execute_msg(
+            json!({"deposit_ticket_book_funds": {"identity_key": "foomp"}}),
+            vec![coin(75000000)],
+        );
+        let failed = execute_msg(json!({"refund_deposit": {"deposit_id": 42}}), vec![]);
+
+        let mut storage_tx = RecordingTransaction::default();
+        let tx = transaction(vec![deposit.clone()], true);
+        module
+            .handle_msg(0, &deposit, &tx, &mut storage_tx)
+            .await
+            .unwrap();
+        let tx = transaction(vec![failed.clone()], false);
+        module
+            .handle_msg(0, &failed, &tx, &mut storage_tx)
+            .await
+            .unwrap();
+
+        assert_eq!(storage_tx.executions.len(), 1);
+        let execution = &storage_tx.executions[0];
+        assert_eq!(execution.contract, NymContract::Ecash);
+        assert_eq!(execution.sender, SENDER);
+        assert_eq!(execution.message_type, "deposit_ticket_book_funds");
+        assert_eq!(execution.funds, "75000000unym");
+        assert!(storage_tx.delegations.is_empty());
+        assert!(storage_tx.bondings.is_empty());
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_processor::types::ParsedTransactionResponse;
+use crate::error::ScraperError;
+use crate::modules::nym_contracts::{decode_execution, DecodedExecution};
+use crate::modules::MsgModule;
+use crate::storage::models::{
+    BondingAction, BondingEvent, DelegationAction, DelegationEvent, NodeType, NymContract,
+};
+use crate::storage::NyxdScraperTransaction;
+use async_trait::async_trait;
+use cosmrs::{AccountId, Any};
+use nym_mixnet_contract_common::{ExecuteMsg, MixId};
+
+/// Records all executions of the mixnet contract alongside the bonding and delegation history.
+pub struct MixnetContractModule {
+    contract_address: AccountId,
+}
+
+impl MixnetContractModule {
+    pub fn new(contract_address: AccountId) -> Self {
+        MixnetContractModule { contract_address }
+    }
+}
+
+fn delegation_event(
+    decoded: &DecodedExecution<ExecuteMsg>,
+    mix_id: MixId,
+    action: DelegationAction,
+    (amount, denom): (Option<String>, Option<String>),
+) -> DelegationEvent {
+    DelegationEvent {
+        transaction_hash: decoded.execution.transaction_hash.clone(),
+        index: decoded.execution.index,
+        height: decoded.execution.height,
+        delegator: decoded.sender().to_string(),
+        mix_id: mix_id.into(),
+        action,
+        amount,
+        denom,
+        vesting: false,
+    }
+}
+
+fn bonding_event(
+    decoded: &DecodedExecution<ExecuteMsg>,
+    node_type: NodeType,
+    identity_key: Option<String>,
+    action: BondingAction,
+    (amount, denom): (Option<String>, Option<String>),
+) -> BondingEvent {
+    BondingEvent {
+        transaction_hash: decoded.execution.transaction_hash.clone(),
+        index: decoded.execution.index,
+        height: decoded.execution.height,
+        owner: decoded.sender().to_string(),
+        node_type,
+        identity_key,
+        action,
+        amount,
+        denom,
+        vesting: false,
+    }
+}
+
+#[async_trait]
+impl<T> MsgModule<T> for MixnetContractModule
+where
+    T: NyxdScraperTransaction,
+{
+    async fn handle_msg(
+        &mut self,
+        index: usize,
+        msg: &Any,
+        tx: &ParsedTransactionResponse,
+        storage_tx: &mut T,
+    ) -> Result<(), ScraperError> {
+        let Some(decoded) = decode_execution::<ExecuteMsg>(
+            NymContract::Mixnet,
+            &self.contract_address,
+            index,
+            msg,
+            tx,
+        )?
+        else {
+            return Ok(());
+        };
+
+        storage_tx
+            .insert_contract_execution(&decoded.execution)
+            .await?;
+
+        let Some(msg) = &decoded.msg else {
+            return Ok(());
+        };
+
+        // note: the `OnBehalf` variants are only ever executed by the vesting contract,
+        // so they'd never appear as top-level messages
+        let mut delegation_events = Vec::new();
+        let mut bonding_events = Vec::new();
+        match msg {
+            ExecuteMsg::DelegateToMixnode { mix_id } => delegation_events.push(delegation_event(
+                &decoded,
+                *mix_id,
+                DelegationAction::Delegate,
+                decoded.sent_amount(),
+            )),
+            ExecuteMsg::UndelegateFromMixnode { mix_id } => {
+                delegation_events.push(delegation_event(
+                    &decoded,
+                    *mix_id,
+                    DelegationAction::Undelegate,
+                    (None, None),
+                ))
+            }
+            ExecuteMsg::RedelegateFromMixnode { from, to, amount } => {
+                let amount = (Some(amount.amount.to_string()), Some(amount.denom.clone()));
+                delegation_events.push(delegation_event(
+                    &decoded,
+                    *from,
+                    DelegationAction::RedelegateFrom,
+                    amount.clone(),
+                ));
+                delegation_events.push(delegation_event(
+                    &decoded,
+                    *to,
+                    DelegationAction::RedelegateTo,
+                    amount,
+                ));
+            }
+            ExecuteMsg::BondMixnode { mix_node, .. } => bonding_events.push(bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                Some(mix_node.identity_key.clone()),
+                BondingAction::Bond,
+                decoded.sent_amount(),
+            )),
+            ExecuteMsg::PledgeMore {} => bonding_events.push(bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                None,
+                BondingAction::PledgeMore,
+                decoded.sent_amount(),
+            )),
+            ExecuteMsg::DecreasePledge { decrease_by } => bonding_events.push(bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                None,
+                BondingAction::DecreasePledge,
+                (
+                    Some(decrease_by.amount.to_string()),
+                    Some(decrease_by.denom.clone()),
+                ),
+            )),
+            ExecuteMsg::UnbondMixnode {} => bonding_events.push(bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                None,
+                BondingAction::Unbond,
+                (None, None),
+            )),
+            ExecuteMsg::BondGateway { gateway, .. } => bonding_events.push(bonding_event(
+                &decoded,
+                NodeType::Gateway,
+                Some(gateway.identity_key.clone()),
+                BondingAction::Bond,
+                decoded.sent_amount(),
+            )),
+            ExecuteMsg::UnbondGateway {} => bonding_events.push(bonding_event(
+                &decoded,
+                NodeType::Gateway,
+                None,
+                BondingAction::Unbond,
+                (None, None),
+            )),
+            _ => {}
+        }
+
+        for event in delegation_events {
+            storage_tx.insert_delegation_event(&event).await?;
+        }
+        for event in bonding_events {
+            storage_tx.insert_bonding_event(&event).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::nym_contracts::tests::{
+        coin, execute_msg, transaction, RecordingTransaction, CONTRACT, OTHER, SENDER,
+    };
+    use serde_json::json;
+
+    async fn handle(msg: serde_json::Value, funds: Vec<cosmrs::Coin>) -> RecordingTransaction {
+        let mut module = MixnetContractModule::new(CONTRACT.parse().unwrap());
+        let msg = execute_msg(msg, funds);
+        let tx = transaction(vec![msg.clone()], true);
+
+        let mut storage_tx = RecordingTransaction::default();
+        module
+            .handle_msg(0, &msg, &tx, &mut storage_tx)
+            .await
+            .unwrap();
+        storage_tx
+    }
+
+    #[tokio::test]
+    async fn delegation_is_recorded() {
+        let recorded = handle(
+            json!({"delegate_to_mixnode": {"mix_id": 42}}),
+            vec![coin(1000)],
+        )
+        .await;
+
+        assert_eq!(recorded.executions.len(), 1);
+        assert_eq!(recorded.executions[0].contract, NymContract::Mixnet);
+        assert_eq!(recorded.executions[0].message_type, "delegate_to_mixnode");
+
+        assert_eq!(recorded.delegations.len(), 1);
+        let event = &recorded.delegations[0];
+        assert_eq!(event.delegator, SENDER);
+        assert_eq!(event.mix_id, 42);
+        assert_eq!(event.action, DelegationAction::Delegate);
+        assert_eq!(event.amount.as_deref(), Some("1000"));
+        assert_eq!(event.denom.as_deref(), Some("unym"));
+        assert!(!event.vesting);
+        assert!(recorded.bondings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn redelegation_is_recorded_for_both_nodes() {
+        let recorded = handle(
+            json!({"redelegate_from_mixnode": {"from": 1, "to": 2, "amount": {"denom": "unym", "amount": "500"}}}),
+            vec![],
+        )
+        .await;
+
+        assert_eq!(recorded.delegations.len(), 2);
+        let (from, to) = (&recorded.delegations[0], &recorded.delegations[1]);
+        assert_eq!(from.mix_id, 1);
+        assert_eq!(from.action, DelegationAction::RedelegateFrom);
+        assert_eq!(to.mix_id, 2);
+        assert_eq!(to.action, DelegationAction::RedelegateTo);
+        for event in [from, to] {
+            assert_eq!(event.amount.as_deref(), Some("500"));
+            assert_eq!(event.denom.as_deref(), Some("unym"));
+        }
+    }
+
+    #[tokio::test]
+    async fn bonding_changes_are_recorded() {
+        let recorded = handle(
+            json!({"decrease_pledge": {"decrease_by": {"denom": "unym", "amount": "100"}}}),
+            vec![],
+        )
+        .await;
+        assert_eq!(recorded.bondings.len(), 1);
+        let event = &recorded.bondings[0];
+        assert_eq!(event.owner, SENDER);
+        assert_eq!(event.node_type, NodeType::Mixnode);
+        assert_eq!(event.action, BondingAction::DecreasePledge);
+        assert_eq!(event.amount.as_deref(), Some("100"));
+        assert!(event.identity_key.is_none());
+
+        let recorded = handle(json!({"unbond_gateway": {}}), vec![]).await;
+        assert_eq!(recorded.bondings.len(), 1);
+        let event = &recorded.bondings[0];
+        assert_eq!(event.node_type, NodeType::Gateway);
+        assert_eq!(event.action, BondingAction::Unbond);
+        assert!(event.amount.is_none());
+    }
+
+    #[tokio::test]
+    async fn other_messages_only_record_the_execution() {
+        let recorded = handle(json!({"withdraw_operator_reward": {}}), vec![]).await;
+        assert_eq!(recorded.executions.len(), 1);
+        assert!(recorded.delegations.is_empty());
+        assert!(recorded.bondings.is_empty());
+
+        // this one can't be decoded, but it's still worth keeping around
+        let recorded = handle(json!({"delegate_to_mixnode": {"mix_id": "foo"}}), vec![]).await;
+        assert_eq!(recorded.executions.len(), 1);
+        assert!(recorded.delegations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn other_contracts_are_ignored() {
+        let mut module = MixnetContractModule::new(OTHER.parse().unwrap());
+        let msg = execute_msg(
+            json!({"delegate_to_mixnode": {"mix_id": 42}}),
+            vec![coin(1000)],
+        );
+        let tx = transaction(vec![msg.clone()], true);
+
+        let mut storage_tx = RecordingTransaction::default();
+        module
+            .handle_msg(0, &msg, &tx, &mut storage_tx)
+            .await
+            .unwrap();
+        assert!(storage_tx.executions.is_empty());
+        assert!(storage_tx.delegations.is_empty());
+    }
+}
@@ -0,0 +1,384 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ready-made [`MsgModule`](crate::MsgModule)s decoding executions of the Nym contracts
+//! into typed tables, i.e. `nym_contract_execution`, `delegation_event` and `bonding_event`.
+//! Only messages included in successful transactions are processed.
+
+use crate::block_processor::types::ParsedTransactionResponse;
+use crate::error::ScraperError;
+use crate::storage::models::{ContractExecution, NymContract};
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::tx::Msg;
+use cosmrs::{AccountId, Any, Coin};
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+pub use ecash::EcashContractModule;
+pub use mixnet::MixnetContractModule;
+pub use vesting::VestingContractModule;
+
+mod ecash;
+mod mixnet;
+mod vesting;
+
+const EXECUTE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+
+pub(crate) struct DecodedExecution<M> {
+    pub(crate) execution: ContractExecution,
+
+    /// The typed message, if it could have been decoded
+    pub(crate) msg: Option<M>,
+    pub(crate) funds: Vec<Coin>,
+}
+
+impl<M> DecodedExecution<M> {
+    pub(crate) fn sender(&self) -> &str {
+        &self.execution.sender
+    }
+
+    /// Amount and denom of the first coin sent alongside the message
+    pub(crate) fn sent_amount(&self) -> (Option<String>, Option<String>) {
+        match self.funds.first() {
+            Some(coin) => (Some(coin.amount.to_string()), Some(coin.denom.to_string())),
+            None => (None, None),
+        }
+    }
+}
+
+fn format_funds(funds: &[Coin]) -> String {
+    funds
+        .iter()
+        .map(|coin| format!("{}{}", coin.amount, coin.denom))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub(crate) fn decode_execution<M: DeserializeOwned>(
+    contract: NymContract,
+    contract_address: &AccountId,
+    index: usize,
+    msg: &Any,
+    tx: &ParsedTransactionResponse,
+) -> Result<Option<DecodedExecution<M>>, ScraperError> {
+    if !tx.tx_result.code.is_ok() || msg.type_url != EXECUTE_CONTRACT_TYPE_URL {
+        return Ok(None);
+    }
+
+    let execute = MsgExecuteContract::from_any(msg).map_err(|source| {
+        ScraperError::MalformedExecuteContractMessage {
+            hash: tx.hash,
+            index,
+            source,
+        }
+    })?;
+
+    if &execute.contract != contract_address {
+        return Ok(None);
+    }
+
+    // the contract would have rejected anything that's not valid json, but let's not stall
+    // the whole processing if that assumption doesn't hold
+    let raw: serde_json::Value = match serde_json::from_slice(&execute.msg) {
+        Ok(raw) => raw,
+        Err(err) => {
+            warn!(
+                "{} contract execution in tx {} (index {index}) is not valid json: {err}",
+                contract.as_str(),
+                tx.hash
+            );
+            return Ok(None);
+        }
+    };
+
+    // all our execute messages are externally tagged enums
+    let message_type = raw
+        .as_object()
+        .and_then(|obj| obj.keys().next().cloned())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let typed = match serde_json::from_value(raw.clone()) {
+        Ok(typed) => Some(typed),
+        Err(err) => {
+            warn!(
+                "failed to decode '{message_type}' {} contract execution in tx {}: {err}",
+                contract.as_str(),
+                tx.hash
+            );
+            None
+        }
+    };
+
+    Ok(Some(DecodedExecution {
+        execution: ContractExecution {
+            transaction_hash: tx.hash.to_string(),
+            index: index as i64,
+            height: tx.height.into(),
+            contract,
+            contract_address: execute.contract.to_string(),
+            sender: execute.sender.to_string(),
+            message_type,
+            message: raw.to_string(),
+            funds: format_funds(&execute.funds),
+        },
+        msg: typed,
+        funds: execute.funds,
+    }))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::storage::models::{BondingEvent, DelegationEvent};
+    use crate::storage::NyxdScraperTransaction;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use sqlx::types::time::OffsetDateTime;
+    use tendermint::{abci, Hash};
+
+    pub(crate) const CONTRACT: &str = "n17n9flp6jflljg6fp05dsy07wcprf2uuu8g40rf";
+    pub(crate) const SENDER: &str = "n1jw6mp7d5xqc7w6xm79lha27glmd0vdt3l9artf";
+    pub(crate) const OTHER: &str = "n1h5hgn94nsq4kh99rjj794hr5h5q6yfm2lr52es";
+
+    /// Storage transaction that only keeps track of the data inserted by the contract modules.
+    #[derive(Default)]
+    pub(crate) struct RecordingTransaction {
+        pub(crate) executions: Vec<ContractExecution>,
+        pub(crate) delegations: Vec<DelegationEvent>,
+        pub(crate) bondings: Vec<BondingEvent>,
+    }
+
+    #[async_trait]
+    impl NyxdScraperTransaction for RecordingTransaction {
+        async fn commit(self) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn insert_validator(&mut self, _: String, _: String) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn insert_block(
+            &mut self,
+            _: i64,
+            _: String,
+            _: u32,
+            _: i64,
+            _: String,
+            _: OffsetDateTime,
+        ) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn insert_precommit(
+            &mut self,
+            _: String,
+            _: i64,
+            _: OffsetDateTime,
+            _: i64,
+            _: i64,
+        ) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn insert_transaction(
+            &mut self,
+            _: String,
+            _: i64,
+            _: i64,
+            _: bool,
+            _: i64,
+            _: String,
+            _: i64,
+            _: i64,
+            _: String,
+        ) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn insert_message(
+            &mut self,
+            _: String,
+            _: i64,
+            _: String,
+            _: i64,
+        ) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn update_last_processed(&mut self, _: i64) -> Result<(), ScraperError> {
+            Ok(())
+        }
+
+        async fn insert_contract_execution(
+            &mut self,
+            execution: &ContractExecution,
+        ) -> Result<(), ScraperError> {
+            self.executions.push(execution.clone());
+            Ok(())
+        }
+
+        async fn insert_delegation_event(
+            &mut self,
+            event: &DelegationEvent,
+        ) -> Result<(), ScraperError> {
+            self.delegations.push(event.clone());
+            Ok(())
+        }
+
+        async fn insert_bonding_event(&mut self, event: &BondingEvent) -> Result<(), ScraperError> {
+            self.bondings.push(event.clone());
+            Ok(())
+        }
+    }
+
+    pub(crate) fn address(raw: &str) -> AccountId {
+        raw.parse().unwrap()
+    }
+
+    pub(crate) fn coin(amount: u128) -> Coin {
+        Coin::new(amount, "unym").unwrap()
+    }
+
+    pub(crate) fn raw_execute_msg(contract: &str, msg: Vec<u8>, funds: Vec<Coin>) -> Any {
+        MsgExecuteContract {
+            sender: address(SENDER),
+            contract: address(contract),
+            msg,
+            funds,
+        }
+        .to_any()
+        .unwrap()
+    }
+
+    pub(crate) fn execute_msg(msg: serde_json::Value, funds: Vec<Coin>) -> Any {
+        raw_execute_msg(CONTRACT, serde_json::to_vec(&msg).unwrap(), funds)
+    }
+
+    pub(crate) fn transaction(messages: Vec<Any>, success: bool) -> ParsedTransactionResponse {
+        ParsedTransactionResponse {
+            hash: Hash::Sha256([42; 32]),
+            height: 1000u32.into(),
+            index: 0,
+            tx_result: abci::types::ExecTxResult {
+                code: if success {
+                    abci::Code::Ok
+                } else {
+                    abci::Code::from(5)
+                },
+                data: Default::default(),
+                log: Default::default(),
+                info: Default::default(),
+                gas_wanted: 0,
+                gas_used: 0,
+                events: vec![],
+                codespace: Default::default(),
+            },
+            tx: cosmrs::tx::Tx {
+                body: cosmrs::tx::Body::new(messages, "", 0u32),
+                auth_info: cosmrs::tx::AuthInfo {
+                    signer_infos: vec![],
+                    fee: cosmrs::tx::Fee::from_amount_and_gas(coin(5000), 200000u64),
+                },
+                signatures: vec![],
+            },
+            proof: None,
+        }
+    }
+
+    fn decode(msg: &Any, tx: &ParsedTransactionResponse) -> Option<DecodedExecution<ExampleMsg>> {
+        decode_execution(NymContract::Mixnet, &address(CONTRACT), 0, msg, tx).unwrap()
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    #[serde(rename_all = "snake_case", deny_unknown_fields)]
+    enum ExampleMsg {
+        DoThing { value: u32 },
+    }
+
+    #[test]
+    fn decoding_execution() {
+        let msg = execute_msg(
+            json!({"do_thing": {"value": 42}}),
+            vec![coin(100), coin(200)],
+        );
+        let tx = transaction(vec![msg.clone()], true);
+
+        let decoded = decode(&msg, &tx).unwrap();
+        assert_eq!(decoded.msg, Some(ExampleMsg::DoThing { value: 42 }));
+        assert_eq!(decoded.sender(), SENDER);
+        assert_eq!(
+            decoded.sent_amount(),
+            (Some("100".to_string()), Some("unym".to_string()))
+        );
+
+        let execution = decoded.execution;
+        assert_eq!(execution.transaction_hash, tx.hash.to_string());
+        assert_eq!(execution.height, 1000);
+        assert_eq!(execution.contract, NymContract::Mixnet);
+        assert_eq!(execution.contract_address, CONTRACT);
+        assert_eq!(execution.message_type, "do_thing");
+        assert_eq!(execution.funds, "100unym,200unym");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&execution.message).unwrap(),
+            json!({"do_thing": {"value": 42}})
+        );
+    }
+
+    #[test]
+    fn failed_transactions_are_ignored() {
+        let msg = execute_msg(json!({"do_thing": {"value": 42}}), vec![]);
+        let tx = transaction(vec![msg.clone()], false);
+        assert!(decode(&msg, &tx).is_none());
+    }
+
+    #[test]
+    fn other_contracts_and_messages_are_ignored() {
+        let other_contract =
+            raw_execute_msg(OTHER, br#"{"do_thing":{"value":42}}"#.to_vec(), vec![]);
+        let tx = transaction(vec![other_contract.clone()], true);
+        assert!(decode(&other_contract, &tx).is_none());
+
+        let send = cosmrs::bank::MsgSend {
+            from_address: address(SENDER),
+            to_address: address(CONTRACT),
+            amount: vec![coin(100)],
+        }
+        .to_any()
+        .unwrap();
+        let tx = transaction(vec![send.clone()], true);
+        assert!(decode(&send, &tx).is_none());
+    }
+
+    #[test]
+    fn unknown_messages_are_still_recorded() {
+        let msg = execute_msg(json!({"do_other_thing": {}}), vec![]);
+        let tx = transaction(vec![msg.clone()], true);
+
+        let decoded = decode(&msg, &tx).unwrap();
+        assert!(decoded.msg.is_none());
+        assert_eq!(decoded.execution.message_type, "do_other_thing");
+        assert!(decoded.execution.funds.is_empty());
+        assert_eq!(decoded.sent_amount(), (None, None));
+    }
+
+    #[test]
+    fn invalid_json_is_skipped() {
+        let msg = raw_execute_msg(CONTRACT, b"not json".to_vec(), vec![]);
+        let tx = transaction(vec![msg.clone()], true);
+        assert!(decode(&msg, &tx).is_none());
+    }
+
+    #[test]
+    fn malformed_execute_message_is_an_error() {
+        let msg = Any {
+            type_url: EXECUTE_CONTRACT_TYPE_URL.to_string(),
+            value: vec![0xff, 0xff, 0xff],
+        };
+        let tx = transaction(vec![msg.clone()], true);
+        assert!(matches!(
+            decode_execution::<ExampleMsg>(NymContract::Mixnet, &address(CONTRACT), 3, &msg, &tx),
+            Err(ScraperError::MalformedExecuteContractMessage { index: 3, .. })
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_processor::types::ParsedTransactionResponse;
+use crate::error::ScraperError;
+use crate::modules::nym_contracts::{decode_execution, DecodedExecution};
+use crate::modules::MsgModule;
+use crate::storage::models::{
+    BondingAction, BondingEvent, DelegationAction, DelegationEvent, NodeType, NymContract,
+};
+use crate::storage::NyxdScraperTransaction;
+use async_trait::async_trait;
+use cosmrs::{AccountId, Any};
+use nym_vesting_contract_common::ExecuteMsg;
+
+/// Records all executions of the vesting contract alongside the bonding and delegation history
+/// of the vesting accounts.
+pub struct VestingContractModule {
+    contract_address: AccountId,
+}
+
+impl VestingContractModule {
+    pub fn new(contract_address: AccountId) -> Self {
+        VestingContractModule { contract_address }
+    }
+}
+
+fn amount_parts(amount: &impl ToString, denom: &str) -> (Option<String>, Option<String>) {
+    (Some(amount.to_string()), Some(denom.to_string()))
+}
+
+fn bonding_event(
+    decoded: &DecodedExecution<ExecuteMsg>,
+    node_type: NodeType,
+    identity_key: Option<String>,
+    action: BondingAction,
+    (amount, denom): (Option<String>, Option<String>),
+) -> BondingEvent {
+    BondingEvent {
+        transaction_hash: decoded.execution.transaction_hash.clone(),
+        index: decoded.execution.index,
+        height: decoded.execution.height,
+        owner: decoded.sender().to_string(),
+        node_type,
+        identity_key,
+        action,
+        amount,
+        denom,
+        vesting: true,
+    }
+}
+
+#[async_trait]
+impl<T> MsgModule<T> for VestingContractModule
+where
+    T: NyxdScraperTransaction,
+{
+    async fn handle_msg(
+        &mut self,
+        index: usize,
+        msg: &Any,
+        tx: &ParsedTransactionResponse,
+        storage_tx: &mut T,
+    ) -> Result<(), ScraperError> {
+        let Some(decoded) = decode_execution::<ExecuteMsg>(
+            NymContract::Vesting,
+            &self.contract_address,
+            index,
+            msg,
+            tx,
+        )?
+        else {
+            return Ok(());
+        };
+
+        storage_tx
+            .insert_contract_execution(&decoded.execution)
+            .await?;
+
+        let Some(msg) = &decoded.msg else {
+            return Ok(());
+        };
+
+        let bonding = match msg {
+            ExecuteMsg::DelegateToMixnode {
+                mix_id,
+                amount,
+                on_behalf_of,
+            } => {
+                let event = DelegationEvent {
+                    transaction_hash: decoded.execution.transaction_hash.clone(),
+                    index: decoded.execution.index,
+                    height: decoded.execution.height,
+                    delegator: on_behalf_of
+                        .clone()
+                        .unwrap_or_else(|| decoded.sender().to_string()),
+                    mix_id: (*mix_id).into(),
+                    action: DelegationAction::Delegate,
+                    amount: Some(amount.amount.to_string()),
+                    denom: Some(amount.denom.clone()),
+                    vesting: true,
+                };
+                return storage_tx.insert_delegation_event(&event).await;
+            }
+            ExecuteMsg::UndelegateFromMixnode {
+                mix_id,
+                on_behalf_of,
+            } => {
+                let event = DelegationEvent {
+                    transaction_hash: decoded.execution.transaction_hash.clone(),
+                    index: decoded.execution.index,
+                    height: decoded.execution.height,
+                    delegator: on_behalf_of
+                        .clone()
+                        .unwrap_or_else(|| decoded.sender().to_string()),
+                    mix_id: (*mix_id).into(),
+                    action: DelegationAction::Undelegate,
+                    amount: None,
+                    denom: None,
+                    vesting: true,
+                };
+                return storage_tx.insert_delegation_event(&event).await;
+            }
+            ExecuteMsg::BondMixnode {
+                mix_node, amount, ..
+            } => bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                Some(mix_node.identity_key.clone()),
+                BondingAction::Bond,
+                amount_parts(&amount.amount, &amount.denom),
+            ),
+            ExecuteMsg::PledgeMore { amount } => bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                None,
+                BondingAction::PledgeMore,
+                amount_parts(&amount.amount, &amount.denom),
+            ),
+            ExecuteMsg::DecreasePledge { amount } => bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                None,
+                BondingAction::DecreasePledge,
+                amount_parts(&amount.amount, &amount.denom),
+            ),
+            ExecuteMsg::UnbondMixnode {} => bonding_event(
+                &decoded,
+                NodeType::Mixnode,
+                None,
+                BondingAction::Unbond,
+                (None, None),
+            ),
+            ExecuteMsg::BondGateway {
+                gateway, amount, ..
+            } => bonding_event(
+                &decoded,
+                NodeType::Gateway,
+                Some(gateway.identity_key.clone()),
+                BondingAction::Bond,
+                amount_parts(&amount.amount, &amount.denom),
+            ),
+            ExecuteMsg::UnbondGateway {} => bonding_event(
+                &decoded,
+                NodeType::Gateway,
+                None,
+                BondingAction::Unbond,
+                (None, None),
+            ),
+            _ => return Ok(()),
+        };
+
+        storage_tx.insert_bonding_event(&bonding).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::nym_contracts::tests::{
+        execute_msg, transaction, RecordingTransaction, CONTRACT, OTHER, SENDER,
+    };
+    use serde_json::json;
+
+    async fn handle(msg: serde_json::Value) -> RecordingTransaction {
+        let mut module = VestingContractModule::new(CONTRACT.parse().unwrap());
+        let msg = execute_msg(msg, vec![]);
+        let tx = transaction(vec![msg.clone()], true);
+
+        let mut storage_tx = RecordingTransaction::default();
+        module
+            .handle_msg(0, &msg, &tx, &mut storage_tx)
+            .await
+            .unwrap();
+        storage_tx
+    }
+
+    #[tokio::test]
+    async fn vesting_delegation_is_recorded() {
+        let recorded = handle(json!({"delegate_to_mixnode": {
+            "mix_id": 42,
+            "amount": {"denom": "unym", "amount": "1000"},
+            "on_behalf_of": null
+        }}))
+        .await;
+
+        assert_eq!(recorded.executions.len(), 1);
+        assert_eq!(recorded.executions[0].contract, NymContract::Vesting);
+        assert_eq!(recorded.delegations.len(), 1);
+        let event = &recorded.delegations[0];
+        assert_eq!(event.delegator, SENDER);
+        assert_eq!(event.mix_id, 42);
+        assert_eq!(event.action, DelegationAction::Delegate);
+        assert_eq!(event.amount.as_deref(), Some("1000"));
+        assert!(event.vesting);
+    }
+
+    #[tokio::test]
+    async fn delegations_on_behalf_are_attributed_to_the_owner() {
+        let recorded = handle(json!({"undelegate_from_mixnode": {
+            "mix_id": 42,
+            "on_behalf_of": OTHER
+        }}))
+        .await;
+
+        assert_eq!(recorded.delegations.len(), 1);
+        let event = &recorded.delegations[0];
+        assert_eq!(event.delegator, OTHER);
+        assert_eq!(event.action, DelegationAction::Undelegate);
+        assert!(event.amount.is_none());
+    }
+
+    #[tokio::test]
+    async fn vesting_bonding_changes_are_recorded() {
+        let recorded =
+            handle(json!({"pledge_more": {"amount": {"denom": "unym", "amount": "100"}}})).await;
+        assert_eq!(recorded.bondings.len(), 1);
+        let event = &recorded.bondings[0];
+        assert_eq!(event.owner, SENDER);
+        assert_eq!(event.node_type, NodeType::Mixnode);
+        assert_eq!(event.action, BondingAction::PledgeMore);
+        assert_eq!(event.amount.as_deref(), Some("100"));
+        assert!(event.vesting);
+
+        let recorded = handle(json!({"unbond_mixnode": {}})).await;
+        assert_eq!(recorded.bondings.len(), 1);
+        assert_eq!(recorded.bondings[0].action, BondingAction::Unbond);
+    }
+
+    #[tokio::test]
+    async fn other_messages_only_record_the_execution() {
+        let recorded = handle(json!({"claim_operator_reward": {}})).await;
+        assert_eq!(recorded.executions.len(), 1);
+        assert_eq!(recorded.executions[0].message_type, "claim_operator_reward");
+        assert!(recorded.delegations.is_empty());
+        assert!(recorded.bondings.is_empty());
+    }
+}
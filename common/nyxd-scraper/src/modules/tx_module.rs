@@ -3,14 +3,14 @@
 
 use crate::block_processor::types::ParsedTransactionResponse;
 use crate::error::ScraperError;
-use crate::storage::StorageTransaction;
+use crate::storage::NyxdScraperTransaction;
 use async_trait::async_trait;
 
 #[async_trait]
-pub trait TxModule {
+pub trait TxModule<T: NyxdScraperTransaction> {
     async fn handle_tx(
         &mut self,
         tx: &ParsedTransactionResponse,
-        storage_tx: &mut StorageTransaction,
+        storage_tx: &mut T,
     ) -> Result<(), ScraperError>;
 }
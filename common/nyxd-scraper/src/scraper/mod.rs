@@ -7,7 +7,8 @@ use crate::error::ScraperError;
 use crate::modules::{BlockModule, MsgModule, TxModule};
 use crate::rpc_client::RpcClient;
 use crate::scraper::subscriber::ChainSubscriber;
-use crate::storage::ScraperStorage;
+use crate::storage::sqlite::SqliteScraperStorage;
+use crate::storage::NyxdScraperStorage;
 use crate::PruningOptions;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -27,22 +28,28 @@ pub struct Config {
     /// Url to the rpc endpoint of a validator, for example `https://rpc.nymtech.net/`
     pub rpc_url: Url,
 
+    /// Path to the database file used by the default, SQLite, storage.
+    /// It's ignored if the scraper is created with an explicit storage backend.
     pub database_path: PathBuf,
 
     pub pruning_options: PruningOptions,
 }
 
-pub struct NyxdScraperBuilder {
+pub struct NyxdScraperBuilder<S: NyxdScraperStorage = SqliteScraperStorage> {
     config: Config,
+    storage: S,
 
-    block_modules: Vec<Box<dyn BlockModule + Send>>,
-    tx_modules: Vec<Box<dyn TxModule + Send>>,
-    msg_modules: Vec<Box<dyn MsgModule + Send>>,
+    block_modules: Vec<Box<dyn BlockModule<S::StorageTransaction> + Send>>,
+    tx_modules: Vec<Box<dyn TxModule<S::StorageTransaction> + Send>>,
+    msg_modules: Vec<Box<dyn MsgModule<S::StorageTransaction> + Send>>,
 }
 
-impl NyxdScraperBuilder {
-    pub async fn build_and_start(self) -> Result<NyxdScraper, ScraperError> {
-        let scraper = NyxdScraper::new(self.config).await?;
+impl<S> NyxdScraperBuilder<S>
+where
+    S: NyxdScraperStorage,
+{
+    pub async fn build_and_start(self) -> Result<NyxdScraper<S>, ScraperError> {
+        let scraper = NyxdScraper::new_with_storage(self.config, self.storage)?;
 
         let (processing_tx, processing_rx) = unbounded_channel();
         let (req_tx, req_rx) = channel(5);
@@ -83,48 +90,69 @@ impl NyxdScraperBuilder {
         Ok(scraper)
     }
 
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config, storage: S) -> Self {
         NyxdScraperBuilder {
             config,
+            storage,
             block_modules: vec![],
             tx_modules: vec![],
             msg_modules: vec![],
         }
     }
 
-    pub fn with_block_module<M: BlockModule + Send + 'static>(mut self, module: M) -> Self {
+    pub fn with_block_module<M>(mut self, module: M) -> Self
+    where
+        M: BlockModule<S::StorageTransaction> + Send + 'static,
+    {
         self.block_modules.push(Box::new(module));
         self
     }
 
-    pub fn with_tx_module<M: TxModule + Send + 'static>(mut self, module: M) -> Self {
+    pub fn with_tx_module<M>(mut self, module: M) -> Self
+    where
+        M: TxModule<S::StorageTransaction> + Send + 'static,
+    {
         self.tx_modules.push(Box::new(module));
         self
     }
 
-    pub fn with_msg_module<M: MsgModule + Send + 'static>(mut self, module: M) -> Self {
+    pub fn with_msg_module<M>(mut self, module: M) -> Self
+    where
+        M: MsgModule<S::StorageTransaction> + Send + 'static,
+    {
         self.msg_modules.push(Box::new(module));
         self
     }
 }
 
-pub struct NyxdScraper {
+pub struct NyxdScraper<S: NyxdScraperStorage = SqliteScraperStorage> {
     config: Config,
 
     task_tracker: TaskTracker,
     cancel_token: CancellationToken,
     startup_sync: Arc<Notify>,
-    pub storage: ScraperStorage,
+    pub storage: S,
+}
+
+impl NyxdScraper<SqliteScraperStorage> {
+    pub async fn new(config: Config) -> Result<Self, ScraperError> {
+        config.pruning_options.validate()?;
+        let storage = SqliteScraperStorage::init(&config.database_path).await?;
+
+        Self::new_with_storage(config, storage)
+    }
 }
 
-impl NyxdScraper {
-    pub fn builder(config: Config) -> NyxdScraperBuilder {
-        NyxdScraperBuilder::new(config)
+impl<S> NyxdScraper<S>
+where
+    S: NyxdScraperStorage,
+{
+    pub fn builder(config: Config, storage: S) -> NyxdScraperBuilder<S> {
+        NyxdScraperBuilder::new(config, storage)
     }
 
-    pub async fn new(config: Config) -> Result<Self, ScraperError> {
+    pub fn new_with_storage(config: Config, storage: S) -> Result<Self, ScraperError> {
         config.pruning_options.validate()?;
-        let storage = ScraperStorage::init(&config.database_path).await?;
 
         Ok(NyxdScraper {
             config,
@@ -138,7 +166,7 @@ impl NyxdScraper {
     fn start_tasks(
         &self,
         mut block_requester: BlockRequester,
-        mut block_processor: BlockProcessor,
+        mut block_processor: BlockProcessor<S>,
         mut chain_subscriber: ChainSubscriber,
     ) {
         self.task_tracker
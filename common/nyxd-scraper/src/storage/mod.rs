@@ -3,32 +3,22 @@
 
 use crate::block_processor::types::{FullBlockInformation, ParsedTransactionResponse};
 use crate::error::ScraperError;
-use crate::storage::manager::{
-    insert_block, insert_message, insert_precommit, insert_transaction, insert_validator,
-    prune_blocks, prune_messages, prune_pre_commits, prune_transactions, update_last_processed,
-    update_last_pruned, StorageManager,
+use crate::storage::models::{
+    BondingEvent, CommitSignature, ContractExecution, DelegationEvent, Validator,
 };
-use crate::storage::models::{CommitSignature, Validator};
+use async_trait::async_trait;
 use sqlx::types::time::OffsetDateTime;
-use sqlx::{ConnectOptions, Sqlite, Transaction};
-use std::fmt::Debug;
-use std::path::Path;
 use tendermint::block::{Commit, CommitSig};
 use tendermint::Block;
 use tendermint_rpc::endpoint::validators;
 use tokio::time::Instant;
-use tracing::{debug, error, info, instrument, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
 mod helpers;
-mod manager;
 pub mod models;
-
-pub type StorageTransaction = Transaction<'static, Sqlite>;
-
-#[derive(Clone)]
-pub struct ScraperStorage {
-    pub(crate) manager: StorageManager,
-}
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod sqlite;
 
 pub(crate) fn log_db_operation_time(op_name: &str, start_time: Instant) {
     let elapsed = start_time.elapsed();
@@ -43,98 +33,33 @@ pub(crate) fn log_db_operation_time(op_name: &str, start_time: Instant) {
     }
 }
 
-impl ScraperStorage {
-    #[instrument]
-    pub async fn init<P: AsRef<Path> + Debug>(database_path: P) -> Result<Self, ScraperError> {
-        let mut opts = sqlx::sqlite::SqliteConnectOptions::new()
-            .filename(database_path)
-            .create_if_missing(true);
+/// Storage backend of the scraper, i.e. where all the processed chain data is going to end up.
+#[async_trait]
+pub trait NyxdScraperStorage: Clone + Send + Sync + 'static {
+    type StorageTransaction: NyxdScraperTransaction;
 
-        // TODO: do we want auto_vacuum ?
+    /// Begin a storage transaction within which an entire block is going to be persisted.
+    async fn begin_processing_tx(&self) -> Result<Self::StorageTransaction, ScraperError>;
 
-        opts.disable_statement_logging();
-
-        let connection_pool = match sqlx::SqlitePool::connect_with(opts).await {
-            Ok(db) => db,
-            Err(err) => {
-                error!("Failed to connect to SQLx database: {err}");
-                return Err(err.into());
-            }
-        };
-
-        if let Err(err) = sqlx::migrate!("./sql_migrations")
-            .run(&connection_pool)
-            .await
-        {
-            error!("Failed to initialize SQLx database: {err}");
-            return Err(err.into());
-        }
-
-        info!("Database migration finished!");
-
-        let manager = StorageManager { connection_pool };
-        manager.set_initial_metadata().await?;
-
-        let storage = ScraperStorage { manager };
-
-        Ok(storage)
-    }
-
-    #[instrument(skip(self))]
-    pub async fn prune_storage(
+    async fn prune_storage(
         &self,
         oldest_to_keep: u32,
         current_height: u32,
-    ) -> Result<(), ScraperError> {
-        let start = Instant::now();
-
-        let mut tx = self.begin_processing_tx().await?;
-
-        prune_messages(oldest_to_keep.into(), &mut tx).await?;
-        prune_transactions(oldest_to_keep.into(), &mut tx).await?;
-        prune_pre_commits(oldest_to_keep.into(), &mut tx).await?;
-        prune_blocks(oldest_to_keep.into(), &mut tx).await?;
-        update_last_pruned(current_height.into(), &mut tx).await?;
+    ) -> Result<(), ScraperError>;
 
-        let commit_start = Instant::now();
-        tx.commit()
-            .await
-            .map_err(|source| ScraperError::StorageTxCommitFailure { source })?;
-        log_db_operation_time("committing pruning tx", commit_start);
-
-        log_db_operation_time("pruning storage", start);
-        Ok(())
-    }
+    async fn lowest_block_height(&self) -> Result<Option<i64>, ScraperError>;
 
-    #[instrument(skip_all)]
-    pub async fn begin_processing_tx(&self) -> Result<StorageTransaction, ScraperError> {
-        debug!("starting storage tx");
-        self.manager
-            .connection_pool
-            .begin()
-            .await
-            .map_err(|source| ScraperError::StorageTxBeginFailure { source })
-    }
-
-    pub async fn lowest_block_height(&self) -> Result<Option<i64>, ScraperError> {
-        Ok(self.manager.get_lowest_block().await?)
-    }
-
-    pub async fn get_first_block_height_after(
+    async fn get_first_block_height_after(
         &self,
         time: OffsetDateTime,
-    ) -> Result<Option<i64>, ScraperError> {
-        Ok(self.manager.get_first_block_height_after(time).await?)
-    }
+    ) -> Result<Option<i64>, ScraperError>;
 
-    pub async fn get_last_block_height_before(
+    async fn get_last_block_height_before(
         &self,
         time: OffsetDateTime,
-    ) -> Result<Option<i64>, ScraperError> {
-        Ok(self.manager.get_last_block_height_before(time).await?)
-    }
+    ) -> Result<Option<i64>, ScraperError>;
 
-    pub async fn get_blocks_between(
+    async fn get_blocks_between(
         &self,
         start_time: OffsetDateTime,
         end_time: OffsetDateTime,
@@ -149,19 +74,14 @@ impl ScraperStorage {
         Ok(block_end - block_start)
     }
 
-    pub async fn get_signed_between(
+    async fn get_signed_between(
         &self,
         consensus_address: &str,
         start_height: i64,
         end_height: i64,
-    ) -> Result<i32, ScraperError> {
-        Ok(self
-            .manager
-            .get_signed_between(consensus_address, start_height, end_height)
-            .await?)
-    }
+    ) -> Result<i32, ScraperError>;
 
-    pub async fn get_signed_between_times(
+    async fn get_signed_between_times(
         &self,
         consensus_address: &str,
         start_time: OffsetDateTime,
@@ -178,37 +98,93 @@ impl ScraperStorage {
             .await
     }
 
-    pub async fn get_precommit(
+    async fn get_precommit(
         &self,
         consensus_address: &str,
         height: i64,
-    ) -> Result<Option<CommitSignature>, ScraperError> {
-        Ok(self
-            .manager
-            .get_precommit(consensus_address, height)
-            .await?)
-    }
+    ) -> Result<Option<CommitSignature>, ScraperError>;
 
-    pub async fn get_block_signers(&self, height: i64) -> Result<Vec<Validator>, ScraperError> {
-        Ok(self.manager.get_block_validators(height).await?)
-    }
+    async fn get_block_signers(&self, height: i64) -> Result<Vec<Validator>, ScraperError>;
 
-    pub async fn get_all_known_validators(&self) -> Result<Vec<Validator>, ScraperError> {
-        Ok(self.manager.get_validators().await?)
-    }
+    async fn get_all_known_validators(&self) -> Result<Vec<Validator>, ScraperError>;
 
-    pub async fn get_last_processed_height(&self) -> Result<i64, ScraperError> {
-        Ok(self.manager.get_last_processed_height().await?)
-    }
+    async fn get_last_processed_height(&self) -> Result<i64, ScraperError>;
 
-    pub async fn get_pruned_height(&self) -> Result<i64, ScraperError> {
-        Ok(self.manager.get_pruned_height().await?)
-    }
+    async fn get_pruned_height(&self) -> Result<i64, ScraperError>;
 }
 
-pub async fn persist_block(
+/// Storage transaction used for persisting a single block alongside all of its transactions and messages.
+/// It is also passed to all registered modules so that they could persist any additional data atomically.
+#[async_trait]
+pub trait NyxdScraperTransaction: Send {
+    async fn commit(self) -> Result<(), ScraperError>;
+
+    async fn insert_validator(
+        &mut self,
+        consensus_address: String,
+        consensus_pubkey: String,
+    ) -> Result<(), ScraperError>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_block(
+        &mut self,
+        height: i64,
+        hash: String,
+        num_txs: u32,
+        total_gas: i64,
+        proposer_address: String,
+        timestamp: OffsetDateTime,
+    ) -> Result<(), ScraperError>;
+
+    async fn insert_precommit(
+        &mut self,
+        validator_address: String,
+        height: i64,
+        timestamp: OffsetDateTime,
+        voting_power: i64,
+        proposer_priority: i64,
+    ) -> Result<(), ScraperError>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_transaction(
+        &mut self,
+        hash: String,
+        height: i64,
+        index: i64,
+        success: bool,
+        messages: i64,
+        memo: String,
+        gas_wanted: i64,
+        gas_used: i64,
+        raw_log: String,
+    ) -> Result<(), ScraperError>;
+
+    async fn insert_message(
+        &mut self,
+        transaction_hash: String,
+        index: i64,
+        typ: String,
+        height: i64,
+    ) -> Result<(), ScraperError>;
+
+    async fn update_last_processed(&mut self, height: i64) -> Result<(), ScraperError>;
+
+    async fn insert_contract_execution(
+        &mut self,
+        execution: &ContractExecution,
+    ) -> Result<(), ScraperError>;
+
+    async fn insert_delegation_event(
+        &mut self,
+        event: &DelegationEvent,
+    ) -> Result<(), ScraperError>;
+
+    async fn insert_bonding_event(&mut self, event: &BondingEvent) -> Result<(), ScraperError>;
+}
+
+pub async fn persist_block<T: NyxdScraperTransaction>(
     block: &FullBlockInformation,
-    tx: &mut StorageTransaction,
+    tx: &mut T,
 ) -> Result<(), ScraperError> {
     let total_gas = crate::helpers::tx_gas_sum(&block.transactions);
 
@@ -234,56 +210,52 @@ pub async fn persist_block(
     // persist messages (inside the transactions)
     persist_messages(&block.transactions, tx).await?;
 
-    update_last_processed(block.block.header.height.into(), tx).await?;
+    tx.update_last_processed(block.block.header.height.into())
+        .await?;
 
     Ok(())
 }
 
-async fn persist_validators(
+async fn persist_validators<T: NyxdScraperTransaction>(
     validators: &validators::Response,
-    tx: &mut StorageTransaction,
+    tx: &mut T,
 ) -> Result<(), ScraperError> {
     debug!("persisting {} validators", validators.total);
     for validator in &validators.validators {
         let consensus_address = crate::helpers::validator_consensus_address(validator.address)?;
         let consensus_pubkey = crate::helpers::validator_pubkey_to_bech32(validator.pub_key)?;
 
-        insert_validator(
-            consensus_address.to_string(),
-            consensus_pubkey.to_string(),
-            &mut *tx,
-        )
-        .await?;
+        tx.insert_validator(consensus_address.to_string(), consensus_pubkey.to_string())
+            .await?;
     }
 
     Ok(())
 }
 
-async fn persist_block_data(
+async fn persist_block_data<T: NyxdScraperTransaction>(
     block: &Block,
     total_gas: i64,
-    tx: &mut StorageTransaction,
+    tx: &mut T,
 ) -> Result<(), ScraperError> {
     let proposer_address =
         crate::helpers::validator_consensus_address(block.header.proposer_address)?.to_string();
 
-    insert_block(
+    tx.insert_block(
         block.header.height.into(),
         block.header.hash().to_string(),
         block.data.len() as u32,
         total_gas,
         proposer_address,
         block.header.time.into(),
-        tx,
     )
     .await?;
     Ok(())
 }
 
-async fn persist_commits(
+async fn persist_commits<T: NyxdScraperTransaction>(
     commits: &Commit,
     validators: &validators::Response,
-    tx: &mut StorageTransaction,
+    tx: &mut T,
 ) -> Result<(), ScraperError> {
     debug!("persisting up to {} commits", commits.signatures.len());
     let height: i64 = commits.height.into();
@@ -314,13 +286,12 @@ async fn persist_commits(
             continue;
         }
 
-        insert_precommit(
+        tx.insert_precommit(
             validator_address.to_string(),
             height,
             (*timestamp).into(),
             validator.power.into(),
             validator.proposer_priority.value(),
-            &mut *tx,
         )
         .await?;
     }
@@ -328,14 +299,14 @@ async fn persist_commits(
     Ok(())
 }
 
-async fn persist_txs(
+async fn persist_txs<T: NyxdScraperTransaction>(
     txs: &[ParsedTransactionResponse],
-    tx: &mut StorageTransaction,
+    tx: &mut T,
 ) -> Result<(), ScraperError> {
     debug!("persisting {} txs", txs.len());
 
     for chain_tx in txs {
-        insert_transaction(
+        tx.insert_transaction(
             chain_tx.hash.to_string(),
             chain_tx.height.into(),
             chain_tx.index as i64,
@@ -345,7 +316,6 @@ async fn persist_txs(
             chain_tx.tx_result.gas_wanted,
             chain_tx.tx_result.gas_used,
             chain_tx.tx_result.log.clone(),
-            &mut *tx,
         )
         .await?;
     }
@@ -353,20 +323,19 @@ async fn persist_txs(
     Ok(())
 }
 
-async fn persist_messages(
+async fn persist_messages<T: NyxdScraperTransaction>(
     txs: &[ParsedTransactionResponse],
-    tx: &mut StorageTransaction,
+    tx: &mut T,
 ) -> Result<(), ScraperError> {
     debug!("persisting messages");
 
     for chain_tx in txs {
         for (index, msg) in chain_tx.tx.body.messages.iter().enumerate() {
-            insert_message(
+            tx.insert_message(
                 chain_tx.hash.to_string(),
                 index as i64,
                 msg.type_url.clone(),
                 chain_tx.height.into(),
-                &mut *tx,
             )
             .await?
         }
@@ -374,3 +343,198 @@ async fn persist_messages(
 
     Ok(())
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::storage::models::{BondingAction, DelegationAction, NodeType, NymContract};
+    use time::Duration;
+
+    const VALIDATOR1: &str = "nvalcons1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq1";
+    const VALIDATOR2: &str = "nvalcons1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq2";
+
+    fn block_time(height: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(1700000000).unwrap() + Duration::seconds(height * 10)
+    }
+
+    async fn persist_test_blocks<S: NyxdScraperStorage>(storage: &S) {
+        let mut tx = storage.begin_processing_tx().await.unwrap();
+        for validator in [VALIDATOR1, VALIDATOR2] {
+            tx.insert_validator(validator.to_string(), format!("{validator}-pubkey"))
+                .await
+                .unwrap();
+        }
+
+        for height in 1..=3 {
+            tx.insert_block(
+                height,
+                format!("block-hash-{height}"),
+                1,
+                100000,
+                VALIDATOR1.to_string(),
+                block_time(height),
+            )
+            .await
+            .unwrap();
+            tx.insert_precommit(VALIDATOR1.to_string(), height, block_time(height), 10, -5)
+                .await
+                .unwrap();
+
+            let tx_hash = format!("tx-hash-{height}");
+            tx.insert_transaction(
+                tx_hash.clone(),
+                height,
+                0,
+                true,
+                1,
+                "memo".to_string(),
+                200000,
+                100000,
+                String::new(),
+            )
+            .await
+            .unwrap();
+            tx.insert_message(
+                tx_hash,
+                0,
+                "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+                height,
+            )
+            .await
+            .unwrap();
+        }
+        // the second validator has only signed the second block
+        tx.insert_precommit(VALIDATOR2.to_string(), 2, block_time(2), 5, 5)
+            .await
+            .unwrap();
+
+        tx.insert_contract_execution(&ContractExecution {
+            transaction_hash: "tx-hash-3".to_string(),
+            index: 0,
+            height: 3,
+            contract: NymContract::Mixnet,
+            contract_address: "n1contract".to_string(),
+            sender: "n1sender".to_string(),
+            message_type: "delegate_to_mixnode".to_string(),
+            message: r#"{"delegate_to_mixnode":{"mix_id":42}}"#.to_string(),
+            funds: "1000unym".to_string(),
+        })
+        .await
+        .unwrap();
+        tx.insert_delegation_event(&DelegationEvent {
+            transaction_hash: "tx-hash-3".to_string(),
+            index: 0,
+            height: 3,
+            delegator: "n1sender".to_string(),
+            mix_id: 42,
+            action: DelegationAction::Delegate,
+            amount: Some("1000".to_string()),
+            denom: Some("unym".to_string()),
+            vesting: false,
+        })
+        .await
+        .unwrap();
+        tx.insert_bonding_event(&BondingEvent {
+            transaction_hash: "tx-hash-2".to_string(),
+            index: 0,
+            height: 2,
+            owner: "n1sender".to_string(),
+            node_type: NodeType::Gateway,
+            identity_key: Some("foomp".to_string()),
+            action: BondingAction::Bond,
+            amount: Some("100000000".to_string()),
+            denom: Some("unym".to_string()),
+            vesting: true,
+        })
+        .await
+        .unwrap();
+
+        tx.update_last_processed(3).await.unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    /// Runs every storage operation against the provided (empty) backend.
+    pub(crate) async fn exercise_storage<S: NyxdScraperStorage>(storage: S) {
+        assert_eq!(storage.get_last_processed_height().await.unwrap(), 0);
+        assert!(storage.lowest_block_height().await.unwrap().is_none());
+
+        persist_test_blocks(&storage).await;
+        // persisting the same data again must not fail
+        persist_test_blocks(&storage).await;
+
+        assert_eq!(storage.get_last_processed_height().await.unwrap(), 3);
+        assert_eq!(storage.lowest_block_height().await.unwrap(), Some(1));
+        assert_eq!(
+            storage
+                .get_first_block_height_after(block_time(1) + Duration::seconds(5))
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            storage
+                .get_last_block_height_before(block_time(3) - Duration::seconds(5))
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            storage
+                .get_blocks_between(block_time(0), block_time(4))
+                .await
+                .unwrap(),
+            2
+        );
+
+        assert_eq!(
+            storage.get_signed_between(VALIDATOR1, 1, 3).await.unwrap(),
+            3
+        );
+        assert_eq!(
+            storage.get_signed_between(VALIDATOR2, 1, 3).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            storage
+                .get_signed_between_times(VALIDATOR2, block_time(0), block_time(4))
+                .await
+                .unwrap(),
+            1
+        );
+
+        let precommit = storage.get_precommit(VALIDATOR2, 2).await.unwrap().unwrap();
+        assert_eq!(precommit.validator_address, VALIDATOR2);
+        assert_eq!(precommit.voting_power, 5);
+        assert_eq!(precommit.proposer_priority, 5);
+        assert_eq!(precommit.timestamp, block_time(2));
+        assert!(storage
+            .get_precommit(VALIDATOR2, 1)
+            .await
+            .unwrap()
+            .is_none());
+
+        let mut signers = storage.get_block_signers(2).await.unwrap();
+        signers.sort_by(|a, b| a.consensus_address.cmp(&b.consensus_address));
+        assert_eq!(
+            signers
+                .iter()
+                .map(|v| v.consensus_address.as_str())
+                .collect::<Vec<_>>(),
+            vec![VALIDATOR1, VALIDATOR2]
+        );
+        assert_eq!(storage.get_block_signers(1).await.unwrap().len(), 1);
+        assert_eq!(storage.get_all_known_validators().await.unwrap().len(), 2);
+
+        storage.prune_storage(3, 3).await.unwrap();
+        assert_eq!(storage.lowest_block_height().await.unwrap(), Some(3));
+        assert_eq!(
+            storage.get_signed_between(VALIDATOR1, 1, 3).await.unwrap(),
+            1
+        );
+        assert!(storage
+            .get_precommit(VALIDATOR2, 2)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -28,3 +28,130 @@ pub struct CommitSignature {
     pub proposer_priority: i64,
     pub timestamp: OffsetDateTime,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NymContract {
+    Mixnet,
+    Vesting,
+    Ecash,
+}
+
+impl NymContract {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NymContract::Mixnet => "mixnet",
+            NymContract::Vesting => "vesting",
+            NymContract::Ecash => "ecash",
+        }
+    }
+}
+
+/// Top-level execution of one of the Nym contracts included in a successful transaction.
+#[derive(Debug, Clone)]
+pub struct ContractExecution {
+    pub transaction_hash: String,
+    pub index: i64,
+    pub height: i64,
+    pub contract: NymContract,
+    pub contract_address: String,
+    pub sender: String,
+
+    /// Name of the executed variant, for example `delegate_to_mixnode`
+    pub message_type: String,
+
+    /// Full json-encoded execute message
+    pub message: String,
+
+    /// Funds sent alongside the message, for example `100000000unym`, comma separated
+    pub funds: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegationAction {
+    Delegate,
+    Undelegate,
+    RedelegateFrom,
+    RedelegateTo,
+}
+
+impl DelegationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DelegationAction::Delegate => "delegate",
+            DelegationAction::Undelegate => "undelegate",
+            DelegationAction::RedelegateFrom => "redelegate_from",
+            DelegationAction::RedelegateTo => "redelegate_to",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DelegationEvent {
+    pub transaction_hash: String,
+    pub index: i64,
+    pub height: i64,
+    pub delegator: String,
+    pub mix_id: i64,
+    pub action: DelegationAction,
+
+    /// Amount of the delegation, if known at the time of the execution (it's not for undelegations)
+    pub amount: Option<String>,
+    pub denom: Option<String>,
+
+    /// Indicates whether the delegation has been made with tokens from a vesting account
+    pub vesting: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeType {
+    Mixnode,
+    Gateway,
+}
+
+impl NodeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::Mixnode => "mixnode",
+            NodeType::Gateway => "gateway",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondingAction {
+    Bond,
+    Unbond,
+    PledgeMore,
+    DecreasePledge,
+}
+
+impl BondingAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BondingAction::Bond => "bond",
+            BondingAction::Unbond => "unbond",
+            BondingAction::PledgeMore => "pledge_more",
+            BondingAction::DecreasePledge => "decrease_pledge",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BondingEvent {
+    pub transaction_hash: String,
+    pub index: i64,
+    pub height: i64,
+    pub owner: String,
+    pub node_type: NodeType,
+
+    /// Identity key of the node, only known at the time of bonding
+    pub identity_key: Option<String>,
+    pub action: BondingAction,
+
+    /// Amount of the pledge change, if known at the time of the execution (it's not for unbonding)
+    pub amount: Option<String>,
+    pub denom: Option<String>,
+
+    /// Indicates whether the node has been bonded with tokens from a vesting account
+    pub vesting: bool,
+}
@@ -0,0 +1,601 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+// note: unlike the SQLite manager, the queries in here are not checked at compile time
+// as that would have required access to a live postgres instance during the build.
+// instead, all of them are exercised by the storage test that's run against a live database in CI
+// (see `ci-nyxd-scraper-postgres.yml`)
+
+use crate::storage::log_db_operation_time;
+use crate::storage::models::{
+    BondingEvent, CommitSignature, ContractExecution, DelegationEvent, Validator,
+};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{Executor, Postgres};
+use tokio::time::Instant;
+use tracing::{instrument, trace};
+
+#[derive(Clone)]
+pub(crate) struct StorageManager {
+    pub(crate) connection_pool: sqlx::PgPool,
+}
+
+impl StorageManager {
+    pub(crate) async fn set_initial_metadata(&self) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO metadata (id, last_processed_height) VALUES (0, 0) ON CONFLICT DO NOTHING",
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn get_lowest_block(&self) -> Result<Option<i64>, sqlx::Error> {
+        trace!("get_lowest_block");
+        let start = Instant::now();
+
+        let res = sqlx::query_scalar("SELECT height FROM block ORDER BY height ASC LIMIT 1")
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        log_db_operation_time("get_lowest_block", start);
+
+        Ok(res)
+    }
+
+    pub(crate) async fn get_first_block_height_after(
+        &self,
+        time: OffsetDateTime,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        trace!("get_first_block_height_after");
+        let start = Instant::now();
+
+        let res = sqlx::query_scalar(
+            r#"
+                SELECT height
+                FROM block
+                WHERE timestamp > $1
+                ORDER BY timestamp
+                LIMIT 1
+            "#,
+        )
+        .bind(time)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        log_db_operation_time("get_first_block_height_after", start);
+
+        Ok(res)
+    }
+
+    pub(crate) async fn get_last_block_height_before(
+        &self,
+        time: OffsetDateTime,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        trace!("get_last_block_height_before");
+        let start = Instant::now();
+
+        let res = sqlx::query_scalar(
+            r#"
+                SELECT height
+                FROM block
+                WHERE timestamp < $1
+                ORDER BY timestamp DESC
+                LIMIT 1
+            "#,
+        )
+        .bind(time)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        log_db_operation_time("get_last_block_height_before", start);
+
+        Ok(res)
+    }
+
+    pub(crate) async fn get_signed_between(
+        &self,
+        consensus_address: &str,
+        start_height: i64,
+        end_height: i64,
+    ) -> Result<i64, sqlx::Error> {
+        trace!("get_signed_between");
+        let start = Instant::now();
+
+        let count = sqlx::query_scalar(
+            r#"
+                SELECT COUNT(*) FROM pre_commit
+                WHERE
+                    validator_address = $1
+                    AND height >= $2
+                    AND height <= $3
+            "#,
+        )
+        .bind(consensus_address)
+        .bind(start_height)
+        .bind(end_height)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        log_db_operation_time("get_signed_between", start);
+
+        Ok(count)
+    }
+
+    pub(crate) async fn get_precommit(
+        &self,
+        consensus_address: &str,
+        height: i64,
+    ) -> Result<Option<CommitSignature>, sqlx::Error> {
+        trace!("get_precommit");
+        let start = Instant::now();
+
+        let res = sqlx::query_as(
+            r#"
+                SELECT * FROM pre_commit
+                WHERE validator_address = $1
+                AND height = $2
+            "#,
+        )
+        .bind(consensus_address)
+        .bind(height)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        log_db_operation_time("get_precommit", start);
+
+        Ok(res)
+    }
+
+    pub(crate) async fn get_block_validators(
+        &self,
+        height: i64,
+    ) -> Result<Vec<Validator>, sqlx::Error> {
+        trace!("get_block_validators");
+        let start = Instant::now();
+
+        let res = sqlx::query_as(
+            r#"
+                SELECT * FROM validator
+                WHERE EXISTS (
+                    SELECT 1 FROM pre_commit
+                    WHERE height = $1
+                    AND pre_commit.validator_address = validator.consensus_address
+                )
+            "#,
+        )
+        .bind(height)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        log_db_operation_time("get_block_validators", start);
+
+        Ok(res)
+    }
+
+    pub(crate) async fn get_validators(&self) -> Result<Vec<Validator>, sqlx::Error> {
+        trace!("get_validators");
+        let start = Instant::now();
+
+        let res = sqlx::query_as("SELECT * FROM validator")
+            .fetch_all(&self.connection_pool)
+            .await?;
+        log_db_operation_time("get_validators", start);
+
+        Ok(res)
+    }
+
+    pub(crate) async fn get_last_processed_height(&self) -> Result<i64, sqlx::Error> {
+        trace!("get_last_processed_height");
+        let start = Instant::now();
+
+        let res = sqlx::query_scalar("SELECT last_processed_height FROM metadata")
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        log_db_operation_time("get_last_processed_height", start);
+
+        Ok(res.unwrap_or(-1))
+    }
+
+    pub(crate) async fn get_pruned_height(&self) -> Result<i64, sqlx::Error> {
+        trace!("get_pruned_height");
+        let start = Instant::now();
+
+        let res = sqlx::query_scalar("SELECT last_pruned_height FROM pruning")
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        log_db_operation_time("get_pruned_height", start);
+
+        Ok(res.unwrap_or(-1))
+    }
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_validator<'a, E>(
+    consensus_address: String,
+    consensus_pubkey: String,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_validator");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO validator (consensus_address, consensus_pubkey)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(consensus_address)
+    .bind(consensus_pubkey)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_validator", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_block<'a, E>(
+    height: i64,
+    hash: String,
+    num_txs: u32,
+    total_gas: i64,
+    proposer_address: String,
+    timestamp: OffsetDateTime,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_block");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO block (height, hash, num_txs, total_gas, proposer_address, timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(height)
+    .bind(hash)
+    .bind(num_txs as i64)
+    .bind(total_gas)
+    .bind(proposer_address)
+    .bind(timestamp)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_block", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_precommit<'a, E>(
+    validator_address: String,
+    height: i64,
+    timestamp: OffsetDateTime,
+    voting_power: i64,
+    proposer_priority: i64,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_precommit");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO pre_commit (validator_address, height, timestamp, voting_power, proposer_priority)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (validator_address, timestamp) DO NOTHING
+        "#,
+    )
+    .bind(validator_address)
+    .bind(height)
+    .bind(timestamp)
+    .bind(voting_power)
+    .bind(proposer_priority)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_precommit", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn insert_transaction<'a, E>(
+    hash: String,
+    height: i64,
+    index: i64,
+    success: bool,
+    messages: i64,
+    memo: String,
+    gas_wanted: i64,
+    gas_used: i64,
+    raw_log: String,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_transaction");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO "transaction" (hash, height, "index", success, num_messages, memo, gas_wanted, gas_used, raw_log)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               ON CONFLICT (hash) DO UPDATE
+               SET height = excluded.height,
+               "index" = excluded."index",
+               success = excluded.success,
+               num_messages = excluded.num_messages,
+               memo = excluded.memo,
+               gas_wanted = excluded.gas_wanted,
+               gas_used = excluded.gas_used,
+               raw_log = excluded.raw_log
+        "#,
+    )
+    .bind(hash)
+    .bind(height)
+    .bind(index as i32)
+    .bind(success)
+    .bind(messages as i32)
+    .bind(memo)
+    .bind(gas_wanted)
+    .bind(gas_used)
+    .bind(raw_log)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_transaction", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_message<'a, E>(
+    transaction_hash: String,
+    index: i64,
+    typ: String,
+    height: i64,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_message");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO message (transaction_hash, "index", type, height)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (transaction_hash, "index") DO UPDATE
+                SET height = excluded.height,
+                type = excluded.type
+        "#,
+    )
+    .bind(transaction_hash)
+    .bind(index)
+    .bind(typ)
+    .bind(height)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_message", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_contract_execution<'a, E>(
+    execution: &ContractExecution,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_contract_execution");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO nym_contract_execution (transaction_hash, "index", height, contract, contract_address, sender, message_type, message, funds)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8::jsonb, $9)
+            ON CONFLICT (transaction_hash, "index") DO NOTHING
+        "#,
+    )
+    .bind(&execution.transaction_hash)
+    .bind(execution.index)
+    .bind(execution.height)
+    .bind(execution.contract.as_str())
+    .bind(&execution.contract_address)
+    .bind(&execution.sender)
+    .bind(&execution.message_type)
+    .bind(&execution.message)
+    .bind(&execution.funds)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_contract_execution", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_delegation_event<'a, E>(
+    event: &DelegationEvent,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_delegation_event");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO delegation_event (transaction_hash, "index", height, delegator, mix_id, action, amount, denom, vesting)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (transaction_hash, "index", mix_id, action) DO NOTHING
+        "#,
+    )
+    .bind(&event.transaction_hash)
+    .bind(event.index)
+    .bind(event.height)
+    .bind(&event.delegator)
+    .bind(event.mix_id)
+    .bind(event.action.as_str())
+    .bind(&event.amount)
+    .bind(&event.denom)
+    .bind(event.vesting)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_delegation_event", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_bonding_event<'a, E>(
+    event: &BondingEvent,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("insert_bonding_event");
+    let start = Instant::now();
+
+    sqlx::query(
+        r#"
+            INSERT INTO bonding_event (transaction_hash, "index", height, owner, node_type, identity_key, action, amount, denom, vesting)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (transaction_hash, "index") DO NOTHING
+        "#,
+    )
+    .bind(&event.transaction_hash)
+    .bind(event.index)
+    .bind(event.height)
+    .bind(&event.owner)
+    .bind(event.node_type.as_str())
+    .bind(&event.identity_key)
+    .bind(event.action.as_str())
+    .bind(&event.amount)
+    .bind(&event.denom)
+    .bind(event.vesting)
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_bonding_event", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn update_last_processed<'a, E>(
+    height: i64,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("update_last_processed");
+    let start = Instant::now();
+
+    sqlx::query("UPDATE metadata SET last_processed_height = $1")
+        .bind(height)
+        .execute(executor)
+        .await?;
+    log_db_operation_time("update_last_processed", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn update_last_pruned<'a, E>(height: i64, executor: E) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("update_last_pruned");
+    let start = Instant::now();
+
+    sqlx::query("UPDATE pruning SET last_pruned_height = $1")
+        .bind(height)
+        .execute(executor)
+        .await?;
+    log_db_operation_time("update_last_pruned", start);
+
+    Ok(())
+}
+
+pub(crate) async fn prune_blocks<'a, E>(oldest_to_keep: i64, executor: E) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("prune_blocks");
+    let start = Instant::now();
+
+    sqlx::query("DELETE FROM block WHERE height < $1")
+        .bind(oldest_to_keep)
+        .execute(executor)
+        .await?;
+    log_db_operation_time("prune_blocks", start);
+
+    Ok(())
+}
+
+pub(crate) async fn prune_pre_commits<'a, E>(
+    oldest_to_keep: i64,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("prune_pre_commits");
+    let start = Instant::now();
+
+    sqlx::query("DELETE FROM pre_commit WHERE height < $1")
+        .bind(oldest_to_keep)
+        .execute(executor)
+        .await?;
+    log_db_operation_time("prune_pre_commits", start);
+
+    Ok(())
+}
+
+pub(crate) async fn prune_transactions<'a, E>(
+    oldest_to_keep: i64,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("prune_transactions");
+    let start = Instant::now();
+
+    sqlx::query("DELETE FROM \"transaction\" WHERE height < $1")
+        .bind(oldest_to_keep)
+        .execute(executor)
+        .await?;
+    log_db_operation_time("prune_transactions", start);
+
+    Ok(())
+}
+
+pub(crate) async fn prune_messages<'a, E>(
+    oldest_to_keep: i64,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    trace!("prune_messages");
+    let start = Instant::now();
+
+    sqlx::query("DELETE FROM message WHERE height < $1")
+        .bind(oldest_to_keep)
+        .execute(executor)
+        .await?;
+    log_db_operation_time("prune_messages", start);
+
+    Ok(())
+}
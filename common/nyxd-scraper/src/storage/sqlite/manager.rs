@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::storage::log_db_operation_time;
-use crate::storage::models::{CommitSignature, Validator};
+use crate::storage::models::{
+    BondingEvent, CommitSignature, ContractExecution, DelegationEvent, Validator,
+};
 use sqlx::types::time::OffsetDateTime;
 use sqlx::{Executor, Sqlite};
 use tokio::time::Instant;
@@ -424,6 +426,113 @@ where
     Ok(())
 }
 
+#[instrument(skip(executor))]
+pub(crate) async fn insert_contract_execution<'a, E>(
+    execution: &ContractExecution,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    trace!("insert_contract_execution");
+    let start = Instant::now();
+
+    let contract = execution.contract.as_str();
+    sqlx::query!(
+        r#"
+            INSERT INTO nym_contract_execution (transaction_hash, "index", height, contract, contract_address, sender, message_type, message, funds)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (transaction_hash, "index") DO NOTHING
+        "#,
+        execution.transaction_hash,
+        execution.index,
+        execution.height,
+        contract,
+        execution.contract_address,
+        execution.sender,
+        execution.message_type,
+        execution.message,
+        execution.funds,
+    )
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_contract_execution", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_delegation_event<'a, E>(
+    event: &DelegationEvent,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    trace!("insert_delegation_event");
+    let start = Instant::now();
+
+    let action = event.action.as_str();
+    sqlx::query!(
+        r#"
+            INSERT INTO delegation_event (transaction_hash, "index", height, delegator, mix_id, action, amount, denom, vesting)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (transaction_hash, "index", mix_id, action) DO NOTHING
+        "#,
+        event.transaction_hash,
+        event.index,
+        event.height,
+        event.delegator,
+        event.mix_id,
+        action,
+        event.amount,
+        event.denom,
+        event.vesting,
+    )
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_delegation_event", start);
+
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub(crate) async fn insert_bonding_event<'a, E>(
+    event: &BondingEvent,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    trace!("insert_bonding_event");
+    let start = Instant::now();
+
+    let node_type = event.node_type.as_str();
+    let action = event.action.as_str();
+    sqlx::query!(
+        r#"
+            INSERT INTO bonding_event (transaction_hash, "index", height, owner, node_type, identity_key, action, amount, denom, vesting)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (transaction_hash, "index") DO NOTHING
+        "#,
+        event.transaction_hash,
+        event.index,
+        event.height,
+        event.owner,
+        node_type,
+        event.identity_key,
+        action,
+        event.amount,
+        event.denom,
+        event.vesting,
+    )
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_bonding_event", start);
+
+    Ok(())
+}
+
 #[instrument(skip(executor))]
 pub(crate) async fn update_last_processed<'a, E>(
     height: i64,
@@ -0,0 +1,324 @@
+// Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::ScraperError;
+use crate::storage::log_db_operation_time;
+use crate::storage::models::{
+    BondingEvent, CommitSignature, ContractExecution, DelegationEvent, Validator,
+};
+use crate::storage::sqlite::manager::{
+    insert_block, insert_bonding_event, insert_contract_execution, insert_delegation_event,
+    insert_message, insert_precommit, insert_transaction, insert_validator, prune_blocks,
+    prune_messages, prune_pre_commits, prune_transactions, update_last_processed,
+    update_last_pruned, StorageManager,
+};
+use crate::storage::{NyxdScraperStorage, NyxdScraperTransaction};
+use async_trait::async_trait;
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{ConnectOptions, Sqlite, Transaction};
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use tokio::time::Instant;
+use tracing::{debug, error, info, instrument};
+
+mod manager;
+
+/// Wrapper around an sqlx SQLite transaction. It derefs into the underlying transaction
+/// so that any custom modules could also persist their own data.
+pub struct SqliteStorageTransaction(Transaction<'static, Sqlite>);
+
+impl Deref for SqliteStorageTransaction {
+    type Target = Transaction<'static, Sqlite>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SqliteStorageTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteScraperStorage {
+    pub(crate) manager: StorageManager,
+}
+
+impl SqliteScraperStorage {
+    #[instrument]
+    pub async fn init<P: AsRef<Path> + Debug>(database_path: P) -> Result<Self, ScraperError> {
+        let mut opts = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(database_path)
+            .create_if_missing(true);
+
+        // TODO: do we want auto_vacuum ?
+
+        opts.disable_statement_logging();
+
+        let connection_pool = match sqlx::SqlitePool::connect_with(opts).await {
+            Ok(db) => db,
+            Err(err) => {
+                error!("Failed to connect to SQLx database: {err}");
+                return Err(err.into());
+            }
+        };
+
+        if let Err(err) = sqlx::migrate!("./sql_migrations")
+            .run(&connection_pool)
+            .await
+        {
+            error!("Failed to initialize SQLx database: {err}");
+            return Err(err.into());
+        }
+
+        info!("Database migration finished!");
+
+        let manager = StorageManager { connection_pool };
+        manager.set_initial_metadata().await?;
+
+        let storage = SqliteScraperStorage { manager };
+
+        Ok(storage)
+    }
+}
+
+#[async_trait]
+impl NyxdScraperStorage for SqliteScraperStorage {
+    type StorageTransaction = SqliteStorageTransaction;
+
+    #[instrument(skip_all)]
+    async fn begin_processing_tx(&self) -> Result<Self::StorageTransaction, ScraperError> {
+        debug!("starting storage tx");
+        self.manager
+            .connection_pool
+            .begin()
+            .await
+            .map(SqliteStorageTransaction)
+            .map_err(|source| ScraperError::StorageTxBeginFailure { source })
+    }
+
+    #[instrument(skip(self))]
+    async fn prune_storage(
+        &self,
+        oldest_to_keep: u32,
+        current_height: u32,
+    ) -> Result<(), ScraperError> {
+        let start = Instant::now();
+
+        let mut tx = self.begin_processing_tx().await?;
+
+        prune_messages(oldest_to_keep.into(), &mut *tx).await?;
+        prune_transactions(oldest_to_keep.into(), &mut *tx).await?;
+        prune_pre_commits(oldest_to_keep.into(), &mut *tx).await?;
+        prune_blocks(oldest_to_keep.into(), &mut *tx).await?;
+        update_last_pruned(current_height.into(), &mut *tx).await?;
+
+        let commit_start = Instant::now();
+        tx.commit().await?;
+        log_db_operation_time("committing pruning tx", commit_start);
+
+        log_db_operation_time("pruning storage", start);
+        Ok(())
+    }
+
+    async fn lowest_block_height(&self) -> Result<Option<i64>, ScraperError> {
+        Ok(self.manager.get_lowest_block().await?)
+    }
+
+    async fn get_first_block_height_after(
+        &self,
+        time: OffsetDateTime,
+    ) -> Result<Option<i64>, ScraperError> {
+        Ok(self.manager.get_first_block_height_after(time).await?)
+    }
+
+    async fn get_last_block_height_before(
+        &self,
+        time: OffsetDateTime,
+    ) -> Result<Option<i64>, ScraperError> {
+        Ok(self.manager.get_last_block_height_before(time).await?)
+    }
+
+    async fn get_signed_between(
+        &self,
+        consensus_address: &str,
+        start_height: i64,
+        end_height: i64,
+    ) -> Result<i32, ScraperError> {
+        Ok(self
+            .manager
+            .get_signed_between(consensus_address, start_height, end_height)
+            .await?)
+    }
+
+    async fn get_precommit(
+        &self,
+        consensus_address: &str,
+        height: i64,
+    ) -> Result<Option<CommitSignature>, ScraperError> {
+        Ok(self
+            .manager
+            .get_precommit(consensus_address, height)
+            .await?)
+    }
+
+    async fn get_block_signers(&self, height: i64) -> Result<Vec<Validator>, ScraperError> {
+        Ok(self.manager.get_block_validators(height).await?)
+    }
+
+    async fn get_all_known_validators(&self) -> Result<Vec<Validator>, ScraperError> {
+        Ok(self.manager.get_validators().await?)
+    }
+
+    async fn get_last_processed_height(&self) -> Result<i64, ScraperError> {
+        Ok(self.manager.get_last_processed_height().await?)
+    }
+
+    async fn get_pruned_height(&self) -> Result<i64, ScraperError> {
+        Ok(self.manager.get_pruned_height().await?)
+    }
+}
+
+#[async_trait]
+impl NyxdScraperTransaction for SqliteStorageTransaction {
+    async fn commit(self) -> Result<(), ScraperError> {
+        self.0
+            .commit()
+            .await
+            .map_err(|source| ScraperError::StorageTxCommitFailure { source })
+    }
+
+    async fn insert_validator(
+        &mut self,
+        consensus_address: String,
+        consensus_pubkey: String,
+    ) -> Result<(), ScraperError> {
+        insert_validator(consensus_address, consensus_pubkey, &mut self.0).await?;
+        Ok(())
+    }
+
+    async fn insert_block(
+        &mut self,
+        height: i64,
+        hash: String,
+        num_txs: u32,
+        total_gas: i64,
+        proposer_address: String,
+        timestamp: OffsetDateTime,
+    ) -> Result<(), ScraperError> {
+        insert_block(
+            height,
+            hash,
+            num_txs,
+            total_gas,
+            proposer_address,
+            timestamp,
+            &mut self.0,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_precommit(
+        &mut self,
+        validator_address: String,
+        height: i64,
+        timestamp: OffsetDateTime,
+        voting_power: i64,
+        proposer_priority: i64,
+    ) -> Result<(), ScraperError> {
+        insert_precommit(
+            validator_address,
+            height,
+            timestamp,
+            voting_power,
+            proposer_priority,
+            &mut self.0,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_transaction(
+        &mut self,
+        hash: String,
+        height: i64,
+        index: i64,
+        success: bool,
+        messages: i64,
+        memo: String,
+        gas_wanted: i64,
+        gas_used: i64,
+        raw_log: String,
+    ) -> Result<(), ScraperError> {
+        insert_transaction(
+            hash,
+            height,
+            index,
+            success,
+            messages,
+            memo,
+            gas_wanted,
+            gas_used,
+            raw_log,
+            &mut self.0,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_message(
+        &mut self,
+        transaction_hash: String,
+        index: i64,
+        typ: String,
+        height: i64,
+    ) -> Result<(), ScraperError> {
+        insert_message(transaction_hash, index, typ, height, &mut self.0).await?;
+        Ok(())
+    }
+
+    async fn update_last_processed(&mut self, height: i64) -> Result<(), ScraperError> {
+        update_last_processed(height, &mut self.0).await?;
+        Ok(())
+    }
+
+    async fn insert_contract_execution(
+        &mut self,
+        execution: &ContractExecution,
+    ) -> Result<(), ScraperError> {
+        insert_contract_execution(execution, &mut self.0).await?;
+        Ok(())
+    }
+
+    async fn insert_delegation_event(
+        &mut self,
+        event: &DelegationEvent,
+    ) -> Result<(), ScraperError> {
+        insert_delegation_event(event, &mut self.0).await?;
+        Ok(())
+    }
+
+    async fn insert_bonding_event(&mut self, event: &BondingEvent) -> Result<(), ScraperError> {
+        insert_bonding_event(event, &mut self.0).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::exercise_storage;
+
+    #[tokio::test]
+    async fn sqlite_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteScraperStorage::init(dir.path().join("scraper.sqlite"))
+            .await
+            .unwrap();
+        exercise_storage(storage).await
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Variants of the key storage functions that keep the private keys encrypted at rest.
+//! The ciphertext is stored in a regular pem file whose tag is the original key tag
+//! prefixed with [`ENCRYPTED_TAG_PREFIX`]. Public keys are always stored in plaintext.
+
+use crate::traits::{PemStorableKey, PemStorableKeyPair};
+use crate::{load_key, read_pem_file, store_key, write_pem_file, KeyPairPath};
+use nym_store_cipher::StoreCipher;
+use std::io;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+pub const ENCRYPTED_TAG_PREFIX: &str = "ENCRYPTED ";
+
+fn encrypted_pem_type<T: PemStorableKey>() -> String {
+    format!("{ENCRYPTED_TAG_PREFIX}{}", T::pem_type())
+}
+
+/// Checks whether the pem file at the provided path contains an encrypted key.
+pub fn is_encrypted_key_file<P: AsRef<Path>>(path: P) -> io::Result<bool> {
+    let pem = read_pem_file(path)?;
+    Ok(pem.tag.starts_with(ENCRYPTED_TAG_PREFIX))
+}
+
+pub fn load_encrypted_keypair<T>(paths: &KeyPairPath, cipher: &StoreCipher) -> io::Result<T>
+where
+    T: PemStorableKeyPair,
+{
+    let private: T::PrivatePemKey = load_encrypted_key(&paths.private_key_path, cipher)?;
+    let public: T::PublicPemKey = load_key(&paths.public_key_path)?;
+    Ok(T::from_keys(private, public))
+}
+
+pub fn store_encrypted_keypair<T>(
+    keypair: &T,
+    paths: &KeyPairPath,
+    cipher: &StoreCipher,
+) -> io::Result<()>
+where
+    T: PemStorableKeyPair,
+{
+    store_key(keypair.public_key(), &paths.public_key_path)?;
+    store_encrypted_key(keypair.private_key(), &paths.private_key_path, cipher)
+}
+
+pub fn load_encrypted_key<T, P>(path: P, cipher: &StoreCipher) -> io::Result<T>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    let key_pem = read_pem_file(path)?;
+
+    let expected_tag = encrypted_pem_type::<T>();
+    if key_pem.tag != expected_tag {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "unexpected key pem tag. Got '{}', expected: '{expected_tag}'",
+                key_pem.tag,
+            ),
+        ));
+    }
+
+    // the decrypted plaintext is the raw key material, so make sure to not leave it lying around
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt_blob(&key_pem.contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?,
+    );
+
+    T::from_bytes(&plaintext)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+}
+
+pub fn store_encrypted_key<T, P>(key: &T, path: P, cipher: &StoreCipher) -> io::Result<()>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    let plaintext = Zeroizing::new(key.to_bytes());
+    let ciphertext = cipher
+        .encrypt_to_blob(&plaintext)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+    write_pem_file(path, ciphertext, &encrypted_pem_type::<T>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_store_cipher::{KdfInfo, Params};
+    use std::convert::Infallible;
+
+    #[derive(Debug, PartialEq)]
+    struct DummyKey(Vec<u8>);
+
+    impl PemStorableKey for DummyKey {
+        type Error = Infallible;
+
+        fn pem_type() -> &'static str {
+            "DUMMY KEY"
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.0.clone()
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+            Ok(DummyKey(bytes.to_vec()))
+        }
+    }
+
+    // use cheap kdf parameters so that the tests wouldn't take forever
+    fn test_cipher(passphrase: &[u8], kdf_salt: [u8; 16]) -> StoreCipher {
+        let kdf_info = KdfInfo::Argon2 {
+            params: Params::new(8, 1, 1, None).unwrap(),
+            algorithm: Default::default(),
+            version: Default::default(),
+            kdf_salt,
+        };
+        StoreCipher::new(passphrase, kdf_info).unwrap()
+    }
+
+    #[test]
+    fn encrypted_key_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        let cipher = test_cipher(b"my-secret-passphrase", [42u8; 16]);
+        let key = DummyKey(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        store_encrypted_key(&key, &path, &cipher).unwrap();
+        assert!(is_encrypted_key_file(&path).unwrap());
+
+        // the key material is not stored in plaintext
+        let pem = read_pem_file(&path).unwrap();
+        assert_eq!(pem.tag, "ENCRYPTED DUMMY KEY");
+        assert_ne!(pem.contents, key.0);
+
+        let loaded: DummyKey = load_encrypted_key(&path, &cipher).unwrap();
+        assert_eq!(loaded, key);
+    }
+
+    #[test]
+    fn plaintext_and_encrypted_keys_are_distinguished() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        let cipher = test_cipher(b"my-secret-passphrase", [42u8; 16]);
+        let key = DummyKey(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        store_key(&key, &path).unwrap();
+        assert!(!is_encrypted_key_file(&path).unwrap());
+        assert!(load_encrypted_key::<DummyKey, _>(&path, &cipher).is_err());
+
+        store_encrypted_key(&key, &path, &cipher).unwrap();
+        assert!(load_key::<DummyKey, _>(&path).is_err());
+    }
+
+    #[test]
+    fn loading_with_wrong_passphrase_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        let cipher = test_cipher(b"my-secret-passphrase", [42u8; 16]);
+        let key = DummyKey(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        store_encrypted_key(&key, &path, &cipher).unwrap();
+
+        let wrong_cipher = test_cipher(b"not-my-passphrase", [42u8; 16]);
+        let err = load_encrypted_key::<DummyKey, _>(&path, &wrong_cipher).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn loading_tampered_key_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        let cipher = test_cipher(b"my-secret-passphrase", [42u8; 16]);
+        let key = DummyKey(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        store_encrypted_key(&key, &path, &cipher).unwrap();
+
+        let mut pem = read_pem_file(&path).unwrap();
+        *pem.contents.last_mut().unwrap() ^= 1;
+        write_pem_file(&path, pem.contents, &pem.tag).unwrap();
+
+        let err = load_encrypted_key::<DummyKey, _>(&path, &cipher).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -7,6 +7,8 @@ use std::fs::File;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "encryption")]
+pub mod encrypted;
 pub mod traits;
 
 #[derive(Debug, Default)]
@@ -75,14 +77,18 @@ where
     write_pem_file(path, key.to_bytes(), T::pem_type())
 }
 
-fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<Pem> {
+pub(crate) fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<Pem> {
     let mut pem_bytes = File::open(filepath)?;
     let mut buf = Vec::new();
     pem_bytes.read_to_end(&mut buf)?;
     pem::parse(&buf).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
 }
 
-fn write_pem_file<P: AsRef<Path>>(filepath: P, data: Vec<u8>, tag: &str) -> io::Result<()> {
+pub(crate) fn write_pem_file<P: AsRef<Path>>(
+    filepath: P,
+    data: Vec<u8>,
+    tag: &str,
+) -> io::Result<()> {
     // ensure the whole directory structure exists
     if let Some(parent_dir) = filepath.as_ref().parent() {
         std::fs::create_dir_all(parent_dir)?;
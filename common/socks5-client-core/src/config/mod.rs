@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::str::FromStr;
+use std::time::Duration;
 
 pub mod old_config_v1_1_20_2;
 pub mod old_config_v1_1_30;
@@ -19,6 +20,8 @@ pub use nym_socks5_requests::Socks5ProtocolVersion;
 
 const DEFAULT_CONNECTION_START_SURBS: u32 = 20;
 const DEFAULT_PER_REQUEST_SURBS: u32 = 3;
+const DEFAULT_UDP_ASSOCIATION_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+const DEFAULT_BIND_TIMEOUT: Duration = Duration::from_secs(180);
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -168,6 +171,16 @@ pub struct Socks5Debug {
 
     /// Number of reply SURBs attached to each `Request::Send` message.
     pub per_request_surbs: u32,
+
+    /// Specifies how long an UDP association can remain without any traffic
+    /// going through it before it gets closed.
+    #[serde(with = "humantime_serde")]
+    pub udp_association_idle_timeout: Duration,
+
+    /// Specifies how long a BIND request can wait for the inbound connection
+    /// to be accepted by the service provider before it gets abandoned.
+    #[serde(with = "humantime_serde")]
+    pub bind_timeout: Duration,
 }
 
 impl Default for Socks5Debug {
@@ -175,6 +188,8 @@ impl Default for Socks5Debug {
         Socks5Debug {
             connection_start_surbs: DEFAULT_CONNECTION_START_SURBS,
             per_request_surbs: DEFAULT_PER_REQUEST_SURBS,
+            udp_association_idle_timeout: DEFAULT_UDP_ASSOCIATION_IDLE_TIMEOUT,
+            bind_timeout: DEFAULT_BIND_TIMEOUT,
         }
     }
 }
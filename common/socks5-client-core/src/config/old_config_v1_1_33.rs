@@ -93,6 +93,7 @@ impl From<Socks5DebugV1_1_33> for Socks5Debug {
         Socks5Debug {
             connection_start_surbs: value.connection_start_surbs,
             per_request_surbs: value.per_request_surbs,
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,61 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::channel::mpsc;
+use nym_socks5_requests::{BindResponse, ConnectionId};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Progress of a BIND request as reported by the service provider.
+#[derive(Debug)]
+pub(crate) enum BindUpdate {
+    Response(BindResponse),
+    Failure(String),
+}
+
+pub(crate) type BindUpdateSender = mpsc::UnboundedSender<BindUpdate>;
+pub(crate) type BindUpdateReceiver = mpsc::UnboundedReceiver<BindUpdate>;
+
+/// All BIND requests still waiting for their inbound connection, so that the replies received
+/// from the mixnet could be forwarded to the appropriate local client.
+#[derive(Clone, Default)]
+pub(crate) struct PendingBinds {
+    inner: Arc<RwLock<HashMap<ConnectionId, BindUpdateSender>>>,
+}
+
+impl PendingBinds {
+    pub(crate) fn new() -> Self {
+        Default::default()
+    }
+
+    pub(crate) fn insert(&self, connection_id: ConnectionId, sender: BindUpdateSender) {
+        self.inner
+            .write()
+            .expect("pending binds lock got poisoned")
+            .insert(connection_id, sender);
+    }
+
+    pub(crate) fn remove(&self, connection_id: ConnectionId) {
+        self.inner
+            .write()
+            .expect("pending binds lock got poisoned")
+            .remove(&connection_id);
+    }
+
+    pub(crate) fn contains(&self, connection_id: ConnectionId) -> bool {
+        self.inner
+            .read()
+            .expect("pending binds lock got poisoned")
+            .contains_key(&connection_id)
+    }
+
+    /// Forwards the update to the client waiting on the BIND request.
+    /// Returns `false` if the request is no longer pending.
+    pub(crate) fn forward(&self, connection_id: ConnectionId, update: BindUpdate) -> bool {
+        let guard = self.inner.read().expect("pending binds lock got poisoned");
+        match guard.get(&connection_id) {
+            Some(sender) => sender.unbounded_send(update).is_ok(),
+            None => false,
+        }
+    }
+}
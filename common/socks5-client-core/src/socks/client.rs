@@ -1,12 +1,16 @@
 #![forbid(unsafe_code)]
 
 use super::authentication::{AuthenticationMethods, Authenticator, User};
+use super::bind::{BindUpdate, BindUpdateReceiver, PendingBinds};
 use super::request::{SocksCommand, SocksRequest};
 use super::types::{ResponseCodeV4, ResponseCodeV5, SocksProxyError};
+use super::udp::{UdpAssociation, UdpAssociations};
+use super::utils::encode_socket_address;
 use super::{SocksVersion, RESERVED, SOCKS4_VERSION, SOCKS5_VERSION};
 use crate::config;
 use futures::channel::mpsc;
 use futures::task::{Context, Poll};
+use futures::StreamExt;
 use log::*;
 use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
 use nym_service_providers_common::interface::{ProviderInterfaceVersion, RequestVersion};
@@ -15,7 +19,8 @@ use nym_socks5_proxy_helpers::connection_controller::{
 };
 use nym_socks5_proxy_helpers::proxy_runner::ProxyRunner;
 use nym_socks5_requests::{
-    ConnectionId, RemoteAddress, Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request,
+    BindStatus, ConnectionId, RemoteAddress, Socks5ProtocolVersion, Socks5ProviderRequest,
+    Socks5Request,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::params::PacketSize;
@@ -27,8 +32,10 @@ use rand::RngCore;
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
+use std::time::Duration;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::time::{timeout_at, Instant};
 
 #[pin_project(project = StateProject)]
 enum StreamState {
@@ -69,6 +76,17 @@ impl StreamState {
         }
     }
 
+    /// Returns the local address that this stream is bound to.
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            StreamState::RunningProxy => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "stream is being used to run the proxy",
+            )),
+            StreamState::Available(ref stream) => stream.local_addr(),
+        }
+    }
+
     async fn shutdown(&mut self) -> io::Result<()> {
         // shutdown should only be called if proxy is not being run. If it is, there's some bug
         // somewhere
@@ -140,6 +158,8 @@ pub(crate) struct Config {
     use_surbs_for_responses: bool,
     connection_start_surbs: u32,
     per_request_surbs: u32,
+    udp_association_idle_timeout: Duration,
+    bind_timeout: Duration,
 }
 
 impl Config {
@@ -157,6 +177,8 @@ impl Config {
             use_surbs_for_responses,
             connection_start_surbs: debug_config.connection_start_surbs,
             per_request_surbs: debug_config.per_request_surbs,
+            udp_association_idle_timeout: debug_config.udp_association_idle_timeout,
+            bind_timeout: debug_config.bind_timeout,
         }
     }
 
@@ -184,6 +206,8 @@ pub(crate) struct SocksClient {
     service_provider: Recipient,
     self_address: Recipient,
     started_proxy: bool,
+    udp_associations: UdpAssociations,
+    pending_binds: PendingBinds,
     lane_queue_lengths: LaneQueueLengths,
     shutdown_listener: TaskClient,
     packet_type: Option<PacketType>,
@@ -213,6 +237,8 @@ impl SocksClient {
         service_provider: &Recipient,
         controller_sender: ControllerSender,
         self_address: &Recipient,
+        udp_associations: UdpAssociations,
+        pending_binds: PendingBinds,
         lane_queue_lengths: LaneQueueLengths,
         mut shutdown_listener: TaskClient,
         packet_type: Option<PacketType>,
@@ -234,6 +260,8 @@ impl SocksClient {
             service_provider: *service_provider,
             self_address: *self_address,
             started_proxy: false,
+            udp_associations,
+            pending_binds,
             lane_queue_lengths,
             shutdown_listener,
             packet_type,
@@ -393,10 +421,51 @@ impl SocksClient {
         }
     }
 
+    async fn send_bind_to_mixnet(&mut self, remote_address: RemoteAddress) {
+        let anonymous = self.config.use_surbs_for_responses;
+        let req = Socks5Request::new_bind(
+            self.config.socks5_protocol_version,
+            self.connection_id,
+            remote_address,
+            (!anonymous).then_some(self.self_address),
+        );
+        let msg =
+            Socks5ProviderRequest::new_provider_data(self.config.provider_interface_version, req);
+
+        let lane = TransmissionLane::ConnectionId(self.connection_id);
+        let input_message = if anonymous {
+            InputMessage::new_anonymous(
+                self.service_provider,
+                msg.into_bytes(),
+                self.config.connection_start_surbs,
+                lane,
+                self.packet_type,
+            )
+        } else {
+            InputMessage::new_regular(
+                self.service_provider,
+                msg.into_bytes(),
+                lane,
+                self.packet_type,
+            )
+        };
+        self.input_sender
+            .send(input_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
     async fn run_proxy(&mut self, conn_receiver: ConnectionReceiver, remote_proxy_target: String) {
         self.send_connect_to_mixnet(remote_proxy_target.clone())
             .await;
+        self.relay_proxy(conn_receiver, remote_proxy_target).await
+    }
 
+    async fn relay_proxy(
+        &mut self,
+        conn_receiver: ConnectionReceiver,
+        remote_proxy_target: String,
+    ) {
         let stream = self.stream.run_proxy();
         let peer_addr = match stream.peer_addr() {
             Ok(peer_addr) => peer_addr,
@@ -459,6 +528,184 @@ impl SocksClient {
         self.stream.finish_proxy(stream)
     }
 
+    /// Waits for both of the replies to the BIND request, i.e. the one informing about the socket
+    /// being bound and the one informing about the inbound connection being accepted,
+    /// and forwards them to the local client.
+    /// Returns the address of the connected peer.
+    async fn wait_for_bind(
+        &mut self,
+        updates: &mut BindUpdateReceiver,
+    ) -> Result<RemoteAddress, SocksProxyError> {
+        let timeout = self.config.bind_timeout;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let update = tokio::select! {
+                biased;
+                _ = self.shutdown_listener.recv() => {
+                    return Err(SocksProxyError::BindFailure {
+                        message: "received shutdown".to_string(),
+                    })
+                }
+                update = timeout_at(deadline, updates.next()) => update
+                    .map_err(|_| SocksProxyError::BindTimeout { timeout })?,
+            };
+
+            let response = match update {
+                Some(BindUpdate::Response(response)) => response,
+                Some(BindUpdate::Failure(message)) => {
+                    return Err(SocksProxyError::BindFailure { message })
+                }
+                None => {
+                    return Err(SocksProxyError::BindFailure {
+                        message: "the bind request has been abandoned".to_string(),
+                    })
+                }
+            };
+
+            let address = response.address.parse::<SocketAddr>().map_err(|_| {
+                SocksProxyError::MalformedBindAddress {
+                    address: response.address.clone(),
+                }
+            })?;
+            self.acknowledge_socks5_with_address(address).await?;
+
+            match response.status {
+                BindStatus::Listening => {
+                    info!(
+                        "The service provider is listening on {address} (id: {})",
+                        self.connection_id
+                    )
+                }
+                BindStatus::Accepted => return Ok(response.address),
+            }
+        }
+    }
+
+    /// Asks the service provider to open a listening socket and, once the expected peer
+    /// connects to it, relays the inbound connection as a normal proxied stream.
+    async fn run_bind(
+        &mut self,
+        conn_receiver: ConnectionReceiver,
+        remote_address: RemoteAddress,
+    ) -> Result<(), SocksProxyError> {
+        let connection_id = self.connection_id;
+        let (update_sender, mut update_receiver) = mpsc::unbounded();
+        self.pending_binds.insert(connection_id, update_sender);
+
+        self.send_bind_to_mixnet(remote_address.clone()).await;
+        let result = self.wait_for_bind(&mut update_receiver).await;
+        self.pending_binds.remove(connection_id);
+        let peer_address = result?;
+
+        info!("Starting proxy for inbound connection from {peer_address} (id: {connection_id})");
+        self.relay_proxy(conn_receiver, peer_address.clone()).await;
+        info!("Proxy for inbound connection from {peer_address} is finished (id: {connection_id})");
+        Ok(())
+    }
+
+    /// Relays datagrams between the local application and the service provider for as long as
+    /// the control connection (i.e. the one that requested the association) remains open.
+    async fn run_udp_association(&mut self, request: &SocksRequest) -> Result<(), SocksProxyError> {
+        let client_ip = self
+            .stream
+            .peer_addr()
+            .map_err(|source| SocksProxyError::PeerAddrExtractionFailure { source })?
+            .ip();
+        let local_ip = self
+            .stream
+            .local_addr()
+            .map_err(|source| SocksProxyError::PeerAddrExtractionFailure { source })?
+            .ip();
+
+        // the client might tell us upfront from which address it's going to send its datagrams
+        let client_address = request
+            .address_string()
+            .parse::<SocketAddr>()
+            .ok()
+            .filter(|address| !address.ip().is_unspecified() && address.port() != 0);
+
+        let socket = UdpSocket::bind(SocketAddr::new(local_ip, 0))
+            .await
+            .map_err(|source| SocksProxyError::UdpBindFailure { source })?;
+        let bound_address = socket
+            .local_addr()
+            .map_err(|source| SocksProxyError::UdpBindFailure { source })?;
+
+        self.acknowledge_socks5_with_address(bound_address).await?;
+
+        let association_id = self.connection_id;
+        let (datagram_sender, datagram_receiver) = mpsc::unbounded();
+        self.udp_associations
+            .insert(association_id, datagram_sender);
+
+        let anonymous = self.config.use_surbs_for_responses;
+        let connection_start_surbs = self.config.connection_start_surbs;
+        let per_request_surbs = self.config.per_request_surbs;
+        let request_version = self.config.request_version();
+        let recipient = self.service_provider;
+        let return_address = (!anonymous).then_some(self.self_address);
+        let packet_type = self.packet_type;
+        let mut sent_datagrams = 0u64;
+
+        info!("Starting UDP association on {bound_address} (id: {association_id})");
+        UdpAssociation::new(
+            association_id,
+            socket,
+            client_ip,
+            client_address,
+            self.config.udp_association_idle_timeout,
+            self.shutdown_listener.clone(),
+        )
+        .run(
+            &mut self.stream,
+            datagram_receiver,
+            self.input_sender.clone(),
+            move |remote_address, data| {
+                let lane = TransmissionLane::ConnectionId(association_id);
+                let provider_request = Socks5Request::new_datagram(
+                    request_version.provider_protocol,
+                    association_id,
+                    remote_address,
+                    return_address,
+                    data,
+                );
+                let provider_message = Socks5ProviderRequest::new_provider_data(
+                    request_version.provider_interface,
+                    provider_request,
+                );
+                if anonymous {
+                    // make sure the service provider has enough surbs to get the association going
+                    let surbs = if sent_datagrams == 0 {
+                        connection_start_surbs
+                    } else {
+                        per_request_surbs
+                    };
+                    sent_datagrams += 1;
+                    InputMessage::new_anonymous(
+                        recipient,
+                        provider_message.into_bytes(),
+                        surbs,
+                        lane,
+                        packet_type,
+                    )
+                } else {
+                    InputMessage::new_regular(
+                        recipient,
+                        provider_message.into_bytes(),
+                        lane,
+                        packet_type,
+                    )
+                }
+            },
+        )
+        .await;
+        info!("UDP association {association_id} is finished");
+
+        self.udp_associations.remove(association_id);
+        Ok(())
+    }
+
     /// Handles a client request.
     async fn handle_request(&mut self) -> Result<(), SocksProxyError> {
         debug!("Handling CONNECT Command");
@@ -507,8 +754,32 @@ impl SocksClient {
                 );
             }
 
-            SocksCommand::Bind => return Err(SocksProxyError::BindNotSupported), // not handled
-            SocksCommand::UdpAssociate => return Err(SocksProxyError::UdpNotSupported),
+            SocksCommand::Bind => {
+                // the two-reply BIND flow is only implemented for SOCKS5
+                if *version != SocksVersion::V5 {
+                    return Err(ResponseCodeV5::CommandNotSupported.into());
+                }
+                trace!("Binding for inbound connection from: {remote_address:?}");
+
+                // register the connection upfront so that any data sent by the peer
+                // right after it has been accepted would not get lost
+                self.started_proxy = true;
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::Insert {
+                        connection_id: self.connection_id,
+                        connection_sender: mix_sender,
+                    })
+                    .unwrap();
+
+                self.run_bind(mix_receiver, remote_address).await?;
+            }
+            SocksCommand::UdpAssociate => {
+                // UDP is only supported by SOCKS5
+                if *version != SocksVersion::V5 {
+                    return Err(ResponseCodeV5::CommandNotSupported.into());
+                }
+                self.run_udp_association(&request).await?;
+            }
         };
 
         Ok(())
@@ -534,6 +805,22 @@ impl SocksClient {
             .unwrap();
     }
 
+    /// Writes a Socks5 header back to the requesting client's TCP stream,
+    /// including the address the client should use for the request it made.
+    async fn acknowledge_socks5_with_address(
+        &mut self,
+        address: SocketAddr,
+    ) -> Result<(), SocksProxyError> {
+        let response: Vec<u8> = [SOCKS5_VERSION, ResponseCodeV5::Success as u8, RESERVED]
+            .into_iter()
+            .chain(encode_socket_address(address))
+            .collect();
+        self.stream
+            .write_all(&response)
+            .await
+            .map_err(|source| SocksProxyError::SocketWriteError { source })
+    }
+
     /// Writes a Socks4 header back to the requesting client's TCP stream,
     async fn acknowledge_socks4(&mut self) {
         self.stream
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Socks5ClientCoreError;
+use crate::socks::bind::{BindUpdate, PendingBinds};
+use crate::socks::udp::UdpAssociations;
 use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
@@ -19,6 +21,8 @@ pub(crate) struct MixnetResponseListener {
     buffer_requester: ReceivedBufferRequestSender,
     mix_response_receiver: ReconstructedMessagesReceiver,
     controller_sender: ControllerSender,
+    udp_associations: UdpAssociations,
+    pending_binds: PendingBinds,
     shutdown: TaskClient,
 }
 
@@ -41,6 +45,8 @@ impl MixnetResponseListener {
     pub(crate) fn new(
         buffer_requester: ReceivedBufferRequestSender,
         controller_sender: ControllerSender,
+        udp_associations: UdpAssociations,
+        pending_binds: PendingBinds,
         shutdown: TaskClient,
     ) -> Self {
         let (mix_response_sender, mix_response_receiver) = mpsc::unbounded();
@@ -52,6 +58,8 @@ impl MixnetResponseListener {
             buffer_requester,
             mix_response_receiver,
             controller_sender,
+            udp_associations,
+            pending_binds,
             shutdown,
         }
     }
@@ -73,6 +81,26 @@ impl MixnetResponseListener {
         provider_response: Socks5Response,
     ) -> Result<(), Socks5ClientCoreError> {
         match provider_response.content {
+            Socks5ResponseContent::ConnectionError(err_response)
+                if self.udp_associations.contains(err_response.connection_id) =>
+            {
+                // a single rejected datagram shouldn't tear down the whole association
+                warn!(
+                    "Network requester failed to relay datagram on association {}: {}",
+                    err_response.connection_id, err_response.network_requester_error
+                );
+                Ok(())
+            }
+            Socks5ResponseContent::ConnectionError(err_response)
+                if self.pending_binds.contains(err_response.connection_id) =>
+            {
+                // the failure is going to be reported to the local client waiting on the BIND
+                self.pending_binds.forward(
+                    err_response.connection_id,
+                    BindUpdate::Failure(err_response.network_requester_error),
+                );
+                Ok(())
+            }
             Socks5ResponseContent::ConnectionError(err_response) => {
                 error!(
                     "Network requester failed on connection id {} with error: {}",
@@ -86,6 +114,23 @@ impl MixnetResponseListener {
                     .unwrap();
                 Ok(())
             }
+            Socks5ResponseContent::Datagram(datagram) => {
+                let association_id = datagram.association_id;
+                if !self.udp_associations.forward(datagram) {
+                    debug!("received a datagram for a closed association {association_id}");
+                }
+                Ok(())
+            }
+            Socks5ResponseContent::Bind(response) => {
+                let connection_id = response.connection_id;
+                if !self
+                    .pending_binds
+                    .forward(connection_id, BindUpdate::Response(response))
+                {
+                    debug!("received a bind response for a closed connection {connection_id}");
+                }
+                Ok(())
+            }
             Socks5ResponseContent::Query(response) => {
                 error!("received a query response which we don't know how to handle yet!");
                 error!("got: {:?}", response);
@@ -3,11 +3,13 @@
 use self::types::SocksProxyError;
 
 pub mod authentication;
+pub(crate) mod bind;
 pub(crate) mod client;
 pub(crate) mod mixnet_responses;
 mod request;
 pub mod server;
 pub mod types;
+pub(crate) mod udp;
 pub mod utils;
 
 /// Version of socks
@@ -1,7 +1,8 @@
 use crate::error::Socks5ClientCoreError;
 
 use super::{
-    authentication::Authenticator, client::SocksClient, mixnet_responses::MixnetResponseListener,
+    authentication::Authenticator, bind::PendingBinds, client::SocksClient,
+    mixnet_responses::MixnetResponseListener, udp::UdpAssociations,
 };
 use crate::socks::client;
 use log::*;
@@ -78,10 +79,18 @@ impl NymSocksServer {
             active_streams_controller.run().await;
         });
 
+        // all active UDP associations
+        let udp_associations = UdpAssociations::new();
+
+        // all BIND requests still waiting for their inbound connections
+        let pending_binds = PendingBinds::new();
+
         // listener for mix messages
         let mut mixnet_response_listener = MixnetResponseListener::new(
             buffer_requester,
             controller_sender.clone(),
+            udp_associations.clone(),
+            pending_binds.clone(),
             self.shutdown.clone(),
         );
         tokio::spawn(async move {
@@ -104,6 +113,8 @@ impl NymSocksServer {
                         &self.service_provider,
                         controller_sender.clone(),
                         &self.self_address,
+                        udp_associations.clone(),
+                        pending_binds.clone(),
                         self.lane_queue_lengths.clone(),
                         self.shutdown.clone(),
                         Some(self.packet_type)
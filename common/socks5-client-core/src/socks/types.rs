@@ -1,5 +1,6 @@
 use nym_socks5_requests::Socks5RequestError;
 use std::string::FromUtf8Error;
+use std::time::Duration;
 use thiserror::Error;
 
 /// SOCKS4 Response codes
@@ -84,11 +85,26 @@ pub enum SocksProxyError {
         source: Socks5RequestError,
     },
 
-    #[error("SOCKS5 UDP not (yet) supported")]
-    UdpNotSupported,
+    #[error("failed to bind the socket for the UDP association: {source}")]
+    UdpBindFailure {
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("received a datagram with a malformed SOCKS5 UDP header")]
+    MalformedUdpHeader,
+
+    #[error("fragmented SOCKS5 UDP datagrams are not supported")]
+    UnsupportedUdpFragmentation,
+
+    #[error("the service provider failed to handle the BIND request: {message}")]
+    BindFailure { message: String },
+
+    #[error("did not receive the inbound connection for the BIND request within {timeout:?}")]
+    BindTimeout { timeout: Duration },
 
-    #[error("SOCKS5 BIND not (yet) supported")]
-    BindNotSupported,
+    #[error("received a malformed bind address '{address}' from the service provider")]
+    MalformedBindAddress { address: String },
 }
 
 /// DST.addr variant types
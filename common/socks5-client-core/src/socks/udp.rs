@@ -0,0 +1,352 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::types::{AddrType, ResponseCodeV5, SocksProxyError};
+use super::utils::{self as socks_utils, encode_socket_address};
+use super::RESERVED;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use log::*;
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_socks5_requests::{ConnectionId, DatagramResponse, RemoteAddress};
+use nym_task::TaskClient;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::net::UdpSocket;
+use tokio::time::{sleep_until, Instant};
+
+// the maximum size of an UDP datagram
+const MAX_DATAGRAM_SIZE: usize = 65535;
+
+pub(crate) type DatagramSender = mpsc::UnboundedSender<DatagramResponse>;
+pub(crate) type DatagramReceiver = mpsc::UnboundedReceiver<DatagramResponse>;
+
+/// All currently active UDP associations, so that the datagrams received from the mixnet
+/// could be forwarded to the appropriate local socket.
+#[derive(Clone, Default)]
+pub(crate) struct UdpAssociations {
+    inner: Arc<RwLock<HashMap<ConnectionId, DatagramSender>>>,
+}
+
+impl UdpAssociations {
+    pub(crate) fn new() -> Self {
+        Default::default()
+    }
+
+    pub(crate) fn insert(&self, association_id: ConnectionId, sender: DatagramSender) {
+        self.inner
+            .write()
+            .expect("udp associations lock got poisoned")
+            .insert(association_id, sender);
+    }
+
+    pub(crate) fn remove(&self, association_id: ConnectionId) {
+        self.inner
+            .write()
+            .expect("udp associations lock got poisoned")
+            .remove(&association_id);
+    }
+
+    pub(crate) fn contains(&self, association_id: ConnectionId) -> bool {
+        self.inner
+            .read()
+            .expect("udp associations lock got poisoned")
+            .contains_key(&association_id)
+    }
+
+    /// Forwards the received datagram to its association.
+    /// Returns `false` if the association no longer exists.
+    pub(crate) fn forward(&self, datagram: DatagramResponse) -> bool {
+        let guard = self
+            .inner
+            .read()
+            .expect("udp associations lock got poisoned");
+        match guard.get(&datagram.association_id) {
+            Some(sender) => sender.unbounded_send(datagram).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// A single datagram received from the local application, parsed from the SOCKS5 UDP request header:
+///
+/// +----+------+------+----------+----------+----------+
+/// |RSV | FRAG | ATYP | DST.ADDR | DST.PORT |   DATA   |
+/// +----+------+------+----------+----------+----------+
+/// | 2  |  1   |  1   | Variable |    2     | Variable |
+/// +----+------+------+----------+----------+----------+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SocksDatagram {
+    pub(crate) remote_address: RemoteAddress,
+    pub(crate) data: Vec<u8>,
+}
+
+impl SocksDatagram {
+    pub(crate) fn try_from_bytes(b: &[u8]) -> Result<Self, SocksProxyError> {
+        if b.len() < 4 {
+            return Err(SocksProxyError::MalformedUdpHeader);
+        }
+
+        // we don't support fragmentation, as allowed by the RFC
+        if b[2] != 0 {
+            return Err(SocksProxyError::UnsupportedUdpFragmentation);
+        }
+
+        let Some(addr_type) = AddrType::from(b[3] as usize) else {
+            return Err(ResponseCodeV5::AddrTypeNotSupported.into());
+        };
+
+        let (addr, addr_end) = match addr_type {
+            AddrType::V4 => (b.get(4..8), 8),
+            AddrType::V6 => (b.get(4..20), 20),
+            AddrType::Domain => {
+                let domain_len = *b.get(4).ok_or(SocksProxyError::MalformedUdpHeader)? as usize;
+                (b.get(5..5 + domain_len), 5 + domain_len)
+            }
+        };
+        let addr = addr.ok_or(SocksProxyError::MalformedUdpHeader)?;
+        let port = b
+            .get(addr_end..addr_end + 2)
+            .ok_or(SocksProxyError::MalformedUdpHeader)?;
+        let port = u16::from_be_bytes([port[0], port[1]]);
+
+        let address = socks_utils::pretty_print_addr(&addr_type, addr);
+        let remote_address = if addr_type == AddrType::V6 {
+            format!("[{address}]:{port}")
+        } else {
+            format!("{address}:{port}")
+        };
+
+        Ok(SocksDatagram {
+            remote_address,
+            data: b[addr_end + 2..].to_vec(),
+        })
+    }
+}
+
+/// Attach the SOCKS5 UDP header to the datagram received from the specified remote.
+fn encapsulate_datagram(source: SocketAddr, data: Vec<u8>) -> Vec<u8> {
+    [RESERVED, RESERVED, 0]
+        .into_iter()
+        .chain(encode_socket_address(source))
+        .chain(data)
+        .collect()
+}
+
+/// Local end of a SOCKS5 UDP association, relaying datagrams between the local application
+/// and the service provider.
+pub(crate) struct UdpAssociation {
+    association_id: ConnectionId,
+    socket: UdpSocket,
+
+    // datagrams are only accepted from the host that has established the association
+    client_ip: IpAddr,
+
+    // the address the local application sends its datagrams from. it is learned from the
+    // first received datagram if it wasn't explicitly specified in the request
+    client_address: Option<SocketAddr>,
+    idle_timeout: Duration,
+    shutdown: TaskClient,
+}
+
+impl UdpAssociation {
+    pub(crate) fn new(
+        association_id: ConnectionId,
+        socket: UdpSocket,
+        client_ip: IpAddr,
+        client_address: Option<SocketAddr>,
+        idle_timeout: Duration,
+        shutdown: TaskClient,
+    ) -> Self {
+        UdpAssociation {
+            association_id,
+            socket,
+            client_ip,
+            client_address,
+            idle_timeout,
+            shutdown,
+        }
+    }
+
+    fn is_valid_sender(&mut self, sender: SocketAddr) -> bool {
+        if sender.ip() != self.client_ip {
+            return false;
+        }
+        match self.client_address {
+            Some(client_address) => client_address == sender,
+            None => {
+                self.client_address = Some(sender);
+                true
+            }
+        }
+    }
+
+    async fn handle_local_datagram<F>(
+        &mut self,
+        sender: SocketAddr,
+        raw: &[u8],
+        input_sender: &InputMessageSender,
+        make_request: &mut F,
+    ) where
+        F: FnMut(RemoteAddress, Vec<u8>) -> InputMessage,
+    {
+        if !self.is_valid_sender(sender) {
+            debug!(
+                "received a datagram from {sender} which is not part of the association {}",
+                self.association_id
+            );
+            return;
+        }
+
+        let datagram = match SocksDatagram::try_from_bytes(raw) {
+            Ok(datagram) => datagram,
+            Err(err) => {
+                debug!("dropping invalid datagram from {sender}: {err}");
+                return;
+            }
+        };
+
+        trace!(
+            "sending {} bytes to {} on association {}",
+            datagram.data.len(),
+            datagram.remote_address,
+            self.association_id
+        );
+        let input_message = make_request(datagram.remote_address, datagram.data);
+        input_sender
+            .send(input_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    async fn handle_mixnet_datagram(&self, datagram: DatagramResponse) {
+        let Some(client_address) = self.client_address else {
+            debug!(
+                "received a datagram before the local application has sent anything - dropping it"
+            );
+            return;
+        };
+
+        let source: SocketAddr = match datagram.source_addr.parse() {
+            Ok(source) => source,
+            Err(err) => {
+                warn!(
+                    "received a datagram with malformed source address '{}': {err}",
+                    datagram.source_addr
+                );
+                return;
+            }
+        };
+
+        let encapsulated = encapsulate_datagram(source, datagram.data);
+        if let Err(err) = self.socket.send_to(&encapsulated, client_address).await {
+            warn!("failed to forward the datagram to {client_address}: {err}")
+        }
+    }
+
+    /// Relays the datagrams until either the control connection is closed, the association
+    /// has been idle for too long or a shutdown signal is received.
+    pub(crate) async fn run<C, F>(
+        mut self,
+        control_connection: &mut C,
+        mut mix_receiver: DatagramReceiver,
+        input_sender: InputMessageSender,
+        mut make_request: F,
+    ) where
+        C: AsyncRead + Unpin,
+        F: FnMut(RemoteAddress, Vec<u8>) -> InputMessage,
+    {
+        let mut control_buf = [0u8; 64];
+        let mut datagram_buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut idle_deadline = Instant::now() + self.idle_timeout;
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("UdpAssociation {}: received shutdown", self.association_id);
+                }
+                control = control_connection.read(&mut control_buf) => match control {
+                    Ok(0) | Err(_) => {
+                        debug!("the control connection of association {} got closed", self.association_id);
+                        break
+                    }
+                    // the RFC does not define any messages sent on the control connection
+                    Ok(_) => {}
+                },
+                received = self.socket.recv_from(&mut datagram_buf) => match received {
+                    Ok((n, sender)) => {
+                        idle_deadline = Instant::now() + self.idle_timeout;
+                        self.handle_local_datagram(sender, &datagram_buf[..n], &input_sender, &mut make_request).await
+                    }
+                    Err(err) => {
+                        warn!("failed to receive a datagram on association {}: {err}", self.association_id);
+                        break
+                    }
+                },
+                datagram = mix_receiver.next() => match datagram {
+                    Some(datagram) => {
+                        idle_deadline = Instant::now() + self.idle_timeout;
+                        self.handle_mixnet_datagram(datagram).await
+                    }
+                    None => break,
+                },
+                _ = sleep_until(idle_deadline) => {
+                    debug!("association {} has been idle for {:?}", self.association_id, self.idle_timeout);
+                    break
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_datagrams() {
+        let ipv4 = [0, 0, 0, 1, 1, 1, 1, 1, 0, 53, 42, 42];
+        assert_eq!(
+            SocksDatagram::try_from_bytes(&ipv4).unwrap(),
+            SocksDatagram {
+                remote_address: "1.1.1.1:53".to_string(),
+                data: vec![42, 42]
+            }
+        );
+
+        let domain = [0, 0, 0, 3, 3, 102, 111, 111, 1, 187];
+        assert_eq!(
+            SocksDatagram::try_from_bytes(&domain).unwrap(),
+            SocksDatagram {
+                remote_address: "foo:443".to_string(),
+                data: vec![]
+            }
+        );
+
+        let fragmented = [0, 0, 1, 1, 1, 1, 1, 1, 0, 53, 42, 42];
+        assert!(matches!(
+            SocksDatagram::try_from_bytes(&fragmented),
+            Err(SocksProxyError::UnsupportedUdpFragmentation)
+        ));
+
+        let truncated = [0, 0, 0, 1, 1, 1, 1, 1, 0];
+        assert!(matches!(
+            SocksDatagram::try_from_bytes(&truncated),
+            Err(SocksProxyError::MalformedUdpHeader)
+        ));
+    }
+
+    #[test]
+    fn encapsulated_datagrams_can_be_parsed_back() {
+        let source: SocketAddr = "[2001:db8::1]:5353".parse().unwrap();
+        let encapsulated = encapsulate_datagram(source, vec![1, 2, 3]);
+        let parsed = SocksDatagram::try_from_bytes(&encapsulated).unwrap();
+
+        assert_eq!(parsed.remote_address.parse::<SocketAddr>().unwrap(), source);
+        assert_eq!(parsed.data, vec![1, 2, 3]);
+    }
+}
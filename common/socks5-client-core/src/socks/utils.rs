@@ -1,4 +1,5 @@
 use super::types::AddrType;
+use std::net::SocketAddr;
 
 /// Convert an AddrType and address to String
 pub(crate) fn pretty_print_addr(addr_type: &AddrType, addr: &[u8]) -> String {
@@ -22,3 +23,20 @@ pub(crate) fn pretty_print_addr(addr_type: &AddrType, addr: &[u8]) -> String {
         }
     }
 }
+
+/// Encode the socket address as ATYP || ADDR || PORT, as used in SOCKS5 replies and UDP headers
+pub(crate) fn encode_socket_address(address: SocketAddr) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    match address {
+        SocketAddr::V4(address) => {
+            encoded.push(AddrType::V4 as u8);
+            encoded.extend_from_slice(&address.ip().octets());
+        }
+        SocketAddr::V6(address) => {
+            encoded.push(AddrType::V6 as u8);
+            encoded.extend_from_slice(&address.ip().octets());
+        }
+    }
+    encoded.extend_from_slice(&address.port().to_be_bytes());
+    encoded
+}
@@ -22,6 +22,8 @@ pub enum RequestFlag {
     Connect = 0,
     Send = 1,
     Query = 2,
+    Datagram = 3,
+    Bind = 4,
 }
 
 impl TryFrom<u8> for RequestFlag {
@@ -32,6 +34,8 @@ impl TryFrom<u8> for RequestFlag {
             _ if value == (RequestFlag::Connect as u8) => Ok(Self::Connect),
             _ if value == (RequestFlag::Send as u8) => Ok(Self::Send),
             _ if value == (RequestFlag::Query as u8) => Ok(Self::Query),
+            _ if value == (RequestFlag::Datagram as u8) => Ok(Self::Datagram),
+            _ if value == (RequestFlag::Bind as u8) => Ok(Self::Bind),
             value => Err(RequestDeserializationError::UnknownRequestFlag { value }),
         }
     }
@@ -97,6 +101,52 @@ impl Debug for ConnectRequest {
     }
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct BindRequest {
+    pub conn_id: ConnectionId,
+
+    /// Address of the host that is expected to connect to the bound socket.
+    /// Only connections originating from its ip address are going to be accepted.
+    pub remote_addr: RemoteAddress,
+    pub return_address: Option<Recipient>,
+}
+
+impl Debug for BindRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BindRequest")
+            .field("conn_id", &self.conn_id)
+            .field("remote_addr", &self.remote_addr)
+            .field(
+                "return_address",
+                &self.return_address.map(|r| r.to_string()),
+            )
+            .finish()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct DatagramRequest {
+    /// Identifier of the UDP association this datagram belongs to.
+    pub association_id: ConnectionId,
+    pub remote_addr: RemoteAddress,
+    pub return_address: Option<Recipient>,
+    pub data: Vec<u8>,
+}
+
+impl Debug for DatagramRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DatagramRequest")
+            .field("association_id", &self.association_id)
+            .field("remote_addr", &self.remote_addr)
+            .field(
+                "return_address",
+                &self.return_address.map(|r| r.to_string()),
+            )
+            .field("data_len", &self.data.len())
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SendRequest {
     pub data: SocketData,
@@ -214,6 +264,36 @@ impl Socks5Request {
             content: Socks5RequestContent::Query(query),
         }
     }
+
+    pub fn new_bind(
+        protocol_version: Socks5ProtocolVersion,
+        conn_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Option<Recipient>,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::new_bind(conn_id, remote_addr, return_address),
+        }
+    }
+
+    pub fn new_datagram(
+        protocol_version: Socks5ProtocolVersion,
+        association_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Option<Recipient>,
+        data: Vec<u8>,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::new_datagram(
+                association_id,
+                remote_addr,
+                return_address,
+                data,
+            ),
+        }
+    }
 }
 
 /// A request from a SOCKS5 client that a Nym Socks5 service provider should
@@ -229,6 +309,16 @@ pub enum Socks5RequestContent {
     Send(SendRequest),
 
     Query(QueryRequest),
+
+    /// Relay a single UDP datagram to the specified `RemoteAddress` as part of the UDP association
+    /// identified by the provided id.
+    /// Any datagrams received in response should come back to the specified `Recipient`
+    Datagram(Box<DatagramRequest>),
+
+    /// Open a listening TCP socket and wait for a single inbound connection from the specified
+    /// `RemoteAddress`. Once accepted, the connection is relayed as a normal proxied stream.
+    /// All responses produced on this `ConnectionId` should come back to the specified `Recipient`
+    Bind(Box<BindRequest>),
 }
 
 impl Socks5RequestContent {
@@ -245,11 +335,39 @@ impl Socks5RequestContent {
         }))
     }
 
+    /// Construct a new Request::Bind instance
+    pub fn new_bind(
+        conn_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Option<Recipient>,
+    ) -> Socks5RequestContent {
+        Socks5RequestContent::Bind(Box::new(BindRequest {
+            conn_id,
+            remote_addr,
+            return_address,
+        }))
+    }
+
     /// Construct a new Request::Send instance
     pub fn new_send(data: SocketData) -> Socks5RequestContent {
         Socks5RequestContent::Send(SendRequest { data })
     }
 
+    /// Construct a new Request::Datagram instance
+    pub fn new_datagram(
+        association_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Option<Recipient>,
+        data: Vec<u8>,
+    ) -> Socks5RequestContent {
+        Socks5RequestContent::Datagram(Box::new(DatagramRequest {
+            association_id,
+            remote_addr,
+            return_address,
+            data,
+        }))
+    }
+
     /// Deserialize the request type, connection id, destination address and port,
     /// and the request body from bytes.
     ///
@@ -271,6 +389,12 @@ impl Socks5RequestContent {
     // send:
     // RequestFlag::Send || CONN_ID || LOCAL_CLOSED || DATA
     // where DATA: SEQ || TRUE_DATA
+    //
+    // datagram:
+    // RequestFlag::Datagram || ASSOC_ID || ADDR_LEN || ADDR || HAS_RETURN || <RETURN_ADDR> || DATA
+    //
+    // bind:
+    // RequestFlag::Bind || CONN_ID || ADDR_LEN || ADDR || <RETURN_ADDR>
 
     pub fn try_from_bytes(b: &[u8]) -> Result<Socks5RequestContent, RequestDeserializationError> {
         // each request needs to at least contain flag and ConnectionId
@@ -280,66 +404,137 @@ impl Socks5RequestContent {
 
         match RequestFlag::try_from(b[0])? {
             RequestFlag::Connect => {
+                let (conn_id, remote_address, return_address) =
+                    Self::parse_connection_request(&b[1..])?;
+                Ok(Socks5RequestContent::new_connect(
+                    conn_id,
+                    remote_address,
+                    return_address,
+                ))
+            }
+            RequestFlag::Bind => {
+                let (conn_id, remote_address, return_address) =
+                    Self::parse_connection_request(&b[1..])?;
+                Ok(Socks5RequestContent::new_bind(
+                    conn_id,
+                    remote_address,
+                    return_address,
+                ))
+            }
+            RequestFlag::Send => Ok(Socks5RequestContent::Send(SendRequest {
+                data: SocketData::try_from_request_bytes(&b[1..])?,
+            })),
+            RequestFlag::Query => {
+                use bincode::Options;
+                let query = make_bincode_serializer().deserialize(&b[1..])?;
+                Ok(Socks5RequestContent::Query(query))
+            }
+            RequestFlag::Datagram => {
                 if b.len() < 9 {
                     return Err(RequestDeserializationError::ConnectionIdTooShort);
                 }
-                let conn_id = u64::from_be_bytes([b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8]]);
-
-                let connect_request_bytes = &b[9..];
+                let association_id =
+                    u64::from_be_bytes([b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8]]);
 
-                // we need to be able to read at least 2 bytes that specify address length
-                if connect_request_bytes.len() < 2 {
+                let datagram_bytes = &b[9..];
+                if datagram_bytes.len() < 2 {
                     return Err(RequestDeserializationError::AddressLengthTooShort);
                 }
 
                 let address_length =
-                    u16::from_be_bytes([connect_request_bytes[0], connect_request_bytes[1]])
-                        as usize;
-
-                if connect_request_bytes.len() < 2 + address_length {
-                    return Err(RequestDeserializationError::AddressTooShort);
-                }
+                    u16::from_be_bytes([datagram_bytes[0], datagram_bytes[1]]) as usize;
 
                 let address_start = 2;
                 let address_end = address_start + address_length;
-                let address_bytes = &connect_request_bytes[address_start..address_end];
-                let remote_address = String::from_utf8_lossy(address_bytes).to_string();
+                if datagram_bytes.len() < address_end {
+                    return Err(RequestDeserializationError::AddressTooShort);
+                }
+                let remote_address =
+                    String::from_utf8_lossy(&datagram_bytes[address_start..address_end])
+                        .to_string();
 
-                // just a temporary reference to mid-slice for ease of use
-                let recipient_data_bytes = &connect_request_bytes[address_end..];
+                // a single byte indicating whether the return address is attached
+                let Some(&has_return) = datagram_bytes.get(address_end) else {
+                    return Err(RequestDeserializationError::ReturnAddressTooShort);
+                };
+                let mut data_start = address_end + 1;
 
-                let return_address = if recipient_data_bytes.is_empty() {
-                    None
-                } else {
-                    if recipient_data_bytes.len() != Recipient::LEN {
+                let return_address = if has_return != 0 {
+                    let return_end = data_start + Recipient::LEN;
+                    if datagram_bytes.len() < return_end {
                         return Err(RequestDeserializationError::ReturnAddressTooShort);
                     }
 
                     let mut return_bytes = [0u8; Recipient::LEN];
-                    return_bytes.copy_from_slice(&recipient_data_bytes[..Recipient::LEN]);
+                    return_bytes.copy_from_slice(&datagram_bytes[data_start..return_end]);
+                    data_start = return_end;
                     Some(
                         Recipient::try_from_bytes(return_bytes)
                             .map_err(RequestDeserializationError::MalformedReturnAddress)?,
                     )
+                } else {
+                    None
                 };
 
-                Ok(Socks5RequestContent::new_connect(
-                    conn_id,
+                Ok(Socks5RequestContent::new_datagram(
+                    association_id,
                     remote_address,
                     return_address,
+                    datagram_bytes[data_start..].to_vec(),
                 ))
             }
-            RequestFlag::Send => Ok(Socks5RequestContent::Send(SendRequest {
-                data: SocketData::try_from_request_bytes(&b[1..])?,
-            })),
-            RequestFlag::Query => {
-                use bincode::Options;
-                let query = make_bincode_serializer().deserialize(&b[1..])?;
-                Ok(Socks5RequestContent::Query(query))
-            }
         }
     }
 
+    // CONN_ID || ADDR_LEN || ADDR || <RETURN_ADDR>
+    fn parse_connection_request(
+        b: &[u8],
+    ) -> Result<(ConnectionId, RemoteAddress, Option<Recipient>), RequestDeserializationError> {
+        if b.len() < 8 {
+            return Err(RequestDeserializationError::ConnectionIdTooShort);
+        }
+        let conn_id = u64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+
+        let connect_request_bytes = &b[8..];
+
+        // we need to be able to read at least 2 bytes that specify address length
+        if connect_request_bytes.len() < 2 {
+            return Err(RequestDeserializationError::AddressLengthTooShort);
+        }
+
+        let address_length =
+            u16::from_be_bytes([connect_request_bytes[0], connect_request_bytes[1]]) as usize;
+
+        if connect_request_bytes.len() < 2 + address_length {
+            return Err(RequestDeserializationError::AddressTooShort);
+        }
+
+        let address_start = 2;
+        let address_end = address_start + address_length;
+        let address_bytes = &connect_request_bytes[address_start..address_end];
+        let remote_address = String::from_utf8_lossy(address_bytes).to_string();
+
+        // just a temporary reference to mid-slice for ease of use
+        let recipient_data_bytes = &connect_request_bytes[address_end..];
+
+        let return_address = if recipient_data_bytes.is_empty() {
+            None
+        } else {
+            if recipient_data_bytes.len() != Recipient::LEN {
+                return Err(RequestDeserializationError::ReturnAddressTooShort);
+            }
+
+            let mut return_bytes = [0u8; Recipient::LEN];
+            return_bytes.copy_from_slice(&recipient_data_bytes[..Recipient::LEN]);
+            Some(
+                Recipient::try_from_bytes(return_bytes)
+                    .map_err(RequestDeserializationError::MalformedReturnAddress)?,
+            )
+        };
+
+        Ok((conn_id, remote_address, return_address))
+    }
+
     /// Serialize a Socks5 request into bytes, so that it can be packed inside
     /// a Sphinx packet, sent through the mixnet, and deserialized by the Socks5
     /// service provider which will make the request.
@@ -377,6 +572,42 @@ impl Socks5RequestContent {
                     .chain(query_bytes)
                     .collect()
             }
+            // bind is: BIND_FLAG || CONN_ID || REMOTE_LEN || REMOTE || RETURN
+            Socks5RequestContent::Bind(req) => {
+                let remote_address_bytes = req.remote_addr.into_bytes();
+                let remote_address_bytes_len = remote_address_bytes.len() as u16;
+
+                let iter = std::iter::once(RequestFlag::Bind as u8)
+                    .chain(req.conn_id.to_be_bytes())
+                    .chain(remote_address_bytes_len.to_be_bytes())
+                    .chain(remote_address_bytes);
+
+                if let Some(return_address) = req.return_address {
+                    iter.chain(return_address.to_bytes()).collect()
+                } else {
+                    iter.collect()
+                }
+            }
+            // datagram is: DATAGRAM_FLAG || ASSOC_ID || REMOTE_LEN || REMOTE || HAS_RETURN || RETURN || DATA
+            Socks5RequestContent::Datagram(req) => {
+                let req = *req;
+                let remote_address_bytes = req.remote_addr.into_bytes();
+                let remote_address_bytes_len = remote_address_bytes.len() as u16;
+
+                let iter = std::iter::once(RequestFlag::Datagram as u8)
+                    .chain(req.association_id.to_be_bytes())
+                    .chain(remote_address_bytes_len.to_be_bytes())
+                    .chain(remote_address_bytes);
+
+                if let Some(return_address) = req.return_address {
+                    iter.chain(std::iter::once(1))
+                        .chain(return_address.to_bytes())
+                        .chain(req.data)
+                        .collect()
+                } else {
+                    iter.chain(std::iter::once(0)).chain(req.data).collect()
+                }
+            }
         }
     }
 }
@@ -715,4 +946,104 @@ mod request_deserialization_tests {
             assert_eq!(description, description2);
         }
     }
+
+    #[cfg(test)]
+    mod bind_requests {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+
+            let with_return =
+                Socks5RequestContent::new_bind(42, "1.2.3.4:20".to_string(), Some(recipient));
+            let bytes = with_return.clone().into_bytes();
+            assert_eq!(
+                with_return,
+                Socks5RequestContent::try_from_bytes(&bytes).unwrap()
+            );
+
+            let without_return = Socks5RequestContent::new_bind(42, "foo.com:20".to_string(), None);
+            let bytes = without_return.clone().into_bytes();
+            assert_eq!(
+                bytes,
+                vec![
+                    4, 0, 0, 0, 0, 0, 0, 0, 42, 0, 10, 102, 111, 111, 46, 99, 111, 109, 58, 50, 48
+                ]
+            );
+            assert_eq!(
+                without_return,
+                Socks5RequestContent::try_from_bytes(&bytes).unwrap()
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod relaying_datagrams {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+
+            let with_return = Socks5RequestContent::new_datagram(
+                42,
+                "1.1.1.1:53".to_string(),
+                Some(recipient),
+                vec![1, 2, 3],
+            );
+            let bytes = with_return.clone().into_bytes();
+            assert_eq!(
+                with_return,
+                Socks5RequestContent::try_from_bytes(&bytes).unwrap()
+            );
+
+            let without_return =
+                Socks5RequestContent::new_datagram(42, "foo.com:443".to_string(), None, vec![]);
+            let bytes = without_return.clone().into_bytes();
+            assert_eq!(
+                bytes,
+                vec![
+                    3, 0, 0, 0, 0, 0, 0, 0, 42, 0, 11, 102, 111, 111, 46, 99, 111, 109, 58, 52, 52,
+                    51, 0
+                ]
+            );
+            assert_eq!(
+                without_return,
+                Socks5RequestContent::try_from_bytes(&bytes).unwrap()
+            );
+        }
+
+        #[test]
+        fn returns_error_when_return_address_is_too_short() {
+            // "foo.com" remote address with the return address flag set but no return address
+            let request_bytes = [
+                RequestFlag::Datagram as u8,
+                1,
+                2,
+                3,
+                4,
+                5,
+                6,
+                7,
+                8,
+                0,
+                7,
+                102,
+                111,
+                111,
+                46,
+                99,
+                111,
+                109,
+                1,
+                255,
+            ];
+
+            match Socks5RequestContent::try_from_bytes(&request_bytes).unwrap_err() {
+                RequestDeserializationError::ReturnAddressTooShort => {}
+                _ => unreachable!(),
+            }
+        }
+    }
 }
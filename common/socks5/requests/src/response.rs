@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    make_bincode_serializer, ConnectionId, InsufficientSocketDataError, SocketData,
+    make_bincode_serializer, ConnectionId, InsufficientSocketDataError, RemoteAddress, SocketData,
     Socks5ProtocolVersion, Socks5RequestError,
 };
 use nym_exit_policy::ExitPolicy;
@@ -20,6 +20,8 @@ pub enum ResponseFlag {
     NetworkData = 1,
     ConnectionError = 2,
     Query = 3,
+    Datagram = 4,
+    Bind = 5,
 }
 
 impl TryFrom<u8> for ResponseFlag {
@@ -30,6 +32,8 @@ impl TryFrom<u8> for ResponseFlag {
             _ if value == (ResponseFlag::NetworkData as u8) => Ok(Self::NetworkData),
             _ if value == (ResponseFlag::ConnectionError as u8) => Ok(Self::ConnectionError),
             _ if value == (ResponseFlag::Query as u8) => Ok(Self::Query),
+            _ if value == (ResponseFlag::Datagram as u8) => Ok(Self::Datagram),
+            _ if value == (ResponseFlag::Bind as u8) => Ok(Self::Bind),
             value => Err(ResponseDeserializationError::UnknownResponseFlag { value }),
         }
     }
@@ -46,9 +50,18 @@ pub enum ResponseDeserializationError {
     #[error("not enough bytes to recover the connection id")]
     ConnectionIdTooShort,
 
+    #[error("not enough bytes to recover the length of the address")]
+    AddressLengthTooShort,
+
+    #[error("not enough bytes to recover the address")]
+    AddressTooShort,
+
     #[error("{value} is not a valid response flag")]
     UnknownResponseFlag { value: u8 },
 
+    #[error("{value} is not a valid bind status")]
+    UnknownBindStatus { value: u8 },
+
     #[error("no data provided")]
     NoData,
 
@@ -157,6 +170,30 @@ impl Socks5Response {
         }
     }
 
+    pub fn new_datagram(
+        protocol_version: Socks5ProtocolVersion,
+        association_id: ConnectionId,
+        source_addr: RemoteAddress,
+        data: Vec<u8>,
+    ) -> Socks5Response {
+        Socks5Response {
+            protocol_version,
+            content: Socks5ResponseContent::new_datagram(association_id, source_addr, data),
+        }
+    }
+
+    pub fn new_bind(
+        protocol_version: Socks5ProtocolVersion,
+        connection_id: ConnectionId,
+        status: BindStatus,
+        address: RemoteAddress,
+    ) -> Socks5Response {
+        Socks5Response {
+            protocol_version,
+            content: Socks5ResponseContent::new_bind(connection_id, status, address),
+        }
+    }
+
     pub fn new_query_error<S: Into<String>>(
         protocol_version: Socks5ProtocolVersion,
         message: S,
@@ -175,6 +212,8 @@ pub enum Socks5ResponseContent {
     NetworkData { content: SocketData },
     ConnectionError(ConnectionError),
     Query(QueryResponse),
+    Datagram(DatagramResponse),
+    Bind(BindResponse),
 }
 
 impl Socks5ResponseContent {
@@ -196,6 +235,22 @@ impl Socks5ResponseContent {
         Socks5ResponseContent::ConnectionError(ConnectionError::new(connection_id, error_message))
     }
 
+    pub fn new_datagram(
+        association_id: ConnectionId,
+        source_addr: RemoteAddress,
+        data: Vec<u8>,
+    ) -> Socks5ResponseContent {
+        Socks5ResponseContent::Datagram(DatagramResponse::new(association_id, source_addr, data))
+    }
+
+    pub fn new_bind(
+        connection_id: ConnectionId,
+        status: BindStatus,
+        address: RemoteAddress,
+    ) -> Socks5ResponseContent {
+        Socks5ResponseContent::Bind(BindResponse::new(connection_id, status, address))
+    }
+
     pub fn into_bytes(self) -> Vec<u8> {
         match self {
             Socks5ResponseContent::NetworkData { content } => {
@@ -220,6 +275,14 @@ impl Socks5ResponseContent {
                     .chain(query_bytes)
                     .collect()
             }
+            Socks5ResponseContent::Datagram(datagram) => {
+                std::iter::once(ResponseFlag::Datagram as u8)
+                    .chain(datagram.into_bytes())
+                    .collect()
+            }
+            Socks5ResponseContent::Bind(bind) => std::iter::once(ResponseFlag::Bind as u8)
+                .chain(bind.into_bytes())
+                .collect(),
         }
     }
 
@@ -243,6 +306,12 @@ impl Socks5ResponseContent {
                 let query = make_bincode_serializer().deserialize(&b[1..])?;
                 Ok(Socks5ResponseContent::Query(query))
             }
+            ResponseFlag::Datagram => Ok(Socks5ResponseContent::Datagram(
+                DatagramResponse::try_from_bytes(&b[1..])?,
+            )),
+            ResponseFlag::Bind => Ok(Socks5ResponseContent::Bind(BindResponse::try_from_bytes(
+                &b[1..],
+            )?)),
         }
     }
 
@@ -308,6 +377,144 @@ impl ConnectionError {
     }
 }
 
+/// A single UDP datagram received by the service provider as part of an existing UDP association.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatagramResponse {
+    pub association_id: ConnectionId,
+    pub source_addr: RemoteAddress,
+    pub data: Vec<u8>,
+}
+
+impl DatagramResponse {
+    pub fn new(association_id: ConnectionId, source_addr: RemoteAddress, data: Vec<u8>) -> Self {
+        DatagramResponse {
+            association_id,
+            source_addr,
+            data,
+        }
+    }
+
+    // ASSOC_ID || ADDR_LEN || ADDR || DATA
+    pub fn try_from_bytes(b: &[u8]) -> Result<DatagramResponse, ResponseDeserializationError> {
+        if b.is_empty() {
+            return Err(ResponseDeserializationError::NoData);
+        }
+
+        if b.len() < 8 {
+            return Err(ResponseDeserializationError::ConnectionIdTooShort);
+        }
+        // the unwrap is fine as we've just checked the length
+        let association_id = ConnectionId::from_be_bytes(b[..8].try_into().unwrap());
+
+        if b.len() < 10 {
+            return Err(ResponseDeserializationError::AddressLengthTooShort);
+        }
+        let address_length = u16::from_be_bytes([b[8], b[9]]) as usize;
+
+        let address_end = 10 + address_length;
+        if b.len() < address_end {
+            return Err(ResponseDeserializationError::AddressTooShort);
+        }
+        let source_addr = String::from_utf8(b[10..address_end].to_vec())?;
+
+        Ok(DatagramResponse {
+            association_id,
+            source_addr,
+            data: b[address_end..].to_vec(),
+        })
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        let source_address_bytes = self.source_addr.into_bytes();
+        let source_address_bytes_len = source_address_bytes.len() as u16;
+
+        self.association_id
+            .to_be_bytes()
+            .into_iter()
+            .chain(source_address_bytes_len.to_be_bytes())
+            .chain(source_address_bytes)
+            .chain(self.data)
+            .collect()
+    }
+}
+
+/// Stage of the SOCKS5 BIND command, as defined in RFC 1928, which requires two separate replies.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindStatus {
+    /// The service provider has started listening for the inbound connection.
+    Listening = 0,
+
+    /// The inbound connection has been accepted and is now going to be relayed.
+    Accepted = 1,
+}
+
+impl TryFrom<u8> for BindStatus {
+    type Error = ResponseDeserializationError;
+
+    fn try_from(value: u8) -> Result<BindStatus, ResponseDeserializationError> {
+        match value {
+            _ if value == (BindStatus::Listening as u8) => Ok(Self::Listening),
+            _ if value == (BindStatus::Accepted as u8) => Ok(Self::Accepted),
+            value => Err(ResponseDeserializationError::UnknownBindStatus { value }),
+        }
+    }
+}
+
+/// Progress of a SOCKS5 BIND request handled by the service provider.
+/// With the `Listening` status the address is the one the socket has been bound to,
+/// while with the `Accepted` status it's the address of the connected peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindResponse {
+    pub connection_id: ConnectionId,
+    pub status: BindStatus,
+    pub address: RemoteAddress,
+}
+
+impl BindResponse {
+    pub fn new(connection_id: ConnectionId, status: BindStatus, address: RemoteAddress) -> Self {
+        BindResponse {
+            connection_id,
+            status,
+            address,
+        }
+    }
+
+    // CONN_ID || STATUS || ADDR
+    pub fn try_from_bytes(b: &[u8]) -> Result<BindResponse, ResponseDeserializationError> {
+        if b.is_empty() {
+            return Err(ResponseDeserializationError::NoData);
+        }
+
+        if b.len() < 8 {
+            return Err(ResponseDeserializationError::ConnectionIdTooShort);
+        }
+        // the unwrap is fine as we've just checked the length
+        let connection_id = ConnectionId::from_be_bytes(b[..8].try_into().unwrap());
+
+        let Some(&status) = b.get(8) else {
+            return Err(ResponseDeserializationError::NoData);
+        };
+        let status = BindStatus::try_from(status)?;
+        let address = String::from_utf8(b[9..].to_vec())?;
+
+        Ok(BindResponse {
+            connection_id,
+            status,
+            address,
+        })
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.connection_id
+            .to_be_bytes()
+            .into_iter()
+            .chain(std::iter::once(self.status as u8))
+            .chain(self.address.into_bytes())
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum QueryResponse {
@@ -371,6 +578,87 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod datagram_response_serde_tests {
+        use super::*;
+
+        #[test]
+        fn simple_serde() {
+            let response =
+                Socks5ResponseContent::new_datagram(42, "1.1.1.1:53".to_string(), vec![1, 2, 3, 4]);
+            let bytes = response.clone().into_bytes();
+            assert_eq!(
+                response,
+                Socks5ResponseContent::try_from_bytes(&bytes).unwrap()
+            );
+        }
+
+        #[test]
+        fn deserialization_errors() {
+            let err = DatagramResponse::try_from_bytes(&[]).err().unwrap();
+            assert!(matches!(err, ResponseDeserializationError::NoData));
+
+            let err = DatagramResponse::try_from_bytes(&[1, 2, 3]).err().unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::ConnectionIdTooShort
+            ));
+
+            let err = DatagramResponse::try_from_bytes(&[0, 0, 0, 0, 0, 0, 0, 42, 0])
+                .err()
+                .unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::AddressLengthTooShort
+            ));
+
+            let err = DatagramResponse::try_from_bytes(&[0, 0, 0, 0, 0, 0, 0, 42, 0, 5, 1])
+                .err()
+                .unwrap();
+            assert!(matches!(err, ResponseDeserializationError::AddressTooShort));
+        }
+    }
+
+    #[cfg(test)]
+    mod bind_response_serde_tests {
+        use super::*;
+
+        #[test]
+        fn simple_serde() {
+            for status in [BindStatus::Listening, BindStatus::Accepted] {
+                let response =
+                    Socks5ResponseContent::new_bind(42, status, "1.2.3.4:21".to_string());
+                let bytes = response.clone().into_bytes();
+                assert_eq!(
+                    response,
+                    Socks5ResponseContent::try_from_bytes(&bytes).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn deserialization_errors() {
+            let err = BindResponse::try_from_bytes(&[1, 2, 3]).err().unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::ConnectionIdTooShort
+            ));
+
+            let err = BindResponse::try_from_bytes(&[0, 0, 0, 0, 0, 0, 0, 42])
+                .err()
+                .unwrap();
+            assert!(matches!(err, ResponseDeserializationError::NoData));
+
+            let err = BindResponse::try_from_bytes(&[0, 0, 0, 0, 0, 0, 0, 42, 7])
+                .err()
+                .unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::UnknownBindStatus { value: 7 }
+            ));
+        }
+    }
+
     #[cfg(test)]
     mod serialize_query_response {
         use super::*;
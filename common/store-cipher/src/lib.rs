@@ -22,6 +22,15 @@ pub const AES256GCM_NONCE_SIZE: usize = 12;
 
 const VERIFICATION_PHRASE: &[u8] = &[0u8; 32];
 
+/// Prefix of every blob produced by [`StoreCipher::encrypt_to_blob`] allowing to distinguish
+/// encrypted data from legacy plaintext values kept in the same storage.
+pub const ENCRYPTED_BLOB_PREFIX: &[u8] = b"nym-enc\0";
+
+/// Checks whether the provided data has been produced by [`StoreCipher::encrypt_to_blob`].
+pub fn is_encrypted_blob(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_BLOB_PREFIX)
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Unsupported cipher")]
@@ -54,6 +63,9 @@ pub enum Error {
 
     #[error("could not import the store - the provided passphrase was invalid")]
     InvalidImportPassphrase,
+
+    #[error("the provided data is not a valid encrypted blob")]
+    MalformedEncryptedBlob,
 }
 
 // it's weird that this couldn't be auto-derived with a `#[from]`...
@@ -172,6 +184,18 @@ where
     kdf_info: KdfInfo,
 }
 
+// manual implementation to make sure the key material never ends up in the logs
+impl<C> std::fmt::Debug for StoreCipher<C>
+where
+    C: KeySizeUser,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoreCipher")
+            .field("kdf_info", &self.kdf_info)
+            .finish_non_exhaustive()
+    }
+}
+
 impl StoreCipher<Aes256Gcm> {
     pub fn import_aes256gcm(
         passphrase: &[u8],
@@ -319,6 +343,28 @@ where
         self.decrypt_data_unchecked(data)
     }
 
+    /// Encrypts the provided data into a self-describing blob suitable for being persisted
+    /// in places that only accept raw bytes, like database columns or files.
+    pub fn encrypt_to_blob(&self, data: &[u8]) -> Result<Vec<u8>, Error>
+    where
+        C: Aead,
+    {
+        let encrypted = self.encrypt_data_ref(data)?;
+        Ok(encrypted.to_blob())
+    }
+
+    /// Recovers the plaintext out of the blob produced by [`Self::encrypt_to_blob`].
+    pub fn decrypt_blob(&self, blob: &[u8]) -> Result<Vec<u8>, Error>
+    where
+        C: Aead,
+    {
+        let encrypted = EncryptedData::try_from_blob(blob)?;
+        if encrypted.nonce.len() != <C as AeadCore>::NonceSize::USIZE {
+            return Err(Error::MalformedEncryptedBlob);
+        }
+        self.decrypt_data(encrypted)
+    }
+
     pub fn random_nonce() -> Result<Nonce<C>, Error>
     where
         C: AeadCore,
@@ -355,6 +401,40 @@ pub struct EncryptedData {
     pub nonce: Vec<u8>,
 }
 
+impl EncryptedData {
+    /// Serialises the data as `PREFIX || version || nonce_len || nonce || ciphertext`.
+    pub fn to_blob(&self) -> Vec<u8> {
+        let mut blob = Vec::with_capacity(
+            ENCRYPTED_BLOB_PREFIX.len() + 2 + self.nonce.len() + self.ciphertext.len(),
+        );
+        blob.extend_from_slice(ENCRYPTED_BLOB_PREFIX);
+        blob.push(self.version);
+        blob.push(self.nonce.len() as u8);
+        blob.extend_from_slice(&self.nonce);
+        blob.extend_from_slice(&self.ciphertext);
+        blob
+    }
+
+    pub fn try_from_blob(blob: &[u8]) -> Result<Self, Error> {
+        let Some(data) = blob.strip_prefix(ENCRYPTED_BLOB_PREFIX) else {
+            return Err(Error::MalformedEncryptedBlob);
+        };
+        let [version, nonce_len, rest @ ..] = data else {
+            return Err(Error::MalformedEncryptedBlob);
+        };
+        let nonce_len = *nonce_len as usize;
+        if rest.len() < nonce_len {
+            return Err(Error::MalformedEncryptedBlob);
+        }
+
+        Ok(EncryptedData {
+            version: *version,
+            nonce: rest[..nonce_len].to_vec(),
+            ciphertext: rest[nonce_len..].to_vec(),
+        })
+    }
+}
+
 pub fn argon2_derive_cipher_key<C>(
     passphrase: &[u8],
     salt: &[u8],
@@ -377,3 +457,144 @@ where
 
     Ok(key)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // use cheap kdf parameters so that the tests wouldn't take forever
+    fn test_kdf_info() -> KdfInfo {
+        KdfInfo::Argon2 {
+            params: Params::new(8, 1, 1, None).unwrap(),
+            algorithm: Default::default(),
+            version: Default::default(),
+            kdf_salt: KdfInfo::random_salt().unwrap(),
+        }
+    }
+
+    fn test_cipher(passphrase: &[u8]) -> StoreCipher {
+        StoreCipher::new(passphrase, test_kdf_info()).unwrap()
+    }
+
+    #[test]
+    fn blob_roundtrip() {
+        let cipher = test_cipher(b"my-secret-passphrase");
+        let plaintext = b"some very secret data";
+
+        let blob = cipher.encrypt_to_blob(plaintext).unwrap();
+        assert!(is_encrypted_blob(&blob));
+        assert!(!is_encrypted_blob(plaintext));
+        assert_eq!(cipher.decrypt_blob(&blob).unwrap(), plaintext);
+
+        let decoded = EncryptedData::try_from_blob(&blob).unwrap();
+        assert_eq!(decoded.version, CURRENT_VERSION);
+        assert_eq!(decoded.nonce.len(), AES256GCM_NONCE_SIZE);
+        assert_eq!(decoded.to_blob(), blob);
+
+        // every encryption uses a fresh nonce
+        let another = cipher.encrypt_to_blob(plaintext).unwrap();
+        assert_ne!(blob, another);
+        assert_eq!(cipher.decrypt_blob(&another).unwrap(), plaintext);
+
+        let empty = cipher.encrypt_to_blob(&[]).unwrap();
+        assert!(cipher.decrypt_blob(&empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn exported_cipher_roundtrip() {
+        let cipher = test_cipher(b"my-secret-passphrase");
+        let blob = cipher.encrypt_to_blob(b"some very secret data").unwrap();
+
+        let exported = cipher.export_aes256gcm().unwrap();
+        let imported = StoreCipher::import_aes256gcm(b"my-secret-passphrase", exported).unwrap();
+        assert_eq!(
+            imported.decrypt_blob(&blob).unwrap(),
+            b"some very secret data"
+        );
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let cipher = test_cipher(b"my-secret-passphrase");
+        let blob = cipher.encrypt_to_blob(b"some very secret data").unwrap();
+
+        let exported = cipher.export_aes256gcm().unwrap();
+        assert!(matches!(
+            StoreCipher::import_aes256gcm(b"not-my-passphrase", exported),
+            Err(Error::InvalidImportPassphrase)
+        ));
+
+        // same kdf parameters, different passphrase
+        let KdfInfo::Argon2 { kdf_salt, .. } = cipher.kdf_info.clone();
+        let other = StoreCipher::new(
+            b"not-my-passphrase",
+            KdfInfo::Argon2 {
+                params: Params::new(8, 1, 1, None).unwrap(),
+                algorithm: Default::default(),
+                version: Default::default(),
+                kdf_salt,
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            other.decrypt_blob(&blob),
+            Err(Error::AesFailure { .. })
+        ));
+    }
+
+    #[test]
+    fn tampered_blob() {
+        let cipher = test_cipher(b"my-secret-passphrase");
+        let blob = cipher.encrypt_to_blob(b"some very secret data").unwrap();
+
+        // modified ciphertext
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            cipher.decrypt_blob(&tampered),
+            Err(Error::AesFailure { .. })
+        ));
+
+        // modified nonce
+        let mut tampered = blob.clone();
+        tampered[ENCRYPTED_BLOB_PREFIX.len() + 2] ^= 1;
+        assert!(matches!(
+            cipher.decrypt_blob(&tampered),
+            Err(Error::AesFailure { .. })
+        ));
+
+        // missing prefix
+        assert!(matches!(
+            cipher.decrypt_blob(&blob[ENCRYPTED_BLOB_PREFIX.len()..]),
+            Err(Error::MalformedEncryptedBlob)
+        ));
+
+        // truncated header
+        assert!(matches!(
+            cipher.decrypt_blob(&blob[..ENCRYPTED_BLOB_PREFIX.len() + 1]),
+            Err(Error::MalformedEncryptedBlob)
+        ));
+
+        // truncated nonce
+        assert!(matches!(
+            cipher.decrypt_blob(&blob[..ENCRYPTED_BLOB_PREFIX.len() + 2 + 4]),
+            Err(Error::MalformedEncryptedBlob)
+        ));
+
+        // invalid nonce length
+        let mut tampered = blob.clone();
+        tampered[ENCRYPTED_BLOB_PREFIX.len() + 1] = 8;
+        assert!(matches!(
+            cipher.decrypt_blob(&tampered),
+            Err(Error::MalformedEncryptedBlob)
+        ));
+
+        // unknown version
+        let mut tampered = blob;
+        tampered[ENCRYPTED_BLOB_PREFIX.len()] = CURRENT_VERSION + 1;
+        assert!(matches!(
+            cipher.decrypt_blob(&tampered),
+            Err(Error::VersionMismatch { received }) if received == CURRENT_VERSION + 1
+        ));
+    }
+}
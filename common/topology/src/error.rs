@@ -30,6 +30,12 @@ pub enum NymTopologyError {
     #[error("No mixnodes available on layer {layer}")]
     EmptyMixLayer { layer: MixLayer },
 
+    #[error("None of the mixnodes on layer {layer} satisfy the route selection criteria")]
+    NoSuitableMixnodes { layer: MixLayer },
+
+    #[error("None of the gateways satisfy the route selection criteria")]
+    NoSuitableGateways,
+
     #[error("Uneven layer distribution. Layer {layer} has {nodes} on it, while we expected a value between {lower_bound} and {upper_bound} as we have {total_nodes} nodes in total. Full breakdown: {layer_distribution:?}")]
     UnevenLayerDistribution {
         layer: MixLayer,
@@ -1,9 +1,11 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::sphinx_key_rotation::{apply_rotation, SphinxKeyRotation};
 use crate::{filter, NetworkAddress, NodeVersion};
 use nym_api_requests::models::DescribedGateway;
 use nym_crypto::asymmetric::{encryption, identity};
+use nym_mixnet_contract_common::reward_params::Performance;
 use nym_mixnet_contract_common::GatewayBond;
 use nym_sphinx_addressing::nodes::{NodeIdentity, NymNodeRoutingAddress};
 use nym_sphinx_types::Node as SphinxNode;
@@ -17,6 +19,7 @@ use std::io;
 use std::net::AddrParseError;
 use std::net::SocketAddr;
 use thiserror::Error;
+use time::OffsetDateTime;
 
 #[derive(Error, Debug)]
 pub enum GatewayConversionError {
@@ -64,6 +67,15 @@ pub struct Node {
     pub identity_key: identity::PublicKey,
     pub sphinx_key: encryption::PublicKey, // TODO: or nymsphinx::PublicKey? both are x25519
 
+    /// Total stake (in the base denomination) of this node, if known.
+    pub stake: Option<u128>,
+
+    /// Recent performance of this node, if known.
+    pub performance: Option<Performance>,
+
+    /// Information about the upcoming sphinx key, if this node rotates its keys.
+    pub sphinx_key_rotation: Option<SphinxKeyRotation>,
+
     // to be removed:
     pub owner: Option<String>,
     pub version: NodeVersion,
@@ -79,6 +91,9 @@ impl std::fmt::Debug for Node {
             .field("clients_wss_port", &self.clients_wss_port)
             .field("identity_key", &self.identity_key.to_base58_string())
             .field("sphinx_key", &self.sphinx_key.to_base58_string())
+            .field("stake", &self.stake)
+            .field("performance", &self.performance)
+            .field("sphinx_key_rotation", &self.sphinx_key_rotation)
             .field("version", &self.version)
             .finish()
     }
@@ -103,6 +118,11 @@ impl Node {
         })?[0])
     }
 
+    /// Switches to the upcoming sphinx key if its rotation has already started.
+    pub fn apply_sphinx_key_rotation(&mut self, now: OffsetDateTime) {
+        apply_rotation(&mut self.sphinx_key, &mut self.sphinx_key_rotation, now)
+    }
+
     pub fn identity(&self) -> &NodeIdentity {
         &self.identity_key
     }
@@ -167,6 +187,9 @@ impl<'a> TryFrom<&'a GatewayBond> for Node {
             clients_wss_port: None,
             identity_key: identity::PublicKey::from_base58_string(&bond.gateway.identity_key)?,
             sphinx_key: encryption::PublicKey::from_base58_string(&bond.gateway.sphinx_key)?,
+            stake: Some(bond.pledge_amount.amount.u128()),
+            performance: None,
+            sphinx_key_rotation: None,
             version: bond.gateway.version.as_str().into(),
         })
     }
@@ -204,6 +227,21 @@ impl<'a> TryFrom<&'a DescribedGateway> for Node {
         // (which doesn't really work in wasm)
         let mix_host = SocketAddr::new(ips[0], value.bond.gateway.mix_port);
 
+        // if the node is rotating its keys, the announced current key takes precedence
+        let (sphinx_key, sphinx_key_rotation) =
+            match &self_described.host_information.keys.x25519_sphinx_rotation {
+                Some(rotation) => {
+                    let (current, rotation) = SphinxKeyRotation::try_from_announced(rotation)?;
+                    (current, Some(rotation))
+                }
+                None => (
+                    encryption::PublicKey::from_base58_string(
+                        &self_described.host_information.keys.x25519,
+                    )?,
+                    None,
+                ),
+            };
+
         Ok(Node {
             owner: Some(value.bond.owner.as_str().to_owned()),
             host,
@@ -213,9 +251,10 @@ impl<'a> TryFrom<&'a DescribedGateway> for Node {
             identity_key: identity::PublicKey::from_base58_string(
                 &self_described.host_information.keys.ed25519,
             )?,
-            sphinx_key: encryption::PublicKey::from_base58_string(
-                &self_described.host_information.keys.x25519,
-            )?,
+            sphinx_key,
+            stake: Some(value.bond.pledge_amount.amount.u128()),
+            performance: None,
+            sphinx_key_rotation,
             version: self_described
                 .build_information
                 .build_version
@@ -249,13 +288,25 @@ impl<'a> TryFrom<&'a SkimmedNode> for Node {
             NetworkAddress::IpAddr(*ip)
         };
 
+        // if the node is rotating its keys, the announced current key takes precedence
+        let (sphinx_key, sphinx_key_rotation) = match &value.x25519_sphinx_rotation {
+            Some(rotation) => {
+                let (current, rotation) = SphinxKeyRotation::try_from_announced(rotation)?;
+                (current, Some(rotation))
+            }
+            None => (value.x25519_sphinx_pubkey.parse()?, None),
+        };
+
         Ok(Node {
             host,
             mix_host: SocketAddr::new(*ip, value.mix_port),
             clients_ws_port: entry_details.ws_port,
             clients_wss_port: entry_details.wss_port,
             identity_key: value.ed25519_identity_pubkey.parse()?,
-            sphinx_key: value.x25519_sphinx_pubkey.parse()?,
+            sphinx_key,
+            stake: value.total_stake.map(|stake| stake.u128()),
+            performance: Some(value.performance),
+            sphinx_key_rotation,
             owner: None,
             version: NodeVersion::Unknown,
         })
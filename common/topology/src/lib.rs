@@ -8,11 +8,11 @@ use crate::filter::VersionFilterable;
 pub use error::NymTopologyError;
 use log::{debug, warn};
 use nym_mixnet_contract_common::mixnode::MixNodeDetails;
-use nym_mixnet_contract_common::{GatewayBond, IdentityKeyRef, MixId};
+use nym_mixnet_contract_common::{truncate_decimal, GatewayBond, IdentityKeyRef, MixId};
 use nym_sphinx_addressing::nodes::NodeIdentity;
 use nym_sphinx_types::Node as SphinxNode;
-use rand::prelude::SliceRandom;
 use rand::{CryptoRng, Rng};
+pub use route_selection::RouteSelectionStrategy;
 use std::collections::BTreeMap;
 use std::convert::Infallible;
 
@@ -20,6 +20,7 @@ use std::fmt::{self, Display, Formatter};
 use std::io;
 use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 use std::str::FromStr;
+use time::OffsetDateTime;
 
 #[cfg(feature = "serializable")]
 use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -30,6 +31,8 @@ pub mod filter;
 pub mod gateway;
 pub mod mix;
 pub mod random_route_provider;
+pub mod route_selection;
+pub mod sphinx_key_rotation;
 
 #[cfg(feature = "provider-trait")]
 pub mod provider_trait;
@@ -120,11 +123,16 @@ pub type MixLayer = u8;
 pub struct NymTopology {
     mixes: BTreeMap<MixLayer, Vec<mix::Node>>,
     gateways: Vec<gateway::Node>,
+    route_selection: RouteSelectionStrategy,
 }
 
 impl NymTopology {
     pub fn new(mixes: BTreeMap<MixLayer, Vec<mix::Node>>, gateways: Vec<gateway::Node>) -> Self {
-        NymTopology { mixes, gateways }
+        NymTopology {
+            mixes,
+            gateways,
+            route_selection: Default::default(),
+        }
     }
 
     pub fn new_unordered(unordered_mixes: Vec<mix::Node>, gateways: Vec<gateway::Node>) -> Self {
@@ -135,7 +143,7 @@ impl NymTopology {
             layer_entry.push(node)
         }
 
-        NymTopology { mixes, gateways }
+        NymTopology::new(mixes, gateways)
     }
 
     pub fn from_unordered<MI, GI, M, G>(unordered_mixes: MI, unordered_gateways: GI) -> Self
@@ -183,6 +191,33 @@ impl NymTopology {
         nym_topology_from_detailed(mix_details, gateway_bonds)
     }
 
+    /// Switches the sphinx keys of all nodes whose announced key rotation has already started.
+    pub fn apply_sphinx_key_rotations(&mut self, now: OffsetDateTime) {
+        for mix in self.mixes.values_mut().flatten() {
+            mix.apply_sphinx_key_rotation(now)
+        }
+        for gateway in &mut self.gateways {
+            gateway.apply_sphinx_key_rotation(now)
+        }
+    }
+
+    #[must_use]
+    pub fn with_route_selection_strategy(
+        mut self,
+        route_selection: RouteSelectionStrategy,
+    ) -> Self {
+        self.route_selection = route_selection;
+        self
+    }
+
+    pub fn set_route_selection_strategy(&mut self, route_selection: RouteSelectionStrategy) {
+        self.route_selection = route_selection
+    }
+
+    pub fn route_selection_strategy(&self) -> RouteSelectionStrategy {
+        self.route_selection
+    }
+
     pub fn find_mix(&self, mix_id: MixId) -> Option<&mix::Node> {
         for nodes in self.mixes.values() {
             for node in nodes {
@@ -259,9 +294,13 @@ impl NymTopology {
     where
         R: Rng + CryptoRng,
     {
-        self.gateways
-            .choose(rng)
-            .ok_or(NymTopologyError::NoGatewaysAvailable)
+        if self.gateways.is_empty() {
+            return Err(NymTopologyError::NoGatewaysAvailable);
+        }
+
+        self.route_selection
+            .choose_gateway(rng, &self.gateways)
+            .ok_or(NymTopologyError::NoSuitableGateways)
     }
 
     /// Returns a vec of size of `num_mix_hops` of mixnodes, such that each subsequent node is on
@@ -290,11 +329,16 @@ impl NymTopology {
                 .get(&layer)
                 .ok_or(NymTopologyError::EmptyMixLayer { layer })?;
 
-            // choose a random mix from the above list
-            // this can return a 'None' only if slice is empty
-            let random_mix = layer_mixes
-                .choose(rng)
-                .ok_or(NymTopologyError::EmptyMixLayer { layer })?;
+            if layer_mixes.is_empty() {
+                return Err(NymTopologyError::EmptyMixLayer { layer });
+            }
+
+            // choose a random mix from the above list according to the selection strategy
+            // this can return a 'None' only if none of the nodes satisfy the strategy requirements
+            let random_mix = self
+                .route_selection
+                .choose_mixnode(rng, layer_mixes)
+                .ok_or(NymTopologyError::NoSuitableMixnodes { layer })?;
             route.push(random_mix.into());
         }
 
@@ -416,6 +460,7 @@ impl NymTopology {
         NymTopology {
             mixes: self.mixes.filter_by_version(expected_mix_version),
             gateways: self.gateways.clone(),
+            route_selection: self.route_selection,
         }
     }
 }
@@ -469,10 +514,9 @@ where
     <G as TryInto<gateway::Node>>::Error: Display,
 {
     let mut mixes = BTreeMap::new();
-    for bond in mix_details
-        .into_iter()
-        .map(|details| details.bond_information)
-    {
+    for details in mix_details {
+        let stake = truncate_decimal(details.total_stake()).u128();
+        let bond = details.bond_information;
         let layer = bond.layer as MixLayer;
         if layer == 0 || layer > 3 {
             warn!(
@@ -485,8 +529,11 @@ where
         let mix_identity = bond.mix_node.identity_key.clone();
 
         let layer_entry = mixes.entry(layer).or_insert_with(Vec::new);
-        match bond.try_into() {
-            Ok(mix) => layer_entry.push(mix),
+        match mix::Node::try_from(bond) {
+            Ok(mut mix) => {
+                mix.stake = Some(stake);
+                layer_entry.push(mix)
+            }
             Err(err) => {
                 warn!("Mix {mix_id} / {mix_identity} is malformed: {err}");
                 continue;
@@ -536,6 +583,9 @@ mod converting_mixes_to_vec {
                 )
                 .unwrap(),
                 layer: Layer::One,
+                stake: None,
+                performance: None,
+                sphinx_key_rotation: None,
                 version: "0.2.0".into(),
             };
 
@@ -1,8 +1,10 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::sphinx_key_rotation::{apply_rotation, SphinxKeyRotation};
 use crate::{filter, NetworkAddress, NodeVersion};
 use nym_crypto::asymmetric::{encryption, identity};
+use nym_mixnet_contract_common::reward_params::Performance;
 pub use nym_mixnet_contract_common::Layer;
 use nym_mixnet_contract_common::{MixId, MixNodeBond};
 use nym_sphinx_addressing::nodes::NymNodeRoutingAddress;
@@ -15,6 +17,7 @@ use std::fmt::Formatter;
 use std::io;
 use std::net::SocketAddr;
 use thiserror::Error;
+use time::OffsetDateTime;
 
 #[derive(Error, Debug)]
 pub enum MixnodeConversionError {
@@ -52,6 +55,15 @@ pub struct Node {
     pub sphinx_key: encryption::PublicKey, // TODO: or nymsphinx::PublicKey? both are x25519
     pub layer: Layer,
 
+    /// Total stake (in the base denomination) of this node, if known.
+    pub stake: Option<u128>,
+
+    /// Recent performance of this node, if known.
+    pub performance: Option<Performance>,
+
+    /// Information about the upcoming sphinx key, if this node rotates its keys.
+    pub sphinx_key_rotation: Option<SphinxKeyRotation>,
+
     // to be removed:
     pub version: NodeVersion,
     pub owner: Option<String>,
@@ -67,6 +79,9 @@ impl std::fmt::Debug for Node {
             .field("identity_key", &self.identity_key.to_base58_string())
             .field("sphinx_key", &self.sphinx_key.to_base58_string())
             .field("layer", &self.layer)
+            .field("stake", &self.stake)
+            .field("performance", &self.performance)
+            .field("sphinx_key_rotation", &self.sphinx_key_rotation)
             .field("version", &self.version)
             .finish()
     }
@@ -79,6 +94,11 @@ impl Node {
         Ok(raw.parse().unwrap())
     }
 
+    /// Switches to the upcoming sphinx key if its rotation has already started.
+    pub fn apply_sphinx_key_rotation(&mut self, now: OffsetDateTime) {
+        apply_rotation(&mut self.sphinx_key, &mut self.sphinx_key_rotation, now)
+    }
+
     pub fn extract_mix_host(
         host: &NetworkAddress,
         mix_port: u16,
@@ -127,6 +147,9 @@ impl<'a> TryFrom<&'a MixNodeBond> for Node {
             identity_key: identity::PublicKey::from_base58_string(&bond.mix_node.identity_key)?,
             sphinx_key: encryption::PublicKey::from_base58_string(&bond.mix_node.sphinx_key)?,
             layer: bond.layer,
+            stake: None,
+            performance: None,
+            sphinx_key_rotation: None,
             version: bond.mix_node.version.as_str().into(),
         })
     }
@@ -155,13 +178,25 @@ impl<'a> TryFrom<&'a SkimmedNode> for Node {
 
         let host = NetworkAddress::IpAddr(*ip);
 
+        // if the node is rotating its keys, the announced current key takes precedence
+        let (sphinx_key, sphinx_key_rotation) = match &value.x25519_sphinx_rotation {
+            Some(rotation) => {
+                let (current, rotation) = SphinxKeyRotation::try_from_announced(rotation)?;
+                (current, Some(rotation))
+            }
+            None => (value.x25519_sphinx_pubkey.parse()?, None),
+        };
+
         Ok(Node {
             mix_id: value.node_id,
             host,
             mix_host: SocketAddr::new(*ip, value.mix_port),
             identity_key: value.ed25519_identity_pubkey.parse()?,
-            sphinx_key: value.x25519_sphinx_pubkey.parse()?,
+            sphinx_key,
             layer,
+            stake: value.total_stake.map(|stake| stake.u128()),
+            performance: Some(value.performance),
+            sphinx_key_rotation,
             owner: None,
             version: NodeVersion::Unknown,
         })
@@ -1,18 +1,39 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{NymTopology, NymTopologyError};
+use crate::{NymTopology, NymTopologyError, RouteSelectionStrategy};
 use nym_sphinx_addressing::clients::Recipient;
 use nym_sphinx_routing::SphinxRouteMaker;
 use nym_sphinx_types::Node;
 use rand::{CryptoRng, Rng};
 
-#[allow(dead_code)]
 pub struct NymTopologyRouteProvider<R> {
     rng: R,
     inner: NymTopology,
 }
 
+impl<R> NymTopologyRouteProvider<R> {
+    pub fn new(rng: R, topology: NymTopology) -> Self {
+        NymTopologyRouteProvider {
+            rng,
+            inner: topology,
+        }
+    }
+
+    #[must_use]
+    pub fn with_route_selection_strategy(
+        mut self,
+        route_selection: RouteSelectionStrategy,
+    ) -> Self {
+        self.inner.set_route_selection_strategy(route_selection);
+        self
+    }
+
+    pub fn topology(&self) -> &NymTopology {
+        &self.inner
+    }
+}
+
 impl<R> SphinxRouteMaker for NymTopologyRouteProvider<R>
 where
     R: Rng + CryptoRng,
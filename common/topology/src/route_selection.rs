@@ -0,0 +1,267 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{gateway, mix};
+use log::trace;
+use nym_mixnet_contract_common::reward_params::Performance;
+use rand::distributions::WeightedError;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Defines how nodes are picked from the topology when constructing routes through the mixnet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RouteSelectionStrategy {
+    /// Every node has the same probability of being chosen.
+    #[default]
+    Uniform,
+
+    /// Nodes are chosen with probability proportional to their total stake.
+    /// Nodes with unknown stake are never chosen.
+    StakeWeighted,
+
+    /// Nodes are chosen with probability proportional to their performance score.
+    /// Nodes below the specified thresholds or with unknown performance are never chosen.
+    PerformanceWeighted {
+        minimum_mixnode_performance: u8,
+        minimum_gateway_performance: u8,
+    },
+}
+
+/// Information about a node that can be used for biasing its selection probability.
+pub trait RoutingWeight {
+    /// Total stake (in the base denomination) associated with the node, if known.
+    fn stake(&self) -> Option<u128>;
+
+    /// Recent performance of the node, if known.
+    fn performance(&self) -> Option<Performance>;
+}
+
+impl RoutingWeight for mix::Node {
+    fn stake(&self) -> Option<u128> {
+        self.stake
+    }
+
+    fn performance(&self) -> Option<Performance> {
+        self.performance
+    }
+}
+
+impl RoutingWeight for gateway::Node {
+    fn stake(&self) -> Option<u128> {
+        self.stake
+    }
+
+    fn performance(&self) -> Option<Performance> {
+        self.performance
+    }
+}
+
+impl RouteSelectionStrategy {
+    /// Chooses a mixnode out of the provided slice according to the strategy.
+    /// Returns `None` if the slice is empty or none of the nodes satisfy the selection criteria.
+    pub fn choose_mixnode<'a, R>(
+        &self,
+        rng: &mut R,
+        nodes: &'a [mix::Node],
+    ) -> Option<&'a mix::Node>
+    where
+        R: Rng + ?Sized,
+    {
+        let minimum_performance = match self {
+            RouteSelectionStrategy::PerformanceWeighted {
+                minimum_mixnode_performance,
+                ..
+            } => *minimum_mixnode_performance,
+            _ => 0,
+        };
+        self.choose(rng, nodes, minimum_performance)
+    }
+
+    /// Chooses a gateway out of the provided slice according to the strategy.
+    /// Returns `None` if the slice is empty or none of the nodes satisfy the selection criteria.
+    pub fn choose_gateway<'a, R>(
+        &self,
+        rng: &mut R,
+        nodes: &'a [gateway::Node],
+    ) -> Option<&'a gateway::Node>
+    where
+        R: Rng + ?Sized,
+    {
+        let minimum_performance = match self {
+            RouteSelectionStrategy::PerformanceWeighted {
+                minimum_gateway_performance,
+                ..
+            } => *minimum_gateway_performance,
+            _ => 0,
+        };
+        self.choose(rng, nodes, minimum_performance)
+    }
+
+    fn choose<'a, R, N>(
+        &self,
+        rng: &mut R,
+        nodes: &'a [N],
+        minimum_performance: u8,
+    ) -> Option<&'a N>
+    where
+        R: Rng + ?Sized,
+        N: RoutingWeight,
+    {
+        let weighted = match self {
+            RouteSelectionStrategy::Uniform => return nodes.choose(rng),
+            RouteSelectionStrategy::StakeWeighted => {
+                // do not silently degrade into uniform selection if the topology has no stake information,
+                // nodes with unknown stake simply can't be chosen
+                nodes.choose_weighted(rng, |node| node.stake().unwrap_or_default() as f64)
+            }
+            RouteSelectionStrategy::PerformanceWeighted { .. } => {
+                // similarly to the stake, do not silently degrade into uniform selection
+                // if the topology has no performance information
+                nodes.choose_weighted(rng, |node| {
+                    performance_weight(node.performance(), minimum_performance)
+                })
+            }
+        };
+
+        match weighted {
+            Ok(node) => Some(node),
+            Err(WeightedError::NoItem) | Err(WeightedError::AllWeightsZero) => None,
+            Err(err) => {
+                // this should never happen as all of our weights are finite and non-negative
+                trace!("failed to perform weighted node selection: {err}");
+                None
+            }
+        }
+    }
+}
+
+fn performance_weight(performance: Option<Performance>, minimum_performance: u8) -> f64 {
+    match performance {
+        Some(performance) => {
+            let score = performance.round_to_integer();
+            if score < minimum_performance {
+                0.
+            } else {
+                score as f64
+            }
+        }
+        // nodes with unknown performance can't be shown to be above the threshold
+        None => 0.,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::{encryption, identity};
+    use nym_mixnet_contract_common::Layer;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mixnode(mix_id: u32, stake: Option<u128>, performance: Option<u8>) -> mix::Node {
+        mix::Node {
+            mix_id,
+            owner: None,
+            host: "3.3.3.3".parse().unwrap(),
+            mix_host: "3.3.3.3:1789".parse().unwrap(),
+            identity_key: identity::PublicKey::from_base58_string(
+                "3ebjp1Fb9hdcS1AR6AZihgeJiMHkB5jjJUsvqNnfQwU7",
+            )
+            .unwrap(),
+            sphinx_key: encryption::PublicKey::from_base58_string(
+                "C7cown6dYCLZpLiMFC1PaBmhvLvmJmLDJGeRTbPD45bX",
+            )
+            .unwrap(),
+            layer: Layer::One,
+            stake,
+            performance: performance.map(|p| Performance::from_percentage_value(p as u64).unwrap()),
+            sphinx_key_rotation: None,
+            version: "0.2.0".into(),
+        }
+    }
+
+    fn test_rng() -> StdRng {
+        StdRng::seed_from_u64(42)
+    }
+
+    #[test]
+    fn uniform_selection_ignores_weights() {
+        let nodes = vec![mixnode(1, Some(0), Some(0)), mixnode(2, None, None)];
+        let mut rng = test_rng();
+
+        let mut seen = [false; 2];
+        for _ in 0..100 {
+            let chosen = RouteSelectionStrategy::Uniform
+                .choose_mixnode(&mut rng, &nodes)
+                .unwrap();
+            seen[chosen.mix_id as usize - 1] = true;
+        }
+        assert!(seen.iter().all(|s| *s))
+    }
+
+    #[test]
+    fn stake_weighted_selection_never_picks_unstaked_nodes() {
+        let nodes = vec![mixnode(1, Some(1000), None), mixnode(2, Some(0), None)];
+        let mut rng = test_rng();
+
+        for _ in 0..100 {
+            let chosen = RouteSelectionStrategy::StakeWeighted
+                .choose_mixnode(&mut rng, &nodes)
+                .unwrap();
+            assert_eq!(chosen.mix_id, 1)
+        }
+    }
+
+    #[test]
+    fn stake_weighted_selection_rejects_nodes_without_stake() {
+        let mut rng = test_rng();
+
+        let partial = vec![mixnode(1, None, None), mixnode(2, Some(1000), None)];
+        for _ in 0..100 {
+            let chosen = RouteSelectionStrategy::StakeWeighted
+                .choose_mixnode(&mut rng, &partial)
+                .unwrap();
+            assert_eq!(chosen.mix_id, 2)
+        }
+
+        let nodes = vec![mixnode(1, None, None), mixnode(2, None, None)];
+        assert!(RouteSelectionStrategy::StakeWeighted
+            .choose_mixnode(&mut rng, &nodes)
+            .is_none())
+    }
+
+    #[test]
+    fn performance_weighted_selection_respects_the_floor() {
+        let strategy = RouteSelectionStrategy::PerformanceWeighted {
+            minimum_mixnode_performance: 50,
+            minimum_gateway_performance: 0,
+        };
+        let nodes = vec![
+            mixnode(1, None, Some(90)),
+            mixnode(2, None, Some(49)),
+            mixnode(3, None, None),
+        ];
+        let mut rng = test_rng();
+
+        for _ in 0..100 {
+            let chosen = strategy.choose_mixnode(&mut rng, &nodes).unwrap();
+            assert_eq!(chosen.mix_id, 1)
+        }
+
+        let bad_nodes = vec![mixnode(1, None, Some(10)), mixnode(2, None, Some(20))];
+        assert!(strategy.choose_mixnode(&mut rng, &bad_nodes).is_none());
+        assert!(strategy.choose_mixnode(&mut rng, &[]).is_none());
+    }
+
+    #[test]
+    fn performance_weighted_selection_rejects_nodes_without_performance() {
+        let strategy = RouteSelectionStrategy::PerformanceWeighted {
+            minimum_mixnode_performance: 0,
+            minimum_gateway_performance: 0,
+        };
+        let nodes = vec![mixnode(1, Some(1000), None), mixnode(2, Some(1000), None)];
+        let mut rng = test_rng();
+
+        assert!(strategy.choose_mixnode(&mut rng, &nodes).is_none())
+    }
+}
@@ -155,6 +155,9 @@ impl TryFrom<SerializableMixNode> for mix::Node {
                 .map_err(MixnodeConversionError::from)?,
             layer: mix::Layer::try_from(value.layer)
                 .map_err(|_| SerializableTopologyError::InvalidMixLayer { value: value.layer })?,
+            stake: None,
+            performance: None,
+            sphinx_key_rotation: None,
             version,
         })
     }
@@ -245,6 +248,9 @@ impl TryFrom<SerializableGateway> for gateway::Node {
                 .map_err(GatewayConversionError::from)?,
             sphinx_key: encryption::PublicKey::from_base58_string(&value.sphinx_key)
                 .map_err(GatewayConversionError::from)?,
+            stake: None,
+            performance: None,
+            sphinx_key_rotation: None,
             version,
         })
     }
@@ -0,0 +1,53 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_api_requests::models::SphinxKeyRotation as AnnouncedSphinxKeyRotation;
+use nym_crypto::asymmetric::encryption;
+use time::OffsetDateTime;
+
+/// Information about the upcoming sphinx key of a node that periodically rotates its keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SphinxKeyRotation {
+    /// Id of the rotation the current sphinx key of the node belongs to.
+    pub current_rotation_id: u32,
+
+    /// Sphinx key the node is going to switch to at `next_rotation_start`.
+    pub next_sphinx_key: encryption::PublicKey,
+
+    pub next_rotation_start: OffsetDateTime,
+}
+
+impl SphinxKeyRotation {
+    /// Parses the rotation information announced by a node, returning its current sphinx key
+    /// alongside the information about the upcoming one.
+    pub(crate) fn try_from_announced(
+        announced: &AnnouncedSphinxKeyRotation,
+    ) -> Result<(encryption::PublicKey, Self), encryption::KeyRecoveryError> {
+        let current = encryption::PublicKey::from_base58_string(&announced.current_x25519_sphinx)?;
+        let next = encryption::PublicKey::from_base58_string(&announced.next_x25519_sphinx)?;
+
+        Ok((
+            current,
+            SphinxKeyRotation {
+                current_rotation_id: announced.current_rotation_id,
+                next_sphinx_key: next,
+                next_rotation_start: announced.next_rotation_start,
+            },
+        ))
+    }
+}
+
+/// Switches to the upcoming sphinx key if its rotation has already started.
+pub(crate) fn apply_rotation(
+    sphinx_key: &mut encryption::PublicKey,
+    rotation: &mut Option<SphinxKeyRotation>,
+    now: OffsetDateTime,
+) {
+    let Some(next) = rotation else {
+        return;
+    };
+    if now >= next.next_rotation_start {
+        *sphinx_key = next.next_sphinx_key;
+        *rotation = None;
+    }
+}
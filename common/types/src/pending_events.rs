@@ -5,7 +5,7 @@ use crate::currency::{DecCoin, RegisteredCoins};
 use crate::error::TypesError;
 use crate::mixnode::MixNodeCostParams;
 use nym_mixnet_contract_common::{
-    BlockHeight, EpochEventId, IntervalEventId, IntervalRewardingParamsUpdate, MixId,
+    BlockHeight, EpochEventId, IdentityKey, IntervalEventId, IntervalRewardingParamsUpdate, MixId,
     PendingEpochEvent as MixnetContractPendingEpochEvent,
     PendingEpochEventKind as MixnetContractPendingEpochEventKind,
     PendingIntervalEvent as MixnetContractPendingIntervalEvent,
@@ -57,6 +57,13 @@ pub enum PendingEpochEventData {
         mix_id: MixId,
         proxy: Option<String>,
     },
+    Redelegate {
+        owner: String,
+        from: MixId,
+        to: MixId,
+        amount: DecCoin,
+        proxy: Option<String>,
+    },
     PledgeMore {
         mix_id: MixId,
         amount: DecCoin,
@@ -71,6 +78,15 @@ pub enum PendingEpochEventData {
     UpdateActiveSetSize {
         new_size: u32,
     },
+    DelegateToGateway {
+        owner: String,
+        identity: IdentityKey,
+        amount: DecCoin,
+    },
+    UndelegateFromGateway {
+        owner: String,
+        identity: IdentityKey,
+    },
 }
 
 impl PendingEpochEventData {
@@ -101,6 +117,20 @@ impl PendingEpochEventData {
                 mix_id,
                 proxy: proxy.map(|p| p.into_string()),
             }),
+            MixnetContractPendingEpochEventKind::Redelegate {
+                owner,
+                from,
+                to,
+                amount,
+                proxy,
+                ..
+            } => Ok(PendingEpochEventData::Redelegate {
+                owner: owner.into_string(),
+                from,
+                to,
+                amount: reg.attempt_convert_to_display_dec_coin(amount.into())?,
+                proxy: proxy.map(|p| p.into_string()),
+            }),
             MixnetContractPendingEpochEventKind::PledgeMore { mix_id, amount } => {
                 Ok(PendingEpochEventData::PledgeMore {
                     mix_id,
@@ -120,6 +150,21 @@ impl PendingEpochEventData {
             MixnetContractPendingEpochEventKind::UpdateActiveSetSize { new_size } => {
                 Ok(PendingEpochEventData::UpdateActiveSetSize { new_size })
             }
+            MixnetContractPendingEpochEventKind::DelegateToGateway {
+                owner,
+                identity,
+                amount,
+            } => Ok(PendingEpochEventData::DelegateToGateway {
+                owner: owner.into_string(),
+                identity,
+                amount: reg.attempt_convert_to_display_dec_coin(amount.into())?,
+            }),
+            MixnetContractPendingEpochEventKind::UndelegateFromGateway { owner, identity } => {
+                Ok(PendingEpochEventData::UndelegateFromGateway {
+                    owner: owner.into_string(),
+                    identity,
+                })
+            }
         }
     }
 }
@@ -171,6 +216,10 @@ pub enum PendingIntervalEventData {
         epochs_in_interval: u32,
         epoch_duration_secs: u64,
     },
+    ChangeGatewayCostParams {
+        identity: IdentityKey,
+        new_costs: MixNodeCostParams,
+    },
 }
 
 impl PendingIntervalEventData {
@@ -197,6 +246,15 @@ impl PendingIntervalEventData {
                 epochs_in_interval,
                 epoch_duration_secs,
             }),
+            MixnetContractPendingIntervalEventKind::ChangeGatewayCostParams {
+                identity,
+                new_costs,
+            } => Ok(PendingIntervalEventData::ChangeGatewayCostParams {
+                identity,
+                new_costs: MixNodeCostParams::from_mixnet_contract_mixnode_cost_params(
+                    new_costs, reg,
+                )?,
+            }),
         }
     }
 }
@@ -123,6 +123,7 @@ impl From<DebugWasm> for ConfigDebug {
             acknowledgements: debug.acknowledgements.into(),
             topology: debug.topology.into(),
             reply_surbs: debug.reply_surbs.into(),
+            message_reconstruction: Default::default(),
         }
     }
 }
@@ -195,6 +196,7 @@ impl From<TrafficWasm> for ConfigTraffic {
             primary_packet_size: PacketSize::RegularPacket,
             secondary_packet_size: use_extended_packet_size,
             packet_type,
+            payload_compression: None,
         }
     }
 }
@@ -392,6 +394,7 @@ impl From<TopologyWasm> for ConfigTopology {
             topology_structure: Default::default(),
             minimum_mixnode_performance: topology.minimum_mixnode_performance,
             minimum_gateway_performance: topology.minimum_gateway_performance,
+            route_selection: Default::default(),
         }
     }
 }
@@ -3,3 +3,4 @@
 
 pub const BLACKLIST_PROPOSAL_REPLY_ID: u64 = 7759;
 pub const REDEMPTION_PROPOSAL_REPLY_ID: u64 = 2137;
+pub const REFUND_PROPOSAL_REPLY_ID: u64 = 4182;
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::contract::NymEcashContract;
-use crate::helpers::{create_batch_redemption_proposal, create_blacklist_proposal, ProposalId};
+use crate::helpers::{
+    create_batch_redemption_proposal, create_blacklist_proposal, create_deposit_refund_proposal,
+    ProposalId,
+};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{to_binary, Addr, Deps, Storage, SubMsg};
 use cw3::ProposalResponse;
+use nym_ecash_contract_common::deposit::DepositId;
 use nym_ecash_contract_common::EcashContractError;
 use nym_multisig_contract_common::msg::QueryMsg as MultisigQueryMsg;
 use nym_network_defaults::TICKETBOOK_SIZE;
@@ -59,6 +63,21 @@ impl NymEcashContract<'_> {
         .map_err(Into::into)
     }
 
+    pub(crate) fn create_refund_proposal(
+        &self,
+        ctx: &ExecCtx,
+        deposit_id: DepositId,
+    ) -> Result<SubMsg, EcashContractError> {
+        let multisig_addr = self.must_get_multisig_addr(ctx.deps.as_ref())?;
+
+        create_deposit_refund_proposal(
+            deposit_id,
+            ctx.env.contract.address.to_string(),
+            multisig_addr.into_string(),
+        )
+        .map_err(Into::into)
+    }
+
     // temporarily dead
     #[allow(dead_code)]
     pub(crate) fn create_blacklist_proposal(
@@ -1,7 +1,9 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::constants::{BLACKLIST_PROPOSAL_REPLY_ID, REDEMPTION_PROPOSAL_REPLY_ID};
+use crate::constants::{
+    BLACKLIST_PROPOSAL_REPLY_ID, REDEMPTION_PROPOSAL_REPLY_ID, REFUND_PROPOSAL_REPLY_ID,
+};
 use crate::contract::helpers::Invariants;
 use crate::deposit::DepositStorage;
 use crate::helpers::{
@@ -9,8 +11,9 @@ use crate::helpers::{
     CONTRACT_NAME, CONTRACT_VERSION, DEPOSITS_PAGE_DEFAULT_LIMIT, DEPOSITS_PAGE_MAX_LIMIT,
 };
 use cosmwasm_std::{
-    coin, BankMsg, Coin, Decimal, Event, Order, Reply, Response, StdResult, Uint128,
+    coin, BankMsg, Coin, Decimal, Event, Order, Reply, Response, StdError, StdResult, Uint128,
 };
+use cw3::Status;
 use cw4::Cw4Contract;
 use cw_controllers::Admin;
 use cw_storage_plus::{Bound, Item, Map};
@@ -19,9 +22,13 @@ use nym_ecash_contract_common::blacklist::{
     BlacklistedAccount, BlacklistedAccountResponse, Blacklisting, PagedBlacklistedAccountResponse,
 };
 use nym_ecash_contract_common::counters::PoolCounters;
-use nym_ecash_contract_common::deposit::{DepositData, DepositResponse, PagedDepositsResponse};
+use nym_ecash_contract_common::deposit::{
+    deposit_refund_request_plaintext, DepositData, DepositId, DepositRefund, DepositRefundResponse,
+    DepositResponse, PagedDepositsResponse,
+};
 use nym_ecash_contract_common::events::{
-    DEPOSITED_FUNDS_EVENT_TYPE, DEPOSIT_ID, PROPOSAL_ID_ATTRIBUTE_NAME,
+    DEPOSITED_FUNDS_EVENT_TYPE, DEPOSIT_ID, DEPOSIT_REFUND_EVENT_TYPE, PROPOSAL_ID_ATTRIBUTE_NAME,
+    REFUND_RECEIVER,
 };
 use nym_ecash_contract_common::EcashContractError;
 use nym_network_defaults::TICKETBOOK_SIZE;
@@ -43,6 +50,7 @@ pub struct NymEcashContract<'a> {
     pub(crate) blacklist: Map<'a, BlacklistKey, Blacklisting>,
 
     pub(crate) deposits: DepositStorage<'a>,
+    pub(crate) deposit_refunds: Map<'a, DepositId, DepositRefund>,
 }
 
 #[entry_points]
@@ -59,6 +67,7 @@ impl NymEcashContract<'_> {
             expected_invariants: Item::new("expected_invariants"),
             blacklist: Map::new("blacklist"),
             deposits: DepositStorage::new(),
+            deposit_refunds: Map::new("deposit_refunds"),
         }
     }
 
@@ -97,6 +106,7 @@ impl NymEcashContract<'_> {
             &PoolCounters {
                 total_deposited: coin(0, &deposit_amount.denom),
                 total_redeemed: coin(0, &deposit_amount.denom),
+                total_refunded: coin(0, &deposit_amount.denom),
             },
         )?;
 
@@ -207,6 +217,20 @@ impl NymEcashContract<'_> {
         })
     }
 
+    #[msg(query)]
+    pub fn get_deposit_refund(
+        &self,
+        ctx: QueryCtx,
+        deposit_id: u32,
+    ) -> StdResult<DepositRefundResponse> {
+        Ok(DepositRefundResponse {
+            id: deposit_id,
+            refund: self
+                .deposit_refunds
+                .may_load(ctx.deps.storage, deposit_id)?,
+        })
+    }
+
     /*=====================
     ======EXECUTIONS=======
     =====================*/
@@ -310,6 +334,115 @@ impl NymEcashContract<'_> {
         }))
     }
 
+    #[msg(exec)]
+    pub fn request_deposit_refund(
+        &self,
+        ctx: ExecCtx,
+        deposit_id: u32,
+        signature_bs58: String,
+    ) -> Result<Response, EcashContractError> {
+        let Some(deposit) = self.deposits.try_load_by_id(ctx.deps.storage, deposit_id)? else {
+            return Err(EcashContractError::NonExistentDeposit { deposit_id });
+        };
+
+        // only allow a single refund attempt at a time,
+        // unless the previous one has been rejected by the signers
+        if let Some(existing) = self
+            .deposit_refunds
+            .may_load(ctx.deps.storage, deposit_id)?
+        {
+            let rejected = match existing.proposal_id {
+                Some(proposal_id) => {
+                    self.query_multisig_proposal(ctx.deps.as_ref(), proposal_id)?
+                        .status
+                        == Status::Rejected
+                }
+                None => false,
+            };
+            if !rejected {
+                return Err(EcashContractError::RefundAlreadyRequested { deposit_id });
+            }
+        }
+
+        // the request must be signed with the identity key that was used for the deposit
+        // and bound to the account that's going to receive the funds
+        let Ok(signature) = bs58::decode(&signature_bs58).into_vec() else {
+            return Err(EcashContractError::MalformedRefundSignature);
+        };
+        let public_key = deposit.to_bytes()?;
+        let plaintext = deposit_refund_request_plaintext(deposit_id, ctx.info.sender.as_str());
+
+        let valid = ctx
+            .deps
+            .api
+            .ed25519_verify(&plaintext, &signature, &public_key)
+            .map_err(|_| EcashContractError::MalformedRefundSignature)?;
+        if !valid {
+            return Err(EcashContractError::InvalidRefundSignature { deposit_id });
+        }
+
+        self.deposit_refunds.save(
+            ctx.deps.storage,
+            deposit_id,
+            &DepositRefund {
+                receiver: ctx.info.sender.clone(),
+                proposal_id: None,
+            },
+        )?;
+
+        let msg = self.create_refund_proposal(&ctx, deposit_id)?;
+        Ok(Response::new().add_submessage(msg))
+    }
+
+    #[msg(exec)]
+    pub fn refund_deposit(
+        &self,
+        ctx: ExecCtx,
+        deposit_id: u32,
+    ) -> Result<Response, EcashContractError> {
+        // only a mutlisig proposal can do that
+        self.multisig
+            .assert_admin(ctx.deps.as_ref(), &ctx.info.sender)?;
+
+        let Some(refund) = self
+            .deposit_refunds
+            .may_load(ctx.deps.storage, deposit_id)?
+        else {
+            return Err(EcashContractError::NoPendingRefund { deposit_id });
+        };
+
+        if self
+            .deposits
+            .try_load_by_id(ctx.deps.storage, deposit_id)?
+            .is_none()
+        {
+            return Err(EcashContractError::NonExistentDeposit { deposit_id });
+        }
+
+        // remove the deposit so that no ticketbook could ever be issued for it
+        self.deposits.remove_deposit(ctx.deps.storage, deposit_id);
+        self.deposit_refunds.remove(ctx.deps.storage, deposit_id);
+
+        let deposit_amount = self.config.load(ctx.deps.storage)?.deposit_amount;
+
+        self.pool_counters
+            .update(ctx.deps.storage, |mut counters| -> StdResult<_> {
+                counters.total_refunded.amount += deposit_amount.amount;
+                Ok(counters)
+            })?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: refund.receiver.to_string(),
+                amount: vec![deposit_amount],
+            })
+            .add_event(
+                Event::new(DEPOSIT_REFUND_EVENT_TYPE)
+                    .add_attribute(DEPOSIT_ID, deposit_id.to_string())
+                    .add_attribute(REFUND_RECEIVER, refund.receiver),
+            ))
+    }
+
     #[msg(exec)]
     pub fn update_admin(
         &self,
@@ -403,6 +536,7 @@ impl NymEcashContract<'_> {
             n if n == REDEMPTION_PROPOSAL_REPLY_ID => {
                 self.handle_redemption_proposal_reply(ctx, msg)
             }
+            n if n == REFUND_PROPOSAL_REPLY_ID => self.handle_refund_proposal_reply(ctx, msg),
             other => Err(EcashContractError::InvalidReplyId { id: other }),
         }
     }
@@ -439,6 +573,33 @@ impl NymEcashContract<'_> {
         Ok(Response::new().set_data(proposal_id.to_be_bytes()))
     }
 
+    fn handle_refund_proposal_reply(
+        &self,
+        ctx: ReplyCtx,
+        msg: Reply,
+    ) -> Result<Response, EcashContractError> {
+        let proposal_id = msg.multisig_proposal_id()?;
+
+        let proposal = self.query_multisig_proposal(ctx.deps.as_ref(), proposal_id)?;
+        let deposit_id: DepositId = proposal.description.parse().map_err(|_| {
+            StdError::generic_err("the deposit refund proposal has malformed description")
+        })?;
+
+        self.deposit_refunds.update(
+            ctx.deps.storage,
+            deposit_id,
+            |refund| -> Result<_, EcashContractError> {
+                let mut refund =
+                    refund.ok_or(EcashContractError::NoPendingRefund { deposit_id })?;
+                refund.proposal_id = Some(proposal_id);
+                Ok(refund)
+            },
+        )?;
+
+        // emit the proposal_id in the response data for easy client access
+        Ok(Response::new().set_data(proposal_id.to_be_bytes()))
+    }
+
     /*=====================
     =======MIGRATION=======
     =====================*/
@@ -447,6 +608,13 @@ impl NymEcashContract<'_> {
         set_build_information!(ctx.deps.storage)?;
         cw2::ensure_from_older_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+        // the refund counter didn't exist before, so make sure it's using the correct denom
+        let mut counters = self.pool_counters.load(ctx.deps.storage)?;
+        if counters.total_refunded.denom.is_empty() {
+            counters.total_refunded = coin(0, &counters.total_deposited.denom);
+            self.pool_counters.save(ctx.deps.storage, &counters)?;
+        }
+
         Ok(Response::new())
     }
 }
@@ -59,8 +59,36 @@ impl TestSetup {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use nym_ecash_contract_common::deposit::Deposit;
+    use crate::support::tests::test_rng;
+    use cosmwasm_std::{BankMsg, CosmosMsg};
+    use nym_crypto::asymmetric::ed25519;
+    use nym_ecash_contract_common::deposit::{deposit_refund_request_plaintext, Deposit};
+    use nym_ecash_contract_common::EcashContractError;
     use sylvia::anyhow;
+    use sylvia::types::ExecCtx;
+
+    fn exec_ctx<'a>(
+        deps: &'a mut OwnedDeps<MemoryStorage, MockApi, MockQuerier<Empty>>,
+        env: &Env,
+        sender: &str,
+    ) -> ExecCtx<'a> {
+        ExecCtx::from((deps.as_mut(), env.clone(), mock_info(sender, &[])))
+    }
+
+    fn add_deposit(test: &mut TestSetup, keypair: &ed25519::KeyPair) -> u32 {
+        test.contract
+            .deposits
+            .save_deposit(
+                test.deps.as_mut().storage,
+                keypair.public_key().to_base58_string(),
+            )
+            .unwrap()
+    }
+
+    fn refund_signature(keypair: &ed25519::KeyPair, deposit_id: u32, receiver: &str) -> String {
+        let plaintext = deposit_refund_request_plaintext(deposit_id, receiver);
+        keypair.private_key().sign(plaintext).to_base58_string()
+    }
 
     #[test]
     fn deposit_queries() -> anyhow::Result<()> {
@@ -86,4 +114,211 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn requesting_deposit_refund() {
+        let mut test = TestSetup::init();
+        let mut rng = test_rng();
+        let keypair = ed25519::KeyPair::new(&mut rng);
+        let receiver = "receiver";
+
+        // deposit doesn't exist
+        let signature = refund_signature(&keypair, 42, receiver);
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test.contract.request_deposit_refund(ctx, 42, signature);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::NonExistentDeposit { deposit_id: 42 }
+        );
+
+        let deposit_id = add_deposit(&mut test, &keypair);
+
+        // malformed signature
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test
+            .contract
+            .request_deposit_refund(ctx, deposit_id, "foomp".to_string());
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::MalformedRefundSignature
+        );
+
+        // signature made for a different receiver
+        let signature = refund_signature(&keypair, deposit_id, "someone-else");
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test
+            .contract
+            .request_deposit_refund(ctx, deposit_id, signature);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::InvalidRefundSignature { deposit_id }
+        );
+
+        // signature made with a different key
+        let other_keypair = ed25519::KeyPair::new(&mut rng);
+        let signature = refund_signature(&other_keypair, deposit_id, receiver);
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test
+            .contract
+            .request_deposit_refund(ctx, deposit_id, signature);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::InvalidRefundSignature { deposit_id }
+        );
+
+        // valid request creates the multisig proposal
+        let signature = refund_signature(&keypair, deposit_id, receiver);
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test
+            .contract
+            .request_deposit_refund(ctx, deposit_id, signature.clone())
+            .unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let refund = test
+            .contract
+            .get_deposit_refund(test.query_ctx(), deposit_id)
+            .unwrap()
+            .refund
+            .unwrap();
+        assert_eq!(refund.receiver, Addr::unchecked(receiver));
+        assert!(refund.proposal_id.is_none());
+
+        // and it can't be requested again whilst it's pending
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test
+            .contract
+            .request_deposit_refund(ctx, deposit_id, signature);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::RefundAlreadyRequested { deposit_id }
+        );
+    }
+
+    #[test]
+    fn refunding_deposit() {
+        let mut test = TestSetup::init();
+        let mut rng = test_rng();
+        let keypair = ed25519::KeyPair::new(&mut rng);
+        let receiver = "receiver";
+
+        let deposit_id = add_deposit(&mut test, &keypair);
+
+        // there's nothing to refund
+        let ctx = exec_ctx(&mut test.deps, &test.env, "multisig");
+        let res = test.contract.refund_deposit(ctx, deposit_id);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::NoPendingRefund { deposit_id }
+        );
+
+        let signature = refund_signature(&keypair, deposit_id, receiver);
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        test.contract
+            .request_deposit_refund(ctx, deposit_id, signature)
+            .unwrap();
+
+        // only the multisig contract can execute the refund
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        assert!(test.contract.refund_deposit(ctx, deposit_id).is_err());
+
+        let ctx = exec_ctx(&mut test.deps, &test.env, "multisig");
+        let res = test.contract.refund_deposit(ctx, deposit_id).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: receiver.to_string(),
+                amount: vec![coin(75000000, TEST_DENOM)],
+            })
+        );
+
+        // the deposit is gone alongside the refund request
+        let res = test
+            .contract
+            .get_deposit(test.query_ctx(), deposit_id)
+            .unwrap();
+        assert!(res.deposit.is_none());
+        let res = test
+            .contract
+            .get_deposit_refund(test.query_ctx(), deposit_id)
+            .unwrap();
+        assert!(res.refund.is_none());
+    }
+
+    #[test]
+    fn refunds_are_tracked_in_pool_counters() {
+        let mut test = TestSetup::init();
+        let mut rng = test_rng();
+        let keypair = ed25519::KeyPair::new(&mut rng);
+        let receiver = "receiver";
+
+        let deposit_id = add_deposit(&mut test, &keypair);
+        let signature = refund_signature(&keypair, deposit_id, receiver);
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        test.contract
+            .request_deposit_refund(ctx, deposit_id, signature)
+            .unwrap();
+
+        let counters = test
+            .contract
+            .pool_counters
+            .load(&test.deps.storage)
+            .unwrap();
+        assert_eq!(counters.total_refunded, coin(0, TEST_DENOM));
+
+        let ctx = exec_ctx(&mut test.deps, &test.env, "multisig");
+        test.contract.refund_deposit(ctx, deposit_id).unwrap();
+
+        let counters = test
+            .contract
+            .pool_counters
+            .load(&test.deps.storage)
+            .unwrap();
+        assert_eq!(counters.total_refunded, coin(75000000, TEST_DENOM));
+        assert_eq!(counters.total_redeemed, coin(0, TEST_DENOM));
+    }
+
+    #[test]
+    fn deposit_cannot_be_refunded_twice() {
+        let mut test = TestSetup::init();
+        let mut rng = test_rng();
+        let keypair = ed25519::KeyPair::new(&mut rng);
+        let receiver = "receiver";
+
+        let deposit_id = add_deposit(&mut test, &keypair);
+        let signature = refund_signature(&keypair, deposit_id, receiver);
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        test.contract
+            .request_deposit_refund(ctx, deposit_id, signature.clone())
+            .unwrap();
+
+        let ctx = exec_ctx(&mut test.deps, &test.env, "multisig");
+        test.contract.refund_deposit(ctx, deposit_id).unwrap();
+
+        // executing the same refund again
+        let ctx = exec_ctx(&mut test.deps, &test.env, "multisig");
+        let res = test.contract.refund_deposit(ctx, deposit_id);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::NoPendingRefund { deposit_id }
+        );
+
+        // requesting another refund with the same signature
+        let ctx = exec_ctx(&mut test.deps, &test.env, receiver);
+        let res = test
+            .contract
+            .request_deposit_refund(ctx, deposit_id, signature);
+        assert_eq!(
+            res.unwrap_err(),
+            EcashContractError::NonExistentDeposit { deposit_id }
+        );
+
+        // only a single refund has been paid out
+        let counters = test
+            .contract
+            .pool_counters
+            .load(&test.deps.storage)
+            .unwrap();
+        assert_eq!(counters.total_refunded, coin(75000000, TEST_DENOM));
+    }
 }
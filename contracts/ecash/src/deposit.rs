@@ -56,6 +56,12 @@ impl<'a> DepositStorage<'a> {
 
         Ok(Some(Deposit::try_from_bytes(&deposit_bytes)?))
     }
+
+    pub fn remove_deposit(&self, storage: &mut dyn Storage, id: DepositId) {
+        let storage_key = StoredDeposits::storage_key(id);
+        storage.remove(&storage_key)
+    }
+
     pub fn range(
         &'a self,
         store: &'a dyn Storage,
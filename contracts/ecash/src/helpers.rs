@@ -1,15 +1,20 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::constants::{BLACKLIST_PROPOSAL_REPLY_ID, REDEMPTION_PROPOSAL_REPLY_ID};
+use crate::constants::{
+    BLACKLIST_PROPOSAL_REPLY_ID, REDEMPTION_PROPOSAL_REPLY_ID, REFUND_PROPOSAL_REPLY_ID,
+};
 use cosmwasm_std::{
     to_binary, Addr, Coin, CosmosMsg, Decimal, Reply, StdError, StdResult, SubMsg, SubMsgResult,
     WasmMsg,
 };
 use cw4::Cw4Contract;
 use nym_contracts_common::events::try_find_attribute;
+use nym_ecash_contract_common::deposit::DepositId;
 use nym_ecash_contract_common::events::{PROPOSAL_ID_ATTRIBUTE_NAME, WASM_EVENT_NAME};
-use nym_ecash_contract_common::redeem_credential::BATCH_REDEMPTION_PROPOSAL_TITLE;
+use nym_ecash_contract_common::redeem_credential::{
+    BATCH_REDEMPTION_PROPOSAL_TITLE, DEPOSIT_REFUND_PROPOSAL_TITLE,
+};
 use nym_ecash_contract_common::{msg::ExecuteMsg, EcashContractError};
 use nym_multisig_contract_common::msg::ExecuteMsg as MultisigExecuteMsg;
 use serde::{Deserialize, Serialize};
@@ -69,6 +74,34 @@ pub(crate) fn create_batch_redemption_proposal(
     Ok(submsg)
 }
 
+pub(crate) fn create_deposit_refund_proposal(
+    deposit_id: DepositId,
+    ecash_bandwidth_address: String,
+    multisig_addr: String,
+) -> StdResult<SubMsg> {
+    let refund_req = ExecuteMsg::RefundDeposit { deposit_id };
+    let refund_req_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: ecash_bandwidth_address,
+        msg: to_binary(&refund_req)?,
+        funds: vec![],
+    });
+    let req = MultisigExecuteMsg::Propose {
+        title: DEPOSIT_REFUND_PROPOSAL_TITLE.to_string(),
+        description: deposit_id.to_string(),
+        msgs: vec![refund_req_msg],
+        latest: None,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: multisig_addr,
+        msg: to_binary(&req)?,
+        funds: vec![],
+    });
+
+    let submsg = SubMsg::reply_always(msg, REFUND_PROPOSAL_REPLY_ID);
+
+    Ok(submsg)
+}
+
 pub(crate) fn create_blacklist_proposal(
     public_key: String,
     ecash_bandwidth_address: String,
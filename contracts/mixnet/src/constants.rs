@@ -9,6 +9,9 @@ pub const INITIAL_GATEWAY_PLEDGE_AMOUNT: Uint128 = Uint128::new(100_000_000);
 /// Constant specifying minimum of coin amount required to bond a mixnode
 pub const INITIAL_MIXNODE_PLEDGE_AMOUNT: Uint128 = Uint128::new(100_000_000);
 
+/// Id of the reply to the vesting contract failing to track a compounded delegator reward
+pub const TRACK_COMPOUNDED_REWARD_REPLY_ID: u64 = 1;
+
 // retrieval limits
 // TODO: those would need to be empirically verified whether they're not way too small or way too high
 pub const GATEWAY_BOND_DEFAULT_RETRIEVAL_LIMIT: u32 = 100;
@@ -26,6 +29,9 @@ pub const UNBONDED_MIXNODES_MAX_RETRIEVAL_LIMIT: u32 = 300;
 pub const DELEGATION_PAGE_DEFAULT_RETRIEVAL_LIMIT: u32 = 250;
 pub const DELEGATION_PAGE_MAX_RETRIEVAL_LIMIT: u32 = 300;
 
+pub const GATEWAY_DELEGATION_PAGE_DEFAULT_RETRIEVAL_LIMIT: u32 = 250;
+pub const GATEWAY_DELEGATION_PAGE_MAX_RETRIEVAL_LIMIT: u32 = 300;
+
 pub const EPOCH_EVENTS_DEFAULT_RETRIEVAL_LIMIT: u32 = 200;
 pub const EPOCH_EVENTS_MAX_RETRIEVAL_LIMIT: u32 = 250;
 
@@ -42,9 +48,17 @@ pub const FAMILIES_MAX_RETRIEVAL_LIMIT: u32 = 20;
 pub const DELEGATION_PK_NAMESPACE: &str = "dl";
 pub const DELEGATION_OWNER_IDX_NAMESPACE: &str = "dlo";
 pub const DELEGATION_MIXNODE_IDX_NAMESPACE: &str = "dlm";
+pub const AUTO_COMPOUNDING_DELEGATIONS_NAMESPACE: &str = "acd";
+pub const AUTO_COMPOUNDING_PROGRESS_KEY: &str = "acp";
 
 pub const GATEWAYS_PK_NAMESPACE: &str = "gt";
 pub const GATEWAYS_OWNER_IDX_NAMESPACE: &str = "gto";
+pub const GATEWAYS_REWARDING_PK_NAMESPACE: &str = "gtr";
+pub const GATEWAY_REWARDING_PARAMS_KEY: &str = "gtrparams";
+pub const GATEWAYS_REWARDED_IN_EPOCH_KEY: &str = "gtrepoch";
+
+pub const GATEWAY_DELEGATION_PK_NAMESPACE: &str = "gdl";
+pub const GATEWAY_DELEGATION_OWNER_IDX_NAMESPACE: &str = "gdlo";
 
 pub const REWARDED_SET_KEY: &str = "rs";
 pub const CURRENT_EPOCH_STATUS_KEY: &str = "ces";
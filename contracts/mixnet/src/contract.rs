@@ -1,18 +1,23 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::constants::{INITIAL_GATEWAY_PLEDGE_AMOUNT, INITIAL_MIXNODE_PLEDGE_AMOUNT};
+use crate::constants::{
+    INITIAL_GATEWAY_PLEDGE_AMOUNT, INITIAL_MIXNODE_PLEDGE_AMOUNT, TRACK_COMPOUNDED_REWARD_REPLY_ID,
+};
+use crate::gateways::storage as gateways_storage;
 use crate::interval::storage as interval_storage;
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::mixnodes::storage as mixnode_storage;
 use crate::rewards::storage as rewards_storage;
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Coin, Deps, DepsMut, Env, MessageInfo, QueryResponse, Response,
+    entry_point, to_binary, Addr, Coin, Deps, DepsMut, Env, MessageInfo, QueryResponse, Reply,
+    Response, SubMsgResult,
 };
 use mixnet_contract_common::error::MixnetContractError;
+use mixnet_contract_common::events::new_compounded_reward_tracking_failure_event;
 use mixnet_contract_common::{
-    ContractState, ContractStateParams, ExecuteMsg, InstantiateMsg, Interval, MigrateMsg,
-    OperatingCostRange, ProfitMarginRange, QueryMsg,
+    ContractState, ContractStateParams, ExecuteMsg, GatewayRewardingParams, InstantiateMsg,
+    Interval, MigrateMsg, OperatingCostRange, ProfitMarginRange, QueryMsg,
 };
 use nym_contracts_common::set_build_information;
 
@@ -92,6 +97,11 @@ pub fn instantiate(
     )?;
     mixnet_params_storage::initialise_storage(deps.storage, state)?;
     mixnode_storage::initialise_storage(deps.storage)?;
+    // gateways are not rewarded until the owner explicitly sets the budget
+    gateways_storage::initialise_storage(
+        deps.storage,
+        GatewayRewardingParams::disabled(reward_params.interval.stake_saturation_point),
+    )?;
     rewards_storage::initialise_storage(deps.storage, reward_params)?;
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     set_build_information!(deps.storage)?;
@@ -236,6 +246,11 @@ pub fn execute(
         ExecuteMsg::UpdateGatewayConfig { new_config } => {
             crate::gateways::transactions::try_update_gateway_config(deps, info, new_config)
         }
+        ExecuteMsg::UpdateGatewayCostParams { new_costs } => {
+            crate::gateways::transactions::try_update_gateway_cost_params(
+                deps, env, info, new_costs,
+            )
+        }
 
         // delegation-related:
         ExecuteMsg::DelegateToMixnode { mix_id } => {
@@ -246,12 +261,43 @@ pub fn execute(
                 deps, env, info, mix_id,
             )
         }
+        ExecuteMsg::RedelegateFromMixnode { from, to, amount } => {
+            crate::delegations::transactions::try_redelegate_from_mixnode(
+                deps, env, info, from, to, amount,
+            )
+        }
+        ExecuteMsg::SetDelegationAutoCompounding { mix_id, enabled } => {
+            crate::delegations::transactions::try_set_delegation_auto_compounding(
+                deps, info, mix_id, enabled,
+            )
+        }
+        ExecuteMsg::DelegateToGateway { identity } => {
+            crate::delegations::transactions::try_delegate_to_gateway(deps, env, info, identity)
+        }
+        ExecuteMsg::UndelegateFromGateway { identity } => {
+            crate::delegations::transactions::try_remove_delegation_from_gateway(
+                deps, env, info, identity,
+            )
+        }
 
         // reward-related
         ExecuteMsg::RewardMixnode {
             mix_id,
             performance,
         } => crate::rewards::transactions::try_reward_mixnode(deps, env, info, mix_id, performance),
+        ExecuteMsg::RewardGateway {
+            identity,
+            performance,
+        } => {
+            crate::rewards::transactions::try_reward_gateway(deps, env, info, identity, performance)
+        }
+        ExecuteMsg::UpdateGatewayRewardingParams { updated_params } => {
+            crate::rewards::transactions::try_update_gateway_rewarding_params(
+                deps,
+                info,
+                updated_params,
+            )
+        }
 
         ExecuteMsg::WithdrawOperatorReward {} => {
             crate::rewards::transactions::try_withdraw_operator_reward(deps, info)
@@ -284,6 +330,7 @@ pub fn execute(
         | ExecuteMsg::UpdateGatewayConfigOnBehalf { .. }
         | ExecuteMsg::DelegateToMixnodeOnBehalf { .. }
         | ExecuteMsg::UndelegateFromMixnodeOnBehalf { .. }
+        | ExecuteMsg::RedelegateFromMixnodeOnBehalf { .. }
         | ExecuteMsg::WithdrawOperatorRewardOnBehalf { .. }
         | ExecuteMsg::WithdrawDelegatorRewardOnBehalf { .. } => {
             Err(MixnetContractError::DisabledVestingOperation)
@@ -297,6 +344,21 @@ pub fn execute(
     }
 }
 
+#[entry_point]
+pub fn reply(_deps: DepsMut<'_>, _env: Env, msg: Reply) -> Result<Response, MixnetContractError> {
+    match msg.id {
+        // the vesting contract failed to track a compounded reward (for example because the
+        // vesting account no longer exists). it must not prevent the epoch from being advanced
+        TRACK_COMPOUNDED_REWARD_REPLY_ID => match msg.result {
+            SubMsgResult::Err(err) => {
+                Ok(Response::new().add_event(new_compounded_reward_tracking_failure_event(err)))
+            }
+            SubMsgResult::Ok(_) => Ok(Response::new()),
+        },
+        id => Err(MixnetContractError::UnknownReplyId { id }),
+    }
+}
+
 #[entry_point]
 pub fn query(
     deps: Deps<'_>,
@@ -414,6 +476,27 @@ pub fn query(
         QueryMsg::GetOwnedGateway { address } => to_binary(
             &crate::gateways::queries::query_owned_gateway(deps, address)?,
         ),
+        QueryMsg::GetGatewayRewardingParams {} => to_binary(
+            &crate::gateways::queries::query_gateway_rewarding_params(deps)?,
+        ),
+        QueryMsg::GetGatewayRewardingDetails { identity } => to_binary(
+            &crate::gateways::queries::query_gateway_rewarding_details(deps, identity)?,
+        ),
+        QueryMsg::GetGatewayDelegations {
+            identity,
+            start_after,
+            limit,
+        } => to_binary(&crate::gateways::queries::query_gateway_delegations_paged(
+            deps,
+            identity,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetPendingGatewayDelegatorReward { address, identity } => to_binary(
+            &crate::gateways::queries::query_pending_gateway_delegator_reward(
+                deps, address, identity,
+            )?,
+        ),
 
         // delegation-related:
         QueryMsg::GetMixnodeDelegations {
@@ -536,6 +619,10 @@ pub fn migrate(
     cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     crate::queued_migrations::vesting_purge(deps.branch())?;
+    crate::queued_migrations::introduce_gateway_rewarding(
+        deps.branch(),
+        msg.gateway_rewarding_params,
+    )?;
 
     // due to circular dependency on contract addresses (i.e. mixnet contract requiring vesting contract address
     // and vesting contract requiring the mixnet contract address), if we ever want to deploy any new fresh
@@ -655,4 +742,35 @@ mod tests {
         assert_eq!(interval.current_interval_id(), 0);
         assert_eq!(interval.current_epoch_id(), 0);
     }
+
+    #[test]
+    fn failing_to_track_compounded_reward_is_not_a_hard_error() {
+        let mut deps = mock_dependencies();
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: TRACK_COMPOUNDED_REWARD_REPLY_ID,
+                result: SubMsgResult::Err("vesting account not found".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.events,
+            vec![new_compounded_reward_tracking_failure_event(
+                "vesting account not found".to_string()
+            )]
+        );
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: 42,
+                result: SubMsgResult::Err("foomp".to_string()),
+            },
+        );
+        assert_eq!(res, Err(MixnetContractError::UnknownReplyId { id: 42 }));
+    }
 }
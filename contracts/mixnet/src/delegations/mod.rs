@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod helpers;
+pub(crate) mod models;
 pub(crate) mod queries;
 pub(crate) mod storage;
 pub(crate) mod transactions;
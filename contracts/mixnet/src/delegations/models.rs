@@ -0,0 +1,27 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::delegations::storage::PrimaryKey;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub(crate) struct CompoundingProgress {
+    /// Absolute id of the epoch for which the delegator rewards are being compounded.
+    pub absolute_epoch_id: u32,
+
+    /// Key of the last delegation that got processed in this epoch, if any.
+    pub last_processed: Option<PrimaryKey>,
+
+    /// Indicates whether all opted-in delegations have already been processed in this epoch.
+    pub finished: bool,
+}
+
+impl CompoundingProgress {
+    pub(crate) fn new(absolute_epoch_id: u32) -> Self {
+        CompoundingProgress {
+            absolute_epoch_id,
+            last_processed: None,
+            finished: false,
+        }
+    }
+}
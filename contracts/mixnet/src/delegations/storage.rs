@@ -2,14 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::constants::{
+    AUTO_COMPOUNDING_DELEGATIONS_NAMESPACE, AUTO_COMPOUNDING_PROGRESS_KEY,
     DELEGATION_MIXNODE_IDX_NAMESPACE, DELEGATION_OWNER_IDX_NAMESPACE, DELEGATION_PK_NAMESPACE,
 };
-use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use crate::delegations::models::CompoundingProgress;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use mixnet_contract_common::delegation::OwnerProxySubKey;
 use mixnet_contract_common::{Addr, Delegation, MixId};
 
 // It's a composite key on node's id and delegator address
-type PrimaryKey = (MixId, OwnerProxySubKey);
+pub(crate) type PrimaryKey = (MixId, OwnerProxySubKey);
+
+/// Keys of all delegations that have opted into having their rewards automatically compounded.
+/// Note: entries might get stale (i.e. the underlying delegation got removed), in which case
+/// they're lazily cleaned up during the compounding run itself.
+pub(crate) const AUTO_COMPOUNDING_DELEGATIONS: Map<'_, PrimaryKey, ()> =
+    Map::new(AUTO_COMPOUNDING_DELEGATIONS_NAMESPACE);
+
+pub(crate) const AUTO_COMPOUNDING_PROGRESS: Item<'_, CompoundingProgress> =
+    Item::new(AUTO_COMPOUNDING_PROGRESS_KEY);
 
 pub(crate) struct DelegationIndex<'a> {
     pub(crate) owner: MultiIndex<'a, Addr, Delegation, PrimaryKey>,
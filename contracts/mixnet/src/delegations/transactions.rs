@@ -2,17 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::storage;
+use crate::constants::TRACK_COMPOUNDED_REWARD_REPLY_ID;
+use crate::delegations::models::CompoundingProgress;
+use crate::gateways::storage as gateways_storage;
 use crate::interval::storage as interval_storage;
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::mixnodes::storage as mixnodes_storage;
+use crate::rewards::storage as rewards_storage;
 use crate::support::helpers::{ensure_epoch_in_progress_state, validate_delegation_stake};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{wasm_execute, Coin, DepsMut, Env, MessageInfo, Order, Response, SubMsg};
+use cw_storage_plus::Bound;
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_pending_delegation_event, new_pending_undelegation_event,
+    new_delegation_auto_compounding_update_event, new_delegator_reward_compounding_event,
+    new_pending_delegation_event, new_pending_gateway_delegation_event,
+    new_pending_gateway_undelegation_event, new_pending_redelegation_event,
+    new_pending_undelegation_event,
 };
 use mixnet_contract_common::pending_events::PendingEpochEventKind;
-use mixnet_contract_common::{Delegation, MixId};
+use mixnet_contract_common::{Delegation, GatewayDelegation, IdentityKey, MixId};
+use vesting_contract_common::messages::ExecuteMsg as VestingExecuteMsg;
 
 pub(crate) fn try_delegate_to_mixnode(
     deps: DepsMut<'_>,
@@ -81,6 +90,303 @@ pub(crate) fn try_remove_delegation_from_mixnode(
     Ok(Response::new().add_event(cosmos_event))
 }
 
+pub(crate) fn try_redelegate_from_mixnode(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    from: MixId,
+    to: MixId,
+    amount: Coin,
+) -> Result<Response, MixnetContractError> {
+    // redelegation is only allowed if the epoch is currently not in the process of being advanced
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    if from == to {
+        return Err(MixnetContractError::RedelegationToSameMixnode { mix_id: from });
+    }
+
+    // see if the source delegation even exists
+    let storage_key = Delegation::generate_storage_key(from, &info.sender, None);
+
+    if storage::delegations()
+        .may_load(deps.storage, storage_key)?
+        .is_none()
+    {
+        return Err(MixnetContractError::NoMixnodeDelegationFound {
+            mix_id: from,
+            address: info.sender.into_string(),
+            proxy: None,
+        });
+    }
+
+    // make sure the moved amount would have been a valid delegation on its own
+    let contract_state = mixnet_params_storage::CONTRACT_STATE.load(deps.storage)?;
+    let amount = validate_delegation_stake(
+        vec![amount],
+        contract_state.params.minimum_mixnode_delegation,
+        contract_state.rewarding_denom,
+    )?;
+
+    // check if the target node actually exists and is still bonded
+    match mixnodes_storage::mixnode_bonds().may_load(deps.storage, to)? {
+        None => return Err(MixnetContractError::MixNodeBondNotFound { mix_id: to }),
+        Some(bond) if bond.is_unbonding => {
+            return Err(MixnetContractError::MixnodeIsUnbonding { mix_id: to })
+        }
+        _ => (),
+    }
+
+    // push the event onto the queue and wait for it to be picked up at the end of the epoch
+    let cosmos_event = new_pending_redelegation_event(&info.sender, &amount, from, to);
+
+    let epoch_event = PendingEpochEventKind::new_redelegate(info.sender, from, to, amount);
+    interval_storage::push_new_epoch_event(deps.storage, &env, epoch_event)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
+pub(crate) fn try_delegate_to_gateway(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    identity: IdentityKey,
+) -> Result<Response, MixnetContractError> {
+    // delegation is only allowed if the epoch is currently not in the process of being advanced
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    // check if the delegation contains any funds of the appropriate denomination
+    let contract_state = mixnet_params_storage::CONTRACT_STATE.load(deps.storage)?;
+    let delegation = validate_delegation_stake(
+        info.funds,
+        contract_state.params.minimum_mixnode_delegation,
+        contract_state.rewarding_denom,
+    )?;
+
+    // check if the target gateway actually exists
+    if gateways_storage::gateways()
+        .may_load(deps.storage, &identity)?
+        .is_none()
+    {
+        return Err(MixnetContractError::GatewayBondNotFound { identity });
+    }
+
+    // push the event onto the queue and wait for it to be picked up at the end of the epoch
+    let cosmos_event = new_pending_gateway_delegation_event(&info.sender, &delegation, &identity);
+
+    let epoch_event = PendingEpochEventKind::DelegateToGateway {
+        owner: info.sender,
+        identity,
+        amount: delegation,
+    };
+    interval_storage::push_new_epoch_event(deps.storage, &env, epoch_event)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
+pub(crate) fn try_remove_delegation_from_gateway(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    identity: IdentityKey,
+) -> Result<Response, MixnetContractError> {
+    // undelegation is only allowed if the epoch is currently not in the process of being advanced
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    // see if the delegation even exists
+    let storage_key = GatewayDelegation::generate_storage_key(identity.clone(), &info.sender);
+
+    if gateways_storage::gateway_delegations()
+        .may_load(deps.storage, storage_key)?
+        .is_none()
+    {
+        return Err(MixnetContractError::NoGatewayDelegationFound {
+            identity,
+            address: info.sender.into_string(),
+        });
+    }
+
+    // push the event onto the queue and wait for it to be picked up at the end of the epoch
+    let cosmos_event = new_pending_gateway_undelegation_event(&info.sender, &identity);
+
+    let epoch_event = PendingEpochEventKind::UndelegateFromGateway {
+        owner: info.sender,
+        identity,
+    };
+    interval_storage::push_new_epoch_event(deps.storage, &env, epoch_event)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
+pub(crate) fn try_set_delegation_auto_compounding(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    mix_id: MixId,
+    enabled: bool,
+) -> Result<Response, MixnetContractError> {
+    // the flag is read during epoch event reconciliation, so don't allow changing it mid-way
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    let vesting_contract = mixnet_params_storage::vesting_contract_address(deps.storage)?;
+
+    // the flag applies to both, the liquid and the vested, delegations towards the node
+    let storage_keys = [
+        Delegation::generate_storage_key(mix_id, &info.sender, None),
+        Delegation::generate_storage_key(mix_id, &info.sender, Some(&vesting_contract)),
+    ];
+
+    let mut found = false;
+    for storage_key in storage_keys {
+        let Some(mut delegation) =
+            storage::delegations().may_load(deps.storage, storage_key.clone())?
+        else {
+            continue;
+        };
+        found = true;
+
+        let old_delegation = delegation.clone();
+        delegation.auto_compound = enabled;
+        storage::delegations().replace(
+            deps.storage,
+            storage_key.clone(),
+            Some(&delegation),
+            Some(&old_delegation),
+        )?;
+
+        if enabled {
+            storage::AUTO_COMPOUNDING_DELEGATIONS.save(deps.storage, storage_key, &())?;
+        } else {
+            storage::AUTO_COMPOUNDING_DELEGATIONS.remove(deps.storage, storage_key);
+        }
+    }
+
+    if !found {
+        return Err(MixnetContractError::NoMixnodeDelegationFound {
+            mix_id,
+            address: info.sender.into_string(),
+            proxy: None,
+        });
+    }
+
+    Ok(
+        Response::new().add_event(new_delegation_auto_compounding_update_event(
+            &info.sender,
+            mix_id,
+            enabled,
+        )),
+    )
+}
+
+// similarly to the pending events, this should be called during epoch event reconciliation
+// and might require multiple calls if there are a lot of delegations that opted into compounding.
+// returns the number of processed delegations and whether all of them have been processed
+// for the current epoch
+pub(crate) fn perform_auto_compounding(
+    deps: DepsMut<'_>,
+    limit: Option<u32>,
+) -> Result<(Response, u32, bool), MixnetContractError> {
+    let absolute_epoch_id =
+        interval_storage::current_interval(deps.storage)?.current_epoch_absolute_id();
+
+    let progress = match storage::AUTO_COMPOUNDING_PROGRESS.may_load(deps.storage)? {
+        Some(progress) if progress.absolute_epoch_id == absolute_epoch_id => progress,
+        _ => CompoundingProgress::new(absolute_epoch_id),
+    };
+
+    if progress.finished {
+        return Ok((Response::new(), 0, true));
+    }
+
+    let start = progress.last_processed.clone().map(Bound::exclusive);
+    let keys = storage::AUTO_COMPOUNDING_DELEGATIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let finished = match limit {
+        Some(limit) => (keys.len() as u32) < limit,
+        None => true,
+    };
+
+    let mut response = Response::new();
+    for storage_key in &keys {
+        let Some(mut delegation) =
+            storage::delegations().may_load(deps.storage, storage_key.clone())?
+        else {
+            // the delegation has been removed in the meantime
+            storage::AUTO_COMPOUNDING_DELEGATIONS.remove(deps.storage, storage_key.clone());
+            continue;
+        };
+        if !delegation.auto_compound {
+            storage::AUTO_COMPOUNDING_DELEGATIONS.remove(deps.storage, storage_key.clone());
+            continue;
+        }
+
+        // don't touch delegations towards nodes that are leaving the network
+        // (the delegators will be able to withdraw everything once the node has unbonded)
+        let mix_id = delegation.mix_id;
+        match mixnodes_storage::mixnode_bonds().may_load(deps.storage, mix_id)? {
+            Some(bond) if !bond.is_unbonding => (),
+            _ => continue,
+        }
+        let Some(mut mix_rewarding) =
+            rewards_storage::MIXNODE_REWARDING.may_load(deps.storage, mix_id)?
+        else {
+            continue;
+        };
+        if !mix_rewarding.still_bonded() {
+            continue;
+        }
+
+        let old_delegation = delegation.clone();
+        let reward = mix_rewarding.compound_delegator_reward(&mut delegation)?;
+        if reward.amount.is_zero() {
+            continue;
+        }
+
+        rewards_storage::MIXNODE_REWARDING.save(deps.storage, mix_id, &mix_rewarding)?;
+        storage::delegations().replace(
+            deps.storage,
+            storage_key.clone(),
+            Some(&delegation),
+            Some(&old_delegation),
+        )?;
+
+        response = response.add_event(new_delegator_reward_compounding_event(
+            &delegation.owner,
+            &delegation.proxy,
+            reward.clone(),
+            mix_id,
+        ));
+
+        // the vesting contract has to know the delegated amount got increased.
+        // however, a failure there (say, the vesting account no longer exists) must not
+        // revert the entire epoch advancement, hence we only get notified about it in a reply
+        if let Some(proxy) = &delegation.proxy {
+            response = response.add_submessage(SubMsg::reply_on_error(
+                wasm_execute(
+                    proxy,
+                    &VestingExecuteMsg::TrackReward {
+                        amount: reward,
+                        address: delegation.owner.clone().into_string(),
+                        compounded_into: Some(mix_id),
+                    },
+                    vec![],
+                )?,
+                TRACK_COMPOUNDED_REWARD_REPLY_ID,
+            ));
+        }
+    }
+
+    let updated_progress = CompoundingProgress {
+        absolute_epoch_id,
+        last_processed: keys.last().cloned().or(progress.last_processed),
+        finished,
+    };
+    storage::AUTO_COMPOUNDING_PROGRESS.save(deps.storage, &updated_progress)?;
+
+    Ok((response, keys.len() as u32, finished))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,4 +774,461 @@ mod tests {
             assert!(res.is_ok());
         }
     }
+
+    #[cfg(test)]
+    mod redelegating_between_mixnodes {
+        use super::*;
+        use crate::mixnodes::transactions::try_remove_mixnode;
+        use crate::support::tests::fixtures::TEST_COIN_DENOM;
+        use crate::support::tests::test_helpers::TestSetup;
+        use cosmwasm_std::testing::mock_info;
+        use cosmwasm_std::{coin, Addr};
+        use mixnet_contract_common::{EpochState, EpochStatus};
+
+        #[test]
+        fn cant_be_performed_if_epoch_transition_is_in_progress() {
+            let bad_states = vec![
+                EpochState::Rewarding {
+                    last_rewarded: 0,
+                    final_node_id: 0,
+                },
+                EpochState::ReconcilingEvents,
+                EpochState::AdvancingEpoch,
+            ];
+
+            for bad_state in bad_states {
+                let mut test = TestSetup::new();
+                let from = test.add_dummy_mixnode("owner1", None);
+                let to = test.add_dummy_mixnode("owner2", None);
+                test.add_immediate_delegation("delegator", 100_000_000u32, from);
+
+                let mut status = EpochStatus::new(test.rewarding_validator().sender);
+                status.state = bad_state;
+                interval_storage::save_current_epoch_status(test.deps_mut().storage, &status)
+                    .unwrap();
+
+                let env = test.env();
+                let res = try_redelegate_from_mixnode(
+                    test.deps_mut(),
+                    env,
+                    mock_info("delegator", &[]),
+                    from,
+                    to,
+                    coin(50_000_000, TEST_COIN_DENOM),
+                );
+                assert!(matches!(
+                    res,
+                    Err(MixnetContractError::EpochAdvancementInProgress { .. })
+                ));
+            }
+        }
+
+        #[test]
+        fn cannot_be_performed_towards_the_same_mixnode() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.add_immediate_delegation("delegator", 100_000_000u32, mix_id);
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info("delegator", &[]),
+                mix_id,
+                mix_id,
+                coin(50_000_000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::RedelegationToSameMixnode { mix_id })
+            )
+        }
+
+        #[test]
+        fn cannot_be_performed_if_source_delegation_never_existed() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from,
+                to,
+                coin(50_000_000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::NoMixnodeDelegationFound {
+                    mix_id: from,
+                    address: owner.to_string(),
+                    proxy: None
+                })
+            )
+        }
+
+        #[test]
+        fn must_contain_valid_amount_of_coins() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info(owner, &[]),
+                from,
+                to,
+                coin(0, TEST_COIN_DENOM),
+            );
+            assert_eq!(res, Err(MixnetContractError::EmptyDelegation));
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from,
+                to,
+                coin(1000, "some-weird-coin"),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::WrongDenom {
+                    received: "some-weird-coin".to_string(),
+                    expected: TEST_COIN_DENOM.to_string()
+                })
+            );
+        }
+
+        #[test]
+        fn can_only_be_done_towards_fully_bonded_mixnode() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from = test.add_dummy_mixnode("owner1", None);
+            let unbonding = test.add_dummy_mixnode("owner2", None);
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+
+            try_remove_mixnode(test.deps_mut(), env.clone(), mock_info("owner2", &[])).unwrap();
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info(owner, &[]),
+                from,
+                unbonding,
+                coin(50_000_000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::MixnodeIsUnbonding { mix_id: unbonding })
+            );
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from,
+                42,
+                coin(50_000_000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::MixNodeBondNotFound { mix_id: 42 })
+            );
+        }
+
+        #[test]
+        fn correctly_pushes_appropriate_epoch_event() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+
+            let amount = coin(50_000_000, TEST_COIN_DENOM);
+            try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from,
+                to,
+                amount.clone(),
+            )
+            .unwrap();
+
+            let events = test.pending_epoch_events();
+
+            assert_eq!(
+                events[0].kind,
+                PendingEpochEventKind::new_redelegate(Addr::unchecked(owner), from, to, amount)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod setting_delegation_auto_compounding {
+        use super::*;
+        use crate::support::tests::test_helpers::TestSetup;
+        use cosmwasm_std::testing::mock_info;
+        use cosmwasm_std::Addr;
+        use mixnet_contract_common::{EpochState, EpochStatus};
+
+        #[test]
+        fn cant_be_performed_if_epoch_transition_is_in_progress() {
+            let bad_states = vec![
+                EpochState::Rewarding {
+                    last_rewarded: 0,
+                    final_node_id: 0,
+                },
+                EpochState::ReconcilingEvents,
+                EpochState::AdvancingEpoch,
+            ];
+
+            for bad_state in bad_states {
+                let mut test = TestSetup::new();
+                let mix_id = test.add_dummy_mixnode("mix-owner", None);
+                test.add_immediate_delegation("delegator", 100_000_000u128, mix_id);
+
+                let mut status = EpochStatus::new(test.rewarding_validator().sender);
+                status.state = bad_state;
+                interval_storage::save_current_epoch_status(test.deps_mut().storage, &status)
+                    .unwrap();
+
+                let res = try_set_delegation_auto_compounding(
+                    test.deps_mut(),
+                    mock_info("delegator", &[]),
+                    mix_id,
+                    true,
+                );
+                assert!(matches!(
+                    res,
+                    Err(MixnetContractError::EpochAdvancementInProgress { .. })
+                ));
+            }
+        }
+
+        #[test]
+        fn cannot_be_performed_if_delegation_doesnt_exist() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+
+            let res = try_set_delegation_auto_compounding(
+                test.deps_mut(),
+                mock_info("delegator", &[]),
+                mix_id,
+                true,
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::NoMixnodeDelegationFound {
+                    mix_id,
+                    address: "delegator".to_string(),
+                    proxy: None,
+                })
+            );
+        }
+
+        #[test]
+        fn updates_the_delegation_and_the_compounding_registry() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.add_immediate_delegation("delegator", 100_000_000u128, mix_id);
+            let storage_key =
+                Delegation::generate_storage_key(mix_id, &Addr::unchecked("delegator"), None);
+
+            assert!(!test.delegation(mix_id, "delegator", &None).auto_compound);
+
+            try_set_delegation_auto_compounding(
+                test.deps_mut(),
+                mock_info("delegator", &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+            assert!(test.delegation(mix_id, "delegator", &None).auto_compound);
+            assert!(
+                storage::AUTO_COMPOUNDING_DELEGATIONS.has(test.deps().storage, storage_key.clone())
+            );
+
+            // topping up the delegation doesn't reset the flag
+            test.add_immediate_delegation("delegator", 50_000_000u128, mix_id);
+            assert!(test.delegation(mix_id, "delegator", &None).auto_compound);
+
+            try_set_delegation_auto_compounding(
+                test.deps_mut(),
+                mock_info("delegator", &[]),
+                mix_id,
+                false,
+            )
+            .unwrap();
+            assert!(!test.delegation(mix_id, "delegator", &None).auto_compound);
+            assert!(!storage::AUTO_COMPOUNDING_DELEGATIONS.has(test.deps().storage, storage_key));
+        }
+    }
+
+    #[cfg(test)]
+    mod performing_auto_compounding {
+        use super::*;
+        use crate::support::tests::test_helpers::{performance, TestSetup};
+        use cosmwasm_std::testing::mock_info;
+        use cosmwasm_std::{Addr, ReplyOn, Uint128};
+        use mixnet_contract_common::rewarding::helpers::truncate_reward_amount;
+
+        #[test]
+        fn adds_pending_reward_to_the_delegation() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(Uint128::new(100_000_000_000)));
+            let og_amount = Uint128::new(200_000_000);
+            test.add_immediate_delegation("delegator", og_amount, mix_id);
+            test.add_immediate_delegation("other-delegator", og_amount, mix_id);
+            try_set_delegation_auto_compounding(
+                test.deps_mut(),
+                mock_info("delegator", &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+
+            test.skip_to_next_epoch_end();
+            test.force_change_rewarded_set(vec![mix_id]);
+            test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+            let pending = test.pending_delegator_reward("delegator", mix_id);
+            let delegates_before = test.mix_rewarding(mix_id).delegates;
+
+            let (res, processed, finished) =
+                perform_auto_compounding(test.deps_mut(), None).unwrap();
+            assert_eq!(processed, 1);
+            assert!(finished);
+            // liquid delegation, so there's nothing to notify the vesting contract about
+            assert!(res.messages.is_empty());
+
+            let delegation = test.delegation(mix_id, "delegator", &None);
+            assert_eq!(
+                delegation.amount.amount,
+                og_amount + truncate_reward_amount(pending)
+            );
+            // the tokens never left the node, they just got attributed to the principal
+            assert_eq!(test.mix_rewarding(mix_id).delegates, delegates_before);
+
+            // the other delegation didn't opt in
+            let other = test.delegation(mix_id, "other-delegator", &None);
+            assert_eq!(other.amount.amount, og_amount);
+
+            // and the compounding doesn't happen twice in the same epoch
+            let (_, processed, finished) = perform_auto_compounding(test.deps_mut(), None).unwrap();
+            assert_eq!(processed, 0);
+            assert!(finished);
+            assert_eq!(test.delegation(mix_id, "delegator", &None), delegation);
+        }
+
+        #[test]
+        fn respects_the_limit() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(Uint128::new(100_000_000_000)));
+            for i in 0..5 {
+                let delegator = format!("delegator{i}");
+                test.add_immediate_delegation(&delegator, 100_000_000u128, mix_id);
+                try_set_delegation_auto_compounding(
+                    test.deps_mut(),
+                    mock_info(&delegator, &[]),
+                    mix_id,
+                    true,
+                )
+                .unwrap();
+            }
+
+            test.skip_to_next_epoch_end();
+            test.force_change_rewarded_set(vec![mix_id]);
+            test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+
+            let (_, processed, finished) =
+                perform_auto_compounding(test.deps_mut(), Some(3)).unwrap();
+            assert_eq!(processed, 3);
+            assert!(!finished);
+
+            let (_, processed, finished) =
+                perform_auto_compounding(test.deps_mut(), Some(3)).unwrap();
+            assert_eq!(processed, 2);
+            assert!(finished);
+
+            for i in 0..5 {
+                let delegation = test.delegation(mix_id, &format!("delegator{i}"), &None);
+                assert!(delegation.amount.amount > Uint128::new(100_000_000));
+            }
+        }
+
+        #[test]
+        fn removes_stale_registry_entries() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.add_immediate_delegation("delegator", 100_000_000u128, mix_id);
+            try_set_delegation_auto_compounding(
+                test.deps_mut(),
+                mock_info("delegator", &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+            test.remove_immediate_delegation("delegator", mix_id);
+
+            perform_auto_compounding(test.deps_mut(), None).unwrap();
+            let storage_key =
+                Delegation::generate_storage_key(mix_id, &Addr::unchecked("delegator"), None);
+            assert!(!storage::AUTO_COMPOUNDING_DELEGATIONS.has(test.deps().storage, storage_key));
+        }
+
+        #[test]
+        fn vesting_contract_failures_dont_revert_the_compounding() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(Uint128::new(100_000_000_000)));
+            test.add_immediate_delegation("delegator", 200_000_000u128, mix_id);
+
+            // turn it into a legacy vested delegation
+            let vesting_contract =
+                mixnet_params_storage::vesting_contract_address(test.deps().storage).unwrap();
+            let liquid_key =
+                Delegation::generate_storage_key(mix_id, &Addr::unchecked("delegator"), None);
+            let mut delegation = storage::delegations()
+                .load(test.deps().storage, liquid_key.clone())
+                .unwrap();
+            storage::delegations()
+                .remove(test.deps_mut().storage, liquid_key)
+                .unwrap();
+            delegation.proxy = Some(vesting_contract.clone());
+            delegation.auto_compound = true;
+            let vested_key = delegation.storage_key();
+            storage::delegations()
+                .save(test.deps_mut().storage, vested_key.clone(), &delegation)
+                .unwrap();
+            storage::AUTO_COMPOUNDING_DELEGATIONS
+                .save(test.deps_mut().storage, vested_key, &())
+                .unwrap();
+
+            test.skip_to_next_epoch_end();
+            test.force_change_rewarded_set(vec![mix_id]);
+            test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+
+            let (res, processed, finished) =
+                perform_auto_compounding(test.deps_mut(), None).unwrap();
+            assert_eq!(processed, 1);
+            assert!(finished);
+
+            // the vesting contract is notified, but if it fails (say, because the vesting account
+            // no longer exists), we only get a reply rather than having the epoch advancement reverted
+            assert_eq!(res.messages.len(), 1);
+            assert_eq!(res.messages[0].id, TRACK_COMPOUNDED_REWARD_REPLY_ID);
+            assert_eq!(res.messages[0].reply_on, ReplyOn::Error);
+
+            let compounded = test.delegation(mix_id, "delegator", &Some(vesting_contract));
+            assert!(compounded.amount.amount > Uint128::new(200_000_000));
+        }
+    }
 }
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::storage;
-use cosmwasm_std::{Addr, Storage};
-use mixnet_contract_common::{error::MixnetContractError, GatewayBond};
+use crate::mixnet_contract_settings::storage as mixnet_params_storage;
+use cosmwasm_std::{Addr, Coin, Storage, Uint128};
+use mixnet_contract_common::mixnode::MixNodeCostParams;
+use mixnet_contract_common::{error::MixnetContractError, GatewayBond, Percent};
 
 pub(crate) fn must_get_gateway_bond_by_owner(
     store: &dyn Storage,
@@ -18,3 +20,20 @@ pub(crate) fn must_get_gateway_bond_by_owner(
         })?
         .1)
 }
+
+// the cost parameters assigned to freshly bonded gateways (and the ones that got bonded before
+// the rewarding got introduced) until the operator explicitly chooses different ones
+pub(crate) fn default_gateway_cost_params(
+    store: &dyn Storage,
+) -> Result<MixNodeCostParams, MixnetContractError> {
+    let contract_state = mixnet_params_storage::CONTRACT_STATE.load(store)?;
+    let params = contract_state.params;
+
+    Ok(MixNodeCostParams {
+        profit_margin_percent: params.profit_margin.normalise(Percent::zero()),
+        interval_operating_cost: Coin {
+            denom: contract_state.rewarding_denom,
+            amount: params.interval_operating_cost.normalise(Uint128::zero()),
+        },
+    })
+}
@@ -3,10 +3,17 @@
 
 use super::storage;
 use crate::constants::{GATEWAY_BOND_DEFAULT_RETRIEVAL_LIMIT, GATEWAY_BOND_MAX_RETRIEVAL_LIMIT}; // Keeps gateway and mixnode retrieval in sync by re-using the constant. Could be split into its own constant.
+use crate::constants::{
+    GATEWAY_DELEGATION_PAGE_DEFAULT_RETRIEVAL_LIMIT, GATEWAY_DELEGATION_PAGE_MAX_RETRIEVAL_LIMIT,
+};
 use cosmwasm_std::{Deps, Order, StdResult};
 use cw_storage_plus::Bound;
+use mixnet_contract_common::rewarding::helpers::truncate_reward;
+use mixnet_contract_common::rewarding::PendingRewardResponse;
 use mixnet_contract_common::{
-    GatewayBond, GatewayBondResponse, GatewayOwnershipResponse, IdentityKey, PagedGatewayResponse,
+    GatewayBond, GatewayBondResponse, GatewayDelegation, GatewayOwnershipResponse,
+    GatewayRewardingDetailsResponse, GatewayRewardingParams, IdentityKey,
+    PagedGatewayDelegationsResponse, PagedGatewayResponse,
 };
 
 pub(crate) fn query_gateways_paged(
@@ -56,6 +63,75 @@ pub fn query_gateway_bond(deps: Deps<'_>, identity: IdentityKey) -> StdResult<Ga
     })
 }
 
+pub(crate) fn query_gateway_rewarding_params(deps: Deps<'_>) -> StdResult<GatewayRewardingParams> {
+    storage::GATEWAY_REWARDING_PARAMS.load(deps.storage)
+}
+
+pub(crate) fn query_gateway_rewarding_details(
+    deps: Deps<'_>,
+    identity: IdentityKey,
+) -> StdResult<GatewayRewardingDetailsResponse> {
+    Ok(GatewayRewardingDetailsResponse {
+        rewarding_details: storage::GATEWAY_REWARDING.may_load(deps.storage, &identity)?,
+        identity,
+    })
+}
+
+pub(crate) fn query_gateway_delegations_paged(
+    deps: Deps<'_>,
+    identity: IdentityKey,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PagedGatewayDelegationsResponse> {
+    let limit = limit
+        .unwrap_or(GATEWAY_DELEGATION_PAGE_DEFAULT_RETRIEVAL_LIMIT)
+        .min(GATEWAY_DELEGATION_PAGE_MAX_RETRIEVAL_LIMIT) as usize;
+
+    let start = start_after.map(Bound::exclusive);
+
+    let delegations = storage::gateway_delegations()
+        .prefix(identity)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|record| record.map(|r| r.1))
+        .collect::<StdResult<Vec<GatewayDelegation>>>()?;
+
+    let start_next_after = delegations.last().map(|del| del.owner.to_string());
+
+    Ok(PagedGatewayDelegationsResponse::new(
+        delegations,
+        start_next_after,
+    ))
+}
+
+pub(crate) fn query_pending_gateway_delegator_reward(
+    deps: Deps<'_>,
+    owner: String,
+    identity: IdentityKey,
+) -> StdResult<PendingRewardResponse> {
+    let owner_address = deps.api.addr_validate(&owner)?;
+
+    let rewarding = match storage::GATEWAY_REWARDING.may_load(deps.storage, &identity)? {
+        Some(rewarding) => rewarding,
+        None => return Ok(PendingRewardResponse::default()),
+    };
+
+    let storage_key = GatewayDelegation::generate_storage_key(identity, &owner_address);
+    let delegation = match storage::gateway_delegations().may_load(deps.storage, storage_key)? {
+        Some(delegation) => delegation,
+        None => return Ok(PendingRewardResponse::default()),
+    };
+
+    let detailed_reward = delegation.pending_reward(&rewarding)?;
+
+    Ok(PendingRewardResponse {
+        amount_earned: Some(truncate_reward(detailed_reward, &delegation.amount.denom)),
+        amount_staked: Some(delegation.amount),
+        amount_earned_detailed: Some(detailed_reward),
+        mixnode_still_fully_bonded: rewarding.still_bonded(),
+    })
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
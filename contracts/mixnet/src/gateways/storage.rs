@@ -1,10 +1,17 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::constants::{GATEWAYS_OWNER_IDX_NAMESPACE, GATEWAYS_PK_NAMESPACE};
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Index, IndexList, IndexedMap, UniqueIndex};
-use mixnet_contract_common::{GatewayBond, IdentityKeyRef};
+use crate::constants::{
+    GATEWAYS_OWNER_IDX_NAMESPACE, GATEWAYS_PK_NAMESPACE, GATEWAYS_REWARDED_IN_EPOCH_KEY,
+    GATEWAYS_REWARDING_PK_NAMESPACE, GATEWAY_DELEGATION_OWNER_IDX_NAMESPACE,
+    GATEWAY_DELEGATION_PK_NAMESPACE, GATEWAY_REWARDING_PARAMS_KEY,
+};
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+use mixnet_contract_common::gateway::GatewayDelegationStorageKey;
+use mixnet_contract_common::{
+    GatewayBond, GatewayDelegation, GatewayRewarding, GatewayRewardingParams, IdentityKeyRef,
+};
 
 pub(crate) struct GatewayBondIndex<'a> {
     pub(crate) owner: UniqueIndex<'a, Addr, GatewayBond>,
@@ -27,3 +34,48 @@ pub(crate) fn gateways<'a>() -> IndexedMap<'a, IdentityKeyRef<'a>, GatewayBond,
     };
     IndexedMap::new(GATEWAYS_PK_NAMESPACE, indexes)
 }
+
+// current parameters used for gateway rewarding purposes
+pub(crate) const GATEWAY_REWARDING_PARAMS: Item<'_, GatewayRewardingParams> =
+    Item::new(GATEWAY_REWARDING_PARAMS_KEY);
+
+// (absolute epoch id, number of gateways rewarded in that epoch)
+// used for making sure no more than `rewarded_gateways` get rewarded in any epoch
+pub(crate) const GATEWAYS_REWARDED_IN_EPOCH: Item<'_, (u32, u32)> =
+    Item::new(GATEWAYS_REWARDED_IN_EPOCH_KEY);
+
+// note: the entry might outlive the bond itself if there are still delegations left on the gateway
+pub(crate) const GATEWAY_REWARDING: Map<IdentityKeyRef, GatewayRewarding> =
+    Map::new(GATEWAYS_REWARDING_PK_NAMESPACE);
+
+pub(crate) struct GatewayDelegationIndex<'a> {
+    pub(crate) owner: MultiIndex<'a, Addr, GatewayDelegation, GatewayDelegationStorageKey>,
+}
+
+impl<'a> IndexList<GatewayDelegation> for GatewayDelegationIndex<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<GatewayDelegation>> + '_> {
+        let v: Vec<&dyn Index<GatewayDelegation>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+// It's a composite key on gateway's identity and delegator address
+pub(crate) fn gateway_delegations<'a>(
+) -> IndexedMap<'a, GatewayDelegationStorageKey, GatewayDelegation, GatewayDelegationIndex<'a>> {
+    let indexes = GatewayDelegationIndex {
+        owner: MultiIndex::new(
+            |_pk, d| d.owner.clone(),
+            GATEWAY_DELEGATION_PK_NAMESPACE,
+            GATEWAY_DELEGATION_OWNER_IDX_NAMESPACE,
+        ),
+    };
+
+    IndexedMap::new(GATEWAY_DELEGATION_PK_NAMESPACE, indexes)
+}
+
+pub(crate) fn initialise_storage(
+    storage: &mut dyn Storage,
+    rewarding_params: GatewayRewardingParams,
+) -> StdResult<()> {
+    GATEWAY_REWARDING_PARAMS.save(storage, &rewarding_params)
+}
@@ -1,19 +1,26 @@
 // Copyright 2021-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use super::helpers::must_get_gateway_bond_by_owner;
+use super::helpers::{default_gateway_cost_params, must_get_gateway_bond_by_owner};
 use super::storage;
 use crate::gateways::signature_helpers::verify_gateway_bonding_signature;
+use crate::interval::storage as interval_storage;
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::signing::storage as signing_storage;
-use crate::support::helpers::{ensure_no_existing_bond, validate_pledge};
+use crate::support::helpers::{
+    ensure_epoch_in_progress_state, ensure_no_existing_bond, ensure_operating_cost_within_range,
+    ensure_profit_margin_within_range, validate_pledge,
+};
 use cosmwasm_std::{BankMsg, DepsMut, Env, MessageInfo, Response};
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_gateway_bonding_event, new_gateway_config_update_event, new_gateway_unbonding_event,
+    new_gateway_bonding_event, new_gateway_config_update_event,
+    new_gateway_pending_cost_params_update_event, new_gateway_unbonding_event,
 };
 use mixnet_contract_common::gateway::GatewayConfigUpdate;
-use mixnet_contract_common::{Gateway, GatewayBond};
+use mixnet_contract_common::mixnode::MixNodeCostParams;
+use mixnet_contract_common::pending_events::PendingIntervalEventKind;
+use mixnet_contract_common::{Gateway, GatewayBond, GatewayRewarding};
 use nym_contracts_common::signing::MessageSignature;
 
 // TODO: perhaps also require the user to explicitly provide what it thinks is the current nonce
@@ -63,7 +70,24 @@ pub(crate) fn try_add_gateway(
         gateway,
     );
 
+    let cost_params = default_gateway_cost_params(deps.storage)?;
+    let rewarding = match storage::GATEWAY_REWARDING.may_load(deps.storage, bond.identity())? {
+        // the gateway has been bonded before and some of its delegators haven't yet withdrawn
+        // their tokens - they simply get to continue accruing rewards with the new bond
+        Some(mut existing) => {
+            existing.cost_params = cost_params;
+            existing.increase_operator_uint128(pledge.amount)?;
+            existing
+        }
+        None => {
+            let current_epoch =
+                interval_storage::current_interval(deps.storage)?.current_epoch_absolute_id();
+            GatewayRewarding::initialise_new(cost_params, &pledge, current_epoch)?
+        }
+    };
+
     storage::gateways().save(deps.storage, bond.identity(), &bond)?;
+    storage::GATEWAY_REWARDING.save(deps.storage, bond.identity(), &rewarding)?;
 
     Ok(Response::new().add_event(new_gateway_bonding_event(
         &info.sender,
@@ -86,10 +110,34 @@ pub(crate) fn try_remove_gateway(
         None => return Err(MixnetContractError::NoAssociatedGatewayBond { owner: info.sender }),
     };
 
+    // the operator gets back its original pledge alongside all the rewards it has accumulated
+    let pledge = gateway_bond.pledge_amount();
+    let tokens = match storage::GATEWAY_REWARDING.may_load(deps.storage, gateway_bond.identity())? {
+        Some(mut rewarding) => {
+            let tokens = rewarding.operator_pledge_with_reward(&pledge.denom);
+
+            // if there are no delegations left, we can completely purge the rewarding information,
+            // otherwise it has to be kept around so that the delegators could reclaim their tokens
+            if rewarding.unique_delegations == 0 {
+                storage::GATEWAY_REWARDING.remove(deps.storage, gateway_bond.identity());
+            } else {
+                let operator = rewarding.operator;
+                rewarding.decrease_operator_decimal(operator)?;
+                storage::GATEWAY_REWARDING.save(
+                    deps.storage,
+                    gateway_bond.identity(),
+                    &rewarding,
+                )?;
+            }
+            tokens
+        }
+        None => pledge,
+    };
+
     // send bonded funds back to the bond owner
     let return_tokens = BankMsg::Send {
         to_address: info.sender.to_string(),
-        amount: vec![gateway_bond.pledge_amount()],
+        amount: vec![tokens.clone()],
     };
 
     // remove the bond
@@ -99,11 +147,45 @@ pub(crate) fn try_remove_gateway(
         .add_message(return_tokens)
         .add_event(new_gateway_unbonding_event(
             &info.sender,
-            &gateway_bond.pledge_amount,
+            &tokens,
             gateway_bond.identity(),
         )))
 }
 
+pub(crate) fn try_update_gateway_cost_params(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    new_costs: MixNodeCostParams,
+) -> Result<Response, MixnetContractError> {
+    // see if the gateway still exists
+    let existing_bond = must_get_gateway_bond_by_owner(deps.storage, &info.sender)?;
+
+    // changing cost params is only allowed if the epoch is currently not in the process of being advanced
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    // ensure the profit margin is within the defined range
+    ensure_profit_margin_within_range(deps.storage, new_costs.profit_margin_percent)?;
+
+    // ensure the operating cost is within the defined range
+    ensure_operating_cost_within_range(deps.storage, &new_costs.interval_operating_cost)?;
+
+    let cosmos_event = new_gateway_pending_cost_params_update_event(
+        existing_bond.identity(),
+        &info.sender,
+        &new_costs,
+    );
+
+    // push the interval event
+    let interval_event = PendingIntervalEventKind::ChangeGatewayCostParams {
+        identity: existing_bond.identity().clone(),
+        new_costs,
+    };
+    interval_storage::push_new_interval_event(deps.storage, &env, interval_event)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
 pub(crate) fn try_update_gateway_config(
     deps: DepsMut<'_>,
     info: MessageInfo,
@@ -401,4 +483,293 @@ pub mod tests {
         assert_eq!(bond.gateway.location, update.location);
         assert_eq!(bond.gateway.version, update.version);
     }
+
+    #[cfg(test)]
+    mod gateway_rewarding {
+        use super::*;
+        use crate::delegations::transactions::{
+            try_delegate_to_gateway, try_remove_delegation_from_gateway,
+        };
+        use crate::rewards::transactions::{
+            try_reward_gateway, try_update_gateway_rewarding_params,
+        };
+        use cosmwasm_std::Decimal;
+        use mixnet_contract_common::{GatewayDelegation, GatewayRewardingParams, Percent};
+
+        fn enable_gateway_rewarding(test: &mut TestSetup) {
+            let owner = test.owner();
+            let params = GatewayRewardingParams {
+                epoch_reward_budget: Decimal::from_atomics(100_000_000u32, 0).unwrap(),
+                stake_saturation_point: Decimal::from_atomics(1_000_000_000_000u64, 0).unwrap(),
+                rewarded_gateways: 10,
+            };
+            try_update_gateway_rewarding_params(test.deps_mut(), owner, params).unwrap();
+        }
+
+        #[test]
+        fn bonding_initialises_rewarding_details() {
+            let mut test = TestSetup::new();
+            let identity = test.add_dummy_gateway("alice", None);
+
+            let rewarding = storage::GATEWAY_REWARDING
+                .load(test.deps().storage, &identity)
+                .unwrap();
+            assert!(rewarding.still_bonded());
+            assert_eq!(
+                rewarding.operator,
+                Decimal::from_atomics(tests::fixtures::good_gateway_pledge()[0].amount, 0).unwrap()
+            );
+            assert_eq!(rewarding.unique_delegations, 0);
+        }
+
+        #[test]
+        fn delegation_is_only_applied_at_epoch_end() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let identity = test.add_dummy_gateway("alice", None);
+
+            let delegation = test.coins(100_000_000);
+            let res = try_delegate_to_gateway(
+                test.deps_mut(),
+                env.clone(),
+                mock_info("bob", &delegation),
+                "non-existent".to_string(),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::GatewayBondNotFound {
+                    identity: "non-existent".to_string()
+                })
+            );
+
+            try_delegate_to_gateway(
+                test.deps_mut(),
+                env,
+                mock_info("bob", &delegation),
+                identity.clone(),
+            )
+            .unwrap();
+
+            let storage_key =
+                GatewayDelegation::generate_storage_key(identity.clone(), &Addr::unchecked("bob"));
+            assert!(storage::gateway_delegations()
+                .may_load(test.deps().storage, storage_key.clone())
+                .unwrap()
+                .is_none());
+
+            test.execute_all_pending_events();
+
+            let stored = storage::gateway_delegations()
+                .load(test.deps().storage, storage_key)
+                .unwrap();
+            assert_eq!(stored.amount, delegation[0]);
+
+            let rewarding = storage::GATEWAY_REWARDING
+                .load(test.deps().storage, &identity)
+                .unwrap();
+            assert_eq!(rewarding.unique_delegations, 1);
+        }
+
+        #[test]
+        fn rewarding_increases_operator_and_delegator_stake() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            enable_gateway_rewarding(&mut test);
+
+            let identity = test.add_dummy_gateway("alice", None);
+            let delegation = test.coins(100_000_000);
+            try_delegate_to_gateway(
+                test.deps_mut(),
+                env,
+                mock_info("bob", &delegation),
+                identity.clone(),
+            )
+            .unwrap();
+            test.execute_all_pending_events();
+
+            let before = storage::GATEWAY_REWARDING
+                .load(test.deps().storage, &identity)
+                .unwrap();
+
+            // rewarding is not allowed until all mixnodes got rewarded
+            test.skip_to_current_epoch_end();
+            let env = test.env();
+            let sender = test.rewarding_validator();
+            let performance = Percent::from_percentage_value(100).unwrap();
+            let res = try_reward_gateway(
+                test.deps_mut(),
+                env.clone(),
+                sender.clone(),
+                identity.clone(),
+                performance,
+            );
+            assert!(matches!(
+                res,
+                Err(MixnetContractError::EpochNotInEventReconciliationState { .. })
+            ));
+
+            test.set_epoch_reconciliation_state();
+            try_reward_gateway(
+                test.deps_mut(),
+                env.clone(),
+                sender.clone(),
+                identity.clone(),
+                performance,
+            )
+            .unwrap();
+
+            let after = storage::GATEWAY_REWARDING
+                .load(test.deps().storage, &identity)
+                .unwrap();
+            assert!(after.operator > before.operator);
+            assert!(after.delegates > before.delegates);
+            assert_eq!(
+                after.last_rewarded_epoch,
+                test.current_interval().current_epoch_absolute_id()
+            );
+
+            // and the same gateway can't be rewarded twice in the same epoch
+            let res =
+                try_reward_gateway(test.deps_mut(), env, sender, identity.clone(), performance);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::GatewayAlreadyRewarded {
+                    identity,
+                    absolute_epoch_id: test.current_interval().current_epoch_absolute_id(),
+                })
+            );
+        }
+
+        #[test]
+        fn at_most_rewarded_gateways_can_be_rewarded_per_epoch() {
+            let mut test = TestSetup::new();
+            let owner = test.owner();
+            let params = GatewayRewardingParams {
+                epoch_reward_budget: Decimal::from_atomics(100_000_000u32, 0).unwrap(),
+                stake_saturation_point: Decimal::from_atomics(1_000_000_000_000u64, 0).unwrap(),
+                rewarded_gateways: 2,
+            };
+            try_update_gateway_rewarding_params(test.deps_mut(), owner, params).unwrap();
+
+            let alice = test.add_dummy_gateway("alice", None);
+            let bob = test.add_dummy_gateway("bob", None);
+            let carol = test.add_dummy_gateway("carol", None);
+
+            test.skip_to_current_epoch_end();
+            test.set_epoch_reconciliation_state();
+            let env = test.env();
+            let sender = test.rewarding_validator();
+            let performance = Percent::from_percentage_value(100).unwrap();
+            let absolute_epoch_id = test.current_interval().current_epoch_absolute_id();
+
+            try_reward_gateway(
+                test.deps_mut(),
+                env.clone(),
+                sender.clone(),
+                alice.clone(),
+                performance,
+            )
+            .unwrap();
+            // zero performance rewards count towards the limit as well
+            try_reward_gateway(
+                test.deps_mut(),
+                env.clone(),
+                sender.clone(),
+                bob.clone(),
+                Percent::zero(),
+            )
+            .unwrap();
+
+            let res = try_reward_gateway(
+                test.deps_mut(),
+                env,
+                sender.clone(),
+                carol.clone(),
+                performance,
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::GatewayRewardingLimitReached {
+                    rewarded_gateways: 2,
+                    absolute_epoch_id,
+                })
+            );
+
+            // the limit is reset in the following epoch
+            test.skip_to_next_epoch_end();
+            test.set_epoch_reconciliation_state();
+            let env = test.env();
+            try_reward_gateway(
+                test.deps_mut(),
+                env.clone(),
+                sender.clone(),
+                carol,
+                performance,
+            )
+            .unwrap();
+            try_reward_gateway(
+                test.deps_mut(),
+                env.clone(),
+                sender.clone(),
+                alice,
+                performance,
+            )
+            .unwrap();
+            let res = try_reward_gateway(test.deps_mut(), env, sender, bob, performance);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::GatewayRewardingLimitReached {
+                    rewarded_gateways: 2,
+                    absolute_epoch_id: absolute_epoch_id + 1,
+                })
+            );
+        }
+
+        #[test]
+        fn delegators_can_withdraw_after_gateway_unbonds() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let identity = test.add_dummy_gateway("alice", None);
+
+            let delegation = test.coins(100_000_000);
+            try_delegate_to_gateway(
+                test.deps_mut(),
+                env.clone(),
+                mock_info("bob", &delegation),
+                identity.clone(),
+            )
+            .unwrap();
+            test.execute_all_pending_events();
+
+            try_remove_gateway(test.deps_mut(), mock_info("alice", &[])).unwrap();
+
+            // the rewarding details are kept around for the sake of the delegator
+            let rewarding = storage::GATEWAY_REWARDING
+                .load(test.deps().storage, &identity)
+                .unwrap();
+            assert!(!rewarding.still_bonded());
+
+            try_remove_delegation_from_gateway(
+                test.deps_mut(),
+                env,
+                mock_info("bob", &[]),
+                identity.clone(),
+            )
+            .unwrap();
+            test.execute_all_pending_events();
+
+            // and once the final delegation is gone, so is the rewarding information
+            assert!(storage::GATEWAY_REWARDING
+                .may_load(test.deps().storage, &identity)
+                .unwrap()
+                .is_none());
+            assert!(storage::gateway_delegations()
+                .may_load(
+                    test.deps().storage,
+                    GatewayDelegation::generate_storage_key(identity, &Addr::unchecked("bob"))
+                )
+                .unwrap()
+                .is_none());
+        }
+    }
 }
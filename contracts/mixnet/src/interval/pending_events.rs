@@ -6,8 +6,11 @@ use cosmwasm_std::{Addr, Coin, DepsMut, Env, Response};
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
     new_active_set_update_event, new_delegation_event, new_delegation_on_unbonded_node_event,
+    new_gateway_cost_params_update_event, new_gateway_delegation_event,
+    new_gateway_delegation_on_unbonded_node_event, new_gateway_undelegation_event,
     new_mixnode_cost_params_update_event, new_mixnode_unbonding_event, new_pledge_decrease_event,
-    new_pledge_increase_event, new_rewarding_params_update_event, new_undelegation_event,
+    new_pledge_increase_event, new_redelegation_event, new_redelegation_on_unbonded_node_event,
+    new_rewarding_params_update_event, new_undelegation_event,
 };
 use mixnet_contract_common::mixnode::MixNodeCostParams;
 use mixnet_contract_common::pending_events::{
@@ -15,10 +18,11 @@ use mixnet_contract_common::pending_events::{
     PendingIntervalEventKind,
 };
 use mixnet_contract_common::reward_params::IntervalRewardingParamsUpdate;
-use mixnet_contract_common::{BlockHeight, Delegation, MixId};
+use mixnet_contract_common::{BlockHeight, Delegation, GatewayDelegation, IdentityKey, MixId};
 
 use crate::delegations;
 use crate::delegations::storage as delegations_storage;
+use crate::gateways::storage as gateways_storage;
 use crate::interval::helpers::change_interval_config;
 use crate::interval::storage;
 use crate::mixnodes::helpers::{cleanup_post_unbond_mixnode_storage, get_mixnode_details_by_id};
@@ -40,6 +44,19 @@ pub(crate) fn delegate(
     owner: Addr,
     mix_id: MixId,
     amount: Coin,
+) -> Result<Response, MixnetContractError> {
+    delegate_with_auto_compounding(deps, env, created_at, owner, mix_id, amount, None)
+}
+
+// if the compounding preference is not explicitly provided, the one of any existing delegation is kept
+fn delegate_with_auto_compounding(
+    deps: DepsMut<'_>,
+    env: &Env,
+    created_at: BlockHeight,
+    owner: Addr,
+    mix_id: MixId,
+    amount: Coin,
+    auto_compound: Option<bool>,
 ) -> Result<Response, MixnetContractError> {
     // check if the target node still exists (it might have unbonded between this event getting created
     // and being executed). Do note that it's absolutely possible for a mixnode to get immediately
@@ -99,21 +116,33 @@ pub(crate) fn delegate(
         mix_rewarding.total_unit_reward,
     );
 
-    let delegation = Delegation::new(
+    let mut delegation = Delegation::new(
         owner,
         mix_id,
         mix_rewarding.total_unit_reward,
         stored_delegation_amount,
         env.block.height,
     );
+    // topping up the delegation shouldn't reset its compounding preference
+    if let Some(old_delegation) = &old_delegation {
+        delegation.auto_compound = old_delegation.auto_compound;
+    }
+    if let Some(auto_compound) = auto_compound {
+        delegation.auto_compound = auto_compound;
+    }
 
     // save on reading since `.save()` would have attempted to read old data that we already have on hand
     delegations_storage::delegations().replace(
         deps.storage,
-        storage_key,
+        storage_key.clone(),
         Some(&delegation),
         old_delegation.as_ref(),
     )?;
+    if delegation.auto_compound {
+        delegations_storage::AUTO_COMPOUNDING_DELEGATIONS.save(deps.storage, storage_key, &())?;
+    } else {
+        delegations_storage::AUTO_COMPOUNDING_DELEGATIONS.remove(deps.storage, storage_key);
+    }
     rewards_storage::MIXNODE_REWARDING.save(deps.storage, mix_id, &mix_rewarding)?;
 
     Ok(Response::new().add_event(cosmos_event))
@@ -147,6 +176,78 @@ pub(crate) fn undelegate(
     Ok(response)
 }
 
+pub(crate) fn redelegate(
+    mut deps: DepsMut<'_>,
+    env: &Env,
+    created_at: BlockHeight,
+    owner: Addr,
+    from: MixId,
+    to: MixId,
+    amount: Coin,
+) -> Result<Response, MixnetContractError> {
+    // see if the source delegation still exists (it might have been removed by an undelegation
+    // request that got resolved earlier in this epoch)
+    let storage_key = Delegation::generate_storage_key(from, &owner, None);
+    let delegation = match delegations_storage::delegations().may_load(deps.storage, storage_key)? {
+        None => return Ok(Response::default()),
+        Some(delegation) => delegation,
+    };
+
+    // if the target node is no longer bonded or is in the process of unbonding, leave the existing
+    // delegation untouched
+    match get_mixnode_details_by_id(deps.storage, to)? {
+        Some(details)
+            if details.rewarding_details.still_bonded()
+                && !details.bond_information.is_unbonding => {}
+        _ => {
+            return Ok(Response::new()
+                .add_event(new_redelegation_on_unbonded_node_event(&owner, from, to)))
+        }
+    }
+
+    let mix_rewarding =
+        rewards_storage::MIXNODE_REWARDING.may_load(deps.storage, from)?.ok_or(MixnetContractError::inconsistent_state(
+            "mixnode rewarding got removed from the storage whilst there's still an existing delegation",
+        ))?;
+
+    // both the remaining and the moved tokens keep the compounding preference of the source delegation
+    let auto_compound = Some(delegation.auto_compound);
+
+    // completely remove the source delegation alongside all of its accumulated rewards
+    // (this also appropriately adjusts the storage)
+    let mut available = delegations::helpers::undelegate(deps.storage, delegation, mix_rewarding)?;
+
+    // the delegation might be worth less than requested (for example if the user requested more than it had)
+    let mut moved = amount;
+    moved.amount = moved.amount.min(available.amount);
+    available.amount -= moved.amount;
+
+    let mut response =
+        Response::new().add_event(new_redelegation_event(created_at, &owner, &moved, from, to));
+
+    // put whatever is left back onto the source node
+    if !available.amount.is_zero() {
+        let remaining = delegate_with_auto_compounding(
+            deps.branch(),
+            env,
+            created_at,
+            owner.clone(),
+            from,
+            available,
+            auto_compound,
+        )?;
+        response = response
+            .add_submessages(remaining.messages)
+            .add_events(remaining.events);
+    }
+
+    let moved =
+        delegate_with_auto_compounding(deps, env, created_at, owner, to, moved, auto_compound)?;
+    Ok(response
+        .add_submessages(moved.messages)
+        .add_events(moved.events))
+}
+
 pub(crate) fn unbond_mixnode(
     deps: DepsMut<'_>,
     env: &Env,
@@ -296,6 +397,130 @@ pub(crate) fn decrease_pledge(
     Ok(response)
 }
 
+pub(crate) fn delegate_to_gateway(
+    deps: DepsMut<'_>,
+    env: &Env,
+    created_at: BlockHeight,
+    owner: Addr,
+    identity: IdentityKey,
+    amount: Coin,
+) -> Result<Response, MixnetContractError> {
+    // check if the target gateway still exists (it might have unbonded between this event getting created
+    // and being executed). If not, return the tokens back to the delegator
+    let is_bonded = gateways_storage::gateways()
+        .may_load(deps.storage, &identity)?
+        .is_some();
+    let mut gateway_rewarding =
+        match gateways_storage::GATEWAY_REWARDING.may_load(deps.storage, &identity)? {
+            Some(rewarding) if is_bonded && rewarding.still_bonded() => rewarding,
+            _ => {
+                let response = Response::new()
+                    .send_tokens(&owner, amount.clone())
+                    .add_event(new_gateway_delegation_on_unbonded_node_event(
+                        &owner, &identity,
+                    ));
+
+                return Ok(response);
+            }
+        };
+
+    let new_delegation_amount = amount.clone();
+    let mut stored_delegation_amount = amount;
+
+    // if there's an existing delegation, then withdraw the full reward and create a new delegation
+    // with the sum of both
+    let storage_key = GatewayDelegation::generate_storage_key(identity.clone(), &owner);
+    let old_delegation = if let Some(existing_delegation) =
+        gateways_storage::gateway_delegations().may_load(deps.storage, storage_key.clone())?
+    {
+        let og_with_reward = gateway_rewarding.remove_delegated(
+            existing_delegation.cumulative_reward_ratio,
+            &existing_delegation.amount,
+        )?;
+        stored_delegation_amount.amount += og_with_reward.amount;
+
+        Some(existing_delegation)
+    } else {
+        None
+    };
+
+    gateway_rewarding.add_base_delegation(stored_delegation_amount.amount)?;
+
+    let cosmos_event = new_gateway_delegation_event(
+        created_at,
+        &owner,
+        &new_delegation_amount,
+        &identity,
+        gateway_rewarding.total_unit_reward,
+    );
+
+    let delegation = GatewayDelegation::new(
+        owner,
+        identity.clone(),
+        gateway_rewarding.total_unit_reward,
+        stored_delegation_amount,
+        env.block.height,
+    );
+
+    gateways_storage::gateway_delegations().replace(
+        deps.storage,
+        storage_key,
+        Some(&delegation),
+        old_delegation.as_ref(),
+    )?;
+    gateways_storage::GATEWAY_REWARDING.save(deps.storage, &identity, &gateway_rewarding)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
+pub(crate) fn undelegate_from_gateway(
+    deps: DepsMut<'_>,
+    created_at: BlockHeight,
+    owner: Addr,
+    identity: IdentityKey,
+) -> Result<Response, MixnetContractError> {
+    // see if the delegation still exists (in case of impatient user who decided to send multiple
+    // undelegation requests in an epoch)
+    let storage_key = GatewayDelegation::generate_storage_key(identity.clone(), &owner);
+    let delegation =
+        match gateways_storage::gateway_delegations().may_load(deps.storage, storage_key)? {
+            None => return Ok(Response::default()),
+            Some(delegation) => delegation,
+        };
+    let mut gateway_rewarding = gateways_storage::GATEWAY_REWARDING
+        .may_load(deps.storage, &identity)?
+        .ok_or(MixnetContractError::inconsistent_state(
+            "gateway rewarding got removed from the storage whilst there's still an existing delegation",
+        ))?;
+
+    let tokens_to_return = gateway_rewarding
+        .remove_delegated(delegation.cumulative_reward_ratio, &delegation.amount)?;
+
+    // if the gateway has already unbonded and this was the final delegation, purge the rewarding data
+    if !gateway_rewarding.still_bonded() && gateway_rewarding.unique_delegations == 0 {
+        gateways_storage::GATEWAY_REWARDING.remove(deps.storage, &identity);
+    } else {
+        gateways_storage::GATEWAY_REWARDING.save(deps.storage, &identity, &gateway_rewarding)?;
+    }
+    gateways_storage::gateway_delegations().replace(
+        deps.storage,
+        delegation.storage_key(),
+        None,
+        Some(&delegation),
+    )?;
+
+    let response = Response::new()
+        .send_tokens(&owner, tokens_to_return.clone())
+        .add_event(new_gateway_undelegation_event(
+            created_at,
+            &owner,
+            &identity,
+            &tokens_to_return,
+        ));
+
+    Ok(response)
+}
+
 impl ContractExecutableEvent for PendingEpochEventData {
     fn execute(self, deps: DepsMut<'_>, env: &Env) -> Result<Response, MixnetContractError> {
         // note that the basic validation on all those events was already performed before
@@ -310,6 +535,13 @@ impl ContractExecutableEvent for PendingEpochEventData {
             PendingEpochEventKind::Undelegate { owner, mix_id, .. } => {
                 undelegate(deps, self.created_at, owner, mix_id)
             }
+            PendingEpochEventKind::Redelegate {
+                owner,
+                from,
+                to,
+                amount,
+                ..
+            } => redelegate(deps, env, self.created_at, owner, from, to, amount),
             PendingEpochEventKind::PledgeMore { mix_id, amount } => {
                 increase_pledge(deps, self.created_at, mix_id, amount)
             }
@@ -323,6 +555,14 @@ impl ContractExecutableEvent for PendingEpochEventData {
             PendingEpochEventKind::UpdateActiveSetSize { new_size } => {
                 update_active_set_size(deps, self.created_at, new_size)
             }
+            PendingEpochEventKind::DelegateToGateway {
+                owner,
+                identity,
+                amount,
+            } => delegate_to_gateway(deps, env, self.created_at, owner, identity, amount),
+            PendingEpochEventKind::UndelegateFromGateway { owner, identity } => {
+                undelegate_from_gateway(deps, self.created_at, owner, identity)
+            }
         }
     }
 }
@@ -355,6 +595,29 @@ pub(crate) fn change_mix_cost_params(
     Ok(Response::new().add_event(cosmos_event))
 }
 
+pub(crate) fn change_gateway_cost_params(
+    deps: DepsMut<'_>,
+    created_at: BlockHeight,
+    identity: IdentityKey,
+    new_costs: MixNodeCostParams,
+) -> Result<Response, MixnetContractError> {
+    // almost an entire interval might have passed since the request was issued -> check if the
+    // gateway is still bonded
+    let mut gateway_rewarding =
+        match gateways_storage::GATEWAY_REWARDING.may_load(deps.storage, &identity)? {
+            Some(rewarding) if rewarding.still_bonded() => rewarding,
+            // if gateway doesn't exist anymore, don't do anything, simple as that.
+            _ => return Ok(Response::default()),
+        };
+
+    let cosmos_event = new_gateway_cost_params_update_event(created_at, &identity, &new_costs);
+
+    gateway_rewarding.cost_params = new_costs;
+    gateways_storage::GATEWAY_REWARDING.save(deps.storage, &identity, &gateway_rewarding)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
 pub(crate) fn update_rewarding_params(
     deps: DepsMut<'_>,
     created_at: BlockHeight,
@@ -408,6 +671,10 @@ impl ContractExecutableEvent for PendingIntervalEventData {
                 mix_id: mix,
                 new_costs,
             } => change_mix_cost_params(deps, self.created_at, mix, new_costs),
+            PendingIntervalEventKind::ChangeGatewayCostParams {
+                identity,
+                new_costs,
+            } => change_gateway_cost_params(deps, self.created_at, identity, new_costs),
             PendingIntervalEventKind::UpdateRewardingParams { update } => {
                 update_rewarding_params(deps, self.created_at, update)
             }
@@ -851,6 +1118,178 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod redelegating {
+        use super::*;
+        use crate::delegations::transactions::try_set_delegation_auto_compounding;
+        use crate::mixnodes::transactions::try_remove_mixnode;
+        use crate::support::tests::fixtures::TEST_COIN_DENOM;
+        use crate::support::tests::test_helpers::get_bank_send_msg;
+        use cosmwasm_std::coin;
+        use cosmwasm_std::testing::mock_info;
+
+        fn stored_delegation(test: &TestSetup, mix_id: MixId, owner: &str) -> Option<Delegation> {
+            let storage_key =
+                Delegation::generate_storage_key(mix_id, &Addr::unchecked(owner), None);
+            delegations_storage::delegations()
+                .may_load(test.deps().storage, storage_key)
+                .unwrap()
+        }
+
+        #[test]
+        fn doesnt_do_anything_if_source_delegation_doesnt_exist() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+
+            let owner = Addr::unchecked("delegator");
+            let amount = coin(50_000_000, TEST_COIN_DENOM);
+
+            let res = redelegate(test.deps_mut(), &env, 123, owner, from, to, amount).unwrap();
+            assert_eq!(res, Response::default());
+            assert!(stored_delegation(&test, to, "delegator").is_none());
+        }
+
+        #[test]
+        fn leaves_source_delegation_intact_if_target_is_not_bonded() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+
+            let owner = "delegator";
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+            let before = stored_delegation(&test, from, owner).unwrap();
+
+            try_remove_mixnode(test.deps_mut(), env.clone(), mock_info("owner2", &[])).unwrap();
+
+            let amount = coin(50_000_000, TEST_COIN_DENOM);
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from,
+                to,
+                amount,
+            )
+            .unwrap();
+            assert!(get_bank_send_msg(&res).is_none());
+
+            assert_eq!(stored_delegation(&test, from, owner).unwrap(), before);
+            assert!(stored_delegation(&test, to, owner).is_none());
+        }
+
+        #[test]
+        fn moves_requested_amount_and_keeps_the_rest_on_the_source() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+
+            let owner = "delegator";
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+
+            let amount = coin(30_000_000, TEST_COIN_DENOM);
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from,
+                to,
+                amount,
+            )
+            .unwrap();
+            assert!(get_bank_send_msg(&res).is_none());
+
+            let source = stored_delegation(&test, from, owner).unwrap();
+            let target = stored_delegation(&test, to, owner).unwrap();
+            assert_eq!(source.amount, coin(70_000_000, TEST_COIN_DENOM));
+            assert_eq!(target.amount, coin(30_000_000, TEST_COIN_DENOM));
+
+            assert_eq!(
+                test.mix_rewarding(from).delegates,
+                Decimal::from_atomics(70_000_000u128, 0).unwrap()
+            );
+            assert_eq!(
+                test.mix_rewarding(to).delegates,
+                Decimal::from_atomics(30_000_000u128, 0).unwrap()
+            );
+        }
+
+        #[test]
+        fn moves_entire_delegation_if_requested_amount_exceeds_it() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+
+            let owner = "delegator";
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+            test.add_immediate_delegation(owner, 20_000_000u32, to);
+
+            let amount = coin(500_000_000, TEST_COIN_DENOM);
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from,
+                to,
+                amount,
+            )
+            .unwrap();
+            assert!(get_bank_send_msg(&res).is_none());
+
+            assert!(stored_delegation(&test, from, owner).is_none());
+            let target = stored_delegation(&test, to, owner).unwrap();
+            assert_eq!(target.amount, coin(120_000_000, TEST_COIN_DENOM));
+
+            let rewarding = test.mix_rewarding(from);
+            assert!(rewarding.delegates.is_zero());
+            assert_eq!(rewarding.unique_delegations, 0);
+        }
+
+        #[test]
+        fn keeps_the_auto_compounding_preference() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let from = test.add_dummy_mixnode("owner1", None);
+            let to = test.add_dummy_mixnode("owner2", None);
+
+            let owner = "delegator";
+            test.add_immediate_delegation(owner, 100_000_000u32, from);
+            try_set_delegation_auto_compounding(test.deps_mut(), mock_info(owner, &[]), from, true)
+                .unwrap();
+
+            let amount = coin(30_000_000, TEST_COIN_DENOM);
+            redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from,
+                to,
+                amount,
+            )
+            .unwrap();
+
+            for mix_id in [from, to] {
+                assert!(
+                    stored_delegation(&test, mix_id, owner)
+                        .unwrap()
+                        .auto_compound
+                );
+                let storage_key =
+                    Delegation::generate_storage_key(mix_id, &Addr::unchecked(owner), None);
+                assert!(delegations_storage::AUTO_COMPOUNDING_DELEGATIONS
+                    .has(test.deps().storage, storage_key));
+            }
+        }
+    }
+
     #[cfg(test)]
     mod mixnode_unbonding {
         use super::*;
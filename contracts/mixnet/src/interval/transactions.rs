@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::storage;
+use crate::delegations::transactions::perform_auto_compounding;
 use crate::interval::helpers::change_interval_config;
 use crate::interval::pending_events::ContractExecutableEvent;
 use crate::interval::storage::push_new_interval_event;
@@ -14,9 +15,10 @@ use crate::support::helpers::{
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Order, Response, Storage};
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_advance_epoch_event, new_epoch_transition_start_event,
-    new_pending_epoch_events_execution_event, new_pending_interval_config_update_event,
-    new_pending_interval_events_execution_event, new_reconcile_pending_events,
+    new_advance_epoch_event, new_auto_compounding_execution_event,
+    new_epoch_transition_start_event, new_pending_epoch_events_execution_event,
+    new_pending_interval_config_update_event, new_pending_interval_events_execution_event,
+    new_reconcile_pending_events,
 };
 use mixnet_contract_common::pending_events::PendingIntervalEventKind;
 use mixnet_contract_common::{EpochState, EpochStatus, LayerAssignment, MixId};
@@ -149,6 +151,19 @@ pub fn try_reconcile_epoch_events(
         limit = limit.map(|l| l - executed)
     }
 
+    // once the epoch events got cleared (so that the compounding uses the most up to date delegations),
+    // add the delegator rewards to the principal of the delegations that opted into it
+    let (mut sub_response, compounded, compounding_finished) =
+        perform_auto_compounding(deps.branch(), limit)?;
+    response.messages.append(&mut sub_response.messages);
+    response.attributes.append(&mut sub_response.attributes);
+    response.events.append(&mut sub_response.events);
+    response
+        .events
+        .push(new_auto_compounding_execution_event(compounded));
+
+    limit = limit.map(|l| l - compounded);
+
     if interval.is_current_interval_over(&env) {
         // first clear epoch events queue and then touch the interval actions
         let (mut sub_response, executed) =
@@ -164,7 +179,8 @@ pub fn try_reconcile_epoch_events(
     // if there are no more events to clear, go into the next state
     let pending_events = super::queries::query_number_of_pending_events(deps.as_ref())?;
     // we can only progress if there are no epoch events AND if the interval has finished, that there are no interval events
-    let progress = if pending_events.epoch_events == 0 {
+    // (and all the delegator rewards got compounded)
+    let progress = if pending_events.epoch_events == 0 && compounding_finished {
         if interval.is_current_interval_over(&env) {
             pending_events.interval_events == 0
         } else {
@@ -1308,6 +1324,7 @@ mod tests {
                 amount: coins(123, TEST_COIN_DENOM),
             }));
             expected_events.push(new_pending_epoch_events_execution_event(1));
+            expected_events.push(new_auto_compounding_execution_event(0));
 
             // interval event
             let update = IntervalRewardingParamsUpdate {
@@ -1,10 +1,15 @@
 // Copyright 2022-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::gateways::helpers::default_gateway_cost_params;
+use crate::gateways::storage as gateways_storage;
 use crate::interval::storage as interval_storage;
+use crate::rewards::storage as rewards_storage;
 use cosmwasm_std::{DepsMut, Order, Storage};
 use mixnet_contract_common::error::MixnetContractError;
-use mixnet_contract_common::PendingEpochEventKind;
+use mixnet_contract_common::{
+    GatewayBond, GatewayRewarding, GatewayRewardingParams, PendingEpochEventKind,
+};
 
 fn ensure_no_pending_proxy_events(storage: &dyn Storage) -> Result<(), MixnetContractError> {
     let last_executed = interval_storage::LAST_PROCESSED_EPOCH_EVENT.load(storage)?;
@@ -49,3 +54,45 @@ pub(crate) fn vesting_purge(deps: DepsMut) -> Result<(), MixnetContractError> {
 
     Ok(())
 }
+
+pub(crate) fn introduce_gateway_rewarding(
+    deps: DepsMut,
+    gateway_rewarding_params: Option<GatewayRewardingParams>,
+) -> Result<(), MixnetContractError> {
+    if let Some(params) = gateway_rewarding_params {
+        gateways_storage::GATEWAY_REWARDING_PARAMS.save(deps.storage, &params)?;
+    } else if !gateways_storage::GATEWAY_REWARDING_PARAMS.exists(deps.storage) {
+        let stake_saturation_point = rewards_storage::REWARDING_PARAMS
+            .load(deps.storage)?
+            .interval
+            .stake_saturation_point;
+        gateways_storage::GATEWAY_REWARDING_PARAMS.save(
+            deps.storage,
+            &GatewayRewardingParams::disabled(stake_saturation_point),
+        )?;
+    }
+
+    // every gateway that has bonded before the rewarding was introduced needs its rewarding details
+    let cost_params = default_gateway_cost_params(deps.storage)?;
+    let current_epoch =
+        interval_storage::current_interval(deps.storage)?.current_epoch_absolute_id();
+
+    let gateways = gateways_storage::gateways()
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|record| record.map(|(_, bond)| bond))
+        .collect::<Result<Vec<GatewayBond>, _>>()?;
+
+    for bond in gateways {
+        if gateways_storage::GATEWAY_REWARDING.has(deps.storage, bond.identity()) {
+            continue;
+        }
+        let rewarding = GatewayRewarding::initialise_new(
+            cost_params.clone(),
+            &bond.pledge_amount,
+            current_epoch,
+        )?;
+        gateways_storage::GATEWAY_REWARDING.save(deps.storage, bond.identity(), &rewarding)?;
+    }
+
+    Ok(())
+}
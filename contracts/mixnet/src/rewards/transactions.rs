@@ -3,6 +3,7 @@
 
 use super::storage;
 use crate::delegations::storage as delegations_storage;
+use crate::gateways::storage as gateways_storage;
 use crate::interval::storage as interval_storage;
 use crate::interval::storage::{push_new_epoch_event, push_new_interval_event};
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
@@ -17,17 +18,19 @@ use crate::support::helpers::{
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_active_set_update_event, new_mix_rewarding_event,
-    new_not_found_mix_operator_rewarding_event, new_pending_active_set_update_event,
-    new_pending_rewarding_params_update_event, new_rewarding_params_update_event,
-    new_withdraw_delegator_reward_event, new_withdraw_operator_reward_event,
+    new_active_set_update_event, new_gateway_rewarding_event,
+    new_gateway_rewarding_params_update_event, new_mix_rewarding_event,
+    new_not_found_gateway_operator_rewarding_event, new_not_found_mix_operator_rewarding_event,
+    new_pending_active_set_update_event, new_pending_rewarding_params_update_event,
+    new_rewarding_params_update_event, new_withdraw_delegator_reward_event,
+    new_withdraw_operator_reward_event, new_zero_uptime_gateway_operator_rewarding_event,
     new_zero_uptime_mix_operator_rewarding_event,
 };
 use mixnet_contract_common::pending_events::{PendingEpochEventKind, PendingIntervalEventKind};
 use mixnet_contract_common::reward_params::{
     IntervalRewardingParamsUpdate, NodeRewardParams, Performance,
 };
-use mixnet_contract_common::{Delegation, EpochState, MixId};
+use mixnet_contract_common::{Delegation, EpochState, GatewayRewardingParams, IdentityKey, MixId};
 
 pub(crate) fn try_reward_mixnode(
     deps: DepsMut<'_>,
@@ -140,6 +143,117 @@ pub(crate) fn try_reward_mixnode(
     )))
 }
 
+pub(crate) fn try_reward_gateway(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    identity: IdentityKey,
+    node_performance: Performance,
+) -> Result<Response, MixnetContractError> {
+    // gateways are rewarded by the same validator that's advancing the epoch,
+    // once all the mixnodes have already been rewarded and before any events got reconciled
+    let current_epoch_status = ensure_can_advance_epoch(&info.sender, deps.storage)?;
+    if !matches!(current_epoch_status.state, EpochState::ReconcilingEvents) {
+        return Err(MixnetContractError::EpochNotInEventReconciliationState {
+            current_state: current_epoch_status.state,
+        });
+    }
+
+    // see if the epoch has finished
+    let interval = interval_storage::current_interval(deps.storage)?;
+    if !interval.is_current_epoch_over(&env) {
+        return Err(MixnetContractError::EpochInProgress {
+            current_block_time: env.block.time.seconds(),
+            epoch_start: interval.current_epoch_start_unix_timestamp(),
+            epoch_end: interval.current_epoch_end_unix_timestamp(),
+        });
+    }
+    let absolute_epoch_id = interval.current_epoch_absolute_id();
+
+    // there's a chance of this failing to load the details if the gateway unbonded before rewards
+    // were distributed and all of its delegators are also gone
+    let mut gateway_rewarding =
+        match gateways_storage::GATEWAY_REWARDING.may_load(deps.storage, &identity)? {
+            Some(gateway_rewarding) if gateway_rewarding.still_bonded() => gateway_rewarding,
+            // don't fail if the node has unbonded as we don't want to fail the underlying transaction
+            _ => {
+                return Ok(Response::new().add_event(
+                    new_not_found_gateway_operator_rewarding_event(interval, &identity),
+                ));
+            }
+        };
+
+    // unlike mixnodes, gateways are not tracked via the epoch state, so this is the only guard
+    // against rewarding the same node twice
+    if absolute_epoch_id == gateway_rewarding.last_rewarded_epoch {
+        return Err(MixnetContractError::GatewayAlreadyRewarded {
+            identity,
+            absolute_epoch_id,
+        });
+    }
+
+    // only up to `rewarded_gateways` nodes can get rewarded within a single epoch,
+    // otherwise the epoch budget could have been exceeded
+    let rewarding_params = gateways_storage::GATEWAY_REWARDING_PARAMS.load(deps.storage)?;
+    let rewarded_in_epoch =
+        match gateways_storage::GATEWAYS_REWARDED_IN_EPOCH.may_load(deps.storage)? {
+            Some((epoch_id, rewarded)) if epoch_id == absolute_epoch_id => rewarded,
+            _ => 0,
+        };
+    if rewarded_in_epoch >= rewarding_params.rewarded_gateways {
+        return Err(MixnetContractError::GatewayRewardingLimitReached {
+            rewarded_gateways: rewarding_params.rewarded_gateways,
+            absolute_epoch_id,
+        });
+    }
+    gateways_storage::GATEWAYS_REWARDED_IN_EPOCH
+        .save(deps.storage, &(absolute_epoch_id, rewarded_in_epoch + 1))?;
+
+    let prior_delegates = gateway_rewarding.delegates;
+    let prior_unit_reward = gateway_rewarding.full_reward_ratio();
+
+    // no need to calculate anything as rewards are going to be 0 for everything
+    // however, we still need to update last_rewarded_epoch field
+    if node_performance.is_zero() {
+        gateway_rewarding.last_rewarded_epoch = absolute_epoch_id;
+        gateways_storage::GATEWAY_REWARDING.save(deps.storage, &identity, &gateway_rewarding)?;
+        return Ok(
+            Response::new().add_event(new_zero_uptime_gateway_operator_rewarding_event(
+                interval, &identity,
+            )),
+        );
+    }
+
+    // make sure node's profit margin is within the allowed range,
+    // if not adjust it accordingly
+    let params = mixnet_params_storage::CONTRACT_STATE
+        .load(deps.storage)?
+        .params;
+    gateway_rewarding.normalise_profit_margin(params.profit_margin);
+    gateway_rewarding.normalise_operating_cost(params.interval_operating_cost);
+
+    // calculate each step separate for easier accounting
+    let node_reward = rewarding_params.node_reward(&gateway_rewarding, node_performance);
+    let reward_distribution = gateway_rewarding.determine_reward_split(
+        node_reward,
+        node_performance,
+        interval.epochs_in_interval(),
+    );
+    gateway_rewarding.distribute_rewards(reward_distribution, absolute_epoch_id);
+
+    // persist changes happened to the storage
+    gateways_storage::GATEWAY_REWARDING.save(deps.storage, &identity, &gateway_rewarding)?;
+    storage::reward_accounting(deps.storage, node_reward)?;
+
+    Ok(Response::new().add_event(new_gateway_rewarding_event(
+        interval,
+        &identity,
+        reward_distribution,
+        prior_delegates,
+        prior_unit_reward,
+    )))
+}
+
 pub(crate) fn try_withdraw_operator_reward(
     deps: DepsMut<'_>,
     info: MessageInfo,
@@ -309,6 +423,20 @@ pub(crate) fn try_update_rewarding_params(
     }
 }
 
+pub(crate) fn try_update_gateway_rewarding_params(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    updated_params: GatewayRewardingParams,
+) -> Result<Response, MixnetContractError> {
+    ensure_is_owner(info.sender, deps.storage)?;
+
+    // changing rewarding parameters is only allowed if the epoch is currently not in the process of being advanced
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    gateways_storage::GATEWAY_REWARDING_PARAMS.save(deps.storage, &updated_params)?;
+    Ok(Response::new().add_event(new_gateway_rewarding_params_update_event(&updated_params)))
+}
+
 #[cfg(test)]
 pub mod tests {
     use cosmwasm_std::testing::mock_info;
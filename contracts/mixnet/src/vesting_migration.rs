@@ -81,8 +81,21 @@ pub(crate) fn try_migrate_vested_delegation(
     // update the delegation and save it under the correct storage key
     delegation.proxy = None;
     let updated_storage_key = Delegation::generate_storage_key(mix_id, &info.sender, None);
-    delegations_storage::delegations().remove(deps.storage, storage_key)?;
-    delegations_storage::delegations().save(deps.storage, updated_storage_key, &delegation)?;
+    delegations_storage::delegations().remove(deps.storage, storage_key.clone())?;
+    delegations_storage::delegations().save(
+        deps.storage,
+        updated_storage_key.clone(),
+        &delegation,
+    )?;
+
+    if delegation.auto_compound {
+        delegations_storage::AUTO_COMPOUNDING_DELEGATIONS.remove(deps.storage, storage_key);
+        delegations_storage::AUTO_COMPOUNDING_DELEGATIONS.save(
+            deps.storage,
+            updated_storage_key,
+            &(),
+        )?;
+    }
 
     Ok(Response::new().add_message(wasm_execute(
         vesting_contract,
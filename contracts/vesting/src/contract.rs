@@ -70,9 +70,11 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, VestingContractError> {
     match msg {
-        ExecuteMsg::TrackReward { amount, address } => {
-            try_track_reward(deps, info, amount, &address)
-        }
+        ExecuteMsg::TrackReward {
+            amount,
+            address,
+            compounded_into,
+        } => try_track_reward(deps, env, info, amount, &address, compounded_into),
         ExecuteMsg::UpdateMixnetAddress { address } => {
             try_update_mixnet_address(address, info, deps)
         }
@@ -44,6 +44,15 @@ pub trait DelegatingAccount {
         amount: Coin,
         storage: &mut dyn Storage,
     ) -> Result<(), VestingContractError>;
+    // track_compounded_reward performs internal vesting accounting necessary when
+    // the reward of the vesting delegation got added to its principal.
+    fn track_compounded_reward(
+        &self,
+        block_timestamp_secs: u64,
+        mix_id: MixId,
+        reward: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), VestingContractError>;
     fn track_migrated_delegation(
         &self,
         mix_id: MixId,
@@ -377,15 +377,22 @@ pub fn try_track_decrease_mixnode_pledge(
 /// Track reward collection, invoked by the mixnert contract after sucessful reward compounding or claiming
 pub fn try_track_reward(
     deps: DepsMut<'_>,
+    env: Env,
     info: MessageInfo,
     amount: Coin,
     address: &str,
+    compounded_into: Option<MixId>,
 ) -> Result<Response, VestingContractError> {
     if info.sender != MIXNET_CONTRACT_ADDRESS.load(deps.storage)? {
         return Err(VestingContractError::NotMixnetContract(info.sender));
     }
     let account = account_from_address(address, deps.storage, deps.api)?;
-    account.track_reward(amount, deps.storage)?;
+    if let Some(mix_id) = compounded_into {
+        // the tokens never left the mixnet contract, they just got added to the existing delegation
+        account.track_compounded_reward(env.block.time.seconds(), mix_id, amount, deps.storage)?;
+    } else {
+        account.track_reward(amount, deps.storage)?;
+    }
     Ok(Response::new().add_event(new_track_reward_event()))
 }
 
@@ -126,6 +126,22 @@ impl DelegatingAccount for Account {
         Ok(())
     }
 
+    fn track_compounded_reward(
+        &self,
+        block_timestamp_secs: u64,
+        mix_id: MixId,
+        reward: Coin,
+        storage: &mut dyn Storage,
+    ) -> Result<(), VestingContractError> {
+        // unlike a fresh delegation, the balance is left untouched as the tokens
+        // have never been paid out to this account
+        save_delegation(
+            (self.storage_key(), mix_id, block_timestamp_secs),
+            reward.amount,
+            storage,
+        )
+    }
+
     fn track_migrated_delegation(
         &self,
         mix_id: MixId,
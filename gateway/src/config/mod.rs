@@ -10,6 +10,7 @@ use nym_config::{
     must_get_home, read_config_from_toml_file, save_formatted_config_to_file, NymConfigTemplate,
     DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
 use nym_network_defaults::{mainnet, DEFAULT_NYM_NODE_HTTP_PORT};
 use serde::{Deserialize, Serialize};
 use std::io;
@@ -461,6 +462,22 @@ pub struct Debug {
 
     #[serde(default)]
     pub zk_nym_tickets: ZkNymTicketHandlerDebug,
+
+    /// Settings of the filter used for detecting replayed sphinx packets.
+    #[serde(default)]
+    pub replay_protection: ReplayProtectionConfig,
+
+    /// Specifies whether this node should **NOT** use noise protocol for its mix connections.
+    #[serde(default)]
+    pub unsafe_disable_noise: bool,
+
+    /// Retention limits of the messages stored for offline clients.
+    #[serde(default)]
+    pub inbox_retention: InboxRetentionDebug,
+
+    /// Limits on the rate at which a single connected client is allowed to send sphinx packets.
+    #[serde(default)]
+    pub client_rate_limiting: ClientRateLimitingDebug,
 }
 
 impl Default for Debug {
@@ -478,6 +495,10 @@ impl Default for Debug {
                 DEFAULT_CLIENT_BANDWIDTH_MAX_DELTA_FLUSHING_AMOUNT,
             use_legacy_framed_packet_version: false,
             zk_nym_tickets: Default::default(),
+            replay_protection: Default::default(),
+            unsafe_disable_noise: false,
+            inbox_retention: Default::default(),
+            client_rate_limiting: Default::default(),
         }
     }
 }
@@ -525,3 +546,74 @@ impl Default for ZkNymTicketHandlerDebug {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InboxRetentionDebug {
+    /// Specifies the maximum amount of time a message is going to be kept for an offline client
+    /// before it gets removed.
+    #[serde(with = "humantime_serde")]
+    pub max_message_age: Duration,
+
+    /// Specifies the maximum total size (in bytes) of messages stored for a single client.
+    /// Once exceeded, the oldest messages of that client are removed first.
+    pub max_client_bytes: u64,
+
+    /// Specifies how often the stored messages are checked against the above limits.
+    #[serde(with = "humantime_serde")]
+    pub pruning_interval: Duration,
+}
+
+impl InboxRetentionDebug {
+    pub const DEFAULT_MAX_MESSAGE_AGE: Duration = Duration::from_secs(7 * 86400);
+    pub const DEFAULT_MAX_CLIENT_BYTES: u64 = 64 * 1024 * 1024; // 64MB
+    pub const DEFAULT_PRUNING_INTERVAL: Duration = Duration::from_secs(600);
+}
+
+impl Default for InboxRetentionDebug {
+    fn default() -> Self {
+        InboxRetentionDebug {
+            max_message_age: Self::DEFAULT_MAX_MESSAGE_AGE,
+            max_client_bytes: Self::DEFAULT_MAX_CLIENT_BYTES,
+            pruning_interval: Self::DEFAULT_PRUNING_INTERVAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientRateLimitingDebug {
+    /// Specifies whether the per-client rate limiting of forwarded sphinx packets is enabled.
+    pub enabled: bool,
+
+    /// Specifies the sustained number of sphinx packets a single client is allowed to send per second.
+    pub packets_per_second: u32,
+
+    /// Specifies the maximum number of sphinx packets a single client is allowed to send in a burst.
+    pub packet_burst: u32,
+
+    /// Specifies the sustained number of bytes a single client is allowed to send per second.
+    pub bytes_per_second: u64,
+
+    /// Specifies the maximum number of bytes a single client is allowed to send in a burst.
+    pub byte_burst: u64,
+}
+
+impl ClientRateLimitingDebug {
+    pub const DEFAULT_PACKETS_PER_SECOND: u32 = 2000;
+    pub const DEFAULT_PACKET_BURST: u32 = 4000;
+    pub const DEFAULT_BYTES_PER_SECOND: u64 = 8 * 1024 * 1024; // 8MB
+    pub const DEFAULT_BYTE_BURST: u64 = 16 * 1024 * 1024; // 16MB
+}
+
+impl Default for ClientRateLimitingDebug {
+    fn default() -> Self {
+        ClientRateLimitingDebug {
+            enabled: true,
+            packets_per_second: Self::DEFAULT_PACKETS_PER_SECOND,
+            packet_burst: Self::DEFAULT_PACKET_BURST,
+            bytes_per_second: Self::DEFAULT_BYTES_PER_SECOND,
+            byte_burst: Self::DEFAULT_BYTE_BURST,
+        }
+    }
+}
@@ -6,10 +6,12 @@ use crate::error::GatewayError;
 use crate::helpers::load_public_key;
 use nym_bin_common::bin_info_owned;
 use nym_crypto::asymmetric::{encryption, identity};
+use nym_exit_policy::upstream::SharedExitPolicy;
 use nym_network_requester::RequestFilter;
 use nym_node_http_api::api::api_requests;
-use nym_node_http_api::api::api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use nym_node_http_api::api::api_requests::SignedHostInformation;
+use nym_node_http_api::state::metrics::{SharedInboxStats, SharedMixingStats};
+use nym_node_http_api::state::AppState;
 use nym_node_http_api::NymNodeHttpError;
 use nym_sphinx::addressing::clients::Recipient;
 use nym_task::TaskClient;
@@ -43,6 +45,7 @@ fn load_host_details(
             ed25519_identity: identity_keypair.public_key().to_base58_string(),
             x25519_sphinx: sphinx_key.to_base58_string(),
             x25519_noise: "".to_string(),
+            x25519_sphinx_rotation: None,
         },
     };
 
@@ -134,8 +137,10 @@ fn load_ip_packet_router_details(
 pub(crate) struct HttpApiBuilder<'a> {
     gateway_config: &'a Config,
     network_requester_config: Option<&'a nym_network_requester::Config>,
-    exit_policy: Option<UsedExitPolicy>,
+    exit_policy: Option<SharedExitPolicy>,
     ip_packet_router_config: Option<&'a nym_ip_packet_router::Config>,
+    inbox_stats: SharedInboxStats,
+    mixing_stats: SharedMixingStats,
 
     identity_keypair: &'a identity::KeyPair,
     // TODO: this should be a wg specific key and not re-used sphinx
@@ -153,6 +158,8 @@ impl<'a> HttpApiBuilder<'a> {
             network_requester_config: None,
             ip_packet_router_config: None,
             exit_policy: None,
+            inbox_stats: Default::default(),
+            mixing_stats: Default::default(),
             identity_keypair,
             sphinx_keypair,
         }
@@ -177,34 +184,10 @@ impl<'a> HttpApiBuilder<'a> {
             return self;
         };
 
-        // we can cheat here a bit since we're not refreshing the exit policy
-        // thus:
-        // - we can ignore the Arc pointer and clone the inner value
-        // - we can set the last refresh time to the current time
-        //
-        // once we start refreshing it, we'll have to change it, but at that point
-        // the allow list will be probably be completely removed and thus the pointer management
-        // will be much easier
-
-        let upstream = request_filter.current_exit_policy_filter().upstream();
-
-        // if there's no upstream (i.e. open proxy), we couldn't have possibly updated it : )
-        let last_updated = if upstream.is_some() {
-            #[allow(clippy::expect_used)]
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("system clock is set to before the unix epoch")
-                .as_secs()
-        } else {
-            0
-        };
-
-        self.exit_policy = Some(UsedExitPolicy {
-            enabled: true,
-            upstream_source: upstream.map(|u| u.to_string()).unwrap_or_default(),
-            last_updated,
-            policy: Some(request_filter.current_exit_policy_filter().policy().clone()),
-        });
+        // share the policy itself rather than its snapshot so that any refreshes are reflected
+        self.exit_policy = Some(Arc::clone(
+            request_filter.current_exit_policy_filter().shared_policy(),
+        ));
 
         self
     }
@@ -218,6 +201,18 @@ impl<'a> HttpApiBuilder<'a> {
         self
     }
 
+    #[must_use]
+    pub(crate) fn with_inbox_stats(mut self, inbox_stats: SharedInboxStats) -> Self {
+        self.inbox_stats = inbox_stats;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_mixing_stats(mut self, mixing_stats: SharedMixingStats) -> Self {
+        self.mixing_stats = mixing_stats;
+        self
+    }
+
     pub(crate) fn start(self, task_client: TaskClient) -> Result<(), GatewayError> {
         debug!("starting http API");
 
@@ -245,7 +240,7 @@ impl<'a> HttpApiBuilder<'a> {
             )?);
 
             if let Some(exit_policy) = self.exit_policy {
-                config = config.with_used_exit_policy(exit_policy)
+                config = config.with_exit_policy_source(exit_policy)
             }
         }
 
@@ -257,7 +252,10 @@ impl<'a> HttpApiBuilder<'a> {
         }
 
         let bind_address = self.gateway_config.http.bind_address;
-        let router = nym_node_http_api::NymNodeRouter::new(config, None);
+        let app_state = AppState::new()
+            .with_inbox_stats(self.inbox_stats)
+            .with_mixing_stats(self.mixing_stats);
+        let router = nym_node_http_api::NymNodeRouter::new(config, Some(app_state));
 
         tokio::spawn(async move {
             let server = match router.build_server(&bind_address).await {
@@ -6,6 +6,7 @@ use crate::node::client_handling::bandwidth::Bandwidth;
 pub(crate) mod active_clients;
 mod bandwidth;
 pub(crate) mod embedded_clients;
+pub(crate) mod rate_limiting;
 pub(crate) mod websocket;
 
 pub(crate) const FREE_TESTNET_BANDWIDTH_VALUE: Bandwidth =
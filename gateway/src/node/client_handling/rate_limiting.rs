@@ -0,0 +1,234 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::ClientRateLimitingDebug;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Maximum amount of time a client is ever asked to back off for, so that it wouldn't have to
+/// deal with absurd values when, for example, the configured sending rate is 0.
+pub(crate) const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Simple token bucket that gets continuously refilled at a constant rate up to its capacity.
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(refill_per_sec: u64, capacity: u64, now: Instant) -> Self {
+        // make sure the bucket can always hold at least a single second worth of tokens
+        let capacity = capacity.max(refill_per_sec) as f64;
+        TokenBucket {
+            capacity,
+            available: capacity,
+            refill_per_sec: refill_per_sec as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Returns the amount of time the caller has to wait until the bucket contains the specified
+    /// number of tokens, or `None` if they're already available.
+    fn time_until_available(&self, amount: f64) -> Option<Duration> {
+        if self.capacity <= 0. {
+            return Some(Duration::MAX);
+        }
+
+        // if the bucket can never hold the requested amount, a full bucket is good enough
+        let missing = amount.min(self.capacity) - self.available;
+        if missing <= 0. {
+            return None;
+        }
+        if self.refill_per_sec <= 0. {
+            return Some(Duration::MAX);
+        }
+        Some(Duration::from_secs_f64(missing / self.refill_per_sec))
+    }
+
+    fn consume(&mut self, amount: f64) {
+        self.available = (self.available - amount).max(0.);
+    }
+}
+
+/// Per-client limiter of the rate at which sphinx packets are allowed to be forwarded into the mixnet,
+/// both in terms of the number of packets and the number of bytes.
+pub(crate) struct ClientRateLimiter {
+    packets: TokenBucket,
+    bytes: TokenBucket,
+}
+
+impl ClientRateLimiter {
+    /// Creates new instance of the limiter if the rate limiting has been enabled in the config.
+    pub(crate) fn new(cfg: ClientRateLimitingDebug) -> Option<Self> {
+        if !cfg.enabled {
+            return None;
+        }
+
+        let now = Instant::now();
+        Some(ClientRateLimiter {
+            packets: TokenBucket::new(cfg.packets_per_second as u64, cfg.packet_burst as u64, now),
+            bytes: TokenBucket::new(cfg.bytes_per_second, cfg.byte_burst, now),
+        })
+    }
+
+    /// Attempts to admit a packet of the specified size.
+    /// If the client has exceeded its allowance, the packet should be rejected and the returned
+    /// value (never exceeding [`MAX_RETRY_AFTER`]) indicates how long the client should back off for.
+    pub(crate) fn try_admit(&mut self, packet_size: usize) -> Result<(), Duration> {
+        self.try_admit_at(packet_size, Instant::now())
+    }
+
+    fn try_admit_at(&mut self, packet_size: usize, now: Instant) -> Result<(), Duration> {
+        self.packets.refill(now);
+        self.bytes.refill(now);
+
+        let packet_size = packet_size as f64;
+
+        // make sure to not consume any tokens unless both buckets have enough of them
+        let wait = match (
+            self.packets.time_until_available(1.),
+            self.bytes.time_until_available(packet_size),
+        ) {
+            (None, None) => None,
+            (Some(wait), None) | (None, Some(wait)) => Some(wait),
+            (Some(wait1), Some(wait2)) => Some(wait1.max(wait2)),
+        };
+
+        if let Some(wait) = wait {
+            return Err(wait.min(MAX_RETRY_AFTER));
+        }
+
+        self.packets.consume(1.);
+        self.bytes.consume(packet_size);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(cfg: ClientRateLimitingDebug, now: Instant) -> ClientRateLimiter {
+        ClientRateLimiter {
+            packets: TokenBucket::new(cfg.packets_per_second as u64, cfg.packet_burst as u64, now),
+            bytes: TokenBucket::new(cfg.bytes_per_second, cfg.byte_burst, now),
+        }
+    }
+
+    fn cfg(
+        packets_per_second: u32,
+        packet_burst: u32,
+        bytes_per_second: u64,
+        byte_burst: u64,
+    ) -> ClientRateLimitingDebug {
+        ClientRateLimitingDebug {
+            enabled: true,
+            packets_per_second,
+            packet_burst,
+            bytes_per_second,
+            byte_burst,
+        }
+    }
+
+    #[test]
+    fn disabled_limiter_is_not_created() {
+        let mut cfg = cfg(10, 10, 1000, 1000);
+        assert!(ClientRateLimiter::new(cfg).is_some());
+        cfg.enabled = false;
+        assert!(ClientRateLimiter::new(cfg).is_none());
+    }
+
+    #[test]
+    fn bucket_is_refilled_at_constant_rate_up_to_its_capacity() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(10, 20, now);
+        bucket.consume(20.);
+        assert_eq!(
+            bucket.time_until_available(5.),
+            Some(Duration::from_millis(500))
+        );
+
+        bucket.refill(now + Duration::from_millis(500));
+        assert!(bucket.time_until_available(5.).is_none());
+        assert!(bucket.time_until_available(6.).is_some());
+
+        // it never goes above the capacity
+        bucket.refill(now + Duration::from_secs(1000));
+        assert_eq!(bucket.available, 20.);
+    }
+
+    #[test]
+    fn bucket_can_always_hold_a_second_worth_of_tokens() {
+        let bucket = TokenBucket::new(100, 10, Instant::now());
+        assert_eq!(bucket.capacity, 100.);
+    }
+
+    #[test]
+    fn allows_bursts_up_to_the_limit() {
+        let now = Instant::now();
+        let mut limiter = limiter(cfg(10, 50, 1_000_000, 1_000_000), now);
+
+        for _ in 0..50 {
+            assert!(limiter.try_admit_at(100, now).is_ok());
+        }
+        assert_eq!(
+            limiter.try_admit_at(100, now),
+            Err(Duration::from_millis(100))
+        );
+
+        // after a second, another 10 packets are allowed
+        let later = now + Duration::from_secs(1);
+        for _ in 0..10 {
+            assert!(limiter.try_admit_at(100, later).is_ok());
+        }
+        assert!(limiter.try_admit_at(100, later).is_err());
+    }
+
+    #[test]
+    fn limits_number_of_bytes() {
+        let now = Instant::now();
+        let mut limiter = limiter(cfg(1000, 1000, 1000, 2000), now);
+
+        assert!(limiter.try_admit_at(1500, now).is_ok());
+        assert_eq!(
+            limiter.try_admit_at(1000, now),
+            Err(Duration::from_millis(500))
+        );
+
+        // rejected packets don't consume any tokens
+        assert!(limiter.try_admit_at(500, now).is_ok());
+        assert!(limiter.try_admit_at(1, now).is_err());
+
+        // packets bigger than the whole bucket are allowed once it's full
+        let later = now + Duration::from_secs(2);
+        assert!(limiter.try_admit_at(5000, later).is_ok());
+    }
+
+    #[test]
+    fn zero_rate_results_in_bounded_retry_duration() {
+        let now = Instant::now();
+        let mut limiter = limiter(cfg(0, 2, 1000, 1000), now);
+
+        assert!(limiter.try_admit_at(10, now).is_ok());
+        assert!(limiter.try_admit_at(10, now).is_ok());
+        assert_eq!(limiter.try_admit_at(10, now), Err(MAX_RETRY_AFTER));
+
+        // and the bucket never gets refilled
+        let later = now + Duration::from_secs(1000);
+        assert_eq!(limiter.try_admit_at(10, later), Err(MAX_RETRY_AFTER));
+
+        // nothing ever gets through an empty bucket
+        let mut limiter = limiter(cfg(0, 0, 1000, 1000), now);
+        assert_eq!(limiter.try_admit_at(10, now), Err(MAX_RETRY_AFTER));
+    }
+}
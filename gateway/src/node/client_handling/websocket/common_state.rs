@@ -1,6 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::config::ClientRateLimitingDebug;
 use crate::node::client_handling::websocket::connection_handler::ecash::EcashManager;
 use crate::node::client_handling::websocket::connection_handler::BandwidthFlushingBehaviourConfig;
 use nym_crypto::asymmetric::identity;
@@ -14,4 +15,5 @@ pub(crate) struct CommonHandlerState<S> {
     pub(crate) local_identity: Arc<identity::KeyPair>,
     pub(crate) only_coconut_credentials: bool,
     pub(crate) bandwidth_cfg: BandwidthFlushingBehaviourConfig,
+    pub(crate) rate_limiting_cfg: ClientRateLimitingDebug,
 }
@@ -3,6 +3,7 @@
 
 use crate::node::client_handling::{
     bandwidth::{Bandwidth, BandwidthError},
+    rate_limiting::ClientRateLimiter,
     websocket::{
         connection_handler::{
             ecash::error::EcashTicketError, ClientBandwidth, ClientDetails, FreshHandler,
@@ -104,6 +105,11 @@ pub enum RequestHandlingError {
         "the received payment contained more than a single ticket. that's currently not supported"
     )]
     MultipleTickets,
+
+    #[error(
+        "the client has exceeded its allowed sending rate. it should back off for {retry_after:?}"
+    )]
+    Throttled { retry_after: Duration },
 }
 
 impl RequestHandlingError {
@@ -118,6 +124,11 @@ impl RequestHandlingError {
                     available,
                 },
             },
+            RequestHandlingError::Throttled { retry_after } => ServerResponse::TypedError {
+                error: SimpleGatewayRequestsError::Throttled {
+                    retry_after_ms: u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX),
+                },
+            },
             other => ServerResponse::new_error(other.to_string()),
         };
         server_response.into()
@@ -154,6 +165,7 @@ pub(crate) struct AuthenticatedHandler<R, S, St> {
     inner: FreshHandler<R, S, St>,
     client: ClientDetails,
     client_bandwidth: ClientBandwidth,
+    rate_limiter: Option<ClientRateLimiter>,
     mix_receiver: MixMessageReceiver,
     // Occasionally the handler is requested to ping the connected client for confirm that it's
     // active, such as when a duplicate connection is detected. This hashmap stores the oneshot
@@ -205,10 +217,13 @@ where
                 client_address: client.address.as_base58_string(),
             })?;
 
+        let rate_limiter = ClientRateLimiter::new(fresh.shared_state.rate_limiting_cfg);
+
         Ok(AuthenticatedHandler {
             inner: fresh,
             client,
             client_bandwidth: ClientBandwidth::new(bandwidth.into()),
+            rate_limiter,
             mix_receiver,
             is_active_request_receiver,
             is_active_ping_pending_reply: None,
@@ -511,7 +526,7 @@ where
     }
 
     /// Tries to handle request to forward sphinx packet into the network. The request can only succeed
-    /// if the client has enough available bandwidth.
+    /// if the client has enough available bandwidth and has not exceeded its allowed sending rate.
     ///
     /// Upon forwarding, client's bandwidth is decreased by the size of the forwarded packet.
     ///
@@ -525,6 +540,13 @@ where
     ) -> Result<ServerResponse, RequestHandlingError> {
         let required_bandwidth = mix_packet.packet().len() as i64;
 
+        // throttled packets are dropped without touching the client's bandwidth
+        if let Some(rate_limiter) = &mut self.rate_limiter {
+            rate_limiter
+                .try_admit(mix_packet.packet().len())
+                .map_err(|retry_after| RequestHandlingError::Throttled { retry_after })?;
+        }
+
         let remaining_bandwidth = self.try_use_bandwidth(required_bandwidth).await?;
         self.forward_packet(mix_packet);
 
@@ -0,0 +1,104 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::InboxRetentionDebug;
+use crate::node::Storage;
+use nym_node_http_api::state::metrics::SharedInboxStats;
+use nym_task::TaskClient;
+use time::OffsetDateTime;
+use tokio::time::interval;
+use tracing::{debug, info, trace, warn};
+
+/// Task responsible for periodically removing messages stored for offline clients
+/// that are either too old or exceed the per-client inbox size limit.
+pub(crate) struct InboxPruner<St> {
+    config: InboxRetentionDebug,
+    storage: St,
+    stats: SharedInboxStats,
+}
+
+impl<St> InboxPruner<St>
+where
+    St: Storage + Send + Sync + 'static,
+{
+    pub(crate) fn new(config: InboxRetentionDebug, storage: St, stats: SharedInboxStats) -> Self {
+        InboxPruner {
+            config,
+            storage,
+            stats,
+        }
+    }
+
+    fn expiration_cutoff(&self, now: OffsetDateTime) -> OffsetDateTime {
+        // if somebody has set an absurdly large maximum age, just keep everything
+        time::Duration::try_from(self.config.max_message_age)
+            .ok()
+            .and_then(|max_age| now.checked_sub(max_age))
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+    }
+
+    async fn prune_inboxes(&self) {
+        let now = OffsetDateTime::now_utc();
+
+        let expired = match self
+            .storage
+            .remove_messages_older_than(self.expiration_cutoff(now))
+            .await
+        {
+            Ok(removed) => removed,
+            Err(err) => {
+                warn!("failed to remove expired client messages: {err}");
+                0
+            }
+        };
+
+        let max_client_bytes = i64::try_from(self.config.max_client_bytes).unwrap_or(i64::MAX);
+        let excessive = match self.storage.trim_inboxes(max_client_bytes).await {
+            Ok(removed) => removed,
+            Err(err) => {
+                warn!("failed to trim oversized client inboxes: {err}");
+                0
+            }
+        };
+
+        if expired != 0 || excessive != 0 {
+            info!("removed {expired} expired and {excessive} excessive stored client messages");
+        } else {
+            debug!("there were no stored client messages to remove");
+        }
+
+        let usage = self.storage.inbox_usage().await;
+
+        let mut stats = self.stats.write().await;
+        stats.last_pruning_time = now;
+        stats.expired_messages_removed_since_startup += expired;
+        stats.excessive_messages_removed_since_startup += excessive;
+        match usage {
+            Ok(usage) => {
+                stats.stored_messages = usage.stored_messages as u64;
+                stats.stored_bytes = usage.stored_bytes as u64;
+                stats.inboxes = usage.inboxes as u64;
+            }
+            Err(err) => warn!("failed to retrieve current inbox usage: {err}"),
+        }
+    }
+
+    async fn run(&self, mut shutdown: TaskClient) {
+        info!("Starting client inbox pruner");
+        let mut interval = interval(self.config.pruning_interval);
+
+        while !shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("InboxPruner: received shutdown");
+                },
+                _ = interval.tick() => self.prune_inboxes().await,
+            }
+        }
+    }
+
+    pub(crate) fn start(self, shutdown: TaskClient) {
+        tokio::spawn(async move { self.run(shutdown).await });
+    }
+}
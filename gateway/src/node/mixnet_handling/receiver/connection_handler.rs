@@ -3,12 +3,17 @@
 
 use crate::node::client_handling::active_clients::ActiveClientsStore;
 use crate::node::client_handling::websocket::message_receiver::MixMessageSender;
-use crate::node::mixnet_handling::receiver::packet_processing::PacketProcessor;
+use crate::node::mixnet_handling::receiver::packet_processing::{
+    GatewayProcessingError, PacketProcessor,
+};
 use futures::channel::mpsc::SendError;
 use futures::StreamExt;
 use nym_gateway_storage::{error::StorageError, Storage};
 use nym_mixnet_client::forwarder::MixForwardingSender;
+use nym_mixnode_common::packet_processor::error::MixProcessingError;
 use nym_mixnode_common::packet_processor::processor::ProcessedFinalHop;
+use nym_node_http_api::state::metrics::SharedMixingStats;
+use nym_noise::{upgrade_noise_responder, NoiseConfig};
 use nym_sphinx::forwarding::packet::MixPacket;
 use nym_sphinx::framing::codec::NymCodec;
 use nym_sphinx::framing::packet::FramedNymPacket;
@@ -39,6 +44,8 @@ pub(crate) struct ConnectionHandler<St: Storage> {
     active_clients_store: ActiveClientsStore,
     storage: St,
     ack_sender: MixForwardingSender,
+    noise_config: NoiseConfig,
+    mixing_stats: SharedMixingStats,
 }
 
 impl<St: Storage + Clone> Clone for ConnectionHandler<St> {
@@ -57,6 +64,8 @@ impl<St: Storage + Clone> Clone for ConnectionHandler<St> {
             active_clients_store: self.active_clients_store.clone(),
             storage: self.storage.clone(),
             ack_sender: self.ack_sender.clone(),
+            noise_config: self.noise_config.clone(),
+            mixing_stats: self.mixing_stats.clone(),
         }
     }
 }
@@ -67,6 +76,8 @@ impl<St: Storage> ConnectionHandler<St> {
         storage: St,
         ack_sender: MixForwardingSender,
         active_clients_store: ActiveClientsStore,
+        noise_config: NoiseConfig,
+        mixing_stats: SharedMixingStats,
     ) -> Self {
         ConnectionHandler {
             packet_processor,
@@ -74,6 +85,8 @@ impl<St: Storage> ConnectionHandler<St> {
             storage,
             active_clients_store,
             ack_sender,
+            noise_config,
+            mixing_stats,
         }
     }
 
@@ -178,14 +191,18 @@ impl<St: Storage> ConnectionHandler<St> {
         &mut self,
         framed_sphinx_packet: FramedNymPacket,
     ) -> Result<(), CriticalPacketProcessingError> {
-        //
-        // TODO: here be replay attack detection - it will require similar key cache to the one in
-        // packet processor for vpn packets,
-        // question: can it also be per connection vs global?
-        //
-
+        // note: replay detection happens as part of the packet processing and replayed packets
+        // are rejected alongside all other malformed ones
         let processed_final_hop = match self.packet_processor.process_received(framed_sphinx_packet)
         {
+            Err(GatewayProcessingError::PacketProcessingError(
+                MixProcessingError::ReplayedPacket,
+            )) => {
+                debug!("We received a replayed sphinx packet");
+                let mut stats = self.mixing_stats.write().await;
+                stats.packets_replayed_since_startup += 1;
+                return Ok(());
+            }
             Err(err) => {
                 debug!("We failed to process received sphinx packet - {err}");
                 return Ok(());
@@ -204,6 +221,15 @@ impl<St: Storage> ConnectionHandler<St> {
     ) {
         debug!("Starting connection handler for {:?}", remote);
         shutdown.mark_as_success();
+        let conn = match upgrade_noise_responder(conn, &self.noise_config).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!(
+                    "{remote} - failed to complete the noise handshake: {err}. Closing the socket"
+                );
+                return;
+            }
+        };
         let mut framed_conn = Framed::new(conn, NymCodec);
         while !shutdown.is_shutdown() {
             tokio::select! {
@@ -238,7 +264,7 @@ impl<St: Storage> ConnectionHandler<St> {
             }
         }
 
-        match framed_conn.into_inner().peer_addr() {
+        match framed_conn.into_inner().get_ref().peer_addr() {
             Ok(peer_addr) => {
                 debug!("closing connection from {peer_addr}")
             }
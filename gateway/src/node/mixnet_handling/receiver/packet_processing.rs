@@ -1,10 +1,11 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use nym_crypto::asymmetric::encryption;
 use nym_mixnode_common::packet_processor::error::MixProcessingError;
 pub use nym_mixnode_common::packet_processor::processor::MixProcessingResult;
 use nym_mixnode_common::packet_processor::processor::{ProcessedFinalHop, SphinxPacketProcessor};
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtection;
+use nym_mixnode_common::packet_processor::sphinx_keys::SphinxKeys;
 use nym_sphinx::framing::packet::FramedNymPacket;
 use thiserror::Error;
 
@@ -24,9 +25,13 @@ pub struct PacketProcessor {
 }
 
 impl PacketProcessor {
-    pub(crate) fn new(encryption_key: &encryption::PrivateKey) -> Self {
+    pub(crate) fn new(
+        sphinx_keys: SphinxKeys,
+        replay_protection: Option<ReplayProtection>,
+    ) -> Self {
         PacketProcessor {
-            inner_processor: SphinxPacketProcessor::new(encryption_key.into()),
+            inner_processor: SphinxPacketProcessor::new_with_rotating_keys(sphinx_keys)
+                .with_replay_filter(replay_protection),
         }
     }
 
@@ -14,12 +14,20 @@ use crate::node::client_handling::embedded_clients::{LocalEmbeddedClientHandle,
 use crate::node::client_handling::websocket;
 use crate::node::client_handling::websocket::connection_handler::ecash::EcashManager;
 use crate::node::helpers::{initialise_main_storage, load_network_requester_config};
+use crate::node::inbox_pruner::InboxPruner;
 use crate::node::mixnet_handling::receiver::connection_handler::ConnectionHandler;
 use futures::channel::{mpsc, oneshot};
 use nym_crypto::asymmetric::{encryption, identity};
 use nym_mixnet_client::forwarder::{MixForwardingSender, PacketForwarder};
+use nym_mixnode_common::noise_network::{
+    NoiseNetworkRefresher, DEFAULT_NOISE_NETWORK_REFRESH_INTERVAL,
+};
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtection;
+use nym_mixnode_common::packet_processor::sphinx_keys::SphinxKeys;
 use nym_network_defaults::NymNetworkDetails;
 use nym_network_requester::{LocalGateway, NRServiceProviderBuilder, RequestFilter};
+use nym_node_http_api::state::metrics::{SharedInboxStats, SharedMixingStats};
+use nym_noise::{NoiseConfig, NoiseNetworkView};
 use nym_task::{TaskClient, TaskHandle, TaskManager};
 use nym_types::gateway::GatewayNodeDetailsResponse;
 use nym_validator_client::nyxd::{Coin, CosmWasmClient};
@@ -33,6 +41,7 @@ use tracing::*;
 
 pub(crate) mod client_handling;
 pub(crate) mod helpers;
+pub(crate) mod inbox_pruner;
 pub(crate) mod mixnet_handling;
 
 use crate::node::client_handling::websocket::connection_handler::ecash::credential_sender::CredentialHandlerConfig;
@@ -143,8 +152,24 @@ pub struct Gateway<St = PersistentStorage> {
     /// x25519 keypair used for Diffie-Hellman. Currently only used for sphinx key derivation.
     sphinx_keypair: Arc<encryption::KeyPair>,
 
+    /// Set of sphinx keys accepted when processing received mix packets.
+    /// Unless explicitly overridden, it only consists of the `sphinx_keypair`.
+    sphinx_keys: SphinxKeys,
+
+    /// Filter used for detecting replayed mix packets.
+    /// Unless explicitly overridden, a new instance is created based on the config.
+    replay_protection: Option<ReplayProtection>,
+
+    /// x25519 keypair used for the noise protocol.
+    /// If not set, an ephemeral keypair is used, since it's not announced to the network anyway.
+    noise_keypair: Option<Arc<encryption::KeyPair>>,
+
     storage: St,
 
+    inbox_stats: SharedInboxStats,
+
+    mixing_stats: SharedMixingStats,
+
     #[cfg(all(feature = "wireguard", target_os = "linux"))]
     wireguard_data: Option<nym_wireguard::WireguardData>,
 
@@ -160,14 +185,20 @@ impl<St> Gateway<St> {
         ip_packet_router_opts: Option<LocalIpPacketRouterOpts>,
         storage: St,
     ) -> Result<Self, GatewayError> {
+        let sphinx_keypair = Arc::new(helpers::load_sphinx_keys(&config)?);
         Ok(Gateway {
             storage,
             identity_keypair: Arc::new(load_identity_keys(&config)?),
-            sphinx_keypair: Arc::new(helpers::load_sphinx_keys(&config)?),
+            sphinx_keys: SphinxKeys::new(sphinx_keypair.private_key().into()),
+            replay_protection: None,
+            noise_keypair: None,
+            sphinx_keypair,
             config,
             network_requester_opts,
             ip_packet_router_opts,
             authenticator_opts: None,
+            inbox_stats: SharedInboxStats::new(),
+            mixing_stats: SharedMixingStats::new(),
             #[cfg(all(feature = "wireguard", target_os = "linux"))]
             wireguard_data: None,
             run_http_server: true,
@@ -190,8 +221,13 @@ impl<St> Gateway<St> {
             ip_packet_router_opts,
             authenticator_opts,
             identity_keypair,
+            sphinx_keys: SphinxKeys::new(sphinx_keypair.private_key().into()),
+            replay_protection: None,
+            noise_keypair: None,
             sphinx_keypair,
             storage,
+            inbox_stats: SharedInboxStats::new(),
+            mixing_stats: SharedMixingStats::new(),
             #[cfg(all(feature = "wireguard", target_os = "linux"))]
             wireguard_data: None,
             run_http_server: true,
@@ -207,6 +243,26 @@ impl<St> Gateway<St> {
         self.task_client = Some(task_client)
     }
 
+    pub fn set_inbox_stats(&mut self, inbox_stats: SharedInboxStats) {
+        self.inbox_stats = inbox_stats
+    }
+
+    pub fn set_mixing_stats(&mut self, mixing_stats: SharedMixingStats) {
+        self.mixing_stats = mixing_stats
+    }
+
+    pub fn set_sphinx_keys(&mut self, sphinx_keys: SphinxKeys) {
+        self.sphinx_keys = sphinx_keys
+    }
+
+    pub fn set_replay_protection(&mut self, replay_protection: ReplayProtection) {
+        self.replay_protection = Some(replay_protection)
+    }
+
+    pub fn set_noise_keys(&mut self, noise_keypair: Arc<encryption::KeyPair>) {
+        self.noise_keypair = Some(noise_keypair)
+    }
+
     #[cfg(all(feature = "wireguard", target_os = "linux"))]
     pub fn set_wireguard_data(&mut self, wireguard_data: nym_wireguard::WireguardData) {
         self.wireguard_data = Some(wireguard_data)
@@ -217,24 +273,66 @@ impl<St> Gateway<St> {
         crate::helpers::node_details(&self.config).await
     }
 
+    async fn start_noise_network_refresher(
+        &self,
+        shutdown: TaskClient,
+    ) -> Result<NoiseConfig, GatewayError> {
+        let noise_keypair = self
+            .noise_keypair
+            .clone()
+            .unwrap_or_else(|| Arc::new(encryption::KeyPair::new(&mut thread_rng())));
+        let network_view = NoiseNetworkView::new_empty();
+        let noise_config = NoiseConfig::new(noise_keypair, network_view.clone())
+            .with_unsafe_disabled(self.config.debug.unsafe_disable_noise);
+
+        if self.config.debug.unsafe_disable_noise {
+            warn!("noise is disabled - all mix connections are going to use plaintext");
+            return Ok(noise_config);
+        }
+
+        let nym_api_urls = self.config.get_nym_api_endpoints();
+        if nym_api_urls.is_empty() {
+            return Err(GatewayError::NoNymApisAvailable);
+        }
+
+        info!("Starting noise network refresher...");
+        let mut refresher = NoiseNetworkRefresher::new(
+            nym_api_urls,
+            DEFAULT_NOISE_NETWORK_REFRESH_INTERVAL,
+            network_view,
+            shutdown,
+        );
+        refresher.refresh_network_view().await;
+        refresher.start();
+
+        Ok(noise_config)
+    }
+
     fn start_mix_socket_listener(
         &self,
         ack_sender: MixForwardingSender,
         active_clients_store: ActiveClientsStore,
+        noise_config: NoiseConfig,
         shutdown: TaskClient,
     ) where
         St: Storage + Clone + 'static,
     {
         info!("Starting mix socket listener...");
 
+        let replay_protection = self
+            .replay_protection
+            .clone()
+            .or_else(|| ReplayProtection::new_if_enabled(self.config.debug.replay_protection));
         let packet_processor =
-            mixnet_handling::PacketProcessor::new(self.sphinx_keypair.private_key());
+            mixnet_handling::PacketProcessor::new(self.sphinx_keys.clone(), replay_protection);
 
         let connection_handler = ConnectionHandler::new(
             packet_processor,
             self.storage.clone(),
             ack_sender,
             active_clients_store,
+            noise_config,
+            self.mixing_stats.clone(),
         );
 
         let listening_address = SocketAddr::new(
@@ -352,6 +450,7 @@ impl<St> Gateway<St> {
             local_identity: Arc::clone(&self.identity_keypair),
             only_coconut_credentials: self.config.gateway.only_coconut_credentials,
             bandwidth_cfg: (&self.config).into(),
+            rate_limiting_cfg: self.config.debug.client_rate_limiting,
         };
 
         websocket::Listener::new(listening_address, shared_state).start(
@@ -361,7 +460,11 @@ impl<St> Gateway<St> {
         );
     }
 
-    fn start_packet_forwarder(&self, shutdown: TaskClient) -> MixForwardingSender {
+    fn start_packet_forwarder(
+        &self,
+        noise_config: NoiseConfig,
+        shutdown: TaskClient,
+    ) -> MixForwardingSender {
         info!("Starting mix packet forwarder...");
 
         let (mut packet_forwarder, packet_sender) = PacketForwarder::new(
@@ -370,6 +473,7 @@ impl<St> Gateway<St> {
             self.config.debug.initial_connection_timeout,
             self.config.debug.maximum_connection_buffer_size,
             self.config.debug.use_legacy_framed_packet_version,
+            noise_config,
             shutdown,
         );
 
@@ -611,12 +715,25 @@ impl<St> Gateway<St> {
             .await
         }?;
 
-        let mix_forwarding_channel = self.start_packet_forwarder(shutdown.fork("PacketForwarder"));
+        InboxPruner::new(
+            self.config.debug.inbox_retention,
+            self.storage.clone(),
+            self.inbox_stats.clone(),
+        )
+        .start(shutdown.fork("InboxPruner"));
+
+        let noise_config = self
+            .start_noise_network_refresher(shutdown.fork("NoiseNetworkRefresher"))
+            .await?;
+
+        let mix_forwarding_channel =
+            self.start_packet_forwarder(noise_config.clone(), shutdown.fork("PacketForwarder"));
 
         let active_clients_store = ActiveClientsStore::new();
         self.start_mix_socket_listener(
             mix_forwarding_channel.clone(),
             active_clients_store.clone(),
+            noise_config,
             shutdown.fork("mixnet_handling::Listener"),
         );
 
@@ -673,6 +790,8 @@ impl<St> Gateway<St> {
             .with_maybe_network_requester(self.network_requester_opts.as_ref().map(|o| &o.config))
             .with_maybe_network_request_filter(nr_request_filter)
             .with_maybe_ip_packet_router(self.ip_packet_router_opts.as_ref().map(|o| &o.config))
+            .with_inbox_stats(self.inbox_stats.clone())
+            .with_mixing_stats(self.mixing_stats.clone())
             .start(shutdown.fork("http-api"))?;
         }
 
@@ -14,6 +14,7 @@ use nym_config::{
     serde_helpers::de_maybe_stringified, NymConfigTemplate, DEFAULT_CONFIG_DIR,
     DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
 use serde::{Deserialize, Serialize};
 use std::io;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -389,6 +390,14 @@ pub struct Debug {
     // existing nodes whilst everyone else is upgrading and getting the code for handling the new field.
     // It shall be disabled in the subsequent releases.
     pub use_legacy_framed_packet_version: bool,
+
+    /// Settings of the filter used for detecting replayed sphinx packets.
+    #[serde(default)]
+    pub replay_protection: ReplayProtectionConfig,
+
+    /// Specifies whether this node should **NOT** use noise protocol for its mix connections.
+    #[serde(default)]
+    pub unsafe_disable_noise: bool,
 }
 
 impl Default for Debug {
@@ -401,6 +410,8 @@ impl Default for Debug {
             initial_connection_timeout: DEFAULT_INITIAL_CONNECTION_TIMEOUT,
             maximum_connection_buffer_size: DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE,
             use_legacy_framed_packet_version: false,
+            replay_protection: Default::default(),
+            unsafe_disable_noise: false,
         }
     }
 }
@@ -10,6 +10,7 @@ use futures::StreamExt;
 use log::debug;
 use log::{error, info, warn};
 use nym_metrics::nanos;
+use nym_noise::{upgrade_noise_responder, NoiseConfig};
 use nym_sphinx::forwarding::packet::MixPacket;
 use nym_sphinx::framing::codec::NymCodec;
 use nym_sphinx::framing::packet::FramedNymPacket;
@@ -25,16 +26,19 @@ pub(crate) mod packet_processing;
 pub(crate) struct ConnectionHandler {
     packet_processor: PacketProcessor,
     delay_forwarding_channel: PacketDelayForwardSender,
+    noise_config: NoiseConfig,
 }
 
 impl ConnectionHandler {
     pub(crate) fn new(
         packet_processor: PacketProcessor,
         delay_forwarding_channel: PacketDelayForwardSender,
+        noise_config: NoiseConfig,
     ) -> Self {
         ConnectionHandler {
             packet_processor,
             delay_forwarding_channel,
+            noise_config,
         }
     }
 
@@ -51,11 +55,8 @@ impl ConnectionHandler {
     }
 
     fn handle_received_packet(&self, framed_sphinx_packet: FramedNymPacket) {
-        //
-        // TODO: here be replay attack detection - it will require similar key cache to the one in
-        // packet processor for vpn packets,
-        // question: can it also be per connection vs global?
-        //
+        // note: replay detection happens as part of the packet processing and replayed packets
+        // are rejected alongside all other malformed ones
 
         // all processing such, key caching, etc. was done.
         // however, if it was a forward hop, we still need to delay it
@@ -82,6 +83,13 @@ impl ConnectionHandler {
     ) {
         debug!("Starting connection handler for {:?}", remote);
         shutdown.mark_as_success();
+        let conn = match upgrade_noise_responder(conn, &self.noise_config).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!("{remote:?} - failed to complete the noise handshake: {err}. Closing the socket");
+                return;
+            }
+        };
         let mut framed_conn = Framed::new(conn, NymCodec);
         while !shutdown.is_shutdown() {
             tokio::select! {
@@ -116,7 +124,7 @@ impl ConnectionHandler {
 
         info!(
             "Closing connection from {:?}",
-            framed_conn.into_inner().peer_addr()
+            framed_conn.into_inner().get_ref().peer_addr()
         );
         log::trace!("ConnectionHandler: Exiting");
     }
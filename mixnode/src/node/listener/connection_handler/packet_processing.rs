@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::node::node_statistics;
-use nym_crypto::asymmetric::encryption;
 use nym_mixnode_common::packet_processor::error::MixProcessingError;
 pub use nym_mixnode_common::packet_processor::processor::MixProcessingResult;
 use nym_mixnode_common::packet_processor::processor::SphinxPacketProcessor;
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtection;
+use nym_mixnode_common::packet_processor::sphinx_keys::SphinxKeys;
 use nym_sphinx::framing::packet::FramedNymPacket;
 
 // PacketProcessor contains all data required to correctly unwrap and forward sphinx packets
@@ -20,11 +21,13 @@ pub struct PacketProcessor {
 
 impl PacketProcessor {
     pub(crate) fn new(
-        encryption_key: &encryption::PrivateKey,
+        sphinx_keys: SphinxKeys,
+        replay_protection: Option<ReplayProtection>,
         node_stats_update_sender: node_statistics::UpdateSender,
     ) -> Self {
         PacketProcessor {
-            inner_processor: SphinxPacketProcessor::new(encryption_key.into()),
+            inner_processor: SphinxPacketProcessor::new_with_rotating_keys(sphinx_keys)
+                .with_replay_filter(replay_protection),
             node_stats_update_sender,
         }
     }
@@ -34,6 +37,10 @@ impl PacketProcessor {
         received: FramedNymPacket,
     ) -> Result<MixProcessingResult, MixProcessingError> {
         self.node_stats_update_sender.report_received();
-        self.inner_processor.process_received(received)
+        let res = self.inner_processor.process_received(received);
+        if let Err(MixProcessingError::ReplayedPacket) = res {
+            self.node_stats_update_sender.report_replayed();
+        }
+        res
     }
 }
@@ -13,9 +13,15 @@ use crate::node::packet_delayforwarder::{DelayForwarder, PacketDelayForwardSende
 use log::{error, info, warn};
 use nym_bin_common::output_format::OutputFormat;
 use nym_crypto::asymmetric::{encryption, identity};
+use nym_mixnode_common::noise_network::{
+    NoiseNetworkRefresher, DEFAULT_NOISE_NETWORK_REFRESH_INTERVAL,
+};
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtection;
+use nym_mixnode_common::packet_processor::sphinx_keys::SphinxKeys;
 use nym_mixnode_common::verloc;
 use nym_mixnode_common::verloc::VerlocMeasurer;
 use nym_node_http_api::state::metrics::{SharedMixingStats, SharedVerlocStats};
+use nym_noise::{NoiseConfig, NoiseNetworkView};
 use nym_task::{TaskClient, TaskHandle};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -41,6 +47,12 @@ pub struct MixNode {
     task_client: Option<TaskClient>,
     mixing_stats: Option<SharedMixingStats>,
     verloc_stats: Option<SharedVerlocStats>,
+    sphinx_keys: Option<SphinxKeys>,
+    replay_protection: Option<ReplayProtection>,
+
+    /// x25519 keypair used for the noise protocol.
+    /// If not set, an ephemeral keypair is used, since it's not announced to the network anyway.
+    noise_keypair: Option<Arc<encryption::KeyPair>>,
 }
 
 impl MixNode {
@@ -54,6 +66,9 @@ impl MixNode {
             task_client: None,
             mixing_stats: None,
             verloc_stats: None,
+            sphinx_keys: None,
+            replay_protection: None,
+            noise_keypair: None,
         })
     }
 
@@ -72,6 +87,9 @@ impl MixNode {
             sphinx_keypair,
             mixing_stats: None,
             verloc_stats: None,
+            sphinx_keys: None,
+            replay_protection: None,
+            noise_keypair: None,
         }
     }
 
@@ -91,6 +109,23 @@ impl MixNode {
         self.verloc_stats = Some(verloc_stats)
     }
 
+    /// Overrides the set of sphinx keys used for processing received packets.
+    /// If not set, only the sphinx keypair of this node is going to be used.
+    pub fn set_sphinx_keys(&mut self, sphinx_keys: SphinxKeys) {
+        self.sphinx_keys = Some(sphinx_keys)
+    }
+
+    /// Sets the announced keypair used for establishing noise connections.
+    pub fn set_noise_keys(&mut self, noise_keypair: Arc<encryption::KeyPair>) {
+        self.noise_keypair = Some(noise_keypair)
+    }
+
+    /// Overrides the filter used for detecting replayed packets, so that it could be rotated
+    /// alongside the sphinx keys. If not set, a new filter is created based on the config.
+    pub fn set_replay_protection(&mut self, replay_protection: ReplayProtection) {
+        self.replay_protection = Some(replay_protection)
+    }
+
     fn load_node_description(config: &Config) -> NodeDescription {
         NodeDescription::load_from_file(&config.storage_paths.node_description).unwrap_or_default()
     }
@@ -143,18 +178,56 @@ impl MixNode {
         (mixing_stats, update_sender)
     }
 
+    async fn start_noise_network_refresher(&self, shutdown: TaskClient) -> NoiseConfig {
+        let noise_keypair = self
+            .noise_keypair
+            .clone()
+            .unwrap_or_else(|| Arc::new(encryption::KeyPair::new(&mut thread_rng())));
+        let network_view = NoiseNetworkView::new_empty();
+        let noise_config = NoiseConfig::new(noise_keypair, network_view.clone())
+            .with_unsafe_disabled(self.config.debug.unsafe_disable_noise);
+
+        if self.config.debug.unsafe_disable_noise {
+            warn!("noise is disabled - all mix connections are going to use plaintext");
+            return noise_config;
+        }
+
+        info!("Starting noise network refresher...");
+        let mut refresher = NoiseNetworkRefresher::new(
+            self.config.get_nym_api_endpoints(),
+            DEFAULT_NOISE_NETWORK_REFRESH_INTERVAL,
+            network_view,
+            shutdown,
+        );
+        refresher.refresh_network_view().await;
+        refresher.start();
+
+        noise_config
+    }
+
     fn start_socket_listener(
         &self,
         node_stats_update_sender: node_statistics::UpdateSender,
         delay_forwarding_channel: PacketDelayForwardSender,
+        noise_config: NoiseConfig,
         shutdown: TaskClient,
     ) {
         info!("Starting socket listener...");
 
+        let sphinx_keys = self
+            .sphinx_keys
+            .clone()
+            .unwrap_or_else(|| SphinxKeys::new(self.sphinx_keypair.private_key().into()));
+
+        let replay_protection = self.replay_protection.clone().or_else(|| {
+            ReplayProtection::new_if_enabled(self.config.debug.replay_protection)
+        });
+
         let packet_processor =
-            PacketProcessor::new(self.sphinx_keypair.private_key(), node_stats_update_sender);
+            PacketProcessor::new(sphinx_keys, replay_protection, node_stats_update_sender);
 
-        let connection_handler = ConnectionHandler::new(packet_processor, delay_forwarding_channel);
+        let connection_handler =
+            ConnectionHandler::new(packet_processor, delay_forwarding_channel, noise_config);
 
         let listening_address = SocketAddr::new(
             self.config.mixnode.listening_address,
@@ -167,6 +240,7 @@ impl MixNode {
     fn start_packet_delay_forwarder(
         &mut self,
         node_stats_update_sender: node_statistics::UpdateSender,
+        noise_config: NoiseConfig,
         shutdown: TaskClient,
     ) -> PacketDelayForwardSender {
         info!("Starting packet delay-forwarder...");
@@ -177,7 +251,8 @@ impl MixNode {
             self.config.debug.initial_connection_timeout,
             self.config.debug.maximum_connection_buffer_size,
             self.config.debug.use_legacy_framed_packet_version,
-        );
+        )
+        .with_noise_config(noise_config);
 
         let mut packet_forwarder = DelayForwarder::new(
             nym_mixnet_client::Client::new(client_config),
@@ -273,13 +348,18 @@ impl MixNode {
 
         let (node_stats_pointer, node_stats_update_sender) =
             self.start_node_stats_controller(shutdown.fork("node_statistics::Controller"));
+        let noise_config = self
+            .start_noise_network_refresher(shutdown.fork("NoiseNetworkRefresher"))
+            .await;
         let delay_forwarding_channel = self.start_packet_delay_forwarder(
             node_stats_update_sender.clone(),
+            noise_config.clone(),
             shutdown.fork("DelayForwarder"),
         );
         self.start_socket_listener(
             node_stats_update_sender,
             delay_forwarding_channel,
+            noise_config,
             shutdown.fork("Listener"),
         );
         let atomic_verloc_results = self.start_verloc_measurements(shutdown.fork("VerlocMeasurer"));
@@ -355,6 +355,20 @@ pub struct EcashBatchTicketRedemptionResponse {
     pub proposal_accepted: bool,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, PartialEq)]
+pub struct DepositRefundRequestBody {
+    /// Id of the deposit whose funds are meant to be returned.
+    pub deposit_id: u32,
+
+    /// Id of the multisig proposal created by the ecash contract upon receiving the refund request.
+    pub proposal_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EcashDepositRefundResponse {
+    pub proposal_accepted: bool,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SpentCredentialsResponse {
@@ -12,6 +12,7 @@ use nym_mixnet_contract_common::rewarding::RewardEstimate;
 use nym_mixnet_contract_common::{
     GatewayBond, IdentityKey, Interval, MixId, MixNode, MixNodeBond, Percent, RewardedSetNodeStatus,
 };
+pub use nym_node_requests::api::v1::node::models::SphinxKeyRotation;
 use nym_node_requests::api::v1::node::models::{AuxiliaryDetails, BinaryBuildInformationOwned};
 use schemars::gen::SchemaGenerator;
 use schemars::schema::{InstanceType, Schema, SchemaObject};
@@ -391,6 +392,13 @@ impl From<nym_node_requests::api::v1::node::models::HostInformation> for HostInf
 pub struct HostKeys {
     pub ed25519: String,
     pub x25519: String,
+
+    #[serde(default)]
+    pub x25519_sphinx_rotation: Option<SphinxKeyRotation>,
+
+    /// Key used for the noise protocol. It's empty if the node does not support noise.
+    #[serde(default)]
+    pub x25519_noise: String,
 }
 
 impl From<nym_node_requests::api::v1::node::models::HostKeys> for HostKeys {
@@ -398,6 +406,8 @@ impl From<nym_node_requests::api::v1::node::models::HostKeys> for HostKeys {
         HostKeys {
             ed25519: value.ed25519_identity,
             x25519: value.x25519_sphinx,
+            x25519_sphinx_rotation: value.x25519_sphinx_rotation,
+            x25519_noise: value.x25519_noise,
         }
     }
 }
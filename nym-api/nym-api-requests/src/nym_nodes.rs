@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::models::{
-    GatewayBondAnnotated, MixNodeBondAnnotated, NymNodeDescription, OffsetDateTimeJsonSchemaWrapper,
+    GatewayBondAnnotated, MixNodeBondAnnotated, NymNodeDescription,
+    OffsetDateTimeJsonSchemaWrapper, SphinxKeyRotation,
 };
+use cosmwasm_std::Uint128;
 use nym_mixnet_contract_common::reward_params::Performance;
-use nym_mixnet_contract_common::MixId;
+use nym_mixnet_contract_common::{truncate_decimal, MixId};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 
@@ -75,9 +77,34 @@ pub struct SkimmedNode {
 
     /// Average node performance in last 24h period
     pub performance: Performance,
+
+    /// Total stake (in the base denomination) of this node, i.e. its pledge and all of its delegations
+    #[serde(default)]
+    pub total_stake: Option<Uint128>,
+
+    /// Rotating sphinx keys of this node, if it has enabled the key rotation.
+    #[serde(default)]
+    pub x25519_sphinx_rotation: Option<SphinxKeyRotation>,
+
+    /// Key used for establishing noise connections with this node, if it supports them.
+    #[serde(default)]
+    pub x25519_noise_pubkey: Option<String>,
 }
 
 impl SkimmedNode {
+    pub fn from_described_mixnode(
+        annotated: &MixNodeBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        let mut base: SkimmedNode = annotated.into();
+        if let Some(description) = description {
+            base.x25519_sphinx_rotation
+                .clone_from(&description.host_information.keys.x25519_sphinx_rotation);
+            base.x25519_noise_pubkey = announced_noise_key(description);
+        }
+        base
+    }
+
     pub fn from_described_gateway(
         annotated: &GatewayBondAnnotated,
         description: Option<&NymNodeDescription>,
@@ -95,6 +122,10 @@ impl SkimmedNode {
         entry.ws_port = description.mixnet_websockets.ws_port;
         entry.wss_port = description.mixnet_websockets.wss_port;
 
+        base.x25519_sphinx_rotation
+            .clone_from(&description.host_information.keys.x25519_sphinx_rotation);
+        base.x25519_noise_pubkey = announced_noise_key(description);
+
         // always prefer self-described data
         if !description.host_information.ip_address.is_empty() {
             base.ip_addresses
@@ -105,6 +136,12 @@ impl SkimmedNode {
     }
 }
 
+/// Nodes that don't support noise announce an empty key.
+fn announced_noise_key(description: &NymNodeDescription) -> Option<String> {
+    let key = &description.host_information.keys.x25519_noise;
+    (!key.is_empty()).then(|| key.clone())
+}
+
 impl<'a> From<&'a MixNodeBondAnnotated> for SkimmedNode {
     fn from(value: &'a MixNodeBondAnnotated) -> Self {
         SkimmedNode {
@@ -118,6 +155,9 @@ impl<'a> From<&'a MixNodeBondAnnotated> for SkimmedNode {
             },
             entry: None,
             performance: value.node_performance.last_24h,
+            total_stake: Some(truncate_decimal(value.mixnode_details.total_stake())),
+            x25519_sphinx_rotation: None,
+            x25519_noise_pubkey: None,
         }
     }
 }
@@ -137,6 +177,9 @@ impl<'a> From<&'a GatewayBondAnnotated> for SkimmedNode {
                 wss_port: None,
             }),
             performance: value.node_performance.last_24h,
+            total_stake: Some(value.gateway_bond.pledge_amount.amount),
+            x25519_sphinx_rotation: None,
+            x25519_noise_pubkey: None,
         }
     }
 }
@@ -5,4 +5,5 @@ pub(crate) mod aggregation;
 mod helpers;
 pub(crate) mod issued;
 pub(crate) mod partial_signing;
+pub(crate) mod refunds;
 pub(crate) mod spending;
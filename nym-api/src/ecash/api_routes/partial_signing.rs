@@ -37,8 +37,11 @@ pub async fn post_blind_sign(
     // see if we're not in the middle of new dkg
     state.ensure_dkg_not_in_progress().await?;
 
-    // check if we already issued a credential for this deposit
+    // make sure we won't concurrently vote for the refund of this deposit (or issue another credential for it)
     let deposit_id = blind_sign_request_body.deposit_id;
+    let _deposit_guard = state.lock_deposit(deposit_id).await;
+
+    // check if we already issued a credential for this deposit
     debug!(
         "checking if we have already issued credential for this deposit (deposit_id: {deposit_id})",
     );
@@ -46,6 +49,9 @@ pub async fn post_blind_sign(
         return Ok(Json(BlindedSignatureResponse { blinded_signature }));
     }
 
+    // make sure the deposit isn't in the process of being refunded
+    state.ensure_no_pending_refund(deposit_id).await?;
+
     //check if account was blacklisted
     let pub_key_bs58 = blind_sign_request_body.ecash_pubkey.to_base58_string();
     state.aux.ensure_not_blacklisted(&pub_key_bs58).await?;
@@ -0,0 +1,34 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::ecash::state::EcashState;
+use nym_api_requests::ecash::models::{DepositRefundRequestBody, EcashDepositRefundResponse};
+use rocket::serde::json::Json;
+use rocket::State as RocketState;
+use rocket_okapi::openapi;
+
+#[openapi(tag = "Ecash")]
+#[post("/deposit-refund", data = "<refund_request_body>")]
+pub async fn deposit_refund(
+    refund_request_body: Json<DepositRefundRequestBody>,
+    state: &RocketState<EcashState>,
+) -> crate::ecash::error::Result<Json<EcashDepositRefundResponse>> {
+    let deposit_id = refund_request_body.deposit_id;
+    let proposal_id = refund_request_body.proposal_id;
+
+    // 1. verify the associated on-chain proposal (whether it's made by correct sender, has valid messages, etc.)
+    state.validate_refund_proposal(&refund_request_body).await?;
+
+    // 2. make sure the contract has actually recorded the refund request associated with this proposal
+    state.ensure_pending_refund(deposit_id, proposal_id).await?;
+
+    // 3. make sure we have never issued a partial ticketbook for this deposit as its tickets might have been spent
+    // (and that we won't issue one until we have voted)
+    let _deposit_guard = state.lock_deposit(deposit_id).await;
+    state.ensure_refundable_deposit(deposit_id).await?;
+
+    state.accept_proposal(proposal_id).await?;
+    Ok(Json(EcashDepositRefundResponse {
+        proposal_accepted: true,
+    }))
+}
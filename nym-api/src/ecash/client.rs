@@ -19,7 +19,7 @@ use nym_coconut_dkg_common::verification_key::{ContractVKShare, VerificationKeyS
 use nym_contracts_common::IdentityKey;
 use nym_dkg::Threshold;
 use nym_ecash_contract_common::blacklist::BlacklistedAccountResponse;
-use nym_ecash_contract_common::deposit::{DepositId, DepositResponse};
+use nym_ecash_contract_common::deposit::{DepositId, DepositRefundResponse, DepositResponse};
 use nym_validator_client::nyxd::cosmwasm_client::types::ExecuteResult;
 use nym_validator_client::nyxd::{AccountId, Fee};
 use nym_validator_client::EcashApiClient;
@@ -32,6 +32,8 @@ pub trait Client {
 
     async fn get_deposit(&self, deposit_id: DepositId) -> Result<DepositResponse>;
 
+    async fn get_deposit_refund(&self, deposit_id: DepositId) -> Result<DepositRefundResponse>;
+
     async fn get_proposal(&self, proposal_id: u64) -> Result<ProposalResponse>;
 
     async fn list_proposals(&self) -> Result<Vec<ProposalResponse>>;
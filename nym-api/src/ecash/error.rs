@@ -11,7 +11,9 @@ use nym_crypto::asymmetric::{
 use nym_dkg::error::DkgError;
 use nym_dkg::Threshold;
 use nym_ecash_contract_common::deposit::DepositId;
-use nym_ecash_contract_common::redeem_credential::BATCH_REDEMPTION_PROPOSAL_TITLE;
+use nym_ecash_contract_common::redeem_credential::{
+    BATCH_REDEMPTION_PROPOSAL_TITLE, DEPOSIT_REFUND_PROPOSAL_TITLE,
+};
 use nym_validator_client::coconut::EcashApiError;
 use nym_validator_client::nyxd::error::NyxdError;
 use nym_validator_client::nyxd::AccountId;
@@ -26,7 +28,7 @@ use std::io::Cursor;
 use std::num::ParseIntError;
 use thiserror::Error;
 use time::error::ComponentRange;
-use time::OffsetDateTime;
+use time::{Date, OffsetDateTime};
 
 pub type Result<T, E = EcashError> = std::result::Result<T, E>;
 
@@ -195,6 +197,21 @@ pub enum EcashError {
         source: RedemptionError,
     },
 
+    #[error("the provided deposit refund proposal is invalid: {source}")]
+    DepositRefundProposalFailure {
+        #[from]
+        source: DepositRefundError,
+    },
+
+    #[error("refund of deposit {deposit_id} has been requested. no further ticketbooks will be issued for it")]
+    PendingDepositRefund { deposit_id: DepositId },
+
+    #[error("we have already issued a partial ticketbook for deposit {deposit_id} (valid until {expiration_date}) so some of its tickets might have been spent")]
+    IssuedTicketbook {
+        deposit_id: DepositId,
+        expiration_date: Date,
+    },
+
     #[error("this gateway hasn't submitted any tickets for verification")]
     NotTicketsProvided,
 
@@ -300,3 +317,65 @@ pub enum RedemptionError {
         received: u16,
     },
 }
+
+#[derive(Debug, Error)]
+pub enum DepositRefundError {
+    #[error("failed to retrieve proposal {proposal_id} from the chain")]
+    ProposalRetrievalFailure { proposal_id: u64 },
+
+    #[error(
+        "the proposal {proposal_id} has invalid title. got {received} but expected {}",
+        DEPOSIT_REFUND_PROPOSAL_TITLE
+    )]
+    InvalidProposalTitle { proposal_id: u64, received: String },
+
+    #[error("the proposal {proposal_id} has invalid description. got {received} but expected {expected}")]
+    InvalidProposalDescription {
+        proposal_id: u64,
+        received: String,
+        expected: String,
+    },
+
+    #[error("the proposal {proposal_id} is still pending")]
+    StillPending { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} has already been executed")]
+    AlreadyExecuted { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} has already been rejected")]
+    AlreadyRejected { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} has already been passed")]
+    AlreadyPassed { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} was proposed by an unexpected address {received}. expected the ecash contract at {expected}")]
+    InvalidProposer {
+        proposal_id: u64,
+        received: String,
+        expected: AccountId,
+    },
+
+    #[error(
+        "the proposal {proposal_id} did not contain exactly a single contract execution message"
+    )]
+    TooManyMessages { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} did not contain the correct refund execution message")]
+    InvalidMessage { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} has not been made against the expected e-cash contract")]
+    InvalidContract { proposal_id: u64 },
+
+    #[error("the proposal {proposal_id} proposes refund of deposit {proposed}, but the request has been sent for {received} instead")]
+    InvalidRefundedDeposit {
+        proposal_id: u64,
+        proposed: DepositId,
+        received: DepositId,
+    },
+
+    #[error("the ecash contract does not have any pending refund of deposit {deposit_id} associated with proposal {proposal_id}")]
+    UnknownRefundRequest {
+        proposal_id: u64,
+        deposit_id: DepositId,
+    },
+}
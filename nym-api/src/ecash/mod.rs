@@ -32,6 +32,7 @@ pub(crate) fn routes_open_api(settings: &OpenApiSettings, enabled: bool) -> (Vec
             api_routes::spending::verify_ticket,
             api_routes::spending::batch_redeem_tickets,
             api_routes::spending::double_spending_filter_v1,
+            api_routes::refunds::deposit_refund,
             api_routes::issued::epoch_credentials,
             api_routes::issued::issued_credential,
             api_routes::issued::issued_credentials,
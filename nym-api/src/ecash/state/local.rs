@@ -8,11 +8,13 @@ use crate::ecash::helpers::{
 use crate::ecash::keys::KeyPair;
 use nym_config::defaults::BloomfilterParameters;
 use nym_crypto::asymmetric::identity;
+use nym_ecash_contract_common::deposit::DepositId;
 use nym_ecash_double_spending::DoubleSpendingFilter;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use time::{Date, OffsetDateTime};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard, RwLock};
 
 pub(crate) struct TicketDoubleSpendingFilter {
     built_on: Date,
@@ -92,6 +94,51 @@ pub(crate) struct ExportedDoubleSpendingFilter {
     pub(crate) data: Arc<RwLock<ExportedDoubleSpendingFilterData>>,
 }
 
+/// Locks making sure we never concurrently issue a partial ticketbook for a deposit
+/// and vote for the refund of that same deposit.
+#[derive(Default)]
+pub(crate) struct DepositLocks {
+    inner: Mutex<HashMap<DepositId, Arc<AsyncMutex<()>>>>,
+}
+
+impl DepositLocks {
+    pub(crate) async fn lock(&self, deposit_id: DepositId) -> DepositGuard<'_> {
+        let lock = self
+            .inner
+            .lock()
+            .unwrap()
+            .entry(deposit_id)
+            .or_default()
+            .clone();
+
+        DepositGuard {
+            locks: self,
+            deposit_id,
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+}
+
+pub(crate) struct DepositGuard<'a> {
+    locks: &'a DepositLocks,
+    deposit_id: DepositId,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for DepositGuard<'_> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.inner.lock().unwrap();
+        drop(self.guard.take());
+
+        // if nobody else is holding or waiting for the lock, get rid of it so the map wouldn't grow forever
+        if let Some(lock) = locks.get(&self.deposit_id) {
+            if Arc::strong_count(lock) == 1 {
+                locks.remove(&self.deposit_id);
+            }
+        }
+    }
+}
+
 pub(crate) struct LocalEcashState {
     pub(crate) ecash_keypair: KeyPair,
     pub(crate) identity_keypair: identity::KeyPair,
@@ -105,6 +152,9 @@ pub(crate) struct LocalEcashState {
 
     // the cached byte representation of the bloomfilter to be used by the clients
     pub(crate) exported_double_spending_filter: ExportedDoubleSpendingFilter,
+
+    // locks held while issuing partial ticketbooks or voting on refunds of particular deposits
+    pub(crate) deposit_locks: DepositLocks,
 }
 
 impl LocalEcashState {
@@ -126,6 +176,7 @@ impl LocalEcashState {
                 })),
             },
             double_spending_filter: Arc::new(RwLock::new(double_spending_filter)),
+            deposit_locks: Default::default(),
         }
     }
 
@@ -4,7 +4,7 @@
 use crate::ecash::client::Client as LocalClient;
 use crate::ecash::comm::APICommunicationChannel;
 use crate::ecash::deposit::validate_deposit;
-use crate::ecash::error::{EcashError, RedemptionError, Result};
+use crate::ecash::error::{DepositRefundError, EcashError, RedemptionError, Result};
 use crate::ecash::helpers::{IssuedCoinIndicesSignatures, IssuedExpirationDateSignatures};
 use crate::ecash::keys::KeyPair;
 use crate::ecash::state::auxiliary::AuxiliaryEcashState;
@@ -13,14 +13,14 @@ use crate::ecash::state::helpers::{
     ensure_sane_expiration_date, prepare_partial_bloomfilter_builder, query_all_threshold_apis,
     try_rebuild_bloomfilter,
 };
-use crate::ecash::state::local::LocalEcashState;
+use crate::ecash::state::local::{DepositGuard, LocalEcashState};
 use crate::ecash::storage::models::{SerialNumberWrapper, TicketProvider};
 use crate::ecash::storage::EcashStorageExt;
 use crate::support::storage::NymApiStorage;
 use cosmwasm_std::{from_binary, CosmosMsg, WasmMsg};
 use cw3::Status;
 use nym_api_requests::ecash::helpers::issued_credential_plaintext;
-use nym_api_requests::ecash::models::BatchRedeemTicketsBody;
+use nym_api_requests::ecash::models::{BatchRedeemTicketsBody, DepositRefundRequestBody};
 use nym_api_requests::ecash::BlindSignRequestBody;
 use nym_coconut_dkg_common::types::EpochId;
 use nym_compact_ecash::scheme::coin_indices_signatures::{
@@ -39,7 +39,9 @@ use nym_credentials::{aggregate_verification_keys, CredentialSpendingData};
 use nym_crypto::asymmetric::identity;
 use nym_ecash_contract_common::deposit::{Deposit, DepositId};
 use nym_ecash_contract_common::msg::ExecuteMsg;
-use nym_ecash_contract_common::redeem_credential::BATCH_REDEMPTION_PROPOSAL_TITLE;
+use nym_ecash_contract_common::redeem_credential::{
+    BATCH_REDEMPTION_PROPOSAL_TITLE, DEPOSIT_REFUND_PROPOSAL_TITLE,
+};
 use nym_ecash_double_spending::DoubleSpendingFilter;
 use nym_ecash_time::cred_exp_date;
 use nym_validator_client::nyxd::AccountId;
@@ -417,6 +419,13 @@ impl EcashState {
         Ok(())
     }
 
+    /// Acquire the lock of the provided deposit. It must be held from checking whether the deposit
+    /// is pending a refund until storing the issued credential, and, correspondingly,
+    /// from checking whether the deposit is refundable until voting on its refund.
+    pub(crate) async fn lock_deposit(&self, deposit_id: DepositId) -> DepositGuard<'_> {
+        self.local.deposit_locks.lock(deposit_id).await
+    }
+
     /// Check if this nym-api has already issued a credential for the provided deposit id.
     /// If so, return it.
     pub async fn already_issued(&self, deposit_id: DepositId) -> Result<Option<BlindedSignature>> {
@@ -437,6 +446,65 @@ impl EcashState {
             .ok_or(EcashError::NonExistentDeposit { deposit_id })
     }
 
+    /// Make sure there is no pending refund of the provided deposit,
+    /// as otherwise we must not issue any partial ticketbooks for it.
+    pub async fn ensure_no_pending_refund(&self, deposit_id: DepositId) -> Result<()> {
+        if self
+            .aux
+            .client
+            .get_deposit_refund(deposit_id)
+            .await?
+            .refund
+            .is_some()
+        {
+            return Err(EcashError::PendingDepositRefund { deposit_id });
+        }
+        Ok(())
+    }
+
+    /// Check whether the contract holds a pending refund of the provided deposit
+    /// that is associated with the specified proposal.
+    pub async fn ensure_pending_refund(
+        &self,
+        deposit_id: DepositId,
+        proposal_id: u64,
+    ) -> Result<()> {
+        let refund = self.aux.client.get_deposit_refund(deposit_id).await?.refund;
+        if refund.and_then(|r| r.proposal_id) != Some(proposal_id) {
+            return Err(DepositRefundError::UnknownRefundRequest {
+                proposal_id,
+                deposit_id,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Make sure we have never issued a partial ticketbook for the provided deposit.
+    ///
+    /// Spent tickets can't be linked back to their deposit, so we can't tell whether any of them
+    /// have been used. However, the refund only goes through if at least `threshold` signers vote
+    /// for it, in which case at most `n - threshold` partial ticketbooks could have been issued,
+    /// which is not enough to aggregate a usable ticketbook (as `threshold > n / 2`).
+    /// Expiration is irrelevant here as tickets might have been spent before the expiry.
+    ///
+    /// The caller must hold the deposit lock (see [`Self::lock_deposit`]) until it has voted on the refund,
+    /// as otherwise a partial ticketbook could be issued in the meantime.
+    pub async fn ensure_refundable_deposit(&self, deposit_id: DepositId) -> Result<()> {
+        if let Some(issued) = self
+            .aux
+            .storage
+            .get_issued_bandwidth_credential_by_deposit_id(deposit_id)
+            .await?
+        {
+            return Err(EcashError::IssuedTicketbook {
+                deposit_id,
+                expiration_date: issued.expiration_date,
+            });
+        }
+        Ok(())
+    }
+
     pub async fn validate_request(
         &self,
         request: &BlindSignRequestBody,
@@ -547,6 +615,96 @@ impl EcashState {
         Ok(())
     }
 
+    pub(crate) async fn validate_refund_proposal(
+        &self,
+        request: &DepositRefundRequestBody,
+    ) -> std::result::Result<(), DepositRefundError> {
+        let proposal_id = request.proposal_id;
+
+        // retrieve the proposal itself
+        let mut proposal = self
+            .aux
+            .client
+            .get_proposal(proposal_id)
+            .await
+            .map_err(|_| DepositRefundError::ProposalRetrievalFailure { proposal_id })?;
+
+        if proposal.title != DEPOSIT_REFUND_PROPOSAL_TITLE {
+            return Err(DepositRefundError::InvalidProposalTitle {
+                proposal_id,
+                received: proposal.title,
+            });
+        }
+
+        // make sure you can still vote on it
+        match proposal.status {
+            Status::Pending => return Err(DepositRefundError::StillPending { proposal_id }),
+            Status::Open => {}
+            Status::Rejected => return Err(DepositRefundError::AlreadyRejected { proposal_id }),
+            Status::Passed => return Err(DepositRefundError::AlreadyPassed { proposal_id }),
+            Status::Executed => return Err(DepositRefundError::AlreadyExecuted { proposal_id }),
+        }
+
+        // check if the description matches the deposit id
+        let expected_description = request.deposit_id.to_string();
+        if expected_description != proposal.description {
+            return Err(DepositRefundError::InvalidProposalDescription {
+                proposal_id,
+                received: proposal.description,
+                expected: expected_description,
+            });
+        }
+
+        // check if it was actually created by the ecash contract
+        if proposal.proposer != self.global.contract_address.as_ref() {
+            return Err(DepositRefundError::InvalidProposer {
+                proposal_id,
+                received: proposal.proposer.into_string(),
+                expected: self.global.contract_address.clone(),
+            });
+        }
+
+        // check if contains exactly the content we expect,
+        // i.e. single `RefundDeposit` message with no funds, etc.
+        if proposal.msgs.len() != 1 {
+            return Err(DepositRefundError::TooManyMessages { proposal_id });
+        }
+
+        // SAFETY: we just checked we have exactly one message
+        #[allow(clippy::unwrap_used)]
+        let msg = proposal.msgs.pop().unwrap();
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) = msg
+        else {
+            return Err(DepositRefundError::InvalidMessage { proposal_id });
+        };
+
+        if !funds.is_empty() {
+            return Err(DepositRefundError::InvalidMessage { proposal_id });
+        }
+
+        if contract_addr != self.global.contract_address.as_ref() {
+            return Err(DepositRefundError::InvalidContract { proposal_id });
+        }
+
+        let Ok(ExecuteMsg::RefundDeposit { deposit_id }) = from_binary(&msg) else {
+            return Err(DepositRefundError::InvalidMessage { proposal_id });
+        };
+
+        if deposit_id != request.deposit_id {
+            return Err(DepositRefundError::InvalidRefundedDeposit {
+                proposal_id,
+                proposed: deposit_id,
+                received: request.deposit_id,
+            });
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn accept_proposal(&self, proposal_id: u64) -> Result<()> {
         //SW NOTE: What to do if this fails
         if let Err(err) = self.aux.client.vote_proposal(proposal_id, true, None).await {
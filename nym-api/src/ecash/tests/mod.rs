@@ -37,7 +37,9 @@ use nym_credentials_interface::TicketType;
 use nym_crypto::asymmetric::identity;
 use nym_dkg::{NodeIndex, Threshold};
 use nym_ecash_contract_common::blacklist::{BlacklistedAccountResponse, Blacklisting};
-use nym_ecash_contract_common::deposit::{Deposit, DepositId, DepositResponse};
+use nym_ecash_contract_common::deposit::{
+    Deposit, DepositId, DepositRefund, DepositRefundResponse, DepositResponse,
+};
 use nym_validator_client::nym_api::routes::{API_VERSION, ECASH_BLIND_SIGN, ECASH_ROUTES};
 use nym_validator_client::nyxd::cosmwasm_client::logs::Log;
 use nym_validator_client::nyxd::cosmwasm_client::types::ExecuteResult;
@@ -279,6 +281,7 @@ impl FakeMultisigContractState {
 pub(crate) struct FakeEcashContractState {
     pub(crate) address: Addr,
     pub(crate) deposits: HashMap<DepositId, Deposit>,
+    pub(crate) deposit_refunds: HashMap<DepositId, DepositRefund>,
     pub(crate) blacklist: HashMap<String, Blacklisting>,
 }
 
@@ -351,6 +354,7 @@ impl Default for FakeChainState {
             ecash_contract: FakeEcashContractState {
                 address: ecash_contract,
                 deposits: Default::default(),
+                deposit_refunds: Default::default(),
                 blacklist: Default::default(),
             },
         }
@@ -531,6 +535,22 @@ impl super::client::Client for DummyClient {
         })
     }
 
+    async fn get_deposit_refund(&self, deposit_id: DepositId) -> Result<DepositRefundResponse> {
+        let refund = self
+            .state
+            .lock()
+            .unwrap()
+            .ecash_contract
+            .deposit_refunds
+            .get(&deposit_id)
+            .cloned();
+
+        Ok(DepositRefundResponse {
+            id: deposit_id,
+            refund,
+        })
+    }
+
     async fn get_proposal(&self, proposal_id: u64) -> Result<ProposalResponse> {
         let chain = self.state.lock().unwrap();
         let proposal = chain
@@ -1624,3 +1644,197 @@ mod credential_tests {
         assert_eq!(recovered, request)
     }
 }
+
+#[cfg(test)]
+mod deposit_refund_tests {
+    use super::*;
+    use time::{Date, Duration, OffsetDateTime};
+
+    struct RefundFixture {
+        state: EcashState,
+        storage: NymApiStorage,
+        chain_state: SharedFakeChain,
+
+        _tmp_dir: TempDir,
+    }
+
+    impl RefundFixture {
+        async fn new() -> Self {
+            let mut rng = OsRng;
+            let identity = identity::KeyPair::new(&mut rng);
+            let chain_state = SharedFakeChain::default();
+
+            let nyxd_client = DummyClient::new(
+                AccountId::from_str(TEST_REWARDING_VALIDATOR_ADDRESS).unwrap(),
+                chain_state.clone(),
+            );
+            let key_pair = ttp_keygen(1, 1).unwrap().remove(0);
+            let tmp_dir = tempdir().unwrap();
+
+            let storage = NymApiStorage::init(tmp_dir.path().join("storage.db"))
+                .await
+                .unwrap();
+            let comm_channel =
+                DummyCommunicationChannel::new_single_dummy(key_pair.verification_key().clone());
+            let staged_key_pair = crate::ecash::keys::KeyPair::new();
+            staged_key_pair
+                .set(KeyPairWithEpoch {
+                    keys: key_pair,
+                    issued_for_epoch: 1,
+                })
+                .await;
+            staged_key_pair.validate();
+
+            let state = EcashState::new(
+                "n16a32stm6kknhq5cc8rx77elr66pygf2hfszw7wvpq746x3uffylqkjar4l"
+                    .parse()
+                    .unwrap(),
+                nyxd_client,
+                identity,
+                staged_key_pair,
+                comm_channel,
+                storage.clone(),
+            )
+            .await
+            .unwrap();
+
+            RefundFixture {
+                state,
+                storage,
+                chain_state,
+                _tmp_dir: tmp_dir,
+            }
+        }
+
+        async fn issue_partial_ticketbook(&self, deposit_id: DepositId, expiration_date: Date) {
+            let voucher = voucher_fixture(Some(deposit_id));
+            let signing_data = voucher.prepare_for_signing();
+            let request_body = voucher.create_blind_sign_request_body(&signing_data);
+
+            self.storage
+                .store_issued_credential(
+                    42,
+                    deposit_id,
+                    &blinded_signature_fixture(),
+                    dummy_signature(),
+                    request_body.encode_commitments(),
+                    expiration_date,
+                    voucher.ticketbook_type(),
+                )
+                .await
+                .unwrap();
+        }
+
+        fn request_refund(&self, deposit_id: DepositId, proposal_id: Option<u64>) {
+            self.chain_state
+                .lock()
+                .unwrap()
+                .ecash_contract
+                .deposit_refunds
+                .insert(
+                    deposit_id,
+                    DepositRefund {
+                        receiver: Addr::unchecked("receiver"),
+                        proposal_id,
+                    },
+                );
+        }
+    }
+
+    #[tokio::test]
+    async fn deposit_without_issued_ticketbook_is_refundable() {
+        let test = RefundFixture::new().await;
+        assert!(test.state.ensure_refundable_deposit(42).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn deposit_with_unexpired_ticketbook_is_not_refundable() {
+        let test = RefundFixture::new().await;
+        let expiration_date = OffsetDateTime::now_utc().date() + Duration::days(7);
+        test.issue_partial_ticketbook(42, expiration_date).await;
+
+        let err = test.state.ensure_refundable_deposit(42).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EcashError::IssuedTicketbook { deposit_id: 42, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn deposit_with_expired_ticketbook_is_not_refundable() {
+        // the tickets might have been spent before the ticketbook expired
+        let test = RefundFixture::new().await;
+        let expiration_date = OffsetDateTime::now_utc().date() - Duration::days(60);
+        test.issue_partial_ticketbook(42, expiration_date).await;
+
+        let err = test.state.ensure_refundable_deposit(42).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EcashError::IssuedTicketbook { deposit_id: 42, .. }
+        ));
+
+        // but it doesn't affect other deposits
+        assert!(test.state.ensure_refundable_deposit(43).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn no_ticketbooks_are_issued_for_deposits_pending_refund() {
+        let test = RefundFixture::new().await;
+        assert!(test.state.ensure_no_pending_refund(42).await.is_ok());
+
+        test.request_refund(42, Some(1));
+        let err = test.state.ensure_no_pending_refund(42).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EcashError::PendingDepositRefund { deposit_id: 42 }
+        ));
+    }
+
+    #[tokio::test]
+    async fn refund_must_match_pending_request() {
+        let test = RefundFixture::new().await;
+
+        // nothing has been requested
+        assert!(test.state.ensure_pending_refund(42, 1).await.is_err());
+
+        // the proposal hasn't been created yet
+        test.request_refund(42, None);
+        assert!(test.state.ensure_pending_refund(42, 1).await.is_err());
+
+        test.request_refund(42, Some(1));
+        assert!(test.state.ensure_pending_refund(42, 1).await.is_ok());
+
+        // repeated refund attempt using a different proposal
+        assert!(test.state.ensure_pending_refund(42, 2).await.is_err());
+
+        // the refund has already been executed
+        test.chain_state
+            .lock()
+            .unwrap()
+            .ecash_contract
+            .deposit_refunds
+            .remove(&42);
+        assert!(test.state.ensure_pending_refund(42, 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn deposit_can_only_be_locked_once_at_a_time() {
+        let test = RefundFixture::new().await;
+        let timeout = std::time::Duration::from_millis(50);
+
+        let guard = test.state.lock_deposit(42).await;
+        assert!(tokio::time::timeout(timeout, test.state.lock_deposit(42))
+            .await
+            .is_err());
+
+        // but it doesn't affect other deposits
+        assert!(tokio::time::timeout(timeout, test.state.lock_deposit(43))
+            .await
+            .is_ok());
+
+        drop(guard);
+        assert!(tokio::time::timeout(timeout, test.state.lock_deposit(42))
+            .await
+            .is_ok());
+    }
+}
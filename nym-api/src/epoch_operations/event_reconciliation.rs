@@ -54,6 +54,17 @@ impl RewardedSetUpdater {
             self.nyxd_client.reconcile_epoch_events(Some(limit)).await?;
         }
 
+        // compounding the delegator rewards shares the same limit and it's not known upfront
+        // how many delegations have opted into it, so keep going until the contract moves on
+        while self
+            .nyxd_client
+            .get_current_epoch_status()
+            .await?
+            .is_reconciling()
+        {
+            self.nyxd_client.reconcile_epoch_events(Some(limit)).await?;
+        }
+
         // in the incredibly unlikely/borderline impossible scenario a HUGE number of events got pushed
         // while we were reconciling the events, do it one more time
         //
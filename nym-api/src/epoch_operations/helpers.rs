@@ -4,7 +4,7 @@
 use crate::epoch_operations::RewardedSetUpdater;
 use cosmwasm_std::{Decimal, Fraction};
 use nym_mixnet_contract_common::reward_params::Performance;
-use nym_mixnet_contract_common::{ExecuteMsg, Interval, MixId};
+use nym_mixnet_contract_common::{ExecuteMsg, IdentityKey, Interval, MixId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -23,6 +23,22 @@ impl From<MixnodeWithPerformance> for ExecuteMsg {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GatewayWithPerformance {
+    pub(crate) identity: IdentityKey,
+
+    pub(crate) performance: Performance,
+}
+
+impl From<GatewayWithPerformance> for ExecuteMsg {
+    fn from(gateway_reward: GatewayWithPerformance) -> Self {
+        ExecuteMsg::RewardGateway {
+            identity: gateway_reward.identity,
+            performance: gateway_reward.performance,
+        }
+    }
+}
+
 pub(super) fn stake_to_f64(stake: Decimal) -> f64 {
     let max = f64::MAX.round() as u128;
 
@@ -58,6 +74,26 @@ impl RewardedSetUpdater {
         }
     }
 
+    pub(crate) async fn load_gateway_performance(
+        &self,
+        interval: &Interval,
+        identity: IdentityKey,
+    ) -> GatewayWithPerformance {
+        let uptime = self
+            .storage
+            .get_average_gateway_uptime_in_the_last_24hrs(
+                &identity,
+                interval.current_epoch_end_unix_timestamp(),
+            )
+            .await
+            .unwrap_or_default();
+
+        GatewayWithPerformance {
+            identity,
+            performance: uptime.into(),
+        }
+    }
+
     pub(crate) async fn load_nodes_performance(
         &self,
         interval: &Interval,
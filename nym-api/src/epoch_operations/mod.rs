@@ -17,7 +17,7 @@ use crate::nym_contract_cache::cache::NymContractCache;
 use crate::support::nyxd::Client;
 use crate::support::storage::NymApiStorage;
 use error::RewardingError;
-pub(crate) use helpers::MixnodeWithPerformance;
+pub(crate) use helpers::{GatewayWithPerformance, MixnodeWithPerformance};
 use nym_mixnet_contract_common::{CurrentIntervalResponse, Interval};
 use nym_task::{TaskClient, TaskManager};
 use std::collections::HashSet;
@@ -76,6 +76,9 @@ impl RewardedSetUpdater {
     ///    it sends (in a single batch) `RewardMixnode` message with the measured performance.
     ///    Once the final message gets executed, the mixnet contract automatically transitions
     ///    the state to `ReconcilingEvents`.
+    /// 4a. if gateway rewarding is enabled in the contract, it sends (in a single batch) `RewardGateway`
+    ///    message for each bonded gateway (that hasn't yet been rewarded in this epoch)
+    ///    with its measured performance.
     /// 5. it obtains the number of pending epoch and interval events and repeatedly sends
     ///    `ReconcileEpochEvents` transaction until all of them are resolved.
     ///    At this point the mixnet contract automatically transitions the state to `AdvancingEpoch`.
@@ -135,6 +138,9 @@ impl RewardedSetUpdater {
         log::info!("Rewarding the current rewarded set...");
         self.reward_current_rewarded_set(&rewards, interval).await?;
 
+        log::info!("Rewarding the bonded gateways...");
+        self.reward_gateways(interval).await?;
+
         // note: those operations don't really have to be atomic, so it's fine to send them
         // as separate transactions
         self.reconcile_epoch_events().await?;
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::epoch_operations::error::RewardingError;
-use crate::epoch_operations::helpers::MixnodeWithPerformance;
+use crate::epoch_operations::helpers::{GatewayWithPerformance, MixnodeWithPerformance};
 use crate::RewardedSetUpdater;
 use nym_mixnet_contract_common::{EpochState, Interval, MixId};
+use std::cmp::Ordering;
 
 impl RewardedSetUpdater {
     pub(super) async fn reward_current_rewarded_set(
@@ -69,6 +70,106 @@ impl RewardedSetUpdater {
         Ok(())
     }
 
+    pub(super) async fn reward_gateways(
+        &self,
+        current_interval: Interval,
+    ) -> Result<(), RewardingError> {
+        let epoch_status = self.nyxd_client.get_current_epoch_status().await?;
+        match epoch_status.state {
+            EpochState::ReconcilingEvents => {}
+            EpochState::AdvancingEpoch => {
+                warn!("we seem to have crashed mid epoch operations... no need to reward gateways as we've already done that!");
+                return Ok(());
+            }
+            other => {
+                // hard error, mixnode rewarding should have already been completed!
+                error!(
+                    "tried to perform gateway rewarding while the epoch is in the {other} state!"
+                );
+                return Err(RewardingError::InvalidEpochState {
+                    current_state: other,
+                    operation: "gateway rewarding".to_string(),
+                });
+            }
+        }
+
+        let rewarding_params = self.nyxd_client.get_gateway_rewarding_params().await?;
+        if rewarding_params.rewarded_gateways == 0 || rewarding_params.epoch_reward_budget.is_zero()
+        {
+            log::info!("gateway rewarding is currently disabled in the contract");
+            return Ok(());
+        }
+
+        let to_reward = self
+            .gateways_to_reward(current_interval, rewarding_params.rewarded_gateways)
+            .await?;
+        if to_reward.is_empty() {
+            log::info!("there are no gateways to reward in this epoch");
+            return Ok(());
+        }
+
+        if let Err(err) = self
+            .nyxd_client
+            .send_gateway_rewarding_messages(&to_reward)
+            .await
+        {
+            error!(
+                "failed to perform gateway rewarding for epoch {}! Error encountered: {err}",
+                current_interval.current_epoch_absolute_id(),
+            );
+            return Err(err.into());
+        }
+
+        log::info!("rewarded {} gateways...", to_reward.len());
+
+        Ok(())
+    }
+
+    async fn gateways_to_reward(
+        &self,
+        interval: Interval,
+        rewarded_gateways: u32,
+    ) -> Result<Vec<GatewayWithPerformance>, RewardingError> {
+        let absolute_epoch_id = interval.current_epoch_absolute_id();
+        let mut candidates = Vec::new();
+
+        for gateway in self.nym_contract_cache.gateways_all().await {
+            let identity = gateway.identity().clone();
+
+            let details = self
+                .nyxd_client
+                .get_gateway_rewarding_details(identity.clone())
+                .await?;
+            let rewarding = match details.rewarding_details {
+                Some(rewarding) if rewarding.still_bonded() => rewarding,
+                _ => continue,
+            };
+
+            let with_performance = self.load_gateway_performance(&interval, identity).await;
+            let already_rewarded = rewarding.last_rewarded_epoch == absolute_epoch_id;
+            candidates.push((rewarding.node_bond(), already_rewarded, with_performance))
+        }
+
+        // only the top `rewarded_gateways` nodes, by their total stake and then performance,
+        // are eligible for rewards as the contract would reject any subsequent ones anyway
+        candidates.sort_by(|(stake_a, _, gateway_a), (stake_b, _, gateway_b)| {
+            stake_b.cmp(stake_a).then(
+                gateway_b
+                    .performance
+                    .partial_cmp(&gateway_a.performance)
+                    .unwrap_or(Ordering::Equal),
+            )
+        });
+        candidates.truncate(rewarded_gateways as usize);
+
+        // make sure to skip nodes that got already rewarded in case we crashed mid-rewarding
+        Ok(candidates
+            .into_iter()
+            .filter(|(_, already_rewarded, _)| !already_rewarded)
+            .map(|(_, _, gateway)| gateway)
+            .collect())
+    }
+
     pub(crate) async fn nodes_to_reward(&self, interval: Interval) -> Vec<MixnodeWithPerformance> {
         // try to get current up to date view of the network bypassing the cache
         // in case the epochs were significantly shortened for the purposes of testing
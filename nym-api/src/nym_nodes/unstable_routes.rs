@@ -43,7 +43,7 @@ pub async fn nodes_basic(
     if let Some(role) = role {
         match role {
             NodeRoleQueryParam::ActiveMixnode => {
-                return mixnodes_basic(status_cache, semver_compatibility).await
+                return mixnodes_basic(status_cache, describe_cache, semver_compatibility).await
             }
             NodeRoleQueryParam::EntryGateway => {
                 return gateways_basic(status_cache, describe_cache, semver_compatibility).await
@@ -183,6 +183,7 @@ pub async fn gateways_detailed(
 #[get("/mixnodes/skimmed?<semver_compatibility>")]
 pub async fn mixnodes_basic(
     cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
 ) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
     let mixnodes_cache = cache
@@ -192,8 +193,17 @@ pub async fn mixnodes_basic(
             "could not obtain mixnodes cache",
             Status::InternalServerError,
         ))?;
+
+    // the self-described data is only used for the sphinx key rotation information,
+    // so if it's unavailable, just fallback to the basic bond information
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(described) => min(mixnodes_cache.timestamp(), described.timestamp()),
+        None => mixnodes_cache.timestamp(),
+    };
+
     Ok(Json(CachedNodesResponse {
-        refreshed_at: mixnodes_cache.timestamp().into(),
+        refreshed_at: refreshed_at.into(),
         nodes: mixnodes_cache
             .iter()
             .filter(|annotated_bond| {
@@ -210,7 +220,14 @@ pub async fn mixnodes_basic(
                     true
                 }
             })
-            .map(Into::into)
+            .map(|annotated_bond| {
+                SkimmedNode::from_described_mixnode(
+                    annotated_bond,
+                    self_descriptions
+                        .as_ref()
+                        .and_then(|described| described.deref().get(annotated_bond.identity_key())),
+                )
+            })
             .collect(),
     }))
 }
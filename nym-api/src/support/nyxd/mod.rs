@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::ecash::error::EcashError;
-use crate::epoch_operations::MixnodeWithPerformance;
+use crate::epoch_operations::{GatewayWithPerformance, MixnodeWithPerformance};
 use crate::support::config::Config;
 use anyhow::Result;
 use async_trait::async_trait;
@@ -23,8 +23,11 @@ use nym_coconut_dkg_common::{
 use nym_config::defaults::{ChainDetails, NymNetworkDetails};
 use nym_dkg::Threshold;
 use nym_ecash_contract_common::blacklist::BlacklistedAccountResponse;
-use nym_ecash_contract_common::deposit::{DepositId, DepositResponse};
+use nym_ecash_contract_common::deposit::{DepositId, DepositRefundResponse, DepositResponse};
 use nym_mixnet_contract_common::families::FamilyHead;
+use nym_mixnet_contract_common::gateway::{
+    GatewayRewardingDetailsResponse, GatewayRewardingParams,
+};
 use nym_mixnet_contract_common::mixnode::MixNodeDetails;
 use nym_mixnet_contract_common::reward_params::RewardingParams;
 use nym_mixnet_contract_common::{
@@ -235,6 +238,19 @@ impl Client {
         nyxd_query!(self, get_rewarding_parameters().await)
     }
 
+    pub(crate) async fn get_gateway_rewarding_params(
+        &self,
+    ) -> Result<GatewayRewardingParams, NyxdError> {
+        nyxd_query!(self, get_gateway_rewarding_params().await)
+    }
+
+    pub(crate) async fn get_gateway_rewarding_details(
+        &self,
+        identity: IdentityKey,
+    ) -> Result<GatewayRewardingDetailsResponse, NyxdError> {
+        nyxd_query!(self, get_gateway_rewarding_details(identity).await)
+    }
+
     pub(crate) async fn get_rewarded_set_mixnodes(
         &self,
     ) -> Result<Vec<(MixId, RewardedSetNodeStatus)>, NyxdError> {
@@ -332,6 +348,51 @@ impl Client {
         Ok(())
     }
 
+    pub(crate) async fn send_gateway_rewarding_messages(
+        &self,
+        gateways: &[GatewayWithPerformance],
+    ) -> Result<(), NyxdError> {
+        #[inline]
+        #[allow(unused_variables)]
+        fn generate_reward_messages(
+            eligible_gateways: &[GatewayWithPerformance],
+        ) -> Vec<(ExecuteMsg, Vec<Coin>)> {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "no-reward")] {
+                    vec![]
+                } else {
+                    eligible_gateways
+                        .iter()
+                        .map(|gateway| gateway.clone().into())
+                        .zip(std::iter::repeat(Vec::new()))
+                        .collect()
+                }
+            }
+        }
+
+        // the expect is fine as we always construct the client with the mixnet contract explicitly set
+        let mixnet_contract = nyxd_query!(
+            self,
+            mixnet_contract_address()
+                .expect("mixnet contract address is not available")
+                .clone()
+        );
+
+        let msgs = generate_reward_messages(gateways);
+
+        nyxd_signing!(
+            self,
+            execute_multiple(
+                &mixnet_contract,
+                msgs,
+                Default::default(),
+                format!("rewarding {} gateways", gateways.len()),
+            )
+            .await?
+        );
+        Ok(())
+    }
+
     pub(crate) async fn advance_current_epoch(
         &self,
         new_rewarded_set: Vec<LayerAssignment>,
@@ -372,6 +433,13 @@ impl crate::ecash::client::Client for Client {
         Ok(nyxd_query!(self, get_deposit(deposit_id).await?))
     }
 
+    async fn get_deposit_refund(
+        &self,
+        deposit_id: DepositId,
+    ) -> crate::ecash::error::Result<DepositRefundResponse> {
+        Ok(nyxd_query!(self, get_deposit_refund(deposit_id).await?))
+    }
+
     async fn get_proposal(
         &self,
         proposal_id: u64,
@@ -0,0 +1,33 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::state::metrics::MetricsAppState;
+use axum::extract::{Query, State};
+use nym_http_api_common::{FormattedResponse, OutputParams};
+use nym_node_requests::api::v1::metrics::models::InboxStats;
+
+/// If applicable, returns statistics of the messages stored by this node for its offline clients.
+/// This information is **PURELY** self-reported and in no way validated.
+#[utoipa::path(
+    get,
+    path = "/inboxes",
+    context_path = "/api/v1/metrics",
+    tag = "Metrics",
+    responses(
+        (status = 200, content(
+            ("application/json" = InboxStats),
+            ("application/yaml" = InboxStats)
+        ))
+    ),
+    params(OutputParams),
+)]
+pub(crate) async fn inbox_stats(
+    Query(output): Query<OutputParams>,
+    State(metrics_state): State<MetricsAppState>,
+) -> InboxStatsResponse {
+    let output = output.output.unwrap_or_default();
+    let response = metrics_state.inbox_stats.read().await.as_response();
+    output.to_response(response)
+}
+
+pub type InboxStatsResponse = FormattedResponse<InboxStats>;
@@ -1,6 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::api::v1::metrics::inboxes::inbox_stats;
 use crate::api::v1::metrics::mixing::mixing_stats;
 use crate::api::v1::metrics::prometheus::prometheus_metrics;
 use crate::api::v1::metrics::verloc::verloc_stats;
@@ -10,6 +11,7 @@ use axum::routing::get;
 use axum::Router;
 use nym_node_requests::routes::api::v1::metrics;
 
+pub mod inboxes;
 pub mod mixing;
 pub mod prometheus;
 pub mod verloc;
@@ -28,4 +30,5 @@ where
         .route(metrics::MIXING, get(mixing_stats))
         .route(metrics::VERLOC, get(verloc_stats))
         .route(metrics::PROMETHEUS, get(prometheus_metrics))
+        .route(metrics::INBOXES, get(inbox_stats))
 }
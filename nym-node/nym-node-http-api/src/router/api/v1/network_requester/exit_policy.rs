@@ -3,6 +3,7 @@
 
 use crate::api::{FormattedResponse, OutputParams};
 use axum::extract::Query;
+use nym_exit_policy::upstream::UpstreamExitPolicy;
 use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 
 /// Returns information about the exit policy used by this node.
@@ -27,4 +28,23 @@ pub(crate) async fn node_exit_policy(
     output.to_response(policy)
 }
 
+/// Describes the current state of the exit policy actively used by the node.
+pub(crate) fn used_exit_policy(source: &UpstreamExitPolicy) -> UsedExitPolicy {
+    let upstream = source.upstream();
+
+    // if there's no upstream (i.e. open proxy), we couldn't have possibly updated it : )
+    let last_updated = if upstream.is_some() {
+        source.last_updated().unix_timestamp() as u64
+    } else {
+        0
+    };
+
+    UsedExitPolicy {
+        enabled: true,
+        upstream_source: upstream.map(|u| u.to_string()).unwrap_or_default(),
+        last_updated,
+        policy: Some(source.policy().as_ref().clone()),
+    }
+}
+
 pub type ExitPolicyResponse = FormattedResponse<UsedExitPolicy>;
@@ -1,9 +1,10 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::api::v1::network_requester::exit_policy::node_exit_policy;
+use crate::api::v1::network_requester::exit_policy::{node_exit_policy, used_exit_policy};
 use axum::routing::get;
 use axum::Router;
+use nym_exit_policy::upstream::SharedExitPolicy;
 use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use nym_node_requests::api::v1::network_requester::models;
 use nym_node_requests::routes::api::v1::network_requester;
@@ -15,6 +16,10 @@ pub mod root;
 pub struct Config {
     pub details: Option<models::NetworkRequester>,
     pub exit_policy: Option<UsedExitPolicy>,
+
+    /// Exit policy actively used by the node. If set, it takes precedence over the static
+    /// `exit_policy` so that any refreshes of the policy are reflected in the responses.
+    pub exit_policy_source: Option<SharedExitPolicy>,
 }
 
 pub(crate) fn routes<S: Send + Sync + 'static + Clone>(config: Config) -> Router<S> {
@@ -30,7 +35,14 @@ pub(crate) fn routes<S: Send + Sync + 'static + Clone>(config: Config) -> Router
             network_requester::EXIT_POLICY,
             get({
                 let policy = config.exit_policy.unwrap_or_default();
-                move |query| node_exit_policy(policy, query)
+                let source = config.exit_policy_source;
+                move |query| {
+                    let policy = match source {
+                        Some(source) => used_exit_policy(&source),
+                        None => policy,
+                    };
+                    node_exit_policy(policy, query)
+                }
             }),
         )
 }
@@ -4,6 +4,37 @@
 use crate::api::{FormattedResponse, OutputParams};
 use axum::extract::Query;
 use nym_node_requests::api::v1::node::models::SignedHostInformation;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Signed host information that can be updated while the server is running,
+/// for example after the node has rotated its sphinx keys.
+#[derive(Debug, Clone)]
+pub struct SharedHostInformation {
+    inner: Arc<RwLock<SignedHostInformation>>,
+}
+
+impl SharedHostInformation {
+    pub fn new(host_information: SignedHostInformation) -> Self {
+        SharedHostInformation {
+            inner: Arc::new(RwLock::new(host_information)),
+        }
+    }
+
+    pub async fn get(&self) -> SignedHostInformation {
+        self.inner.read().await.clone()
+    }
+
+    pub async fn update(&self, host_information: SignedHostInformation) {
+        *self.inner.write().await = host_information
+    }
+}
+
+impl From<SignedHostInformation> for SharedHostInformation {
+    fn from(host_information: SignedHostInformation) -> Self {
+        SharedHostInformation::new(host_information)
+    }
+}
 
 /// Returns host information of this node.
 #[utoipa::path(
@@ -20,11 +51,11 @@ use nym_node_requests::api::v1::node::models::SignedHostInformation;
     params(OutputParams)
 )]
 pub(crate) async fn host_information(
-    host_information: SignedHostInformation,
+    host_information: SharedHostInformation,
     Query(output): Query<OutputParams>,
 ) -> HostInformationResponse {
     let output = output.output.unwrap_or_default();
-    output.to_response(host_information)
+    output.to_response(host_information.get().await)
 }
 
 pub type HostInformationResponse = FormattedResponse<SignedHostInformation>;
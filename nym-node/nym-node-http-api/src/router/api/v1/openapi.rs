@@ -23,6 +23,7 @@ use utoipa_swagger_ui::SwaggerUi;
         api::v1::metrics::mixing::mixing_stats,
         api::v1::metrics::verloc::verloc_stats,
         api::v1::metrics::prometheus::prometheus_metrics,
+        api::v1::metrics::inboxes::inbox_stats,
         api::v1::health::root_health,
         api::v1::gateway::root::root_gateway,
         api::v1::gateway::client_interfaces::client_interfaces,
@@ -43,6 +44,7 @@ use utoipa_swagger_ui::SwaggerUi;
             api_requests::v1::node::models::SignedHostInformation,
             api_requests::v1::node::models::HostInformation,
             api_requests::v1::node::models::HostKeys,
+            api_requests::v1::node::models::SphinxKeyRotation,
             api_requests::v1::node::models::NodeRoles,
             api_requests::v1::node::models::HostSystem,
             api_requests::v1::node::models::Hardware,
@@ -56,6 +58,7 @@ use utoipa_swagger_ui::SwaggerUi;
             api_requests::v1::metrics::models::VerlocResultData,
             api_requests::v1::metrics::models::VerlocNodeResult,
             api_requests::v1::metrics::models::VerlocMeasurement,
+            api_requests::v1::metrics::models::InboxStats,
             api_requests::v1::gateway::models::Gateway,
             api_requests::v1::gateway::models::Wireguard,
             api_requests::v1::gateway::models::ClientInterfaces,
@@ -1,6 +1,7 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::api::v1::node::host_information::SharedHostInformation;
 use crate::error::NymNodeHttpError;
 use crate::middleware::logging;
 use crate::state::AppState;
@@ -8,6 +9,7 @@ use crate::NymNodeHTTPServer;
 use axum::response::Redirect;
 use axum::routing::get;
 use axum::Router;
+use nym_exit_policy::upstream::SharedExitPolicy;
 use nym_node_requests::api::v1::authenticator::models::Authenticator;
 use nym_node_requests::api::v1::gateway::models::{Gateway, Wireguard};
 use nym_node_requests::api::v1::ip_packet_router::models::IpPacketRouter;
@@ -16,7 +18,6 @@ use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExit
 use nym_node_requests::api::v1::network_requester::models::NetworkRequester;
 use nym_node_requests::api::v1::node::models;
 use nym_node_requests::api::v1::node::models::{AuxiliaryDetails, HostSystem, NodeDescription};
-use nym_node_requests::api::SignedHostInformation;
 use nym_node_requests::routes;
 use std::net::SocketAddr;
 use std::path::Path;
@@ -35,7 +36,7 @@ pub struct Config {
 impl Config {
     pub fn new(
         build_information: models::BinaryBuildInformationOwned,
-        host_information: SignedHostInformation,
+        host_information: impl Into<SharedHostInformation>,
     ) -> Self {
         Config {
             landing: Default::default(),
@@ -43,7 +44,7 @@ impl Config {
                 v1_config: api::v1::Config {
                     node: api::v1::node::Config {
                         build_information,
-                        host_information,
+                        host_information: host_information.into(),
                         system_info: None,
                         roles: Default::default(),
                         description: Default::default(),
@@ -138,6 +139,13 @@ impl Config {
         self
     }
 
+    /// Serve the current state of the provided exit policy rather than a fixed snapshot.
+    #[must_use]
+    pub fn with_exit_policy_source(mut self, exit_policy: SharedExitPolicy) -> Self {
+        self.api.v1_config.network_requester.exit_policy_source = Some(exit_policy);
+        self
+    }
+
     #[must_use]
     pub fn with_ip_packet_router(mut self, ip_packet_router: IpPacketRouter) -> Self {
         self.api.v1_config.node.roles.ip_packet_router_enabled = true;
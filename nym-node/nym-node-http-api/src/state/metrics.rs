@@ -4,7 +4,7 @@
 use crate::state::AppState;
 use axum::extract::FromRef;
 use nym_node_requests::api::v1::metrics::models::{
-    MixingStats, VerlocResult, VerlocResultData, VerlocStats,
+    InboxStats, MixingStats, VerlocResult, VerlocResultData, VerlocStats,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -53,6 +53,10 @@ pub struct MixingStatsState {
     pub packets_dropped_since_startup_all: u64,
     pub packets_received_since_last_update: u64,
 
+    // packets rejected due to being replays of packets we've already seen
+    pub packets_replayed_since_startup: u64,
+    pub packets_replayed_since_last_update: u64,
+
     // note: sent does not imply forwarded. We don't know if it was delivered successfully
     pub packets_sent_since_last_update: PacketsMap,
 
@@ -69,6 +73,8 @@ impl MixingStatsState {
             sent_since_startup: self.packets_sent_since_startup_all,
             dropped_since_startup: self.packets_dropped_since_startup_all,
             received_since_last_update: self.packets_received_since_last_update,
+            replayed_since_startup: self.packets_replayed_since_startup,
+            replayed_since_last_update: self.packets_replayed_since_last_update,
             sent_since_last_update: self.packets_sent_since_last_update.values().sum(),
             dropped_since_last_update: self
                 .packets_explicitly_dropped_since_last_update
@@ -87,6 +93,8 @@ impl Default for MixingStatsState {
             packets_sent_since_startup_all: 0,
             packets_dropped_since_startup_all: 0,
             packets_received_since_last_update: 0,
+            packets_replayed_since_startup: 0,
+            packets_replayed_since_last_update: 0,
             packets_sent_since_last_update: Default::default(),
             packets_explicitly_dropped_since_last_update: Default::default(),
         }
@@ -132,6 +140,66 @@ impl VerlocStatsState {
     }
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct SharedInboxStats {
+    inner: Arc<RwLock<InboxStatsState>>,
+}
+
+impl SharedInboxStats {
+    pub fn new() -> SharedInboxStats {
+        SharedInboxStats::default()
+    }
+
+    pub async fn read(&self) -> RwLockReadGuard<'_, InboxStatsState> {
+        self.inner.read().await
+    }
+
+    pub async fn write(&self) -> RwLockWriteGuard<'_, InboxStatsState> {
+        self.inner.write().await
+    }
+}
+
+#[derive(Debug)]
+pub struct InboxStatsState {
+    pub last_pruning_time: OffsetDateTime,
+
+    pub stored_messages: u64,
+    pub stored_bytes: u64,
+    pub inboxes: u64,
+
+    // messages removed for being older than the maximum allowed age
+    pub expired_messages_removed_since_startup: u64,
+
+    // messages removed for exceeding the per-client inbox size limit
+    pub excessive_messages_removed_since_startup: u64,
+}
+
+impl InboxStatsState {
+    pub fn as_response(&self) -> InboxStats {
+        InboxStats {
+            last_pruning_time: self.last_pruning_time,
+            stored_messages: self.stored_messages,
+            stored_bytes: self.stored_bytes,
+            inboxes: self.inboxes,
+            expired_removed_since_startup: self.expired_messages_removed_since_startup,
+            excessive_removed_since_startup: self.excessive_messages_removed_since_startup,
+        }
+    }
+}
+
+impl Default for InboxStatsState {
+    fn default() -> Self {
+        InboxStatsState {
+            last_pruning_time: OffsetDateTime::UNIX_EPOCH,
+            stored_messages: 0,
+            stored_bytes: 0,
+            inboxes: 0,
+            expired_messages_removed_since_startup: 0,
+            excessive_messages_removed_since_startup: 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MetricsAppState {
     pub(crate) prometheus_access_token: Option<String>,
@@ -139,6 +207,8 @@ pub struct MetricsAppState {
     pub(crate) mixing_stats: SharedMixingStats,
 
     pub(crate) verloc: SharedVerlocStats,
+
+    pub(crate) inbox_stats: SharedInboxStats,
 }
 
 impl FromRef<AppState> for MetricsAppState {
@@ -1,7 +1,9 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::state::metrics::{MetricsAppState, SharedMixingStats, SharedVerlocStats};
+use crate::state::metrics::{
+    MetricsAppState, SharedInboxStats, SharedMixingStats, SharedVerlocStats,
+};
 use tokio::time::Instant;
 
 pub mod metrics;
@@ -38,6 +40,12 @@ impl AppState {
         self
     }
 
+    #[must_use]
+    pub fn with_inbox_stats(mut self, inbox_stats: SharedInboxStats) -> Self {
+        self.metrics.inbox_stats = inbox_stats;
+        self
+    }
+
     #[must_use]
     pub fn with_metrics_key(mut self, bearer_token: impl Into<Option<String>>) -> Self {
         self.metrics.prometheus_access_token = bearer_token.into();
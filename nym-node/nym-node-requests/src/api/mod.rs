@@ -113,6 +113,7 @@ mod tests {
                 ed25519_identity: ed22519.public_key().to_base58_string(),
                 x25519_sphinx: x25519_sphinx.public_key().to_base58_string(),
                 x25519_noise: "".to_string(),
+                x25519_sphinx_rotation: None,
             },
         };
 
@@ -121,6 +122,52 @@ mod tests {
         assert!(signed_info.verify_host_information())
     }
 
+    #[test]
+    fn signed_host_verification_with_sphinx_key_rotation() {
+        let mut rng = rand_chacha::ChaCha20Rng::from_seed([0u8; 32]);
+        let ed22519 = ed25519::KeyPair::new(&mut rng);
+        let x25519_sphinx = x25519::KeyPair::new(&mut rng);
+        let current_sphinx = x25519::KeyPair::new(&mut rng);
+        let next_sphinx = x25519::KeyPair::new(&mut rng);
+
+        let mut host_info = crate::api::v1::node::models::HostInformation {
+            ip_address: vec!["1.1.1.1".parse().unwrap()],
+            hostname: Some("foomp.com".to_string()),
+            keys: crate::api::v1::node::models::HostKeys {
+                ed25519_identity: ed22519.public_key().to_base58_string(),
+                x25519_sphinx: x25519_sphinx.public_key().to_base58_string(),
+                x25519_noise: "".to_string(),
+                x25519_sphinx_rotation: None,
+            },
+        };
+
+        // lack of rotation information must not affect the signed data
+        let without_rotation = serde_json::to_string(&host_info).unwrap();
+        assert!(!without_rotation.contains("x25519_sphinx_rotation"));
+
+        host_info.keys.x25519_sphinx_rotation =
+            Some(crate::api::v1::node::models::SphinxKeyRotation {
+                current_rotation_id: 42,
+                current_x25519_sphinx: current_sphinx.public_key().to_base58_string(),
+                next_x25519_sphinx: next_sphinx.public_key().to_base58_string(),
+                next_rotation_start: time::OffsetDateTime::from_unix_timestamp(1714564800).unwrap(),
+            });
+
+        let signed_info = SignedHostInformation::new(host_info, ed22519.private_key()).unwrap();
+        assert!(signed_info.verify_host_information());
+
+        // make sure the rotation information can't be altered without invalidating the signature
+        let mut tampered = signed_info.clone();
+        tampered
+            .data
+            .keys
+            .x25519_sphinx_rotation
+            .as_mut()
+            .unwrap()
+            .current_rotation_id = 43;
+        assert!(!tampered.verify_host_information())
+    }
+
     #[test]
     fn dummy_legacy_signed_host_verification() {
         let mut rng = rand_chacha::ChaCha20Rng::from_seed([0u8; 32]);
@@ -143,6 +190,7 @@ mod tests {
                 ed25519_identity: legacy_info.keys.ed25519.clone(),
                 x25519_sphinx: legacy_info.keys.x25519.clone(),
                 x25519_noise: "".to_string(),
+                x25519_sphinx_rotation: None,
             },
         };
 
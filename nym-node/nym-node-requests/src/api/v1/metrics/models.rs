@@ -33,6 +33,31 @@ pub struct MixingStats {
 
     // we know for sure we dropped those packets
     pub dropped_since_last_update: u64,
+
+    // packets rejected for being replays of previously received packets
+    #[serde(default)]
+    pub replayed_since_startup: u64,
+
+    #[serde(default)]
+    pub replayed_since_last_update: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InboxStats {
+    #[serde(with = "time::serde::rfc3339")]
+    pub last_pruning_time: OffsetDateTime,
+
+    // current state of the messages stored for the offline clients
+    pub stored_messages: u64,
+    pub stored_bytes: u64,
+    pub inboxes: u64,
+
+    // messages removed for being older than the maximum allowed age
+    pub expired_removed_since_startup: u64,
+
+    // messages removed for exceeding the per-client inbox size limit
+    pub excessive_removed_since_startup: u64,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
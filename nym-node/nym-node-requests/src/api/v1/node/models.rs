@@ -5,6 +5,7 @@ use celes::Country;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
+use time::OffsetDateTime;
 
 pub use crate::api::SignedHostInformation;
 pub use nym_bin_common::build_information::BinaryBuildInformationOwned;
@@ -65,6 +66,44 @@ pub struct HostKeys {
     /// Base58-encoded x25519 public key of this node used for the noise protocol.
     #[serde(default)]
     pub x25519_noise: String,
+
+    /// Sphinx keys used by this node if it periodically rotates them.
+    /// In that case `x25519_sphinx` is only accepted by the node for compatibility with clients
+    /// that are not aware of the key rotation.
+    // note: the field is skipped if empty so that the signatures of nodes without key rotation
+    // would remain valid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x25519_sphinx_rotation: Option<SphinxKeyRotation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SphinxKeyRotation {
+    /// Id of the current key rotation.
+    pub current_rotation_id: u32,
+
+    /// Base58-encoded x25519 sphinx key used by this node during the current rotation.
+    pub current_x25519_sphinx: String,
+
+    /// Base58-encoded x25519 sphinx key that is going to be used by this node during the next rotation.
+    pub next_x25519_sphinx: String,
+
+    /// Time at which the next key rotation begins.
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    #[cfg_attr(feature = "openapi", schema(value_type = String))]
+    pub next_rotation_start: OffsetDateTime,
+}
+
+impl SphinxKeyRotation {
+    /// Returns the key that should be used for packets created at the provided time.
+    pub fn key_at(&self, time: OffsetDateTime) -> &str {
+        if time >= self.next_rotation_start {
+            &self.next_x25519_sphinx
+        } else {
+            &self.current_x25519_sphinx
+        }
+    }
 }
 
 impl From<HostKeys> for LegacyHostKeys {
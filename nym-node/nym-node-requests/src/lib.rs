@@ -67,10 +67,12 @@ pub mod routes {
                 pub const MIXING: &str = "/mixing";
                 pub const VERLOC: &str = "/verloc";
                 pub const PROMETHEUS: &str = "/prometheus";
+                pub const INBOXES: &str = "/inboxes";
 
                 absolute_route!(mixing_absolute, metrics_absolute(), MIXING);
                 absolute_route!(verloc_absolute, metrics_absolute(), VERLOC);
                 absolute_route!(prometheus_absolute, metrics_absolute(), PROMETHEUS);
+                absolute_route!(inboxes_absolute, metrics_absolute(), INBOXES);
             }
 
             pub mod gateway {
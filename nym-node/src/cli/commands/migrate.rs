@@ -17,7 +17,9 @@ use nym_gateway::GatewayError;
 use nym_mixnode::MixnodeError;
 use nym_network_requester::{CustomGatewayDetails, GatewayDetails};
 use nym_node::config;
-use nym_node::config::entry_gateway::ZkNymTicketHandlerDebug;
+use nym_node::config::entry_gateway::{
+    ClientRateLimitingDebug, InboxRetentionDebug, ZkNymTicketHandlerDebug,
+};
 use nym_node::config::mixnode::DEFAULT_VERLOC_PORT;
 use nym_node::config::Config;
 use nym_node::config::{default_config_filepath, ConfigBuilder, NodeMode};
@@ -228,6 +230,7 @@ async fn migrate_mixnode(mut args: Args) -> Result<(), NymNodeError> {
                 packet_forwarding_maximum_backoff: cfg.debug.packet_forwarding_maximum_backoff,
                 initial_connection_timeout: cfg.debug.initial_connection_timeout,
                 maximum_connection_buffer_size: cfg.debug.maximum_connection_buffer_size,
+                replay_protection: cfg.debug.replay_protection,
                 ..Default::default()
             },
             ..Default::default()
@@ -388,11 +391,13 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
             bind_address: SocketAddr::new(ip, cfg.gateway.mix_port),
             nym_api_urls: cfg.gateway.nym_api_urls.clone(),
             nyxd_urls: cfg.gateway.nyxd_urls.clone(),
+            sphinx_key_rotation: Default::default(),
             debug: config::MixnetDebug {
                 packet_forwarding_initial_backoff: cfg.debug.packet_forwarding_initial_backoff,
                 packet_forwarding_maximum_backoff: cfg.debug.packet_forwarding_maximum_backoff,
                 initial_connection_timeout: cfg.debug.initial_connection_timeout,
                 maximum_connection_buffer_size: cfg.debug.maximum_connection_buffer_size,
+                replay_protection: cfg.debug.replay_protection,
                 ..Default::default()
             },
         }))
@@ -422,6 +427,18 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
                         maximum_time_between_redemption:
                             cfg.debug.zk_nym_tickets.maximum_time_between_redemption,
                     },
+                    inbox_retention: InboxRetentionDebug {
+                        max_message_age: cfg.debug.inbox_retention.max_message_age,
+                        max_client_bytes: cfg.debug.inbox_retention.max_client_bytes,
+                        pruning_interval: cfg.debug.inbox_retention.pruning_interval,
+                    },
+                    client_rate_limiting: ClientRateLimitingDebug {
+                        enabled: cfg.debug.client_rate_limiting.enabled,
+                        packets_per_second: cfg.debug.client_rate_limiting.packets_per_second,
+                        packet_burst: cfg.debug.client_rate_limiting.packet_burst,
+                        bytes_per_second: cfg.debug.client_rate_limiting.bytes_per_second,
+                        byte_burst: cfg.debug.client_rate_limiting.byte_burst,
+                    },
                 },
             },
         ))
@@ -436,6 +453,10 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
                         .unwrap_or(
                             config::ExitGatewayConfig::new_default(".").upstream_exit_policy_url,
                         ),
+                    exit_policy_refresh_interval: nr_cfg
+                        .as_ref()
+                        .map(|c| c.network_requester.exit_policy_refresh_interval)
+                        .unwrap_or(config::ExitGatewayConfig::DEFAULT_EXIT_POLICY_REFRESH_INTERVAL),
                     network_requester: config::exit_gateway::NetworkRequester {
                         debug: config::exit_gateway::NetworkRequesterDebug {
                             enabled: cfg.network_requester.enabled,
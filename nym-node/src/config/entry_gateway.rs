@@ -55,6 +55,10 @@ pub struct Debug {
     pub message_retrieval_limit: i64,
 
     pub zk_nym_tickets: ZkNymTicketHandlerDebug,
+
+    pub inbox_retention: InboxRetentionDebug,
+
+    pub client_rate_limiting: ClientRateLimitingDebug,
 }
 
 impl Debug {
@@ -66,6 +70,8 @@ impl Default for Debug {
         Debug {
             message_retrieval_limit: Self::DEFAULT_MESSAGE_RETRIEVAL_LIMIT,
             zk_nym_tickets: Default::default(),
+            inbox_retention: Default::default(),
+            client_rate_limiting: Default::default(),
         }
     }
 }
@@ -115,6 +121,77 @@ impl Default for ZkNymTicketHandlerDebug {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InboxRetentionDebug {
+    /// Specifies the maximum amount of time a message is going to be kept for an offline client
+    /// before it gets removed.
+    #[serde(with = "humantime_serde")]
+    pub max_message_age: Duration,
+
+    /// Specifies the maximum total size (in bytes) of messages stored for a single client.
+    /// Once exceeded, the oldest messages of that client are removed first.
+    pub max_client_bytes: u64,
+
+    /// Specifies how often the stored messages are checked against the above limits.
+    #[serde(with = "humantime_serde")]
+    pub pruning_interval: Duration,
+}
+
+impl InboxRetentionDebug {
+    pub const DEFAULT_MAX_MESSAGE_AGE: Duration = Duration::from_secs(7 * 86400);
+    pub const DEFAULT_MAX_CLIENT_BYTES: u64 = 64 * 1024 * 1024; // 64MB
+    pub const DEFAULT_PRUNING_INTERVAL: Duration = Duration::from_secs(600);
+}
+
+impl Default for InboxRetentionDebug {
+    fn default() -> Self {
+        InboxRetentionDebug {
+            max_message_age: Self::DEFAULT_MAX_MESSAGE_AGE,
+            max_client_bytes: Self::DEFAULT_MAX_CLIENT_BYTES,
+            pruning_interval: Self::DEFAULT_PRUNING_INTERVAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientRateLimitingDebug {
+    /// Specifies whether the per-client rate limiting of forwarded sphinx packets is enabled.
+    pub enabled: bool,
+
+    /// Specifies the sustained number of sphinx packets a single client is allowed to send per second.
+    pub packets_per_second: u32,
+
+    /// Specifies the maximum number of sphinx packets a single client is allowed to send in a burst.
+    pub packet_burst: u32,
+
+    /// Specifies the sustained number of bytes a single client is allowed to send per second.
+    pub bytes_per_second: u64,
+
+    /// Specifies the maximum number of bytes a single client is allowed to send in a burst.
+    pub byte_burst: u64,
+}
+
+impl ClientRateLimitingDebug {
+    pub const DEFAULT_PACKETS_PER_SECOND: u32 = 2000;
+    pub const DEFAULT_PACKET_BURST: u32 = 4000;
+    pub const DEFAULT_BYTES_PER_SECOND: u64 = 8 * 1024 * 1024; // 8MB
+    pub const DEFAULT_BYTE_BURST: u64 = 16 * 1024 * 1024; // 16MB
+}
+
+impl Default for ClientRateLimitingDebug {
+    fn default() -> Self {
+        ClientRateLimitingDebug {
+            enabled: true,
+            packets_per_second: Self::DEFAULT_PACKETS_PER_SECOND,
+            packet_burst: Self::DEFAULT_PACKET_BURST,
+            bytes_per_second: Self::DEFAULT_BYTES_PER_SECOND,
+            byte_burst: Self::DEFAULT_BYTE_BURST,
+        }
+    }
+}
+
 impl EntryGatewayConfig {
     pub fn new_default<P: AsRef<Path>>(data_dir: P) -> Self {
         EntryGatewayConfig {
@@ -12,6 +12,7 @@ use nym_gateway::node::{
 };
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::Duration;
 use url::Url;
 
 use super::{
@@ -31,6 +32,14 @@ pub struct ExitGatewayConfig {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     pub upstream_exit_policy_url: Url,
 
+    /// Specifies how often the exit policy should be refreshed from its upstream.
+    /// Setting it to zero disables the refreshing.
+    #[serde(
+        default = "ExitGatewayConfig::default_exit_policy_refresh_interval",
+        with = "humantime_serde"
+    )]
+    pub exit_policy_refresh_interval: Duration,
+
     pub network_requester: NetworkRequester,
 
     pub ip_packet_router: IpPacketRouter,
@@ -59,6 +68,12 @@ impl Default for Debug {
 }
 
 impl ExitGatewayConfig {
+    pub const DEFAULT_EXIT_POLICY_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+    fn default_exit_policy_refresh_interval() -> Duration {
+        Self::DEFAULT_EXIT_POLICY_REFRESH_INTERVAL
+    }
+
     pub fn new_default<P: AsRef<Path>>(data_dir: P) -> Self {
         #[allow(clippy::expect_used)]
         // SAFETY:
@@ -69,6 +84,7 @@ impl ExitGatewayConfig {
             upstream_exit_policy_url: mainnet::EXIT_POLICY_URL
                 .parse()
                 .expect("invalid default exit policy URL"),
+            exit_policy_refresh_interval: Self::DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
             network_requester: Default::default(),
             ip_packet_router: Default::default(),
             debug: Default::default(),
@@ -182,6 +198,7 @@ pub fn ephemeral_exit_gateway_config(
                 upstream_exit_policy_url: Some(
                     config.exit_gateway.upstream_exit_policy_url.clone(),
                 ),
+                exit_policy_refresh_interval: config.exit_gateway.exit_policy_refresh_interval,
             },
             storage_paths: nym_network_requester::config::NetworkRequesterPaths {
                 common_paths: config
@@ -224,6 +241,7 @@ pub fn ephemeral_exit_gateway_config(
                 upstream_exit_policy_url: Some(
                     config.exit_gateway.upstream_exit_policy_url.clone(),
                 ),
+                exit_policy_refresh_interval: config.exit_gateway.exit_policy_refresh_interval,
             },
             storage_paths: nym_ip_packet_router::config::IpPacketRouterPaths {
                 common_paths: config
@@ -100,6 +100,28 @@ pub fn ephemeral_gateway_config(
                     .zk_nym_tickets
                     .maximum_time_between_redemption,
             },
+            unsafe_disable_noise: config.mixnet.debug.unsafe_disable_noise,
+            replay_protection: config.mixnet.debug.replay_protection,
+            inbox_retention: nym_gateway::config::InboxRetentionDebug {
+                max_message_age: config.entry_gateway.debug.inbox_retention.max_message_age,
+                max_client_bytes: config.entry_gateway.debug.inbox_retention.max_client_bytes,
+                pruning_interval: config.entry_gateway.debug.inbox_retention.pruning_interval,
+            },
+            client_rate_limiting: nym_gateway::config::ClientRateLimitingDebug {
+                enabled: config.entry_gateway.debug.client_rate_limiting.enabled,
+                packets_per_second: config
+                    .entry_gateway
+                    .debug
+                    .client_rate_limiting
+                    .packets_per_second,
+                packet_burst: config.entry_gateway.debug.client_rate_limiting.packet_burst,
+                bytes_per_second: config
+                    .entry_gateway
+                    .debug
+                    .client_rate_limiting
+                    .bytes_per_second,
+                byte_burst: config.entry_gateway.debug.client_rate_limiting.byte_burst,
+            },
             ..Default::default()
         },
     ))
@@ -17,6 +17,7 @@ use nym_config::{
     must_get_home, parse_urls, read_config_from_toml_file, save_formatted_config_to_file,
     NymConfigTemplate, DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fmt::{Display, Formatter};
@@ -422,10 +423,53 @@ pub struct Mixnet {
     /// Addresses to nyxd which the node uses to interact with the nyx chain.
     pub nyxd_urls: Vec<Url>,
 
+    #[serde(default)]
+    pub sphinx_key_rotation: SphinxKeyRotation,
+
     #[serde(default)]
     pub debug: MixnetDebug,
 }
 
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct SphinxKeyRotation {
+    /// Specifies whether this node should periodically rotate its sphinx keys.
+    pub enabled: bool,
+
+    /// Specifies whether packets constructed for the long-term sphinx key should still be accepted
+    /// for the clients unaware of the rotation. This is a temporary measure that stops having any
+    /// effect once the transition period is over.
+    pub accept_long_term_key: bool,
+
+    /// Duration of a single rotation. Rotations are aligned to the multiples of this value
+    /// since the unix epoch, so that all nodes using the same interval would rotate their keys
+    /// at the same time.
+    #[serde(with = "humantime_serde")]
+    pub rotation_interval: Duration,
+
+    /// Period of time after each rotation during which the previous key is still accepted
+    /// so that clients with slightly outdated view of the network could still use this node.
+    #[serde(with = "humantime_serde")]
+    pub overlap: Duration,
+}
+
+impl SphinxKeyRotation {
+    const DEFAULT_ROTATION_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+    const DEFAULT_OVERLAP: Duration = Duration::from_secs(60 * 60);
+}
+
+impl Default for SphinxKeyRotation {
+    fn default() -> Self {
+        SphinxKeyRotation {
+            enabled: false,
+            accept_long_term_key: false,
+            rotation_interval: Self::DEFAULT_ROTATION_INTERVAL,
+            overlap: Self::DEFAULT_OVERLAP,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 #[serde(deny_unknown_fields)]
@@ -447,8 +491,11 @@ pub struct MixnetDebug {
     /// Maximum number of packets that can be stored waiting to get sent to a particular connection.
     pub maximum_connection_buffer_size: usize,
 
-    /// Specifies whether this node should **NOT** use noise protocol in the connections (currently not implemented)
+    /// Specifies whether this node should **NOT** use noise protocol in the connections
     pub unsafe_disable_noise: bool,
+
+    /// Settings of the filter used for detecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
 }
 
 impl MixnetDebug {
@@ -465,8 +512,8 @@ impl Default for MixnetDebug {
             packet_forwarding_maximum_backoff: Self::DEFAULT_PACKET_FORWARDING_MAXIMUM_BACKOFF,
             initial_connection_timeout: Self::DEFAULT_INITIAL_CONNECTION_TIMEOUT,
             maximum_connection_buffer_size: Self::DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE,
-            // to be changed by @SW once the implementation is there
-            unsafe_disable_noise: true,
+            unsafe_disable_noise: false,
+            replay_protection: Default::default(),
         }
     }
 }
@@ -494,6 +541,7 @@ impl Default for Mixnet {
             bind_address: SocketAddr::new(inaddr_any(), DEFAULT_MIXNET_PORT),
             nym_api_urls,
             nyxd_urls,
+            sphinx_key_rotation: Default::default(),
             debug: Default::default(),
         }
     }
@@ -994,6 +994,7 @@ pub async fn try_upgrade_config_v3<P: AsRef<Path>>(
             bind_address: old_cfg.mixnet.bind_address,
             nym_api_urls: old_cfg.mixnet.nym_api_urls,
             nyxd_urls: old_cfg.mixnet.nyxd_urls,
+            sphinx_key_rotation: Default::default(),
             debug: MixnetDebug {
                 packet_forwarding_initial_backoff: old_cfg
                     .mixnet
@@ -1006,6 +1007,7 @@ pub async fn try_upgrade_config_v3<P: AsRef<Path>>(
                 initial_connection_timeout: old_cfg.mixnet.debug.initial_connection_timeout,
                 maximum_connection_buffer_size: old_cfg.mixnet.debug.maximum_connection_buffer_size,
                 unsafe_disable_noise: old_cfg.mixnet.debug.unsafe_disable_noise,
+                replay_protection: Default::default(),
             },
         },
         storage_paths: NymNodePaths {
@@ -1131,6 +1133,8 @@ pub async fn try_upgrade_config_v3<P: AsRef<Path>>(
                 message_retrieval_limit: old_cfg.entry_gateway.debug.message_retrieval_limit,
                 // \/ ADDED
                 zk_nym_tickets: Default::default(),
+                inbox_retention: Default::default(),
+                client_rate_limiting: Default::default(),
             },
         },
         exit_gateway: ExitGatewayConfig {
@@ -1250,6 +1254,8 @@ pub async fn try_upgrade_config_v3<P: AsRef<Path>>(
             },
             open_proxy: old_cfg.exit_gateway.open_proxy,
             upstream_exit_policy_url: old_cfg.exit_gateway.upstream_exit_policy_url,
+            // \/ ADDED
+            exit_policy_refresh_interval: ExitGatewayConfig::DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
             network_requester: NetworkRequester {
                 debug: NetworkRequesterDebug {
                     enabled: old_cfg.exit_gateway.network_requester.debug.enabled,
@@ -16,6 +16,7 @@ pub const DEFAULT_ED25519_PRIVATE_IDENTITY_KEY_FILENAME: &str = "ed25519_identit
 pub const DEFAULT_ED25519_PUBLIC_IDENTITY_KEY_FILENAME: &str = "ed25519_identity.pub";
 pub const DEFAULT_X25519_PRIVATE_SPHINX_KEY_FILENAME: &str = "x25519_sphinx";
 pub const DEFAULT_X25519_PUBLIC_SPHINX_KEY_FILENAME: &str = "x25519_sphinx.pub";
+pub const DEFAULT_SPHINX_KEY_ROTATIONS_DIR: &str = "sphinx_key_rotations";
 pub const DEFAULT_X25519_PRIVATE_NOISE_KEY_FILENAME: &str = "x25519_noise";
 pub const DEFAULT_X25519_PUBLIC_NOISE_KEY_FILENAME: &str = "x25519_noise.pub";
 pub const DEFAULT_NYMNODE_DESCRIPTION_FILENAME: &str = "description.toml";
@@ -128,6 +129,29 @@ impl KeysPaths {
         )
     }
 
+    /// Directory containing the rotating sphinx keys. It lives alongside the long-term sphinx key.
+    pub fn x25519_sphinx_rotations_directory(&self) -> PathBuf {
+        self.private_x25519_sphinx_key_file
+            .parent()
+            .map(|parent| parent.join(DEFAULT_SPHINX_KEY_ROTATIONS_DIR))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SPHINX_KEY_ROTATIONS_DIR))
+    }
+
+    pub fn x25519_sphinx_rotation_storage_paths(
+        &self,
+        rotation_id: u32,
+    ) -> nym_pemstore::KeyPairPath {
+        let directory = self.x25519_sphinx_rotations_directory();
+        nym_pemstore::KeyPairPath::new(
+            directory.join(format!(
+                "{DEFAULT_X25519_PRIVATE_SPHINX_KEY_FILENAME}_{rotation_id}"
+            )),
+            directory.join(format!(
+                "{DEFAULT_X25519_PRIVATE_SPHINX_KEY_FILENAME}_{rotation_id}.pub"
+            )),
+        )
+    }
+
     pub fn x25519_noise_storage_paths(&self) -> nym_pemstore::KeyPairPath {
         nym_pemstore::KeyPairPath::new(
             &self.private_x25519_noise_key_file,
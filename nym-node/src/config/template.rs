@@ -51,6 +51,21 @@ nyxd_urls = [
     {{#each mixnet.nyxd_urls }}'{{this}}',{{/each}}
 ]
 
+[mixnet.sphinx_key_rotation]
+# Specifies whether this node should periodically rotate its sphinx keys.
+enabled = {{ mixnet.sphinx_key_rotation.enabled }}
+
+# Specifies whether packets constructed for the long-term sphinx key should still be accepted
+# for the clients unaware of the rotation. It stops having any effect once the transition period is over.
+accept_long_term_key = {{ mixnet.sphinx_key_rotation.accept_long_term_key }}
+
+# Duration of a single rotation. Rotations are aligned to the multiples of this value
+# since the unix epoch.
+rotation_interval = '{{ mixnet.sphinx_key_rotation.rotation_interval }}'
+
+# Period of time after each rotation during which the previous key is still accepted.
+overlap = '{{ mixnet.sphinx_key_rotation.overlap }}'
+
 # Storage paths to persistent nym-node data, such as its long term keys.
 [storage_paths]
 
@@ -213,6 +228,10 @@ open_proxy = {{ exit_gateway.open_proxy }}
 # Specifies the custom url for an upstream source of the exit policy used by this node.
 upstream_exit_policy_url = '{{ exit_gateway.upstream_exit_policy_url }}'
 
+# Specifies how often the exit policy should be refreshed from its upstream.
+# Setting it to zero disables the refreshing.
+exit_policy_refresh_interval = '{{ exit_gateway.exit_policy_refresh_interval }}'
+
 [exit_gateway.network_requester]
 # currently empty (there are some debug options one might want to configure)
 
@@ -8,10 +8,13 @@ use crate::node::helpers::{
     store_x25519_sphinx_keypair, DisplayDetails,
 };
 use crate::node::http::{sign_host_details, system_info::get_system_info};
+use crate::node::sphinx_key_rotation::SphinxKeyRotator;
 use nym_bin_common::bin_info_owned;
 use nym_crypto::asymmetric::{ed25519, x25519};
 use nym_gateway::Gateway;
 use nym_mixnode::MixNode;
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtection;
+use nym_mixnode_common::packet_processor::sphinx_keys::SphinxKeys;
 use nym_network_requester::{
     set_active_gateway, setup_fs_gateways_storage, store_gateway_details, CustomGatewayDetails,
     GatewayDetails, GatewayRegistration,
@@ -26,7 +29,8 @@ use nym_node::config::{
 use nym_node::error::{EntryGatewayError, ExitGatewayError, MixnodeError, NymNodeError};
 use nym_node_http_api::api::api_requests;
 use nym_node_http_api::api::api_requests::v1::node::models::NodeDescription;
-use nym_node_http_api::state::metrics::{SharedMixingStats, SharedVerlocStats};
+use nym_node_http_api::router::api::v1::node::host_information::SharedHostInformation;
+use nym_node_http_api::state::metrics::{SharedInboxStats, SharedMixingStats, SharedVerlocStats};
 use nym_node_http_api::state::AppState;
 use nym_node_http_api::{NymNodeHTTPServer, NymNodeRouter};
 use nym_sphinx_acknowledgements::AckKey;
@@ -47,6 +51,7 @@ pub mod bonding_information;
 pub mod description;
 pub mod helpers;
 pub(crate) mod http;
+pub(crate) mod sphinx_key_rotation;
 
 pub struct MixnodeData {
     mixing_stats: SharedMixingStats,
@@ -319,6 +324,7 @@ pub(crate) struct NymNode {
 
     // TODO: currently we're only making measurements in 'mixnode' mode; this should be changed
     verloc_stats: SharedVerlocStats,
+    inbox_stats: SharedInboxStats,
 
     #[allow(dead_code)]
     mixnode: MixnodeData,
@@ -333,8 +339,16 @@ pub(crate) struct NymNode {
     ed25519_identity_keys: Arc<ed25519::KeyPair>,
     x25519_sphinx_keys: Arc<x25519::KeyPair>,
 
-    // to be used when noise is integrated
-    #[allow(dead_code)]
+    /// Sphinx keys used for processing received packets.
+    /// Unless the key rotation is enabled, it only consists of the long-term `x25519_sphinx_keys`.
+    sphinx_keys: SphinxKeys,
+
+    /// Filter used for detecting replayed packets, shared with the sphinx key rotator
+    /// so that it would be rotated whenever any of the keys retires.
+    replay_protection: Option<ReplayProtection>,
+
+    /// Keys used for establishing noise connections with other nodes. Their public part is announced
+    /// alongside the host information (unless noise is disabled).
     x25519_noise_keys: Arc<x25519::KeyPair>,
 }
 
@@ -455,18 +469,23 @@ impl NymNode {
 
     pub(crate) async fn new(config: Config) -> Result<Self, NymNodeError> {
         let wireguard_data = WireguardData::new(&config.wireguard)?;
+        let x25519_sphinx_keys =
+            load_x25519_sphinx_keypair(config.storage_paths.keys.x25519_sphinx_storage_paths())?;
         Ok(NymNode {
             ed25519_identity_keys: Arc::new(load_ed25519_identity_keypair(
                 config.storage_paths.keys.ed25519_identity_storage_paths(),
             )?),
-            x25519_sphinx_keys: Arc::new(load_x25519_sphinx_keypair(
-                config.storage_paths.keys.x25519_sphinx_storage_paths(),
-            )?),
+            sphinx_keys: SphinxKeys::new(x25519_sphinx_keys.private_key().into()),
+            replay_protection: ReplayProtection::new_if_enabled(
+                config.mixnet.debug.replay_protection,
+            ),
+            x25519_sphinx_keys: Arc::new(x25519_sphinx_keys),
             x25519_noise_keys: Arc::new(load_x25519_noise_keypair(
                 config.storage_paths.keys.x25519_noise_storage_paths(),
             )?),
             description: load_node_description(&config.storage_paths.description)?,
             verloc_stats: Default::default(),
+            inbox_stats: Default::default(),
             mixnode: MixnodeData::new(&config.mixnode)?,
             entry_gateway: EntryGatewayData::new(&config.entry_gateway).await?,
             exit_gateway: ExitGatewayData::new(&config.exit_gateway).await?,
@@ -555,6 +574,11 @@ impl NymNode {
         mixnode.set_task_client(task_client);
         mixnode.set_mixing_stats(self.mixnode.mixing_stats.clone());
         mixnode.set_verloc_stats(self.verloc_stats.clone());
+        mixnode.set_sphinx_keys(self.sphinx_keys.clone());
+        mixnode.set_noise_keys(self.x25519_noise_keys.clone());
+        if let Some(replay_protection) = &self.replay_protection {
+            mixnode.set_replay_protection(replay_protection.clone());
+        }
 
         tokio::spawn(async move {
             if let Err(err) = mixnode.run().await {
@@ -580,6 +604,13 @@ impl NymNode {
         );
         entry_gateway.disable_http_server();
         entry_gateway.set_task_client(task_client);
+        entry_gateway.set_inbox_stats(self.inbox_stats.clone());
+        entry_gateway.set_mixing_stats(self.mixnode.mixing_stats.clone());
+        entry_gateway.set_sphinx_keys(self.sphinx_keys.clone());
+        entry_gateway.set_noise_keys(self.x25519_noise_keys.clone());
+        if let Some(replay_protection) = &self.replay_protection {
+            entry_gateway.set_replay_protection(replay_protection.clone());
+        }
         #[cfg(all(feature = "wireguard", target_os = "linux"))]
         entry_gateway.set_wireguard_data(self.wireguard.into());
 
@@ -608,6 +639,13 @@ impl NymNode {
         );
         exit_gateway.disable_http_server();
         exit_gateway.set_task_client(task_client);
+        exit_gateway.set_inbox_stats(self.inbox_stats.clone());
+        exit_gateway.set_mixing_stats(self.mixnode.mixing_stats.clone());
+        exit_gateway.set_sphinx_keys(self.sphinx_keys.clone());
+        exit_gateway.set_noise_keys(self.x25519_noise_keys.clone());
+        if let Some(replay_protection) = &self.replay_protection {
+            exit_gateway.set_replay_protection(replay_protection.clone());
+        }
         #[cfg(all(feature = "wireguard", target_os = "linux"))]
         exit_gateway.set_wireguard_data(self.wireguard.into());
 
@@ -619,14 +657,29 @@ impl NymNode {
         Ok(())
     }
 
-    pub(crate) async fn build_http_server(&self) -> Result<NymNodeHTTPServer, NymNodeError> {
-        let host_details = sign_host_details(
-            &self.config,
-            self.x25519_sphinx_keys.public_key(),
-            self.x25519_noise_keys.public_key(),
-            &self.ed25519_identity_keys,
+    fn setup_sphinx_key_rotation(&self) -> Result<Option<SphinxKeyRotator>, NymNodeError> {
+        if !self.config.mixnet.sphinx_key_rotation.enabled {
+            return Ok(None);
+        }
+
+        let rotator = SphinxKeyRotator::new(
+            self.config.clone(),
+            self.ed25519_identity_keys.clone(),
+            self.x25519_noise_keys.clone(),
+            self.x25519_sphinx_keys.clone(),
+            self.sphinx_keys.clone(),
         )?;
 
+        Ok(Some(match &self.replay_protection {
+            Some(replay_protection) => rotator.with_replay_protection(replay_protection.clone()),
+            None => rotator,
+        }))
+    }
+
+    pub(crate) async fn build_http_server(
+        &self,
+        host_details: SharedHostInformation,
+    ) -> Result<NymNodeHTTPServer, NymNodeError> {
         let auxiliary_details = api_requests::v1::node::models::AuxiliaryDetails {
             location: self.config.host.location,
             accepted_operator_terms_and_conditions: self.accepted_operator_terms_and_conditions,
@@ -722,6 +775,7 @@ impl NymNode {
         let app_state = AppState::new()
             .with_mixing_stats(self.mixnode.mixing_stats.clone())
             .with_verloc_stats(self.verloc_stats.clone())
+            .with_inbox_stats(self.inbox_stats.clone())
             .with_metrics_key(self.config.http.access_token.clone());
 
         Ok(NymNodeRouter::new(config, Some(app_state))
@@ -731,8 +785,20 @@ impl NymNode {
 
     pub(crate) async fn run(self) -> Result<(), NymNodeError> {
         let mut task_manager = TaskManager::default().named("NymNode");
+
+        let sphinx_key_rotator = self.setup_sphinx_key_rotation()?;
+        let host_details = SharedHostInformation::new(sign_host_details(
+            &self.config,
+            self.x25519_sphinx_keys.public_key(),
+            self.x25519_noise_keys.public_key(),
+            &self.ed25519_identity_keys,
+            sphinx_key_rotator
+                .as_ref()
+                .map(|rotator| rotator.announced_rotation()),
+        )?);
+
         let http_server = self
-            .build_http_server()
+            .build_http_server(host_details.clone())
             .await?
             .with_task_client(task_manager.subscribe_named("http-server"));
         let bind_address = self.config.http.bind_address;
@@ -743,6 +809,15 @@ impl NymNode {
             }
         });
 
+        if let Some(sphinx_key_rotator) = sphinx_key_rotator {
+            let shutdown = task_manager.subscribe_named("sphinx-key-rotator");
+            tokio::spawn(
+                sphinx_key_rotator
+                    .with_host_information(host_details)
+                    .run(shutdown),
+            );
+        }
+
         match self.config.mode {
             NodeMode::Mixnode => {
                 self.start_mixnode(task_manager.subscribe_named("mixnode"))?;
@@ -0,0 +1,404 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::helpers::{load_keypair, store_keypair};
+use crate::node::http::sign_host_details;
+use nym_crypto::asymmetric::{ed25519, x25519};
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtection;
+use nym_mixnode_common::packet_processor::sphinx_keys::SphinxKeys;
+use nym_node::config::persistence::{KeysPaths, DEFAULT_X25519_PRIVATE_SPHINX_KEY_FILENAME};
+use nym_node::config::Config;
+use nym_node::error::NymNodeError;
+use nym_node_http_api::api::api_requests::v1::node::models::SphinxKeyRotation as AnnouncedRotation;
+use nym_node_http_api::router::api::v1::node::host_information::SharedHostInformation;
+use nym_task::TaskClient;
+use rand::rngs::OsRng;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tracing::{debug, info, trace, warn};
+
+/// Unix timestamp (2027-04-01T00:00:00Z) after which the long-term sphinx key is no longer accepted
+/// as a fallback, regardless of the configuration, as all clients are expected to be aware of the rotation by then.
+const LONG_TERM_KEY_FALLBACK_SUNSET: i64 = 1806537600;
+
+fn long_term_key_fallback_active(now: OffsetDateTime) -> bool {
+    now.unix_timestamp() < LONG_TERM_KEY_FALLBACK_SUNSET
+}
+
+/// Keeps track of the epoch-based sphinx keys of this node.
+///
+/// Every rotation is identified by `unix_timestamp / rotation_interval`, so that all nodes using the same
+/// interval switch their keys at the same time. At any given point the node accepts packets constructed
+/// for the current key, the next key (which is announced ahead of time), the previous key (only during the
+/// overlap period right after a rotation) and, if explicitly enabled and until the sunset, the long-term key
+/// for the clients unaware of the rotation.
+pub(crate) struct SphinxKeyRotator {
+    config: Config,
+    rotation_interval: Duration,
+    overlap: Duration,
+    accept_long_term_key: bool,
+
+    ed25519_identity_keys: Arc<ed25519::KeyPair>,
+    x25519_noise_keys: Arc<x25519::KeyPair>,
+    long_term_sphinx_keys: Arc<x25519::KeyPair>,
+
+    current_rotation_id: u32,
+    current: x25519::KeyPair,
+    next: x25519::KeyPair,
+    previous: Option<x25519::KeyPair>,
+
+    sphinx_keys: SphinxKeys,
+    replay_protection: Option<ReplayProtection>,
+    host_information: Option<SharedHostInformation>,
+}
+
+impl SphinxKeyRotator {
+    pub(crate) fn new(
+        config: Config,
+        ed25519_identity_keys: Arc<ed25519::KeyPair>,
+        x25519_noise_keys: Arc<x25519::KeyPair>,
+        long_term_sphinx_keys: Arc<x25519::KeyPair>,
+        sphinx_keys: SphinxKeys,
+    ) -> Result<Self, NymNodeError> {
+        let rotation_config = &config.mixnet.sphinx_key_rotation;
+
+        // make sure we don't end up dividing by zero
+        let rotation_interval = rotation_config
+            .rotation_interval
+            .max(Duration::from_secs(1));
+        let overlap = rotation_config.overlap.min(rotation_interval);
+        let accept_long_term_key = rotation_config.accept_long_term_key;
+
+        let now = OffsetDateTime::now_utc();
+        if accept_long_term_key && !long_term_key_fallback_active(now) {
+            warn!("the transition period for the sphinx key rotation is over - the long-term sphinx key is no longer going to be accepted");
+        }
+
+        let current_rotation_id = rotation_id(rotation_interval, now);
+        let keys_paths = &config.storage_paths.keys;
+
+        let current = load_or_generate_rotation_key(keys_paths, current_rotation_id)?;
+        let next = load_or_generate_rotation_key(keys_paths, current_rotation_id + 1)?;
+        let previous = if now < rotation_start(rotation_interval, current_rotation_id) + overlap {
+            load_rotation_key(keys_paths, current_rotation_id.saturating_sub(1))
+        } else {
+            None
+        };
+
+        let rotator = SphinxKeyRotator {
+            config,
+            rotation_interval,
+            overlap,
+            accept_long_term_key,
+            ed25519_identity_keys,
+            x25519_noise_keys,
+            long_term_sphinx_keys,
+            current_rotation_id,
+            current,
+            next,
+            previous,
+            sphinx_keys,
+            replay_protection: None,
+            host_information: None,
+        };
+        rotator.update_sphinx_keys();
+        rotator.remove_retired_keys();
+
+        info!("using sphinx key of rotation {current_rotation_id}");
+        Ok(rotator)
+    }
+
+    /// Sets the host information that is going to get re-signed after every rotation
+    /// so that the newly announced keys would be exposed via the http API.
+    #[must_use]
+    pub(crate) fn with_host_information(mut self, host_information: SharedHostInformation) -> Self {
+        self.host_information = Some(host_information);
+        self
+    }
+
+    /// Sets the replay protection filter that is going to get rotated whenever any of the sphinx keys retires,
+    /// so that the replay tags would be remembered for as long as the keys they were processed with.
+    #[must_use]
+    pub(crate) fn with_replay_protection(mut self, replay_protection: ReplayProtection) -> Self {
+        self.replay_protection = Some(replay_protection);
+        self
+    }
+
+    /// Information about the current and the upcoming sphinx key to be included in the signed host information.
+    pub(crate) fn announced_rotation(&self) -> AnnouncedRotation {
+        AnnouncedRotation {
+            current_rotation_id: self.current_rotation_id,
+            current_x25519_sphinx: self.current.public_key().to_base58_string(),
+            next_x25519_sphinx: self.next.public_key().to_base58_string(),
+            next_rotation_start: rotation_start(
+                self.rotation_interval,
+                self.current_rotation_id + 1,
+            ),
+        }
+    }
+
+    fn keys_paths(&self) -> &KeysPaths {
+        &self.config.storage_paths.keys
+    }
+
+    fn accepts_long_term_key(&self) -> bool {
+        self.accept_long_term_key && long_term_key_fallback_active(OffsetDateTime::now_utc())
+    }
+
+    fn update_sphinx_keys(&self) {
+        let mut additional = vec![self.next.private_key().into()];
+        if let Some(previous) = &self.previous {
+            additional.push(previous.private_key().into());
+        }
+        if self.accepts_long_term_key() {
+            additional.push(self.long_term_sphinx_keys.private_key().into());
+        }
+
+        self.sphinx_keys
+            .update(self.current.private_key().into(), additional)
+    }
+
+    /// Starts a new generation of the replay protection filter as the tags of packets processed
+    /// with the retired keys no longer have to be remembered.
+    fn on_keys_retired(&self) {
+        let Some(replay_protection) = &self.replay_protection else {
+            return;
+        };
+
+        // the long-term key never retires (until the sunset), so its tags have to be kept
+        // for as long as possible, i.e. until the filter reaches its capacity
+        if self.accepts_long_term_key() {
+            debug!("not rotating the replay protection filter as the long-term sphinx key is still accepted");
+            return;
+        }
+        replay_protection.rotate()
+    }
+
+    /// Removes all rotation keys from the storage that are no longer going to be used.
+    fn remove_retired_keys(&self) {
+        let directory = self.keys_paths().x25519_sphinx_rotations_directory();
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(err) => {
+                debug!("could not read the sphinx key rotations directory: {err}");
+                return;
+            }
+        };
+
+        let retained_from = if self.previous.is_some() {
+            self.current_rotation_id.saturating_sub(1)
+        } else {
+            self.current_rotation_id
+        };
+
+        for entry in entries.flatten() {
+            let Some(rotation_id) = parse_rotation_id(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            if rotation_id >= retained_from {
+                continue;
+            }
+
+            trace!(
+                "removing retired sphinx key file {}",
+                entry.path().display()
+            );
+            if let Err(err) = fs::remove_file(entry.path()) {
+                warn!(
+                    "failed to remove retired sphinx key file {}: {err}",
+                    entry.path().display()
+                )
+            }
+        }
+    }
+
+    async fn update_host_information(&self) -> Result<(), NymNodeError> {
+        let Some(host_information) = &self.host_information else {
+            return Ok(());
+        };
+
+        let signed = sign_host_details(
+            &self.config,
+            self.long_term_sphinx_keys.public_key(),
+            self.x25519_noise_keys.public_key(),
+            &self.ed25519_identity_keys,
+            Some(self.announced_rotation()),
+        )?;
+        host_information.update(signed).await;
+        Ok(())
+    }
+
+    /// Returns the time at which the rotator should wake up next, i.e. either the beginning of the next
+    /// rotation or the end of the current overlap period.
+    fn next_event(&self, now: OffsetDateTime) -> OffsetDateTime {
+        let next_rotation = rotation_start(self.rotation_interval, self.current_rotation_id + 1);
+        if self.previous.is_some() {
+            let overlap_end =
+                rotation_start(self.rotation_interval, self.current_rotation_id) + self.overlap;
+            if overlap_end > now {
+                return overlap_end.min(next_rotation);
+            }
+        }
+        next_rotation
+    }
+
+    async fn on_tick(&mut self) -> Result<(), NymNodeError> {
+        let now = OffsetDateTime::now_utc();
+        let rotation_id = rotation_id(self.rotation_interval, now);
+
+        if rotation_id != self.current_rotation_id {
+            let keys_paths = &self.config.storage_paths.keys;
+            let next = load_or_generate_rotation_key(keys_paths, rotation_id + 1)?;
+
+            if rotation_id == self.current_rotation_id + 1 {
+                let current = std::mem::replace(&mut self.next, next);
+                let retired = self
+                    .previous
+                    .replace(std::mem::replace(&mut self.current, current));
+                if retired.is_some() {
+                    self.on_keys_retired()
+                }
+            } else {
+                // we must have missed some rotations (e.g. the machine was suspended),
+                // so there's no point in keeping any of the old keys around
+                warn!(
+                    "skipped sphinx key rotations between {} and {rotation_id}",
+                    self.current_rotation_id
+                );
+                self.current = load_or_generate_rotation_key(keys_paths, rotation_id)?;
+                self.next = next;
+                self.previous = None;
+                self.on_keys_retired();
+            }
+            self.current_rotation_id = rotation_id;
+            info!("rotated the sphinx key. current rotation: {rotation_id}");
+
+            self.update_sphinx_keys();
+            self.update_host_information().await?;
+        }
+
+        if self.previous.is_some()
+            && now
+                >= rotation_start(self.rotation_interval, self.current_rotation_id) + self.overlap
+        {
+            debug!(
+                "the overlap period has finished - the previous sphinx key is no longer accepted"
+            );
+            self.previous = None;
+            self.update_sphinx_keys();
+            self.on_keys_retired();
+        }
+
+        self.remove_retired_keys();
+        Ok(())
+    }
+
+    pub(crate) async fn run(mut self, mut shutdown: TaskClient) {
+        while !shutdown.is_shutdown() {
+            let now = OffsetDateTime::now_utc();
+            let wait = (self.next_event(now) - now)
+                .try_into()
+                .unwrap_or(Duration::ZERO);
+
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("SphinxKeyRotator: Received shutdown");
+                }
+                _ = tokio::time::sleep(wait) => {
+                    if let Err(err) = self.on_tick().await {
+                        warn!("failed to rotate the sphinx keys: {err}");
+                        // don't keep on retrying in a tight loop
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                    }
+                }
+            }
+        }
+        trace!("SphinxKeyRotator: Exiting");
+    }
+}
+
+fn rotation_id(rotation_interval: Duration, time: OffsetDateTime) -> u32 {
+    let timestamp = time.unix_timestamp().max(0) as u64;
+    (timestamp / rotation_interval.as_secs()) as u32
+}
+
+fn rotation_start(rotation_interval: Duration, rotation_id: u32) -> OffsetDateTime {
+    let timestamp = rotation_id as i64 * rotation_interval.as_secs() as i64;
+    // SAFETY: the timestamp is derived from the current time (and a u32 id), so it's well within the valid range
+    #[allow(clippy::unwrap_used)]
+    OffsetDateTime::from_unix_timestamp(timestamp).unwrap()
+}
+
+fn parse_rotation_id(file_name: &str) -> Option<u32> {
+    let id = file_name
+        .strip_prefix(DEFAULT_X25519_PRIVATE_SPHINX_KEY_FILENAME)?
+        .strip_prefix('_')?;
+    id.strip_suffix(".pub").unwrap_or(id).parse().ok()
+}
+
+fn rotation_key_name(rotation_id: u32) -> String {
+    format!("x25519-sphinx-rotation-{rotation_id}")
+}
+
+fn load_rotation_key(keys_paths: &KeysPaths, rotation_id: u32) -> Option<x25519::KeyPair> {
+    let paths = keys_paths.x25519_sphinx_rotation_storage_paths(rotation_id);
+    load_keypair(paths, rotation_key_name(rotation_id)).ok()
+}
+
+fn load_or_generate_rotation_key(
+    keys_paths: &KeysPaths,
+    rotation_id: u32,
+) -> Result<x25519::KeyPair, NymNodeError> {
+    if let Some(keys) = load_rotation_key(keys_paths, rotation_id) {
+        return Ok(keys);
+    }
+
+    debug!("generating new sphinx key for rotation {rotation_id}");
+    let keys = x25519::KeyPair::new(&mut OsRng);
+    store_keypair(
+        &keys,
+        keys_paths.x25519_sphinx_rotation_storage_paths(rotation_id),
+        rotation_key_name(rotation_id),
+    )?;
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations_are_aligned_to_the_interval() {
+        let interval = Duration::from_secs(24 * 60 * 60);
+        let time = OffsetDateTime::from_unix_timestamp(1714521600 + 3600).unwrap();
+
+        let id = rotation_id(interval, time);
+        assert_eq!(id, 19844);
+        assert_eq!(rotation_start(interval, id).unix_timestamp(), 1714521600);
+        assert_eq!(
+            rotation_id(interval, rotation_start(interval, id + 1)),
+            id + 1
+        );
+    }
+
+    #[test]
+    fn long_term_key_fallback_has_a_sunset() {
+        let sunset = OffsetDateTime::from_unix_timestamp(LONG_TERM_KEY_FALLBACK_SUNSET).unwrap();
+        assert!(long_term_key_fallback_active(
+            sunset - Duration::from_secs(1)
+        ));
+        assert!(!long_term_key_fallback_active(sunset));
+    }
+
+    #[test]
+    fn parsing_rotation_key_file_names() {
+        assert_eq!(parse_rotation_id("x25519_sphinx_42"), Some(42));
+        assert_eq!(parse_rotation_id("x25519_sphinx_42.pub"), Some(42));
+        assert_eq!(parse_rotation_id("x25519_sphinx"), None);
+        assert_eq!(parse_rotation_id("x25519_sphinx.pub"), None);
+        assert_eq!(parse_rotation_id("x25519_noise_42"), None);
+    }
+}
@@ -3,6 +3,7 @@ use nym_crypto::asymmetric::identity::Ed25519RecoveryError;
 use nym_types::error::TypesError;
 use nym_validator_client::nym_api::error::NymAPIError;
 use nym_validator_client::signing::direct_wallet::DirectSecp256k1HdWalletError;
+use nym_validator_client::signing::ledger::LedgerSignerError;
 use nym_validator_client::{nyxd::error::NyxdError, ValidatorClientError};
 use nym_wallet_types::network::Network;
 use serde::{Serialize, Serializer};
@@ -145,6 +146,12 @@ pub enum BackendError {
         source: DirectSecp256k1HdWalletError,
     },
 
+    #[error(transparent)]
+    LedgerError {
+        #[from]
+        source: LedgerSignerError,
+    },
+
     #[error("received unexpected signing algorithm: {received:?}. Expected to get {expected:?}")]
     UnexpectedSigningAlgorithm {
         received: SigningAlgorithm,
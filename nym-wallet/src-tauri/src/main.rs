@@ -24,6 +24,7 @@ mod menu;
 mod network_config;
 mod operations;
 mod platform_constants;
+mod signer;
 mod state;
 mod utils;
 mod wallet_storage;
@@ -39,6 +40,7 @@ fn main() {
             app::version::check_version,
             mixnet::account::add_account_for_password,
             mixnet::account::archive_wallet_file,
+            mixnet::account::connect_with_ledger,
             mixnet::account::connect_with_mnemonic,
             mixnet::account::create_new_mnemonic,
             mixnet::account::create_password,
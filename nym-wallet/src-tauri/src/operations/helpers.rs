@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::BackendError;
+use crate::signer::WalletSigningClient;
 use async_trait::async_trait;
 use cosmwasm_std::Addr;
 use nym_contracts_common::signing::{
@@ -15,7 +16,6 @@ use nym_mixnet_contract_common::{
 use nym_validator_client::nyxd::contract_traits::MixnetQueryClient;
 use nym_validator_client::nyxd::error::NyxdError;
 use nym_validator_client::nyxd::Coin;
-use nym_validator_client::DirectSigningHttpRpcValidatorClient;
 
 // define this as a separate trait for mocking purposes
 #[async_trait]
@@ -25,7 +25,7 @@ pub(crate) trait AddressAndNonceProvider {
 }
 
 #[async_trait]
-impl AddressAndNonceProvider for DirectSigningHttpRpcValidatorClient {
+impl AddressAndNonceProvider for WalletSigningClient {
     async fn get_signing_nonce(&self) -> Result<Nonce, NyxdError> {
         self.nyxd.get_signing_nonce(&self.nyxd.address()).await
     }
@@ -1,6 +1,7 @@
 use crate::config::{Config, CUSTOM_SIMULATED_GAS_MULTIPLIER};
 use crate::error::BackendError;
 use crate::network_config;
+use crate::signer::{WalletSigner, WalletSigningClient};
 use crate::state::{WalletAccountIds, WalletState};
 use crate::wallet_storage::{self, UserPassword, DEFAULT_LOGIN_ID};
 use bip39::rand::seq::SliceRandom;
@@ -9,10 +10,11 @@ use cosmrs::bip32::DerivationPath;
 use itertools::Itertools;
 use nym_config::defaults::{NymNetworkDetails, COSMOS_DERIVATION_PATH};
 use nym_types::account::{Account, AccountEntry, Balance};
+use nym_validator_client::nyxd::error::NyxdError;
 use nym_validator_client::nyxd::CosmWasmClient;
 use nym_validator_client::signing::direct_wallet::DirectSecp256k1HdWallet;
+use nym_validator_client::signing::ledger::LedgerSigner;
 use nym_validator_client::signing::AccountData;
-use nym_validator_client::DirectSigningHttpRpcValidatorClient;
 use nym_wallet_types::network::Network as WalletNetwork;
 use std::collections::HashMap;
 use strum::IntoEnumIterator;
@@ -26,6 +28,44 @@ pub async fn connect_with_mnemonic(
     _connect_with_mnemonic(mnemonic, state).await
 }
 
+/// Connects to the Ledger device and uses its account with the specified index,
+/// i.e. `m/44'/118'/{account_index}'/0/0`, for signing all transactions.
+#[tauri::command]
+pub async fn connect_with_ledger(
+    account_index: u32,
+    state: tauri::State<'_, WalletState>,
+) -> Result<Account, BackendError> {
+    let hd_path: DerivationPath = format!("m/44'/118'/{account_index}'/0/0")
+        .parse()
+        .map_err(|_| BackendError::FailedToDeriveAddress)?;
+
+    // all of the supported networks use the same account prefix,
+    // so there's no need to connect to the device more than once
+    let prefix = NymNetworkDetails::from(WalletNetwork::MAINNET)
+        .chain_details
+        .bech32_account_prefix;
+    let ledger = LedgerSigner::connect(&prefix, vec![hd_path])?;
+
+    _connect_with_signer(SignerSource::Ledger(ledger), state).await
+}
+
+/// Source of the signers for the clients of all the networks.
+enum SignerSource {
+    Mnemonic(Mnemonic),
+    Ledger(LedgerSigner),
+}
+
+impl SignerSource {
+    fn signer(&self, prefix: &str) -> WalletSigner {
+        match self {
+            SignerSource::Mnemonic(mnemonic) => WalletSigner::Mnemonic(
+                DirectSecp256k1HdWallet::from_mnemonic(prefix, mnemonic.clone()),
+            ),
+            SignerSource::Ledger(ledger) => WalletSigner::Ledger(ledger.clone()),
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn get_balance(state: tauri::State<'_, WalletState>) -> Result<Balance, BackendError> {
     let guard = state.read().await;
@@ -90,6 +130,13 @@ fn random_mnemonic() -> Mnemonic {
 async fn _connect_with_mnemonic(
     mnemonic: Mnemonic,
     state: tauri::State<'_, WalletState>,
+) -> Result<Account, BackendError> {
+    _connect_with_signer(SignerSource::Mnemonic(mnemonic), state).await
+}
+
+async fn _connect_with_signer(
+    signer_source: SignerSource,
+    state: tauri::State<'_, WalletState>,
 ) -> Result<Account, BackendError> {
     {
         let mut w_state = state.write().await;
@@ -146,7 +193,7 @@ async fn _connect_with_mnemonic(
     }
 
     // Create clients for all networks
-    let clients = create_clients(&nyxd_urls, &api_urls, &config, &mnemonic)?;
+    let clients = create_clients(&nyxd_urls, &api_urls, &config, &signer_source)?;
 
     // Set the default account
     let default_network = WalletNetwork::MAINNET;
@@ -251,8 +298,8 @@ fn create_clients(
     default_nyxd_urls: &HashMap<WalletNetwork, Url>,
     default_api_urls: &HashMap<WalletNetwork, Url>,
     config: &Config,
-    mnemonic: &Mnemonic,
-) -> Result<Vec<(WalletNetwork, DirectSigningHttpRpcValidatorClient)>, BackendError> {
+    signer_source: &SignerSource,
+) -> Result<Vec<(WalletNetwork, WalletSigningClient)>, BackendError> {
     let mut clients = Vec::new();
     for network in WalletNetwork::iter() {
         let nyxd_url = if let Some(url) = config.get_selected_validator_nyxd_url(network) {
@@ -285,11 +332,16 @@ fn create_clients(
             .with_mixnet_contract(Some(config.get_mixnet_contract_address(network).as_ref()))
             .with_vesting_contract(Some(config.get_vesting_contract_address(network).as_ref()));
 
+        let rpc_client =
+            nym_validator_client::http_client(nyxd_url.as_str()).map_err(NyxdError::from)?;
+        let signer = signer_source.signer(&network_details.chain_details.bech32_account_prefix);
+
         let config = nym_validator_client::Config::try_from_nym_network_details(&network_details)?
             .with_urls(nyxd_url, api_url)
             .with_simulated_gas_multiplier(CUSTOM_SIMULATED_GAS_MULTIPLIER);
 
-        let client = nym_validator_client::Client::new_signing(config, mnemonic.clone())?;
+        let client =
+            nym_validator_client::Client::new_signing_with_rpc_client(config, rpc_client, signer);
         clients.push((network, client));
     }
     Ok(clients)
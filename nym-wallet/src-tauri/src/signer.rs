@@ -0,0 +1,77 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_validator_client::nyxd::error::NyxdError;
+use nym_validator_client::nyxd::{AccountId, PublicKey};
+use nym_validator_client::signing::amino::AminoSignDoc;
+use nym_validator_client::signing::direct_wallet::DirectSecp256k1HdWallet;
+use nym_validator_client::signing::ledger::LedgerSigner;
+use nym_validator_client::signing::signer::{OfflineSigner, Signature, SignerType};
+use nym_validator_client::signing::AccountData;
+use nym_validator_client::HttpRpcClient;
+
+pub type WalletSigningClient = nym_validator_client::Client<HttpRpcClient, WalletSigner>;
+
+/// Signer used by all the signing clients of the wallet. The keys are either derived from the
+/// mnemonic of the logged in account or they stay on the connected Ledger device.
+#[derive(Debug, Clone)]
+pub enum WalletSigner {
+    Mnemonic(DirectSecp256k1HdWallet),
+    Ledger(LedgerSigner),
+}
+
+impl OfflineSigner for WalletSigner {
+    type Error = NyxdError;
+
+    fn signer_addresses(&self) -> Result<Vec<AccountId>, Self::Error> {
+        match self {
+            WalletSigner::Mnemonic(wallet) => Ok(wallet.signer_addresses()?),
+            WalletSigner::Ledger(ledger) => Ok(ledger.signer_addresses()?),
+        }
+    }
+
+    fn get_accounts(&self) -> Result<Vec<AccountData>, Self::Error> {
+        match self {
+            WalletSigner::Mnemonic(wallet) => Ok(wallet.get_accounts()?),
+            WalletSigner::Ledger(ledger) => Ok(ledger.get_accounts()?),
+        }
+    }
+
+    fn preferred_signer_type(&self) -> SignerType {
+        match self {
+            WalletSigner::Mnemonic(wallet) => wallet.preferred_signer_type(),
+            WalletSigner::Ledger(ledger) => ledger.preferred_signer_type(),
+        }
+    }
+
+    fn account_public_key(&self, signer_address: &AccountId) -> Result<PublicKey, Self::Error> {
+        match self {
+            WalletSigner::Mnemonic(wallet) => Ok(wallet.account_public_key(signer_address)?),
+            WalletSigner::Ledger(ledger) => Ok(ledger.account_public_key(signer_address)?),
+        }
+    }
+
+    fn sign_direct_with_account(
+        &self,
+        signer: &AccountData,
+        sign_doc: cosmrs::tx::SignDoc,
+    ) -> Result<cosmrs::tx::Raw, Self::Error> {
+        match self {
+            WalletSigner::Mnemonic(wallet) => {
+                Ok(wallet.sign_direct_with_account(signer, sign_doc)?)
+            }
+            WalletSigner::Ledger(ledger) => Ok(ledger.sign_direct_with_account(signer, sign_doc)?),
+        }
+    }
+
+    fn sign_amino_doc(
+        &self,
+        signer_address: &AccountId,
+        sign_doc: &AminoSignDoc,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            WalletSigner::Mnemonic(wallet) => Ok(wallet.sign_amino_doc(signer_address, sign_doc)?),
+            WalletSigner::Ledger(ledger) => Ok(ledger.sign_amino_doc(signer_address, sign_doc)?),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config;
 use crate::error::BackendError;
+use crate::signer::WalletSigningClient;
 use crate::simulate::SimulateResult;
 use ::nym_config::defaults::NymNetworkDetails;
 use cosmwasm_std::Decimal;
@@ -9,7 +10,6 @@ use nym_types::currency::{DecCoin, Denom, RegisteredCoins};
 use nym_types::fees::FeeDetails;
 use nym_validator_client::nyxd::cosmwasm_client::types::SimulateResponse;
 use nym_validator_client::nyxd::{AccountId as CosmosAccountId, Coin, Fee, SigningCosmWasmClient};
-use nym_validator_client::DirectSigningHttpRpcValidatorClient;
 use nym_wallet_types::network::Network;
 use nym_wallet_types::network_config;
 use once_cell::sync::Lazy;
@@ -65,7 +65,7 @@ impl WalletState {
 #[derive(Default)]
 pub struct WalletStateInner {
     config: config::Config,
-    signing_clients: HashMap<Network, DirectSigningHttpRpcValidatorClient>,
+    signing_clients: HashMap<Network, WalletSigningClient>,
     current_network: Network,
 
     // All the accounts the we get from decrypting the wallet. We hold on to these for being able to
@@ -191,10 +191,7 @@ impl WalletStateInner {
         Ok(FeeDetails::new(amount, res.to_fee()))
     }
 
-    pub fn client(
-        &self,
-        network: Network,
-    ) -> Result<&DirectSigningHttpRpcValidatorClient, BackendError> {
+    pub fn client(&self, network: Network) -> Result<&WalletSigningClient, BackendError> {
         self.signing_clients
             .get(&network)
             .ok_or(BackendError::ClientNotInitialized)
@@ -203,22 +200,20 @@ impl WalletStateInner {
     pub fn client_mut(
         &mut self,
         network: Network,
-    ) -> Result<&mut DirectSigningHttpRpcValidatorClient, BackendError> {
+    ) -> Result<&mut WalletSigningClient, BackendError> {
         self.signing_clients
             .get_mut(&network)
             .ok_or(BackendError::ClientNotInitialized)
     }
 
-    pub fn current_client(&self) -> Result<&DirectSigningHttpRpcValidatorClient, BackendError> {
+    pub fn current_client(&self) -> Result<&WalletSigningClient, BackendError> {
         self.signing_clients
             .get(&self.current_network)
             .ok_or(BackendError::ClientNotInitialized)
     }
 
     #[allow(unused)]
-    pub fn current_client_mut(
-        &mut self,
-    ) -> Result<&mut DirectSigningHttpRpcValidatorClient, BackendError> {
+    pub fn current_client_mut(&mut self) -> Result<&mut WalletSigningClient, BackendError> {
         self.signing_clients
             .get_mut(&self.current_network)
             .ok_or(BackendError::ClientNotInitialized)
@@ -238,7 +233,7 @@ impl WalletStateInner {
         Ok(self.config.save_to_files()?)
     }
 
-    pub fn add_client(&mut self, network: Network, client: DirectSigningHttpRpcValidatorClient) {
+    pub fn add_client(&mut self, network: Network, client: WalletSigningClient) {
         self.signing_clients.insert(network, client);
     }
 
@@ -452,7 +447,7 @@ impl WalletStateInner {
         }
         self.config.select_nyxd_url(url.parse()?, network);
         if let Ok(client) = self.client_mut(network) {
-            client.change_nyxd(url.parse()?)?;
+            client.nyxd.change_endpoint(url)?;
         }
         Ok(())
     }
@@ -462,7 +457,7 @@ impl WalletStateInner {
         let default_nyxd = self.config.get_default_nyxd_url(network);
         if let Ok(client) = self.client_mut(network) {
             if let Some(url) = default_nyxd {
-                client.change_nyxd(url)?;
+                client.nyxd.change_endpoint(url.as_str())?;
             }
         }
         Ok(())
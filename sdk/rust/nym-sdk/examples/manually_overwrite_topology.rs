@@ -31,6 +31,9 @@ async fn main() {
                 .parse()
                 .unwrap(),
             layer: Layer::One,
+            stake: None,
+            performance: None,
+            sphinx_key_rotation: None,
             version: "1.1.0".into(),
         }],
     );
@@ -48,6 +51,9 @@ async fn main() {
                 .parse()
                 .unwrap(),
             layer: Layer::Two,
+            stake: None,
+            performance: None,
+            sphinx_key_rotation: None,
             version: "1.1.0".into(),
         }],
     );
@@ -65,6 +71,9 @@ async fn main() {
                 .parse()
                 .unwrap(),
             layer: Layer::Three,
+            stake: None,
+            performance: None,
+            sphinx_key_rotation: None,
             version: "1.1.0".into(),
         }],
     );
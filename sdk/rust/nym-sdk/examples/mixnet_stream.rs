@@ -0,0 +1,42 @@
+use nym_sdk::mixnet;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::main]
+async fn main() {
+    nym_bin_common::logging::setup_logging();
+
+    // Create two clients: one that is going to open the stream and one that is going to accept it
+    let initiator = mixnet::MixnetClient::connect_new().await.unwrap();
+    let responder = mixnet::MixnetClient::connect_new().await.unwrap();
+
+    let initiator = initiator.into_stream_client();
+    let mut responder = responder.into_stream_client();
+    let responder_address = *responder.nym_address();
+    println!("Responder nym address is: {responder_address}");
+
+    // Open the stream and write some data into it. Note that the responder is never going to learn
+    // the address of the initiator as it's only going to reply using the attached SURBs.
+    let mut outbound = initiator.open_stream(responder_address).await.unwrap();
+    outbound.write_all(b"hello over the mixnet").await.unwrap();
+
+    println!("Waiting for the stream to get opened...");
+    let mut inbound = responder.accept().await.unwrap();
+    let mut buf = [0u8; 21];
+    inbound.read_exact(&mut buf).await.unwrap();
+    println!("Received: {}", String::from_utf8_lossy(&buf));
+
+    // Reply to the initiator and close our side of the stream
+    inbound.write_all(b"hello back").await.unwrap();
+    inbound.shutdown().await.unwrap();
+
+    let mut reply = Vec::new();
+    outbound.read_to_end(&mut reply).await.unwrap();
+    println!("Received reply: {}", String::from_utf8_lossy(&reply));
+
+    outbound.shutdown().await.unwrap();
+    drop(outbound);
+    drop(inbound);
+
+    initiator.disconnect().await;
+    responder.disconnect().await;
+}
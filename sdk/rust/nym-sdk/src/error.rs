@@ -91,6 +91,9 @@ pub enum Error {
     #[error("failed to send the provided message")]
     MessageSendingFailure,
 
+    #[error("could not find any gateway to replace the unreachable one")]
+    NoReplacementGateway,
+
     #[error("this operation is currently unsupported: {details}")]
     Unsupported { details: String },
 }
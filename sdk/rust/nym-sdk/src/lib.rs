@@ -1,10 +1,12 @@
 //! Rust SDK for the Nym platform
 //!
-//! The main component currently is [`mixnet`].
+//! The main component currently is [`mixnet`]. On top of it, [`mixfetch`] provides an HTTP(S)
+//! client that sends all of its requests through the mixnet.
 
 mod error;
 
 pub mod bandwidth;
+pub mod mixfetch;
 pub mod mixnet;
 
 pub use error::{Error, Result};
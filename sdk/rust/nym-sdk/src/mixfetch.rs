@@ -0,0 +1,44 @@
+//! HTTP(S) client that sends all of its requests through the mixnet.
+//!
+//! Every connection is proxied by a network requester, which opens the actual TCP connection to
+//! the remote host on our behalf. TLS is terminated locally, so the network requester only ever
+//! sees the encrypted traffic. Connections are kept alive and reused for subsequent requests to
+//! the same origin.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use nym_sdk::mixfetch::MixFetchClient;
+//! use nym_sdk::mixnet::Recipient;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let network_requester = Recipient::try_from_base58_string("foobar").unwrap();
+//!     let client = MixFetchClient::builder(network_requester)
+//!         .build()
+//!         .await
+//!         .unwrap();
+//!
+//!     let response = client
+//!         .get("https://nymtech.net/.wellknown/wallet/validators.json")
+//!         .await
+//!         .unwrap();
+//!     println!("{}", String::from_utf8_lossy(response.body()));
+//!
+//!     client.disconnect().await;
+//! }
+//! ```
+
+mod client;
+mod connection;
+mod error;
+mod pool;
+
+pub use bytes::Bytes;
+pub use client::{
+    MixFetchClient, MixFetchClientBuilder, DEFAULT_CONNECT_TIMEOUT, DEFAULT_MAX_RESPONSE_SIZE,
+    DEFAULT_POOL_IDLE_TIMEOUT, DEFAULT_POOL_MAX_IDLE_PER_ORIGIN, DEFAULT_REQUEST_TIMEOUT,
+};
+pub use error::MixFetchError;
+pub use http::{header, HeaderMap, Method, Request, Response, StatusCode, Uri};
+pub use tokio_rustls::rustls;
@@ -0,0 +1,320 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixfetch::connection::{ConnectionRouter, Connector};
+use crate::mixfetch::pool::{ConnectionPool, Origin};
+use crate::mixfetch::MixFetchError;
+use crate::mixnet::{MixnetClient, Recipient};
+use bytes::Bytes;
+use http::header::HOST;
+use http::{HeaderValue, Request, Response, Uri};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::client::conn::http1::SendRequest;
+use hyper_util::rt::TokioIo;
+use log::{debug, warn};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+
+/// Default maximum amount of time for establishing a connection, including the TLS handshake.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default maximum amount of time for completing a request, including receiving the full response.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default amount of time an idle connection is kept around for.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Default maximum number of idle connections kept around for each origin.
+pub const DEFAULT_POOL_MAX_IDLE_PER_ORIGIN: usize = 4;
+
+/// Default maximum size of a response body.
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 16 * 1024 * 1024;
+
+fn default_tls_config() -> Arc<ClientConfig> {
+    let mut root_store = RootCertStore::empty();
+    root_store.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            anchor.subject,
+            anchor.spki,
+            anchor.name_constraints,
+        )
+    }));
+
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Arc::new(config)
+}
+
+pub struct MixFetchClientBuilder {
+    network_requester: Recipient,
+    mixnet_client: Option<MixnetClient>,
+    tls_config: Option<Arc<ClientConfig>>,
+
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    pool_idle_timeout: Duration,
+    pool_max_idle_per_origin: usize,
+    max_response_size: usize,
+}
+
+impl MixFetchClientBuilder {
+    /// Creates a client builder that is going to use the specified network requester for
+    /// reaching the remote hosts.
+    #[must_use]
+    pub fn new(network_requester: Recipient) -> Self {
+        MixFetchClientBuilder {
+            network_requester,
+            mixnet_client: None,
+            tls_config: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            pool_idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+            pool_max_idle_per_origin: DEFAULT_POOL_MAX_IDLE_PER_ORIGIN,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+        }
+    }
+
+    /// Use an already connected mixnet client instead of creating a new ephemeral one.
+    /// Note that the mix fetch client takes over all messages received by the client.
+    #[must_use]
+    pub fn with_mixnet_client(mut self, mixnet_client: MixnetClient) -> Self {
+        self.mixnet_client = Some(mixnet_client);
+        self
+    }
+
+    /// Use a custom TLS configuration, for example with a different set of trusted root
+    /// certificates, instead of the default one based on the Mozilla root certificates.
+    #[must_use]
+    pub fn with_tls_config(mut self, tls_config: Arc<ClientConfig>) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Set the maximum amount of time for establishing a new connection,
+    /// including the TLS handshake.
+    #[must_use]
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Set the maximum amount of time for completing a request, including establishing
+    /// the connection (if required) and receiving the full response.
+    #[must_use]
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Set the amount of time an idle connection is kept around for.
+    #[must_use]
+    pub fn with_pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self
+    }
+
+    /// Set the maximum number of idle connections kept around for each origin.
+    /// Setting it to 0 disables connection reuse.
+    #[must_use]
+    pub fn with_pool_max_idle_per_origin(mut self, pool_max_idle_per_origin: usize) -> Self {
+        self.pool_max_idle_per_origin = pool_max_idle_per_origin;
+        self
+    }
+
+    /// Set the maximum size of a response body. Requests whose responses exceed it are going to fail.
+    #[must_use]
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Construct the [`MixFetchClient`], connecting to the mixnet if no client has been provided.
+    pub async fn build(self) -> Result<MixFetchClient, MixFetchError> {
+        let mixnet_client = match self.mixnet_client {
+            Some(mixnet_client) => mixnet_client,
+            None => MixnetClient::connect_new().await?,
+        };
+
+        let (router, connector) = ConnectionRouter::new(mixnet_client, self.network_requester);
+        let (router_shutdown, shutdown_receiver) = oneshot::channel();
+        let router_handle = tokio::spawn(router.run(shutdown_receiver));
+
+        Ok(MixFetchClient {
+            connector,
+            pool: ConnectionPool::new(self.pool_max_idle_per_origin, self.pool_idle_timeout),
+            tls: TlsConnector::from(self.tls_config.unwrap_or_else(default_tls_config)),
+            connect_timeout: self.connect_timeout,
+            request_timeout: self.request_timeout,
+            max_response_size: self.max_response_size,
+            router_shutdown,
+            router_handle,
+        })
+    }
+}
+
+/// HTTP(S) client that sends all of its requests through the mixnet.
+pub struct MixFetchClient {
+    connector: Connector,
+    pool: ConnectionPool,
+    tls: TlsConnector,
+
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    max_response_size: usize,
+
+    router_shutdown: oneshot::Sender<()>,
+    router_handle: JoinHandle<()>,
+}
+
+impl MixFetchClient {
+    /// Creates a [`MixFetchClientBuilder`] that is going to use the specified network requester.
+    #[must_use]
+    pub fn builder(network_requester: Recipient) -> MixFetchClientBuilder {
+        MixFetchClientBuilder::new(network_requester)
+    }
+
+    /// Sends a GET request to the specified url.
+    pub async fn get(&self, url: &str) -> Result<Response<Bytes>, MixFetchError> {
+        let request = Request::get(url).body(Bytes::new())?;
+        self.fetch(request).await
+    }
+
+    /// Sends the provided request and waits for the full response.
+    pub async fn fetch<B>(&self, request: Request<B>) -> Result<Response<Bytes>, MixFetchError>
+    where
+        B: Into<Bytes>,
+    {
+        let timeout = self.request_timeout;
+        tokio::time::timeout(timeout, self.execute(request))
+            .await
+            .map_err(|_| MixFetchError::Timeout { timeout })?
+    }
+
+    async fn execute<B>(&self, request: Request<B>) -> Result<Response<Bytes>, MixFetchError>
+    where
+        B: Into<Bytes>,
+    {
+        let (mut parts, body) = request.into_parts();
+        let origin = Origin::try_from_uri(&parts.uri)?;
+
+        if !parts.headers.contains_key(HOST) {
+            let host = match parts.uri.port() {
+                Some(port) => format!("{}:{port}", parts.uri.host().unwrap_or(&origin.host)),
+                None => parts.uri.host().unwrap_or(&origin.host).to_string(),
+            };
+            let host = HeaderValue::try_from(host).map_err(http::Error::from)?;
+            parts.headers.insert(HOST, host);
+        }
+
+        // HTTP/1.1 requests sent to the origin server must only contain the path and the query
+        let path = parts
+            .uri
+            .path_and_query()
+            .map(|path| path.as_str())
+            .unwrap_or("/");
+        parts.uri = Uri::try_from(path).map_err(http::Error::from)?;
+
+        let request = Request::from_parts(parts, Full::new(body.into()));
+
+        let mut sender = match self.pool.checkout(&origin) {
+            Some(sender) => {
+                debug!("reusing an existing connection to {origin}");
+                sender
+            }
+            None => self.open_connection(&origin).await?,
+        };
+
+        let response = sender.send_request(request).await?;
+        let (parts, body) = response.into_parts();
+        let body = match Limited::new(body, self.max_response_size).collect().await {
+            Ok(collected) => collected.to_bytes(),
+            Err(err) => {
+                return Err(match err.downcast::<hyper::Error>() {
+                    Ok(err) => MixFetchError::HttpFailure(*err),
+                    // the only other possible failure is exceeding the limit
+                    Err(_) => MixFetchError::ResponseTooLarge {
+                        limit: self.max_response_size,
+                    },
+                });
+            }
+        };
+
+        // the connection is ready for another request only after the full response has been read
+        self.pool.checkin(origin, sender);
+
+        Ok(Response::from_parts(parts, body))
+    }
+
+    async fn open_connection(
+        &self,
+        origin: &Origin,
+    ) -> Result<SendRequest<Full<Bytes>>, MixFetchError> {
+        let timeout = self.connect_timeout;
+        tokio::time::timeout(timeout, self.establish_connection(origin))
+            .await
+            .map_err(|_| MixFetchError::Timeout { timeout })?
+    }
+
+    async fn establish_connection(
+        &self,
+        origin: &Origin,
+    ) -> Result<SendRequest<Full<Bytes>>, MixFetchError> {
+        debug!("opening a new connection to {origin}");
+        let connection = self.connector.connect(origin.remote_address()).await?;
+
+        if !origin.https {
+            return handshake(connection).await;
+        }
+
+        let server_name = ServerName::try_from(origin.host.as_str()).map_err(|source| {
+            MixFetchError::InvalidServerName {
+                host: origin.host.clone(),
+                source,
+            }
+        })?;
+        let tls_connection = self
+            .tls
+            .connect(server_name, connection)
+            .await
+            .map_err(|source| MixFetchError::TlsFailure {
+                host: origin.host.clone(),
+                source,
+            })?;
+        handshake(tls_connection).await
+    }
+
+    /// Disconnect from the mixnet. All existing connections will get closed.
+    pub async fn disconnect(self) {
+        self.pool.clear();
+
+        // if the router has already stopped, there's nothing to signal
+        self.router_shutdown.send(()).ok();
+        if let Err(err) = self.router_handle.await {
+            warn!("the connection router has panicked: {err}")
+        }
+    }
+}
+
+async fn handshake<T>(io: T) -> Result<SendRequest<Full<Bytes>>, MixFetchError>
+where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(io)).await?;
+
+    // drive the connection until it gets closed, i.e. until all the senders are dropped
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            debug!("connection failure: {err}")
+        }
+    });
+
+    Ok(sender)
+}
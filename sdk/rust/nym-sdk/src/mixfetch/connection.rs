@@ -0,0 +1,570 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! TCP connections opened by the network requester on our behalf.
+
+use crate::mixnet::{MixnetClient, MixnetClientSender, MixnetMessageSender, Recipient};
+use futures::StreamExt;
+use log::{debug, trace, warn};
+use nym_client_core::client::inbound_messages::InputMessage;
+use nym_ordered_buffer::OrderedMessageBuffer;
+use nym_service_providers_common::interface::{ProviderInterfaceVersion, ResponseContent};
+use nym_socks5_requests::{
+    ConnectionId, RemoteAddress, SocketData, Socks5ProtocolVersion, Socks5ProviderRequest,
+    Socks5ProviderResponse, Socks5Request, Socks5ResponseContent,
+};
+use nym_sphinx::params::PacketType;
+use nym_sphinx::receiver::ReconstructedMessage;
+use nym_task::connections::TransmissionLane;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::PollSender;
+
+const PROVIDER_INTERFACE_VERSION: ProviderInterfaceVersion =
+    ProviderInterfaceVersion::new_current();
+const SOCKS5_PROTOCOL_VERSION: Socks5ProtocolVersion = Socks5ProtocolVersion::new_current();
+
+enum ConnectionEvent {
+    Data(SocketData),
+    Error(String),
+}
+
+type SharedConnectionRegistry =
+    Arc<Mutex<HashMap<ConnectionId, mpsc::UnboundedSender<ConnectionEvent>>>>;
+
+/// Everything required for opening new connections via the network requester.
+#[derive(Clone)]
+pub(crate) struct Connector {
+    network_requester: Recipient,
+    self_address: Recipient,
+    sender: MixnetClientSender,
+    registry: SharedConnectionRegistry,
+}
+
+impl Connector {
+    /// Requests the network requester to open a connection to the specified remote.
+    /// Note that the network requester does not confirm successful connections, so any failures
+    /// are only going to get reported once we attempt to use the connection.
+    pub(crate) async fn connect(&self, remote: RemoteAddress) -> crate::Result<ProxiedConnection> {
+        let mut registry = self
+            .registry
+            .lock()
+            .expect("connection registry lock got poisoned");
+        let connection_id = loop {
+            let candidate = rand::random();
+            if !registry.contains_key(&candidate) {
+                break candidate;
+            }
+        };
+        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        registry.insert(connection_id, event_sender);
+        drop(registry);
+
+        // for now explicitly attach return address, we can worry about surbs later
+        let request = Socks5Request::new_connect(
+            SOCKS5_PROTOCOL_VERSION,
+            connection_id,
+            remote.clone(),
+            Some(self.self_address),
+        );
+
+        // create the connection before sending the request so that it would get cleaned up on failure
+        let connection = ProxiedConnection::new(
+            connection_id,
+            self.network_requester,
+            &self.sender,
+            event_receiver,
+            self.registry.clone(),
+        );
+        self.sender
+            .send(make_input_message(
+                self.network_requester,
+                connection_id,
+                request,
+                self.sender.packet_type(),
+            ))
+            .await?;
+
+        debug!("requested connection {connection_id} to {remote}");
+        Ok(connection)
+    }
+}
+
+fn make_input_message(
+    network_requester: Recipient,
+    connection_id: ConnectionId,
+    request: Socks5Request,
+    packet_type: Option<PacketType>,
+) -> InputMessage {
+    let request = Socks5ProviderRequest::new_provider_data(PROVIDER_INTERFACE_VERSION, request);
+    InputMessage::new_regular(
+        network_requester,
+        request.into_bytes(),
+        TransmissionLane::ConnectionId(connection_id),
+        packet_type,
+    )
+}
+
+/// A TCP connection opened and proxied by the network requester.
+pub(crate) struct ProxiedConnection {
+    id: ConnectionId,
+    network_requester: Recipient,
+    packet_type: Option<PacketType>,
+
+    sender: PollSender<InputMessage>,
+    inbound: mpsc::UnboundedReceiver<ConnectionEvent>,
+    registry: SharedConnectionRegistry,
+
+    next_outbound_seq: u64,
+    ordered_buffer: OrderedMessageBuffer,
+    read_buffer: Vec<u8>,
+
+    // sequence number of the data that closed the remote socket
+    remote_close_seq: Option<u64>,
+    remote_closed: bool,
+    local_closed: bool,
+    remote_error: Option<String>,
+}
+
+impl Debug for ProxiedConnection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxiedConnection")
+            .field("id", &self.id)
+            .field("remote_closed", &self.remote_closed)
+            .field("local_closed", &self.local_closed)
+            .finish()
+    }
+}
+
+impl ProxiedConnection {
+    fn new(
+        id: ConnectionId,
+        network_requester: Recipient,
+        sender: &MixnetClientSender,
+        inbound: mpsc::UnboundedReceiver<ConnectionEvent>,
+        registry: SharedConnectionRegistry,
+    ) -> Self {
+        ProxiedConnection {
+            id,
+            network_requester,
+            packet_type: sender.packet_type(),
+            sender: PollSender::new(sender.input_sender()),
+            inbound,
+            registry,
+            next_outbound_seq: 0,
+            ordered_buffer: OrderedMessageBuffer::new(),
+            read_buffer: Vec::new(),
+            remote_close_seq: None,
+            remote_closed: false,
+            local_closed: false,
+            remote_error: None,
+        }
+    }
+
+    fn next_data_message(&mut self, local_closed: bool, data: Vec<u8>) -> InputMessage {
+        let request = Socks5Request::new_send(
+            SOCKS5_PROTOCOL_VERSION,
+            SocketData::new(self.next_outbound_seq, self.id, local_closed, data),
+        );
+        self.next_outbound_seq += 1;
+        make_input_message(self.network_requester, self.id, request, self.packet_type)
+    }
+
+    fn on_data(&mut self, data: SocketData) {
+        trace!(
+            "connection {}: received data with sequence {}",
+            self.id,
+            data.header.seq
+        );
+        if data.header.local_socket_closed {
+            self.remote_close_seq = Some(data.header.seq);
+        }
+
+        if let Err(err) = self.ordered_buffer.write(data.header.seq, data.data) {
+            warn!(
+                "connection {}: failed to buffer received data: {err}",
+                self.id
+            );
+            return;
+        }
+
+        if let Some(contiguous) = self.ordered_buffer.read() {
+            self.read_buffer.extend_from_slice(&contiguous.data);
+            if let Some(close_seq) = self.remote_close_seq {
+                if contiguous.last_sequence >= close_seq {
+                    self.remote_closed = true;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for ProxiedConnection {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if !this.read_buffer.is_empty() {
+                let available = this.read_buffer.len().min(buf.remaining());
+                buf.put_slice(&this.read_buffer[..available]);
+                this.read_buffer.drain(..available);
+                return Poll::Ready(Ok(()));
+            }
+
+            if let Some(err) = &this.remote_error {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::ConnectionAborted,
+                    err.clone(),
+                )));
+            }
+
+            if this.remote_closed {
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(this.inbound.poll_recv(cx)) {
+                Some(ConnectionEvent::Data(data)) => this.on_data(data),
+                Some(ConnectionEvent::Error(err)) => this.remote_error = Some(err),
+                None => {
+                    // the underlying client has shut down
+                    this.remote_closed = true;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncWrite for ProxiedConnection {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.local_closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        ready!(this.sender.poll_reserve(cx))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        let message = this.next_data_message(false, buf.to_vec());
+        this.sender
+            .send_item(message)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // all written data is immediately handed over to the client
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.local_closed {
+            return Poll::Ready(Ok(()));
+        }
+
+        ready!(this.sender.poll_reserve(cx))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        let message = this.next_data_message(true, Vec::new());
+        this.sender
+            .send_item(message)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        this.local_closed = true;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for ProxiedConnection {
+    fn drop(&mut self) {
+        debug!("connection {} is getting closed", self.id);
+        if !self.local_closed {
+            // best effort attempt at letting the network requester know it can close the socket
+            let message = self.next_data_message(true, Vec::new());
+            if let Some(sender) = self.sender.get_ref() {
+                if sender.try_send(message).is_err() {
+                    debug!(
+                        "failed to inform the network requester about closing connection {}",
+                        self.id
+                    )
+                }
+            }
+        }
+        self.registry
+            .lock()
+            .expect("connection registry lock got poisoned")
+            .remove(&self.id);
+    }
+}
+
+/// Dispatches the responses received from the network requester to the appropriate connections.
+pub(crate) struct ConnectionRouter {
+    client: MixnetClient,
+    registry: SharedConnectionRegistry,
+}
+
+impl ConnectionRouter {
+    pub(crate) fn new(client: MixnetClient, network_requester: Recipient) -> (Self, Connector) {
+        let registry = SharedConnectionRegistry::default();
+        let connector = Connector {
+            network_requester,
+            self_address: *client.nym_address(),
+            sender: client.split_sender(),
+            registry: registry.clone(),
+        };
+        (ConnectionRouter { client, registry }, connector)
+    }
+
+    fn dispatch(&self, connection_id: ConnectionId, event: ConnectionEvent) {
+        let mut registry = self
+            .registry
+            .lock()
+            .expect("connection registry lock got poisoned");
+
+        let Some(connection) = registry.get(&connection_id) else {
+            trace!("received a response for an unknown connection {connection_id}");
+            return;
+        };
+        if connection.send(event).is_err() {
+            registry.remove(&connection_id);
+        }
+    }
+
+    fn on_message(&self, message: ReconstructedMessage) {
+        let response = match Socks5ProviderResponse::try_from_bytes(&message.message) {
+            Ok(response) => response,
+            Err(err) => {
+                debug!("received a message that is not a network requester response - dropping it: {err}");
+                return;
+            }
+        };
+
+        let ResponseContent::ProviderData(response) = response.content else {
+            warn!("received a provider control response even though we didn't send any requests");
+            return;
+        };
+
+        match response.content {
+            Socks5ResponseContent::NetworkData { content } => {
+                self.dispatch(content.header.connection_id, ConnectionEvent::Data(content))
+            }
+            Socks5ResponseContent::ConnectionError(err) => {
+                debug!(
+                    "connection {} has failed: {}",
+                    err.connection_id, err.network_requester_error
+                );
+                self.dispatch(
+                    err.connection_id,
+                    ConnectionEvent::Error(err.network_requester_error),
+                )
+            }
+            Socks5ResponseContent::Query(_)
+            | Socks5ResponseContent::Datagram(_)
+            | Socks5ResponseContent::Bind(_) => {
+                warn!("received an unexpected response from the network requester")
+            }
+        }
+    }
+
+    pub(crate) async fn run(mut self, mut shutdown: oneshot::Receiver<()>) {
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut shutdown => {
+                    debug!("ConnectionRouter: received shutdown");
+                    break
+                }
+                message = self.client.next() => match message {
+                    Some(message) => self.on_message(message),
+                    None => {
+                        debug!("ConnectionRouter: the client has stopped");
+                        break
+                    }
+                }
+            }
+        }
+
+        // close all the connections
+        self.registry
+            .lock()
+            .expect("connection registry lock got poisoned")
+            .clear();
+        self.client.disconnect().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_client_core::client::inbound_messages::InputMessageReceiver;
+    use nym_crypto::asymmetric::{encryption, identity};
+    use rand::rngs::OsRng;
+    use tokio::io::AsyncReadExt;
+
+    const CONNECTION_ID: ConnectionId = 42;
+
+    fn network_requester() -> Recipient {
+        Recipient::new(
+            *identity::KeyPair::new(&mut OsRng).public_key(),
+            *encryption::KeyPair::new(&mut OsRng).public_key(),
+            *identity::KeyPair::new(&mut OsRng).public_key(),
+        )
+    }
+
+    fn connection() -> (
+        ProxiedConnection,
+        mpsc::UnboundedSender<ConnectionEvent>,
+        SharedConnectionRegistry,
+        InputMessageReceiver,
+    ) {
+        let (sender, input_receiver) = MixnetClientSender::new_detached();
+        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        let registry = SharedConnectionRegistry::default();
+        registry
+            .lock()
+            .unwrap()
+            .insert(CONNECTION_ID, event_sender.clone());
+
+        let connection = ProxiedConnection::new(
+            CONNECTION_ID,
+            network_requester(),
+            &sender,
+            event_receiver,
+            registry.clone(),
+        );
+        (connection, event_sender, registry, input_receiver)
+    }
+
+    fn data(seq: u64, closed: bool, content: &[u8]) -> SocketData {
+        SocketData::new(seq, CONNECTION_ID, closed, content.to_vec())
+    }
+
+    #[test]
+    fn data_is_read_in_order() {
+        let (mut connection, ..) = connection();
+
+        connection.on_data(data(1, false, b"world"));
+        assert!(connection.read_buffer.is_empty());
+
+        connection.on_data(data(0, false, b"hello "));
+        assert_eq!(connection.read_buffer, b"hello world");
+
+        connection.on_data(data(3, false, b"!"));
+        connection.on_data(data(2, false, b" foo"));
+        assert_eq!(connection.read_buffer, b"hello world foo!");
+        assert!(!connection.remote_closed);
+    }
+
+    #[test]
+    fn remote_close_waits_for_all_preceding_data() {
+        let (mut connection, ..) = connection();
+
+        connection.on_data(data(2, true, b""));
+        connection.on_data(data(0, false, b"foo"));
+        assert!(connection.remote_close_seq.is_some());
+        assert!(!connection.remote_closed);
+
+        connection.on_data(data(1, false, b"bar"));
+        assert!(connection.remote_closed);
+        assert_eq!(connection.read_buffer, b"foobar");
+    }
+
+    #[test]
+    fn duplicate_data_is_ignored() {
+        let (mut connection, ..) = connection();
+
+        connection.on_data(data(0, false, b"foo"));
+        connection.on_data(data(0, false, b"foo"));
+        connection.on_data(data(1, false, b"bar"));
+        assert_eq!(connection.read_buffer, b"foobar");
+    }
+
+    #[tokio::test]
+    async fn reading_until_the_remote_closes() {
+        let (mut connection, events, ..) = connection();
+
+        events
+            .send(ConnectionEvent::Data(data(1, true, b"bar")))
+            .unwrap();
+        events
+            .send(ConnectionEvent::Data(data(0, false, b"foo")))
+            .unwrap();
+
+        let mut received = Vec::new();
+        connection.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received, b"foobar");
+    }
+
+    #[tokio::test]
+    async fn remote_errors_are_propagated() {
+        let (mut connection, events, ..) = connection();
+
+        events
+            .send(ConnectionEvent::Data(data(0, false, b"foo")))
+            .unwrap();
+        events
+            .send(ConnectionEvent::Error("connection refused".to_string()))
+            .unwrap();
+
+        // the data received before the failure can still be read
+        let mut buf = [0u8; 16];
+        let read = connection.read(&mut buf).await.unwrap();
+        assert_eq!(&buf[..read], b"foo");
+
+        let err = connection.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionAborted);
+    }
+
+    #[tokio::test]
+    async fn client_shutdown_closes_the_connection() {
+        let (mut connection, events, registry, _input) = connection();
+        registry.lock().unwrap().clear();
+        drop(events);
+
+        let mut received = Vec::new();
+        connection.read_to_end(&mut received).await.unwrap();
+        assert!(received.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dropping_the_connection_notifies_the_network_requester() {
+        let (connection, _events, registry, mut input) = connection();
+        drop(connection);
+
+        assert!(registry.lock().unwrap().is_empty());
+        let Ok(InputMessage::Regular { lane, .. }) = input.try_recv() else {
+            panic!("expected the close request")
+        };
+        assert_eq!(lane, TransmissionLane::ConnectionId(CONNECTION_ID));
+    }
+
+    #[tokio::test]
+    async fn writing_after_shutdown_fails() {
+        use tokio::io::AsyncWriteExt;
+
+        let (mut connection, _events, _registry, mut input) = connection();
+        connection.write_all(b"foo").await.unwrap();
+        connection.shutdown().await.unwrap();
+        assert!(connection.write_all(b"bar").await.is_err());
+
+        // the data alongside the close request
+        assert!(input.try_recv().is_ok());
+        assert!(input.try_recv().is_ok());
+        drop(connection);
+        // nothing else is sent once the connection has been closed
+        assert!(input.try_recv().is_err());
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MixFetchError {
+    #[error(transparent)]
+    ClientError(#[from] crate::Error),
+
+    #[error("the provided uri '{uri}' is not a valid target: {reason}")]
+    InvalidUri { uri: String, reason: String },
+
+    #[error("the scheme '{scheme}' is not supported. only 'http' and 'https' are")]
+    UnsupportedScheme { scheme: String },
+
+    #[error("failed to construct the request: {0}")]
+    MalformedRequest(#[from] http::Error),
+
+    #[error("'{host}' is not a valid server name: {source}")]
+    InvalidServerName {
+        host: String,
+        source: tokio_rustls::rustls::client::InvalidDnsNameError,
+    },
+
+    #[error("failed to establish a TLS session with {host}: {source}")]
+    TlsFailure {
+        host: String,
+        source: std::io::Error,
+    },
+
+    #[error("HTTP failure: {0}")]
+    HttpFailure(#[from] hyper::Error),
+
+    #[error("the response body exceeds the maximum allowed size of {limit} bytes")]
+    ResponseTooLarge { limit: usize },
+
+    #[error("the request has not completed within {timeout:?}")]
+    Timeout { timeout: Duration },
+
+    #[error("the mix fetch client has been disconnected")]
+    Disconnected,
+}
@@ -0,0 +1,308 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixfetch::MixFetchError;
+use bytes::Bytes;
+use http::uri::Scheme;
+use http::Uri;
+use http_body_util::Full;
+use hyper::client::conn::http1::SendRequest;
+use log::trace;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Scheme, host and port of the requested resource.
+/// Connections are only ever reused for requests to the same origin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Origin {
+    pub(crate) https: bool,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+}
+
+impl Origin {
+    pub(crate) fn try_from_uri(uri: &Uri) -> Result<Self, MixFetchError> {
+        let https = match uri.scheme() {
+            Some(scheme) if scheme == &Scheme::HTTPS => true,
+            Some(scheme) if scheme == &Scheme::HTTP => false,
+            Some(scheme) => {
+                return Err(MixFetchError::UnsupportedScheme {
+                    scheme: scheme.to_string(),
+                })
+            }
+            None => {
+                return Err(MixFetchError::InvalidUri {
+                    uri: uri.to_string(),
+                    reason: "the scheme is missing".to_string(),
+                })
+            }
+        };
+
+        let host = uri.host().ok_or_else(|| MixFetchError::InvalidUri {
+            uri: uri.to_string(),
+            reason: "the host is missing".to_string(),
+        })?;
+        let port = uri.port_u16().unwrap_or(if https { 443 } else { 80 });
+
+        Ok(Origin {
+            https,
+            // strip the brackets around IPv6 addresses
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port,
+        })
+    }
+
+    /// Address of the remote the network requester should connect to.
+    pub(crate) fn remote_address(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let scheme = if self.https { "https" } else { "http" };
+        write!(f, "{scheme}://{}", self.remote_address())
+    }
+}
+
+struct IdleConnection {
+    sender: SendRequest<Full<Bytes>>,
+    idle_since: Instant,
+}
+
+/// Keeps the established connections around so that they could be reused by subsequent requests.
+#[derive(Clone)]
+pub(crate) struct ConnectionPool {
+    idle: Arc<Mutex<HashMap<Origin, Vec<IdleConnection>>>>,
+    max_idle_per_origin: usize,
+    idle_timeout: Duration,
+}
+
+impl ConnectionPool {
+    pub(crate) fn new(max_idle_per_origin: usize, idle_timeout: Duration) -> Self {
+        ConnectionPool {
+            idle: Default::default(),
+            max_idle_per_origin,
+            idle_timeout,
+        }
+    }
+
+    /// Attempts to retrieve an idle connection to the specified origin that is ready to be reused.
+    pub(crate) fn checkout(&self, origin: &Origin) -> Option<SendRequest<Full<Bytes>>> {
+        let mut idle = self.idle.lock().expect("connection pool lock got poisoned");
+        let connections = idle.get_mut(origin)?;
+
+        let mut reusable = None;
+        while let Some(connection) = connections.pop() {
+            if connection.idle_since.elapsed() > self.idle_timeout || !connection.sender.is_ready()
+            {
+                trace!("dropping stale connection to {origin}");
+                continue;
+            }
+            reusable = Some(connection.sender);
+            break;
+        }
+        if connections.is_empty() {
+            idle.remove(origin);
+        }
+        reusable
+    }
+
+    /// Returns the connection back to the pool once it has finished serving a request.
+    pub(crate) fn checkin(&self, origin: Origin, sender: SendRequest<Full<Bytes>>) {
+        if self.max_idle_per_origin == 0 || sender.is_closed() {
+            return;
+        }
+
+        let mut idle = self.idle.lock().expect("connection pool lock got poisoned");
+        let connections = idle.entry(origin).or_default();
+        connections.retain(|connection| connection.idle_since.elapsed() <= self.idle_timeout);
+        if connections.len() < self.max_idle_per_origin {
+            connections.push(IdleConnection {
+                sender,
+                idle_since: Instant::now(),
+            })
+        }
+    }
+
+    /// Drops all the idle connections.
+    pub(crate) fn clear(&self) {
+        self.idle
+            .lock()
+            .expect("connection pool lock got poisoned")
+            .clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper_util::rt::TokioIo;
+    use tokio::io::DuplexStream;
+
+    fn parse_origin(uri: &str) -> Result<Origin, MixFetchError> {
+        Origin::try_from_uri(&uri.parse().unwrap())
+    }
+
+    // returns the sender alongside the remote end of the connection that has to be kept alive
+    async fn connection() -> (SendRequest<Full<Bytes>>, DuplexStream) {
+        let (local, remote) = tokio::io::duplex(1024);
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(local))
+            .await
+            .unwrap();
+        tokio::spawn(connection);
+        sender.ready().await.unwrap();
+        (sender, remote)
+    }
+
+    #[test]
+    fn origin_uses_default_ports() {
+        let http = parse_origin("http://nymtech.net/foo?bar=baz").unwrap();
+        assert!(!http.https);
+        assert_eq!(http.host, "nymtech.net");
+        assert_eq!(http.port, 80);
+        assert_eq!(http.remote_address(), "nymtech.net:80");
+        assert_eq!(http.to_string(), "http://nymtech.net:80");
+
+        let https = parse_origin("https://nymtech.net").unwrap();
+        assert!(https.https);
+        assert_eq!(https.port, 443);
+
+        let explicit = parse_origin("https://nymtech.net:8443/").unwrap();
+        assert_eq!(explicit.port, 8443);
+        assert_eq!(explicit.remote_address(), "nymtech.net:8443");
+
+        // the origin doesn't depend on the path
+        assert_eq!(http, parse_origin("http://nymtech.net:80/other").unwrap());
+        assert_ne!(http, https);
+    }
+
+    #[test]
+    fn origin_strips_ipv6_brackets() {
+        let origin_v6 = parse_origin("http://[::1]:8080/foo").unwrap();
+        assert_eq!(origin_v6.host, "::1");
+        assert_eq!(origin_v6.port, 8080);
+        assert_eq!(origin_v6.remote_address(), "[::1]:8080");
+        assert_eq!(origin_v6.to_string(), "http://[::1]:8080");
+
+        let default_port = parse_origin("https://[2001:db8::1]/").unwrap();
+        assert_eq!(default_port.host, "2001:db8::1");
+        assert_eq!(default_port.remote_address(), "[2001:db8::1]:443");
+
+        let origin_v4 = parse_origin("http://127.0.0.1:8080").unwrap();
+        assert_eq!(origin_v4.remote_address(), "127.0.0.1:8080");
+    }
+
+    #[test]
+    fn origin_rejects_invalid_uris() {
+        assert!(matches!(
+            parse_origin("ftp://nymtech.net/file"),
+            Err(MixFetchError::UnsupportedScheme { scheme }) if scheme == "ftp"
+        ));
+        assert!(matches!(
+            parse_origin("ws://nymtech.net"),
+            Err(MixFetchError::UnsupportedScheme { .. })
+        ));
+        assert!(matches!(
+            parse_origin("/just/a/path"),
+            Err(MixFetchError::InvalidUri { .. })
+        ));
+        assert!(matches!(
+            parse_origin("nymtech.net:443"),
+            Err(MixFetchError::InvalidUri { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn checked_in_connections_are_reused() {
+        let pool = ConnectionPool::new(4, Duration::from_secs(60));
+        let origin = parse_origin("https://nymtech.net").unwrap();
+        let other = parse_origin("https://nym.com").unwrap();
+
+        assert!(pool.checkout(&origin).is_none());
+
+        let (sender, _remote) = connection().await;
+        pool.checkin(origin.clone(), sender);
+
+        // connections are only reused for the same origin
+        assert!(pool.checkout(&other).is_none());
+        assert!(pool.checkout(&origin).is_some());
+
+        // the connection has been taken out of the pool
+        assert!(pool.checkout(&origin).is_none());
+    }
+
+    #[tokio::test]
+    async fn number_of_idle_connections_is_limited() {
+        let pool = ConnectionPool::new(2, Duration::from_secs(60));
+        let origin = parse_origin("https://nymtech.net").unwrap();
+
+        let mut remotes = Vec::new();
+        for _ in 0..3 {
+            let (sender, remote) = connection().await;
+            remotes.push(remote);
+            pool.checkin(origin.clone(), sender);
+        }
+
+        assert!(pool.checkout(&origin).is_some());
+        assert!(pool.checkout(&origin).is_some());
+        assert!(pool.checkout(&origin).is_none());
+
+        // zero idle connections disable reuse altogether
+        let pool = ConnectionPool::new(0, Duration::from_secs(60));
+        let (sender, _remote) = connection().await;
+        pool.checkin(origin.clone(), sender);
+        assert!(pool.checkout(&origin).is_none());
+    }
+
+    #[tokio::test]
+    async fn expired_connections_are_not_reused() {
+        let pool = ConnectionPool::new(4, Duration::from_millis(10));
+        let origin = parse_origin("https://nymtech.net").unwrap();
+
+        let (sender, _remote) = connection().await;
+        pool.checkin(origin.clone(), sender);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(pool.checkout(&origin).is_none());
+
+        // stale connections got removed altogether
+        assert!(pool.idle.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn closed_connections_are_not_reused() {
+        let pool = ConnectionPool::new(4, Duration::from_secs(60));
+        let origin = parse_origin("https://nymtech.net").unwrap();
+
+        let (local, _remote) = tokio::io::duplex(1024);
+        let (sender, connection) =
+            hyper::client::conn::http1::handshake::<_, Full<Bytes>>(TokioIo::new(local))
+                .await
+                .unwrap();
+        drop(connection);
+        assert!(sender.is_closed());
+
+        pool.checkin(origin.clone(), sender);
+        assert!(pool.checkout(&origin).is_none());
+    }
+
+    #[tokio::test]
+    async fn clearing_the_pool() {
+        let pool = ConnectionPool::new(4, Duration::from_secs(60));
+        let origin = parse_origin("https://nymtech.net").unwrap();
+
+        let (sender, _remote) = connection().await;
+        pool.checkin(origin.clone(), sender);
+        pool.clear();
+        assert!(pool.checkout(&origin).is_none());
+    }
+}
@@ -33,13 +33,16 @@
 mod client;
 mod config;
 mod connection_state;
+mod gateway_failover;
 mod native_client;
 mod paths;
 mod socks5_client;
+mod stream;
 mod traits;
 
 pub use client::{DisconnectedMixnetClient, IncludedSurbs, MixnetClientBuilder};
 pub use config::Config;
+pub use gateway_failover::{GatewaySelectionPolicy, MixnetClientEvent};
 pub use native_client::MixnetClient;
 pub use native_client::MixnetClientSender;
 pub use nym_client_core::{
@@ -82,4 +85,8 @@ pub use nym_task::connections::TransmissionLane;
 pub use nym_topology::{provider_trait::TopologyProvider, NymTopology};
 pub use paths::StoragePaths;
 pub use socks5_client::Socks5MixnetClient;
+pub use stream::{
+    MixnetStream, MixnetStreamClient, StreamId, DEFAULT_STREAM_OPEN_SURBS,
+    DEFAULT_STREAM_PER_MESSAGE_SURBS,
+};
 pub use traits::MixnetMessageSender;
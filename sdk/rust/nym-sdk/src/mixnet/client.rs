@@ -1,6 +1,9 @@
 // Copyright 2022-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use super::gateway_failover::{
+    ActiveConnection, GatewayConnector, GatewayFailover, GatewaySelectionPolicy,
+};
 use super::{connection_state::BuilderState, Config, StoragePaths};
 use crate::bandwidth::BandwidthAcquireClient;
 use crate::mixnet::socks5_client::Socks5MixnetClient;
@@ -57,6 +60,7 @@ pub struct MixnetClientBuilder<S: MixnetClientStorage = Ephemeral> {
     custom_shutdown: Option<TaskClient>,
     force_tls: bool,
     user_agent: Option<UserAgent>,
+    gateway_selection_policy: GatewaySelectionPolicy,
 
     // TODO: incorporate it properly into `MixnetClientStorage` (I will need it in wasm anyway)
     gateway_endpoint_config_path: Option<PathBuf>,
@@ -97,6 +101,7 @@ impl MixnetClientBuilder<OnDiskPersistent> {
             custom_gateway_transceiver: None,
             force_tls: false,
             user_agent: None,
+            gateway_selection_policy: Default::default(),
         })
     }
 }
@@ -125,6 +130,7 @@ where
             custom_shutdown: None,
             force_tls: false,
             user_agent: None,
+            gateway_selection_policy: Default::default(),
             gateway_endpoint_config_path: None,
             storage,
         }
@@ -144,6 +150,7 @@ where
             custom_shutdown: self.custom_shutdown,
             force_tls: self.force_tls,
             user_agent: self.user_agent,
+            gateway_selection_policy: self.gateway_selection_policy,
             gateway_endpoint_config_path: self.gateway_endpoint_config_path,
             storage,
         }
@@ -244,6 +251,14 @@ where
         self
     }
 
+    /// Specify what the client should do once its gateway becomes persistently unreachable.
+    /// By default the client always sticks to the same gateway.
+    #[must_use]
+    pub fn with_gateway_selection_policy(mut self, policy: GatewaySelectionPolicy) -> Self {
+        self.gateway_selection_policy = policy;
+        self
+    }
+
     /// Use custom mixnet sender that might not be the default websocket gateway connection.
     /// only for advanced use
     #[must_use]
@@ -273,6 +288,7 @@ where
         client.wait_for_gateway = self.wait_for_gateway;
         client.force_tls = self.force_tls;
         client.user_agent = self.user_agent;
+        client.gateway_selection_policy = self.gateway_selection_policy;
 
         Ok(client)
     }
@@ -323,6 +339,9 @@ where
     custom_shutdown: Option<TaskClient>,
 
     user_agent: Option<UserAgent>,
+
+    /// What to do once the gateway becomes persistently unreachable.
+    gateway_selection_policy: GatewaySelectionPolicy,
 }
 
 impl<S> DisconnectedMixnetClient<S>
@@ -373,6 +392,7 @@ where
             force_tls: false,
             custom_shutdown: None,
             user_agent: None,
+            gateway_selection_policy: Default::default(),
         })
     }
 
@@ -580,7 +600,7 @@ where
         )
     }
 
-    async fn connect_to_mixnet_common(mut self) -> Result<(BaseClient, Recipient)> {
+    pub(super) async fn connect_to_mixnet_common(mut self) -> Result<(BaseClient, Recipient)> {
         self.setup_client_keys().await?;
         self.setup_gateway().await?;
 
@@ -724,6 +744,10 @@ where
         if self.socks5_config.is_some() {
             return Err(Error::Socks5Config { set: true });
         }
+        if self.gateway_selection_policy == GatewaySelectionPolicy::Failover {
+            return self.connect_to_mixnet_with_failover().await;
+        }
+
         let (mut started_client, nym_address) = self.connect_to_mixnet_common().await?;
         let client_input = started_client.client_input.register_producer();
         let mut client_output = started_client.client_output.register_consumer();
@@ -743,6 +767,40 @@ where
             None,
         ))
     }
+
+    /// Connect the client to the mixnet and keep on switching to a different gateway
+    /// whenever the current one becomes persistently unreachable.
+    async fn connect_to_mixnet_with_failover(self) -> Result<MixnetClient> {
+        if self.custom_shutdown.is_some() {
+            return Err(Error::new_unsupported(
+                "gateway failover is currently unsupported with custom shutdown",
+            ));
+        }
+        if self.custom_gateway_transceiver.is_some() {
+            return Err(Error::new_unsupported(
+                "gateway failover is currently unsupported with a custom gateway transceiver",
+            ));
+        }
+        if self.custom_topology_provider.is_some() {
+            return Err(Error::new_unsupported(
+                "gateway failover is currently unsupported with a custom topology provider",
+            ));
+        }
+
+        let connector = GatewayConnector {
+            network_details: self.config.network_details.clone(),
+            enabled_credentials_mode: self.config.enabled_credentials_mode,
+            debug_config: self.config.debug_config,
+            force_tls: self.force_tls,
+            wireguard_mode: self.wireguard_mode,
+            user_agent: self.user_agent.clone(),
+        };
+
+        let (started_client, _) = self.connect_to_mixnet_common().await?;
+        let connection = ActiveConnection::new(started_client).await?;
+
+        Ok(GatewayFailover::start(connector, connection))
+    }
 }
 
 pub enum IncludedSurbs {
@@ -0,0 +1,521 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Automatic switching to a different gateway once the current one becomes unreachable.
+//!
+//! The [`MixnetClient`] talks to the failover task through a set of channels that remain the
+//! same for its entire lifetime, while the task forwards all the traffic to (and from) whichever
+//! underlying client is currently active. Once the gateway of that client is deemed dead, it gets
+//! replaced with a fresh client registered with another gateway from the network topology.
+
+use crate::mixnet::client::MixnetClientBuilder;
+use crate::mixnet::{MixnetClient, Recipient};
+use crate::{Error, Result};
+use futures::channel::mpsc;
+use futures::StreamExt;
+use log::{debug, error, info, trace, warn};
+use nym_client_core::client::base_client::{BaseClient, ClientInput, ClientOutput, ClientState};
+use nym_client_core::client::inbound_messages::InputMessage;
+use nym_client_core::client::received_buffer::ReconstructedMessagesReceiver;
+use nym_client_core::config::DebugConfig;
+use nym_client_core::error::ClientCoreStatusMessage;
+use nym_crypto::asymmetric::identity;
+use nym_network_defaults::NymNetworkDetails;
+use nym_sphinx::receiver::ReconstructedMessage;
+use nym_task::connections::ConnectionCommand;
+use nym_task::manager::{SentStatus, TaskStatus};
+use nym_task::{StatusReceiver, TaskClient, TaskHandle};
+use nym_topology::gateway;
+use nym_validator_client::UserAgent;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use std::sync::Arc;
+
+// maximum number of gateways we're going to try to register with before giving up
+const MAX_REGISTRATION_ATTEMPTS: usize = 5;
+
+/// Determines what the client should do once its gateway becomes persistently unreachable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GatewaySelectionPolicy {
+    /// Always use the same gateway. Once it becomes unreachable, the client stops sending packets.
+    #[default]
+    Fixed,
+
+    /// Register with and switch to another gateway from the network topology once the current one
+    /// becomes unreachable.
+    ///
+    /// Note that switching the gateway changes the nym address of the client and that any
+    /// replacement connection uses fresh, ephemeral keys. It's therefore only suitable for use
+    /// cases that do not require a stable address, such as sending anonymous messages.
+    Failover,
+}
+
+/// Events emitted by the [`MixnetClient`] during its lifetime.
+#[derive(Debug, Clone)]
+pub enum MixnetClientEvent {
+    /// The client has switched to a different gateway and is now reachable under the new address.
+    GatewayChanged(Recipient),
+}
+
+/// Details of the replacement connection that the [`MixnetClient`] has to update its state with.
+pub(crate) struct GatewaySwitch {
+    pub(crate) nym_address: Recipient,
+    pub(crate) identity_keys: Arc<identity::KeyPair>,
+    pub(crate) client_output: ClientOutput,
+    pub(crate) client_state: ClientState,
+}
+
+pub(crate) type GatewaySwitchReceiver = mpsc::UnboundedReceiver<GatewaySwitch>;
+
+/// Everything that's required for registering with a new gateway.
+pub(crate) struct GatewayConnector {
+    pub(crate) network_details: NymNetworkDetails,
+    pub(crate) enabled_credentials_mode: bool,
+    pub(crate) debug_config: DebugConfig,
+    pub(crate) force_tls: bool,
+    pub(crate) wireguard_mode: bool,
+    pub(crate) user_agent: Option<UserAgent>,
+}
+
+impl GatewayConnector {
+    async fn connect(&self, gateway: identity::PublicKey) -> Result<ActiveConnection> {
+        let mut builder = MixnetClientBuilder::new_ephemeral()
+            .request_gateway(gateway.to_base58_string())
+            .network_details(self.network_details.clone())
+            .credentials_mode(self.enabled_credentials_mode)
+            .debug_config(self.debug_config)
+            .force_tls(self.force_tls)
+            .with_wireguard_mode(self.wireguard_mode);
+
+        if let Some(user_agent) = self.user_agent.clone() {
+            builder = builder.with_user_agent(user_agent);
+        }
+
+        let (started_client, _) = builder.build()?.connect_to_mixnet_common().await?;
+        ActiveConnection::new(started_client).await
+    }
+}
+
+/// The underlying client that is currently used for sending and receiving messages.
+pub(crate) struct ActiveConnection {
+    nym_address: Recipient,
+    identity_keys: Arc<identity::KeyPair>,
+    client_input: ClientInput,
+    client_output: ClientOutput,
+    client_state: ClientState,
+    reconstructed_receiver: ReconstructedMessagesReceiver,
+    status_receiver: StatusReceiver,
+    task_handle: TaskHandle,
+}
+
+impl ActiveConnection {
+    pub(crate) async fn new(mut started_client: BaseClient) -> Result<Self> {
+        let TaskHandle::Internal(task_manager) = &mut started_client.task_handle else {
+            return Err(Error::new_unsupported(
+                "gateway failover is currently unsupported with custom shutdown",
+            ));
+        };
+
+        // that's how we're going to learn about the gateway failures
+        let (status_sender, status_receiver) = mpsc::channel(128);
+        task_manager
+            .start_status_listener(status_sender, TaskStatus::Ready)
+            .await;
+
+        let client_input = started_client.client_input.register_producer();
+        let mut client_output = started_client.client_output.register_consumer();
+        let reconstructed_receiver = client_output.register_receiver()?;
+
+        Ok(ActiveConnection {
+            nym_address: started_client.address,
+            identity_keys: started_client.identity_keys,
+            client_input,
+            client_output,
+            client_state: started_client.client_state,
+            reconstructed_receiver,
+            status_receiver,
+            task_handle: started_client.task_handle,
+        })
+    }
+
+    async fn disconnect(mut self) {
+        if let TaskHandle::Internal(task_manager) = &mut self.task_handle {
+            task_manager.signal_shutdown().ok();
+            task_manager.wait_for_shutdown().await;
+        }
+    }
+}
+
+/// Selects the gateways that could replace the failed one. Similarly to the initial gateway selection,
+/// gateways whose performance is known to be below the configured minimum are not considered.
+fn filter_replacement_candidates(
+    gateways: &[gateway::Node],
+    failed_gateways: &[identity::PublicKey],
+    force_tls: bool,
+    minimum_performance: u8,
+) -> Vec<identity::PublicKey> {
+    gateways
+        .iter()
+        .filter(|gateway| !failed_gateways.contains(&gateway.identity_key))
+        .filter(|gateway| !force_tls || gateway.clients_wss_port.is_some())
+        .filter(|gateway| {
+            gateway
+                .performance
+                .map(|performance| performance.round_to_integer() >= minimum_performance)
+                .unwrap_or(true)
+        })
+        .map(|gateway| gateway.identity_key)
+        .collect()
+}
+
+fn is_gateway_failure(status: &SentStatus) -> bool {
+    matches!(
+        status.downcast_ref::<ClientCoreStatusMessage>(),
+        Some(ClientCoreStatusMessage::GatewayIsUnreachable { .. })
+    )
+}
+
+pub(crate) struct GatewayFailover {
+    connector: GatewayConnector,
+    connection: ActiveConnection,
+    failed_gateways: Vec<identity::PublicKey>,
+
+    input_receiver: tokio::sync::mpsc::Receiver<InputMessage>,
+    connection_command_receiver: mpsc::UnboundedReceiver<ConnectionCommand>,
+    reconstructed_sender: mpsc::UnboundedSender<Vec<ReconstructedMessage>>,
+    gateway_switch_sender: mpsc::UnboundedSender<GatewaySwitch>,
+}
+
+impl GatewayFailover {
+    /// Starts the failover task on top of the provided connection and returns the [`MixnetClient`]
+    /// that is going to use it.
+    pub(crate) fn start(connector: GatewayConnector, connection: ActiveConnection) -> MixnetClient {
+        let (input_sender, input_receiver) = tokio::sync::mpsc::channel(1);
+        let (connection_command_sender, connection_command_receiver) = mpsc::unbounded();
+        let (reconstructed_sender, reconstructed_receiver) = mpsc::unbounded();
+        let (gateway_switch_sender, gateway_switch_receiver) = mpsc::unbounded();
+
+        let task_handle = TaskHandle::default().named("GatewayFailover");
+        let shutdown = task_handle.get_handle();
+        let client = MixnetClient::new(
+            connection.nym_address,
+            Arc::clone(&connection.identity_keys),
+            ClientInput {
+                connection_command_sender,
+                input_sender,
+            },
+            connection.client_output.clone(),
+            connection.client_state.clone(),
+            reconstructed_receiver,
+            task_handle,
+            None,
+        )
+        .with_gateway_switches(gateway_switch_receiver);
+
+        let failover = GatewayFailover {
+            connector,
+            connection,
+            failed_gateways: Vec::new(),
+            input_receiver,
+            connection_command_receiver,
+            reconstructed_sender,
+            gateway_switch_sender,
+        };
+        tokio::spawn(failover.run(shutdown));
+
+        client
+    }
+
+    /// Returns the gateways from the current network topology that we could switch to.
+    async fn replacement_candidates(&self) -> Vec<identity::PublicKey> {
+        let Some(topology) = self
+            .connection
+            .client_state
+            .topology_accessor
+            .current_topology()
+            .await
+        else {
+            return Vec::new();
+        };
+
+        let mut candidates = filter_replacement_candidates(
+            topology.gateways(),
+            &self.failed_gateways,
+            self.connector.force_tls,
+            self.connector
+                .debug_config
+                .topology
+                .minimum_gateway_performance,
+        );
+        candidates.shuffle(&mut OsRng);
+        candidates
+    }
+
+    async fn switch_gateway(&mut self) -> Result<()> {
+        let failed = *self.connection.nym_address.gateway();
+        warn!("gateway {failed} is unreachable - attempting to switch to a different one");
+        self.failed_gateways.push(failed);
+
+        let mut candidates = self.replacement_candidates().await;
+        candidates.truncate(MAX_REGISTRATION_ATTEMPTS);
+
+        for gateway in candidates {
+            let connection = match self.connector.connect(gateway).await {
+                Ok(connection) => connection,
+                Err(err) => {
+                    warn!("failed to switch to gateway {gateway}: {err}");
+                    self.failed_gateways.push(gateway);
+                    continue;
+                }
+            };
+
+            let previous = std::mem::replace(&mut self.connection, connection);
+            previous.disconnect().await;
+
+            info!(
+                "switched to gateway {gateway}. The new address of this client is: {}",
+                self.connection.nym_address
+            );
+            let switch = GatewaySwitch {
+                nym_address: self.connection.nym_address,
+                identity_keys: Arc::clone(&self.connection.identity_keys),
+                client_output: self.connection.client_output.clone(),
+                client_state: self.connection.client_state.clone(),
+            };
+            if self.gateway_switch_sender.unbounded_send(switch).is_err() {
+                trace!("the mixnet client is no longer interested in gateway switches")
+            }
+            return Ok(());
+        }
+
+        Err(Error::NoReplacementGateway)
+    }
+
+    async fn forward_input(&mut self, input: InputMessage) {
+        if self.connection.client_input.send(input).await.is_err() {
+            warn!("the underlying client is no longer accepting input messages")
+        }
+    }
+
+    fn forward_connection_command(&mut self, command: ConnectionCommand) {
+        if self
+            .connection
+            .client_input
+            .connection_command_sender
+            .unbounded_send(command)
+            .is_err()
+        {
+            warn!("the underlying client is no longer accepting connection commands")
+        }
+    }
+
+    fn forward_reconstructed(&mut self, messages: Vec<ReconstructedMessage>) {
+        if self.reconstructed_sender.unbounded_send(messages).is_err() {
+            trace!("the mixnet client is no longer interested in received messages")
+        }
+    }
+
+    async fn run(mut self, mut shutdown: TaskClient) {
+        debug!("Started GatewayFailover with graceful shutdown support");
+
+        while !shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("GatewayFailover: Received shutdown");
+                }
+                Some(status) = self.connection.status_receiver.next() => {
+                    if !is_gateway_failure(&status) {
+                        continue;
+                    }
+                    if let Err(err) = self.switch_gateway().await {
+                        error!("failed to switch to a different gateway: {err}");
+                        break;
+                    }
+                }
+                input = self.input_receiver.recv() => match input {
+                    Some(input) => self.forward_input(input).await,
+                    None => {
+                        trace!("GatewayFailover: Stopping since input channel closed");
+                        break;
+                    }
+                },
+                Some(command) = self.connection_command_receiver.next() => {
+                    self.forward_connection_command(command)
+                }
+                Some(messages) = self.connection.reconstructed_receiver.next() => {
+                    self.forward_reconstructed(messages)
+                }
+            }
+        }
+
+        self.connection.disconnect().await;
+        debug!("GatewayFailover: Exiting");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::channel::mpsc::UnboundedReceiver;
+    use nym_client_core::client::base_client::GatewayConnection;
+    use nym_client_core::client::received_buffer::ReceivedBufferMessage;
+    use nym_client_core::client::replies::reply_controller::requests::ReplyControllerSender;
+    use nym_crypto::asymmetric::encryption;
+    use nym_mixnet_contract_common::reward_params::Performance;
+    use nym_task::connections::LaneQueueLengths;
+    use nym_topology::NodeVersion;
+
+    fn gateway(wss_port: Option<u16>, performance: Option<u64>) -> gateway::Node {
+        gateway::Node {
+            host: "1.2.3.4".parse().unwrap(),
+            mix_host: "1.2.3.4:1789".parse().unwrap(),
+            clients_ws_port: 9000,
+            clients_wss_port: wss_port,
+            identity_key: *identity::KeyPair::new(&mut OsRng).public_key(),
+            sphinx_key: *encryption::KeyPair::new(&mut OsRng).public_key(),
+            stake: None,
+            performance: performance
+                .map(|performance| Performance::from_percentage_value(performance).unwrap()),
+            sphinx_key_rotation: None,
+            owner: None,
+            version: NodeVersion::Unknown,
+        }
+    }
+
+    fn address() -> Recipient {
+        Recipient::new(
+            *identity::KeyPair::new(&mut OsRng).public_key(),
+            *encryption::KeyPair::new(&mut OsRng).public_key(),
+            *identity::KeyPair::new(&mut OsRng).public_key(),
+        )
+    }
+
+    fn client_output() -> (ClientOutput, UnboundedReceiver<ReceivedBufferMessage>) {
+        let (received_buffer_request_sender, receiver) = mpsc::unbounded();
+        (
+            ClientOutput {
+                received_buffer_request_sender,
+            },
+            receiver,
+        )
+    }
+
+    fn client_state() -> ClientState {
+        ClientState {
+            shared_lane_queue_lengths: LaneQueueLengths::new(),
+            reply_controller_sender: ReplyControllerSender::from(mpsc::unbounded().0),
+            topology_accessor: Default::default(),
+            gateway_connection: GatewayConnection {
+                gateway_ws_fd: None,
+            },
+        }
+    }
+
+    #[test]
+    fn failed_gateways_are_not_replacement_candidates() {
+        let gateways = vec![
+            gateway(None, None),
+            gateway(None, None),
+            gateway(None, None),
+        ];
+        let failed = vec![gateways[0].identity_key, gateways[2].identity_key];
+
+        let candidates = filter_replacement_candidates(&gateways, &failed, false, 0);
+        assert_eq!(candidates, vec![gateways[1].identity_key]);
+
+        let candidates = filter_replacement_candidates(&gateways, &[], false, 0);
+        assert_eq!(candidates.len(), 3);
+    }
+
+    #[test]
+    fn forcing_tls_requires_wss_support() {
+        let gateways = vec![gateway(None, None), gateway(Some(9001), None)];
+
+        let candidates = filter_replacement_candidates(&gateways, &[], true, 0);
+        assert_eq!(candidates, vec![gateways[1].identity_key]);
+
+        let candidates = filter_replacement_candidates(&gateways, &[], false, 0);
+        assert_eq!(candidates.len(), 2);
+    }
+
+    #[test]
+    fn underperforming_gateways_are_not_replacement_candidates() {
+        let gateways = vec![
+            gateway(None, Some(10)),
+            gateway(None, Some(49)),
+            gateway(None, Some(50)),
+            gateway(None, Some(100)),
+            // we have no information about this one
+            gateway(None, None),
+        ];
+
+        let candidates = filter_replacement_candidates(&gateways, &[], false, 50);
+        assert_eq!(
+            candidates,
+            vec![
+                gateways[2].identity_key,
+                gateways[3].identity_key,
+                gateways[4].identity_key
+            ]
+        );
+
+        let candidates = filter_replacement_candidates(&gateways, &[], false, 0);
+        assert_eq!(candidates.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn gateway_switch_replaces_the_client_state() {
+        let (old_output, mut old_output_receiver) = client_output();
+        let (_, reconstructed_receiver) = mpsc::unbounded();
+        let (switch_sender, switch_receiver) = mpsc::unbounded();
+        let (connection_command_sender, _) = mpsc::unbounded();
+        let (input_sender, _) = tokio::sync::mpsc::channel(1);
+
+        let mut client = MixnetClient::new(
+            address(),
+            Arc::new(identity::KeyPair::new(&mut OsRng)),
+            ClientInput {
+                connection_command_sender,
+                input_sender,
+            },
+            old_output,
+            client_state(),
+            reconstructed_receiver,
+            TaskHandle::default(),
+            None,
+        )
+        .with_gateway_switches(switch_receiver);
+
+        let new_address = address();
+        let new_keys = Arc::new(identity::KeyPair::new(&mut OsRng));
+        let (new_output, mut new_output_receiver) = client_output();
+        switch_sender
+            .unbounded_send(GatewaySwitch {
+                nym_address: new_address,
+                identity_keys: Arc::clone(&new_keys),
+                client_output: new_output,
+                client_state: client_state(),
+            })
+            .unwrap();
+
+        let Some(MixnetClientEvent::GatewayChanged(changed)) = client.next_event().await else {
+            panic!("expected a gateway change")
+        };
+        assert_eq!(changed, new_address);
+        assert_eq!(client.nym_address(), &new_address);
+        assert_eq!(client.identity_keys.public_key(), new_keys.public_key());
+
+        // receivers are now registered with the output of the new connection
+        client.client_output.register_receiver().unwrap();
+        assert!(matches!(
+            new_output_receiver.try_next(),
+            Ok(Some(ReceivedBufferMessage::ReceiverAnnounce(_)))
+        ));
+        assert!(!matches!(old_output_receiver.try_next(), Ok(Some(_))));
+
+        // once the failover task is gone, no further events are emitted
+        drop(switch_sender);
+        assert!(client.next_event().await.is_none());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::mixnet::client::MixnetClientBuilder;
+use crate::mixnet::gateway_failover::{GatewaySwitch, GatewaySwitchReceiver, MixnetClientEvent};
+use crate::mixnet::stream::MixnetStreamClient;
 use crate::mixnet::traits::MixnetMessageSender;
 use crate::{Error, Result};
 use async_trait::async_trait;
@@ -7,7 +9,7 @@ use log::error;
 use nym_client_core::client::base_client::GatewayConnection;
 use nym_client_core::client::{
     base_client::{ClientInput, ClientOutput, ClientState},
-    inbound_messages::InputMessage,
+    inbound_messages::{InputMessage, InputMessageSender},
     received_buffer::ReconstructedMessagesReceiver,
 };
 use nym_crypto::asymmetric::identity;
@@ -18,6 +20,7 @@ use nym_task::{
     TaskHandle,
 };
 use nym_topology::NymTopology;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -49,6 +52,12 @@ pub struct MixnetClient {
     pub(crate) task_handle: TaskHandle,
     pub(crate) packet_type: Option<PacketType>,
 
+    /// Notifications about the gateway changes performed by the failover task, if enabled.
+    pub(crate) gateway_switches: Option<GatewaySwitchReceiver>,
+
+    // events that have already been applied, but not yet returned to the user
+    _pending_events: VecDeque<MixnetClientEvent>,
+
     // internal state used for the `Stream` implementation
     _buffered: Vec<ReconstructedMessage>,
 }
@@ -74,10 +83,42 @@ impl MixnetClient {
             reconstructed_receiver,
             task_handle,
             packet_type,
+            gateway_switches: None,
+            _pending_events: VecDeque::new(),
             _buffered: Vec::new(),
         }
     }
 
+    #[must_use]
+    pub(crate) fn with_gateway_switches(mut self, gateway_switches: GatewaySwitchReceiver) -> Self {
+        self.gateway_switches = Some(gateway_switches);
+        self
+    }
+
+    fn apply_gateway_switch(&mut self, switch: GatewaySwitch) -> MixnetClientEvent {
+        self.nym_address = switch.nym_address;
+        self.identity_keys = switch.identity_keys;
+        self.client_output = switch.client_output;
+        self.client_state = switch.client_state;
+        MixnetClientEvent::GatewayChanged(switch.nym_address)
+    }
+
+    /// Applies all gateway changes that have happened since the last check.
+    fn apply_gateway_switches(&mut self) {
+        let Some(gateway_switches) = self.gateway_switches.as_mut() else {
+            return;
+        };
+
+        let mut switches = Vec::new();
+        while let Ok(Some(switch)) = gateway_switches.try_next() {
+            switches.push(switch);
+        }
+        for switch in switches {
+            let event = self.apply_gateway_switch(switch);
+            self._pending_events.push_back(event);
+        }
+    }
+
     /// Create a new client and connect to the mixnet using ephemeral in-memory keys that are
     /// discarded at application close.
     ///
@@ -101,6 +142,10 @@ impl MixnetClient {
 
     /// Get the nym address for this client, if it is available. The nym address is composed of the
     /// client identity, the client encryption key, and the gateway identity.
+    ///
+    /// With the [`GatewaySelectionPolicy::Failover`](crate::mixnet::GatewaySelectionPolicy::Failover)
+    /// policy, the address changes whenever the client switches to a different gateway. The change is
+    /// picked up once the client waits for messages or events.
     pub fn nym_address(&self) -> &Recipient {
         &self.nym_address
     }
@@ -130,6 +175,12 @@ impl MixnetClient {
         }
     }
 
+    /// Convert this client into a [`MixnetStreamClient`] that exchanges data with other clients
+    /// using [`MixnetStream`](crate::mixnet::MixnetStream)s rather than individual messages.
+    pub fn into_stream_client(self) -> MixnetStreamClient {
+        MixnetStreamClient::new(self)
+    }
+
     /// Get a shallow clone of [`ConnectionCommandSender`]. This is useful if you want to e.g
     /// explicitly close a transmission lane that is still sending data even though it should
     /// cancel.
@@ -164,9 +215,23 @@ impl MixnetClient {
 
     /// Wait for messages from the mixnet
     pub async fn wait_for_messages(&mut self) -> Option<Vec<ReconstructedMessage>> {
+        self.apply_gateway_switches();
         self.reconstructed_receiver.next().await
     }
 
+    /// Wait for the next event concerning this client, such as a change of its gateway.
+    /// Returns `None` once no further events are going to be emitted, which is immediately
+    /// the case if gateway failover is not enabled.
+    pub async fn next_event(&mut self) -> Option<MixnetClientEvent> {
+        self.apply_gateway_switches();
+        if let Some(event) = self._pending_events.pop_front() {
+            return Some(event);
+        }
+
+        let switch = self.gateway_switches.as_mut()?.next().await?;
+        Some(self.apply_gateway_switch(switch))
+    }
+
     /// Provide a callback to execute on incoming messages from the mixnet.
     pub async fn on_messages<F>(&mut self, fun: F)
     where
@@ -198,10 +263,36 @@ pub struct MixnetClientSender {
     packet_type: Option<PacketType>,
 }
 
+impl MixnetClientSender {
+    pub(crate) fn input_sender(&self) -> InputMessageSender {
+        self.client_input.input_sender.clone()
+    }
+
+    /// Creates a sender that is not attached to any client alongside the receiver of all the
+    /// messages sent through it.
+    #[cfg(test)]
+    pub(crate) fn new_detached() -> (
+        Self,
+        nym_client_core::client::inbound_messages::InputMessageReceiver,
+    ) {
+        let (input_sender, input_receiver) = tokio::sync::mpsc::channel(128);
+        let (connection_command_sender, _) = futures::channel::mpsc::unbounded();
+        let sender = MixnetClientSender {
+            client_input: ClientInput {
+                connection_command_sender,
+                input_sender,
+            },
+            packet_type: None,
+        };
+        (sender, input_receiver)
+    }
+}
+
 impl Stream for MixnetClient {
     type Item = ReconstructedMessage;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.apply_gateway_switches();
         if let Some(next) = self._buffered.pop() {
             cx.waker().wake_by_ref();
             return Poll::Ready(Some(next));
@@ -0,0 +1,757 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reliable, ordered byte streams on top of the message-oriented mixnet.
+//!
+//! Each stream is identified by a random id chosen by its initiator. Every frame sent on the stream
+//! carries a sequence number so that the receiving side can restore the original order of the data
+//! regardless of the order in which the underlying messages got delivered.
+//!
+//! The initiator attaches reply SURBs to all of its frames and the responder only ever replies
+//! using them, so the responder never learns the initiator's address.
+
+use crate::mixnet::native_client::MixnetClientSender;
+use crate::mixnet::{AnonymousSenderTag, MixnetClient, MixnetMessageSender, Recipient};
+use crate::Result;
+use futures::StreamExt;
+use log::{debug, trace, warn};
+use nym_client_core::client::inbound_messages::InputMessage;
+use nym_ordered_buffer::OrderedMessageBuffer;
+use nym_sphinx::params::PacketType;
+use nym_sphinx::receiver::ReconstructedMessage;
+use nym_task::connections::TransmissionLane;
+use rand::RngCore;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_util::sync::PollSender;
+
+pub type StreamId = u64;
+
+/// Version of the stream framing. It's the first byte of every stream frame.
+const STREAM_PROTOCOL_VERSION: u8 = 1;
+
+// VERSION || KIND || STREAM_ID || SEQ
+const FRAME_HEADER_LEN: usize = 1 + 1 + 8 + 8;
+
+/// Number of reply SURBs attached to the frame opening a new stream.
+pub const DEFAULT_STREAM_OPEN_SURBS: u32 = 20;
+
+/// Number of reply SURBs attached to each subsequent frame sent by the stream initiator.
+pub const DEFAULT_STREAM_PER_MESSAGE_SURBS: u32 = 3;
+
+// number of ids of recently closed streams we keep track of
+// so that any late frames would not be treated as new streams
+const MAX_RECENTLY_CLOSED: usize = 1024;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    Open = 0,
+    Data = 1,
+    Close = 2,
+}
+
+impl TryFrom<u8> for FrameKind {
+    type Error = u8;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            _ if value == FrameKind::Open as u8 => Ok(FrameKind::Open),
+            _ if value == FrameKind::Data as u8 => Ok(FrameKind::Data),
+            _ if value == FrameKind::Close as u8 => Ok(FrameKind::Close),
+            other => Err(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StreamFrame {
+    kind: FrameKind,
+    stream_id: StreamId,
+    seq: u64,
+    data: Vec<u8>,
+}
+
+impl StreamFrame {
+    fn into_bytes(self) -> Vec<u8> {
+        [STREAM_PROTOCOL_VERSION, self.kind as u8]
+            .into_iter()
+            .chain(self.stream_id.to_be_bytes())
+            .chain(self.seq.to_be_bytes())
+            .chain(self.data)
+            .collect()
+    }
+
+    fn try_from_bytes(b: &[u8]) -> Option<Self> {
+        if b.len() < FRAME_HEADER_LEN || b[0] != STREAM_PROTOCOL_VERSION {
+            return None;
+        }
+        let kind = FrameKind::try_from(b[1]).ok()?;
+        // the unwraps are fine as we've checked the length of the header
+        let stream_id = StreamId::from_be_bytes(b[2..10].try_into().unwrap());
+        let seq = u64::from_be_bytes(b[10..18].try_into().unwrap());
+
+        Some(StreamFrame {
+            kind,
+            stream_id,
+            seq,
+            data: b[FRAME_HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// The other side of the stream.
+#[derive(Clone, Copy)]
+enum StreamRemote {
+    /// We have initiated the stream and know the address of the responder.
+    Recipient { address: Recipient, surbs: u32 },
+
+    /// The stream was opened by somebody else and we can only reply using the received SURBs.
+    Anonymous(AnonymousSenderTag),
+}
+
+#[derive(Default)]
+struct StreamRegistry {
+    streams: HashMap<StreamId, mpsc::UnboundedSender<StreamFrame>>,
+    recently_closed: VecDeque<StreamId>,
+}
+
+impl StreamRegistry {
+    fn remove(&mut self, stream_id: StreamId) {
+        if self.streams.remove(&stream_id).is_some() {
+            if self.recently_closed.len() >= MAX_RECENTLY_CLOSED {
+                self.recently_closed.pop_front();
+            }
+            self.recently_closed.push_back(stream_id);
+        }
+    }
+
+    fn was_closed(&self, stream_id: StreamId) -> bool {
+        self.recently_closed.contains(&stream_id)
+    }
+}
+
+type SharedStreamRegistry = Arc<Mutex<StreamRegistry>>;
+
+/// A bidirectional, ordered byte stream with another Nym client.
+///
+/// Data written to the stream is split into mixnet messages that are reordered on the receiving
+/// side. Shutting down the write half informs the remote that no more data is going to be sent,
+/// after which any reads on its side will return EOF.
+pub struct MixnetStream {
+    id: StreamId,
+    remote: StreamRemote,
+    packet_type: Option<PacketType>,
+
+    sender: PollSender<InputMessage>,
+    inbound: mpsc::UnboundedReceiver<StreamFrame>,
+    registry: SharedStreamRegistry,
+
+    next_outbound_seq: u64,
+    ordered_buffer: OrderedMessageBuffer,
+    read_buffer: Vec<u8>,
+
+    // sequence number of the received close frame
+    remote_close_seq: Option<u64>,
+    remote_closed: bool,
+    local_closed: bool,
+}
+
+impl Debug for MixnetStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MixnetStream")
+            .field("id", &self.id)
+            .field("remote_closed", &self.remote_closed)
+            .field("local_closed", &self.local_closed)
+            .finish()
+    }
+}
+
+impl MixnetStream {
+    fn new(
+        id: StreamId,
+        remote: StreamRemote,
+        sender: &MixnetClientSender,
+        inbound: mpsc::UnboundedReceiver<StreamFrame>,
+        registry: SharedStreamRegistry,
+        next_outbound_seq: u64,
+    ) -> Self {
+        MixnetStream {
+            id,
+            remote,
+            packet_type: sender.packet_type(),
+            sender: PollSender::new(sender.input_sender()),
+            inbound,
+            registry,
+            next_outbound_seq,
+            ordered_buffer: OrderedMessageBuffer::new(),
+            read_buffer: Vec::new(),
+            remote_close_seq: None,
+            remote_closed: false,
+            local_closed: false,
+        }
+    }
+
+    /// Returns the id of this stream.
+    pub fn id(&self) -> StreamId {
+        self.id
+    }
+
+    fn next_frame(&mut self, kind: FrameKind, data: Vec<u8>) -> InputMessage {
+        let frame = StreamFrame {
+            kind,
+            stream_id: self.id,
+            seq: self.next_outbound_seq,
+            data,
+        };
+        self.next_outbound_seq += 1;
+        make_input_message(self.remote, self.id, frame, self.packet_type)
+    }
+
+    fn on_frame(&mut self, frame: StreamFrame) {
+        trace!(
+            "stream {}: received {:?} frame with sequence {}",
+            self.id,
+            frame.kind,
+            frame.seq
+        );
+        if frame.kind == FrameKind::Close {
+            self.remote_close_seq = Some(frame.seq);
+        }
+
+        // open and close frames do not carry any data, but they still occupy a sequence number
+        if let Err(err) = self.ordered_buffer.write(frame.seq, frame.data) {
+            warn!("stream {}: failed to buffer received frame: {err}", self.id);
+            return;
+        }
+
+        if let Some(contiguous) = self.ordered_buffer.read() {
+            self.read_buffer.extend_from_slice(&contiguous.data);
+            if let Some(close_seq) = self.remote_close_seq {
+                if contiguous.last_sequence >= close_seq {
+                    self.remote_closed = true;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for MixnetStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if !this.read_buffer.is_empty() {
+                let available = this.read_buffer.len().min(buf.remaining());
+                buf.put_slice(&this.read_buffer[..available]);
+                this.read_buffer.drain(..available);
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.remote_closed {
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(this.inbound.poll_recv(cx)) {
+                Some(frame) => this.on_frame(frame),
+                None => {
+                    // the underlying client has shut down
+                    this.remote_closed = true;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncWrite for MixnetStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.local_closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        ready!(this.sender.poll_reserve(cx))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        let message = this.next_frame(FrameKind::Data, buf.to_vec());
+        this.sender
+            .send_item(message)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // all written data is immediately handed over to the client
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.local_closed {
+            return Poll::Ready(Ok(()));
+        }
+
+        ready!(this.sender.poll_reserve(cx))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        let message = this.next_frame(FrameKind::Close, Vec::new());
+        this.sender
+            .send_item(message)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        this.local_closed = true;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for MixnetStream {
+    fn drop(&mut self) {
+        debug!("stream {} is getting closed", self.id);
+        if !self.local_closed {
+            // best effort attempt at letting the remote know we're done
+            let message = self.next_frame(FrameKind::Close, Vec::new());
+            if let Some(sender) = self.sender.get_ref() {
+                if sender.try_send(message).is_err() {
+                    debug!(
+                        "failed to inform the remote about closing stream {}",
+                        self.id
+                    )
+                }
+            }
+        }
+        self.registry
+            .lock()
+            .expect("stream registry lock got poisoned")
+            .remove(self.id);
+    }
+}
+
+fn make_input_message(
+    remote: StreamRemote,
+    stream_id: StreamId,
+    frame: StreamFrame,
+    packet_type: Option<PacketType>,
+) -> InputMessage {
+    let lane = TransmissionLane::ConnectionId(stream_id);
+    match remote {
+        StreamRemote::Recipient { address, surbs } => {
+            InputMessage::new_anonymous(address, frame.into_bytes(), surbs, lane, packet_type)
+        }
+        StreamRemote::Anonymous(sender_tag) => {
+            InputMessage::new_reply(sender_tag, frame.into_bytes(), lane, packet_type)
+        }
+    }
+}
+
+/// A [`MixnetClient`] that exchanges data with other clients using [`MixnetStream`]s rather than
+/// individual messages.
+///
+/// # Example
+///
+/// ```no_run
+/// use nym_sdk::mixnet;
+/// use tokio::io::AsyncWriteExt;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = mixnet::MixnetClient::connect_new().await.unwrap();
+///     let streams = client.into_stream_client();
+///
+///     let recipient = mixnet::Recipient::try_from_base58_string("foobar").unwrap();
+///     let mut stream = streams.open_stream(recipient).await.unwrap();
+///     stream.write_all(b"hello").await.unwrap();
+///     stream.shutdown().await.unwrap();
+/// }
+/// ```
+pub struct MixnetStreamClient {
+    nym_address: Recipient,
+    sender: MixnetClientSender,
+    registry: SharedStreamRegistry,
+    incoming: mpsc::UnboundedReceiver<MixnetStream>,
+
+    open_surbs: u32,
+    per_message_surbs: u32,
+
+    router_shutdown: oneshot::Sender<()>,
+    router_handle: JoinHandle<()>,
+}
+
+impl MixnetStreamClient {
+    pub(crate) fn new(client: MixnetClient) -> Self {
+        let nym_address = *client.nym_address();
+        let sender = client.split_sender();
+        let registry = SharedStreamRegistry::default();
+        let (incoming_sender, incoming) = mpsc::unbounded_channel();
+        let (router_shutdown, shutdown_receiver) = oneshot::channel();
+
+        let router = StreamRouter {
+            client,
+            dispatcher: FrameDispatcher {
+                sender: sender.clone(),
+                registry: registry.clone(),
+                incoming: incoming_sender,
+            },
+        };
+        let router_handle = tokio::spawn(router.run(shutdown_receiver));
+
+        MixnetStreamClient {
+            nym_address,
+            sender,
+            registry,
+            incoming,
+            open_surbs: DEFAULT_STREAM_OPEN_SURBS,
+            per_message_surbs: DEFAULT_STREAM_PER_MESSAGE_SURBS,
+            router_shutdown,
+            router_handle,
+        }
+    }
+
+    /// Specify the number of reply SURBs attached to the frames of the streams we open.
+    #[must_use]
+    pub fn with_reply_surbs(mut self, open_surbs: u32, per_message_surbs: u32) -> Self {
+        self.open_surbs = open_surbs;
+        self.per_message_surbs = per_message_surbs;
+        self
+    }
+
+    /// Get the nym address of the underlying client.
+    pub fn nym_address(&self) -> &Recipient {
+        &self.nym_address
+    }
+
+    /// Opens a new stream to the specified recipient.
+    /// Our own address is never revealed to the recipient as it only ever replies using SURBs.
+    pub async fn open_stream(&self, recipient: Recipient) -> Result<MixnetStream> {
+        let stream_id = rand::rngs::OsRng.next_u64();
+        let (frame_sender, frame_receiver) = mpsc::unbounded_channel();
+        self.registry
+            .lock()
+            .expect("stream registry lock got poisoned")
+            .streams
+            .insert(stream_id, frame_sender);
+
+        let open = StreamFrame {
+            kind: FrameKind::Open,
+            stream_id,
+            seq: 0,
+            data: Vec::new(),
+        };
+        let open_remote = StreamRemote::Recipient {
+            address: recipient,
+            surbs: self.open_surbs,
+        };
+        let message = make_input_message(open_remote, stream_id, open, self.sender.packet_type());
+
+        // create the stream before sending the frame so that it would get cleaned up on failure
+        let stream = MixnetStream::new(
+            stream_id,
+            StreamRemote::Recipient {
+                address: recipient,
+                surbs: self.per_message_surbs,
+            },
+            &self.sender,
+            frame_receiver,
+            self.registry.clone(),
+            1,
+        );
+        self.sender.send(message).await?;
+
+        debug!("opened stream {stream_id} to {recipient}");
+        Ok(stream)
+    }
+
+    /// Wait for the next stream opened by a remote client.
+    /// Returns `None` if the underlying client has shut down.
+    pub async fn accept(&mut self) -> Option<MixnetStream> {
+        self.incoming.recv().await
+    }
+
+    /// Disconnect from the mixnet. All existing streams will get closed.
+    pub async fn disconnect(self) {
+        // if the router has already stopped, there's nothing to signal
+        self.router_shutdown.send(()).ok();
+        if let Err(err) = self.router_handle.await {
+            warn!("the stream router has panicked: {err}")
+        }
+    }
+}
+
+/// Dispatches the frames received by the client to the appropriate streams.
+struct FrameDispatcher {
+    sender: MixnetClientSender,
+    registry: SharedStreamRegistry,
+    incoming: mpsc::UnboundedSender<MixnetStream>,
+}
+
+impl FrameDispatcher {
+    fn on_message(&self, message: ReconstructedMessage) {
+        let Some(frame) = StreamFrame::try_from_bytes(&message.message) else {
+            debug!("received a message that is not a stream frame - dropping it");
+            return;
+        };
+
+        let mut registry = self
+            .registry
+            .lock()
+            .expect("stream registry lock got poisoned");
+
+        if let Some(stream) = registry.streams.get(&frame.stream_id) {
+            let stream_id = frame.stream_id;
+            if stream.send(frame).is_err() {
+                registry.remove(stream_id)
+            }
+            return;
+        }
+
+        // this might be a new stream. note that the open frame might get overtaken by any other
+        // frame, including the close one, so the stream is created on whichever arrives first
+        if registry.was_closed(frame.stream_id) {
+            trace!("received a frame for a closed stream {}", frame.stream_id);
+            return;
+        }
+        let Some(sender_tag) = message.sender_tag else {
+            warn!(
+                "received a frame for an unknown stream {} without any reply SURBs",
+                frame.stream_id
+            );
+            return;
+        };
+
+        let stream_id = frame.stream_id;
+        let (frame_sender, frame_receiver) = mpsc::unbounded_channel();
+        // this can't fail as we're holding the receiver
+        frame_sender.send(frame).ok();
+        registry.streams.insert(stream_id, frame_sender);
+        drop(registry);
+
+        debug!("accepted new stream {stream_id}");
+        let stream = MixnetStream::new(
+            stream_id,
+            StreamRemote::Anonymous(sender_tag),
+            &self.sender,
+            frame_receiver,
+            self.registry.clone(),
+            0,
+        );
+        if self.incoming.send(stream).is_err() {
+            debug!("the stream client is no longer accepting new streams")
+        }
+    }
+}
+
+/// Routes the messages received by the client to the [`FrameDispatcher`] until shutdown.
+struct StreamRouter {
+    client: MixnetClient,
+    dispatcher: FrameDispatcher,
+}
+
+impl StreamRouter {
+    async fn run(mut self, mut shutdown: oneshot::Receiver<()>) {
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut shutdown => {
+                    debug!("StreamRouter: received shutdown");
+                    break
+                }
+                message = self.client.next() => match message {
+                    Some(message) => self.dispatcher.on_message(message),
+                    None => {
+                        debug!("StreamRouter: the client has stopped");
+                        break
+                    }
+                }
+            }
+        }
+
+        // close all the streams
+        self.dispatcher
+            .registry
+            .lock()
+            .expect("stream registry lock got poisoned")
+            .streams
+            .clear();
+        self.client.disconnect().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_client_core::client::inbound_messages::InputMessageReceiver;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn frame(kind: FrameKind, stream_id: StreamId, seq: u64, data: &[u8]) -> StreamFrame {
+        StreamFrame {
+            kind,
+            stream_id,
+            seq,
+            data: data.to_vec(),
+        }
+    }
+
+    fn dispatcher() -> (
+        FrameDispatcher,
+        mpsc::UnboundedReceiver<MixnetStream>,
+        InputMessageReceiver,
+    ) {
+        let (sender, input_receiver) = MixnetClientSender::new_detached();
+        let (incoming, incoming_receiver) = mpsc::unbounded_channel();
+        let dispatcher = FrameDispatcher {
+            sender,
+            registry: Default::default(),
+            incoming,
+        };
+        (dispatcher, incoming_receiver, input_receiver)
+    }
+
+    fn reply(frame: StreamFrame, sender_tag: AnonymousSenderTag) -> ReconstructedMessage {
+        ReconstructedMessage {
+            message: frame.into_bytes(),
+            sender_tag: Some(sender_tag),
+        }
+    }
+
+    fn sent_frame(message: InputMessage) -> StreamFrame {
+        let InputMessage::Reply { data, .. } = message else {
+            panic!("unexpected message variant")
+        };
+        StreamFrame::try_from_bytes(&data).unwrap()
+    }
+
+    #[test]
+    fn frame_encoding_roundtrip() {
+        let original = frame(FrameKind::Data, 12345, 42, b"hello world");
+        let bytes = original.clone().into_bytes();
+        assert_eq!(bytes.len(), FRAME_HEADER_LEN + 11);
+        assert_eq!(StreamFrame::try_from_bytes(&bytes).unwrap(), original);
+
+        let empty = frame(FrameKind::Close, u64::MAX, 0, &[]);
+        assert_eq!(
+            StreamFrame::try_from_bytes(&empty.clone().into_bytes()).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn malformed_frames_are_rejected() {
+        let bytes = frame(FrameKind::Open, 1, 0, b"foo").into_bytes();
+
+        assert!(StreamFrame::try_from_bytes(&bytes[..FRAME_HEADER_LEN - 1]).is_none());
+
+        let mut bad_version = bytes.clone();
+        bad_version[0] = STREAM_PROTOCOL_VERSION + 1;
+        assert!(StreamFrame::try_from_bytes(&bad_version).is_none());
+
+        let mut bad_kind = bytes;
+        bad_kind[1] = 42;
+        assert!(StreamFrame::try_from_bytes(&bad_kind).is_none());
+    }
+
+    #[tokio::test]
+    async fn out_of_order_frames_are_reassembled() {
+        let (dispatcher, mut incoming, _input) = dispatcher();
+        let tag = AnonymousSenderTag::new_random(&mut rand::rngs::OsRng);
+
+        // the open frame got overtaken by the data frames
+        dispatcher.on_message(reply(frame(FrameKind::Data, 1, 2, b" world"), tag));
+        dispatcher.on_message(reply(frame(FrameKind::Data, 1, 1, b"hello"), tag));
+        dispatcher.on_message(reply(frame(FrameKind::Open, 1, 0, &[]), tag));
+        dispatcher.on_message(reply(frame(FrameKind::Close, 1, 3, &[]), tag));
+
+        let mut stream = incoming.try_recv().unwrap();
+        assert_eq!(stream.id(), 1);
+        // only a single stream got created
+        assert!(incoming.try_recv().is_err());
+
+        let mut received = Vec::new();
+        stream.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received, b"hello world");
+    }
+
+    #[tokio::test]
+    async fn close_frame_arriving_first_opens_the_stream() {
+        let (dispatcher, mut incoming, _input) = dispatcher();
+        let tag = AnonymousSenderTag::new_random(&mut rand::rngs::OsRng);
+
+        dispatcher.on_message(reply(frame(FrameKind::Close, 1, 2, &[]), tag));
+        dispatcher.on_message(reply(frame(FrameKind::Data, 1, 1, b"foomp"), tag));
+        dispatcher.on_message(reply(frame(FrameKind::Open, 1, 0, &[]), tag));
+
+        let mut stream = incoming.try_recv().unwrap();
+        let mut received = Vec::new();
+        stream.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received, b"foomp");
+    }
+
+    #[tokio::test]
+    async fn frames_of_closed_streams_are_ignored() {
+        let (dispatcher, mut incoming, _input) = dispatcher();
+        let tag = AnonymousSenderTag::new_random(&mut rand::rngs::OsRng);
+
+        dispatcher.on_message(reply(frame(FrameKind::Open, 1, 0, &[]), tag));
+        let stream = incoming.try_recv().unwrap();
+        drop(stream);
+
+        // late frame of the stream we've already closed
+        dispatcher.on_message(reply(frame(FrameKind::Data, 1, 1, b"foomp"), tag));
+        assert!(incoming.try_recv().is_err());
+        assert!(dispatcher.registry.lock().unwrap().streams.is_empty());
+    }
+
+    #[tokio::test]
+    async fn frames_without_reply_surbs_do_not_open_streams() {
+        let (dispatcher, mut incoming, _input) = dispatcher();
+
+        dispatcher.on_message(ReconstructedMessage {
+            message: frame(FrameKind::Open, 1, 0, &[]).into_bytes(),
+            sender_tag: None,
+        });
+        assert!(incoming.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn written_data_and_shutdown_are_framed() {
+        let (dispatcher, mut incoming, mut input) = dispatcher();
+        let tag = AnonymousSenderTag::new_random(&mut rand::rngs::OsRng);
+
+        dispatcher.on_message(reply(frame(FrameKind::Open, 7, 0, &[]), tag));
+        let mut stream = incoming.try_recv().unwrap();
+
+        stream.write_all(b"foo").await.unwrap();
+        stream.write_all(b"bar").await.unwrap();
+        stream.shutdown().await.unwrap();
+        assert!(stream.write_all(b"baz").await.is_err());
+
+        assert_eq!(
+            sent_frame(input.recv().await.unwrap()),
+            frame(FrameKind::Data, 7, 0, b"foo")
+        );
+        assert_eq!(
+            sent_frame(input.recv().await.unwrap()),
+            frame(FrameKind::Data, 7, 1, b"bar")
+        );
+        assert_eq!(
+            sent_frame(input.recv().await.unwrap()),
+            frame(FrameKind::Close, 7, 2, &[])
+        );
+
+        // no additional close frame is sent on drop
+        drop(stream);
+        assert!(input.try_recv().is_err());
+    }
+}
@@ -13,6 +13,7 @@ use std::{
     io,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 use url::Url;
 
@@ -27,6 +28,8 @@ mod template;
 
 const DEFAULT_IP_PACKET_ROUTER_DIR: &str = "ip-packet-router";
 
+pub const DEFAULT_EXIT_POLICY_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
 /// Derive default path to ip packet routers' config directory.
 /// It should get resolved to `$HOME/.nym/service-providers/ip-packet-router/<id>/config`
 pub fn default_config_directory<P: AsRef<Path>>(id: P) -> PathBuf {
@@ -193,6 +196,11 @@ pub struct IpPacketRouter {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
+
+    /// Specifies how often the exit policy should be refreshed from its upstream.
+    /// Setting it to zero disables the refreshing.
+    #[serde(with = "humantime_serde")]
+    pub exit_policy_refresh_interval: Duration,
 }
 
 impl Default for IpPacketRouter {
@@ -204,6 +212,7 @@ impl Default for IpPacketRouter {
                     .parse()
                     .expect("invalid default exit policy URL"),
             ),
+            exit_policy_refresh_interval: DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
         }
     }
 }
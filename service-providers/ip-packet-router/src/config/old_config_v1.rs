@@ -3,7 +3,9 @@
 
 use crate::config::persistence::IpPacketRouterPaths;
 use crate::config::Config;
-use crate::config::{default_config_filepath, IpPacketRouter};
+use crate::config::{
+    default_config_filepath, IpPacketRouter, DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
+};
 use crate::error::IpPacketRouterError;
 use nym_bin_common::logging::LoggingSettings;
 use nym_client_core::config::disk_persistence::old_v1_1_33::CommonClientPathsV1_1_33;
@@ -90,6 +92,7 @@ impl From<IpPacketRouterV1> for IpPacketRouter {
         IpPacketRouter {
             disable_poisson_rate: value.disable_poisson_rate,
             upstream_exit_policy_url: value.upstream_exit_policy_url,
+            exit_policy_refresh_interval: DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
         }
     }
 }
@@ -166,7 +166,10 @@ impl IpPacketRouter {
         tun_listener.start();
 
         let request_filter = request_filter::RequestFilter::new(&self.config).await?;
-        request_filter.start_update_tasks().await;
+        request_filter.start_update_tasks(
+            self.config.ip_packet_router.exit_policy_refresh_interval,
+            task_handle.fork("exit_policy_refresher"),
+        );
 
         let mixnet_listener = mixnet_listener::MixnetListener {
             _config: self.config,
@@ -2,17 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use crate::error::IpPacketRouterError;
-use nym_exit_policy::client::get_exit_policy;
+use nym_exit_policy::upstream::{SharedExitPolicy, UpstreamExitPolicy};
 use nym_exit_policy::ExitPolicy;
 use reqwest::IntoUrl;
-use url::Url;
 
 pub struct ExitPolicyRequestFilter {
-    #[allow(unused)]
-    upstream: Option<Url>,
-    policy: ExitPolicy,
+    policy: SharedExitPolicy,
 }
 
 impl ExitPolicyRequestFilter {
@@ -22,31 +20,26 @@ impl ExitPolicyRequestFilter {
             .map_err(|source| IpPacketRouterError::MalformedExitPolicyUpstreamUrl { source })?;
 
         Ok(ExitPolicyRequestFilter {
-            upstream: Some(url.clone()),
-            policy: get_exit_policy(url).await?,
+            policy: Arc::new(UpstreamExitPolicy::new_upstream(url).await?),
         })
     }
 
     #[allow(unused)]
     pub(crate) fn new(policy: ExitPolicy) -> Self {
         ExitPolicyRequestFilter {
-            upstream: None,
-            policy,
+            policy: Arc::new(UpstreamExitPolicy::new_from_policy(policy)),
         }
     }
 
-    #[allow(unused)]
-    pub fn policy(&self) -> &ExitPolicy {
+    /// The exit policy used by this filter. Any refreshes of the policy are immediately
+    /// visible to all of its holders.
+    pub fn shared_policy(&self) -> &SharedExitPolicy {
         &self.policy
     }
 
-    #[allow(unused)]
-    pub fn upstream(&self) -> Option<&Url> {
-        self.upstream.as_ref()
-    }
-
     pub(crate) async fn check(&self, addr: &SocketAddr) -> Result<bool, IpPacketRouterError> {
         self.policy
+            .policy()
             .allows_sockaddr(addr)
             .ok_or(IpPacketRouterError::AddressNotCoveredByExitPolicy { addr: *addr })
     }
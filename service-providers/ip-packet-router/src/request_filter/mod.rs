@@ -5,13 +5,16 @@ use crate::config::Config;
 use crate::error::IpPacketRouterError;
 use crate::request_filter::exit_policy::ExitPolicyRequestFilter;
 use log::{info, warn};
+use nym_exit_policy::upstream::ExitPolicyRefresher;
+use nym_task::TaskClient;
+use std::time::Duration;
 use std::{net::SocketAddr, sync::Arc};
 
 pub mod exit_policy;
 
 enum RequestFilterInner {
     ExitPolicy {
-        policy_filter: ExitPolicyRequestFilter,
+        policy_filter: Arc<ExitPolicyRequestFilter>,
     },
 }
 
@@ -29,14 +32,23 @@ impl RequestFilter {
     #[allow(unused)]
     pub fn current_exit_policy_filter(&self) -> Option<&ExitPolicyRequestFilter> {
         match &*self.inner {
-            RequestFilterInner::ExitPolicy { policy_filter } => Some(policy_filter),
+            RequestFilterInner::ExitPolicy { policy_filter } => Some(policy_filter.as_ref()),
         }
     }
 
-    pub(crate) async fn start_update_tasks(&self) {
+    /// Starts the task periodically refreshing the exit policy from its upstream, if applicable.
+    /// Zero interval disables the refreshing.
+    pub(crate) fn start_update_tasks(&self, refresh_interval: Duration, mut shutdown: TaskClient) {
         match &*self.inner {
-            RequestFilterInner::ExitPolicy { .. } => {
-                // nothing to do for the exit policy (yet; we might add a refresher at some point)
+            RequestFilterInner::ExitPolicy { policy_filter } => {
+                let policy = policy_filter.shared_policy();
+                if policy.upstream().is_none() || refresh_interval.is_zero() {
+                    shutdown.disarm();
+                    return;
+                }
+
+                info!("the exit policy is going to be refreshed every {refresh_interval:?}");
+                ExitPolicyRefresher::new(Arc::clone(policy), refresh_interval, shutdown).start()
             }
         }
     }
@@ -47,7 +59,8 @@ impl RequestFilter {
             .upstream_exit_policy_url
             .as_ref()
             .ok_or(IpPacketRouterError::NoUpstreamExitPolicy)?;
-        let policy_filter = ExitPolicyRequestFilter::new_upstream(upstream_url.clone()).await?;
+        let policy_filter =
+            Arc::new(ExitPolicyRequestFilter::new_upstream(upstream_url.clone()).await?);
         Ok(RequestFilter {
             inner: Arc::new(RequestFilterInner::ExitPolicy { policy_filter }),
         })
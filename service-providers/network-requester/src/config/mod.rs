@@ -37,6 +37,10 @@ const DEFAULT_NETWORK_REQUESTERS_DIR: &str = "network-requester";
 
 pub const DEFAULT_STANDARD_LIST_UPDATE_INTERVAL: Duration = Duration::from_secs(30 * 60);
 
+pub const DEFAULT_EXIT_POLICY_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+pub const DEFAULT_UDP_SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// Derive default path to network requester's config directory.
 /// It should get resolved to `$HOME/.nym/service-providers/network-requester/<id>/config`
 pub fn default_config_directory<P: AsRef<Path>>(id: P) -> PathBuf {
@@ -216,6 +220,11 @@ pub struct NetworkRequester {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
+
+    /// Specifies how often the exit policy should be refreshed from its upstream.
+    /// Setting it to zero disables the refreshing.
+    #[serde(with = "humantime_serde")]
+    pub exit_policy_refresh_interval: Duration,
 }
 
 impl Default for NetworkRequester {
@@ -228,6 +237,7 @@ impl Default for NetworkRequester {
                     .parse()
                     .expect("invalid default exit policy URL"),
             ),
+            exit_policy_refresh_interval: DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
         }
     }
 }
@@ -239,12 +249,17 @@ pub struct Debug {
     /// Deprecated
     #[serde(with = "humantime_serde")]
     pub standard_list_update_interval: Duration,
+
+    /// Specifies how long an UDP session can remain without any traffic before it gets closed.
+    #[serde(with = "humantime_serde")]
+    pub udp_session_idle_timeout: Duration,
 }
 
 impl Default for Debug {
     fn default() -> Self {
         Debug {
             standard_list_update_interval: DEFAULT_STANDARD_LIST_UPDATE_INTERVAL,
+            udp_session_idle_timeout: DEFAULT_UDP_SESSION_IDLE_TIMEOUT,
         }
     }
 }
@@ -4,7 +4,10 @@
 use crate::config::persistence::old::v3::NetworkRequesterPathsV3;
 use crate::config::persistence::NetworkRequesterPaths;
 use crate::config::Config;
-use crate::config::{default_config_filepath, Debug, NetworkRequester};
+use crate::config::{
+    default_config_filepath, Debug, NetworkRequester, DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
+    DEFAULT_UDP_SESSION_IDLE_TIMEOUT,
+};
 use nym_bin_common::logging::LoggingSettings;
 use nym_client_core::config::Config as BaseClientConfig;
 use nym_config::read_config_from_toml_file;
@@ -114,6 +117,7 @@ impl From<NetworkRequesterV5> for NetworkRequester {
             open_proxy: value.open_proxy,
             disable_poisson_rate: value.disable_poisson_rate,
             upstream_exit_policy_url: value.upstream_exit_policy_url,
+            exit_policy_refresh_interval: DEFAULT_EXIT_POLICY_REFRESH_INTERVAL,
         }
     }
 }
@@ -131,6 +135,7 @@ impl From<DebugV5> for Debug {
     fn from(value: DebugV5) -> Self {
         Debug {
             standard_list_update_interval: value.standard_list_update_interval,
+            udp_session_idle_timeout: DEFAULT_UDP_SESSION_IDLE_TIMEOUT,
         }
     }
 }
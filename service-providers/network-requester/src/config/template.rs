@@ -88,6 +88,10 @@ disable_poisson_rate = {{ network_requester.disable_poisson_rate }}
 # Specifies the url for an upstream source of the exit policy used by this node.
 upstream_exit_policy_url = '{{ network_requester.upstream_exit_policy_url }}'
 
+# Specifies how often the exit policy should be refreshed from its upstream.
+# Setting it to zero disables the refreshing.
+exit_policy_refresh_interval = '{{ network_requester.exit_policy_refresh_interval }}'
+
 ##### logging configuration options #####
 
 [logging]
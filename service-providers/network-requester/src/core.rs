@@ -25,9 +25,9 @@ use nym_socks5_proxy_helpers::connection_controller::{
 };
 use nym_socks5_proxy_helpers::proxy_runner::{MixProxyReader, MixProxySender};
 use nym_socks5_requests::{
-    ConnectRequest, ConnectionId, QueryRequest, QueryResponse, SendRequest, SocketData,
-    Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request, Socks5RequestContent,
-    Socks5Response,
+    BindRequest, BindStatus, ConnectRequest, ConnectionId, DatagramRequest, QueryRequest,
+    QueryResponse, SendRequest, SocketData, Socks5ProtocolVersion, Socks5ProviderRequest,
+    Socks5Request, Socks5RequestContent, Socks5Response,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
@@ -79,6 +79,7 @@ pub struct NRServiceProvider {
     controller_sender: ControllerSender,
 
     mix_input_sender: MixProxySender<MixnetMessage>,
+    udp_sessions: socks5::udp::UdpSessions,
     shutdown: TaskHandle,
 }
 
@@ -139,7 +140,11 @@ impl ServiceProvider<Socks5Request> for NRServiceProvider {
                 self.handle_proxy_connect(request_version, sender, req)
                     .await
             }
+            Socks5RequestContent::Bind(req) => self.handle_proxy_bind(request_version, sender, req),
             Socks5RequestContent::Send(req) => self.handle_proxy_send(req),
+            Socks5RequestContent::Datagram(req) => {
+                self.handle_datagram(request_version, sender, req)
+            }
             Socks5RequestContent::Query(query) => return self.handle_query(query),
         }
 
@@ -278,6 +283,10 @@ impl NRServiceProviderBuilder {
         });
 
         let request_filter = RequestFilter::new(&self.config).await?;
+        request_filter.start_update_tasks(
+            self.config.network_requester.exit_policy_refresh_interval,
+            shutdown.fork("exit_policy_refresher"),
+        );
 
         let mut service_provider = NRServiceProvider {
             config: self.config,
@@ -285,6 +294,9 @@ impl NRServiceProviderBuilder {
             mixnet_client,
             controller_sender,
             mix_input_sender,
+            udp_sessions: socks5::udp::UdpSessions::new(
+                self.config.network_requester_debug.udp_session_idle_timeout,
+            ),
             shutdown,
         };
 
@@ -380,7 +392,7 @@ impl NRServiceProvider {
         lane_queue_lengths: LaneQueueLengths,
         mut shutdown: TaskClient,
     ) {
-        let mut conn = match socks5::tcp::Connection::new(
+        let conn = match socks5::tcp::Connection::new(
             connection_id,
             remote_addr.clone(),
             return_address.clone(),
@@ -409,7 +421,33 @@ impl NRServiceProvider {
             }
         };
 
-        // Connect implies it's a fresh connection - register it with our controller
+        Self::run_connection_proxy(
+            remote_version,
+            connection_id,
+            remote_addr,
+            conn,
+            biggest_packet_size,
+            controller_sender,
+            mix_input_sender,
+            lane_queue_lengths,
+            shutdown,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_connection_proxy(
+        remote_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        remote_addr: String,
+        mut conn: socks5::tcp::Connection,
+        biggest_packet_size: PacketSize,
+        controller_sender: ControllerSender,
+        mix_input_sender: MixProxySender<MixnetMessage>,
+        lane_queue_lengths: LaneQueueLengths,
+        shutdown: TaskClient,
+    ) {
+        // it's a fresh connection - register it with our controller
         let (mix_sender, mix_receiver) = mpsc::unbounded();
         controller_sender
             .unbounded_send(ControllerCommand::Insert {
@@ -515,12 +553,278 @@ impl NRServiceProvider {
         });
     }
 
+    async fn send_connection_error(
+        mix_input_sender: &MixProxySender<MixnetMessage>,
+        return_address: reply::MixnetAddress,
+        remote_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        error_message: String,
+    ) {
+        let error_msg = MixnetMessage::new_connection_error(
+            return_address,
+            remote_version,
+            connection_id,
+            error_message,
+        );
+        mix_input_sender
+            .send(error_msg)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn start_bind_proxy(
+        remote_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        expected_peer: String,
+        return_address: reply::MixnetAddress,
+        biggest_packet_size: PacketSize,
+        controller_sender: ControllerSender,
+        mix_input_sender: MixProxySender<MixnetMessage>,
+        lane_queue_lengths: LaneQueueLengths,
+        mut shutdown: TaskClient,
+    ) {
+        let listener = match tokio::net::lookup_host(&expected_peer)
+            .await
+            .map(|mut addrs| addrs.next())
+        {
+            Ok(Some(peer)) => socks5::bind::BoundListener::bind(peer)
+                .await
+                .and_then(|listener| listener.local_addr().map(|addr| (listener, addr)))
+                .map_err(|err| format!("failed to bind the listening socket: {err}")),
+            Ok(None) => Err(format!("could not resolve {expected_peer}")),
+            Err(err) => Err(format!("could not resolve {expected_peer}: {err}")),
+        };
+
+        let (listener, bound_address) = match listener {
+            Ok(bound) => bound,
+            Err(err) => {
+                log::info!("{err}");
+                shutdown.disarm();
+                Self::send_connection_error(
+                    &mix_input_sender,
+                    return_address,
+                    remote_version,
+                    connection_id,
+                    err,
+                )
+                .await;
+                return;
+            }
+        };
+
+        log::info!("listening on {bound_address} for an inbound connection from {expected_peer}");
+        let listening_msg = MixnetMessage::new_bind_response(
+            return_address.clone(),
+            remote_version.clone(),
+            connection_id,
+            BindStatus::Listening,
+            bound_address.to_string(),
+        );
+        mix_input_sender
+            .send(listening_msg)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+
+        let (stream, peer) = match listener.accept(&mut shutdown).await {
+            Ok(Some(accepted)) => accepted,
+            Ok(None) => return,
+            Err(err) => {
+                let log_msg = format!("failed to accept the inbound connection: {err}");
+                log::info!("{log_msg}");
+                shutdown.disarm();
+                Self::send_connection_error(
+                    &mix_input_sender,
+                    return_address,
+                    remote_version,
+                    connection_id,
+                    log_msg,
+                )
+                .await;
+                return;
+            }
+        };
+
+        let accepted_msg = MixnetMessage::new_bind_response(
+            return_address.clone(),
+            remote_version.clone(),
+            connection_id,
+            BindStatus::Accepted,
+            peer.to_string(),
+        );
+        mix_input_sender
+            .send(accepted_msg)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+
+        let remote_addr = peer.to_string();
+        let conn = socks5::tcp::Connection::from_stream(
+            connection_id,
+            remote_addr.clone(),
+            stream,
+            return_address,
+        );
+        Self::run_connection_proxy(
+            remote_version,
+            connection_id,
+            remote_addr,
+            conn,
+            biggest_packet_size,
+            controller_sender,
+            mix_input_sender,
+            lane_queue_lengths,
+            shutdown,
+        )
+        .await
+    }
+
+    fn handle_proxy_bind(
+        &self,
+        remote_version: RequestVersion<Socks5Request>,
+        sender_tag: Option<AnonymousSenderTag>,
+        bind_req: Box<BindRequest>,
+    ) {
+        let Some(return_address) = reply::MixnetAddress::new(bind_req.return_address, sender_tag)
+        else {
+            log::warn!(
+                "attempted to bind a socket with no way of returning data back to the sender"
+            );
+            return;
+        };
+
+        let expected_peer = bind_req.remote_addr;
+        let conn_id = bind_req.conn_id;
+        let traffic_config = self.config.base.debug.traffic;
+        let packet_size = traffic_config
+            .secondary_packet_size
+            .unwrap_or(traffic_config.primary_packet_size);
+
+        let controller_sender_clone = self.controller_sender.clone();
+        let mix_input_sender_clone = self.mix_input_sender.clone();
+        let lane_queue_lengths_clone = self.mixnet_client.shared_lane_queue_lengths();
+        let mut shutdown = self.shutdown.get_handle();
+
+        // we're just cloning the underlying pointer, nothing expensive is happening here
+        let request_filter = self.request_filter.clone();
+
+        // waiting for the inbound connection might take a while,
+        // so it definitely has to happen in a separate task
+        tokio::spawn(async move {
+            // the peer is going to be able to exchange data with the client same as a connect
+            // target would, so it has to be permitted by the exit policy
+            if !request_filter.check_address(&expected_peer).await {
+                let log_msg = format!("Domain {expected_peer:?} failed filter check");
+                log::info!("{log_msg}");
+                shutdown.mark_as_success();
+                Self::send_connection_error(
+                    &mix_input_sender_clone,
+                    return_address,
+                    remote_version,
+                    conn_id,
+                    log_msg,
+                )
+                .await;
+                return;
+            }
+
+            Self::start_bind_proxy(
+                remote_version,
+                conn_id,
+                expected_peer,
+                return_address,
+                packet_size,
+                controller_sender_clone,
+                mix_input_sender_clone,
+                lane_queue_lengths_clone,
+                shutdown,
+            )
+            .await
+        });
+    }
+
     fn handle_proxy_send(&mut self, req: SendRequest) {
         self.controller_sender
             .unbounded_send(ControllerCommand::new_send(req.data))
             .unwrap()
     }
 
+    fn handle_datagram(
+        &self,
+        remote_version: RequestVersion<Socks5Request>,
+        sender_tag: Option<AnonymousSenderTag>,
+        datagram_req: Box<DatagramRequest>,
+    ) {
+        let DatagramRequest {
+            association_id,
+            remote_addr,
+            return_address,
+            data,
+        } = *datagram_req;
+
+        let Some(return_address) = reply::MixnetAddress::new(return_address, sender_tag) else {
+            log::warn!(
+                "attempted to relay a datagram with no way of returning data back to the sender"
+            );
+            return;
+        };
+
+        let udp_sessions = self.udp_sessions.clone();
+        let mix_input_sender = self.mix_input_sender.clone();
+        // the handle is only used if a new session has to be started,
+        // and the sessions are allowed to finish on their own
+        let mut shutdown = self.shutdown.get_handle();
+        shutdown.mark_as_success();
+
+        // we're just cloning the underlying pointer, nothing expensive is happening here
+        let request_filter = self.request_filter.clone();
+
+        // same as with connect requests, resolving the address might take a while,
+        // so don't block other incoming requests
+        tokio::spawn(async move {
+            let log_msg = if !request_filter.check_address(&remote_addr).await {
+                format!("Domain {remote_addr:?} failed filter check")
+            } else {
+                match tokio::net::lookup_host(&remote_addr)
+                    .await
+                    .map(|mut addrs| addrs.next())
+                {
+                    Ok(Some(remote)) => {
+                        let context = || socks5::udp::UdpSessionContext {
+                            remote_version: remote_version.clone(),
+                            return_address: return_address.clone(),
+                            mix_sender: mix_input_sender.clone(),
+                            shutdown,
+                        };
+                        match udp_sessions.send_datagram(
+                            return_address.clone(),
+                            association_id,
+                            remote,
+                            data,
+                            context,
+                        ) {
+                            Ok(_) => return,
+                            Err(err) => format!("failed to start UDP session: {err}"),
+                        }
+                    }
+                    Ok(None) => format!("could not resolve {remote_addr}"),
+                    Err(err) => format!("could not resolve {remote_addr}: {err}"),
+                }
+            };
+
+            log::info!("{log_msg}");
+            let error_msg = MixnetMessage::new_connection_error(
+                return_address,
+                remote_version,
+                association_id,
+                log_msg,
+            );
+            mix_input_sender
+                .send(error_msg)
+                .await
+                .expect("InputMessageReceiver has stopped receiving!");
+        });
+    }
+
     fn handle_query(
         &self,
         query: QueryRequest,
@@ -537,14 +841,17 @@ impl NRServiceProvider {
                 QueryResponse::Description("Description (placeholder)".to_string()),
             ),
             QueryRequest::ExitPolicy => {
-                let exit_policy_filter = self.request_filter.current_exit_policy_filter();
+                let exit_policy = self
+                    .request_filter
+                    .current_exit_policy_filter()
+                    .shared_policy();
                 let response = QueryResponse::ExitPolicy {
                     enabled: true,
-                    upstream: exit_policy_filter
+                    upstream: exit_policy
                         .upstream()
                         .map(|u| u.to_string())
                         .unwrap_or_default(),
-                    policy: Some(exit_policy_filter.policy().clone()),
+                    policy: Some(exit_policy.policy().as_ref().clone()),
                 };
 
                 Socks5Response::new_query(protocol_version, response)
@@ -6,14 +6,16 @@ use nym_service_providers_common::interface::{
     ControlRequest, ControlResponse, ProviderInterfaceVersion, RequestVersion,
 };
 use nym_socks5_requests::{
-    ConnectionId, SocketData, Socks5ProviderRequest, Socks5ProviderResponse, Socks5Request,
-    Socks5RequestContent, Socks5Response, Socks5ResponseContent,
+    BindStatus, ConnectionId, RemoteAddress, SocketData, Socks5ProviderRequest,
+    Socks5ProviderResponse, Socks5Request, Socks5RequestContent, Socks5Response,
+    Socks5ResponseContent,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
 use nym_sphinx::params::PacketType;
 use nym_task::connections::TransmissionLane;
 use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 
 /// Generic data this service provider will send back to the mixnet via its connected native client.
 /// It includes serialized socks5 proxy responses to its connected clients
@@ -133,6 +135,44 @@ impl MixnetMessage {
         Self::new_provider_response(address, connection_id, msg)
     }
 
+    pub(crate) fn new_datagram_response(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        association_id: ConnectionId,
+        source_addr: RemoteAddress,
+        data: Vec<u8>,
+    ) -> Self {
+        let res = Socks5Response::new_datagram(
+            request_version.provider_protocol,
+            association_id,
+            source_addr,
+            data,
+        );
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, association_id, msg)
+    }
+
+    pub(crate) fn new_bind_response(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        status: BindStatus,
+        bind_address: RemoteAddress,
+    ) -> Self {
+        let res = Socks5Response::new_bind(
+            request_version.provider_protocol,
+            connection_id,
+            status,
+            bind_address,
+        );
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, connection_id, msg)
+    }
+
     // TODO: the naming is awful, but naming things is difficult...
     pub(crate) fn new_network_data_response_content(
         address: MixnetAddress,
@@ -159,11 +199,26 @@ impl MixnetMessage {
 
 /// A return address is a way to send a message back to the original sender. It can be either
 /// an explicitly known Recipient, or a surb AnonymousSenderTag.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MixnetAddress {
     Known(Box<Recipient>),
     Anonymous(AnonymousSenderTag),
 }
+
+impl Hash for MixnetAddress {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            MixnetAddress::Known(recipient) => {
+                0u8.hash(state);
+                recipient.to_bytes().hash(state)
+            }
+            MixnetAddress::Anonymous(sender_tag) => {
+                1u8.hash(state);
+                sender_tag.hash(state)
+            }
+        }
+    }
+}
 impl MixnetAddress {
     pub fn new(
         explicit_return_address: Option<Recipient>,
@@ -192,6 +247,7 @@ impl MixnetAddress {
                     data: message,
                     lane: TransmissionLane::ConnectionId(connection_id),
                     mix_hops: None,
+                    forward_error_correction: None,
                 }),
                 packet_type,
             },
@@ -4,16 +4,15 @@
 use crate::config::Config;
 use crate::error::NetworkRequesterError;
 use log::trace;
-use nym_exit_policy::client::get_exit_policy;
+use nym_exit_policy::upstream::{SharedExitPolicy, UpstreamExitPolicy};
 use nym_exit_policy::ExitPolicy;
 use nym_socks5_requests::RemoteAddress;
 use reqwest::IntoUrl;
+use std::sync::Arc;
 use tokio::net::lookup_host;
-use url::Url;
 
 pub struct ExitPolicyRequestFilter {
-    upstream: Option<Url>,
-    policy: ExitPolicy,
+    policy: SharedExitPolicy,
 }
 
 impl From<ExitPolicy> for ExitPolicyRequestFilter {
@@ -29,8 +28,7 @@ impl ExitPolicyRequestFilter {
             .map_err(|source| NetworkRequesterError::MalformedExitPolicyUpstreamUrl { source })?;
 
         Ok(ExitPolicyRequestFilter {
-            upstream: Some(url.clone()),
-            policy: get_exit_policy(url).await?,
+            policy: Arc::new(UpstreamExitPolicy::new_upstream(url).await?),
         })
     }
 
@@ -50,19 +48,16 @@ impl ExitPolicyRequestFilter {
 
     pub fn new_from_policy(policy: ExitPolicy) -> Self {
         ExitPolicyRequestFilter {
-            upstream: None,
-            policy,
+            policy: Arc::new(UpstreamExitPolicy::new_from_policy(policy)),
         }
     }
 
-    pub fn policy(&self) -> &ExitPolicy {
+    /// The exit policy used by this filter. Any refreshes of the policy are immediately
+    /// visible to all of its holders.
+    pub fn shared_policy(&self) -> &SharedExitPolicy {
         &self.policy
     }
 
-    pub fn upstream(&self) -> Option<&Url> {
-        self.upstream.as_ref()
-    }
-
     pub(crate) async fn check(
         &self,
         remote: &RemoteAddress,
@@ -86,11 +81,12 @@ impl ExitPolicyRequestFilter {
 
         trace!("{remote} has been resolved to {addrs:?}");
 
+        let policy = self.policy.policy();
+
         // if the remote decided to give us an address that can resolve to multiple socket addresses,
         // they'd better make sure all of them are allowed by the exit policy.
         for addr in addrs {
-            if !self
-                .policy
+            if !policy
                 .allows_sockaddr(&addr)
                 .ok_or(NetworkRequesterError::AddressNotCoveredByExitPolicy { addr })?
             {
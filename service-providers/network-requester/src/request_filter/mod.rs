@@ -3,9 +3,12 @@
 
 use crate::config::Config;
 use crate::error::NetworkRequesterError;
-use log::warn;
+use log::{info, warn};
+use nym_exit_policy::upstream::ExitPolicyRefresher;
 use nym_socks5_requests::RemoteAddress;
+use nym_task::TaskClient;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub mod exit_policy;
 
@@ -27,6 +30,19 @@ impl RequestFilter {
         &self.inner
     }
 
+    /// Starts the task periodically refreshing the exit policy from its upstream, if applicable.
+    /// Zero interval disables the refreshing.
+    pub(crate) fn start_update_tasks(&self, refresh_interval: Duration, mut shutdown: TaskClient) {
+        let policy = self.inner.shared_policy();
+        if policy.upstream().is_none() || refresh_interval.is_zero() {
+            shutdown.disarm();
+            return;
+        }
+
+        info!("the exit policy is going to be refreshed every {refresh_interval:?}");
+        ExitPolicyRefresher::new(Arc::clone(policy), refresh_interval, shutdown).start()
+    }
+
     pub(crate) async fn check_address(&self, address: &RemoteAddress) -> bool {
         self.inner.check(address).await.unwrap_or_else(|err| {
             warn!("failed to validate '{address}' against the exit policy: {err}");
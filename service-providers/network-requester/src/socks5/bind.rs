@@ -0,0 +1,83 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use log::{debug, trace};
+use nym_task::TaskClient;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{sleep_until, Instant};
+
+/// Specifies how long the bound socket is going to wait for the inbound connection before giving up.
+const BIND_ACCEPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Determines the address of the local interface via which the provided peer is reachable,
+/// so that the bound socket could be announced to the client with a meaningful address.
+fn local_address_towards(peer: SocketAddr) -> io::Result<IpAddr> {
+    let unspecified = match peer {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+
+    // 'connecting' an UDP socket does not send anything, it just makes the OS pick the route
+    let probe = std::net::UdpSocket::bind(SocketAddr::new(unspecified, 0))?;
+    probe.connect(peer)?;
+    Ok(probe.local_addr()?.ip())
+}
+
+/// A listening socket opened on behalf of a client's SOCKS5 BIND request,
+/// waiting for a single inbound connection from the expected peer.
+pub(crate) struct BoundListener {
+    listener: TcpListener,
+    expected_peer: IpAddr,
+}
+
+impl BoundListener {
+    pub(crate) async fn bind(expected_peer: SocketAddr) -> io::Result<Self> {
+        let local_ip = local_address_towards(expected_peer)?;
+        let listener = TcpListener::bind(SocketAddr::new(local_ip, 0)).await?;
+
+        Ok(BoundListener {
+            listener,
+            expected_peer: expected_peer.ip(),
+        })
+    }
+
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for the inbound connection from the expected peer. Connections from any other hosts
+    /// are rejected, as recommended by RFC 1928.
+    /// Returns `None` if a shutdown signal has been received in the meantime.
+    pub(crate) async fn accept(
+        self,
+        shutdown: &mut TaskClient,
+    ) -> io::Result<Option<(TcpStream, SocketAddr)>> {
+        let deadline = Instant::now() + BIND_ACCEPT_TIMEOUT;
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("BoundListener: received shutdown");
+                    return Ok(None)
+                }
+                accepted = self.listener.accept() => {
+                    let (stream, peer) = accepted?;
+                    if peer.ip() == self.expected_peer {
+                        return Ok(Some((stream, peer)))
+                    }
+                    debug!("rejecting inbound connection from unexpected peer {peer} (expected {})", self.expected_peer);
+                }
+                _ = sleep_until(deadline) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!("no inbound connection has been received within {BIND_ACCEPT_TIMEOUT:?}"),
+                    ))
+                }
+            }
+        }
+    }
+}
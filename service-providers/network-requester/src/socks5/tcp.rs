@@ -32,12 +32,22 @@ impl Connection {
     ) -> io::Result<Self> {
         let conn = TcpStream::connect(&address).await?;
 
-        Ok(Connection {
+        Ok(Self::from_stream(id, address, conn, return_address))
+    }
+
+    /// Wraps an already established connection, such as one accepted on a bound socket.
+    pub(crate) fn from_stream(
+        id: ConnectionId,
+        address: RemoteAddress,
+        conn: TcpStream,
+        return_address: reply::MixnetAddress,
+    ) -> Self {
+        Connection {
             id,
             address,
             conn: Some(conn),
             return_address,
-        })
+        }
     }
 
     pub(crate) async fn run_proxy(
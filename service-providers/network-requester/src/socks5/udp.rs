@@ -0,0 +1,347 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::reply;
+use crate::reply::MixnetMessage;
+use log::{debug, trace, warn};
+use nym_service_providers_common::interface::RequestVersion;
+use nym_socks5_proxy_helpers::proxy_runner::MixProxySender;
+use nym_socks5_requests::{ConnectionId, Socks5Request};
+use nym_task::TaskClient;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Instant};
+
+// the maximum size of an UDP datagram
+const MAX_DATAGRAM_SIZE: usize = 65535;
+
+type OutboundDatagram = (SocketAddr, Vec<u8>);
+
+/// Association ids are chosen by the clients, so they're only unique in the context of a particular sender.
+type SessionKey = (reply::MixnetAddress, ConnectionId);
+type SessionSenders = HashMap<SessionKey, mpsc::UnboundedSender<OutboundDatagram>>;
+
+/// All currently active UDP sessions, i.e. the outbound ends of the clients' UDP associations.
+#[derive(Clone)]
+pub(crate) struct UdpSessions {
+    inner: Arc<Mutex<SessionSenders>>,
+
+    /// Specifies how long an UDP session can remain without any traffic before it gets closed.
+    idle_timeout: Duration,
+}
+
+impl UdpSessions {
+    pub(crate) fn new(idle_timeout: Duration) -> Self {
+        UdpSessions {
+            inner: Default::default(),
+            idle_timeout,
+        }
+    }
+
+    /// Sends the datagram to the specified remote using the socket associated with the provided
+    /// association of the particular sender. If no such session exists, a new one is created.
+    pub(crate) fn send_datagram(
+        &self,
+        sender: reply::MixnetAddress,
+        association_id: ConnectionId,
+        remote: SocketAddr,
+        data: Vec<u8>,
+        session: impl FnOnce() -> UdpSessionContext,
+    ) -> io::Result<()> {
+        let key = (sender, association_id);
+        let mut guard = self.inner.lock().expect("udp sessions lock got poisoned");
+        if let Some(session_sender) = guard.get(&key) {
+            match session_sender.send((remote, data)) {
+                Ok(_) => return Ok(()),
+                // the session has just timed out - start a new one instead
+                Err(mpsc::error::SendError(datagram)) => {
+                    return self.start_session(&mut guard, key, datagram, session());
+                }
+            }
+        }
+
+        self.start_session(&mut guard, key, (remote, data), session())
+    }
+
+    fn start_session(
+        &self,
+        senders: &mut SessionSenders,
+        key: SessionKey,
+        datagram: OutboundDatagram,
+        context: UdpSessionContext,
+    ) -> io::Result<()> {
+        let socket = bind_session_socket()?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        // this can't fail as we're holding the receiver
+        sender.send(datagram).ok();
+        senders.insert(key.clone(), sender);
+
+        let association_id = key.1;
+        debug!("starting new UDP session for association {association_id}");
+        let session = UdpSession {
+            association_id,
+            dual_stack: socket.local_addr()?.is_ipv6(),
+            socket,
+            contacted_remotes: HashSet::new(),
+            idle_timeout: self.idle_timeout,
+            outbound: receiver,
+            context,
+        };
+        let sessions = self.clone();
+        tokio::spawn(async move {
+            session.run().await;
+            sessions.remove_closed(&key);
+        });
+        Ok(())
+    }
+
+    fn remove_closed(&self, key: &SessionKey) {
+        let mut guard = self.inner.lock().expect("udp sessions lock got poisoned");
+        // make sure we don't remove a session that has been restarted in the meantime
+        if guard
+            .get(key)
+            .map(|sender| sender.is_closed())
+            .unwrap_or_default()
+        {
+            guard.remove(key);
+        }
+    }
+}
+
+/// Binds a socket capable of reaching both IPv4 and IPv6 destinations, since a single association
+/// might be used for talking to remotes of either family.
+/// If IPv6 is unavailable on this host, it falls back to an IPv4-only socket.
+fn bind_session_socket() -> io::Result<UdpSocket> {
+    let std_socket = match bind_dual_stack_socket() {
+        Ok(socket) => socket,
+        Err(err) => {
+            debug!("failed to bind dual-stack UDP socket: {err}. falling back to IPv4 only");
+            std::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)))?
+        }
+    };
+    std_socket.set_nonblocking(true)?;
+    UdpSocket::from_std(std_socket)
+}
+
+fn bind_dual_stack_socket() -> io::Result<std::net::UdpSocket> {
+    let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_only_v6(false)?;
+    socket.bind(&SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)).into())?;
+    Ok(socket.into())
+}
+
+/// IPv4 destinations have to be expressed as IPv4-mapped IPv6 addresses on a dual-stack socket.
+fn to_dual_stack_address(address: SocketAddr) -> SocketAddr {
+    match address {
+        SocketAddr::V4(v4) => {
+            SocketAddr::V6(SocketAddrV6::new(v4.ip().to_ipv6_mapped(), v4.port(), 0, 0))
+        }
+        v6 => v6,
+    }
+}
+
+/// Reverses [`to_dual_stack_address`] so that the client would see the actual address of the remote.
+fn from_dual_stack_address(address: SocketAddr) -> SocketAddr {
+    match address {
+        SocketAddr::V6(v6) => match v6.ip().to_ipv4_mapped() {
+            Some(v4) => SocketAddr::from((v4, v6.port())),
+            None => SocketAddr::V6(v6),
+        },
+        v4 => v4,
+    }
+}
+
+/// Everything required for sending the received datagrams back to the client.
+pub(crate) struct UdpSessionContext {
+    pub(crate) remote_version: RequestVersion<Socks5Request>,
+    pub(crate) return_address: reply::MixnetAddress,
+    pub(crate) mix_sender: MixProxySender<MixnetMessage>,
+    pub(crate) shutdown: TaskClient,
+}
+
+/// An outbound UDP socket relaying datagrams on behalf of a single client association.
+struct UdpSession {
+    association_id: ConnectionId,
+    socket: UdpSocket,
+    dual_stack: bool,
+
+    /// Remotes this session has sent datagrams to, i.e. the only ones it's going to accept datagrams from.
+    /// Note that they all have already passed the request filter.
+    contacted_remotes: HashSet<SocketAddr>,
+    idle_timeout: Duration,
+    outbound: mpsc::UnboundedReceiver<OutboundDatagram>,
+    context: UdpSessionContext,
+}
+
+impl UdpSession {
+    /// Checks whether the datagram from the provided source should be relayed back to the client,
+    /// i.e. whether the client has sent anything to that remote before.
+    fn accepts_inbound_from(&self, source: &SocketAddr) -> bool {
+        self.contacted_remotes.contains(source)
+    }
+
+    async fn handle_inbound(&self, source: SocketAddr, data: Vec<u8>) {
+        trace!(
+            "received {} bytes from {source} on association {}",
+            data.len(),
+            self.association_id
+        );
+        let msg = MixnetMessage::new_datagram_response(
+            self.context.return_address.clone(),
+            self.context.remote_version.clone(),
+            self.association_id,
+            source.to_string(),
+            data,
+        );
+        self.context
+            .mix_sender
+            .send(msg)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    async fn run(mut self) {
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut idle_deadline = Instant::now() + self.idle_timeout;
+
+        while !self.context.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.context.shutdown.recv() => {
+                    trace!("UdpSession {}: received shutdown", self.association_id);
+                }
+                outbound = self.outbound.recv() => match outbound {
+                    Some((remote, data)) => {
+                        idle_deadline = Instant::now() + self.idle_timeout;
+                        self.contacted_remotes.insert(from_dual_stack_address(remote));
+                        let target = if self.dual_stack { to_dual_stack_address(remote) } else { remote };
+                        if let Err(err) = self.socket.send_to(&data, target).await {
+                            warn!("failed to send datagram to {remote} on association {}: {err}", self.association_id)
+                        }
+                    }
+                    None => break,
+                },
+                inbound = self.socket.recv_from(&mut buf) => match inbound {
+                    Ok((n, source)) => {
+                        let source = from_dual_stack_address(source);
+                        if !self.accepts_inbound_from(&source) {
+                            trace!("dropping {n} bytes from unknown remote {source} on association {}", self.association_id);
+                            continue;
+                        }
+                        idle_deadline = Instant::now() + self.idle_timeout;
+                        self.handle_inbound(source, buf[..n].to_vec()).await
+                    }
+                    // this might be caused by an ICMP error from one of the previous destinations,
+                    // so it shouldn't close the whole session
+                    Err(err) => {
+                        debug!("failed to receive datagram on association {}: {err}", self.association_id);
+                    }
+                },
+                _ = sleep_until(idle_deadline) => {
+                    debug!("UDP session {} has been idle for {:?}", self.association_id, self.idle_timeout);
+                    break
+                }
+            }
+        }
+
+        // prevent any further datagrams from being queued up
+        self.outbound.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dual_stack_address_mapping_is_reversible() {
+        let v4: SocketAddr = "1.2.3.4:1234".parse().unwrap();
+        let mapped = to_dual_stack_address(v4);
+        assert_eq!(mapped, "[::ffff:1.2.3.4]:1234".parse().unwrap());
+        assert_eq!(from_dual_stack_address(mapped), v4);
+
+        let v6: SocketAddr = "[2001:db8::1]:1234".parse().unwrap();
+        assert_eq!(to_dual_stack_address(v6), v6);
+        assert_eq!(from_dual_stack_address(v6), v6);
+    }
+
+    #[tokio::test]
+    async fn session_socket_reaches_both_address_families() {
+        let socket = bind_session_socket().unwrap();
+        let target = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let target_address = target.local_addr().unwrap();
+
+        let destination = if socket.local_addr().unwrap().is_ipv6() {
+            to_dual_stack_address(target_address)
+        } else {
+            target_address
+        };
+        socket.send_to(b"foomp", destination).await.unwrap();
+
+        let mut buf = [0u8; 16];
+        let (n, _) = target.recv_from(&mut buf).await.unwrap();
+        assert_eq!(&buf[..n], b"foomp");
+    }
+
+    #[tokio::test]
+    async fn session_only_relays_datagrams_from_contacted_remotes() {
+        use nym_service_providers_common::interface::ProviderInterfaceVersion;
+        use nym_socks5_requests::Socks5ProtocolVersion;
+        use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
+
+        let socket = bind_session_socket().unwrap();
+        let session_port = socket.local_addr().unwrap().port();
+        let dual_stack = socket.local_addr().unwrap().is_ipv6();
+
+        let (mix_sender, mut mix_receiver) = mpsc::channel(16);
+        let (outbound_sender, outbound) = mpsc::unbounded_channel();
+        let session = UdpSession {
+            association_id: 42,
+            socket,
+            dual_stack,
+            contacted_remotes: HashSet::new(),
+            idle_timeout: Duration::from_secs(60),
+            outbound,
+            context: UdpSessionContext {
+                remote_version: RequestVersion::new(
+                    ProviderInterfaceVersion::new_current(),
+                    Socks5ProtocolVersion::new_current(),
+                ),
+                return_address: reply::MixnetAddress::Anonymous(AnonymousSenderTag::new_random(
+                    &mut rand::thread_rng(),
+                )),
+                mix_sender,
+                shutdown: TaskClient::dummy(),
+            },
+        };
+        tokio::spawn(session.run());
+
+        let remote = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let stranger = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let session_address = SocketAddr::from((Ipv4Addr::LOCALHOST, session_port));
+
+        outbound_sender
+            .send((remote.local_addr().unwrap(), b"foomp".to_vec()))
+            .unwrap();
+        let mut buf = [0u8; 16];
+        remote.recv_from(&mut buf).await.unwrap();
+
+        // the stranger's datagram arrives first, but it's never relayed back to the client
+        stranger.send_to(b"bad", session_address).await.unwrap();
+        remote.send_to(b"good", session_address).await.unwrap();
+
+        let relayed = tokio::time::timeout(Duration::from_secs(5), mix_receiver.recv())
+            .await
+            .unwrap();
+        assert!(relayed.is_some());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(mix_receiver.try_recv().is_err());
+    }
+}
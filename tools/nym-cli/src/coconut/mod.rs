@@ -24,6 +24,13 @@ pub(crate) async fn execute(
         nym_cli_commands::coconut::EcashCommands::ImportTicketBook(args) => {
             nym_cli_commands::coconut::import_ticket_book::execute(args).await?
         }
+        nym_cli_commands::coconut::EcashCommands::RequestDepositRefund(args) => {
+            nym_cli_commands::coconut::request_deposit_refund::execute(
+                args,
+                create_signing_client(global_args, network_details)?,
+            )
+            .await?
+        }
     }
     Ok(())
 }
@@ -4,8 +4,11 @@
 use clap::{CommandFactory, Parser, Subcommand};
 use log::{error, warn};
 use nym_bin_common::logging::setup_logging;
-use nym_cli_commands::context::{get_network_details, ClientArgs};
+use nym_cli_commands::context::{account_derivation_path, get_network_details, ClientArgs};
 use nym_validator_client::nyxd::AccountId;
+use nym_validator_client::signing::generate_only::GenerateOnlySigner;
+use nym_validator_client::signing::signer::OfflineSigner;
+use nym_validator_client::DirectSecp256k1HdWallet;
 
 mod coconut;
 mod completion;
@@ -51,6 +54,30 @@ pub(crate) struct Cli {
     )]
     pub(crate) vesting_contract_address: Option<AccountId>,
 
+    #[clap(long, global = true)]
+    #[clap(
+        help = "Rather than signing and broadcasting any transactions, output them as unsigned JSON so that they could be signed on an offline machine with `tx sign`"
+    )]
+    pub(crate) generate_only: bool,
+
+    #[clap(long, global = true, requires = "generate_only")]
+    #[clap(
+        help = "Address of the account that is going to sign the transactions created with --generate-only. If not provided, it is derived from the mnemonic"
+    )]
+    pub(crate) from: Option<AccountId>,
+
+    #[clap(long, global = true, conflicts_with = "mnemonic")]
+    #[clap(
+        help = "Sign the transactions with a connected Ledger device running the Cosmos application"
+    )]
+    pub(crate) ledger: bool,
+
+    #[clap(long, global = true, default_value_t = 0)]
+    #[clap(
+        help = "Index of the account, as in the m/44'/118'/<index>'/0/0 derivation path, used for signing the transactions"
+    )]
+    pub(crate) account_index: u32,
+
     #[clap(subcommand)]
     command: Commands,
 }
@@ -79,13 +106,16 @@ pub(crate) enum Commands {
 }
 
 async fn execute(cli: Cli) -> anyhow::Result<()> {
-    let args = ClientArgs {
+    let mut args = ClientArgs {
         nyxd_url: cli.nyxd_url,
         nym_api_url: cli.nym_api_url,
         mnemonic: cli.mnemonic,
         mixnet_contract_address: cli.mixnet_contract_address,
         vesting_contract_address: cli.vesting_contract_address,
         config_env_file: cli.config_env_file,
+        generate_only: None,
+        ledger: cli.ledger,
+        account_index: cli.account_index,
     };
 
     let network_details = get_network_details(&args)?;
@@ -97,6 +127,29 @@ async fn execute(cli: Cli) -> anyhow::Result<()> {
             .and_then(|m| bip39::Mnemonic::parse(m).ok())
     });
 
+    let generate_only = if cli.generate_only {
+        let address = match (cli.from, mnemonic.clone()) {
+            (Some(address), _) => address,
+            (None, Some(mnemonic)) => {
+                let prefix = &network_details.chain_details.bech32_account_prefix;
+                DirectSecp256k1HdWallet::builder(prefix)
+                    .with_hd_paths(vec![account_derivation_path(cli.account_index)])
+                    .build(mnemonic)
+                    .signer_addresses()?[0]
+                    .clone()
+            }
+            (None, None) => {
+                anyhow::bail!(
+                    "--generate-only requires either --from or the mnemonic of the signer"
+                )
+            }
+        };
+        Some(GenerateOnlySigner::new(address))
+    } else {
+        None
+    };
+    args.generate_only = generate_only.clone();
+
     match cli.command {
         Commands::Account(account) => {
             validator::account::execute(args, account, &network_details, mnemonic).await?
@@ -110,7 +163,7 @@ async fn execute(cli: Cli) -> anyhow::Result<()> {
             validator::cosmwasm::execute(args, cosmwasm, &network_details).await?
         }
         Commands::Tx(transactions) => {
-            validator::transactions::execute(transactions, &network_details).await?
+            validator::transactions::execute(transactions, &network_details, mnemonic).await?
         }
         Commands::VestingSchedule(vesting) => {
             validator::vesting::execute(args, vesting, &network_details).await?
@@ -124,6 +177,12 @@ async fn execute(cli: Cli) -> anyhow::Result<()> {
         }
     }
 
+    if let Some(generate_only) = generate_only {
+        for unsigned in generate_only.take_exported() {
+            println!("{}", serde_json::to_string_pretty(&unsigned)?);
+        }
+    }
+
     Ok(())
 }
 
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use nym_cli_commands::context::create_query_client;
+use nym_cli_commands::context::errors::ContextError;
 use nym_network_defaults::NymNetworkDetails;
 
 pub(crate) async fn execute(
     transactions: nym_cli_commands::validator::transactions::Transactions,
     network_details: &NymNetworkDetails,
+    mnemonic: Option<bip39::Mnemonic>,
 ) -> anyhow::Result<()> {
     match transactions.command {
         Some(nym_cli_commands::validator::transactions::TransactionsCommands::Get(args)) => {
@@ -23,6 +25,20 @@ pub(crate) async fn execute(
             )
             .await
         }
+        Some(nym_cli_commands::validator::transactions::TransactionsCommands::Sign(args)) => {
+            nym_cli_commands::validator::transactions::sign_transaction::sign(
+                args,
+                network_details,
+                mnemonic.ok_or(ContextError::MnemonicNotProvided)?,
+            )?
+        }
+        Some(nym_cli_commands::validator::transactions::TransactionsCommands::Broadcast(args)) => {
+            nym_cli_commands::validator::transactions::broadcast_transaction::broadcast(
+                args,
+                &create_query_client(network_details)?,
+            )
+            .await?
+        }
         _ => unreachable!(),
     }
     Ok(())
@@ -117,6 +117,7 @@ pub(crate) fn execute(args: Args) -> Result<(), NymvisorError> {
             platforms: Default::default(),
             upgrade_time: args.determine_upgrade_time(),
             binary_details: Some(bin_info),
+            failed: None,
         };
 
         if current_upgrade_plan.has_planned_by_name(&args.upgrade_name) {
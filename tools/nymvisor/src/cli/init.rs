@@ -13,6 +13,7 @@ use crate::upgrades::types::{CurrentVersionInfo, UpgradeInfo, UpgradePlan};
 use nym_bin_common::build_information::BinaryBuildInformationOwned;
 use nym_bin_common::logging::setup_tracing_logger;
 use nym_bin_common::output_format::OutputFormat;
+use nym_crypto::asymmetric::identity;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -78,6 +79,17 @@ pub(crate) struct Args {
     #[arg(long)]
     enforce_download_checksum: Option<bool>,
 
+    /// If enabled, nymvisor will require that every upgrade binary is signed by one of the `release_keys`
+    /// and will reject any unsigned or invalidly signed binaries before they're ever executed.
+    /// Can be overridden with $DAEMON_ENFORCE_SIGNATURE environmental variable.
+    #[arg(long)]
+    enforce_signature: Option<bool>,
+
+    /// Base58-encoded ed25519 public keys of the release signers that are trusted to sign upgrade binaries.
+    /// Can be overridden with $DAEMON_RELEASE_KEYS environmental variable.
+    #[arg(long, value_delimiter = ',')]
+    release_keys: Option<Vec<identity::PublicKey>>,
+
     /// If enabled, nymvisor will restart the subprocess with the same command-line arguments and flags (but with the new binary) after a successful upgrade.
     /// Otherwise (if disabled), nymvisor will stop running after an upgrade and will require the system administrator to manually restart it.
     /// Note restart is only after the upgrade and does not auto-restart the subprocess after an error occurs.
@@ -126,6 +138,29 @@ pub(crate) struct Args {
     #[arg(long)]
     unsafe_skip_backup: bool,
 
+    /// If enabled, `nymvisor` will probe the health of the daemon after each upgrade and if it keeps failing,
+    /// it will restore the pre-upgrade backup alongside the previous binary.
+    /// Can be overridden with $DAEMON_ROLLBACK_ON_FAILURE environmental variable.
+    #[arg(long)]
+    rollback_on_failure: Option<bool>,
+
+    /// Defines the length of time the upgraded daemon has to stay alive for the upgrade to be considered successful.
+    /// Can be overridden with $DAEMON_HEALTH_PROBE_PERIOD environmental variable.
+    #[arg(long, value_parser = humantime::parse_duration)]
+    health_probe_period: Option<Duration>,
+
+    /// Optional url of the daemon's health endpoint, e.g. `http://127.0.0.1:8080/api/v1/health`,
+    /// that the upgraded daemon must respond to for the upgrade to be considered successful.
+    /// Can be overridden with $DAEMON_HEALTH_PROBE_URL environmental variable.
+    #[arg(long)]
+    health_probe_url: Option<Url>,
+
+    /// Defines the maximum number of consecutive failed health probes of the upgraded daemon
+    /// before the upgrade is going to be rolled back.
+    /// Can be overridden with $DAEMON_MAX_UPGRADE_FAILURES environmental variable.
+    #[arg(long)]
+    max_upgrade_failures: Option<usize>,
+
     #[arg(short, long, default_value_t = OutputFormat::default())]
     output: OutputFormat,
 }
@@ -160,6 +195,12 @@ impl Args {
         if let Some(enforce_download_checksum) = self.enforce_download_checksum {
             config.daemon.debug.enforce_download_checksum = enforce_download_checksum;
         }
+        if let Some(enforce_signature) = self.enforce_signature {
+            config.daemon.debug.enforce_signature = enforce_signature;
+        }
+        if let Some(release_keys) = &self.release_keys {
+            config.daemon.debug.release_keys.clone_from(release_keys);
+        }
         if let Some(restart_daemon_after_upgrade) = self.restart_daemon_after_upgrade {
             config.daemon.debug.restart_after_upgrade = restart_daemon_after_upgrade;
         }
@@ -184,6 +225,18 @@ impl Args {
         if self.unsafe_skip_backup {
             config.daemon.debug.unsafe_skip_backup = self.unsafe_skip_backup;
         }
+        if let Some(rollback_on_failure) = self.rollback_on_failure {
+            config.daemon.debug.rollback_on_failure = rollback_on_failure;
+        }
+        if let Some(health_probe_period) = self.health_probe_period {
+            config.daemon.debug.health_probe_period = health_probe_period;
+        }
+        if let Some(health_probe_url) = &self.health_probe_url {
+            config.daemon.debug.health_probe_url = Some(health_probe_url.clone());
+        }
+        if let Some(max_upgrade_failures) = self.max_upgrade_failures {
+            config.daemon.debug.max_upgrade_failures = max_upgrade_failures;
+        }
     }
 }
 
@@ -318,6 +371,7 @@ fn generate_and_save_genesis_upgrade_info(
         platforms: Default::default(),
         upgrade_time: OffsetDateTime::UNIX_EPOCH,
         binary_details: Some(genesis_info.clone()),
+        failed: None,
     };
     let save_path = config.upgrade_info_filepath(&info.name);
 
@@ -1,12 +1,14 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::config::serde_helpers::bs58_pubkeys;
 use crate::config::template::CONFIG_TEMPLATE;
 use nym_config::serde_helpers::de_maybe_stringified;
 use nym_config::{
     must_get_home, read_config_from_toml_file, save_formatted_config_to_file, NymConfigTemplate,
     DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_crypto::asymmetric::identity;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::io;
@@ -15,6 +17,7 @@ use std::time::Duration;
 use tracing::{debug, warn};
 use url::Url;
 
+mod serde_helpers;
 mod template;
 
 pub(crate) const DEFAULT_FAILURE_RESTART_DELAY: Duration = Duration::from_secs(10);
@@ -22,6 +25,8 @@ pub(crate) const DEFAULT_STARTUP_PERIOD: Duration = Duration::from_secs(120);
 pub(crate) const DEFAULT_MAX_STARTUP_FAILURES: usize = 10;
 pub(crate) const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(10);
 pub(crate) const DEFAULT_UPSTREAM_POLLING_RATE: Duration = Duration::from_secs(60 * 60);
+pub(crate) const DEFAULT_HEALTH_PROBE_PERIOD: Duration = Duration::from_secs(60);
+pub(crate) const DEFAULT_MAX_UPGRADE_FAILURES: usize = 3;
 
 pub(crate) const DEFAULT_BASE_UPSTREAM_UPGRADE_INFO_SOURCE: &str =
     "https://nymtech.net/.wellknown/";
@@ -123,6 +128,12 @@ impl Display for Config {
 {:<35}{}
 {:<35}{:?}
 {:<35}{}
+{:<35}{}
+{:<35}{}
+{:<35}{}
+{:<35}{}
+{:<35}{}
+{:<35}{}
 "#,
             "id:",
             self.nymvisor.id,
@@ -173,6 +184,29 @@ impl Display for Config {
                 .unwrap_or_default(),
             "UNSAFE skip backups",
             self.daemon.debug.unsafe_skip_backup,
+            "rollback on failure:",
+            self.daemon.debug.rollback_on_failure,
+            "health probe period:",
+            humantime::format_duration(self.daemon.debug.health_probe_period),
+            "health probe url:",
+            self.daemon
+                .debug
+                .health_probe_url
+                .as_ref()
+                .map(|p| p.to_string())
+                .unwrap_or_default(),
+            "max upgrade failures:",
+            self.daemon.debug.max_upgrade_failures,
+            "enforce signature:",
+            self.daemon.debug.enforce_signature,
+            "release keys:",
+            self.daemon
+                .debug
+                .release_keys
+                .iter()
+                .map(|key| key.to_base58_string())
+                .collect::<Vec<_>>()
+                .join(","),
         )
     }
 }
@@ -315,6 +349,18 @@ impl Config {
         self.upgrades_dir().join(upgrade_name)
     }
 
+    // e.g. $HOME/.nym/nymvisors/data/nym-api/upgrades/<upgrade-name>
+    // or $HOME/.nym/nymvisors/data/nym-api/genesis
+    pub fn upgrade_or_genesis_dir<S: AsRef<str>>(&self, upgrade_name: S) -> PathBuf {
+        // special case for genesis
+        let name = upgrade_name.as_ref();
+        if name == GENESIS_DIR {
+            self.genesis_daemon_dir()
+        } else {
+            self.upgrade_dir(name)
+        }
+    }
+
     // e.g. $HOME/.nym/nymvisors/data/nym-api/upgrades/<upgrade-name>/bin
     pub fn upgrade_binary_dir<P: AsRef<Path>>(&self, upgrade_name: P) -> PathBuf {
         self.upgrade_dir(upgrade_name).join(BIN_DIR)
@@ -439,7 +485,7 @@ pub struct Daemon {
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize, Clone)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct DaemonDebug {
     /// Override url to the upstream source for upgrade plans for this daeamon.
     /// The Url has to point to an endpoint containing a valid [`UpgradeInfo`] json.
@@ -460,6 +506,20 @@ pub struct DaemonDebug {
     /// Can be overridden with $DAEMON_ENFORCE_DOWNLOAD_CHECKSUM environmental variable.
     pub enforce_download_checksum: bool,
 
+    /// If enabled, nymvisor will require that every upgrade binary is signed by one of the `release_keys`
+    /// and will reject any unsigned or invalidly signed binaries before they're ever executed.
+    /// If disabled, nymvisor will not require a signature to be provided, but still verify it if one is provided
+    /// and there are release keys configured.
+    /// default: false
+    /// Can be overridden with $DAEMON_ENFORCE_SIGNATURE environmental variable.
+    pub enforce_signature: bool,
+
+    /// Base58-encoded ed25519 public keys of the release signers that are trusted to sign upgrade binaries.
+    /// default: []
+    /// Can be overridden with $DAEMON_RELEASE_KEYS environmental variable (as comma separated values).
+    #[serde(with = "bs58_pubkeys")]
+    pub release_keys: Vec<identity::PublicKey>,
+
     /// If enabled, nymvisor will restart the subprocess with the same command-line arguments and flags (but with the new binary) after a successful upgrade.
     /// Otherwise (if disabled), nymvisor will stop running after an upgrade and will require the system administrator to manually restart it.
     /// Note restart is only after the upgrade and does not auto-restart the subprocess after an error occurs.
@@ -510,6 +570,33 @@ pub struct DaemonDebug {
     /// default: false
     /// Can be overridden with $DAEMON_UNSAFE_SKIP_BACKUP environmental variable.
     pub unsafe_skip_backup: bool,
+
+    /// If enabled, `nymvisor` will probe the health of the daemon after each upgrade and if it keeps failing,
+    /// it will restore the pre-upgrade backup alongside the previous binary.
+    /// Note: the health of the upgraded daemon is only tracked for as long as `nymvisor` keeps running.
+    /// default: true
+    /// Can be overridden with $DAEMON_ROLLBACK_ON_FAILURE environmental variable.
+    pub rollback_on_failure: bool,
+
+    /// Defines the length of time the upgraded daemon has to stay alive for the upgrade to be considered successful.
+    /// default: 60s
+    /// Can be overridden with $DAEMON_HEALTH_PROBE_PERIOD environmental variable.
+    #[serde(with = "humantime_serde")]
+    pub health_probe_period: Duration,
+
+    /// Optional url of the daemon's health endpoint, e.g. `http://127.0.0.1:8080/api/v1/health`.
+    /// If set, apart from staying alive for `health_probe_period`, the upgraded daemon must also
+    /// respond to the request with a success status code for the upgrade to be considered successful.
+    /// default: None
+    /// Can be overridden with $DAEMON_HEALTH_PROBE_URL environmental variable.
+    #[serde(deserialize_with = "de_maybe_stringified")]
+    pub health_probe_url: Option<Url>,
+
+    /// Defines the maximum number of consecutive failed health probes of the upgraded daemon
+    /// before the upgrade is going to be rolled back.
+    /// default: 3
+    /// Can be overridden with $DAEMON_MAX_UPGRADE_FAILURES environmental variable.
+    pub max_upgrade_failures: usize,
 }
 
 impl Default for DaemonDebug {
@@ -518,6 +605,8 @@ impl Default for DaemonDebug {
             absolute_upstream_upgrade_url: None,
             allow_binaries_download: true,
             enforce_download_checksum: true,
+            enforce_signature: false,
+            release_keys: Vec::new(),
             restart_after_upgrade: true,
             restart_on_failure: false,
             failure_restart_delay: DEFAULT_FAILURE_RESTART_DELAY,
@@ -526,6 +615,10 @@ impl Default for DaemonDebug {
             shutdown_grace_period: DEFAULT_SHUTDOWN_GRACE_PERIOD,
             backup_data_directory: None,
             unsafe_skip_backup: false,
+            rollback_on_failure: true,
+            health_probe_period: DEFAULT_HEALTH_PROBE_PERIOD,
+            health_probe_url: None,
+            max_upgrade_failures: DEFAULT_MAX_UPGRADE_FAILURES,
         }
     }
 }
@@ -0,0 +1,25 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+pub(super) mod bs58_pubkeys {
+    use nym_crypto::asymmetric::identity;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        keys: &[identity::PublicKey],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(keys.iter().map(|key| key.to_base58_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<identity::PublicKey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|raw| {
+                identity::PublicKey::from_base58_string(raw).map_err(serde::de::Error::custom)
+            })
+            .collect()
+    }
+}
@@ -68,6 +68,21 @@ allow_binaries_download = {{ daemon.allow_binaries_download }}
 # Can be overridden with $DAEMON_ENFORCE_DOWNLOAD_CHECKSUM environmental variable.
 enforce_download_checksum = {{ daemon.enforce_download_checksum }}
 
+# If enabled, nymvisor will require that every upgrade binary is signed by one of the `release_keys`
+# and will reject any unsigned or invalidly signed binaries before they're ever executed.
+# If disabled, nymvisor will not require a signature to be provided, but still verify it if one is provided
+# and there are release keys configured.
+# default: false
+# Can be overridden with $DAEMON_ENFORCE_SIGNATURE environmental variable.
+enforce_signature = {{ daemon.enforce_signature }}
+
+# Base58-encoded ed25519 public keys of the release signers that are trusted to sign upgrade binaries.
+# default: []
+# Can be overridden with $DAEMON_RELEASE_KEYS environmental variable (as comma separated values).
+release_keys = [
+{{#each daemon.release_keys }}'{{this}}',{{/each}}
+]
+
 # If enabled, nymvisor will restart the subprocess with the same command-line arguments and flags (but with the new binary) after a successful upgrade.
 # Otherwise (if disabled), nymvisor will stop running after an upgrade and will require the system administrator to manually restart it.
 # Note restart is only after the upgrade and does not auto-restart the subprocess after an error occurs.
@@ -115,4 +130,29 @@ backup_data_directory = '{{ daemon.backup_data_directory }}'
 # Can be overridden with $DAEMON_UNSAFE_SKIP_BACKUP environmental variable.
 unsafe_skip_backup = {{ daemon.unsafe_skip_backup }}
 
+# If enabled, `nymvisor` will probe the health of the daemon after each upgrade and if it keeps failing,
+# it will restore the pre-upgrade backup alongside the previous binary.
+# Note: the health of the upgraded daemon is only tracked for as long as `nymvisor` keeps running.
+# default: true
+# Can be overridden with $DAEMON_ROLLBACK_ON_FAILURE environmental variable.
+rollback_on_failure = {{ daemon.rollback_on_failure }}
+
+# Defines the length of time the upgraded daemon has to stay alive for the upgrade to be considered successful.
+# default: 60s
+# Can be overridden with $DAEMON_HEALTH_PROBE_PERIOD environmental variable.
+health_probe_period = '{{ daemon.health_probe_period }}'
+
+# Optional url of the daemon's health endpoint, e.g. `http://127.0.0.1:8080/api/v1/health`.
+# If set, apart from staying alive for `health_probe_period`, the upgraded daemon must also
+# respond to the request with a success status code for the upgrade to be considered successful.
+# default: None
+# Can be overridden with $DAEMON_HEALTH_PROBE_URL environmental variable.
+health_probe_url = '{{ daemon.health_probe_url }}'
+
+# Defines the maximum number of consecutive failed health probes of the upgraded daemon
+# before the upgrade is going to be rolled back.
+# default: 3
+# Can be overridden with $DAEMON_MAX_UPGRADE_FAILURES environmental variable.
+max_upgrade_failures = {{ daemon.max_upgrade_failures }}
+
 "#;
@@ -3,6 +3,7 @@
 
 use crate::config::Config;
 use crate::error::NymvisorError;
+use nym_crypto::asymmetric::identity;
 use std::env::VarError;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -24,6 +25,8 @@ pub mod vars {
     pub const DAEMON_ABSOLUTE_UPSTREAM_UPGRADE_URL: &str = "DAEMON_ABSOLUTE_UPSTREAM_UPGRADE_URL";
     pub const DAEMON_ALLOW_BINARIES_DOWNLOAD: &str = "DAEMON_ALLOW_BINARIES_DOWNLOAD";
     pub const DAEMON_ENFORCE_DOWNLOAD_CHECKSUM: &str = "DAEMON_ENFORCE_DOWNLOAD_CHECKSUM";
+    pub const DAEMON_ENFORCE_SIGNATURE: &str = "DAEMON_ENFORCE_SIGNATURE";
+    pub const DAEMON_RELEASE_KEYS: &str = "DAEMON_RELEASE_KEYS";
     pub const DAEMON_RESTART_AFTER_UPGRADE: &str = "DAEMON_RESTART_AFTER_UPGRADE";
     pub const DAEMON_RESTART_ON_FAILURE: &str = "DAEMON_RESTART_ON_FAILURE";
     pub const DAEMON_FAILURE_RESTART_DELAY: &str = "DAEMON_FAILURE_RESTART_DELAY";
@@ -32,6 +35,10 @@ pub mod vars {
     pub const DAEMON_SHUTDOWN_GRACE_PERIOD: &str = "DAEMON_SHUTDOWN_GRACE_PERIOD";
     pub const DAEMON_BACKUP_DATA_DIRECTORY: &str = "DAEMON_BACKUP_DATA_DIRECTORY";
     pub const DAEMON_UNSAFE_SKIP_BACKUP: &str = "DAEMON_UNSAFE_SKIP_BACKUP";
+    pub const DAEMON_ROLLBACK_ON_FAILURE: &str = "DAEMON_ROLLBACK_ON_FAILURE";
+    pub const DAEMON_HEALTH_PROBE_PERIOD: &str = "DAEMON_HEALTH_PROBE_PERIOD";
+    pub const DAEMON_HEALTH_PROBE_URL: &str = "DAEMON_HEALTH_PROBE_URL";
+    pub const DAEMON_MAX_UPGRADE_FAILURES: &str = "DAEMON_MAX_UPGRADE_FAILURES";
 }
 
 pub(crate) fn setup_env(config_env_file: &Option<PathBuf>) -> Result<(), NymvisorError> {
@@ -55,6 +62,8 @@ pub(crate) struct Env {
     pub(crate) daemon_absolute_upstream_upgrade_url: Option<Url>,
     pub(crate) daemon_allow_binaries_download: Option<bool>,
     pub(crate) daemon_enforce_download_checksum: Option<bool>,
+    pub(crate) daemon_enforce_signature: Option<bool>,
+    pub(crate) daemon_release_keys: Option<Vec<identity::PublicKey>>,
     pub(crate) daemon_restart_after_upgrade: Option<bool>,
     pub(crate) daemon_restart_on_failure: Option<bool>,
     pub(crate) daemon_failure_restart_delay: Option<Duration>,
@@ -63,6 +72,10 @@ pub(crate) struct Env {
     pub(crate) daemon_shutdown_grace_period: Option<Duration>,
     pub(crate) backup_data_directory: Option<PathBuf>,
     pub(crate) daemon_unsafe_skip_backup: Option<bool>,
+    pub(crate) daemon_rollback_on_failure: Option<bool>,
+    pub(crate) daemon_health_probe_period: Option<Duration>,
+    pub(crate) daemon_health_probe_url: Option<Url>,
+    pub(crate) daemon_max_upgrade_failures: Option<usize>,
 }
 
 impl Env {
@@ -98,6 +111,16 @@ impl Env {
         if let Some(daemon_enforce_download_checksum) = self.daemon_enforce_download_checksum {
             config.daemon.debug.enforce_download_checksum = daemon_enforce_download_checksum;
         }
+        if let Some(daemon_enforce_signature) = self.daemon_enforce_signature {
+            config.daemon.debug.enforce_signature = daemon_enforce_signature;
+        }
+        if let Some(daemon_release_keys) = &self.daemon_release_keys {
+            config
+                .daemon
+                .debug
+                .release_keys
+                .clone_from(daemon_release_keys);
+        }
         if let Some(daemon_restart_after_upgrade) = self.daemon_restart_after_upgrade {
             config.daemon.debug.restart_after_upgrade = daemon_restart_after_upgrade;
         }
@@ -122,6 +145,18 @@ impl Env {
         if let Some(daemon_unsafe_skip_backup) = self.daemon_unsafe_skip_backup {
             config.daemon.debug.unsafe_skip_backup = daemon_unsafe_skip_backup;
         }
+        if let Some(daemon_rollback_on_failure) = self.daemon_rollback_on_failure {
+            config.daemon.debug.rollback_on_failure = daemon_rollback_on_failure;
+        }
+        if let Some(daemon_health_probe_period) = self.daemon_health_probe_period {
+            config.daemon.debug.health_probe_period = daemon_health_probe_period;
+        }
+        if let Some(daemon_health_probe_url) = &self.daemon_health_probe_url {
+            config.daemon.debug.health_probe_url = Some(daemon_health_probe_url.clone());
+        }
+        if let Some(daemon_max_upgrade_failures) = self.daemon_max_upgrade_failures {
+            config.daemon.debug.max_upgrade_failures = daemon_max_upgrade_failures;
+        }
     }
 }
 
@@ -186,6 +221,26 @@ fn read_url(var: &str) -> Result<Option<Url>, NymvisorError> {
         .transpose()
 }
 
+fn read_pubkeys(var: &str) -> Result<Option<Vec<identity::PublicKey>>, NymvisorError> {
+    read_string(var)?
+        .map(|raw| {
+            raw.split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| {
+                    identity::PublicKey::from_base58_string(key).map_err(|source| {
+                        NymvisorError::MalformedPublicKeyEnvVariable {
+                            variable: var.to_string(),
+                            value: key.to_string(),
+                            source,
+                        }
+                    })
+                })
+                .collect()
+        })
+        .transpose()
+}
+
 fn read_usize(var: &str) -> Result<Option<usize>, NymvisorError> {
     read_string(var)?
         .map(|raw| {
@@ -217,6 +272,8 @@ impl Env {
             )?,
             daemon_allow_binaries_download: read_bool(vars::DAEMON_ALLOW_BINARIES_DOWNLOAD)?,
             daemon_enforce_download_checksum: read_bool(vars::DAEMON_ENFORCE_DOWNLOAD_CHECKSUM)?,
+            daemon_enforce_signature: read_bool(vars::DAEMON_ENFORCE_SIGNATURE)?,
+            daemon_release_keys: read_pubkeys(vars::DAEMON_RELEASE_KEYS)?,
             daemon_restart_after_upgrade: read_bool(vars::DAEMON_RESTART_AFTER_UPGRADE)?,
             daemon_restart_on_failure: read_bool(vars::DAEMON_RESTART_ON_FAILURE)?,
             daemon_failure_restart_delay: read_duration(vars::DAEMON_FAILURE_RESTART_DELAY)?,
@@ -225,6 +282,10 @@ impl Env {
             daemon_shutdown_grace_period: read_duration(vars::DAEMON_SHUTDOWN_GRACE_PERIOD)?,
             backup_data_directory: read_pathbuf(vars::DAEMON_BACKUP_DATA_DIRECTORY)?,
             daemon_unsafe_skip_backup: read_bool(vars::DAEMON_UNSAFE_SKIP_BACKUP)?,
+            daemon_rollback_on_failure: read_bool(vars::DAEMON_ROLLBACK_ON_FAILURE)?,
+            daemon_health_probe_period: read_duration(vars::DAEMON_HEALTH_PROBE_PERIOD)?,
+            daemon_health_probe_url: read_url(vars::DAEMON_HEALTH_PROBE_URL)?,
+            daemon_max_upgrade_failures: read_usize(vars::DAEMON_MAX_UPGRADE_FAILURES)?,
         })
     }
 }
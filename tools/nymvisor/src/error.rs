@@ -6,6 +6,7 @@ use nix::errno::Errno;
 use nix::sys::signal::Signal;
 use nym_async_file_watcher::NotifyError;
 use nym_bin_common::build_information::BinaryBuildInformationOwned;
+use nym_crypto::asymmetric::identity::Ed25519RecoveryError;
 use std::ffi::OsString;
 use std::io;
 use std::num::ParseIntError;
@@ -213,6 +214,14 @@ While the stored info point to:\n{stored_info:#?}"
         source: io::Error,
     },
 
+    #[error("could not restore the backup {} into {}: {source}", path.display(), target.display())]
+    BackupRestorationFailure {
+        path: PathBuf,
+        target: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
     #[error("failed to initialise the path '{}': {source}", path.display())]
     PathInitFailure {
         path: PathBuf,
@@ -248,6 +257,14 @@ While the stored info point to:\n{stored_info:#?}"
         source: ParseIntError,
     },
 
+    #[error("the value provided for environmental public key variable '{variable}': '{value}' is not a valid ed25519 public key: {source}")]
+    MalformedPublicKeyEnvVariable {
+        variable: String,
+        value: String,
+        #[source]
+        source: Ed25519RecoveryError,
+    },
+
     #[error("the value provided for environmental Url '{variable}': '{value}' is not a valid number: {source}")]
     MalformedUrlEnvVariable {
         variable: String,
@@ -394,6 +411,24 @@ While the stored info point to:\n{stored_info:#?}"
     #[error("download information for upgrade '{upgrade_name}' is missing checksum")]
     MissingDownloadChecksum { upgrade_name: String },
 
+    #[error("download information for upgrade '{upgrade_name}' is missing signature")]
+    MissingUpgradeSignature { upgrade_name: String },
+
+    #[error("signature verification is enforced, but there are no release keys configured")]
+    NoReleaseKeys,
+
+    #[error("the signature for upgrade '{upgrade_name}' is malformed: {source}")]
+    MalformedUpgradeSignature {
+        upgrade_name: String,
+        #[source]
+        source: Ed25519RecoveryError,
+    },
+
+    #[error(
+        "the binary for upgrade '{upgrade_name}' has not been signed by any of the release keys"
+    )]
+    InvalidUpgradeSignature { upgrade_name: String },
+
     #[error("failed to create daemon binary at {}: {source}", path.display())]
     DaemonBinaryCreationFailure {
         path: PathBuf,
@@ -407,6 +442,12 @@ While the stored info point to:\n{stored_info:#?}"
     #[error("the daemon restart on failure is disabled")]
     DisabledRestartOnFailure,
 
+    #[error("the daemon upgraded to '{upgrade_name}' has failed its health probe: {reason}")]
+    FailedUpgradeHealthProbe {
+        upgrade_name: String,
+        reason: String,
+    },
+
     #[error("failed to read directory content of nymvisor instances at {}: {source}", path.display())]
     InstancesReadFailure {
         path: PathBuf,
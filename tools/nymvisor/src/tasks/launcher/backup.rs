@@ -4,6 +4,7 @@
 use crate::config::NYMVISOR_DIR;
 use crate::error::NymvisorError;
 use crate::helpers::init_path;
+use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use std::fs;
@@ -88,19 +89,22 @@ impl BackupBuilder {
         }
     }
 
-    fn finish(mut self) -> Result<(), NymvisorError> {
-        self.tar_builder
-            .finish()
-            .map_err(|source| NymvisorError::BackupTarFinalizationFailure {
-                path: self.backup_filepath,
+    fn finish(mut self) -> Result<PathBuf, NymvisorError> {
+        self.tar_builder.finish().map_err(|source| {
+            NymvisorError::BackupTarFinalizationFailure {
+                path: self.backup_filepath.clone(),
                 source,
-            })
+            }
+        })?;
+        Ok(self.backup_filepath)
     }
 
+    /// Backs up the content of the daemon home directory (apart from the `/nymvisor` directory)
+    /// and returns the path to the created backup file.
     pub(crate) fn backup_daemon_home<P: AsRef<Path>>(
         mut self,
         daemon_home: P,
-    ) -> Result<(), NymvisorError> {
+    ) -> Result<PathBuf, NymvisorError> {
         let home = daemon_home.as_ref();
         let home_entry =
             fs::read_dir(home).map_err(|source| NymvisorError::BackupTarDirFailure {
@@ -120,3 +124,127 @@ impl BackupBuilder {
         self.finish()
     }
 }
+
+/// Restores the content of the daemon home directory from the provided backup file.
+/// Any existing entries (apart from the `/nymvisor` directory and the one containing the backup itself)
+/// are removed beforehand so that no data produced by the failed upgrade is left behind.
+pub(crate) fn restore_backup<P1, P2>(
+    backup_filepath: P1,
+    daemon_home: P2,
+) -> Result<(), NymvisorError>
+where
+    P1: AsRef<Path>,
+    P2: AsRef<Path>,
+{
+    let backup_filepath = backup_filepath.as_ref();
+    let home = daemon_home.as_ref();
+    let restoration_err = |source| NymvisorError::BackupRestorationFailure {
+        path: backup_filepath.to_path_buf(),
+        target: home.to_path_buf(),
+        source,
+    };
+
+    info!(
+        "attempting to restore {} from the backup at {}",
+        home.display(),
+        backup_filepath.display()
+    );
+
+    // make sure the backup is readable before we start removing anything
+    let backup_file = File::open(backup_filepath).map_err(restoration_err)?;
+
+    for entry in fs::read_dir(home).map_err(restoration_err)? {
+        let path = entry.map_err(restoration_err)?.path();
+        if path.file_name() == Some(NYMVISOR_DIR.as_ref()) || backup_filepath.starts_with(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            fs::remove_dir_all(&path).map_err(restoration_err)?;
+        } else {
+            fs::remove_file(&path).map_err(restoration_err)?;
+        }
+    }
+
+    tar::Archive::new(GzDecoder::new(backup_file))
+        .unpack(home)
+        .map_err(restoration_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file<P: AsRef<Path>>(path: P, content: &str) {
+        let path = path.as_ref();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn read_file<P: AsRef<Path>>(path: P) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn restoring_backup_reverts_home_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path();
+        let backup_dir = home.join(NYMVISOR_DIR).join("backups").join("v2");
+
+        write_file(home.join("config").join("config.toml"), "original config");
+        write_file(home.join("data").join("db.sqlite"), "original data");
+        write_file(home.join("keys.pem"), "original keys");
+        write_file(home.join(NYMVISOR_DIR).join("marker"), "nymvisor data");
+
+        let backup = BackupBuilder::new(&backup_dir)
+            .unwrap()
+            .backup_daemon_home(home)
+            .unwrap();
+        assert!(backup.starts_with(&backup_dir));
+
+        // simulate changes made by the upgraded daemon
+        write_file(home.join("config").join("config.toml"), "migrated config");
+        write_file(home.join("config").join("new.toml"), "new config");
+        fs::remove_dir_all(home.join("data")).unwrap();
+        write_file(home.join("new-data"), "new data");
+        write_file(
+            home.join(NYMVISOR_DIR).join("marker"),
+            "updated nymvisor data",
+        );
+
+        restore_backup(&backup, home).unwrap();
+
+        assert_eq!(
+            read_file(home.join("config").join("config.toml")),
+            "original config"
+        );
+        assert_eq!(
+            read_file(home.join("data").join("db.sqlite")),
+            "original data"
+        );
+        assert_eq!(read_file(home.join("keys.pem")), "original keys");
+        assert!(!home.join("config").join("new.toml").exists());
+        assert!(!home.join("new-data").exists());
+
+        // the nymvisor directory (alongside the backup itself) is left untouched
+        assert_eq!(
+            read_file(home.join(NYMVISOR_DIR).join("marker")),
+            "updated nymvisor data"
+        );
+        assert!(backup.exists());
+    }
+
+    #[test]
+    fn restoring_missing_backup_leaves_home_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path();
+        write_file(home.join("config").join("config.toml"), "config");
+
+        let res = restore_backup(home.join("nonexistent.tar.gz"), home);
+        assert!(matches!(
+            res,
+            Err(NymvisorError::BackupRestorationFailure { .. })
+        ));
+        assert_eq!(read_file(home.join("config").join("config.toml")), "config");
+    }
+}
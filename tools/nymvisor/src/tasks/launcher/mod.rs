@@ -4,13 +4,14 @@
 use crate::config::Config;
 use crate::daemon::Daemon;
 use crate::error::NymvisorError;
-use crate::tasks::launcher::backup::BackupBuilder;
+use crate::tasks::launcher::backup::{restore_backup, BackupBuilder};
 use crate::upgrades::types::{CurrentVersionInfo, UpgradeInfo};
-use crate::upgrades::{perform_upgrade, types::UpgradePlan, UpgradeResult};
+use crate::upgrades::{perform_rollback, perform_upgrade, types::UpgradePlan, UpgradeResult};
 use futures::future::{FusedFuture, OptionFuture};
 use futures::{FutureExt, StreamExt};
 use nym_async_file_watcher::FileWatcherEventReceiver;
 use nym_task::signal::wait_for_signal;
+use std::path::PathBuf;
 use std::time::Duration;
 use time::OffsetDateTime;
 use tokio::pin;
@@ -19,9 +20,27 @@ use tracing::{debug, error, info, warn};
 
 mod backup;
 
+const HEALTH_PROBE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// State of the recently upgraded daemon that hasn't yet passed its health probe.
+struct UpgradeProbation {
+    /// Name of the upgrade under probation.
+    upgrade_name: String,
+
+    /// Information on the version that was running before the upgrade.
+    previous: UpgradeInfo,
+
+    /// Path to the pre-upgrade backup of the daemon home directory, if one was made.
+    backup: Option<PathBuf>,
+
+    /// Number of consecutive failures of the upgraded daemon.
+    failures: usize,
+}
+
 pub(crate) struct DaemonLauncher {
     config: Config,
     upgrade_plan_watcher: FileWatcherEventReceiver,
+    probation: Option<UpgradeProbation>,
 }
 
 impl DaemonLauncher {
@@ -29,6 +48,7 @@ impl DaemonLauncher {
         DaemonLauncher {
             config,
             upgrade_plan_watcher,
+            probation: None,
         }
     }
 
@@ -65,6 +85,12 @@ impl DaemonLauncher {
                 Err(failure) => {
                     error!("daemon failed with the following error: {failure}");
 
+                    if self.probation.is_some() {
+                        self.handle_upgrade_failure().await?;
+                        info!("the daemon will be now restarted");
+                        continue;
+                    }
+
                     if !self.config.daemon.debug.restart_on_failure {
                         return Err(NymvisorError::DisabledRestartOnFailure);
                     }
@@ -101,12 +127,92 @@ impl DaemonLauncher {
             return Ok(UpgradeResult::new_shortlived());
         }
 
-        if !self.config.daemon.debug.unsafe_skip_backup {
-            self.perform_backup()?;
-        }
+        let previous = UpgradePlan::try_load(self.config.upgrade_plan_filepath())?
+            .current()
+            .clone();
+
+        let backup = if !self.config.daemon.debug.unsafe_skip_backup {
+            Some(self.perform_backup()?)
+        } else {
+            None
+        };
 
-        perform_upgrade(&self.config).await
         // if we ever wanted to introduce any pre-upgrade scripts like cosmovisor, they'd go here
+        let upgrade_result = perform_upgrade(&self.config).await?;
+
+        if upgrade_result.binary_swapped
+            && !upgrade_result.requires_manual_intervention
+            && self.config.daemon.debug.rollback_on_failure
+        {
+            let current = UpgradePlan::try_load(self.config.upgrade_plan_filepath())?;
+            self.probation = Some(UpgradeProbation {
+                upgrade_name: current.current().name.clone(),
+                previous,
+                backup,
+                failures: 0,
+            });
+        }
+
+        Ok(upgrade_result)
+    }
+
+    /// Called whenever the recently upgraded daemon has failed.
+    /// If it has failed sufficient number of times in a row, the upgrade is rolled back,
+    /// otherwise we just wait before restarting it again.
+    async fn handle_upgrade_failure(&mut self) -> Result<(), NymvisorError> {
+        let Some(probation) = self.probation.as_mut() else {
+            return Ok(());
+        };
+
+        probation.failures += 1;
+        if probation.failures < self.config.daemon.debug.max_upgrade_failures {
+            info!(
+                "the upgraded daemon has failed {} time(s) in a row. waiting for {} before attempting to restart it...",
+                probation.failures,
+                humantime::format_duration(self.config.daemon.debug.failure_restart_delay)
+            );
+            sleep(self.config.daemon.debug.failure_restart_delay).await;
+            return Ok(());
+        }
+
+        // safety: we just checked the probation is set
+        #[allow(clippy::unwrap_used)]
+        let probation = self.probation.take().unwrap();
+        warn!(
+            "the upgraded daemon has failed {} times in a row. the upgrade '{}' will be rolled back",
+            probation.failures, probation.upgrade_name
+        );
+
+        perform_rollback(&self.config, probation.previous, probation.failures)?;
+        match probation.backup {
+            Some(backup) => restore_backup(backup, &self.config.daemon.home)?,
+            None => warn!("there is no pre-upgrade backup available - only the daemon binary has been restored"),
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the upgraded daemon is healthy once it has stayed alive for the probe period.
+    async fn probe_upgrade_health(&self) -> Result<(), String> {
+        let Some(url) = &self.config.daemon.debug.health_probe_url else {
+            return Ok(());
+        };
+
+        debug!("querying the daemon health endpoint at {url}");
+        let client = reqwest::Client::builder()
+            .timeout(HEALTH_PROBE_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| err.to_string())?;
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|err| format!("failed to query {url}: {err}"))?;
+
+        if !response.status().is_success() {
+            return Err(format!("{url} responded with status {}", response.status()));
+        }
+        Ok(())
     }
 
     /// this function gets called whenever the file watcher detects changes in the upgrade plan file
@@ -164,16 +270,34 @@ impl DaemonLauncher {
             .map(FutureExt::fuse)
             .into();
 
+        // if the daemon has been recently upgraded, make sure it's healthy once the probe period elapses
+        let mut health_probe: OptionFuture<_> = self
+            .probation
+            .as_ref()
+            .map(|_| sleep(self.config.daemon.debug.health_probe_period))
+            .map(Box::pin)
+            .map(FutureExt::fuse)
+            .into();
+
         let signal_fut = wait_for_signal();
         pin!(signal_fut);
 
         let mut received_interrupt = false;
+        let mut failed_probe = None;
         loop {
             tokio::select! {
                 daemon_res = &mut fused_runner => {
                     warn!("the daemon has terminated by itself - was it a short lived command?");
                     let exit_status = daemon_res?;
                     info!("it finished with the following exit status: {exit_status}");
+                    if let Some(probation) = &self.probation {
+                        if !exit_status.success() {
+                            return Err(NymvisorError::FailedUpgradeHealthProbe {
+                                upgrade_name: probation.upgrade_name.clone(),
+                                reason: format!("the daemon has terminated with {exit_status}"),
+                            })
+                        }
+                    }
                     return Ok(false)
                 }
                 event = &mut self.upgrade_plan_watcher.next() => {
@@ -190,6 +314,19 @@ impl DaemonLauncher {
                     }
 
                 }
+                _ = &mut health_probe, if !health_probe.is_terminated() => {
+                    match self.probe_upgrade_health().await {
+                        Ok(_) => {
+                            info!("the upgraded daemon has passed its health probe");
+                            self.probation = None;
+                        }
+                        Err(reason) => {
+                            warn!("the upgraded daemon has failed its health probe: {reason}. it will be now stopped");
+                            failed_probe = Some(reason);
+                            break
+                        }
+                    }
+                }
                 _ = &mut upgrade_timeout, if !upgrade_timeout.is_terminated() => {
                     info!("the upgrade timeout has elapsed. the daemon will be now stopped in order to perform the upgrade");
                     break
@@ -216,11 +353,22 @@ impl DaemonLauncher {
             }
         }
 
+        if let Some(reason) = failed_probe {
+            return Err(NymvisorError::FailedUpgradeHealthProbe {
+                upgrade_name: self
+                    .probation
+                    .as_ref()
+                    .map(|p| p.upgrade_name.clone())
+                    .unwrap_or_default(),
+                reason,
+            });
+        }
+
         // if we received an interrupt, don't try to perform upgrade, just exit the nymvisor
         Ok(!received_interrupt)
     }
 
-    fn perform_backup(&self) -> Result<(), NymvisorError> {
+    fn perform_backup(&self) -> Result<PathBuf, NymvisorError> {
         let plan = UpgradePlan::try_load(self.config.upgrade_plan_filepath())?;
 
         let Some(upgrade_name) = plan.next_upgrade().map(|u| &u.name) else {
@@ -233,3 +381,183 @@ impl DaemonLauncher {
             .backup_daemon_home(&self.config.daemon.home)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GENESIS_DIR;
+    use futures::channel::mpsc;
+    use nym_bin_common::build_information::BinaryBuildInformationOwned;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const DAEMON_NAME: &str = "nym-foomp";
+    const UPGRADE_NAME: &str = "v2";
+
+    fn build_info(version: &str) -> BinaryBuildInformationOwned {
+        BinaryBuildInformationOwned {
+            binary_name: DAEMON_NAME.to_string(),
+            build_timestamp: "2023-11-08T10:21:34.035386383Z".to_string(),
+            build_version: version.to_string(),
+            commit_sha: "3245ff7e3d51fbf7a1f8ad1e3b3ac0dca7b1ecee".to_string(),
+            commit_timestamp: "2023-11-08T10:15:45.000000000+00:00".to_string(),
+            commit_branch: "develop".to_string(),
+            rustc_version: "1.73.0".to_string(),
+            rustc_channel: "stable".to_string(),
+            cargo_profile: "release".to_string(),
+            cargo_triple: "x86_64-unknown-linux-gnu".to_string(),
+        }
+    }
+
+    // creates a 'daemon' that only knows how to output its build information
+    fn fake_daemon(path: &Path, version: &str) {
+        let info = serde_json::to_string(&build_info(version)).unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("#!/bin/sh\necho '{info}'\n")).unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn upgrade_info(name: &str, version: &str) -> UpgradeInfo {
+        UpgradeInfo {
+            manual: false,
+            name: name.to_string(),
+            notes: String::new(),
+            publish_date: None,
+            version: version.to_string(),
+            platforms: Default::default(),
+            upgrade_time: OffsetDateTime::now_utc(),
+            binary_details: None,
+            failed: None,
+        }
+    }
+
+    // sets up the directories as they'd look right after upgrading from genesis to `UPGRADE_NAME`
+    fn upgraded_setup(max_upgrade_failures: usize) -> (TempDir, DaemonLauncher) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::new(DAEMON_NAME, dir.path().join("home"));
+        config.nymvisor.debug.upgrade_data_directory = Some(dir.path().join("data"));
+        config.daemon.debug.max_upgrade_failures = max_upgrade_failures;
+        config.daemon.debug.failure_restart_delay = Duration::from_millis(1);
+        fs::create_dir_all(config.daemon_nymvisor_dir()).unwrap();
+
+        let genesis = upgrade_info(GENESIS_DIR, "1.0.0");
+        fake_daemon(&config.genesis_daemon_binary(), &genesis.version);
+        genesis
+            .save(config.upgrade_info_filepath(GENESIS_DIR))
+            .unwrap();
+
+        let upgrade = upgrade_info(UPGRADE_NAME, "2.0.0");
+        fake_daemon(&config.upgrade_binary(UPGRADE_NAME), &upgrade.version);
+        upgrade
+            .save(config.upgrade_info_filepath(UPGRADE_NAME))
+            .unwrap();
+
+        UpgradePlan::new(upgrade)
+            .save_new(config.upgrade_plan_filepath())
+            .unwrap();
+        std::os::unix::fs::symlink(
+            config.upgrade_dir(UPGRADE_NAME),
+            config.current_daemon_dir(),
+        )
+        .unwrap();
+
+        let (_, upgrade_plan_watcher) = mpsc::unbounded();
+        let mut launcher = DaemonLauncher::new(config, upgrade_plan_watcher);
+        launcher.probation = Some(UpgradeProbation {
+            upgrade_name: UPGRADE_NAME.to_string(),
+            previous: genesis,
+            backup: None,
+            failures: 0,
+        });
+
+        (dir, launcher)
+    }
+
+    fn current_upgrade(launcher: &DaemonLauncher) -> UpgradeInfo {
+        UpgradePlan::try_load(launcher.config.upgrade_plan_filepath())
+            .unwrap()
+            .current()
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn failed_upgrade_is_rolled_back() {
+        let (_dir, mut launcher) = upgraded_setup(2);
+        let config = launcher.config.clone();
+
+        // the first failure only results in a restart
+        launcher.handle_upgrade_failure().await.unwrap();
+        assert_eq!(launcher.probation.as_ref().unwrap().failures, 1);
+        assert_eq!(current_upgrade(&launcher).name, UPGRADE_NAME);
+        assert_eq!(
+            fs::read_link(config.current_daemon_dir()).unwrap(),
+            config.upgrade_dir(UPGRADE_NAME)
+        );
+
+        // but the second one triggers the rollback
+        launcher.handle_upgrade_failure().await.unwrap();
+        assert!(launcher.probation.is_none());
+        assert_eq!(current_upgrade(&launcher).name, GENESIS_DIR);
+        assert_eq!(
+            fs::read_link(config.current_daemon_dir()).unwrap(),
+            config.genesis_daemon_dir()
+        );
+
+        let current_version =
+            CurrentVersionInfo::try_load(config.current_daemon_version_filepath()).unwrap();
+        assert_eq!(current_version.name, GENESIS_DIR);
+        assert_eq!(current_version.binary_details, build_info("1.0.0"));
+
+        // the failure got recorded so that the upgrade wouldn't be attempted again
+        let failed = UpgradeInfo::try_load(config.upgrade_info_filepath(UPGRADE_NAME)).unwrap();
+        assert!(failed.has_failed());
+        assert_eq!(failed.failed.unwrap().failures, 2);
+
+        // and the lock got released
+        assert!(!config.upgrade_lock_filepath().exists());
+    }
+
+    #[tokio::test]
+    async fn rollback_restores_pre_upgrade_backup() {
+        let (_dir, mut launcher) = upgraded_setup(1);
+        let home = launcher.config.daemon.home.clone();
+
+        fs::write(home.join("config.toml"), "pre-upgrade config").unwrap();
+        let backup = BackupBuilder::new(launcher.config.daemon_upgrade_backup_dir(UPGRADE_NAME))
+            .unwrap()
+            .backup_daemon_home(&home)
+            .unwrap();
+        launcher.probation.as_mut().unwrap().backup = Some(backup);
+
+        // the upgraded daemon has migrated the config
+        fs::write(home.join("config.toml"), "migrated config").unwrap();
+
+        launcher.handle_upgrade_failure().await.unwrap();
+        assert!(launcher.probation.is_none());
+        assert_eq!(current_upgrade(&launcher).name, GENESIS_DIR);
+        assert_eq!(
+            fs::read_to_string(home.join("config.toml")).unwrap(),
+            "pre-upgrade config"
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_fails_without_valid_previous_binary() {
+        let (_dir, mut launcher) = upgraded_setup(1);
+        let config = launcher.config.clone();
+
+        // the previous binary reports a different version than expected
+        fake_daemon(&config.genesis_daemon_binary(), "1.2.3");
+
+        assert!(launcher.handle_upgrade_failure().await.is_err());
+
+        // nothing has been changed
+        assert_eq!(current_upgrade(&launcher).name, UPGRADE_NAME);
+        assert_eq!(
+            fs::read_link(config.current_daemon_dir()).unwrap(),
+            config.upgrade_dir(UPGRADE_NAME)
+        );
+    }
+}
@@ -45,6 +45,19 @@ impl UpstreamPoller {
             return Ok(());
         }
 
+        // if we have already attempted this upgrade and had to roll it back, don't try it again
+        let upgrade_info_path = self.config.upgrade_info_filepath(&upgrade_info.name);
+        if upgrade_info_path.exists() {
+            let existing = UpgradeInfo::try_load(upgrade_info_path)?;
+            if existing.has_failed() {
+                debug!(
+                    "the upstream upgrade '{}' has previously failed and been rolled back",
+                    existing.name
+                );
+                return Ok(());
+            }
+        }
+
         if !plan.has_planned(&upgrade_info) {
             if let Err(err) =
                 upgrade_info.save(self.config.upgrade_info_filepath(&upgrade_info.name))
@@ -7,11 +7,12 @@ use crate::helpers::{init_path, to_hex_string};
 use crate::upgrades::types::{DownloadUrl, UpgradeInfo};
 use bytes::Buf;
 use futures::stream::StreamExt;
+use nym_crypto::asymmetric::identity;
 use std::io::BufWriter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{env, fs, io};
-use tracing::info;
+use tracing::{info, warn};
 
 const LOGGING_RATE: Duration = Duration::from_millis(25);
 
@@ -89,24 +90,93 @@ async fn chunk_download(
     Ok(())
 }
 
+fn verify_checksum(
+    upgrade_name: String,
+    download_url: &DownloadUrl,
+    binary_path: &Path,
+) -> Result<(), NymvisorError> {
+    let checksum = download_url
+        .checksum_algorithm
+        .calculate_file_checksum(binary_path)?;
+    if checksum != download_url.checksum {
+        return Err(NymvisorError::DownloadChecksumFailure {
+            upgrade_name,
+            encoded_checksum: to_hex_string(&checksum),
+            expected_checksum: to_hex_string(&download_url.checksum),
+            algorithm: download_url.checksum_algorithm,
+        });
+    }
+    Ok(())
+}
+
 fn maybe_verify_checksum(
     upgrade_name: String,
     download_url: &DownloadUrl,
-    download_target: &PathBuf,
+    download_target: &Path,
 ) -> Result<(), NymvisorError> {
     if !download_url.checksum.is_empty() {
-        let checksum = download_url
-            .checksum_algorithm
-            .calculate_file_checksum(download_target)?;
-        if checksum != download_url.checksum {
-            return Err(NymvisorError::DownloadChecksumFailure {
-                upgrade_name,
-                encoded_checksum: to_hex_string(&checksum),
-                expected_checksum: to_hex_string(&download_url.checksum),
-                algorithm: download_url.checksum_algorithm,
+        verify_checksum(upgrade_name, download_url, download_target)?;
+    }
+    Ok(())
+}
+
+/// Verify the signature on the upgrade against the configured release keys.
+/// The signature covers the upgrade name, version, platform and the binary checksum
+/// (see [`UpgradeInfo::signed_message`]), so the binary itself must match the signed checksum.
+/// If the signature is not enforced, it's only going to be checked if it's present and there are any release keys available.
+pub(super) fn verify_upgrade_signature(
+    config: &Config,
+    info: &UpgradeInfo,
+    binary_path: &Path,
+) -> Result<(), NymvisorError> {
+    let enforce = config.daemon.debug.enforce_signature;
+    let release_keys = &config.daemon.debug.release_keys;
+    let upgrade_name = info.name.as_str();
+    let (platform, download_url) = info.get_platform_download_url()?;
+
+    if download_url.signature.is_empty() {
+        if enforce {
+            return Err(NymvisorError::MissingUpgradeSignature {
+                upgrade_name: upgrade_name.to_string(),
             });
         }
+        return Ok(());
+    }
+
+    if release_keys.is_empty() {
+        if enforce {
+            return Err(NymvisorError::NoReleaseKeys);
+        }
+        warn!("the upgrade '{upgrade_name}' has been signed, but there are no release keys configured. the signature will not be verified");
+        return Ok(());
+    }
+
+    let signature = identity::Signature::from_bytes(&download_url.signature).map_err(|source| {
+        NymvisorError::MalformedUpgradeSignature {
+            upgrade_name: upgrade_name.to_string(),
+            source,
+        }
+    })?;
+
+    // without the checksum, the signature wouldn't say anything about the binary itself
+    if download_url.checksum.is_empty() {
+        return Err(NymvisorError::MissingDownloadChecksum {
+            upgrade_name: upgrade_name.to_string(),
+        });
+    }
+    verify_checksum(upgrade_name.to_string(), download_url, binary_path)?;
+
+    let message = info.signed_message(platform, &download_url.checksum);
+    if !release_keys
+        .iter()
+        .any(|key| key.verify(&message, &signature).is_ok())
+    {
+        return Err(NymvisorError::InvalidUpgradeSignature {
+            upgrade_name: upgrade_name.to_string(),
+        });
     }
+
+    info!("verified the signature of the upgrade '{upgrade_name}' binary");
     Ok(())
 }
 
@@ -135,7 +205,10 @@ pub(super) async fn download_upgrade_binary(
     // if the checksum is available, do verify it
     maybe_verify_checksum(info.name.clone(), download_url, &temp_target)?;
 
-    // if the checksum exists and it matches, move the file to the correct location
+    // if the signature is available (or required), do verify it
+    verify_upgrade_signature(config, info, &temp_target)?;
+
+    // if the checksum and signature exist and they match, move the file to the correct location
     fs::rename(&temp_target, &target).map_err(|source| NymvisorError::DaemonBinaryCopyFailure {
         source_path: temp_target,
         target_path: target,
@@ -153,3 +226,154 @@ pub(crate) fn os_arch() -> String {
         format!("{os}-{arch}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::upgrades::types::DigestAlgorithm;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use time::OffsetDateTime;
+
+    fn release_key(seed: u8) -> identity::PrivateKey {
+        identity::PrivateKey::from_bytes(&[seed; 32]).unwrap()
+    }
+
+    fn test_config(release_keys: Vec<identity::PublicKey>) -> Config {
+        let mut config = Config::new("nym-foomp", PathBuf::from("/tmp/nym-foomp"));
+        config.daemon.debug.enforce_signature = true;
+        config.daemon.debug.release_keys = release_keys;
+        config
+    }
+
+    // writes a binary with the specified content and creates (signed) upgrade info for it
+    fn signed_upgrade(
+        dir: &TempDir,
+        content: &[u8],
+        signer: Option<&identity::PrivateKey>,
+    ) -> (UpgradeInfo, PathBuf) {
+        let binary_path = dir.path().join("nym-foomp");
+        fs::write(&binary_path, content).unwrap();
+        let checksum = DigestAlgorithm::Sha256
+            .calculate_file_checksum(&binary_path)
+            .unwrap();
+
+        let mut info = UpgradeInfo {
+            manual: false,
+            name: "v2".to_string(),
+            notes: String::new(),
+            publish_date: None,
+            version: "2.0.0".to_string(),
+            platforms: HashMap::new(),
+            upgrade_time: OffsetDateTime::now_utc(),
+            binary_details: None,
+            failed: None,
+        };
+        let signature = signer
+            .map(|key| {
+                key.sign(info.signed_message(&os_arch(), &checksum))
+                    .to_bytes()
+                    .to_vec()
+            })
+            .unwrap_or_default();
+        info.platforms.insert(
+            os_arch(),
+            DownloadUrl {
+                checksum,
+                checksum_algorithm: DigestAlgorithm::Sha256,
+                signature,
+                url: "https://nymtech.net/nym-foomp".parse().unwrap(),
+            },
+        );
+
+        (info, binary_path)
+    }
+
+    #[test]
+    fn valid_signature_is_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = release_key(1);
+        let config = test_config(vec![release_key(2).public_key(), key.public_key()]);
+        let (info, binary) = signed_upgrade(&dir, b"upgraded binary", Some(&key));
+
+        assert!(verify_upgrade_signature(&config, &info, &binary).is_ok())
+    }
+
+    #[test]
+    fn signature_by_unknown_key_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = test_config(vec![release_key(1).public_key()]);
+        let (info, binary) = signed_upgrade(&dir, b"upgraded binary", Some(&release_key(2)));
+
+        assert!(matches!(
+            verify_upgrade_signature(&config, &info, &binary),
+            Err(NymvisorError::InvalidUpgradeSignature { .. })
+        ))
+    }
+
+    #[test]
+    fn replayed_signature_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = release_key(1);
+        let config = test_config(vec![key.public_key()]);
+        let (info, binary) = signed_upgrade(&dir, b"old vulnerable binary", Some(&key));
+
+        // the same binary and signature presented as a different upgrade
+        let mut renamed = info.clone();
+        renamed.name = "v3".to_string();
+        assert!(matches!(
+            verify_upgrade_signature(&config, &renamed, &binary),
+            Err(NymvisorError::InvalidUpgradeSignature { .. })
+        ));
+
+        let mut reversioned = info.clone();
+        reversioned.version = "3.0.0".to_string();
+        assert!(matches!(
+            verify_upgrade_signature(&config, &reversioned, &binary),
+            Err(NymvisorError::InvalidUpgradeSignature { .. })
+        ));
+
+        // or as a binary for a different platform
+        let mut replatformed = info.clone();
+        let download_url = replatformed.platforms.remove(&os_arch()).unwrap();
+        replatformed
+            .platforms
+            .insert("any".to_string(), download_url);
+        assert!(matches!(
+            verify_upgrade_signature(&config, &replatformed, &binary),
+            Err(NymvisorError::InvalidUpgradeSignature { .. })
+        ));
+
+        // while the original is still fine
+        assert!(verify_upgrade_signature(&config, &info, &binary).is_ok())
+    }
+
+    #[test]
+    fn tampered_binary_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = release_key(1);
+        let config = test_config(vec![key.public_key()]);
+        let (info, binary) = signed_upgrade(&dir, b"upgraded binary", Some(&key));
+        fs::write(&binary, b"malicious binary").unwrap();
+
+        assert!(matches!(
+            verify_upgrade_signature(&config, &info, &binary),
+            Err(NymvisorError::DownloadChecksumFailure { .. })
+        ))
+    }
+
+    #[test]
+    fn missing_signature_is_only_rejected_when_enforced() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = test_config(vec![release_key(1).public_key()]);
+        let (info, binary) = signed_upgrade(&dir, b"upgraded binary", None);
+
+        assert!(matches!(
+            verify_upgrade_signature(&config, &info, &binary),
+            Err(NymvisorError::MissingUpgradeSignature { .. })
+        ));
+
+        config.daemon.debug.enforce_signature = false;
+        assert!(verify_upgrade_signature(&config, &info, &binary).is_ok())
+    }
+}
@@ -1,18 +1,20 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::Config;
+use crate::config::{Config, BIN_DIR};
 use crate::daemon::Daemon;
 use crate::error::NymvisorError;
-use crate::upgrades::download::download_upgrade_binary;
-use crate::upgrades::types::{CurrentVersionInfo, UpgradeHistory, UpgradePlan};
+use crate::upgrades::download::{download_upgrade_binary, verify_upgrade_signature};
+use crate::upgrades::types::{
+    CurrentVersionInfo, FailedUpgrade, UpgradeHistory, UpgradeInfo, UpgradePlan,
+};
 use nix::fcntl::{flock, FlockArg};
 use std::fs;
 use std::fs::File;
 use std::os::fd::AsRawFd;
 use std::path::PathBuf;
 use time::OffsetDateTime;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 pub(crate) mod download;
 mod serde_helpers;
@@ -51,22 +53,7 @@ pub(crate) async fn perform_upgrade(config: &Config) -> Result<UpgradeResult, Ny
         UpgradeHistory::new(history_path)
     };
 
-    debug!("creating the lock file");
-    let lock_path = config.upgrade_lock_filepath();
-    let lock_file =
-        File::create(&lock_path).map_err(|source| NymvisorError::LockFileCreationFailure {
-            path: lock_path.clone(),
-            source,
-        })?;
-    let lock_fd = lock_file.as_raw_fd();
-
-    debug!("attempting to acquire the lock");
-    if let Err(err) = flock(lock_fd, FlockArg::LockExclusiveNonblock) {
-        return Err(NymvisorError::UnableToAcquireUpgradePlanLock {
-            lock_path,
-            libc_code: err,
-        });
-    }
+    let (_lock_file, lock_path) = acquire_upgrade_lock(config)?;
 
     let upgrade_binary_path = config.upgrade_binary(&upgrade_name);
 
@@ -82,6 +69,9 @@ pub(crate) async fn perform_upgrade(config: &Config) -> Result<UpgradeResult, Ny
         );
 
         download_upgrade_binary(config, &next).await?;
+    } else if config.daemon.debug.enforce_signature {
+        // the binary hasn't been downloaded by us, so make sure it's been signed before we ever run it
+        verify_upgrade_signature(config, &next, &upgrade_binary_path)?;
     }
 
     let tmp_daemon = Daemon::new(upgrade_binary_path);
@@ -110,10 +100,7 @@ pub(crate) async fn perform_upgrade(config: &Config) -> Result<UpgradeResult, Ny
     set_upgrade_link(config, config.upgrade_dir(&upgrade_name))?;
 
     // finally remove the lock file
-    fs::remove_file(&lock_path).map_err(|source| NymvisorError::LockFileRemovalFailure {
-        path: lock_path.clone(),
-        source,
-    })?;
+    release_upgrade_lock(lock_path)?;
 
     Ok(UpgradeResult {
         binary_swapped: true,
@@ -121,6 +108,90 @@ pub(crate) async fn perform_upgrade(config: &Config) -> Result<UpgradeResult, Ny
     })
 }
 
+/// Reverts the current upgrade by switching back to the binary of the provided previous version
+/// and marking the current upgrade as failed.
+pub(crate) fn perform_rollback(
+    config: &Config,
+    previous: UpgradeInfo,
+    failures: usize,
+) -> Result<(), NymvisorError> {
+    let mut plan = UpgradePlan::try_load(config.upgrade_plan_filepath())?;
+    let mut failed = plan.current().clone();
+    info!(
+        "attempting to rollback the upgrade '{}' to '{}'",
+        failed.name, previous.name
+    );
+
+    let (_lock_file, lock_path) = acquire_upgrade_lock(config)?;
+
+    let previous_dir = config.upgrade_or_genesis_dir(&previous.name);
+    let previous_daemon = Daemon::new(previous_dir.join(BIN_DIR).join(&config.daemon.name));
+    previous_daemon.verify_binary()?;
+
+    let previous_bin_info = previous_daemon.get_build_information()?;
+    previous.ensure_matches_bin_info(&previous_bin_info)?;
+
+    // record the failure in the 'upgrade-info.json' of the failed upgrade
+    // so that we wouldn't attempt to perform it again
+    failed.failed = Some(FailedUpgrade {
+        rolled_back_at: OffsetDateTime::now_utc(),
+        failures,
+    });
+    if let Err(err) = failed.save(config.upgrade_info_filepath(&failed.name)) {
+        warn!(
+            "failed to record the failure of the upgrade '{}': {err}",
+            failed.name
+        );
+    }
+
+    // update the 'current-version-history.json'
+    CurrentVersionInfo {
+        name: previous.name.clone(),
+        version: previous.version.clone(),
+        upgrade_time: OffsetDateTime::now_utc(),
+        binary_details: previous_bin_info,
+    }
+    .save(config.current_daemon_version_filepath())?;
+
+    // update the 'upgrade-plan.json'
+    plan.set_current(previous);
+    plan.update_on_disk()?;
+
+    // update the 'current' symlink
+    set_upgrade_link(config, previous_dir)?;
+
+    // finally remove the lock file
+    release_upgrade_lock(lock_path)
+}
+
+fn acquire_upgrade_lock(config: &Config) -> Result<(File, PathBuf), NymvisorError> {
+    debug!("creating the lock file");
+    let lock_path = config.upgrade_lock_filepath();
+    let lock_file =
+        File::create(&lock_path).map_err(|source| NymvisorError::LockFileCreationFailure {
+            path: lock_path.clone(),
+            source,
+        })?;
+    let lock_fd = lock_file.as_raw_fd();
+
+    debug!("attempting to acquire the lock");
+    if let Err(err) = flock(lock_fd, FlockArg::LockExclusiveNonblock) {
+        return Err(NymvisorError::UnableToAcquireUpgradePlanLock {
+            lock_path,
+            libc_code: err,
+        });
+    }
+
+    Ok((lock_file, lock_path))
+}
+
+fn release_upgrade_lock(lock_path: PathBuf) -> Result<(), NymvisorError> {
+    fs::remove_file(&lock_path).map_err(|source| NymvisorError::LockFileRemovalFailure {
+        path: lock_path,
+        source,
+    })
+}
+
 fn set_upgrade_link(config: &Config, upgrade_path: PathBuf) -> Result<(), NymvisorError> {
     // remove the existing symlink if it exists
     let link = config.current_daemon_dir();
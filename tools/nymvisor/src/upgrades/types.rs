@@ -207,6 +207,11 @@ pub struct DownloadUrl {
     /// The algorithm used for computing the checksum
     pub checksum_algorithm: DigestAlgorithm,
 
+    /// The hex-encoded detached ed25519 signature made by one of the release keys on the upgrade
+    /// name, version, platform and the checksum of the file behind the download url.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hex")]
+    pub signature: Vec<u8>,
+
     /// Download url for this particular platform
     pub url: Url,
 }
@@ -239,6 +244,22 @@ pub struct UpgradeInfo {
 
     /// Optional build information of the upgraded binary for additional verification
     pub binary_details: Option<BinaryBuildInformationOwned>,
+
+    /// If this upgrade has been rolled back due to the upgraded daemon failing its health probes,
+    /// this field contains the details of the failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed: Option<FailedUpgrade>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedUpgrade {
+    /// Time when the upgrade has been rolled back.
+    #[serde(with = "time::serde::rfc3339")]
+    pub rolled_back_at: OffsetDateTime,
+
+    /// Number of consecutive health probe failures of the upgraded daemon before the rollback.
+    pub failures: usize,
 }
 
 impl UpgradeInfo {
@@ -284,23 +305,54 @@ impl UpgradeInfo {
             })
     }
 
+    pub(crate) fn has_failed(&self) -> bool {
+        self.failed.is_some()
+    }
+
     // pub(crate) fn is_genesis(&self) -> bool {
     //     self.name == GENESIS_DIR
     // }
 
     pub(crate) fn get_download_url(&self) -> Result<&DownloadUrl, NymvisorError> {
-        if let Some(download_url) = self.platforms.get(&os_arch()) {
-            return Ok(download_url);
+        self.get_platform_download_url()
+            .map(|(_, download_url)| download_url)
+    }
+
+    /// Returns the download url for this platform alongside the key under which it was found,
+    /// i.e. either the os-arch pair or `any`.
+    pub(crate) fn get_platform_download_url(&self) -> Result<(&str, &DownloadUrl), NymvisorError> {
+        let platform = os_arch();
+        if let Some((platform, download_url)) = self.platforms.get_key_value(&platform) {
+            return Ok((platform, download_url));
         }
         self.platforms
-            .get("any")
+            .get_key_value("any")
+            .map(|(platform, download_url)| (platform.as_str(), download_url))
             .ok_or(NymvisorError::NoDownloadUrls {
                 upgrade_name: self.name.clone(),
-                arch: os_arch(),
+                arch: platform,
                 available: self.platforms.keys().cloned().collect(),
             })
     }
 
+    /// Returns the message that has to be signed by one of the release keys for the binary of the
+    /// specified platform, i.e. the length-prefixed name, version, platform and binary checksum.
+    /// This binds the signature to this particular upgrade so that it couldn't be replayed
+    /// with a different one.
+    pub(crate) fn signed_message(&self, platform: &str, checksum: &[u8]) -> Vec<u8> {
+        let mut message = Vec::new();
+        for field in [
+            self.name.as_bytes(),
+            self.version.as_bytes(),
+            platform.as_bytes(),
+            checksum,
+        ] {
+            message.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            message.extend_from_slice(field);
+        }
+        message
+    }
+
     /// Check whether the loaded (presumably `current`) upgrade-info matches the provided current version information.
     pub(crate) fn ensure_matches(
         &self,
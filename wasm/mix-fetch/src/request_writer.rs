@@ -67,6 +67,12 @@ impl RequestWriter {
                     Socks5ResponseContent::Query(query) => {
                         console_error!("received a provider query response even though we didn't send any queries! - {query:#?}")
                     }
+                    Socks5ResponseContent::Datagram(datagram) => {
+                        console_error!("received a datagram even though we didn't open any UDP associations! - association {}", datagram.association_id)
+                    }
+                    Socks5ResponseContent::Bind(bind) => {
+                        console_error!("received a bind response even though we didn't send any bind requests! - connection {}", bind.connection_id)
+                    }
                     Socks5ResponseContent::NetworkData { content } => {
                         self.requests.try_send_data_to_go(content).await;
                     }